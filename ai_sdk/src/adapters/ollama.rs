@@ -1,4 +1,6 @@
-use crate::{AiAdapter, AiService, Message};
+use crate::{stream::lines, AiAdapter, AiService, Message, RetryPolicy, Usage, UsageReport};
+use anyhow::anyhow;
+use futures::{future, Stream, TryStreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
@@ -6,6 +8,7 @@ pub struct OllamaAdapter {
   pub host: String,
   pub model: String,
   pub client: Client,
+  pub retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -29,12 +32,30 @@ pub struct OllamaChatCompletionResponse {
   pub done: bool,
   pub total_duration: u64,
   pub load_duration: u64,
+  /// Left out when the prompt was cached
+  #[serde(default)]
   pub prompt_eval_count: u32,
   pub prompt_eval_duration: u64,
+  #[serde(default)]
   pub eval_count: u32,
   pub eval_duration: u64,
 }
 
+/// One line of a streamed chat response; the last has `done` set and no text
+#[derive(Deserialize)]
+pub struct OllamaChatChunk {
+  #[serde(default)]
+  pub message: Option<OllamaMessage>,
+  #[serde(default)]
+  pub done: bool,
+  #[serde(default)]
+  pub error: Option<String>,
+  #[serde(default)]
+  pub prompt_eval_count: Option<u32>,
+  #[serde(default)]
+  pub eval_count: Option<u32>,
+}
+
 impl OllamaAdapter {
   pub fn new(host: impl Into<String>, model: impl Into<String>) -> Self {
     let host = host.into();
@@ -44,6 +65,7 @@ impl OllamaAdapter {
       host,
       model,
       client,
+      retry: RetryPolicy::default(),
     }
   }
 
@@ -54,8 +76,15 @@ impl OllamaAdapter {
       host: "http://localhost:11434".to_string(),
       model,
       client,
+      retry: RetryPolicy::default(),
     }
   }
+
+  /// Retry transient failures by `retry` instead of the default policy
+  pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+    self.retry = retry;
+    self
+  }
 }
 
 impl Default for OllamaAdapter {
@@ -66,15 +95,61 @@ impl Default for OllamaAdapter {
 
 impl AiService for OllamaAdapter {
   async fn complete(&self, messages: &[Message]) -> anyhow::Result<String> {
+    Ok(self.complete_with_usage(messages).await?.0)
+  }
+
+  async fn complete_with_usage(&self, messages: &[Message]) -> anyhow::Result<(String, Usage)> {
     let request = OllamaChatCompletionRequest {
       model: self.model.clone(),
       messages: messages.iter().map(|m| m.into()).collect(),
       stream: false,
     };
     let url = format!("{}/api/chat", self.host);
-    let response = self.client.post(url).json(&request).send().await?;
+    let response = self
+      .retry
+      .send(|| self.client.post(&url).json(&request))
+      .await?;
     let response: OllamaChatCompletionResponse = response.json().await?;
-    Ok(response.message.content)
+    let usage = response.usage();
+    Ok((response.message.content, usage))
+  }
+
+  async fn complete_stream(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<impl Stream<Item = anyhow::Result<String>> + Send + 'static> {
+    Ok(self.complete_stream_with_usage(messages).await?.0)
+  }
+
+  async fn complete_stream_with_usage(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<(
+    impl Stream<Item = anyhow::Result<String>> + Send + 'static,
+    UsageReport,
+  )> {
+    let request = OllamaChatCompletionRequest {
+      model: self.model.clone(),
+      messages: messages.iter().map(|m| m.into()).collect(),
+      stream: true,
+    };
+    let url = format!("{}/api/chat", self.host);
+    let response = self
+      .retry
+      .send(|| self.client.post(&url).json(&request))
+      .await?;
+
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(anyhow!("Ollama API error: {}", error_text));
+    }
+
+    let report = UsageReport::default();
+    let recorder = report.clone();
+    let chunks = lines(response.bytes_stream());
+    let pieces =
+      chunks.try_filter_map(move |line| future::ready(parse_stream_line(&line, &recorder)));
+    Ok((pieces, report))
   }
   
   async fn embed_texts(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
@@ -95,6 +170,38 @@ impl AiService for OllamaAdapter {
   }
 }
 
+impl OllamaChatCompletionResponse {
+  /// Tokens the prompt and the reply took
+  pub fn usage(&self) -> Usage {
+    Usage::new(self.prompt_eval_count, self.eval_count)
+  }
+}
+
+/// Text carried by one line of a streamed chat response, which is newline-delimited JSON; the
+/// last line's token counts go to `usage`
+fn parse_stream_line(line: &str, usage: &UsageReport) -> anyhow::Result<Option<String>> {
+  if line.trim().is_empty() {
+    return Ok(None);
+  }
+
+  let chunk: OllamaChatChunk = serde_json::from_str(line)?;
+  if let Some(error) = chunk.error {
+    return Err(anyhow!("Ollama API error: {}", error));
+  }
+  if chunk.done {
+    usage.record(Usage::new(
+      chunk.prompt_eval_count.unwrap_or(0),
+      chunk.eval_count.unwrap_or(0),
+    ));
+  }
+  Ok(
+    chunk
+      .message
+      .map(|message| message.content)
+      .filter(|content| !content.is_empty()),
+  )
+}
+
 impl From<OllamaAdapter> for AiAdapter {
   fn from(adapter: OllamaAdapter) -> Self {
     AiAdapter::Ollama(adapter)
@@ -128,11 +235,49 @@ mod tests {
   #[tokio::test]
   async fn ollama_complete_should_work() {
     let adapter = OllamaAdapter::new_local("llama3.2");
-    let messages = vec![Message {
-      role: Role::User,
-      content: "Hello".to_string(),
-    }];
+    let messages = vec![Message::new(Role::User, "Hello")];
     let response = adapter.complete(&messages).await.unwrap();
     println!("response: {}", response);
   }
+
+  #[test]
+  fn stream_lines_should_yield_only_generated_text() {
+    let usage = UsageReport::default();
+    let piece =
+      r#"{"model":"llama3.2","message":{"role":"assistant","content":"Hi"},"done":false}"#;
+    assert_eq!(
+      parse_stream_line(piece, &usage).unwrap().as_deref(),
+      Some("Hi")
+    );
+
+    let last = r#"{"model":"llama3.2","message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":26,"eval_count":4}"#;
+    assert_eq!(parse_stream_line(last, &usage).unwrap(), None);
+    assert_eq!(parse_stream_line("", &usage).unwrap(), None);
+    assert_eq!(usage.get(), Usage::new(26, 4));
+
+    assert!(parse_stream_line(r#"{"error":"model not found"}"#, &usage).is_err());
+  }
+
+  #[test]
+  fn completion_usage_should_count_prompt_and_reply_or_zeros_when_omitted() {
+    let body = |counts: &str| {
+      format!(
+        r#"{{"model":"llama3.2","created_at":"2024-01-01T00:00:00Z","message":{{"role":"assistant","content":"Hi"}},"done":true,"total_duration":1,"load_duration":1,"prompt_eval_duration":1,"eval_duration":1{counts}}}"#
+      )
+    };
+
+    let reported: OllamaChatCompletionResponse =
+      serde_json::from_str(&body(r#","prompt_eval_count":26,"eval_count":4"#)).unwrap();
+    assert_eq!(
+      reported.usage(),
+      Usage {
+        prompt_tokens: 26,
+        completion_tokens: 4,
+        total_tokens: 30,
+      }
+    );
+
+    let omitted: OllamaChatCompletionResponse = serde_json::from_str(&body("")).unwrap();
+    assert_eq!(omitted.usage(), Usage::default());
+  }
 }
@@ -1,5 +1,9 @@
-use crate::{AiAdapter, AiService, Message};
+use crate::{
+  stream::lines, AiAdapter, AiService, CompletionOutput, Message, RetryPolicy, ToolCall, ToolDef,
+  Usage, UsageReport,
+};
 use anyhow::anyhow;
+use futures::{future, Stream, TryStreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
@@ -8,18 +12,68 @@ pub struct OpenaiAdapter {
   api_key: String,
   model: String,
   client: Client,
+  retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
 pub struct OpenAIChatCompletionRequest {
   pub model: String,
   pub messages: Vec<OpenAIMessage>,
+  /// Ask for the response as server-sent events, one per generated piece
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub stream: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub stream_options: Option<OpenAIStreamOptions>,
+  /// Functions the model may call instead of replying
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub tools: Vec<OpenAITool>,
+}
+
+#[derive(Serialize)]
+pub struct OpenAIStreamOptions {
+  /// Send the usage of the whole completion in a last event with no choices
+  pub include_usage: bool,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct OpenAIMessage {
   pub role: String,
-  pub content: String,
+  /// Absent from assistant messages that only call tools
+  #[serde(default)]
+  pub content: Option<String>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub tool_calls: Vec<OpenAIToolCall>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub tool_call_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct OpenAITool {
+  #[serde(rename = "type")]
+  pub kind: String,
+  pub function: OpenAIFunction,
+}
+
+#[derive(Serialize)]
+pub struct OpenAIFunction {
+  pub name: String,
+  pub description: String,
+  pub parameters: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OpenAIToolCall {
+  pub id: String,
+  #[serde(rename = "type")]
+  pub kind: String,
+  pub function: OpenAIFunctionCall,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OpenAIFunctionCall {
+  pub name: String,
+  /// JSON text of the arguments
+  pub arguments: String,
 }
 #[derive(Deserialize)]
 pub struct OpenAIChatCompletionResponse {
@@ -29,7 +83,8 @@ pub struct OpenAIChatCompletionResponse {
   pub model: String,
   pub system_fingerprint: String,
   pub choices: Vec<OpenAIChoice>,
-  pub usage: OpenAIUsage,
+  #[serde(default)]
+  pub usage: Option<OpenAIUsage>,
 }
 
 #[derive(Deserialize)]
@@ -40,11 +95,34 @@ pub struct OpenAIChoice {
   pub finish_reason: String,
 }
 
+/// One server-sent event of a streamed chat completion
+#[derive(Deserialize)]
+pub struct OpenAIChatCompletionChunk {
+  pub choices: Vec<OpenAIChunkChoice>,
+  #[serde(default)]
+  pub usage: Option<OpenAIUsage>,
+}
+
+#[derive(Deserialize)]
+pub struct OpenAIChunkChoice {
+  pub delta: OpenAIDelta,
+}
+
+#[derive(Deserialize)]
+pub struct OpenAIDelta {
+  #[serde(default)]
+  pub content: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct OpenAIUsage {
+  #[serde(default)]
   pub prompt_tokens: u32,
+  #[serde(default)]
   pub completion_tokens: u32,
+  #[serde(default)]
   pub total_tokens: u32,
+  #[serde(default)]
   pub completion_tokens_details: Option<OpenAICompletionTokensDetails>,
 }
 
@@ -85,6 +163,9 @@ pub struct ModerationResult {
 }
 
 impl OpenaiAdapter {
+  /// Model `embed_texts` uses, whatever the completion model
+  pub const EMBEDDING_MODEL: &'static str = "text-embedding-3-small";
+
   pub fn new(api_key: impl Into<String>, model: impl Into<String>) -> Self {
     let client = Client::new();
     Self {
@@ -92,62 +173,135 @@ impl OpenaiAdapter {
       api_key: api_key.into(),
       model: model.into(),
       client,
+      retry: RetryPolicy::default(),
     }
   }
+
+  /// Send requests to an OpenAI-compatible API at `host` instead
+  pub fn with_host(mut self, host: impl Into<String>) -> Self {
+    self.host = host.into();
+    self
+  }
+
+  /// Retry transient failures by `retry` instead of the default policy
+  pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+    self.retry = retry;
+    self
+  }
+
+  /// One buffered chat completion, offering `tools` when there are any
+  async fn chat(
+    &self,
+    messages: &[Message],
+    tools: &[ToolDef],
+  ) -> anyhow::Result<(CompletionOutput, Usage)> {
+    let request = OpenAIChatCompletionRequest {
+      model: self.model.clone(),
+      messages: messages.iter().map(|m| m.into()).collect(),
+      stream: None,
+      stream_options: None,
+      tools: tools.iter().map(OpenAITool::from).collect(),
+    };
+
+    let url = format!("{}/chat/completions", self.host);
+    let response = self
+      .retry
+      .send(|| {
+        self
+          .client
+          .post(&url)
+          .json(&request)
+          .header("Authorization", format!("Bearer {}", self.api_key))
+      })
+      .await?;
+    let text = response.text().await?;
+    parse_completion_output(&text)
+  }
 }
 
 impl AiService for OpenaiAdapter {
   async fn complete(&self, messages: &[Message]) -> anyhow::Result<String> {
+    Ok(self.complete_with_usage(messages).await?.0)
+  }
+
+  async fn complete_with_tools(
+    &self,
+    messages: &[Message],
+    tools: &[ToolDef],
+  ) -> anyhow::Result<CompletionOutput> {
+    Ok(self.chat(messages, tools).await?.0)
+  }
+
+  async fn complete_with_usage(&self, messages: &[Message]) -> anyhow::Result<(String, Usage)> {
+    let (output, usage) = self.chat(messages, &[]).await?;
+    Ok((output.into_text()?, usage))
+  }
+
+  async fn complete_stream(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<impl Stream<Item = anyhow::Result<String>> + Send + 'static> {
+    Ok(self.complete_stream_with_usage(messages).await?.0)
+  }
+
+  async fn complete_stream_with_usage(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<(
+    impl Stream<Item = anyhow::Result<String>> + Send + 'static,
+    UsageReport,
+  )> {
     let request = OpenAIChatCompletionRequest {
       model: self.model.clone(),
       messages: messages.iter().map(|m| m.into()).collect(),
+      stream: Some(true),
+      stream_options: Some(OpenAIStreamOptions {
+        include_usage: true,
+      }),
+      tools: Vec::new(),
     };
 
     let url = format!("{}/chat/completions", self.host);
     let response = self
-      .client
-      .post(url)
-      .json(&request)
-      .header("Authorization", format!("Bearer {}", self.api_key))
-      .send()
+      .retry
+      .send(|| {
+        self
+          .client
+          .post(&url)
+          .json(&request)
+          .header("Authorization", format!("Bearer {}", self.api_key))
+      })
       .await?;
-    let text = response.text().await?;
-    println!("OpenAI API Response: {}", text);
-    
-    // Check if response contains an error
-    if text.contains("error") {
-        let error: serde_json::Value = serde_json::from_str(&text)?;
-        if let Some(err_obj) = error.get("error") {
-            if let Some(message) = err_obj.get("message") {
-                return Err(anyhow!("OpenAI API Error: {}", message));
-            }
-        }
-        return Err(anyhow!("Unknown OpenAI API Error: {}", text));
+
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(anyhow!("OpenAI API error: {}", error_text));
     }
-    
-    let mut data: OpenAIChatCompletionResponse = serde_json::from_str(&text)?;
-    let content = data
-      .choices
-      .pop()
-      .ok_or(anyhow!("No response"))?
-      .message
-      .content;
-    Ok(content)
+
+    let report = UsageReport::default();
+    let recorder = report.clone();
+    let chunks = lines(response.bytes_stream());
+    let pieces =
+      chunks.try_filter_map(move |line| future::ready(parse_stream_line(&line, &recorder)));
+    Ok((pieces, report))
   }
   
   async fn embed_texts(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
     let request = EmbeddingRequest {
-      model: "text-embedding-3-small".to_string(),
+      model: Self::EMBEDDING_MODEL.to_string(),
       input: texts,
     };
 
     let url = format!("{}/embeddings", self.host);
     let response = self
-      .client
-      .post(url)
-      .header("Authorization", format!("Bearer {}", self.api_key))
-      .json(&request)
-      .send()
+      .retry
+      .send(|| {
+        self
+          .client
+          .post(&url)
+          .json(&request)
+          .header("Authorization", format!("Bearer {}", self.api_key))
+      })
       .await?;
 
     if !response.status().is_success() {
@@ -172,11 +326,14 @@ impl AiService for OpenaiAdapter {
 
     let url = format!("{}/moderations", self.host);
     let response = self
-      .client
-      .post(url)
-      .header("Authorization", format!("Bearer {}", self.api_key))
-      .json(&request)
-      .send()
+      .retry
+      .send(|| {
+        self
+          .client
+          .post(&url)
+          .json(&request)
+          .header("Authorization", format!("Bearer {}", self.api_key))
+      })
       .await?;
 
     if !response.status().is_success() {
@@ -195,18 +352,80 @@ impl AiService for OpenaiAdapter {
   }
 }
 
+/// Content and usage of a chat completion response body
+fn parse_completion(text: &str) -> anyhow::Result<(String, Usage)> {
+  let (output, usage) = parse_completion_output(text)?;
+  Ok((output.into_text()?, usage))
+}
+
+/// Reply or tool calls, and usage, of a chat completion response body
+fn parse_completion_output(text: &str) -> anyhow::Result<(CompletionOutput, Usage)> {
+  let body: serde_json::Value = serde_json::from_str(text)?;
+  if let Some(err_obj) = body.get("error") {
+    return match err_obj.get("message") {
+      Some(message) => Err(anyhow!("OpenAI API Error: {}", message)),
+      None => Err(anyhow!("Unknown OpenAI API Error: {}", text)),
+    };
+  }
+
+  let mut data: OpenAIChatCompletionResponse = serde_json::from_value(body)?;
+  let message = data.choices.pop().ok_or(anyhow!("No response"))?.message;
+  let output = if message.tool_calls.is_empty() {
+    CompletionOutput::Text(message.content.unwrap_or_default())
+  } else {
+    CompletionOutput::ToolCalls(message.tool_calls.into_iter().map(ToolCall::from).collect())
+  };
+  Ok((output, data.usage.map(Usage::from).unwrap_or_default()))
+}
+
+/// Text carried by one line of a streamed chat completion; `None` for blank lines, comments,
+/// the closing `[DONE]` and the usage event, whose usage goes to `usage`
+fn parse_stream_line(line: &str, usage: &UsageReport) -> anyhow::Result<Option<String>> {
+  let Some(data) = line.strip_prefix("data:") else {
+    return Ok(None);
+  };
+  let data = data.trim();
+  if data == "[DONE]" {
+    return Ok(None);
+  }
+
+  let event: serde_json::Value = serde_json::from_str(data)?;
+  if let Some(message) = event.get("error").and_then(|err| err.get("message")) {
+    return Err(anyhow!("OpenAI API Error: {}", message));
+  }
+  let chunk: OpenAIChatCompletionChunk = serde_json::from_value(event)?;
+  if let Some(reported) = chunk.usage {
+    usage.record(reported.into());
+  }
+  Ok(
+    chunk
+      .choices
+      .into_iter()
+      .next()
+      .and_then(|choice| choice.delta.content)
+      .filter(|content| !content.is_empty()),
+  )
+}
+
 impl From<OpenaiAdapter> for AiAdapter {
   fn from(adapter: OpenaiAdapter) -> Self {
     AiAdapter::Openai(adapter)
   }
 }
 
+impl From<OpenAIUsage> for Usage {
+  fn from(usage: OpenAIUsage) -> Self {
+    let counted = Usage::new(usage.prompt_tokens, usage.completion_tokens);
+    Usage {
+      total_tokens: usage.total_tokens.max(counted.total_tokens),
+      ..counted
+    }
+  }
+}
+
 impl From<Message> for OpenAIMessage {
   fn from(message: Message) -> Self {
-    OpenAIMessage {
-      role: message.role.to_string(),
-      content: message.content,
-    }
+    OpenAIMessage::from(&message)
   }
 }
 
@@ -214,7 +433,51 @@ impl From<&Message> for OpenAIMessage {
   fn from(message: &Message) -> Self {
     OpenAIMessage {
       role: message.role.to_string(),
-      content: message.content.clone(),
+      // Assistant turns that only call tools have no content
+      content: (message.tool_calls.is_empty() || !message.content.is_empty())
+        .then(|| message.content.clone()),
+      tool_calls: message
+        .tool_calls
+        .iter()
+        .map(OpenAIToolCall::from)
+        .collect(),
+      tool_call_id: message.tool_call_id.clone(),
+    }
+  }
+}
+
+impl From<&ToolDef> for OpenAITool {
+  fn from(tool: &ToolDef) -> Self {
+    OpenAITool {
+      kind: "function".to_string(),
+      function: OpenAIFunction {
+        name: tool.name.clone(),
+        description: tool.description.clone(),
+        parameters: tool.parameters.clone(),
+      },
+    }
+  }
+}
+
+impl From<&ToolCall> for OpenAIToolCall {
+  fn from(call: &ToolCall) -> Self {
+    OpenAIToolCall {
+      id: call.id.clone(),
+      kind: "function".to_string(),
+      function: OpenAIFunctionCall {
+        name: call.name.clone(),
+        arguments: call.arguments.clone(),
+      },
+    }
+  }
+}
+
+impl From<OpenAIToolCall> for ToolCall {
+  fn from(call: OpenAIToolCall) -> Self {
+    ToolCall {
+      id: call.id,
+      name: call.function.name,
+      arguments: call.function.arguments,
     }
   }
 }
@@ -230,11 +493,162 @@ mod tests {
   async fn openai_complete_should_work() {
     let api_key = env::var("OPENAI_API_KEY").unwrap();
     let adapter = OpenaiAdapter::new(api_key, "gpt-4o");
-    let messages = vec![Message {
-      role: Role::User,
-      content: "Hello".to_string(),
-    }];
+    let messages = vec![Message::new(Role::User, "Hello")];
     let response = adapter.complete(&messages).await.unwrap();
     assert!(!response.is_empty());
   }
+
+  #[tokio::test]
+  async fn complete_should_retry_unavailable_until_it_succeeds() {
+    use axum::{extract::State, http::StatusCode, routing::post, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let app = Router::new()
+      .route(
+        "/chat/completions",
+        post(|State(attempts): State<Arc<AtomicUsize>>| async move {
+          if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+            (StatusCode::SERVICE_UNAVAILABLE, String::new())
+          } else {
+            (
+              StatusCode::OK,
+              r#"{"id":"c1","object":"chat.completion","created":1,"model":"gpt-4o","system_fingerprint":"fp","choices":[{"index":0,"message":{"role":"assistant","content":"Hi"},"logprobs":null,"finish_reason":"stop"}]}"#
+                .to_string(),
+            )
+          }
+        }),
+      )
+      .with_state(attempts.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let host = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let adapter = OpenaiAdapter::new("key", "gpt-4o")
+      .with_host(host)
+      .with_retry_policy(RetryPolicy {
+        max_retries: 3,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(10),
+        jitter: true,
+      });
+    let response = adapter.complete(&[Message::user("Hello")]).await.unwrap();
+    assert_eq!(response, "Hi");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+  }
+
+  #[test]
+  fn stream_lines_should_yield_only_generated_text() {
+    let usage = UsageReport::default();
+    let delta = r#"data: {"id":"c1","choices":[{"index":0,"delta":{"content":"Hel"}}]}"#;
+    assert_eq!(
+      parse_stream_line(delta, &usage).unwrap().as_deref(),
+      Some("Hel")
+    );
+
+    let role_only = r#"data: {"id":"c1","choices":[{"index":0,"delta":{"role":"assistant"}}]}"#;
+    for line in [role_only, "", ": keep-alive", "data: [DONE]"] {
+      assert_eq!(parse_stream_line(line, &usage).unwrap(), None);
+    }
+    assert_eq!(usage.get(), Usage::default());
+
+    let last = r#"data: {"id":"c1","choices":[],"usage":{"prompt_tokens":9,"completion_tokens":3,"total_tokens":12}}"#;
+    assert_eq!(parse_stream_line(last, &usage).unwrap(), None);
+    assert_eq!(usage.get(), Usage::new(9, 3));
+
+    let error = r#"data: {"error":{"message":"quota exceeded"}}"#;
+    assert!(parse_stream_line(error, &usage).is_err());
+  }
+
+  #[test]
+  fn completion_should_return_the_tool_calls_the_model_asked_for() {
+    let body = r#"{"id":"c1","object":"chat.completion","created":1,"model":"gpt-4o","system_fingerprint":"fp","choices":[{"index":0,"message":{"role":"assistant","content":null,"tool_calls":[{"id":"call_1","type":"function","function":{"name":"search_messages","arguments":"{\"query\":\"error logs\"}"}}]},"logprobs":null,"finish_reason":"tool_calls"}],"usage":{"prompt_tokens":40,"completion_tokens":12,"total_tokens":52}}"#;
+
+    let (output, usage) = parse_completion_output(body).unwrap();
+    assert_eq!(
+      output,
+      CompletionOutput::ToolCalls(vec![ToolCall {
+        id: "call_1".to_string(),
+        name: "search_messages".to_string(),
+        arguments: r#"{"query":"error logs"}"#.to_string(),
+      }])
+    );
+    assert_eq!(usage, Usage::new(40, 12));
+    // Callers that offered no tools only take text
+    assert!(parse_completion(body).is_err());
+  }
+
+  #[test]
+  fn tools_and_tool_results_should_be_sent_in_the_openai_shape() {
+    let call = ToolCall {
+      id: "call_1".to_string(),
+      name: "create_reminder".to_string(),
+      arguments: r#"{"in_minutes":10}"#.to_string(),
+    };
+    let messages = [
+      Message::user("Remind me in 10 minutes"),
+      Message::tool_calls(vec![call]),
+      Message::tool("call_1", "Reminder created"),
+    ];
+    let request = OpenAIChatCompletionRequest {
+      model: "gpt-4o".to_string(),
+      messages: messages.iter().map(|m| m.into()).collect(),
+      stream: None,
+      stream_options: None,
+      tools: vec![OpenAITool::from(&ToolDef::new(
+        "create_reminder",
+        "Remind the user later",
+        serde_json::json!({"type": "object", "properties": {"in_minutes": {"type": "integer"}}}),
+      ))],
+    };
+
+    assert_eq!(
+      serde_json::to_value(&request).unwrap(),
+      serde_json::json!({
+        "model": "gpt-4o",
+        "messages": [
+          {"role": "user", "content": "Remind me in 10 minutes"},
+          {"role": "assistant", "content": null, "tool_calls": [{
+            "id": "call_1",
+            "type": "function",
+            "function": {"name": "create_reminder", "arguments": "{\"in_minutes\":10}"}
+          }]},
+          {"role": "tool", "content": "Reminder created", "tool_call_id": "call_1"}
+        ],
+        "tools": [{
+          "type": "function",
+          "function": {
+            "name": "create_reminder",
+            "description": "Remind the user later",
+            "parameters": {"type": "object", "properties": {"in_minutes": {"type": "integer"}}}
+          }
+        }]
+      })
+    );
+  }
+
+  #[test]
+  fn completion_should_carry_usage_or_zeros_when_omitted() {
+    let body = |usage: &str| {
+      format!(
+        r#"{{"id":"c1","object":"chat.completion","created":1,"model":"gpt-4o","system_fingerprint":"fp","choices":[{{"index":0,"message":{{"role":"assistant","content":"Hi"}},"logprobs":null,"finish_reason":"stop"}}]{usage}}}"#
+      )
+    };
+
+    let reported = body(r#","usage":{"prompt_tokens":9,"completion_tokens":3,"total_tokens":12}"#);
+    assert_eq!(
+      parse_completion(&reported).unwrap(),
+      ("Hi".to_string(), Usage::new(9, 3))
+    );
+
+    for omitted in ["", r#","usage":null"#, r#","usage":{}"#] {
+      assert_eq!(
+        parse_completion(&body(omitted)).unwrap(),
+        ("Hi".to_string(), Usage::default()),
+        "{omitted}"
+      );
+    }
+  }
 }
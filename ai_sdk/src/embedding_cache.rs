@@ -0,0 +1,229 @@
+use crate::{AiService, CompletionOutput, Message, ToolDef, Usage, UsageReport};
+use futures::Stream;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Key-value store embeddings are cached in, such as Redis
+#[allow(async_fn_in_trait)]
+pub trait EmbeddingStore {
+  /// Embeddings stored under `keys`, in the same order; `None` where missing or expired
+  async fn get_many(&self, keys: &[String]) -> anyhow::Result<Vec<Option<Vec<f32>>>>;
+
+  /// Store each embedding under its key, expiring after `ttl`
+  async fn set_many(&self, entries: &[(String, Vec<f32>)], ttl: Duration) -> anyhow::Result<()>;
+}
+
+/// `AiService` that remembers the embeddings `service` made, so identical text is embedded once
+/// per `ttl`. Everything else goes straight to `service`. A failing store only costs the cache:
+/// lookups that fail count as misses and failed writes are dropped
+pub struct EmbeddingCache<S, K> {
+  service: S,
+  store: K,
+  model: String,
+  ttl: Duration,
+}
+
+impl<S, K> EmbeddingCache<S, K> {
+  /// How long an embedding is kept unless `with_ttl` says otherwise
+  pub const DEFAULT_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+  /// Cache for the embeddings `service` makes with `model`, which is part of every key so a
+  /// model change never returns stale vectors
+  pub fn new(service: S, store: K, model: impl Into<String>) -> Self {
+    Self {
+      service,
+      store,
+      model: model.into(),
+      ttl: Self::DEFAULT_TTL,
+    }
+  }
+
+  pub fn with_ttl(mut self, ttl: Duration) -> Self {
+    self.ttl = ttl;
+    self
+  }
+
+  /// The wrapped service
+  pub fn inner(&self) -> &S {
+    &self.service
+  }
+
+  /// Store key of the embedding of `text`
+  fn key(&self, text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(self.model.as_bytes());
+    hasher.update([0]);
+    hasher.update(text.as_bytes());
+    format!("embedding:{}", hex::encode(hasher.finalize()))
+  }
+}
+
+impl<S: AiService, K: EmbeddingStore> AiService for EmbeddingCache<S, K> {
+  async fn complete(&self, messages: &[Message]) -> anyhow::Result<String> {
+    self.service.complete(messages).await
+  }
+
+  async fn complete_with_tools(
+    &self,
+    messages: &[Message],
+    tools: &[ToolDef],
+  ) -> anyhow::Result<CompletionOutput> {
+    self.service.complete_with_tools(messages, tools).await
+  }
+
+  async fn complete_with_usage(&self, messages: &[Message]) -> anyhow::Result<(String, Usage)> {
+    self.service.complete_with_usage(messages).await
+  }
+
+  async fn complete_stream(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<impl Stream<Item = anyhow::Result<String>> + Send + 'static> {
+    self.service.complete_stream(messages).await
+  }
+
+  async fn complete_stream_with_usage(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<(
+    impl Stream<Item = anyhow::Result<String>> + Send + 'static,
+    UsageReport,
+  )> {
+    self.service.complete_stream_with_usage(messages).await
+  }
+
+  /// Cached embeddings where there are any; only the remaining texts, each once, go to the
+  /// provider
+  async fn embed_texts(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
+    if texts.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let keys: Vec<String> = texts.iter().map(|text| self.key(text)).collect();
+    let mut embeddings = match self.store.get_many(&keys).await {
+      Ok(cached) if cached.len() == texts.len() => cached,
+      _ => vec![None; texts.len()],
+    };
+
+    // Positions of the uncached texts, grouped so a repeated text is embedded once
+    let mut missing: Vec<String> = Vec::new();
+    let mut positions: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, text) in texts.iter().enumerate() {
+      if embeddings[i].is_none() {
+        let at = positions.entry(text.as_str()).or_insert_with(|| {
+          missing.push(text.clone());
+          Vec::new()
+        });
+        at.push(i);
+      }
+    }
+    if missing.is_empty() {
+      return Ok(embeddings.into_iter().flatten().collect());
+    }
+
+    let computed = self.service.embed_texts(missing.clone()).await?;
+    if computed.len() != missing.len() {
+      return Err(anyhow::anyhow!(
+        "Provider returned {} embeddings for {} texts",
+        computed.len(),
+        missing.len()
+      ));
+    }
+
+    let mut entries = Vec::with_capacity(missing.len());
+    for (text, embedding) in missing.iter().zip(computed) {
+      for &i in &positions[text.as_str()] {
+        embeddings[i] = Some(embedding.clone());
+      }
+      entries.push((self.key(text), embedding));
+    }
+    let _ = self.store.set_many(&entries, self.ttl).await;
+
+    Ok(embeddings.into_iter().flatten().collect())
+  }
+
+  async fn moderate_content(&self, content: &str) -> anyhow::Result<bool> {
+    self.service.moderate_content(content).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::{Arc, Mutex};
+
+  /// Embeds each text as its length, recording the batches it was asked for
+  #[derive(Default)]
+  struct CountingEmbedder {
+    batches: Mutex<Vec<Vec<String>>>,
+  }
+
+  impl AiService for CountingEmbedder {
+    async fn complete(&self, _messages: &[Message]) -> anyhow::Result<String> {
+      Ok(String::new())
+    }
+
+    async fn embed_texts(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
+      self.batches.lock().unwrap().push(texts.clone());
+      Ok(texts.iter().map(|text| vec![text.len() as f32]).collect())
+    }
+
+    async fn moderate_content(&self, _content: &str) -> anyhow::Result<bool> {
+      Ok(true)
+    }
+  }
+
+  #[derive(Clone, Default)]
+  struct MemoryStore(Arc<Mutex<HashMap<String, Vec<f32>>>>);
+
+  impl EmbeddingStore for MemoryStore {
+    async fn get_many(&self, keys: &[String]) -> anyhow::Result<Vec<Option<Vec<f32>>>> {
+      let entries = self.0.lock().unwrap();
+      Ok(keys.iter().map(|key| entries.get(key).cloned()).collect())
+    }
+
+    async fn set_many(&self, entries: &[(String, Vec<f32>)], _ttl: Duration) -> anyhow::Result<()> {
+      self.0.lock().unwrap().extend(entries.iter().cloned());
+      Ok(())
+    }
+  }
+
+  fn texts(texts: &[&str]) -> Vec<String> {
+    texts.iter().map(|text| text.to_string()).collect()
+  }
+
+  #[tokio::test]
+  async fn repeated_input_should_not_reach_the_provider() {
+    let (embedder, store) = (CountingEmbedder::default(), MemoryStore::default());
+    let cache = EmbeddingCache::new(&embedder, store, "test-model");
+
+    let first = cache.embed_texts(texts(&["a", "bb"])).await.unwrap();
+    let second = cache.embed_texts(texts(&["a", "bb"])).await.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(cache.generate_embedding("bb").await.unwrap(), vec![2.0]);
+    assert_eq!(embedder.batches.lock().unwrap().len(), 1);
+  }
+
+  #[tokio::test]
+  async fn partial_hits_should_only_embed_the_uncached_texts() {
+    let (embedder, store) = (CountingEmbedder::default(), MemoryStore::default());
+    let cache = EmbeddingCache::new(&embedder, store.clone(), "test-model");
+    cache.embed_texts(texts(&["bb"])).await.unwrap();
+
+    let embeddings = cache
+      .embed_texts(texts(&["a", "bb", "cccc", "a"]))
+      .await
+      .unwrap();
+    assert_eq!(embeddings, vec![vec![1.0], vec![2.0], vec![4.0], vec![1.0]]);
+    assert_eq!(
+      *embedder.batches.lock().unwrap(),
+      vec![texts(&["bb"]), texts(&["a", "cccc"])]
+    );
+
+    // Another model shares nothing with the first
+    let other = EmbeddingCache::new(&embedder, store, "other-model");
+    other.embed_texts(texts(&["bb"])).await.unwrap();
+    assert_eq!(embedder.batches.lock().unwrap().len(), 3);
+  }
+}
@@ -1,8 +1,15 @@
 mod adapters;
+mod embedding_cache;
+mod retry;
+pub mod stream;
 
 pub use adapters::*;
+pub use embedding_cache::{EmbeddingCache, EmbeddingStore};
+pub use retry::RetryPolicy;
 
+use futures::{Stream, StreamExt};
 use std::fmt;
+use std::sync::{Arc, Mutex};
 
 pub enum AiAdapter {
   Openai(OpenaiAdapter),
@@ -14,18 +21,104 @@ pub enum Role {
   User,
   Assistant,
   System,
+  /// Result of a tool call, answering the assistant message that asked for it
+  Tool,
 }
 
 #[derive(Debug, Clone)]
 pub struct Message {
   pub role: Role,
   pub content: String,
+  /// Calls an assistant message asked for; empty for every other message
+  pub tool_calls: Vec<ToolCall>,
+  /// Call a `Role::Tool` message carries the result of
+  pub tool_call_id: Option<String>,
 }
 
+/// Function the model may call instead of replying; `parameters` is the JSON Schema of its
+/// arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolDef {
+  pub name: String,
+  pub description: String,
+  pub parameters: serde_json::Value,
+}
+
+/// Call of a registered function the model asked for. `arguments` is the JSON the model wrote,
+/// which callers must validate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCall {
+  pub id: String,
+  pub name: String,
+  pub arguments: String,
+}
+
+/// What a completion produced: a reply, or functions to call before it can reply
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompletionOutput {
+  Text(String),
+  ToolCalls(Vec<ToolCall>),
+}
+
+/// Tokens a completion consumed, as reported by the provider; zero where it reports none
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+  pub prompt_tokens: u32,
+  pub completion_tokens: u32,
+  pub total_tokens: u32,
+}
+
+/// Usage of a streamed completion, known once the stream has ended
+#[derive(Debug, Clone, Default)]
+pub struct UsageReport(Arc<Mutex<Usage>>);
+
 #[allow(async_fn_in_trait)]
 pub trait AiService {
   /// Basic chat completion
   async fn complete(&self, messages: &[Message]) -> anyhow::Result<String>;
+
+  /// Chat completion that may ask to call one of `tools` instead of replying; feed the results
+  /// back as `Message::tool` after the `Message::tool_calls` that asked for them. Adapters
+  /// without tool support ignore `tools` and always reply
+  async fn complete_with_tools(
+    &self,
+    messages: &[Message],
+    tools: &[ToolDef],
+  ) -> anyhow::Result<CompletionOutput> {
+    let _ = tools;
+    Ok(CompletionOutput::Text(self.complete(messages).await?))
+  }
+
+  /// Chat completion with the tokens it consumed. Adapters that can't tell report zero usage
+  async fn complete_with_usage(&self, messages: &[Message]) -> anyhow::Result<(String, Usage)> {
+    Ok((self.complete(messages).await?, Usage::default()))
+  }
+
+  /// Chat completion delivered piece by piece as the model produces it. Adapters that can't
+  /// stream yield the whole `complete` response as a single piece. Dropping the stream cancels
+  /// the upstream request
+  async fn complete_stream(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<impl Stream<Item = anyhow::Result<String>> + Send + 'static> {
+    let content = self.complete(messages).await?;
+    Ok(futures::stream::once(async move { Ok(content) }))
+  }
+
+  /// `complete_stream` with a report of the tokens consumed, filled in by the time the stream
+  /// ends
+  async fn complete_stream_with_usage(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<(
+    impl Stream<Item = anyhow::Result<String>> + Send + 'static,
+    UsageReport,
+  )> {
+    let (content, usage) = self.complete_with_usage(messages).await?;
+    let report = UsageReport::default();
+    report.record(usage);
+    Ok((futures::stream::once(async move { Ok(content) }), report))
+  }
   
   /// Generate embeddings for texts
   async fn embed_texts(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>>;
@@ -76,7 +169,54 @@ impl AiService for AiAdapter {
       AiAdapter::Ollama(adapter) => adapter.complete(messages).await,
     }
   }
+
+  async fn complete_with_usage(&self, messages: &[Message]) -> anyhow::Result<(String, Usage)> {
+    match self {
+      AiAdapter::Openai(adapter) => adapter.complete_with_usage(messages).await,
+      AiAdapter::Ollama(adapter) => adapter.complete_with_usage(messages).await,
+    }
+  }
+
+  async fn complete_with_tools(
+    &self,
+    messages: &[Message],
+    tools: &[ToolDef],
+  ) -> anyhow::Result<CompletionOutput> {
+    match self {
+      AiAdapter::Openai(adapter) => adapter.complete_with_tools(messages, tools).await,
+      AiAdapter::Ollama(adapter) => adapter.complete_with_tools(messages, tools).await,
+    }
+  }
   
+  async fn complete_stream(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<impl Stream<Item = anyhow::Result<String>> + Send + 'static> {
+    Ok(match self {
+      AiAdapter::Openai(adapter) => adapter.complete_stream(messages).await?.left_stream(),
+      AiAdapter::Ollama(adapter) => adapter.complete_stream(messages).await?.right_stream(),
+    })
+  }
+
+  async fn complete_stream_with_usage(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<(
+    impl Stream<Item = anyhow::Result<String>> + Send + 'static,
+    UsageReport,
+  )> {
+    Ok(match self {
+      AiAdapter::Openai(adapter) => {
+        let (pieces, report) = adapter.complete_stream_with_usage(messages).await?;
+        (pieces.left_stream(), report)
+      }
+      AiAdapter::Ollama(adapter) => {
+        let (pieces, report) = adapter.complete_stream_with_usage(messages).await?;
+        (pieces.right_stream(), report)
+      }
+    })
+  }
+
   async fn embed_texts(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
     match self {
       AiAdapter::Openai(adapter) => adapter.embed_texts(texts).await,
@@ -92,12 +232,119 @@ impl AiService for AiAdapter {
   }
 }
 
+/// A borrowed service serves as well as the service, for wrappers like `EmbeddingCache` over a
+/// service that is kept elsewhere
+impl<T: AiService> AiService for &T {
+  async fn complete(&self, messages: &[Message]) -> anyhow::Result<String> {
+    (**self).complete(messages).await
+  }
+
+  async fn complete_with_tools(
+    &self,
+    messages: &[Message],
+    tools: &[ToolDef],
+  ) -> anyhow::Result<CompletionOutput> {
+    (**self).complete_with_tools(messages, tools).await
+  }
+
+  async fn complete_with_usage(&self, messages: &[Message]) -> anyhow::Result<(String, Usage)> {
+    (**self).complete_with_usage(messages).await
+  }
+
+  async fn complete_stream(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<impl Stream<Item = anyhow::Result<String>> + Send + 'static> {
+    (**self).complete_stream(messages).await
+  }
+
+  async fn complete_stream_with_usage(
+    &self,
+    messages: &[Message],
+  ) -> anyhow::Result<(
+    impl Stream<Item = anyhow::Result<String>> + Send + 'static,
+    UsageReport,
+  )> {
+    (**self).complete_stream_with_usage(messages).await
+  }
+
+  async fn embed_texts(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
+    (**self).embed_texts(texts).await
+  }
+
+  async fn generate_embedding(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+    (**self).generate_embedding(text).await
+  }
+
+  async fn generate_summary(&self, text: &str) -> anyhow::Result<String> {
+    (**self).generate_summary(text).await
+  }
+
+  async fn suggest_replies(&self, context: &str) -> anyhow::Result<Vec<String>> {
+    (**self).suggest_replies(context).await
+  }
+
+  async fn moderate_content(&self, content: &str) -> anyhow::Result<bool> {
+    (**self).moderate_content(content).await
+  }
+}
+
 impl fmt::Display for Role {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       Role::User => write!(f, "user"),
       Role::Assistant => write!(f, "assistant"),
       Role::System => write!(f, "system"),
+      Role::Tool => write!(f, "tool"),
+    }
+  }
+}
+
+impl Usage {
+  /// Usage from the prompt and completion counts, which make up the total
+  pub fn new(prompt_tokens: u32, completion_tokens: u32) -> Self {
+    Self {
+      prompt_tokens,
+      completion_tokens,
+      total_tokens: prompt_tokens.saturating_add(completion_tokens),
+    }
+  }
+}
+
+impl UsageReport {
+  /// Usage recorded so far
+  pub fn get(&self) -> Usage {
+    *self.0.lock().unwrap_or_else(|e| e.into_inner())
+  }
+
+  pub(crate) fn record(&self, usage: Usage) {
+    *self.0.lock().unwrap_or_else(|e| e.into_inner()) = usage;
+  }
+}
+
+impl ToolDef {
+  pub fn new(
+    name: impl Into<String>,
+    description: impl Into<String>,
+    parameters: serde_json::Value,
+  ) -> Self {
+    Self {
+      name: name.into(),
+      description: description.into(),
+      parameters,
+    }
+  }
+}
+
+impl CompletionOutput {
+  /// The reply, for callers that offered no tools
+  pub fn into_text(self) -> anyhow::Result<String> {
+    match self {
+      CompletionOutput::Text(text) => Ok(text),
+      CompletionOutput::ToolCalls(calls) => Err(anyhow::anyhow!(
+        "Model asked for {} tool call(s) instead of replying",
+        calls.len()
+      )),
     }
   }
 }
@@ -107,6 +354,8 @@ impl Message {
     Self {
       role,
       content: content.into(),
+      tool_calls: Vec::new(),
+      tool_call_id: None,
     }
   }
 
@@ -121,4 +370,70 @@ impl Message {
   pub fn system(content: impl Into<String>) -> Self {
     Self::new(Role::System, content)
   }
+
+  /// The assistant turn that asked for `calls`, to keep in the history before their results
+  pub fn tool_calls(calls: Vec<ToolCall>) -> Self {
+    Self {
+      tool_calls: calls,
+      ..Self::new(Role::Assistant, "")
+    }
+  }
+
+  /// Result of the tool call `call_id`
+  pub fn tool(call_id: impl Into<String>, content: impl Into<String>) -> Self {
+    Self {
+      tool_call_id: Some(call_id.into()),
+      ..Self::new(Role::Tool, content)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct BufferedOnly;
+
+  impl AiService for BufferedOnly {
+    async fn complete(&self, _messages: &[Message]) -> anyhow::Result<String> {
+      Ok("whole answer".to_string())
+    }
+
+    async fn embed_texts(&self, _texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
+      Ok(vec![])
+    }
+
+    async fn moderate_content(&self, _content: &str) -> anyhow::Result<bool> {
+      Ok(true)
+    }
+  }
+
+  #[tokio::test]
+  async fn adapter_without_streaming_should_stream_the_whole_response_once() {
+    let pieces: Vec<String> = BufferedOnly
+      .complete_stream(&[Message::user("Hello")])
+      .await
+      .unwrap()
+      .map(|piece| piece.unwrap())
+      .collect()
+      .await;
+    assert_eq!(pieces, vec!["whole answer"]);
+  }
+
+  #[tokio::test]
+  async fn adapter_without_usage_should_report_zero_tokens() {
+    let (content, usage) = BufferedOnly
+      .complete_with_usage(&[Message::user("Hello")])
+      .await
+      .unwrap();
+    assert_eq!(content, "whole answer");
+    assert_eq!(usage, Usage::default());
+
+    let (pieces, report) = BufferedOnly
+      .complete_stream_with_usage(&[Message::user("Hello")])
+      .await
+      .unwrap();
+    assert_eq!(pieces.count().await, 1);
+    assert_eq!(report.get(), Usage::default());
+  }
 }
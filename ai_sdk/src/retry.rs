@@ -0,0 +1,187 @@
+use rand::Rng;
+use reqwest::{header::RETRY_AFTER, RequestBuilder, Response, StatusCode};
+use std::time::Duration;
+
+/// How an adapter retries a request that failed transiently: rate limited (429), a server error
+/// (500, 502, 503, 504) or a network error. Anything else, 400 and 401 included, fails at once
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+  /// Retries after the first attempt; 0 makes a single attempt
+  pub max_retries: u32,
+  /// Wait before the first retry, doubled for each later one
+  pub base_delay: Duration,
+  /// Longest wait between attempts, including one asked for by `Retry-After`
+  pub max_delay: Duration,
+  /// Wait a random 50-100% of each backoff, so clients that failed together retry apart
+  pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self {
+      max_retries: 3,
+      base_delay: Duration::from_millis(500),
+      max_delay: Duration::from_secs(10),
+      jitter: true,
+    }
+  }
+}
+
+impl RetryPolicy {
+  /// Make every request once
+  pub fn none() -> Self {
+    Self {
+      max_retries: 0,
+      ..Self::default()
+    }
+  }
+
+  /// Backoff before retry number `retry`, counting from 0
+  fn backoff(&self, retry: u32) -> Duration {
+    let delay = self
+      .base_delay
+      .saturating_mul(2u32.saturating_pow(retry))
+      .min(self.max_delay);
+    if self.jitter {
+      let half = delay / 2;
+      half + half.mul_f64(rand::thread_rng().gen())
+    } else {
+      delay
+    }
+  }
+
+  /// Send the request `build` makes, again after a wait for as long as it fails transiently.
+  /// Once retries run out the last response is returned whatever its status, for the caller
+  /// to report
+  pub(crate) async fn send(&self, build: impl Fn() -> RequestBuilder) -> anyhow::Result<Response> {
+    let mut retry = 0;
+    loop {
+      let delay = match build().send().await {
+        Ok(response) if retry < self.max_retries && is_transient(response.status()) => {
+          match retry_after(&response) {
+            Some(asked) => asked.min(self.max_delay),
+            None => self.backoff(retry),
+          }
+        }
+        Ok(response) => return Ok(response),
+        Err(e)
+          if retry < self.max_retries && (e.is_connect() || e.is_timeout() || e.is_request()) =>
+        {
+          self.backoff(retry)
+        }
+        Err(e) => return Err(e.into()),
+      };
+      tokio::time::sleep(delay).await;
+      retry += 1;
+    }
+  }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+  matches!(
+    status,
+    StatusCode::TOO_MANY_REQUESTS
+      | StatusCode::INTERNAL_SERVER_ERROR
+      | StatusCode::BAD_GATEWAY
+      | StatusCode::SERVICE_UNAVAILABLE
+      | StatusCode::GATEWAY_TIMEOUT
+  )
+}
+
+/// Wait the server asked for; only the delay-seconds form is understood
+fn retry_after(response: &Response) -> Option<Duration> {
+  let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+  seconds.trim().parse().ok().map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use axum::{extract::State, http::HeaderMap, routing::post, Router};
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::Arc;
+  use std::time::Instant;
+
+  /// Endpoint answering with `status` (and `headers`) until the last attempt, then 200
+  async fn flaky_endpoint(
+    status: StatusCode,
+    headers: HeaderMap,
+    failures: usize,
+  ) -> (String, Arc<AtomicUsize>) {
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let app = Router::new()
+      .route(
+        "/",
+        post(move |State(attempts): State<Arc<AtomicUsize>>| {
+          let headers = headers.clone();
+          async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) < failures {
+              (status, headers, "")
+            } else {
+              (StatusCode::OK, HeaderMap::new(), "ok")
+            }
+          }
+        }),
+      )
+      .with_state(attempts.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, attempts)
+  }
+
+  #[test]
+  fn backoff_should_double_up_to_the_cap() {
+    let policy = RetryPolicy {
+      max_retries: 5,
+      base_delay: Duration::from_millis(100),
+      max_delay: Duration::from_millis(500),
+      jitter: false,
+    };
+    let delays: Vec<_> = (0..5)
+      .map(|retry| policy.backoff(retry).as_millis())
+      .collect();
+    assert_eq!(delays, [100, 200, 400, 500, 500]);
+
+    let jittered = RetryPolicy {
+      jitter: true,
+      ..policy
+    };
+    for retry in 0..5 {
+      let delay = jittered.backoff(retry);
+      assert!(delay >= policy.backoff(retry) / 2 && delay <= policy.backoff(retry));
+    }
+  }
+
+  #[tokio::test]
+  async fn client_errors_should_not_be_retried() {
+    let (url, attempts) = flaky_endpoint(StatusCode::BAD_REQUEST, HeaderMap::new(), 1).await;
+    let client = reqwest::Client::new();
+
+    let response = RetryPolicy::default()
+      .send(|| client.post(&url))
+      .await
+      .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+  }
+
+  #[tokio::test]
+  async fn retry_after_should_replace_the_backoff() {
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, "0".parse().unwrap());
+    let (url, attempts) = flaky_endpoint(StatusCode::TOO_MANY_REQUESTS, headers, 1).await;
+    let client = reqwest::Client::new();
+    let policy = RetryPolicy {
+      base_delay: Duration::from_secs(60),
+      max_delay: Duration::from_secs(60),
+      ..RetryPolicy::default()
+    };
+
+    let started = Instant::now();
+    let response = policy.send(|| client.post(&url)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert!(started.elapsed() < Duration::from_secs(10));
+  }
+}
@@ -0,0 +1,109 @@
+use futures::{stream, Stream, StreamExt};
+use std::collections::VecDeque;
+
+/// Splits bytes into lines, holding back a partial line until the rest of it arrives, so a
+/// line cut across network chunks (even inside a UTF-8 character) is only seen whole
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+  pending: Vec<u8>,
+}
+
+impl LineBuffer {
+  /// Add a chunk, returning the lines it completes without their `\n` or `\r\n`
+  pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+    self.pending.extend_from_slice(chunk);
+    let mut lines = Vec::new();
+    while let Some(end) = self.pending.iter().position(|b| *b == b'\n') {
+      let line: Vec<u8> = self.pending.drain(..=end).collect();
+      lines.push(Self::decode(&line[..end]));
+    }
+    lines
+  }
+
+  /// The unterminated last line, if any
+  pub fn finish(&mut self) -> Option<String> {
+    if self.pending.is_empty() {
+      return None;
+    }
+    let line = std::mem::take(&mut self.pending);
+    Some(Self::decode(&line))
+  }
+
+  fn decode(line: &[u8]) -> String {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    String::from_utf8_lossy(line).into_owned()
+  }
+}
+
+/// Lines of a streamed response body. Dropping the returned stream drops `body`, which for an
+/// HTTP response closes the connection and so cancels the upstream request
+pub fn lines<S, B, E>(body: S) -> impl Stream<Item = anyhow::Result<String>> + Send + 'static
+where
+  S: Stream<Item = Result<B, E>> + Send + 'static,
+  B: AsRef<[u8]> + Send,
+  E: std::error::Error + Send + Sync + 'static,
+{
+  let state = (
+    Box::pin(body),
+    LineBuffer::default(),
+    VecDeque::new(),
+    false,
+  );
+  stream::unfold(
+    state,
+    |(mut body, mut buffer, mut ready, mut ended)| async move {
+      loop {
+        if let Some(line) = ready.pop_front() {
+          return Some((Ok(line), (body, buffer, ready, ended)));
+        }
+        if ended {
+          return None;
+        }
+        match body.next().await {
+          Some(Ok(chunk)) => ready.extend(buffer.push(chunk.as_ref())),
+          Some(Err(e)) => return Some((Err(e.into()), (body, buffer, ready, true))),
+          None => {
+            ended = true;
+            ready.extend(buffer.finish());
+          }
+        }
+      }
+    },
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn line_split_across_chunks_should_come_out_whole() {
+    let mut buffer = LineBuffer::default();
+    assert!(buffer.push(b"data: {\"choices\":[{\"del").is_empty());
+    assert_eq!(
+      buffer.push(b"ta\":{}}]}\r\n\r\ndata: [DO"),
+      vec!["data: {\"choices\":[{\"delta\":{}}]}", ""]
+    );
+    assert_eq!(buffer.push(b"NE]\n"), vec!["data: [DONE]"]);
+    assert_eq!(buffer.finish(), None);
+  }
+
+  #[test]
+  fn character_split_across_chunks_should_decode_intact() {
+    let text = "héllo\n".as_bytes();
+    let mut buffer = LineBuffer::default();
+    assert!(buffer.push(&text[..2]).is_empty());
+    assert_eq!(buffer.push(&text[2..]), vec!["héllo"]);
+  }
+
+  #[tokio::test]
+  async fn lines_should_include_an_unterminated_last_line() {
+    let chunks: Vec<Result<&[u8], std::io::Error>> =
+      vec![Ok(b"{\"a\":1}\n{\"b\""), Ok(b":2}\n{\"c\":3}")];
+    let lines: Vec<String> = lines(stream::iter(chunks))
+      .map(|line| line.unwrap())
+      .collect()
+      .await;
+    assert_eq!(lines, vec!["{\"a\":1}", "{\"b\":2}", "{\"c\":3}"]);
+  }
+}
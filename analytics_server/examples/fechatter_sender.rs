@@ -23,6 +23,8 @@ async fn main() -> Result<()> {
       browser_version: "120.0.0".to_string(),
     }),
     geo: None, // will be set by server
+    request_id: String::new(),
+    traceparent: String::new(),
   };
 
   // Example 1: User login event
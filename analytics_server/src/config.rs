@@ -1,4 +1,5 @@
 use anyhow::{Result, bail};
+use fechatter_core::utils::redact::{RedactedSummary, redact_secret, redact_url};
 use serde::{Deserialize, Serialize};
 use std::{env, path::PathBuf};
 
@@ -415,6 +416,26 @@ impl Default for JetStreamConfig {
   }
 }
 
+impl RedactedSummary for AppConfig {
+  fn redacted_summary(&self) -> String {
+    let clickhouse = &self.server.clickhouse;
+    let integrations = &self.integrations;
+
+    format!(
+      "analytics_server config: port={}, clickhouse={} (db={}, user={}, password={}), nats={}, redis={}, postgres={}, jwt_secret={}",
+      self.server.port,
+      redact_url(&clickhouse.http_url),
+      clickhouse.database,
+      clickhouse.user,
+      redact_secret(&clickhouse.password),
+      redact_url(&integrations.messaging.nats_url),
+      redact_url(&integrations.cache.redis_url),
+      redact_url(&integrations.postgres.database_url),
+      redact_secret(&self.security.auth.jwt_secret),
+    )
+  }
+}
+
 impl AppConfig {
   /// Load configuration from file with fallback locations and environment override
   pub fn load() -> Result<Self> {
@@ -537,6 +558,8 @@ impl AppConfig {
       subjects.message_events.clone(),
       subjects.bot_events.clone(),
       subjects.error_events.clone(),
+      // Erasures delete a user's stored events
+      fechatter_core::contracts::events::subjects::USER_ERASED.to_string(),
     ].into_iter()
      .filter(|s| !s.is_empty())
      .collect()
@@ -29,6 +29,9 @@ pub struct AnalyticsEventRow {
   pub geo_city: Option<String>,
   pub client_ts: i64,
   pub server_ts: i64,
+  /// `x-request-id` and W3C `traceparent` of the request behind a server-published event
+  pub request_id: Option<String>,
+  pub traceparent: Option<String>,
   // Common fields
   pub event_type: String,
   // AppExitEvent fields
@@ -330,6 +333,13 @@ impl EventConsume for EventContext {
       row.geo_city = Some(geo.city);
     }
 
+    if !self.request_id.is_empty() {
+      row.request_id = Some(self.request_id);
+    }
+    if !self.traceparent.is_empty() {
+      row.traceparent = Some(self.traceparent);
+    }
+
     row.client_ts = self.client_ts;
     row.server_ts = self.server_ts;
     Ok(())
@@ -568,6 +578,8 @@ mod tests {
         region: "CA".to_string(),
         city: "San Francisco".to_string(),
       }),
+      request_id: String::new(),
+      traceparent: String::new(),
     }
   }
 
@@ -584,6 +596,44 @@ mod tests {
     assert_eq!(row.geo_country, Some("US".to_string()));
   }
 
+  #[test]
+  fn test_published_request_id_is_stored() {
+    use prost::Message;
+
+    let context = EventContext {
+      server_ts: chrono::Utc::now().timestamp_millis(),
+      ..create_test_context()
+    };
+    let event = AnalyticsEvent {
+      context: Some(EventContext {
+        request_id: "req-42".to_string(),
+        traceparent: "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        ..context.clone()
+      }),
+      event_type: Some(analytics_event::EventType::ChatCreated(ChatCreatedEvent {
+        workspace_id: "1".to_string(),
+        chat_type: "group".to_string(),
+        initial_members_count: 3,
+      })),
+    };
+    let payload = event.encode_to_vec();
+
+    let row = AnalyticsEventRow::try_from(AnalyticsEvent::decode(&payload[..]).unwrap()).unwrap();
+    assert_eq!(row.request_id.as_deref(), Some("req-42"));
+    assert_eq!(
+      row.traceparent.as_deref(),
+      Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+    );
+
+    let row = AnalyticsEventRow::try_from(AnalyticsEvent {
+      context: Some(context),
+      ..event
+    })
+    .unwrap();
+    assert_eq!(row.request_id, None);
+    assert_eq!(row.traceparent, None);
+  }
+
   #[test]
   fn test_row_validation() {
     let mut row = AnalyticsEventRow::default();
@@ -392,6 +392,8 @@ mod tests {
           browser_version: "1.0".to_string(),
         }),
         geo: None,
+        request_id: String::new(),
+        traceparent: String::new(),
       }),
       event_type: Some(analytics_event::EventType::AppStart(AppStartEvent {})),
     }
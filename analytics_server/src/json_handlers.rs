@@ -35,6 +35,10 @@ pub struct JsonEventContext {
   pub system: Option<JsonSystemInfo>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub geo: Option<JsonGeoLocation>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub request_id: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub traceparent: Option<String>,
 }
 
 /// JSON format for system info
@@ -358,6 +362,8 @@ fn json_to_protobuf(json_event: JsonAnalyticsEvent) -> Result<AnalyticsEvent, Ap
       region: g.region.unwrap_or_default(),
       city: g.city.unwrap_or_default(),
     }),
+    request_id: json_event.context.request_id.unwrap_or_default(),
+    traceparent: json_event.context.traceparent.unwrap_or_default(),
   };
 
   // Convert event type
@@ -1,5 +1,6 @@
 use analytics_server::{AppConfig, AppState, get_router};
 use anyhow::Result;
+use fechatter_core::utils::RedactedSummary;
 use tokio::net::TcpListener;
 use tracing::{error, info, warn};
 use tracing_subscriber::{
@@ -41,8 +42,7 @@ async fn main() -> Result<()> {
   let addr = format!("0.0.0.0:{}", config.server.port);
   
   info!("Analytics Server will bind to: {}", addr);
-  info!("ClickHouse URL: {}", config.get_database_url());
-  info!("Database: {}", config.get_database_name());
+  info!("{}", config.redacted_summary());
 
   // Initialize application state with comprehensive error handling
   let state = AppState::try_new(config).await.map_err(|e| {
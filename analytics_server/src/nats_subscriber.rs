@@ -6,6 +6,7 @@
 
 use crate::{error::AppError, events::AnalyticsEventRow, AppState};
 use async_nats::jetstream;
+use fechatter_core::contracts::events::{subjects, UserErasedEvent};
 use futures::StreamExt;
 use std::sync::Arc;
 use tracing::{debug, error, info, instrument, warn};
@@ -135,6 +136,7 @@ impl AnalyticsNatsSubscriber {
     current.max_bytes != desired.max_bytes
       || current.max_messages != desired.max_messages
       || current.max_age != desired.max_age
+      || current.subjects != desired.subjects
   }
 
   /// Start subscribing to analytics events
@@ -238,6 +240,15 @@ impl AnalyticsNatsSubscriber {
       subject, payload_size
     );
 
+    if subject.as_str() == subjects::USER_ERASED {
+      self.forget_user(&msg.payload).await?;
+      msg
+        .ack()
+        .await
+        .map_err(|e| AppError::AnyError(anyhow::anyhow!("Failed to ack message: {}", e)))?;
+      return Ok(());
+    }
+
     // Try to parse as protobuf first (existing format), then fallback to JSON
     let mut row = match self.parse_protobuf_event(&msg.payload) {
       Ok(row) => {
@@ -502,6 +513,25 @@ impl AnalyticsNatsSubscriber {
 
   /// Insert event row into ClickHouse
   #[instrument(skip(self, row))]
+  /// Delete every stored event of a user whose data was erased. Left unacked on failure, so
+  /// JetStream redelivers it
+  async fn forget_user(&self, payload: &[u8]) -> Result<(), AppError> {
+    let event: UserErasedEvent = serde_json::from_slice(payload)
+      .map_err(|e| AppError::AnyError(anyhow::anyhow!("Invalid user erased event: {}", e)))?;
+    let user_id = i64::from(event.user_id).to_string();
+
+    self
+      .state
+      .client
+      .query("ALTER TABLE analytics_events DELETE WHERE user_id = ?")
+      .bind(&user_id)
+      .execute()
+      .await?;
+
+    info!("[ANALYTICS] Deleted events of erased user {}", user_id);
+    Ok(())
+  }
+
   async fn insert_event_row(&self, row: AnalyticsEventRow) -> Result<(), AppError> {
     let mut insert = self.state.client.insert("analytics_events")?;
     insert.write(&row).await?;
@@ -586,6 +616,8 @@ mod tests {
           browser_version: "100.0".to_string(),
         }),
         geo: None,
+        request_id: String::new(),
+        traceparent: String::new(),
       }),
       event_type: Some(analytics_event::EventType::MessageSent(MessageSentEvent {
         chat_id: "chat123".to_string(),
@@ -71,6 +71,12 @@ pub struct EventContext {
     pub system: ::core::option::Option<SystemInfo>,
     #[prost(message, optional, tag = "10")]
     pub geo: ::core::option::Option<GeoLocation>,
+    /// Propagated x-request-id of the request that produced the event, if any
+    #[prost(string, tag = "11")]
+    pub request_id: ::prost::alloc::string::String,
+    /// W3C traceparent of the request that produced the event, if any
+    #[prost(string, tag = "12")]
+    pub traceparent: ::prost::alloc::string::String,
 }
 /// 系统信息
 #[derive(Clone, PartialEq, ::prost::Message)]
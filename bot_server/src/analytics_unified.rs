@@ -43,6 +43,8 @@ impl UnifiedBotAnalyticsPublisher {
                 browser_version: String::new(),
             }),
             geo: None,
+            request_id: String::new(),
+            traceparent: String::new(),
         }
     }
 
@@ -1,4 +1,6 @@
 use anyhow::{Result, bail};
+use fechatter_core::services::concurrency::ConcurrencyLimitConfig;
+use fechatter_core::utils::redact::{RedactedSummary, redact_optional_secret, redact_url};
 use serde::{Deserialize, Serialize};
 use std::env;
 
@@ -13,6 +15,13 @@ pub struct AppConfig {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ServerConfig {
   pub db_url: String,
+  /// Port for the health/metrics listener (`/health`, `/ready`, `/live`, `/metrics`)
+  #[serde(default = "default_health_port")]
+  pub health_port: u16,
+}
+
+fn default_health_port() -> u16 {
+  9092
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -25,6 +34,30 @@ pub struct MessagingConfig {
 pub struct NatsConfig {
   pub url: String,
   pub subscription_subjects: Vec<String>,
+  /// Consume the subjects through a durable JetStream consumer instead of
+  /// plain subscriptions, so triggers are not lost across restarts
+  #[serde(default)]
+  pub durable_consumer: Option<DurableConsumerConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DurableConsumerConfig {
+  /// JetStream stream that captures the subscription subjects
+  pub stream: String,
+  /// Durable consumer name
+  pub name: String,
+  #[serde(default = "default_max_deliver")]
+  pub max_deliver: i64,
+  #[serde(default = "default_ack_wait_seconds")]
+  pub ack_wait_seconds: u64,
+}
+
+fn default_max_deliver() -> i64 {
+  3
+}
+
+fn default_ack_wait_seconds() -> u64 {
+  30
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -33,6 +66,9 @@ pub struct BotConfig {
   pub vector: VectorConfig,
   pub response_delay_ms: u64,
   pub max_response_length: usize,
+  /// Process-wide cap on concurrent AI requests
+  #[serde(default)]
+  pub concurrency: ConcurrencyLimitConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -54,6 +90,25 @@ pub struct AnalyticsConfig {
   pub endpoint: String,
 }
 
+impl RedactedSummary for AppConfig {
+  fn redacted_summary(&self) -> String {
+    format!(
+      "bot_server config: db={}, nats={}, messaging={}, model={}, embed_model={}, api_key={}, vector_size={}",
+      redact_url(&self.server.db_url),
+      redact_url(&self.messaging.nats.url),
+      if self.messaging.enabled {
+        "enabled"
+      } else {
+        "disabled"
+      },
+      self.bot.openai.model,
+      self.bot.openai.embed_model,
+      redact_optional_secret(self.bot.openai.api_key.as_deref()),
+      self.bot.vector.size
+    )
+  }
+}
+
 impl AppConfig {
   pub fn load() -> Result<Self> {
     // Enhanced configuration loading with Docker container support
@@ -190,6 +245,19 @@ impl AppConfig {
       if self.messaging.nats.subscription_subjects.is_empty() {
         bail!("At least one NATS subscription subject must be configured");
       }
+
+      for subject in &self.messaging.nats.subscription_subjects {
+        validate_subject(subject)?;
+      }
+
+      if let Some(durable) = &self.messaging.nats.durable_consumer {
+        if durable.stream.is_empty() {
+          bail!("Durable consumer stream name cannot be empty");
+        }
+        if durable.name.is_empty() || durable.name.contains(['.', '*', '>', ' ']) {
+          bail!("Invalid durable consumer name: '{}'", durable.name);
+        }
+      }
     }
 
     // Validate bot configuration
@@ -235,43 +303,6 @@ impl AppConfig {
     Ok(())
   }
 
-  /// Get config summary for logging (exclude sensitive info)
-  pub fn get_summary(&self) -> String {
-    let api_key_status = match &self.bot.openai.api_key {
-      Some(key) => {
-        if key.starts_with("sk-") {
-          format!("set({}***)", &key[..7]) // Show first 7 chars only
-        } else {
-          "set(invalid_format)".to_string()
-        }
-      }
-      None => "not_set".to_string(),
-    };
-
-    format!(
-      "bot_server config: db={}, nats={}, messaging={}, model={}, embed_model={}, api_key={}, vector_size={}",
-      if self.server.db_url.contains("localhost") {
-        "localhost"
-      } else {
-        "remote"
-      },
-      if self.messaging.nats.url.contains("localhost") {
-        "localhost"
-      } else {
-        "remote"
-      },
-      if self.messaging.enabled {
-        "enabled"
-      } else {
-        "disabled"
-      },
-      self.bot.openai.model,
-      self.bot.openai.embed_model,
-      api_key_status,
-      self.bot.vector.size
-    )
-  }
-
   /// Apply environment variable overrides with highest priority
   fn apply_env_overrides(&mut self) {
     println!("Applying environment variable overrides...");
@@ -305,6 +336,31 @@ impl AppConfig {
   }
 }
 
+/// Validate a NATS subscription subject
+///
+/// Subjects are dot-separated non-empty tokens; `*` may replace a whole token
+/// and `>` is only allowed as the last token.
+fn validate_subject(subject: &str) -> Result<()> {
+  if subject.is_empty() || subject.chars().any(char::is_whitespace) {
+    bail!("Invalid NATS subject '{}': must be non-empty without whitespace", subject);
+  }
+
+  let tokens: Vec<&str> = subject.split('.').collect();
+  for (i, token) in tokens.iter().enumerate() {
+    if token.is_empty() {
+      bail!("Invalid NATS subject '{}': empty token", subject);
+    }
+    if token.len() > 1 && token.contains(['*', '>']) {
+      bail!("Invalid NATS subject '{}': wildcards must be whole tokens", subject);
+    }
+    if *token == ">" && i != tokens.len() - 1 {
+      bail!("Invalid NATS subject '{}': '>' must be the last token", subject);
+    }
+  }
+
+  Ok(())
+}
+
 impl OpenAIConfig {
   /// Get the OpenAI API key, preferring environment variable over config file
   pub fn get_api_key(&self) -> Result<&str> {
@@ -319,3 +375,24 @@ impl OpenAIConfig {
     self.api_key.is_some() && !self.api_key.as_ref().unwrap().is_empty()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn validate_subject_accepts_configured_subjects() {
+    assert!(validate_subject("fechatter.messages.created").is_ok());
+    assert!(validate_subject("fechatter.chats.*.joined").is_ok());
+    assert!(validate_subject("fechatter.bot.>").is_ok());
+  }
+
+  #[test]
+  fn validate_subject_rejects_malformed_subjects() {
+    assert!(validate_subject("").is_err());
+    assert!(validate_subject("fechatter..created").is_err());
+    assert!(validate_subject("fechatter.messages created").is_err());
+    assert!(validate_subject("fechatter.>.created").is_err());
+    assert!(validate_subject("fechatter.msg*").is_err());
+  }
+}
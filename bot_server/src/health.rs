@@ -1,8 +1,8 @@
 use anyhow::Result;
 use axum::{
     extract::State,
-    http::StatusCode,
-    response::Json,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json},
     routing::get,
     Router,
 };
@@ -12,6 +12,7 @@ use std::sync::Arc;
 use tokio::net::TcpListener;
 use tracing::info;
 
+use crate::observability::metrics::{collectors::HealthMetrics, render_metrics};
 use crate::AppConfig;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -67,6 +68,7 @@ pub async fn start_health_server(state: HealthState, port: u16) -> Result<()> {
         .route("/health", get(health_check_handler))
         .route("/ready", get(readiness_check_handler))
         .route("/live", get(liveness_check_handler))
+        .route("/metrics", get(metrics_handler))
         .with_state(state);
 
     let addr = format!("0.0.0.0:{}", port);
@@ -109,6 +111,7 @@ async fn health_check_handler(State(state): State<HealthState>) -> Result<Json<H
         timestamp: chrono::Utc::now().timestamp_millis(),
     };
 
+    HealthMetrics::health_check_completed(start_time.elapsed(), all_healthy);
     info!("🏥 Health check completed in {:?}", start_time.elapsed());
 
     if all_healthy {
@@ -151,6 +154,22 @@ async fn liveness_check_handler() -> Json<serde_json::Value> {
     }))
 }
 
+/// Prometheus scrape endpoint
+async fn metrics_handler() -> impl IntoResponse {
+    match render_metrics() {
+        Some(body) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            body,
+        ),
+        None => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            "# metrics recorder not initialized\n".to_string(),
+        ),
+    }
+}
+
 /// Check database connectivity
 async fn check_database(state: &HealthState) -> ServiceStatus {
     let start = std::time::Instant::now();
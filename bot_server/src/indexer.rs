@@ -1,5 +1,6 @@
 use anyhow::Result;
 use bot_server::AppConfig;
+use fechatter_core::utils::RedactedSummary;
 use sqlx::postgres::PgPoolOptions;
 use swiftide::{
   indexing::{
@@ -19,7 +20,7 @@ async fn main() -> Result<()> {
   tracing_subscriber::registry().with(layer).init();
 
   let config = AppConfig::load()?;
-  info!("{}", config.get_summary());
+  info!("{}", config.redacted_summary());
 
   let db_url = &config.server.db_url;
   let vector_size = config.bot.vector.size;
@@ -1,10 +1,15 @@
 use std::collections::HashSet;
 
+use crate::config::DurableConsumerConfig;
+use crate::observability::metrics::collectors::{AIAgentMetrics, NATSEventMetrics};
 use crate::{UnifiedBotAnalyticsPublisher, AppConfig};
+use async_nats::jetstream;
+use fechatter_core::services::concurrency::ConcurrencyLimiter;
 use fechatter_core::{Message, UserId};
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, postgres::PgPoolOptions};
+use std::future::Future;
 use std::sync::Arc;
 use swiftide::{
   integrations,
@@ -74,72 +79,231 @@ pub async fn setup_nats_subscriber(
     config.bot.openai.model
   );
 
-  // Subscribe to configured subjects
-  for subject in &config.messaging.nats.subscription_subjects {
-    let subscriber = nats_client.subscribe(subject.clone()).await?;
+  // Shared by every handler task, so bursts of events cannot exceed the AI concurrency cap
+  let limiter = ConcurrencyLimiter::from_config(&config.bot.concurrency)
+    .with_observer(AIAgentMetrics::set_in_flight);
+  info!(
+    "🚦 AI concurrency capped at {} in-flight requests ({:?} when saturated)",
+    limiter.max_in_flight(),
+    config.bot.concurrency.on_saturated
+  );
+
+  let handler = {
+    let bots = Arc::new(bots);
+    let config = Arc::new(config.clone());
+    move |subject: String, payload: Vec<u8>| {
+      let pool = pool.clone();
+      let bots = bots.clone();
+      let ai_client = ai_client.clone();
+      let limiter = limiter.clone();
+      let config = config.clone();
+      let analytics = analytics_publisher.clone();
+      async move {
+        handle_bot_event(
+          &pool,
+          &bots,
+          &ai_client,
+          &limiter,
+          &config,
+          analytics.as_ref(),
+          &subject,
+          &payload,
+        )
+        .await
+      }
+    }
+  };
+
+  let subjects = config.messaging.nats.subscription_subjects.clone();
+  match &config.messaging.nats.durable_consumer {
+    Some(durable) => {
+      spawn_durable_consumer(nats_client.as_ref().clone(), durable.clone(), subjects, handler)
+        .await?;
+    }
+    None => {
+      spawn_subject_subscriptions(nats_client.as_ref(), &subjects, handler).await?;
+    }
+  }
+
+  info!("Bot NATS event processor setup complete");
+  Ok(())
+}
+
+/// Subscribe to each configured subject with a plain (non-durable) subscription
+///
+/// Every message received on any of `subjects` is passed to `handler`.
+pub async fn spawn_subject_subscriptions<F, Fut>(
+  nats_client: &async_nats::Client,
+  subjects: &[String],
+  handler: F,
+) -> anyhow::Result<()>
+where
+  F: Fn(String, Vec<u8>) -> Fut + Clone + Send + Sync + 'static,
+  Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+  for subject in subjects {
+    let mut subscriber = nats_client.subscribe(subject.clone()).await?;
     let subject_str = subject.clone();
-    let pool_clone = pool.clone();
-    let bots_clone = bots.clone();
-    let ai_client_clone = ai_client.clone();
-    let config_clone = config.clone();
-    let analytics_clone = analytics_publisher.clone();
+    let handler = handler.clone();
 
     // Spawn a handler for each subscription
     tokio::spawn(async move {
       info!("SUBSCRIPTION: Bot NATS subscriber started: {}", subject_str);
-      let mut subscriber = subscriber;
 
       while let Some(msg) = subscriber.next().await {
-        let subject = msg.subject.as_str();
-        let payload = msg.payload.as_ref();
-        let payload_size = payload.len();
-
-        // Upgrade to INFO level with detailed logging
-        info!("EVENT: [BOT] Received NATS event from subject: {} (size: {} bytes)", subject, payload_size);
-
-        // Process the event
-        if let Err(e) = process_nats_event(
-          &pool_clone,
-          &bots_clone,
-          &ai_client_clone,
-          &config_clone,
-          analytics_clone.as_ref(),
-          subject,
-          payload,
-        )
-        .await
-        {
-          error!("ERROR: [BOT] Failed to process event from {}: {}", subject, e);
-          
-          // Track error in analytics using unified publisher
-          if let Some(analytics) = &analytics_clone {
-            let _ = analytics
-              .track_bot_error(
-                "unknown_bot".to_string(),
-                "unknown_chat".to_string(),
-                "NATS_EVENT_PROCESSING".to_string(),
-                format!("Failed to process NATS event {}: {}", subject, e),
-              )
-              .await;
-          }
-        } else {
-          info!("[BOT] Successfully processed event from: {}", subject);
-        }
+        let _ = handler(msg.subject.to_string(), msg.payload.to_vec()).await;
       }
 
       warn!("WARNING: Bot NATS subscriber ended: {}", subject_str);
     });
   }
 
-  info!("Bot NATS event processor setup complete");
   Ok(())
 }
 
+/// Consume the configured subjects through a durable JetStream pull consumer
+///
+/// Messages are acked after successful handling and nak'ed otherwise, so
+/// triggers published while the bot is down are delivered on restart.
+pub async fn spawn_durable_consumer<F, Fut>(
+  nats_client: async_nats::Client,
+  durable: DurableConsumerConfig,
+  subjects: Vec<String>,
+  handler: F,
+) -> anyhow::Result<()>
+where
+  F: Fn(String, Vec<u8>) -> Fut + Clone + Send + Sync + 'static,
+  Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+  let jetstream = jetstream::new(nats_client);
+  let stream = jetstream
+    .get_stream(&durable.stream)
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to get stream {}: {}", durable.stream, e))?;
+
+  let consumer_config = jetstream::consumer::pull::Config {
+    durable_name: Some(durable.name.clone()),
+    filter_subjects: subjects.clone(),
+    ack_policy: jetstream::consumer::AckPolicy::Explicit,
+    max_deliver: durable.max_deliver,
+    ack_wait: std::time::Duration::from_secs(durable.ack_wait_seconds),
+    ..Default::default()
+  };
+
+  let consumer = stream
+    .get_or_create_consumer(&durable.name, consumer_config)
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to create durable consumer {}: {}", durable.name, e))?;
+
+  let mut messages = consumer
+    .messages()
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to get messages for {}: {}", durable.name, e))?;
+
+  info!(
+    "SUBSCRIPTION: Bot durable consumer '{}' started on stream {} for {:?}",
+    durable.name, durable.stream, subjects
+  );
+
+  tokio::spawn(async move {
+    while let Some(msg) = messages.next().await {
+      let msg = match msg {
+        Ok(msg) => msg,
+        Err(e) => {
+          error!("ERROR: [BOT] Durable consumer error: {}", e);
+          continue;
+        }
+      };
+
+      let ack = match handler(msg.subject.to_string(), msg.payload.to_vec()).await {
+        Ok(()) => msg.ack().await,
+        Err(_) => msg.ack_with(jetstream::AckKind::Nak(None)).await,
+      };
+      if let Err(e) = ack {
+        warn!("WARNING: [BOT] Failed to ack message on {}: {}", msg.subject, e);
+      }
+    }
+
+    warn!("WARNING: Bot durable consumer ended: {}", durable.name);
+  });
+
+  Ok(())
+}
+
+/// Handle a single NATS event with metrics and error tracking
+async fn handle_bot_event(
+  pool: &PgPool,
+  bots: &HashSet<UserId>,
+  ai_client: &integrations::openai::OpenAI,
+  limiter: &ConcurrencyLimiter,
+  config: &AppConfig,
+  analytics_publisher: Option<&Arc<UnifiedBotAnalyticsPublisher>>,
+  subject: &str,
+  payload: &[u8],
+) -> anyhow::Result<()> {
+  // Upgrade to INFO level with detailed logging
+  info!("EVENT: [BOT] Received NATS event from subject: {} (size: {} bytes)", subject, payload.len());
+
+  let event_type = event_type_for_subject(subject);
+  NATSEventMetrics::event_received(event_type);
+  let processing_start = std::time::Instant::now();
+
+  // Process the event
+  let processed = process_nats_event(
+    pool,
+    bots,
+    ai_client,
+    limiter,
+    config,
+    analytics_publisher,
+    subject,
+    payload,
+  )
+  .await;
+  match processed {
+    Ok(()) => {
+      NATSEventMetrics::event_processed(event_type, processing_start.elapsed(), true);
+      info!("[BOT] Successfully processed event from: {}", subject);
+      Ok(())
+    }
+    Err(e) => {
+      error!("ERROR: [BOT] Failed to process event from {}: {}", subject, e);
+      NATSEventMetrics::event_processed(event_type, processing_start.elapsed(), false);
+      NATSEventMetrics::processing_error(event_type, "processing_error");
+
+      // Track error in analytics using unified publisher
+      if let Some(analytics) = analytics_publisher {
+        let _ = analytics
+          .track_bot_error(
+            "unknown_bot".to_string(),
+            "unknown_chat".to_string(),
+            "NATS_EVENT_PROCESSING".to_string(),
+            format!("Failed to process NATS event {}: {}", subject, e),
+          )
+          .await;
+      }
+      Err(e)
+    }
+  }
+}
+
+/// Map a NATS subject to the `event_type` label used by the bot metrics
+fn event_type_for_subject(subject: &str) -> &'static str {
+  if subject.contains("message.created") || subject.contains("messages.created") {
+    "message_created"
+  } else if subject.contains("member.joined") {
+    "member_joined"
+  } else {
+    "other"
+  }
+}
+
 /// Process NATS events for bot functionality
 pub async fn process_nats_event(
   pool: &PgPool,
   bots: &HashSet<UserId>,
   ai_client: &integrations::openai::OpenAI,
+  limiter: &ConcurrencyLimiter,
   config: &AppConfig,
   analytics_publisher: Option<&Arc<UnifiedBotAnalyticsPublisher>>,
   subject: &str,
@@ -194,6 +358,7 @@ pub async fn process_nats_event(
         pool,
         ai_client.clone(),
         ai_client.clone(),
+        limiter,
         config,
         analytics_publisher,
       )
@@ -246,6 +411,7 @@ async fn process_message_created_event(
   pool: &PgPool,
   bots: &HashSet<UserId>,
   ai_client: &integrations::openai::OpenAI,
+  limiter: &ConcurrencyLimiter,
   config: &AppConfig,
   analytics_publisher: Option<&Arc<UnifiedBotAnalyticsPublisher>>,
   payload: &[u8],
@@ -278,7 +444,7 @@ async fn process_message_created_event(
 
       // Process the notification with AI
       notification
-        .process(pool, ai_client.clone(), ai_client.clone(), config, analytics_publisher)
+        .process(pool, ai_client.clone(), ai_client.clone(), limiter, config, analytics_publisher)
         .await?;
     } else {
       debug!("USER: Other participant is not a bot, skipping");
@@ -300,6 +466,7 @@ impl BotNotification {
     pool: &PgPool,
     client: impl SimplePrompt + Clone + 'static,
     embed: impl EmbeddingModel + Clone + 'static,
+    limiter: &ConcurrencyLimiter,
     config: &AppConfig,
     analytics_publisher: Option<&Arc<UnifiedBotAnalyticsPublisher>>,
   ) -> anyhow::Result<()> {
@@ -336,8 +503,28 @@ impl BotNotification {
 
     info!("Querying AI with: {}", self.event.content);
 
-    // Generate AI response
-    let result = pipeline.query(&self.event.content).await?;
+    // Generate AI response, waiting for (or refused) a slot when the cap is reached
+    let slot = match limiter.acquire().await {
+      Ok(slot) => slot,
+      Err(e) => {
+        AIAgentMetrics::request_failed("rag", "saturated");
+        return Err(e.into());
+      }
+    };
+    let ai_start = std::time::Instant::now();
+    let query = pipeline.query(&self.event.content).await;
+    drop(slot);
+    let result = match query {
+      Ok(result) => {
+        AIAgentMetrics::request_completed("rag", ai_start.elapsed(), None, true);
+        result
+      }
+      Err(e) => {
+        AIAgentMetrics::request_completed("rag", ai_start.elapsed(), None, false);
+        AIAgentMetrics::request_failed("rag", "query_error");
+        return Err(e);
+      }
+    };
     let mut answer = result.answer().to_string();
 
     // Truncate response if too long
@@ -408,3 +595,102 @@ pub async fn get_bots(pool: &PgPool) -> anyhow::Result<HashSet<UserId>> {
 
   Ok(bot_set)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::time::Duration;
+  use tokio::sync::mpsc;
+
+  #[tokio::test]
+  #[ignore] // Requires NATS server
+  async fn configured_subject_event_reaches_handler() {
+    let url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://localhost:4222".to_string());
+    let client = async_nats::connect(url).await.unwrap();
+    let subjects = vec!["fechatter.test.bot.trigger".to_string()];
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    spawn_subject_subscriptions(&client, &subjects, move |subject, payload| {
+      let tx = tx.clone();
+      async move {
+        tx.send((subject, payload)).unwrap();
+        Ok(())
+      }
+    })
+    .await
+    .unwrap();
+
+    client
+      .publish("fechatter.test.bot.trigger", "hello".into())
+      .await
+      .unwrap();
+    client.flush().await.unwrap();
+
+    let (subject, payload) = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(subject, "fechatter.test.bot.trigger");
+    assert_eq!(payload, b"hello");
+  }
+
+  #[test]
+  fn processing_a_message_increments_consumed_counter() {
+    let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+    let handle = recorder.handle();
+    let config = AppConfig::load().unwrap();
+    // No bot is a member of the chat, so the event is consumed without touching the
+    // database or the AI provider
+    let event = MessageCreatedEvent {
+      msg: Message {
+        id: fechatter_core::MessageId(1),
+        chat_id: fechatter_core::ChatId(1),
+        sender_id: UserId(1),
+        content: "hello".to_string(),
+        files: None,
+        created_at: chrono::Utc::now(),
+        idempotency_key: None,
+        client_message_id: None,
+      },
+      members: HashSet::from([UserId(1), UserId(2)]),
+    };
+    let payload = serde_json::to_vec(&event).unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+      .enable_all()
+      .build()
+      .unwrap();
+    metrics::with_local_recorder(&recorder, || {
+      runtime.block_on(async {
+        let pool = PgPoolOptions::new()
+          .connect_lazy(&config.server.db_url)
+          .unwrap();
+        let ai_client = integrations::openai::OpenAI::builder()
+          .default_embed_model(&config.bot.openai.embed_model)
+          .default_prompt_model(&config.bot.openai.model)
+          .build()
+          .unwrap();
+        let limiter = ConcurrencyLimiter::from_config(&config.bot.concurrency);
+
+        handle_bot_event(
+          &pool,
+          &HashSet::from([UserId(99)]),
+          &ai_client,
+          &limiter,
+          &config,
+          None,
+          "fechatter.messages.created",
+          &payload,
+        )
+        .await
+        .unwrap();
+      })
+    });
+
+    let rendered = handle.render();
+    assert!(rendered.contains(r#"bot_nats_events_received_total{event_type="message_created"} 1"#));
+    assert!(rendered.contains(
+      r#"bot_nats_events_processed_total{event_type="message_created",status="success"} 1"#
+    ));
+  }
+}
@@ -1,27 +1,36 @@
 use anyhow::Result;
 use metrics::{counter, gauge, histogram};
-use metrics_exporter_prometheus::PrometheusBuilder;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use std::sync::OnceLock;
 use std::time::Duration;
 use tracing::info;
 
+/// Handle used by the health server to render the `/metrics` endpoint
+static PROMETHEUS_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
 /// Initialize Prometheus metrics for bot_server
+///
+/// The recorder is installed globally; metrics are exposed on the health
+/// server's `/metrics` route instead of a dedicated listener.
 pub async fn init_metrics() -> Result<()> {
-    let builder = PrometheusBuilder::new();
+    let handle = PrometheusBuilder::new().install_recorder()?;
 
-    // Register all bot_server metrics first
+    // Register all bot_server metrics once the recorder is in place
     register_bot_metrics();
 
-    // Set up Prometheus endpoint on port 9092
-    let bind_address = "0.0.0.0:9092";
-    
-    builder
-        .with_http_listener(bind_address.parse::<std::net::SocketAddr>()?)
-        .install()?;
+    if PROMETHEUS_HANDLE.set(handle).is_err() {
+        anyhow::bail!("Prometheus recorder already initialized");
+    }
 
-    info!("[BOT] Prometheus metrics server started on {}", bind_address);
+    info!("[BOT] Prometheus recorder installed");
     Ok(())
 }
 
+/// Render the current metrics snapshot in Prometheus text format
+pub fn render_metrics() -> Option<String> {
+    PROMETHEUS_HANDLE.get().map(|handle| handle.render())
+}
+
 /// Register all bot_server specific metrics
 fn register_bot_metrics() {
     // NATS event processing metrics
@@ -51,6 +60,7 @@ fn register_bot_metrics() {
     counter!("bot_ai_requests_failed_total", "agent" => "summary", "error_type" => "timeout").absolute(0);
     histogram!("bot_ai_request_duration_seconds", "agent" => "summary").record(0.0);
     histogram!("bot_ai_token_usage", "agent" => "summary", "type" => "input").record(0.0);
+    gauge!("bot_ai_requests_in_flight").set(0.0);
 
     // Database metrics
     counter!("bot_db_operations_total", "operation" => "insert", "table" => "message_embeddings").absolute(0);
@@ -178,6 +188,11 @@ pub mod collectors {
                     "agent" => agent.to_string(), 
                     "error_type" => error_type.to_string()).increment(1);
         }
+
+        /// AI requests currently holding a slot of the concurrency limiter
+        pub fn set_in_flight(count: usize) {
+            gauge!("bot_ai_requests_in_flight").set(count as f64);
+        }
     }
 
     /// Database metrics
@@ -211,4 +226,4 @@ pub mod collectors {
             histogram!("bot_health_check_duration_seconds").record(duration.as_secs_f64());
         }
     }
-}
\ No newline at end of file
+}
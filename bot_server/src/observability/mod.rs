@@ -4,7 +4,7 @@ use anyhow::Result;
 
 /// Initialize observability components (metrics)
 pub async fn init_observability() -> Result<()> {
-    // Initialize Prometheus metrics on port 9092
+    // Install the Prometheus recorder (served by the health server)
     metrics::init_metrics().await?;
     
     tracing::info!("Observability initialized for bot_server");
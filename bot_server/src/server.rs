@@ -6,7 +6,8 @@ use axum::{
     routing::{get, post},
     Router,
 };
-use bot_server::{AppConfig, HealthState, setup_nats_subscriber};
+use bot_server::{AppConfig, HealthState, setup_nats_subscriber, start_health_server};
+use fechatter_core::utils::RedactedSummary;
 use reqwest;
 use serde::{Deserialize, Serialize};
 use sqlx::postgres::PgPoolOptions;
@@ -426,14 +427,12 @@ async fn main() -> Result<()> {
     if let Err(e) = bot_server::observability::init_observability().await {
         eprintln!("ERROR: Failed to initialize observability: {}", e);
         eprintln!("   Continuing without Prometheus metrics");
-    } else {
-        info!("Prometheus metrics available at: http://0.0.0.0:9092/metrics");
     }
 
     // Load configuration
     let config = match AppConfig::load() {
         Ok(config) => {
-            info!("{}", config.get_summary());
+            info!("{}", config.redacted_summary());
             config
         }
         Err(e) => {
@@ -486,8 +485,8 @@ async fn main() -> Result<()> {
         None
     };
 
-    // Setup health check state (currently integrated into handlers)
-    let _health_state = match &nats_client {
+    // Setup health check state and start the health/metrics listener
+    let health_state = match &nats_client {
         Some(nats_client) => {
             HealthState::new(pool.clone(), config_arc.clone()).with_nats(nats_client.clone())
         }
@@ -496,6 +495,14 @@ async fn main() -> Result<()> {
         }
     };
 
+    let health_port = config.server.health_port;
+    tokio::spawn(async move {
+        if let Err(e) = start_health_server(health_state, health_port).await {
+            error!("Health server failed: {}", e);
+        }
+    });
+    info!("Prometheus metrics available at: http://0.0.0.0:{}/metrics", health_port);
+
     // Create application state
     let app_state = AppState::new(config_arc.clone());
 
@@ -1,6 +1,6 @@
 /// Event contracts shared between fechatter_server and notify_server
 /// This module serves as the single source of truth for event definitions
-use crate::{ChatId, Message, MessageId, UserId};
+use crate::{Chat, ChatId, Message, MessageId, UserId, WorkspaceId};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -49,6 +49,39 @@ impl VersionedEvent for MessageEvent {
   }
 }
 
+/// Chat lifecycle states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatLifecycle {
+  Created,
+  Updated,
+  Deleted,
+  OwnershipTransferred,
+}
+
+/// Chat event for the members' sidebars
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatEvent {
+  #[serde(default)]
+  pub version: EventVersion,
+  pub kind: ChatLifecycle,
+  /// The chat after the change; as it was before, for `Deleted`
+  pub chat: Chat,
+  /// User who made the change
+  pub actor_id: UserId,
+  /// Active members, who all need to refresh the chat
+  pub members: Vec<UserId>,
+  pub occurred_at: DateTime<Utc>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sig: Option<String>,
+}
+
+impl VersionedEvent for ChatEvent {
+  fn version(&self) -> EventVersion {
+    self.version
+  }
+}
+
 /// Chat member joined event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMemberJoinedEvent {
@@ -104,15 +137,230 @@ impl VersionedEvent for DuplicateMessageEvent {
   }
 }
 
+/// Every member who was in the chat when a message was sent, and is still in it, has read it.
+/// Published once per message, for the sender
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageFullyReadEvent {
+  #[serde(default)]
+  pub version: EventVersion,
+  pub message_id: MessageId,
+  pub chat_id: ChatId,
+  pub sender_id: UserId,
+  /// Users with a read receipt for the message, sender excluded
+  pub readers: Vec<UserId>,
+  pub occurred_at: DateTime<Utc>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sig: Option<String>,
+}
+
+impl VersionedEvent for MessageFullyReadEvent {
+  fn version(&self) -> EventVersion {
+    self.version
+  }
+}
+
+/// Scan state of an uploaded file; anything but `Clean` keeps it quarantined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileScanStatus {
+  Pending,
+  Clean,
+  Infected,
+  /// The scanner errored or timed out
+  Failed,
+}
+
+impl FileScanStatus {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Pending => "pending",
+      Self::Clean => "clean",
+      Self::Infected => "infected",
+      Self::Failed => "failed",
+    }
+  }
+
+  pub fn parse(status: &str) -> Option<Self> {
+    match status {
+      "pending" => Some(Self::Pending),
+      "clean" => Some(Self::Clean),
+      "infected" => Some(Self::Infected),
+      "failed" => Some(Self::Failed),
+      _ => None,
+    }
+  }
+}
+
+/// Verdict of an attachment scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileScannedEvent {
+  #[serde(default)]
+  pub version: EventVersion,
+  /// Storage id of the file (`hash.ext`)
+  pub file_id: String,
+  pub status: FileScanStatus,
+  /// Signature name or scanner error
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub detail: Option<String>,
+  pub scanner: String,
+  pub uploaded_by: UserId,
+  pub workspace_id: WorkspaceId,
+  pub occurred_at: DateTime<Utc>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sig: Option<String>,
+}
+
+impl VersionedEvent for FileScannedEvent {
+  fn version(&self) -> EventVersion {
+    self.version
+  }
+}
+
+/// An admin erased a user's personal data; stores keyed by user (e.g. analytics) drop theirs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserErasedEvent {
+  #[serde(default)]
+  pub version: EventVersion,
+  pub user_id: UserId,
+  pub workspace_id: WorkspaceId,
+  pub occurred_at: DateTime<Utc>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sig: Option<String>,
+}
+
+impl VersionedEvent for UserErasedEvent {
+  fn version(&self) -> EventVersion {
+    self.version
+  }
+}
+
+/// How prominently clients show a system announcement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnouncementSeverity {
+  #[default]
+  Info,
+  Warning,
+  Critical,
+}
+
+/// Admin announcement (e.g. a maintenance banner) pushed to every connected client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemAnnouncementEvent {
+  #[serde(default)]
+  pub version: EventVersion,
+  pub id: Uuid,
+  pub title: String,
+  pub message: String,
+  #[serde(default)]
+  pub severity: AnnouncementSeverity,
+  /// Only users of this workspace receive it; `None` reaches everyone
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub workspace_id: Option<WorkspaceId>,
+  pub sent_by: UserId,
+  pub occurred_at: DateTime<Utc>,
+}
+
+impl VersionedEvent for SystemAnnouncementEvent {
+  fn version(&self) -> EventVersion {
+    self.version
+  }
+}
+
+/// Reply to a slash command that only its sender sees; delivered over SSE, never stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemeralMessageEvent {
+  #[serde(default)]
+  pub version: EventVersion,
+  pub chat_id: ChatId,
+  pub user_id: UserId,
+  /// Command that produced the reply, without the leading `/`
+  pub command: String,
+  pub content: String,
+  pub occurred_at: DateTime<Utc>,
+}
+
+impl VersionedEvent for EphemeralMessageEvent {
+  fn version(&self) -> EventVersion {
+    self.version
+  }
+}
+
+/// Piece of an AI response streamed to the user who asked for it; delivered over SSE, never
+/// stored. Pieces of one response share a `stream_id` and arrive in `sequence` order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiStreamChunkEvent {
+  #[serde(default)]
+  pub version: EventVersion,
+  pub stream_id: Uuid,
+  pub user_id: UserId,
+  /// Message the response is about
+  pub message_id: MessageId,
+  pub sequence: u32,
+  pub delta: String,
+  /// Set on the final event, which carries no text
+  pub done: bool,
+  pub occurred_at: DateTime<Utc>,
+}
+
+impl VersionedEvent for AiStreamChunkEvent {
+  fn version(&self) -> EventVersion {
+    self.version
+  }
+}
+
+/// Open Graph / Twitter card metadata of a URL found in a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkPreview {
+  pub url: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub title: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  /// Absolute image URL
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub image: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub site_name: Option<String>,
+}
+
+/// Link previews generated after a message was sent, in the order the URLs appear
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessagePreviewsEvent {
+  #[serde(default)]
+  pub version: EventVersion,
+  pub message_id: MessageId,
+  pub chat_id: ChatId,
+  pub previews: Vec<LinkPreview>,
+  pub occurred_at: DateTime<Utc>,
+}
+
+impl VersionedEvent for MessagePreviewsEvent {
+  fn version(&self) -> EventVersion {
+    self.version
+  }
+}
+
 /// Event subjects/topics constants
 pub mod subjects {
   pub const MESSAGE_CREATED: &str = "fechatter.message.created";
   pub const MESSAGE_UPDATED: &str = "fechatter.message.updated";
   pub const MESSAGE_DELETED: &str = "fechatter.message.deleted";
+  pub const MESSAGE_FULLY_READ: &str = "fechatter.message.fully_read";
   pub const CHAT_MEMBER_JOINED: &str = "fechatter.chat.joined";
   pub const CHAT_MEMBER_LEFT: &str = "fechatter.chat.left";
+  pub const CHAT_CREATED: &str = "fechatter.chat.created";
+  pub const CHAT_UPDATED: &str = "fechatter.chat.updated";
+  pub const CHAT_DELETED: &str = "fechatter.chat.deleted";
+  pub const CHAT_OWNERSHIP_TRANSFERRED: &str = "fechatter.chat.ownership_transferred";
   pub const DUPLICATE_MESSAGE: &str = "fechatter.message.duplicate";
+  pub const MESSAGE_PREVIEWS: &str = "fechatter.message.previews";
   pub const SEARCH_INDEX: &str = "fechatter.search.index";
+  pub const SYSTEM_ANNOUNCEMENT: &str = "fechatter.system.announcement";
+  pub const EPHEMERAL_MESSAGE: &str = "fechatter.system.ephemeral";
+  pub const AI_STREAM_CHUNK: &str = "fechatter.system.ai_stream";
+  pub const FILE_SCANNED: &str = "fechatter.file.scanned";
+  pub const USER_ERASED: &str = "fechatter.privacy.user_erased";
 }
 
 /// Signature verification interface
@@ -200,6 +448,7 @@ mod tests {
         files: None,
         created_at: Utc::now(),
         idempotency_key: None,
+        client_message_id: None,
       },
       members: vec![UserId(1), UserId(2)],
       occurred_at: Utc::now(),
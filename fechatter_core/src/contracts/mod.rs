@@ -8,9 +8,13 @@ pub mod infrastructure;
 // Event interface contracts
 pub mod events;
 
+// Shared typing/presence state contracts
+pub mod presence;
+
 // Re-export interface contracts
 pub use events::*;
 pub use infrastructure::*;
+pub use presence::{PresenceStatus, PresenceStore, PresenceTtl, TypingEntry};
 pub use repositories::*;
 pub use services::*;
 
@@ -0,0 +1,144 @@
+//! # Presence Contracts
+//!
+//! Shared typing and online-status store used by both fechatter_server (HTTP writes)
+//! and notify_server (connection lifecycle). Both sides must agree on key names and
+//! TTLs, so they live here rather than in either server.
+//!
+//! ## Key Scheme
+//!
+//! - `fechatter:typing:{chat_id}`: hash of `user_id -> TypingEntry` JSON, key expires with its
+//!   newest entry
+//! - `fechatter:presence:{user_id}`: `PresenceRecord` JSON, expires after `offline_after`
+
+use crate::error::CoreError;
+use crate::models::{ChatId, UserId};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Typing indicator lifetime; clients refresh every 3 seconds
+pub const TYPING_TTL_SECS: u64 = 10;
+
+/// Online users without a heartbeat for this long are reported as away
+pub const AWAY_AFTER_SECS: u64 = 120;
+
+/// Presence records expire (user reported offline) after this long without a heartbeat
+pub const OFFLINE_AFTER_SECS: u64 = 300;
+
+/// Redis key holding the typing users of a chat
+pub fn typing_key(chat_id: ChatId) -> String {
+  format!("fechatter:typing:{}", chat_id.0)
+}
+
+/// Redis key holding a user's presence record
+pub fn presence_key(user_id: UserId) -> String {
+  format!("fechatter:presence:{}", user_id.0)
+}
+
+/// User presence as seen by other members
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PresenceStatus {
+  Online,
+  Away,
+  Offline,
+}
+
+impl PresenceStatus {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      PresenceStatus::Online => "online",
+      PresenceStatus::Away => "away",
+      PresenceStatus::Offline => "offline",
+    }
+  }
+}
+
+impl std::str::FromStr for PresenceStatus {
+  type Err = CoreError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "online" => Ok(PresenceStatus::Online),
+      "away" => Ok(PresenceStatus::Away),
+      "offline" => Ok(PresenceStatus::Offline),
+      other => Err(CoreError::Validation(format!(
+        "Invalid presence status '{}', expected one of: online, away, offline",
+        other
+      ))),
+    }
+  }
+}
+
+/// One user typing in a chat
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypingEntry {
+  pub user_id: UserId,
+  pub user_name: String,
+  pub started_at: DateTime<Utc>,
+  pub expires_at: DateTime<Utc>,
+}
+
+/// Stored presence value; `status` is what the user last reported
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresenceRecord {
+  pub status: PresenceStatus,
+  pub last_seen: DateTime<Utc>,
+}
+
+impl PresenceRecord {
+  /// Effective status at `now`, demoting stale online users to away
+  pub fn status_at(&self, now: DateTime<Utc>, ttl: &PresenceTtl) -> PresenceStatus {
+    let idle = (now - self.last_seen).to_std().unwrap_or_default();
+    match self.status {
+      _ if idle >= ttl.offline_after => PresenceStatus::Offline,
+      PresenceStatus::Online if idle >= ttl.away_after => PresenceStatus::Away,
+      status => status,
+    }
+  }
+}
+
+/// Lifetimes shared by every `PresenceStore` implementation
+#[derive(Debug, Clone)]
+pub struct PresenceTtl {
+  pub typing: Duration,
+  pub away_after: Duration,
+  pub offline_after: Duration,
+}
+
+impl Default for PresenceTtl {
+  fn default() -> Self {
+    Self {
+      typing: Duration::from_secs(TYPING_TTL_SECS),
+      away_after: Duration::from_secs(AWAY_AFTER_SECS),
+      offline_after: Duration::from_secs(OFFLINE_AFTER_SECS),
+    }
+  }
+}
+
+/// Presence Store Interface - Pluggable Component
+///
+/// Single source of truth for ephemeral typing and online state (Redis in production, memory in tests)
+#[async_trait]
+pub trait PresenceStore: Send + Sync {
+  /// Mark user as typing in chat (refreshes the TTL)
+  async fn set_typing(
+    &self,
+    chat_id: ChatId,
+    user_id: UserId,
+    user_name: &str,
+  ) -> Result<(), CoreError>;
+
+  /// Remove user's typing indicator
+  async fn clear_typing(&self, chat_id: ChatId, user_id: UserId) -> Result<(), CoreError>;
+
+  /// Users currently typing in chat (expired entries excluded)
+  async fn get_typing(&self, chat_id: ChatId) -> Result<Vec<TypingEntry>, CoreError>;
+
+  /// Record a presence heartbeat with the reported status; `Offline` removes the record
+  async fn set_online(&self, user_id: UserId, status: PresenceStatus) -> Result<(), CoreError>;
+
+  /// Effective presence status (missing or expired records are offline)
+  async fn get_status(&self, user_id: UserId) -> Result<PresenceStatus, CoreError>;
+}
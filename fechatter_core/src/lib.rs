@@ -7,8 +7,8 @@ pub mod utils;
 
 // Re-export core types and traits
 pub use contracts::{
-  AIService, AuthContext, CacheService, ChatMessage, Document, EventService, SearchQuery,
-  SearchResult, SearchService, Sentiment,
+  AIService, AuthContext, CacheService, ChatMessage, Document, EventService, PresenceStatus,
+  PresenceStore, SearchQuery, SearchResult, SearchService, Sentiment,
 };
 pub use error::{CoreError, ErrorMapper};
 pub use middlewares::{
@@ -5,6 +5,7 @@ pub mod query_token_auth;
 pub mod request_id;
 pub mod server_time;
 pub mod token_refresh;
+pub mod trace_context;
 
 use axum::{middleware::from_fn, Router};
 
@@ -26,6 +27,9 @@ pub use self::custom_builder::*;
 pub use self::query_token_auth::verify_query_token_middleware;
 pub use self::request_id::request_id_middleware;
 pub use self::server_time::ServerTimeLayer;
+pub use self::trace_context::{
+  trace_context_middleware, RequestTrace, TraceContext, TRACEPARENT_HEADER,
+};
 
 pub const REQUEST_ID_HEADER: &str = "x-request-id";
 pub const SERVER_TIME_HEADER: &str = "x-server-time";
@@ -0,0 +1,262 @@
+use axum::{extract::Request, middleware::Next, response::Response};
+use std::future::Future;
+use tracing::{field, info_span, Instrument};
+use uuid::Uuid;
+
+use super::REQUEST_ID_HEADER;
+
+/// W3C Trace Context header
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Sampled flag in `trace-flags`
+const FLAG_SAMPLED: u8 = 0x01;
+
+/**
+ * TraceContext
+ *
+ * The parts of a W3C `traceparent` header (`version-trace_id-parent_id-flags`).
+ * Ids are kept as the lowercase hex strings they travel as, so they can be logged and
+ * forwarded without re-encoding.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+  pub trace_id: String,
+  pub span_id: String,
+  pub flags: u8,
+}
+
+impl TraceContext {
+  /// Parse a `traceparent` value; `None` if it is malformed or uses the invalid all-zero ids
+  pub fn parse(header: &str) -> Option<Self> {
+    let mut parts = header.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let span_id = parts.next()?;
+    let flags = parts.next()?;
+
+    // Version 00 has exactly four fields; later versions may append more
+    let extra_fields = parts.next().is_some();
+    if !is_lower_hex(version, 2) || version == "ff" || (version == "00" && extra_fields) {
+      return None;
+    }
+    if !is_lower_hex(trace_id, 32) || trace_id.bytes().all(|b| b == b'0') {
+      return None;
+    }
+    if !is_lower_hex(span_id, 16) || span_id.bytes().all(|b| b == b'0') {
+      return None;
+    }
+    if !is_lower_hex(flags, 2) {
+      return None;
+    }
+
+    Some(Self {
+      trace_id: trace_id.to_string(),
+      span_id: span_id.to_string(),
+      flags: u8::from_str_radix(flags, 16).ok()?,
+    })
+  }
+
+  /// Start a new sampled trace
+  pub fn generate() -> Self {
+    let span_id = Uuid::new_v4().simple().to_string()[..16].to_string();
+
+    Self {
+      trace_id: Uuid::new_v4().simple().to_string(),
+      span_id,
+      flags: FLAG_SAMPLED,
+    }
+  }
+
+  pub fn sampled(&self) -> bool {
+    self.flags & FLAG_SAMPLED != 0
+  }
+
+  /// Encode as a version 00 `traceparent` value
+  pub fn to_header(&self) -> String {
+    format!("00-{}-{}-{:02x}", self.trace_id, self.span_id, self.flags)
+  }
+}
+
+/**
+ * RequestTrace
+ *
+ * The propagated `x-request-id` and `traceparent` of the request being handled, readable
+ * anywhere inside it without threading them through every call, e.g. to stamp the analytics
+ * events a handler publishes. Work spawned off the request does not inherit it.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestTrace {
+  pub request_id: Option<String>,
+  pub traceparent: Option<String>,
+}
+
+tokio::task_local! {
+  static REQUEST_TRACE: RequestTrace;
+}
+
+impl RequestTrace {
+  /// The trace of the request this task is handling, if any
+  pub fn current() -> Option<Self> {
+    REQUEST_TRACE.try_with(Clone::clone).ok()
+  }
+
+  /// Run `future` with this as the current request trace
+  pub async fn scope<F: Future>(self, future: F) -> F::Output {
+    REQUEST_TRACE.scope(self, future).await
+  }
+}
+
+fn is_lower_hex(value: &str, len: usize) -> bool {
+  value.len() == len
+    && value
+      .bytes()
+      .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/**
+ * Trace Context Middleware
+ *
+ * Reads the incoming `traceparent` header and runs the request inside a span carrying its
+ * trace id and parent span id, so server logs join the trace started at the gateway.
+ * The parsed `TraceContext` is also added to the request extensions for handlers, and the
+ * request runs with a `RequestTrace` holding the incoming request id and trace context.
+ */
+pub async fn trace_context_middleware(mut req: Request, next: Next) -> Response {
+  let context = req
+    .headers()
+    .get(TRACEPARENT_HEADER)
+    .and_then(|h| h.to_str().ok())
+    .and_then(TraceContext::parse);
+
+  let span = info_span!(
+    "request",
+    method = %req.method(),
+    path = %req.uri().path(),
+    trace_id = field::Empty,
+    parent_span_id = field::Empty,
+  );
+  let trace = RequestTrace {
+    request_id: req
+      .headers()
+      .get(REQUEST_ID_HEADER)
+      .and_then(|h| h.to_str().ok())
+      .map(str::to_string)
+      .filter(|id| !id.is_empty()),
+    traceparent: context.as_ref().map(TraceContext::to_header),
+  };
+  if let Some(context) = context {
+    span.record("trace_id", context.trace_id.as_str());
+    span.record("parent_span_id", context.span_id.as_str());
+    req.extensions_mut().insert(context);
+  }
+
+  trace.scope(next.run(req)).instrument(span).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use axum::{
+    body::Body,
+    extract::Extension,
+    http::{Request, StatusCode},
+    middleware::from_fn,
+    routing::get,
+    Router,
+  };
+  use tower::ServiceExt;
+
+  const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+  #[test]
+  fn test_parse_valid_traceparent() {
+    let context = TraceContext::parse(TRACEPARENT).unwrap();
+    assert_eq!(context.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(context.span_id, "00f067aa0ba902b7");
+    assert!(context.sampled());
+    assert_eq!(context.to_header(), TRACEPARENT);
+  }
+
+  #[test]
+  fn test_parse_rejects_malformed_traceparent() {
+    for header in [
+      "",
+      "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+      "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+      "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+      "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+      "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+      "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+    ] {
+      assert_eq!(TraceContext::parse(header), None, "{header:?}");
+    }
+  }
+
+  #[test]
+  fn test_generated_context_round_trips() {
+    let context = TraceContext::generate();
+    assert_eq!(TraceContext::parse(&context.to_header()), Some(context));
+  }
+
+  #[tokio::test]
+  async fn test_middleware_exposes_incoming_trace_context() {
+    async fn handler(context: Option<Extension<TraceContext>>) -> String {
+      context
+        .map(|Extension(context)| context.trace_id)
+        .unwrap_or_default()
+    }
+
+    let app = Router::new()
+      .route("/", get(handler))
+      .layer(from_fn(trace_context_middleware));
+
+    let request = Request::builder()
+      .uri("/")
+      .header(TRACEPARENT_HEADER, TRACEPARENT)
+      .body(Body::empty())
+      .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+      .await
+      .unwrap();
+    assert_eq!(&body[..], b"4bf92f3577b34da6a3ce929d0e0e4736");
+
+    let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+      .await
+      .unwrap();
+    assert!(body.is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_middleware_scopes_the_request_trace() {
+    async fn handler() -> String {
+      let trace = RequestTrace::current().unwrap();
+      format!(
+        "{}|{}",
+        trace.request_id.unwrap_or_default(),
+        trace.traceparent.unwrap_or_default()
+      )
+    }
+
+    let app = Router::new()
+      .route("/", get(handler))
+      .layer(from_fn(trace_context_middleware));
+
+    let request = Request::builder()
+      .uri("/")
+      .header(REQUEST_ID_HEADER, "req-42")
+      .header(TRACEPARENT_HEADER, TRACEPARENT)
+      .body(Body::empty())
+      .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+      .await
+      .unwrap();
+    assert_eq!(&body[..], format!("req-42|{TRACEPARENT}").as_bytes());
+
+    assert_eq!(RequestTrace::current(), None);
+  }
+}
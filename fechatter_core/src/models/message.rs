@@ -22,8 +22,15 @@ pub struct CreateMessage {
   #[serde(default = "default_uuid")]
   #[schema(value_type = String, format = "uuid", example = "01834abd-8c37-7d82-9206-54b2f6b4f7c4")]
   pub idempotency_key: Option<Uuid>,
+  /// Client-side id of the optimistic local copy, returned with the stored message
+  #[serde(default)]
+  #[schema(example = "local-1718000000000-1")]
+  pub client_message_id: Option<String>,
 }
 
+/// Longest accepted `client_message_id`
+pub const MAX_CLIENT_MESSAGE_ID_LEN: usize = 64;
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ListMessages {
   #[serde(default)]
@@ -58,6 +65,8 @@ pub struct MessageView {
   pub is_edited: bool,
   pub sequence_number: Option<i64>,
   pub idempotency_key: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub client_message_id: Option<String>,
 }
 
 impl From<Message> for MessageView {
@@ -75,6 +84,7 @@ impl From<Message> for MessageView {
       is_edited: false,      // TODO: Add to core Message if needed
       sequence_number: None, // TODO: Add to core Message if needed
       idempotency_key: message.idempotency_key.map(|uuid| uuid.to_string()),
+      client_message_id: message.client_message_id,
     }
   }
 }
@@ -85,6 +95,8 @@ pub struct SendMessageInput {
   pub content: String,
   pub files: Option<Vec<String>>,
   pub idempotency_key: Option<Uuid>,
+  #[serde(default)]
+  pub client_message_id: Option<String>,
   pub reply_to: Option<MessageId>,
   pub mentions: Option<Vec<UserId>>,
 }
@@ -95,6 +107,7 @@ impl From<SendMessageInput> for CreateMessage {
       content: input.content,
       files: input.files,
       idempotency_key: input.idempotency_key,
+      client_message_id: input.client_message_id,
     }
   }
 }
@@ -151,6 +164,22 @@ pub struct StreamMessage {
   pub content: String,
   pub files: Vec<String>,
   pub timestamp: i64,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub client_message_id: Option<String>,
+}
+
+impl From<&Message> for StreamMessage {
+  fn from(message: &Message) -> Self {
+    Self {
+      id: message.id.to_string(),
+      chat_id: i64::from(message.chat_id),
+      sender_id: i64::from(message.sender_id),
+      content: message.content.clone(),
+      files: message.files.clone().unwrap_or_default(),
+      timestamp: message.created_at.timestamp(),
+      client_message_id: message.client_message_id.clone(),
+    }
+  }
 }
 
 // Search Models
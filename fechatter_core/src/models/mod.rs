@@ -180,7 +180,7 @@ impl std::str::FromStr for ChatType {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Chat {
   pub id: ChatId,
   pub workspace_id: WorkspaceId,
@@ -207,6 +207,10 @@ pub struct Message {
   #[sqlx(default)] // idempotency_key may be NULL, especially for older records
   #[schema(value_type = Option<String>, format = "uuid", example = "01834abd-8c37-7d82-9206-54b2f6b4f7c4")]
   pub idempotency_key: Option<uuid::Uuid>,
+  /// Sender-chosen id echoed back so clients can reconcile optimistic local messages
+  #[sqlx(default)]
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub client_message_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -281,6 +281,17 @@ pub trait MessageVectorRepository: Send + Sync {
     timestamp: DateTime<Utc>,
   ) -> Result<(), CoreError>;
 
+  /// Store a precomputed embedding for a message
+  async fn store_message_embedding(
+    &self,
+    message_id: MessageId,
+    chat_id: ChatId,
+    sender_id: UserId,
+    content: &str,
+    embedding: &[f32],
+    timestamp: DateTime<Utc>,
+  ) -> Result<(), CoreError>;
+
   /// Remove message from index
   async fn delete_message(&self, message_id: MessageId) -> Result<(), CoreError>;
 
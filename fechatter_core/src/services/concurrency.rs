@@ -0,0 +1,220 @@
+//! # Concurrency Limiting
+//!
+//! **Responsibility**: Cap how many calls to a rate-limited upstream, such as the AI provider,
+//! run at once across a process; shared by fechatter_server and bot_server
+//! **Saturation**: Past the cap, calls either wait for a slot up to a timeout or are refused
+//! straight away, per `SaturationPolicy`
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// What happens to a call made while every slot is taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaturationPolicy {
+  /// Wait for a slot, up to the queue timeout
+  #[default]
+  Queue,
+  /// Refuse the call immediately
+  Reject,
+}
+
+/// Limiter settings, as read from a service's config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyLimitConfig {
+  #[serde(default = "default_max_in_flight")]
+  pub max_in_flight: usize,
+  #[serde(default)]
+  pub on_saturated: SaturationPolicy,
+  /// Longest a queued call waits for a slot before it is refused
+  #[serde(default = "default_queue_timeout_ms")]
+  pub queue_timeout_ms: u64,
+}
+
+fn default_max_in_flight() -> usize {
+  8
+}
+
+fn default_queue_timeout_ms() -> u64 {
+  10_000
+}
+
+impl Default for ConcurrencyLimitConfig {
+  fn default() -> Self {
+    Self {
+      max_in_flight: default_max_in_flight(),
+      on_saturated: SaturationPolicy::default(),
+      queue_timeout_ms: default_queue_timeout_ms(),
+    }
+  }
+}
+
+/// A call refused because every slot stayed taken
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("All {max_in_flight} request slots are in use, try again shortly")]
+pub struct Saturated {
+  pub max_in_flight: usize,
+}
+
+type InFlightObserver = Arc<dyn Fn(usize) + Send + Sync>;
+
+/// Process-wide cap on concurrent calls; clones share the same slots
+#[derive(Clone)]
+pub struct ConcurrencyLimiter {
+  slots: Arc<Semaphore>,
+  max_in_flight: usize,
+  policy: SaturationPolicy,
+  queue_timeout: Duration,
+  observer: Option<InFlightObserver>,
+}
+
+impl ConcurrencyLimiter {
+  pub fn new(max_in_flight: usize, policy: SaturationPolicy, queue_timeout: Duration) -> Self {
+    let max_in_flight = max_in_flight.max(1);
+    Self {
+      slots: Arc::new(Semaphore::new(max_in_flight)),
+      max_in_flight,
+      policy,
+      queue_timeout,
+      observer: None,
+    }
+  }
+
+  pub fn from_config(config: &ConcurrencyLimitConfig) -> Self {
+    Self::new(
+      config.max_in_flight,
+      config.on_saturated,
+      Duration::from_millis(config.queue_timeout_ms),
+    )
+  }
+
+  /// Report the in-flight count whenever it changes, e.g. to a metrics gauge
+  pub fn with_observer(mut self, observer: impl Fn(usize) + Send + Sync + 'static) -> Self {
+    self.observer = Some(Arc::new(observer));
+    self
+  }
+
+  pub fn max_in_flight(&self) -> usize {
+    self.max_in_flight
+  }
+
+  /// Calls currently holding a slot
+  pub fn in_flight(&self) -> usize {
+    self.max_in_flight - self.slots.available_permits()
+  }
+
+  /// Take a slot, held until the permit is dropped
+  pub async fn acquire(&self) -> Result<ConcurrencyPermit, Saturated> {
+    let permit = match self.policy {
+      SaturationPolicy::Reject => self.slots.clone().try_acquire_owned().ok(),
+      SaturationPolicy::Queue => {
+        tokio::time::timeout(self.queue_timeout, self.slots.clone().acquire_owned())
+          .await
+          .ok()
+          .and_then(Result::ok)
+      }
+    };
+    let permit = permit.ok_or(Saturated {
+      max_in_flight: self.max_in_flight,
+    })?;
+    self.report();
+
+    Ok(ConcurrencyPermit {
+      permit: Some(permit),
+      limiter: self.clone(),
+    })
+  }
+
+  /// Run `call` in a slot
+  pub async fn run<F: Future>(&self, call: F) -> Result<F::Output, Saturated> {
+    let _permit = self.acquire().await?;
+    Ok(call.await)
+  }
+
+  fn report(&self) {
+    if let Some(observer) = &self.observer {
+      observer(self.in_flight());
+    }
+  }
+}
+
+/// A held slot of a `ConcurrencyLimiter`
+pub struct ConcurrencyPermit {
+  permit: Option<OwnedSemaphorePermit>,
+  limiter: ConcurrencyLimiter,
+}
+
+impl Drop for ConcurrencyPermit {
+  fn drop(&mut self) {
+    drop(self.permit.take());
+    self.limiter.report();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  #[tokio::test]
+  async fn burst_should_never_exceed_the_cap() {
+    let limiter = ConcurrencyLimiter::new(4, SaturationPolicy::Queue, Duration::from_secs(5));
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let calls = (0..40).map(|_| {
+      let (limiter, running, peak) = (limiter.clone(), running.clone(), peak.clone());
+      tokio::spawn(async move {
+        limiter
+          .run(async {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            running.fetch_sub(1, Ordering::SeqCst);
+          })
+          .await
+      })
+    });
+    for call in futures::future::join_all(calls).await {
+      assert!(call.unwrap().is_ok(), "queued calls all get a slot");
+    }
+
+    assert_eq!(peak.load(Ordering::SeqCst), 4);
+    assert_eq!(limiter.in_flight(), 0);
+  }
+
+  #[tokio::test]
+  async fn saturated_limiter_should_refuse_or_time_out_per_policy() {
+    let reject = ConcurrencyLimiter::new(1, SaturationPolicy::Reject, Duration::from_secs(5));
+    let held = reject.acquire().await.unwrap();
+    assert_eq!(
+      reject.acquire().await.err(),
+      Some(Saturated { max_in_flight: 1 })
+    );
+    drop(held);
+    assert!(reject.acquire().await.is_ok());
+
+    let queue = ConcurrencyLimiter::new(1, SaturationPolicy::Queue, Duration::from_millis(20));
+    let _held = queue.acquire().await.unwrap();
+    assert!(queue.acquire().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn observer_should_follow_the_in_flight_count() {
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = seen.clone();
+    let limiter = ConcurrencyLimiter::new(2, SaturationPolicy::Queue, Duration::from_secs(1))
+      .with_observer(move |count| recorded.lock().unwrap().push(count));
+
+    let first = limiter.acquire().await.unwrap();
+    let second = limiter.acquire().await.unwrap();
+    drop(first);
+    drop(second);
+
+    assert_eq!(*seen.lock().unwrap(), vec![1, 2, 1, 0]);
+  }
+}
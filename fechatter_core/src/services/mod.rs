@@ -1,5 +1,8 @@
 pub mod auth_service;
+pub mod concurrency;
 pub mod mock;
+pub mod presence;
+pub mod rate_limit;
 pub mod retry;
 pub mod service_provider;
 pub mod workspace_service;
@@ -0,0 +1,337 @@
+//! # Presence Store Implementations
+//!
+//! - `InMemoryPresenceStore`: single-process fallback and test double
+//! - `RedisPresenceStore` (feature `redis`): shared store used by fechatter_server and notify_server
+
+use crate::contracts::presence::{
+  PresenceRecord, PresenceStatus, PresenceStore, PresenceTtl, TypingEntry,
+};
+use crate::error::CoreError;
+use crate::models::{ChatId, UserId};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+fn typing_entry(user_id: UserId, user_name: &str, ttl: &PresenceTtl) -> TypingEntry {
+  let now = Utc::now();
+  TypingEntry {
+    user_id,
+    user_name: user_name.to_string(),
+    started_at: now,
+    expires_at: now + chrono::Duration::from_std(ttl.typing).unwrap_or_default(),
+  }
+}
+
+/// Process-local presence store
+#[derive(Default)]
+pub struct InMemoryPresenceStore {
+  typing: RwLock<HashMap<ChatId, HashMap<UserId, TypingEntry>>>,
+  presence: RwLock<HashMap<UserId, PresenceRecord>>,
+  ttl: PresenceTtl,
+}
+
+impl InMemoryPresenceStore {
+  pub fn new(ttl: PresenceTtl) -> Self {
+    Self {
+      ttl,
+      ..Default::default()
+    }
+  }
+}
+
+#[async_trait]
+impl PresenceStore for InMemoryPresenceStore {
+  async fn set_typing(
+    &self,
+    chat_id: ChatId,
+    user_id: UserId,
+    user_name: &str,
+  ) -> Result<(), CoreError> {
+    let entry = typing_entry(user_id, user_name, &self.ttl);
+    self
+      .typing
+      .write()
+      .await
+      .entry(chat_id)
+      .or_default()
+      .insert(user_id, entry);
+    Ok(())
+  }
+
+  async fn clear_typing(&self, chat_id: ChatId, user_id: UserId) -> Result<(), CoreError> {
+    let mut typing = self.typing.write().await;
+    if let Some(chat_users) = typing.get_mut(&chat_id) {
+      chat_users.remove(&user_id);
+      if chat_users.is_empty() {
+        typing.remove(&chat_id);
+      }
+    }
+    Ok(())
+  }
+
+  async fn get_typing(&self, chat_id: ChatId) -> Result<Vec<TypingEntry>, CoreError> {
+    let mut typing = self.typing.write().await;
+    let now = Utc::now();
+
+    let Some(chat_users) = typing.get_mut(&chat_id) else {
+      return Ok(Vec::new());
+    };
+    chat_users.retain(|_, entry| entry.expires_at > now);
+    if chat_users.is_empty() {
+      typing.remove(&chat_id);
+      return Ok(Vec::new());
+    }
+
+    Ok(chat_users.values().cloned().collect())
+  }
+
+  async fn set_online(&self, user_id: UserId, status: PresenceStatus) -> Result<(), CoreError> {
+    let mut presence = self.presence.write().await;
+    if status == PresenceStatus::Offline {
+      presence.remove(&user_id);
+    } else {
+      presence.insert(
+        user_id,
+        PresenceRecord {
+          status,
+          last_seen: Utc::now(),
+        },
+      );
+    }
+    Ok(())
+  }
+
+  async fn get_status(&self, user_id: UserId) -> Result<PresenceStatus, CoreError> {
+    let presence = self.presence.read().await;
+    Ok(
+      presence
+        .get(&user_id)
+        .map(|record| record.status_at(Utc::now(), &self.ttl))
+        .unwrap_or(PresenceStatus::Offline),
+    )
+  }
+}
+
+#[cfg(feature = "redis")]
+pub use self::redis_store::RedisPresenceStore;
+
+#[cfg(feature = "redis")]
+mod redis_store {
+  use super::*;
+  use crate::contracts::presence::{presence_key, typing_key};
+  use redis::{aio::MultiplexedConnection, AsyncCommands};
+
+  fn redis_error(e: redis::RedisError) -> CoreError {
+    CoreError::Internal(format!("Presence store error: {}", e))
+  }
+
+  fn json_error(e: serde_json::Error) -> CoreError {
+    CoreError::Internal(format!("Presence store serialization error: {}", e))
+  }
+
+  /// Redis-backed presence store; key names come from `contracts::presence`
+  #[derive(Clone)]
+  pub struct RedisPresenceStore {
+    conn: MultiplexedConnection,
+    ttl: PresenceTtl,
+  }
+
+  impl RedisPresenceStore {
+    pub async fn new(redis_url: &str, ttl: PresenceTtl) -> Result<Self, CoreError> {
+      let client = redis::Client::open(redis_url).map_err(redis_error)?;
+      let conn = client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(redis_error)?;
+      Ok(Self { conn, ttl })
+    }
+  }
+
+  #[async_trait]
+  impl PresenceStore for RedisPresenceStore {
+    async fn set_typing(
+      &self,
+      chat_id: ChatId,
+      user_id: UserId,
+      user_name: &str,
+    ) -> Result<(), CoreError> {
+      let entry = typing_entry(user_id, user_name, &self.ttl);
+      let value = serde_json::to_string(&entry).map_err(json_error)?;
+      let key = typing_key(chat_id);
+
+      // Hash fields have no TTL of their own: the key lives as long as its newest entry
+      let mut conn = self.conn.clone();
+      redis::pipe()
+        .atomic()
+        .hset(&key, user_id.0, value)
+        .ignore()
+        .expire(&key, self.ttl.typing.as_secs().max(1) as i64)
+        .ignore()
+        .query_async::<_, ()>(&mut conn)
+        .await
+        .map_err(redis_error)
+    }
+
+    async fn clear_typing(&self, chat_id: ChatId, user_id: UserId) -> Result<(), CoreError> {
+      let mut conn = self.conn.clone();
+      conn
+        .hdel::<_, _, ()>(typing_key(chat_id), user_id.0)
+        .await
+        .map_err(redis_error)
+    }
+
+    async fn get_typing(&self, chat_id: ChatId) -> Result<Vec<TypingEntry>, CoreError> {
+      let key = typing_key(chat_id);
+      let mut conn = self.conn.clone();
+      let fields: HashMap<String, String> = conn.hgetall(&key).await.map_err(redis_error)?;
+
+      let now = Utc::now();
+      let mut active = Vec::with_capacity(fields.len());
+      let mut expired = Vec::new();
+      for (field, value) in fields {
+        match serde_json::from_str::<TypingEntry>(&value) {
+          Ok(entry) if entry.expires_at > now => active.push(entry),
+          _ => expired.push(field),
+        }
+      }
+
+      if !expired.is_empty() {
+        conn
+          .hdel::<_, _, ()>(&key, expired)
+          .await
+          .map_err(redis_error)?;
+      }
+
+      Ok(active)
+    }
+
+    async fn set_online(&self, user_id: UserId, status: PresenceStatus) -> Result<(), CoreError> {
+      let key = presence_key(user_id);
+      let mut conn = self.conn.clone();
+
+      if status == PresenceStatus::Offline {
+        return conn.del::<_, ()>(key).await.map_err(redis_error);
+      }
+
+      let record = PresenceRecord {
+        status,
+        last_seen: Utc::now(),
+      };
+      let value = serde_json::to_string(&record).map_err(json_error)?;
+      conn
+        .set_ex::<_, _, ()>(key, value, self.ttl.offline_after.as_secs().max(1))
+        .await
+        .map_err(redis_error)
+    }
+
+    async fn get_status(&self, user_id: UserId) -> Result<PresenceStatus, CoreError> {
+      let mut conn = self.conn.clone();
+      let value: Option<String> = conn.get(presence_key(user_id)).await.map_err(redis_error)?;
+
+      Ok(
+        value
+          .and_then(|value| serde_json::from_str::<PresenceRecord>(&value).ok())
+          .map(|record| record.status_at(Utc::now(), &self.ttl))
+          .unwrap_or(PresenceStatus::Offline),
+      )
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::time::Duration;
+
+  fn short_ttl() -> PresenceTtl {
+    PresenceTtl {
+      typing: Duration::from_millis(50),
+      away_after: Duration::from_millis(50),
+      offline_after: Duration::from_millis(150),
+    }
+  }
+
+  #[tokio::test]
+  async fn typing_should_expire_after_ttl() {
+    let store = InMemoryPresenceStore::new(short_ttl());
+    let chat = ChatId(1);
+
+    store.set_typing(chat, UserId(1), "alice").await.unwrap();
+    store.set_typing(chat, UserId(2), "bob").await.unwrap();
+    store.clear_typing(chat, UserId(2)).await.unwrap();
+
+    let typing = store.get_typing(chat).await.unwrap();
+    assert_eq!(typing.len(), 1);
+    assert_eq!(typing[0].user_id, UserId(1));
+    assert_eq!(typing[0].user_name, "alice");
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert!(store.get_typing(chat).await.unwrap().is_empty());
+  }
+
+  #[tokio::test]
+  async fn presence_should_move_from_online_to_away_to_offline() {
+    let store = InMemoryPresenceStore::new(short_ttl());
+    let user = UserId(7);
+
+    assert_eq!(
+      store.get_status(user).await.unwrap(),
+      PresenceStatus::Offline
+    );
+
+    store
+      .set_online(user, PresenceStatus::Online)
+      .await
+      .unwrap();
+    assert_eq!(
+      store.get_status(user).await.unwrap(),
+      PresenceStatus::Online
+    );
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(store.get_status(user).await.unwrap(), PresenceStatus::Away);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(
+      store.get_status(user).await.unwrap(),
+      PresenceStatus::Offline
+    );
+  }
+
+  #[tokio::test]
+  async fn explicit_status_changes_should_apply_immediately() {
+    let store = InMemoryPresenceStore::new(PresenceTtl::default());
+    let user = UserId(7);
+
+    store.set_online(user, PresenceStatus::Away).await.unwrap();
+    assert_eq!(store.get_status(user).await.unwrap(), PresenceStatus::Away);
+
+    store
+      .set_online(user, PresenceStatus::Online)
+      .await
+      .unwrap();
+    assert_eq!(
+      store.get_status(user).await.unwrap(),
+      PresenceStatus::Online
+    );
+
+    store
+      .set_online(user, PresenceStatus::Offline)
+      .await
+      .unwrap();
+    assert_eq!(
+      store.get_status(user).await.unwrap(),
+      PresenceStatus::Offline
+    );
+  }
+
+  #[test]
+  fn status_should_parse_from_wire_names() {
+    assert_eq!(
+      "away".parse::<PresenceStatus>().unwrap(),
+      PresenceStatus::Away
+    );
+    assert!("busy".parse::<PresenceStatus>().is_err());
+  }
+}
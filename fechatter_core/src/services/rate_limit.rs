@@ -0,0 +1,268 @@
+//! # Rate Limiting
+//!
+//! **Responsibility**: Fixed-window throttling shared by fechatter_server and notify_server
+//! **Stores**: `InMemoryRateLimitStore` here; fechatter_server adds a Redis store for limits
+//! shared across instances
+
+use async_trait::async_trait;
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::error::CoreError;
+
+/// Outcome of a rate limit check, used to build the response headers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitDecision {
+  pub allowed: bool,
+  pub limit: u32,
+  pub remaining: u32,
+  /// Time until the current window resets
+  pub reset_after: Duration,
+}
+
+impl RateLimitDecision {
+  /// Seconds a throttled client should wait, never less than one
+  pub fn retry_after_secs(&self) -> u64 {
+    let secs = self.reset_after.as_secs();
+    let secs = if self.reset_after.subsec_nanos() > 0 {
+      secs + 1
+    } else {
+      secs
+    };
+    secs.max(1)
+  }
+}
+
+/// Attach `X-RateLimit-*` headers, plus `Retry-After` when the request was throttled
+pub fn apply_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+  let reset = decision.retry_after_secs();
+  let mut insert = |name: &'static str, value: u64| {
+    headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+  };
+
+  insert("x-ratelimit-limit", decision.limit as u64);
+  insert("x-ratelimit-remaining", decision.remaining as u64);
+  insert("x-ratelimit-reset", reset);
+  if !decision.allowed {
+    insert("retry-after", reset);
+  }
+}
+
+/// Current state of one rate limit counter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RateLimitBucket {
+  pub key: String,
+  pub count: u64,
+  pub resets_in_seconds: u64,
+}
+
+/// Counter storage for fixed-window rate limiting
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+  /// Count one request for `key`, returning the window total and time until it resets
+  async fn hit(&self, key: &str, window: Duration) -> Result<(u64, Duration), CoreError>;
+
+  /// List the live counters whose key starts with `prefix`
+  async fn buckets(&self, prefix: &str) -> Result<Vec<RateLimitBucket>, CoreError>;
+
+  /// Delete the counters whose key starts with `prefix`, returning how many were removed
+  async fn reset(&self, prefix: &str) -> Result<u64, CoreError>;
+}
+
+/// Process-local store
+#[derive(Default)]
+pub struct InMemoryRateLimitStore {
+  windows: Mutex<HashMap<String, InMemoryWindow>>,
+}
+
+struct InMemoryWindow {
+  count: u64,
+  started: Instant,
+  length: Duration,
+}
+
+impl InMemoryWindow {
+  fn remaining(&self, now: Instant) -> Duration {
+    self.length.saturating_sub(now.duration_since(self.started))
+  }
+}
+
+impl InMemoryRateLimitStore {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn windows(&self) -> std::sync::MutexGuard<'_, HashMap<String, InMemoryWindow>> {
+    // A counter map is still usable after a panic elsewhere
+    self
+      .windows
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+  async fn hit(&self, key: &str, window: Duration) -> Result<(u64, Duration), CoreError> {
+    let now = Instant::now();
+    let mut windows = self.windows();
+    let entry = windows.entry(key.to_string()).or_insert(InMemoryWindow {
+      count: 0,
+      started: now,
+      length: window,
+    });
+
+    if entry.remaining(now).is_zero() {
+      *entry = InMemoryWindow {
+        count: 0,
+        started: now,
+        length: window,
+      };
+    }
+    entry.count += 1;
+
+    Ok((entry.count, entry.remaining(now)))
+  }
+
+  async fn buckets(&self, prefix: &str) -> Result<Vec<RateLimitBucket>, CoreError> {
+    let now = Instant::now();
+    Ok(
+      self
+        .windows()
+        .iter()
+        .filter(|(key, window)| key.starts_with(prefix) && !window.remaining(now).is_zero())
+        .map(|(key, window)| RateLimitBucket {
+          key: key.clone(),
+          count: window.count,
+          resets_in_seconds: window.remaining(now).as_secs(),
+        })
+        .collect(),
+    )
+  }
+
+  async fn reset(&self, prefix: &str) -> Result<u64, CoreError> {
+    let mut windows = self.windows();
+    let before = windows.len();
+    windows.retain(|key, _| !key.starts_with(prefix));
+    Ok((before - windows.len()) as u64)
+  }
+}
+
+/// Fixed-window rate limiter
+pub struct RateLimiter {
+  store: Arc<dyn RateLimitStore>,
+  max_requests: u32,
+  window: Duration,
+}
+
+impl RateLimiter {
+  pub fn new(store: Arc<dyn RateLimitStore>, max_requests: u32, window: Duration) -> Self {
+    Self {
+      store,
+      max_requests,
+      window,
+    }
+  }
+
+  /// Count a request and report whether it is within the limit
+  pub async fn check(&self, key: &str) -> Result<RateLimitDecision, CoreError> {
+    let (count, reset_after) = self.store.hit(key, self.window).await?;
+
+    Ok(RateLimitDecision {
+      allowed: count <= self.max_requests as u64,
+      limit: self.max_requests,
+      remaining: (self.max_requests as u64).saturating_sub(count) as u32,
+      reset_after,
+    })
+  }
+
+  /// Count a request, failing with the decision when over the limit.
+  /// Store failures are logged and the request is allowed (fail open).
+  pub async fn enforce(&self, key: &str) -> Result<(), RateLimitDecision> {
+    match self.check(key).await {
+      Ok(decision) if !decision.allowed => Err(decision),
+      Ok(_) => Ok(()),
+      Err(e) => {
+        warn!("WARNING: Rate limit check failed for {}: {}", key, e);
+        Ok(())
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn limiter(max_requests: u32) -> RateLimiter {
+    RateLimiter::new(
+      Arc::new(InMemoryRateLimitStore::new()),
+      max_requests,
+      Duration::from_secs(60),
+    )
+  }
+
+  #[tokio::test]
+  async fn limits_should_be_tracked_per_key() {
+    let limiter = limiter(1);
+
+    assert!(
+      limiter
+        .check("rate_limit:1:message_send")
+        .await
+        .unwrap()
+        .allowed
+    );
+    assert!(
+      limiter
+        .check("rate_limit:2:message_send")
+        .await
+        .unwrap()
+        .allowed
+    );
+    assert!(
+      !limiter
+        .check("rate_limit:1:message_send")
+        .await
+        .unwrap()
+        .allowed
+    );
+  }
+
+  #[tokio::test]
+  async fn over_limit_should_fail_with_headers_for_a_retry() {
+    let limiter = limiter(2);
+    let key = "rate_limit:42:message_send";
+
+    assert!(limiter.enforce(key).await.is_ok());
+    assert!(limiter.enforce(key).await.is_ok());
+    let decision = limiter.enforce(key).await.unwrap_err();
+
+    let mut headers = HeaderMap::new();
+    apply_rate_limit_headers(&mut headers, &decision);
+    let header = |name: &str| headers[name].to_str().unwrap().parse::<u64>().unwrap();
+    assert!((1..=60).contains(&header("retry-after")));
+    assert_eq!(header("x-ratelimit-remaining"), 0);
+  }
+
+  #[test]
+  fn retry_after_should_round_up_partial_seconds() {
+    let decision = RateLimitDecision {
+      allowed: false,
+      limit: 10,
+      remaining: 0,
+      reset_after: Duration::from_millis(1500),
+    };
+    assert_eq!(decision.retry_after_secs(), 2);
+
+    let decision = RateLimitDecision {
+      reset_after: Duration::ZERO,
+      ..decision
+    };
+    assert_eq!(decision.retry_after_secs(), 1);
+  }
+}
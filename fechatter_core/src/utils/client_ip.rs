@@ -0,0 +1,105 @@
+//! Client IP of a request that may have come through proxies
+//!
+//! The TCP peer is the client unless it is a trusted proxy. Behind one, `X-Forwarded-For` is read
+//! from the right: each proxy appends the address that connected to it, so the nearest hop that
+//! isn't a trusted proxy is the client, and everything left of it was written by the client.
+
+use axum::http::HeaderMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Proxies trusted when a service configures none: the loopback addresses
+pub fn default_trusted_proxies() -> Vec<IpAddr> {
+  vec![
+    IpAddr::V4(Ipv4Addr::LOCALHOST),
+    IpAddr::V6(Ipv6Addr::LOCALHOST),
+  ]
+}
+
+/// Client IP for a request from `peer`, trusting forwarding headers only from `trusted_proxies`.
+/// Falls back to `X-Real-IP`, then the peer, when the headers name no client; `None` when the
+/// peer address is unknown
+pub fn client_ip(
+  peer: Option<IpAddr>,
+  headers: &HeaderMap,
+  trusted_proxies: &[IpAddr],
+) -> Option<IpAddr> {
+  let peer = peer?;
+  if !trusted_proxies.contains(&peer) {
+    return Some(peer);
+  }
+
+  let forwarded_for = headers
+    .get("x-forwarded-for")
+    .and_then(|h| h.to_str().ok())
+    .and_then(|value| forwarded_client(value, trusted_proxies));
+  let real_ip = || {
+    headers
+      .get("x-real-ip")
+      .and_then(|h| h.to_str().ok())
+      .and_then(|value| value.trim().parse().ok())
+  };
+
+  forwarded_for.or_else(real_ip).or(Some(peer))
+}
+
+/// The `X-Forwarded-For` hop, read from the right, that isn't one of `trusted_proxies`; the
+/// leftmost trusted hop when every hop is trusted
+fn forwarded_client(value: &str, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+  let mut nearest = None;
+  for hop in value.rsplit(',') {
+    let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+      break;
+    };
+    if !trusted_proxies.contains(&ip) {
+      return Some(ip);
+    }
+    nearest = Some(ip);
+  }
+  nearest
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ip(value: &str) -> IpAddr {
+    value.parse().unwrap()
+  }
+
+  fn forwarded_for(value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-forwarded-for", value.parse().unwrap());
+    headers
+  }
+
+  #[test]
+  fn hops_written_by_the_client_should_be_ignored() {
+    let trusted = [ip("10.0.0.2"), ip("10.0.0.3")];
+    let headers = forwarded_for("198.51.100.9, 203.0.113.7, 10.0.0.3");
+
+    assert_eq!(
+      client_ip(Some(ip("10.0.0.2")), &headers, &trusted),
+      Some(ip("203.0.113.7"))
+    );
+    // Straight from the client, the header is ignored altogether
+    assert_eq!(
+      client_ip(Some(ip("192.0.2.1")), &headers, &trusted),
+      Some(ip("192.0.2.1"))
+    );
+  }
+
+  #[test]
+  fn unusable_headers_should_fall_back_to_the_peer() {
+    let trusted = [ip("10.0.0.2")];
+
+    assert_eq!(
+      client_ip(Some(ip("10.0.0.2")), &HeaderMap::new(), &trusted),
+      Some(ip("10.0.0.2"))
+    );
+    assert_eq!(
+      client_ip(Some(ip("10.0.0.2")), &forwarded_for("not-an-ip"), &trusted),
+      Some(ip("10.0.0.2"))
+    );
+    assert_eq!(client_ip(None, &HeaderMap::new(), &trusted), None);
+  }
+}
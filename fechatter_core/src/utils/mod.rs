@@ -4,6 +4,24 @@ pub mod retry;
 // Test utilities
 pub mod mock;
 
+// Secret redaction for config summaries
+pub mod redact;
+
+// Startup migration version check
+pub mod schema;
+
+// SSRF guards for user-supplied URLs
+pub mod outbound;
+
+// Client IP behind trusted proxies
+pub mod client_ip;
+
 // Re-export utility classes
+pub use client_ip::{client_ip, default_trusted_proxies};
 pub use mock::*;
+pub use outbound::{
+  check_outbound_url, is_public_ip, is_safe_outbound_url, resolve_public, OutboundAllowlist,
+  OutboundUrlError,
+};
+pub use redact::{redact_optional_secret, redact_secret, redact_url, RedactedSummary, REDACTED};
 pub use retry::*;
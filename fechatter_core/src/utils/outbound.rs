@@ -0,0 +1,234 @@
+//! Guards for outbound requests to user-supplied URLs
+//!
+//! Webhooks, link previews and similar features fetch URLs that users control. Unchecked, those
+//! requests can reach internal services such as the cloud metadata endpoint at
+//! `169.254.169.254`. Hosts are resolved and every address must be publicly routable, unless the
+//! host is on the deployment's allowlist.
+
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use thiserror::Error;
+use url::{Host, Url};
+
+#[derive(Debug, Error)]
+pub enum OutboundUrlError {
+  #[error("invalid URL: {0}")]
+  InvalidUrl(String),
+
+  #[error("unsupported URL scheme: {0}")]
+  UnsupportedScheme(String),
+
+  #[error("{host} resolves to blocked address {ip}")]
+  BlockedAddress { host: String, ip: IpAddr },
+
+  #[error("failed to resolve {host}: {reason}")]
+  Resolution { host: String, reason: String },
+}
+
+/// Trusted hosts exempt from the address checks, e.g. an internal service a deployment
+/// integrates with on purpose. Entries match a host exactly, ignoring case, or any of its
+/// subdomains when written as `*.example.internal`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OutboundAllowlist(Vec<String>);
+
+impl OutboundAllowlist {
+  pub fn new<I, S>(hosts: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    Self(hosts.into_iter().map(Into::into).collect())
+  }
+
+  pub fn allows(&self, host: &str) -> bool {
+    let host = host
+      .trim_start_matches('[')
+      .trim_end_matches(']')
+      .to_ascii_lowercase();
+    self.0.iter().any(|entry| {
+      let entry = entry.to_ascii_lowercase();
+      match entry.strip_prefix("*.") {
+        Some(domain) => host
+          .strip_suffix(domain)
+          .is_some_and(|subdomain| subdomain.ends_with('.')),
+        None => entry == host,
+      }
+    })
+  }
+}
+
+/// Whether `ip` is publicly routable, i.e. not loopback, private, link-local (which includes the
+/// metadata service), carrier-grade NAT, multicast or otherwise reserved
+pub fn is_public_ip(ip: IpAddr) -> bool {
+  match ip {
+    IpAddr::V4(ip) => is_public_ipv4(ip),
+    IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+      Some(mapped) => is_public_ipv4(mapped),
+      None => is_public_ipv6(ip),
+    },
+  }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+  let [a, b, c, _] = ip.octets();
+  !(ip.is_unspecified()
+    || ip.is_loopback()
+    || ip.is_private()
+    || ip.is_link_local()
+    || ip.is_broadcast()
+    || ip.is_documentation()
+    || ip.is_multicast()
+    || a == 0
+    // Carrier-grade NAT
+    || (a == 100 && (64..128).contains(&b))
+    // IETF protocol assignments
+    || (a == 192 && b == 0 && c == 0)
+    // Benchmarking
+    || (a == 198 && (b == 18 || b == 19))
+    // Reserved
+    || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+  let [first, second, ..] = ip.segments();
+  !(ip.is_unspecified()
+    || ip.is_loopback()
+    || ip.is_multicast()
+    // Unique local
+    || (first & 0xfe00) == 0xfc00
+    // Link-local
+    || (first & 0xffc0) == 0xfe80
+    // Documentation
+    || (first == 0x2001 && second == 0x0db8))
+}
+
+/// Resolve `host`, failing if any of its addresses is internal and the host isn't allowlisted.
+/// HTTP clients call this when connecting as well, so a DNS answer that changes after a URL was
+/// checked still can't reach internal services
+pub async fn resolve_public(
+  host: &str,
+  port: u16,
+  allowlist: &OutboundAllowlist,
+) -> Result<Vec<SocketAddr>, OutboundUrlError> {
+  let resolution_error = |reason: String| OutboundUrlError::Resolution {
+    host: host.to_string(),
+    reason,
+  };
+  let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+    .await
+    .map_err(|e| resolution_error(e.to_string()))?
+    .collect();
+
+  if !allowlist.allows(host) {
+    if let Some(blocked) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+      return Err(OutboundUrlError::BlockedAddress {
+        host: host.to_string(),
+        ip: blocked.ip(),
+      });
+    }
+  }
+  if addrs.is_empty() {
+    return Err(resolution_error("no addresses".to_string()));
+  }
+
+  Ok(addrs)
+}
+
+/// Parse `url` and check it may be fetched: http(s) only, and every address of its host must be
+/// public unless the host is allowlisted
+pub async fn check_outbound_url(
+  url: &str,
+  allowlist: &OutboundAllowlist,
+) -> Result<Url, OutboundUrlError> {
+  let parsed = Url::parse(url).map_err(|_| OutboundUrlError::InvalidUrl(url.to_string()))?;
+  if !matches!(parsed.scheme(), "http" | "https") {
+    return Err(OutboundUrlError::UnsupportedScheme(
+      parsed.scheme().to_string(),
+    ));
+  }
+
+  let host = parsed
+    .host_str()
+    .ok_or_else(|| OutboundUrlError::InvalidUrl(url.to_string()))?;
+  if allowlist.allows(host) {
+    return Ok(parsed);
+  }
+
+  // The parser has already normalised IP literals, including forms such as `0x7f000001`
+  let ip = match parsed.host() {
+    Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+    Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+    _ => {
+      let port = parsed.port_or_known_default().unwrap_or(80);
+      resolve_public(host, port, allowlist).await?;
+      return Ok(parsed);
+    }
+  };
+  if !is_public_ip(ip) {
+    return Err(OutboundUrlError::BlockedAddress {
+      host: host.to_string(),
+      ip,
+    });
+  }
+
+  Ok(parsed)
+}
+
+/// `check_outbound_url` as a yes/no answer
+pub async fn is_safe_outbound_url(url: &str, allowlist: &OutboundAllowlist) -> bool {
+  check_outbound_url(url, allowlist).await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn private_ranges_should_be_rejected() {
+    let allowlist = OutboundAllowlist::default();
+    for url in [
+      "http://127.0.0.1:8080/hook",
+      "http://0x7f000001/",
+      "http://10.1.2.3/",
+      "http://172.16.0.1/",
+      "https://192.168.1.10/",
+      "http://169.254.169.254/latest/meta-data/",
+      "http://100.64.0.1/",
+      "http://0.0.0.0/",
+      "http://[::1]/",
+      "http://[fd00::1]/",
+      "http://[fe80::1]/",
+      "http://[::ffff:10.0.0.1]/",
+      "http://localhost:3000/",
+    ] {
+      let result = check_outbound_url(url, &allowlist).await;
+      assert!(
+        matches!(result, Err(OutboundUrlError::BlockedAddress { .. })),
+        "{} should be blocked, got {:?}",
+        url,
+        result
+      );
+      assert!(!is_safe_outbound_url(url, &allowlist).await);
+    }
+
+    assert!(matches!(
+      check_outbound_url("file:///etc/passwd", &allowlist).await,
+      Err(OutboundUrlError::UnsupportedScheme(_))
+    ));
+    assert!(is_safe_outbound_url("https://93.184.216.34/hook", &allowlist).await);
+  }
+
+  #[tokio::test]
+  async fn allowlisted_host_should_be_accepted() {
+    let allowlist = OutboundAllowlist::new(["localhost", "10.0.0.5", "*.svc.cluster.local"]);
+
+    assert!(is_safe_outbound_url("http://localhost:3000/hook", &allowlist).await);
+    assert!(is_safe_outbound_url("http://10.0.0.5/hook", &allowlist).await);
+    assert!(!is_safe_outbound_url("http://10.0.0.6/hook", &allowlist).await);
+
+    assert!(allowlist.allows("Bot.SVC.cluster.local"));
+    assert!(!allowlist.allows("svc.cluster.local"));
+    assert!(!allowlist.allows("evilsvc.cluster.local"));
+  }
+}
@@ -0,0 +1,221 @@
+//! Startup check that the database schema matches the migrations this build was compiled with
+//!
+//! Migrations are applied out of band (sqlx-cli / deploy jobs), so a server can be started
+//! against a database that is behind or ahead of the code. `verify_schema` compares the
+//! `_sqlx_migrations` history with the embedded migrations before any query runs.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{migrate::Migrator, PgPool};
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Workspace migrations, embedded at compile time
+pub static MIGRATOR: Migrator = sqlx::migrate!("../migrations");
+
+/// What to do when the schema does not match the embedded migrations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaCheckMode {
+  /// Refuse to start
+  #[default]
+  Enforce,
+  /// Log the mismatch and start anyway
+  Warn,
+  /// Don't check
+  Skip,
+}
+
+#[derive(Debug, Error)]
+pub enum SchemaError {
+  #[error("database schema is outdated: expected migration {expected}, applied {applied:?}, pending {pending:?}")]
+  Outdated {
+    expected: i64,
+    applied: Option<i64>,
+    pending: Vec<i64>,
+  },
+
+  #[error("database has migration {0} that this build does not know about")]
+  UnknownMigration(i64),
+
+  #[error("migration {0} was changed after it was applied")]
+  ChecksumMismatch(i64),
+
+  #[error("migration {0} failed and left the schema dirty")]
+  Dirty(i64),
+
+  #[error("failed to read applied migrations: {0}")]
+  Database(#[from] sqlx::Error),
+}
+
+/// A row of `_sqlx_migrations`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+  pub version: i64,
+  pub checksum: Vec<u8>,
+  pub success: bool,
+}
+
+/// Check the database against `migrator` and apply `mode`. Returns the applied version
+pub async fn verify_schema(
+  pool: &PgPool,
+  migrator: &Migrator,
+  mode: SchemaCheckMode,
+) -> Result<Option<i64>, SchemaError> {
+  if mode == SchemaCheckMode::Skip {
+    return Ok(None);
+  }
+
+  let applied = applied_migrations(pool).await?;
+  match check_applied(migrator, &applied) {
+    Ok(version) => {
+      info!("Database schema is at migration {:?}", version);
+      Ok(version)
+    }
+    Err(e) if mode == SchemaCheckMode::Warn => {
+      warn!("WARNING: {}. Starting anyway (schema check mode: warn)", e);
+      Ok(applied.iter().map(|m| m.version).max())
+    }
+    Err(e) => Err(e),
+  }
+}
+
+/// Migration history, empty if migrations were never run
+pub async fn applied_migrations(pool: &PgPool) -> Result<Vec<AppliedMigration>, SchemaError> {
+  let has_table: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+    .fetch_one(pool)
+    .await?;
+  if !has_table {
+    return Ok(Vec::new());
+  }
+
+  let rows: Vec<(i64, Vec<u8>, bool)> =
+    sqlx::query_as("SELECT version, checksum, success FROM _sqlx_migrations ORDER BY version")
+      .fetch_all(pool)
+      .await?;
+
+  Ok(
+    rows
+      .into_iter()
+      .map(|(version, checksum, success)| AppliedMigration {
+        version,
+        checksum,
+        success,
+      })
+      .collect(),
+  )
+}
+
+/// Compare a migration history with the embedded migrations. Returns the applied version
+pub fn check_applied(
+  migrator: &Migrator,
+  applied: &[AppliedMigration],
+) -> Result<Option<i64>, SchemaError> {
+  if let Some(dirty) = applied.iter().find(|m| !m.success) {
+    return Err(SchemaError::Dirty(dirty.version));
+  }
+
+  let expected: Vec<_> = migrator
+    .iter()
+    .filter(|m| !m.migration_type.is_down_migration())
+    .collect();
+
+  for migration in applied {
+    match expected.iter().find(|m| m.version == migration.version) {
+      Some(known) if *known.checksum != *migration.checksum => {
+        return Err(SchemaError::ChecksumMismatch(migration.version));
+      }
+      Some(_) => {}
+      None => return Err(SchemaError::UnknownMigration(migration.version)),
+    }
+  }
+
+  let applied_version = applied.iter().map(|m| m.version).max();
+  let pending: Vec<i64> = expected
+    .iter()
+    .map(|m| m.version)
+    .filter(|version| !applied.iter().any(|m| m.version == *version))
+    .collect();
+
+  if !pending.is_empty() {
+    return Err(SchemaError::Outdated {
+      expected: expected.iter().map(|m| m.version).max().unwrap_or_default(),
+      applied: applied_version,
+      pending,
+    });
+  }
+
+  Ok(applied_version)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn up_to_date() -> Vec<AppliedMigration> {
+    MIGRATOR
+      .iter()
+      .filter(|m| !m.migration_type.is_down_migration())
+      .map(|m| AppliedMigration {
+        version: m.version,
+        checksum: m.checksum.to_vec(),
+        success: true,
+      })
+      .collect()
+  }
+
+  #[test]
+  fn up_to_date_schema_should_pass() {
+    let applied = up_to_date();
+    let latest = applied.last().map(|m| m.version);
+    assert_eq!(check_applied(&MIGRATOR, &applied).unwrap(), latest);
+  }
+
+  #[test]
+  fn outdated_schema_should_be_detected() {
+    let mut applied = up_to_date();
+    let latest = applied.pop().expect("at least one migration").version;
+
+    match check_applied(&MIGRATOR, &applied) {
+      Err(SchemaError::Outdated {
+        expected, pending, ..
+      }) => {
+        assert_eq!(expected, latest);
+        assert_eq!(pending, vec![latest]);
+      }
+      other => panic!("expected an outdated schema, got {:?}", other),
+    }
+
+    assert!(matches!(
+      check_applied(&MIGRATOR, &[]),
+      Err(SchemaError::Outdated { applied: None, .. })
+    ));
+  }
+
+  #[test]
+  fn modified_failed_or_unknown_migrations_should_be_rejected() {
+    let mut applied = up_to_date();
+    applied[0].checksum = vec![0; 48];
+    assert!(matches!(
+      check_applied(&MIGRATOR, &applied),
+      Err(SchemaError::ChecksumMismatch(_))
+    ));
+
+    let mut applied = up_to_date();
+    applied.last_mut().unwrap().success = false;
+    assert!(matches!(
+      check_applied(&MIGRATOR, &applied),
+      Err(SchemaError::Dirty(_))
+    ));
+
+    let mut applied = up_to_date();
+    applied.push(AppliedMigration {
+      version: i64::MAX,
+      checksum: Vec::new(),
+      success: true,
+    });
+    assert!(matches!(
+      check_applied(&MIGRATOR, &applied),
+      Err(SchemaError::UnknownMigration(i64::MAX))
+    ));
+  }
+}
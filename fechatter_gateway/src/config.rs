@@ -4,7 +4,7 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Main gateway configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +22,14 @@ pub struct ServerConfig {
   pub max_connections: Option<usize>,
   pub keepalive_timeout: Option<u64>,
   pub request_timeout: Option<u64>,
+  /// CORS preflight cache lifetime in seconds for routes without their own value
+  pub cors_max_age: Option<u64>,
+  /// Most upstreams a config may define; `DEFAULT_MAX_UPSTREAMS` when absent
+  #[serde(default)]
+  pub max_upstreams: Option<usize>,
+  /// Most routes a config may define; `DEFAULT_MAX_ROUTES` when absent
+  #[serde(default)]
+  pub max_routes: Option<usize>,
 }
 
 /// Upstream service configuration
@@ -30,6 +38,34 @@ pub struct UpstreamConfig {
   pub servers: Vec<String>,
   pub health_check: Option<HealthCheckConfig>,
   pub load_balancing: Option<LoadBalancingType>,
+  /// Connection pool limits; Pingora defaults when absent
+  pub pool: Option<PoolConfig>,
+  /// Share of traffic per server address, relative to the others; 1 for servers not listed
+  #[serde(default)]
+  pub weights: Option<HashMap<String, u32>>,
+}
+
+impl UpstreamConfig {
+  /// Weight of `server` in weighted round-robin
+  pub fn weight_of(&self, server: &str) -> u32 {
+    self
+      .weights
+      .as_ref()
+      .and_then(|weights| weights.get(server))
+      .copied()
+      .unwrap_or(1)
+  }
+}
+
+/// Upstream connection pool configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PoolConfig {
+  /// Idle keepalive connections kept for reuse
+  pub max_idle_connections: Option<usize>,
+  /// Seconds an idle connection is kept before it is closed
+  pub idle_timeout: Option<u64>,
+  /// Requests allowed in flight to this upstream at once
+  pub max_connections: Option<usize>,
 }
 
 /// Health check configuration
@@ -64,6 +100,76 @@ pub struct RouteConfig {
   pub cors_enabled: Option<bool>,
   /// Custom CORS origins for this route
   pub cors_origins: Option<Vec<String>>,
+  /// CORS preflight cache lifetime in seconds for this route
+  pub cors_max_age: Option<u64>,
+  /// Upstream that receives a copy of this route's GET/HEAD traffic; its responses are only
+  /// compared with the primary's, never returned to clients
+  #[serde(default)]
+  pub shadow_upstream: Option<String>,
+  /// Rewrites applied to this route's textual response bodies; off when absent
+  #[serde(default)]
+  pub body_transform: Option<BodyTransformConfig>,
+}
+
+/// Find/replace rules for response bodies rewritten at the edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyTransformConfig {
+  pub rules: Vec<BodyRewriteRule>,
+  /// Largest body rewritten, in bytes; larger ones pass through unchanged.
+  /// `DEFAULT_BODY_TRANSFORM_MAX_BYTES` when absent
+  pub max_body_bytes: Option<usize>,
+}
+
+/// Replace every occurrence of `find` with `replace`. `{{request_id}}` in `replace` expands
+/// to the request's id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyRewriteRule {
+  pub find: String,
+  pub replace: String,
+}
+
+impl BodyTransformConfig {
+  pub fn max_body_bytes(&self) -> usize {
+    self
+      .max_body_bytes
+      .unwrap_or(DEFAULT_BODY_TRANSFORM_MAX_BYTES)
+  }
+}
+
+/// Methods safe to replay to a shadow upstream: no side effects on the data it shares
+pub fn is_shadowable_method(method: &str) -> bool {
+  method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD")
+}
+
+/// `access-control-max-age` sent when neither the route nor the server sets one
+pub const DEFAULT_CORS_MAX_AGE: u64 = 86400;
+
+/// Longest `access-control-max-age` any browser honours (Firefox, 24 hours; Chromium caps
+/// at 2 hours)
+pub const MAX_CORS_MAX_AGE: u64 = 86400;
+
+/// Upstream limit when `server.max_upstreams` is not set
+pub const DEFAULT_MAX_UPSTREAMS: usize = 64;
+
+/// Route limit when `server.max_routes` is not set; routes are matched linearly
+pub const DEFAULT_MAX_ROUTES: usize = 1024;
+
+/// Most rewrite rules a route's body transform may have
+pub const MAX_BODY_TRANSFORM_RULES: usize = 16;
+
+/// Body size limit of a transform without `max_body_bytes`
+pub const DEFAULT_BODY_TRANSFORM_MAX_BYTES: usize = 1024 * 1024;
+
+/// Largest `max_body_bytes` accepted; transformed bodies are buffered in full
+pub const MAX_BODY_TRANSFORM_BYTES: usize = 8 * 1024 * 1024;
+
+/// Whether `request_path` falls under the route registered at `route_path`
+pub fn path_matches(route_path: &str, request_path: &str) -> bool {
+  if route_path.ends_with('/') {
+    request_path.starts_with(route_path)
+  } else {
+    request_path == route_path || request_path.starts_with(&format!("{}/", route_path))
+  }
 }
 
 impl Default for ServerConfig {
@@ -74,6 +180,9 @@ impl Default for ServerConfig {
       max_connections: Some(10000),
       keepalive_timeout: Some(60),
       request_timeout: Some(30),
+      cors_max_age: None,
+      max_upstreams: None,
+      max_routes: None,
     }
   }
 }
@@ -186,6 +295,8 @@ impl GatewayConfig {
         servers: vec!["127.0.0.1:6688".to_string()],
         health_check: None, // Disable health checks for tests
         load_balancing: Some(LoadBalancingType::RoundRobin),
+        pool: None,
+        weights: None,
       },
     );
 
@@ -195,6 +306,8 @@ impl GatewayConfig {
         servers: vec!["127.0.0.1:7788".to_string()],
         health_check: None,
         load_balancing: Some(LoadBalancingType::RoundRobin),
+        pool: None,
+        weights: None,
       },
     );
 
@@ -205,6 +318,9 @@ impl GatewayConfig {
         max_connections: Some(100),
         keepalive_timeout: Some(10),
         request_timeout: Some(5),
+        cors_max_age: None,
+        max_upstreams: None,
+        max_routes: None,
       },
       upstreams,
       routes: vec![
@@ -216,6 +332,9 @@ impl GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(false),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // API routes
         RouteConfig {
@@ -230,6 +349,9 @@ impl GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Notification service
         RouteConfig {
@@ -239,6 +361,9 @@ impl GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // WebSocket
         RouteConfig {
@@ -248,6 +373,9 @@ impl GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
       ],
     };
@@ -273,6 +401,24 @@ impl GatewayConfig {
 
   /// Validate configuration
   pub fn validate(&self) -> Result<()> {
+    // Reject generated configs too large to route efficiently
+    let max_upstreams = self.server.max_upstreams.unwrap_or(DEFAULT_MAX_UPSTREAMS);
+    if self.upstreams.len() > max_upstreams {
+      return Err(anyhow::anyhow!(
+        "{} upstreams configured, more than server.max_upstreams {}",
+        self.upstreams.len(),
+        max_upstreams
+      ));
+    }
+    let max_routes = self.server.max_routes.unwrap_or(DEFAULT_MAX_ROUTES);
+    if self.routes.len() > max_routes {
+      return Err(anyhow::anyhow!(
+        "{} routes configured, more than server.max_routes {}",
+        self.routes.len(),
+        max_routes
+      ));
+    }
+
     // Validate that all routes reference existing upstreams
     for route in &self.routes {
       if !self.upstreams.contains_key(&route.upstream) {
@@ -284,6 +430,92 @@ impl GatewayConfig {
       }
     }
 
+    // Shadow traffic must go to a defined upstream other than the primary
+    for route in &self.routes {
+      if let Some(shadow) = &route.shadow_upstream {
+        if !self.upstreams.contains_key(shadow) {
+          return Err(anyhow::anyhow!(
+            "Route '{}' shadows unknown upstream '{}'",
+            route.path,
+            shadow
+          ));
+        }
+        if *shadow == route.upstream {
+          return Err(anyhow::anyhow!(
+            "Route '{}' shadows its own upstream '{}'",
+            route.path,
+            shadow
+          ));
+        }
+      }
+    }
+
+    // Body transforms are buffered per request, so keep them small
+    for route in &self.routes {
+      if let Some(transform) = &route.body_transform {
+        if transform.rules.is_empty() || transform.rules.len() > MAX_BODY_TRANSFORM_RULES {
+          return Err(anyhow::anyhow!(
+            "Route '{}' body_transform needs 1 to {} rules, got {}",
+            route.path,
+            MAX_BODY_TRANSFORM_RULES,
+            transform.rules.len()
+          ));
+        }
+        if transform.rules.iter().any(|rule| rule.find.is_empty()) {
+          return Err(anyhow::anyhow!(
+            "Route '{}' body_transform has a rule with an empty find",
+            route.path
+          ));
+        }
+        if transform.max_body_bytes() > MAX_BODY_TRANSFORM_BYTES {
+          return Err(anyhow::anyhow!(
+            "Route '{}' body_transform.max_body_bytes {} exceeds {}",
+            route.path,
+            transform.max_body_bytes(),
+            MAX_BODY_TRANSFORM_BYTES
+          ));
+        }
+      }
+    }
+
+    // A second route for the same path and method could never be matched
+    let mut seen = HashSet::new();
+    for route in &self.routes {
+      for method in &route.methods {
+        let method = method.to_uppercase();
+        if !seen.insert((route.path.as_str(), method.clone())) {
+          return Err(anyhow::anyhow!(
+            "Duplicate route {} '{}'",
+            method,
+            route.path
+          ));
+        }
+      }
+    }
+
+    // Validate CORS max-age against what browsers will actually cache
+    if let Some(max_age) = self.server.cors_max_age {
+      if max_age > MAX_CORS_MAX_AGE {
+        return Err(anyhow::anyhow!(
+          "server.cors_max_age {} exceeds the browser cap of {} seconds",
+          max_age,
+          MAX_CORS_MAX_AGE
+        ));
+      }
+    }
+    for route in &self.routes {
+      if let Some(max_age) = route.cors_max_age {
+        if max_age > MAX_CORS_MAX_AGE {
+          return Err(anyhow::anyhow!(
+            "Route '{}' cors_max_age {} exceeds the browser cap of {} seconds",
+            route.path,
+            max_age,
+            MAX_CORS_MAX_AGE
+          ));
+        }
+      }
+    }
+
     // Validate upstream configurations
     for (name, upstream) in &self.upstreams {
       if upstream.servers.is_empty() {
@@ -302,11 +534,67 @@ impl GatewayConfig {
           ));
         }
       }
+
+      // Validate weights: only for configured servers, and never zero
+      for (server, weight) in upstream.weights.iter().flatten() {
+        if !upstream.servers.contains(server) {
+          return Err(anyhow::anyhow!(
+            "Upstream '{}' has a weight for unknown server '{}'",
+            name,
+            server
+          ));
+        }
+        if *weight == 0 {
+          return Err(anyhow::anyhow!(
+            "Upstream '{}' weight for '{}' must be greater than 0",
+            name,
+            server
+          ));
+        }
+      }
+
+      // Validate connection pool limits
+      if let Some(pool) = &upstream.pool {
+        if pool.max_connections == Some(0) {
+          return Err(anyhow::anyhow!(
+            "Upstream '{}' pool.max_connections must be greater than 0",
+            name
+          ));
+        }
+        if let (Some(max_idle), Some(max_connections)) =
+          (pool.max_idle_connections, pool.max_connections)
+        {
+          if max_idle > max_connections {
+            return Err(anyhow::anyhow!(
+              "Upstream '{}' pool.max_idle_connections {} exceeds pool.max_connections {}",
+              name,
+              max_idle,
+              max_connections
+            ));
+          }
+        }
+      }
     }
 
     Ok(())
   }
 
+  /// Keepalive pool size for the shared upstream connector: the sum of the configured
+  /// per-upstream idle limits, or `None` to keep Pingora's default
+  pub fn upstream_keepalive_pool_size(&self) -> Option<usize> {
+    let limits: Vec<usize> = self
+      .upstreams
+      .values()
+      .filter_map(|upstream| upstream.pool.as_ref()?.max_idle_connections)
+      .collect();
+
+    if limits.is_empty() {
+      None
+    } else {
+      Some(limits.iter().sum())
+    }
+  }
+
   /// Get CORS origins for a specific route
   pub fn get_cors_origins(&self, route_path: &str) -> Vec<String> {
     // Find the route and return its CORS origins, or default ones
@@ -329,6 +617,18 @@ impl GatewayConfig {
     ]
   }
 
+  /// CORS preflight cache lifetime for a request path: the first matching route's value,
+  /// else the server default, else `DEFAULT_CORS_MAX_AGE`
+  pub fn get_cors_max_age(&self, request_path: &str) -> u64 {
+    self
+      .routes
+      .iter()
+      .find(|route| path_matches(&route.path, request_path))
+      .and_then(|route| route.cors_max_age)
+      .or(self.server.cors_max_age)
+      .unwrap_or(DEFAULT_CORS_MAX_AGE)
+  }
+
   /// Check if route has CORS enabled
   pub fn is_cors_enabled(&self, route_path: &str) -> bool {
     for route in &self.routes {
@@ -358,6 +658,8 @@ impl Default for GatewayConfig {
           unhealthy_threshold: Some(3),
         }),
         load_balancing: Some(LoadBalancingType::RoundRobin),
+        pool: None,
+        weights: None,
       },
     );
 
@@ -374,6 +676,8 @@ impl Default for GatewayConfig {
           unhealthy_threshold: Some(3),
         }),
         load_balancing: Some(LoadBalancingType::RoundRobin),
+        pool: None,
+        weights: None,
       },
     );
 
@@ -390,6 +694,8 @@ impl Default for GatewayConfig {
           unhealthy_threshold: Some(3),
         }),
         load_balancing: Some(LoadBalancingType::RoundRobin),
+        pool: None,
+        weights: None,
       },
     );
 
@@ -405,6 +711,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(false),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Root path for fechatter-server (index page)
         RouteConfig {
@@ -414,6 +723,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(false),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Health check variations
         RouteConfig {
@@ -423,6 +735,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(false),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Authentication routes (fechatter-server)
         RouteConfig {
@@ -432,6 +747,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         RouteConfig {
           path: "/api/signup".to_string(),
@@ -440,6 +758,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         RouteConfig {
           path: "/api/refresh".to_string(),
@@ -448,6 +769,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         RouteConfig {
           path: "/api/logout".to_string(),
@@ -456,6 +780,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         RouteConfig {
           path: "/api/logout-all".to_string(),
@@ -464,6 +791,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Debug routes (temporary)
         RouteConfig {
@@ -473,6 +803,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Chat and workspace API routes (fechatter-server)
         RouteConfig {
@@ -488,6 +821,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Notification service routes
         RouteConfig {
@@ -497,6 +833,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         RouteConfig {
           path: "/online-users".to_string(),
@@ -505,6 +844,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         RouteConfig {
           path: "/sse/health".to_string(),
@@ -513,6 +855,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(false),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Bot service routes
         RouteConfig {
@@ -522,6 +867,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // WebSocket endpoint - NOTE: fechatter-server doesn't have WebSocket implementation yet
         // This is for future compatibility when WebSocket is implemented
@@ -532,6 +880,9 @@ impl Default for GatewayConfig {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
       ],
     };
@@ -560,6 +911,132 @@ mod tests {
     assert!(config.validate().is_ok());
   }
 
+  #[test]
+  fn test_cors_max_age_above_browser_cap_is_rejected() {
+    let mut config = GatewayConfig::default();
+    config.server.cors_max_age = Some(MAX_CORS_MAX_AGE);
+    assert!(config.validate().is_ok());
+
+    config.server.cors_max_age = Some(MAX_CORS_MAX_AGE + 1);
+    assert!(config.validate().is_err());
+
+    config.server.cors_max_age = None;
+    config.routes[0].cors_max_age = Some(MAX_CORS_MAX_AGE + 1);
+    assert!(config.validate().is_err());
+  }
+
+  #[test]
+  fn test_pool_limits_are_validated_and_summed_for_keepalive() {
+    let mut config = GatewayConfig::for_testing();
+    assert_eq!(config.upstream_keepalive_pool_size(), None);
+
+    let server = config.upstreams.get_mut("test-server").unwrap();
+    server.pool = Some(PoolConfig {
+      max_idle_connections: Some(32),
+      idle_timeout: Some(90),
+      max_connections: Some(256),
+    });
+    let notify = config.upstreams.get_mut("test-notify").unwrap();
+    notify.pool = Some(PoolConfig {
+      max_idle_connections: Some(8),
+      ..PoolConfig::default()
+    });
+    assert!(config.validate().is_ok());
+    assert_eq!(config.upstream_keepalive_pool_size(), Some(40));
+
+    let server = config.upstreams.get_mut("test-server").unwrap();
+    server.pool.as_mut().unwrap().max_idle_connections = Some(512);
+    assert!(config.validate().is_err());
+
+    server.pool.as_mut().unwrap().max_connections = Some(0);
+    assert!(config.validate().is_err());
+  }
+
+  #[test]
+  fn test_weights_default_to_one_and_are_validated() {
+    let mut config = GatewayConfig::for_testing();
+    let server = config.upstreams.get_mut("test-server").unwrap();
+    assert_eq!(server.weight_of("127.0.0.1:6688"), 1);
+
+    server.weights = Some(HashMap::from([("127.0.0.1:6688".to_string(), 3)]));
+    assert_eq!(server.weight_of("127.0.0.1:6688"), 3);
+    assert!(config.validate().is_ok());
+
+    let server = config.upstreams.get_mut("test-server").unwrap();
+    server.weights = Some(HashMap::from([("127.0.0.1:6688".to_string(), 0)]));
+    assert!(config.validate().is_err());
+
+    let server = config.upstreams.get_mut("test-server").unwrap();
+    server.weights = Some(HashMap::from([("127.0.0.1:9999".to_string(), 2)]));
+    assert!(config.validate().is_err());
+  }
+
+  #[test]
+  fn test_duplicate_route_is_rejected() {
+    let mut config = GatewayConfig::for_testing();
+    let mut duplicate = config.routes[0].clone();
+    duplicate.methods = vec!["get".to_string()];
+    config.routes.push(duplicate);
+
+    let error = config.validate().unwrap_err().to_string();
+    assert_eq!(error, "Duplicate route GET '/health'");
+  }
+
+  #[test]
+  fn test_route_to_undefined_upstream_is_rejected() {
+    let mut config = GatewayConfig::for_testing();
+    config.routes[0].upstream = "missing-server".to_string();
+
+    let error = config.validate().unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "Route '/health' references unknown upstream 'missing-server'"
+    );
+  }
+
+  #[test]
+  fn test_configs_over_the_limits_are_rejected() {
+    let mut config = GatewayConfig::for_testing();
+    config.server.max_upstreams = Some(1);
+    let error = config.validate().unwrap_err().to_string();
+    assert_eq!(
+      error,
+      "2 upstreams configured, more than server.max_upstreams 1"
+    );
+
+    config.server.max_upstreams = None;
+    let template = config.routes[0].clone();
+    config
+      .routes
+      .extend((0..DEFAULT_MAX_ROUTES).map(|i| RouteConfig {
+        path: format!("/generated/{}", i),
+        ..template.clone()
+      }));
+    let error = config.validate().unwrap_err().to_string();
+    assert!(
+      error.contains("more than server.max_routes 1024"),
+      "{}",
+      error
+    );
+
+    config.server.max_routes = Some(config.routes.len());
+    assert!(config.validate().is_ok());
+  }
+
+  #[test]
+  fn test_shadow_upstream_must_be_another_defined_upstream() {
+    let mut config = GatewayConfig::for_testing();
+    config.routes[1].shadow_upstream = Some("test-notify".to_string());
+    assert!(config.validate().is_ok());
+
+    config.routes[1].shadow_upstream = Some("server-v2".to_string());
+    let error = config.validate().unwrap_err().to_string();
+    assert_eq!(error, "Route '/api/' shadows unknown upstream 'server-v2'");
+
+    config.routes[1].shadow_upstream = Some("test-server".to_string());
+    assert!(config.validate().is_err());
+  }
+
   #[test]
   fn test_for_testing_config() {
     let config = GatewayConfig::for_testing();
@@ -587,6 +1064,8 @@ pub mod testing {
         servers: vec!["127.0.0.1:6688".to_string()],
         health_check: None, // Disable health checks for tests
         load_balancing: Some(LoadBalancingType::RoundRobin),
+        pool: None,
+        weights: None,
       },
     );
 
@@ -596,6 +1075,8 @@ pub mod testing {
         servers: vec!["127.0.0.1:7788".to_string()],
         health_check: None,
         load_balancing: Some(LoadBalancingType::RoundRobin),
+        pool: None,
+        weights: None,
       },
     );
 
@@ -606,6 +1087,9 @@ pub mod testing {
         max_connections: Some(100),
         keepalive_timeout: Some(10),
         request_timeout: Some(5),
+        cors_max_age: None,
+        max_upstreams: None,
+        max_routes: None,
       },
       upstreams,
       routes: vec![
@@ -617,6 +1101,9 @@ pub mod testing {
           strip_prefix: None,
           cors_enabled: Some(false),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // API routes
         RouteConfig {
@@ -631,6 +1118,9 @@ pub mod testing {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // Notification service
         RouteConfig {
@@ -640,6 +1130,9 @@ pub mod testing {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
         // WebSocket
         RouteConfig {
@@ -649,6 +1142,9 @@ pub mod testing {
           strip_prefix: None,
           cors_enabled: Some(true),
           cors_origins: None,
+          cors_max_age: None,
+          shadow_upstream: None,
+          body_transform: None,
         },
       ],
     };
@@ -216,6 +216,14 @@ impl PingoraGateway {
       }
     };
     
+    // Size the shared upstream keepalive pool from the per-upstream idle limits
+    if let Some(pool_size) = self.config.upstream_keepalive_pool_size() {
+      if let Some(conf) = Arc::get_mut(&mut server.configuration) {
+        conf.upstream_keepalive_pool_size = pool_size;
+        info!("Upstream keepalive pool size set to {}", pool_size);
+      }
+    }
+
     // Bootstrap server
     server.bootstrap();
 
@@ -331,6 +339,7 @@ impl PingoraGateway {
           100 - (issues.len() * 20).min(100)
         }
       },
+      upstream_latency: self.proxy.upstream_latency().snapshot(),
     }
   }
 }
@@ -354,6 +363,8 @@ pub struct GatewayMetrics {
   pub cors_enabled_routes: usize,
   pub listen_address: String,
   pub server_compatibility_score: usize, // 0-100 score
+  /// Latency histograms of proxied requests, per upstream and status class
+  pub upstream_latency: Vec<proxy::latency::UpstreamLatencySnapshot>,
 }
 
 #[cfg(test)]
@@ -423,6 +434,7 @@ mod tests {
 
     assert!(metrics.total_upstreams > 0);
     assert!(metrics.total_routes > 0);
+    assert!(metrics.upstream_latency.is_empty());
   }
 
   #[tokio::test]
@@ -0,0 +1,214 @@
+//! # Upstream Latency Histograms
+//!
+//! **Request latency distribution per upstream and response status class**
+//!
+//! Features:
+//! - Fixed buckets from 5ms to 10s, recorded lock-free once a series exists
+//! - Snapshots for `GatewayMetrics`
+//! - Prometheus text exposition for `/gateway/metrics`
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Upper bounds of the latency buckets, in seconds
+pub const LATENCY_BUCKETS: [f64; 11] = [
+  0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Metric name of the histogram
+pub const UPSTREAM_LATENCY_METRIC: &str = "fechatter_gateway_upstream_request_duration_seconds";
+
+/// `2xx`, `3xx`, `4xx`, `5xx`, or `error` when no valid status was written
+pub fn status_class(status: u16) -> &'static str {
+  match status {
+    100..=199 => "1xx",
+    200..=299 => "2xx",
+    300..=399 => "3xx",
+    400..=499 => "4xx",
+    500..=599 => "5xx",
+    _ => "error",
+  }
+}
+
+/// One histogram series
+#[derive(Debug, Default)]
+struct Histogram {
+  /// Per-bucket counts, not cumulative; the last slot is `+Inf`
+  buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+  count: AtomicU64,
+  sum_micros: AtomicU64,
+}
+
+impl Histogram {
+  fn record(&self, duration: Duration) {
+    let seconds = duration.as_secs_f64();
+    let bucket = LATENCY_BUCKETS
+      .iter()
+      .position(|bound| seconds <= *bound)
+      .unwrap_or(LATENCY_BUCKETS.len());
+    self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    self.count.fetch_add(1, Ordering::Relaxed);
+    self
+      .sum_micros
+      .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+  }
+}
+
+/// Point-in-time copy of one upstream/status-class series
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpstreamLatencySnapshot {
+  pub upstream: String,
+  pub status_class: &'static str,
+  /// `(upper bound in seconds, cumulative count)`, ending with `f64::INFINITY`
+  pub buckets: Vec<(f64, u64)>,
+  pub count: u64,
+  pub sum_seconds: f64,
+}
+
+/// Latency histograms keyed by upstream and status class
+#[derive(Debug, Default)]
+pub struct UpstreamLatencyHistograms {
+  series: RwLock<HashMap<(String, &'static str), Arc<Histogram>>>,
+}
+
+impl UpstreamLatencyHistograms {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Record a finished upstream request
+  pub fn record(&self, upstream: &str, status: u16, duration: Duration) {
+    let class = status_class(status);
+    let existing = self
+      .series
+      .read()
+      .unwrap()
+      .get(&(upstream.to_string(), class))
+      .cloned();
+    let histogram = match existing {
+      Some(histogram) => histogram,
+      None => self
+        .series
+        .write()
+        .unwrap()
+        .entry((upstream.to_string(), class))
+        .or_default()
+        .clone(),
+    };
+    histogram.record(duration);
+  }
+
+  /// Every series, sorted by upstream then status class
+  pub fn snapshot(&self) -> Vec<UpstreamLatencySnapshot> {
+    let series = self.series.read().unwrap();
+    let mut snapshots: Vec<_> = series
+      .iter()
+      .map(|((upstream, status_class), histogram)| {
+        let mut cumulative = 0;
+        let buckets = LATENCY_BUCKETS
+          .iter()
+          .copied()
+          .chain(std::iter::once(f64::INFINITY))
+          .zip(&histogram.buckets)
+          .map(|(bound, count)| {
+            cumulative += count.load(Ordering::Relaxed);
+            (bound, cumulative)
+          })
+          .collect();
+        UpstreamLatencySnapshot {
+          upstream: upstream.clone(),
+          status_class,
+          buckets,
+          count: histogram.count.load(Ordering::Relaxed),
+          sum_seconds: histogram.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+        }
+      })
+      .collect();
+    snapshots.sort_by(|a, b| (&a.upstream, a.status_class).cmp(&(&b.upstream, b.status_class)));
+    snapshots
+  }
+
+  /// Histograms in the Prometheus text format
+  pub fn render_prometheus(&self) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+      out,
+      "# HELP {} Latency of requests proxied to each upstream",
+      UPSTREAM_LATENCY_METRIC
+    );
+    let _ = writeln!(out, "# TYPE {} histogram", UPSTREAM_LATENCY_METRIC);
+
+    for snapshot in self.snapshot() {
+      let labels = format!(
+        "upstream=\"{}\",status_class=\"{}\"",
+        snapshot.upstream, snapshot.status_class
+      );
+      for (bound, count) in &snapshot.buckets {
+        let le = if bound.is_infinite() {
+          "+Inf".to_string()
+        } else {
+          bound.to_string()
+        };
+        let _ = writeln!(
+          out,
+          "{}_bucket{{{},le=\"{}\"}} {}",
+          UPSTREAM_LATENCY_METRIC, labels, le, count
+        );
+      }
+      let _ = writeln!(
+        out,
+        "{}_sum{{{}}} {}",
+        UPSTREAM_LATENCY_METRIC, labels, snapshot.sum_seconds
+      );
+      let _ = writeln!(
+        out,
+        "{}_count{{{}}} {}",
+        UPSTREAM_LATENCY_METRIC, labels, snapshot.count
+      );
+    }
+    out
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_status_classes() {
+    assert_eq!(status_class(204), "2xx");
+    assert_eq!(status_class(404), "4xx");
+    assert_eq!(status_class(503), "5xx");
+    assert_eq!(status_class(0), "error");
+  }
+
+  #[test]
+  fn test_buckets_are_cumulative_per_status_class() {
+    let histograms = UpstreamLatencyHistograms::new();
+    histograms.record("api", 200, Duration::from_millis(3));
+    histograms.record("api", 200, Duration::from_millis(40));
+    histograms.record("api", 200, Duration::from_secs(30));
+    histograms.record("api", 502, Duration::from_millis(40));
+
+    let snapshots = histograms.snapshot();
+    assert_eq!(snapshots.len(), 2);
+    let ok = &snapshots[0];
+    assert_eq!((ok.upstream.as_str(), ok.status_class), ("api", "2xx"));
+    assert_eq!(ok.count, 3);
+    assert_eq!(ok.buckets[0], (0.005, 1));
+    assert_eq!(ok.buckets[3], (0.05, 2));
+    assert_eq!(ok.buckets.last(), Some(&(f64::INFINITY, 3)));
+    assert_eq!(snapshots[1].status_class, "5xx");
+
+    let text = histograms.render_prometheus();
+    assert!(text.contains(
+      "fechatter_gateway_upstream_request_duration_seconds_bucket{upstream=\"api\",status_class=\"2xx\",le=\"+Inf\"} 3"
+    ));
+    assert!(text.contains(
+      "fechatter_gateway_upstream_request_duration_seconds_count{upstream=\"api\",status_class=\"5xx\"} 1"
+    ));
+  }
+}
@@ -8,10 +8,12 @@
 //! - Multi-level rate limiting (IP-based)
 //! - Advanced CORS handling with origin validation
 //! - Request/response header manipulation
+//! - Optional per-route rewrites of textual response bodies
 //!
 //! ### Performance & Monitoring
 //! - **High-performance memory caching** with TTL
 //! - **Cache statistics and monitoring**
+//! - **Per-upstream latency histograms** served at `/gateway/metrics`
 //! - **Automatic cache eviction** with LRU strategy
 //!
 //! ### Audit & Compliance
@@ -36,20 +38,28 @@
 // Complete Gateway modules
 pub mod audit;
 pub mod cache;
+pub mod latency;
 pub mod production;
+pub mod transform;
 
-use crate::{config::GatewayConfig, upstream::UpstreamManager};
+use crate::{
+  config::GatewayConfig,
+  upstream::{ConnectionPermit, UpstreamManager},
+};
 use anyhow::Result;
 use async_trait::async_trait;
 use audit::{AuditEventType, GatewayAuditLogger};
 use cache::{CacheConfig, GatewayCache};
+use fechatter_core::middlewares::{TraceContext, TRACEPARENT_HEADER};
+use latency::UpstreamLatencyHistograms;
 use pingora_core::upstreams::peer::HttpPeer;
 use pingora_http::{RequestHeader, ResponseHeader};
 use pingora_proxy::{ProxyHttp, Session};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
+use transform::BodyTransformer;
 
 // ============================================================================
 // TYPE DEFINITIONS AND STRUCTURES
@@ -72,6 +82,9 @@ pub struct ProxyMetrics {
   pub upstreams_configured: usize,
 }
 
+/// Path where the gateway serves its own metrics instead of proxying
+pub const GATEWAY_METRICS_PATH: &str = "/gateway/metrics";
+
 /// **Gateway Proxy** implementing Pingora's ProxyHttp trait
 ///
 /// Focuses on network-level Gateway features:
@@ -88,6 +101,7 @@ pub struct FechatterProxy {
   // Gateway functionality
   cache: Arc<GatewayCache>,
   audit_logger: Arc<GatewayAuditLogger>,
+  upstream_latency: Arc<UpstreamLatencyHistograms>,
 }
 
 /// Request context for Gateway processing
@@ -96,8 +110,17 @@ pub struct RequestContext {
   pub request_id: String,
   pub matched_route: Option<String>,
   pub upstream_name: Option<String>,
+  /// Address of the backend the request was sent to, for reporting its health
+  pub upstream_peer: Option<String>,
+  /// Slot against the selected upstream's `max_connections`, held until the request ends
+  pub upstream_permit: Option<ConnectionPermit>,
   pub start_time: Instant,
 
+  // Distributed tracing context
+  pub trace_context: Option<TraceContext>,
+  /// `traceparent` forwarded upstream: the client's own when valid, otherwise generated
+  pub traceparent: Option<String>,
+
   // Network and monitoring context
   pub rate_limited: bool,
   pub client_ip: Option<String>,
@@ -108,6 +131,9 @@ pub struct RequestContext {
   pub cache_key: Option<String>,
   pub cache_hit: bool,
 
+  // Body rewrite of the response, when its route and content type call for one
+  pub body_transform: Option<BodyTransformer>,
+
   // Audit context
   pub audit_events: Vec<AuditEventType>,
 }
@@ -168,6 +194,7 @@ impl FechatterProxy {
       rate_limiter: Arc::new(std::sync::Mutex::new(HashMap::new())),
       cache: Arc::new(GatewayCache::new(cache_config)),
       audit_logger: Arc::new(GatewayAuditLogger::new(audit_config)),
+      upstream_latency: Arc::new(UpstreamLatencyHistograms::new()),
     }
   }
 
@@ -176,6 +203,11 @@ impl FechatterProxy {
     &self.config
   }
 
+  /// Latency histograms of proxied requests, per upstream and status class
+  pub fn upstream_latency(&self) -> &Arc<UpstreamLatencyHistograms> {
+    &self.upstream_latency
+  }
+
   /// Get proxy metrics for monitoring
   pub fn get_proxy_metrics(&self) -> ProxyMetrics {
     let rate_limiter_count = self.rate_limiter.lock().unwrap().len();
@@ -273,12 +305,48 @@ impl FechatterProxy {
     })
   }
 
+  /// Feed a finished request's outcome into upstream health and latency histograms
+  fn record_upstream_outcome(&self, ctx: &RequestContext, status: u16, duration: Duration) {
+    if let Some(upstream_name) = &ctx.upstream_name {
+      if let Some(peer) = &ctx.upstream_peer {
+        let healthy = status >= 200 && status < 500;
+        self
+          .upstream_manager
+          .report_health(upstream_name, peer, healthy);
+      }
+      self
+        .upstream_latency
+        .record(upstream_name, status, duration);
+    }
+  }
+
+  /// Serve the Prometheus exposition of the gateway's own metrics
+  async fn write_metrics_response(
+    &self,
+    session: &mut Session,
+  ) -> Result<(), Box<pingora_core::Error>> {
+    let body = self.upstream_latency.render_prometheus();
+    let mut header = ResponseHeader::build(200, Some(2))?;
+    header.insert_header("content-type", "text/plain; version=0.0.4")?;
+    header.insert_header("content-length", &body.len().to_string())?;
+    session
+      .write_response_header(Box::new(header), false)
+      .await?;
+    session
+      .write_response_body(Some(bytes::Bytes::from(body)), true)
+      .await?;
+    Ok(())
+  }
+
   /// Get fallback peer for error recovery
-  fn get_fallback_peer(&self, _ctx: &RequestContext) -> Option<HttpPeer> {
-    // Try to find any healthy upstream as fallback
+  fn get_fallback_peer(&self, ctx: &mut RequestContext) -> Option<HttpPeer> {
+    // Try to find any healthy upstream with a free connection slot as fallback
     for (name, _config) in &self.config.upstreams {
-      if let Some(peer) = self.upstream_manager.select_peer(name, None) {
+      if let Some((peer, permit)) = self.upstream_manager.acquire_peer(name) {
         warn!("Using fallback upstream: {}", name);
+        ctx.upstream_name = Some(name.clone());
+        ctx.upstream_peer = Some(peer._address.to_string());
+        ctx.upstream_permit = Some(permit);
         return Some(peer);
       }
     }
@@ -331,13 +399,18 @@ impl Default for RequestContext {
       request_id: uuid::Uuid::new_v4().to_string(),
       matched_route: None,
       upstream_name: None,
+      upstream_peer: None,
+      upstream_permit: None,
       start_time: Instant::now(),
+      trace_context: None,
+      traceparent: None,
       rate_limited: false,
       client_ip: None,
       cors_origin: None,
       security_violations: Vec::new(),
       cache_key: None,
       cache_hit: false,
+      body_transform: None,
       audit_events: Vec::new(),
     }
   }
@@ -378,6 +451,27 @@ impl FechatterProxy {
 // NETWORK AND REQUEST UTILITIES
 // ============================================================================
 
+/// Append the connecting address to `X-Forwarded-For`. Upstreams read the chain from the right,
+/// skipping the hops they trust, so whatever the client sent itself stays to the left
+fn append_forwarded_for(
+  upstream_request: &mut RequestHeader,
+  peer: Option<std::net::IpAddr>,
+) -> Result<(), Box<pingora_core::Error>> {
+  let Some(peer) = peer else {
+    return Ok(());
+  };
+  let forwarded_for = match upstream_request
+    .headers
+    .get("x-forwarded-for")
+    .and_then(|h| h.to_str().ok())
+  {
+    Some(existing) => format!("{}, {}", existing, peer),
+    None => peer.to_string(),
+  };
+  upstream_request.insert_header("x-forwarded-for", &forwarded_for)?;
+  Ok(())
+}
+
 impl FechatterProxy {
   /// Extract client IP from headers
   fn extract_client_ip(&self, session: &Session) -> Option<String> {
@@ -399,6 +493,46 @@ impl FechatterProxy {
     None
   }
 
+  /// Adopt a valid incoming `traceparent` unchanged; generate one when absent or malformed
+  fn resolve_trace_context(&self, incoming: Option<&str>, ctx: &mut RequestContext) {
+    let (context, traceparent) = match incoming.and_then(|value| {
+      TraceContext::parse(value).map(|context| (context, value.to_string()))
+    }) {
+      Some(incoming) => incoming,
+      None => {
+        let context = TraceContext::generate();
+        let traceparent = context.to_header();
+        (context, traceparent)
+      }
+    };
+
+    ctx.trace_context = Some(context);
+    ctx.traceparent = Some(traceparent);
+  }
+
+  /// Gateway headers added to every upstream request
+  fn add_upstream_headers(
+    &self,
+    upstream_request: &mut RequestHeader,
+    ctx: &RequestContext,
+  ) -> Result<(), Box<pingora_core::Error>> {
+    upstream_request.insert_header("x-request-id", &ctx.request_id)?;
+    upstream_request.insert_header("x-forwarded-by", "fechatter-gateway")?;
+    upstream_request.insert_header("x-gateway-version", env!("CARGO_PKG_VERSION"))?;
+
+    // Add client IP for upstream processing
+    if let Some(ip) = &ctx.client_ip {
+      upstream_request.insert_header("x-client-ip", ip)?;
+    }
+
+    // Propagate the trace so upstream spans join it
+    if let Some(traceparent) = &ctx.traceparent {
+      upstream_request.insert_header(TRACEPARENT_HEADER, traceparent)?;
+    }
+
+    Ok(())
+  }
+
   /// Enhanced CORS origin validation using configuration
   fn validate_cors_origin(&self, origin: &str, path: &str) -> bool {
     // Get allowed origins for this route from configuration
@@ -435,8 +569,10 @@ impl FechatterProxy {
         "access-control-allow-headers".to_string(),
         "content-type, authorization, x-api-key, x-request-id, x-workspace-id, cache-control, x-requested-with".to_string(),
       );
-      headers.insert("access-control-max-age".to_string(), "86400".to_string());
-      // 24 hours
+      headers.insert(
+        "access-control-max-age".to_string(),
+        self.config.get_cors_max_age(path).to_string(),
+      );
     }
 
     headers
@@ -462,11 +598,7 @@ impl FechatterProxy {
 
   /// Check if request path matches route pattern
   fn path_matches(&self, route_path: &str, request_path: &str) -> bool {
-    if route_path.ends_with('/') {
-      request_path.starts_with(route_path)
-    } else {
-      request_path == route_path || request_path.starts_with(&format!("{}/", route_path))
-    }
+    crate::config::path_matches(route_path, request_path)
   }
 }
 
@@ -489,6 +621,13 @@ impl ProxyHttp for FechatterProxy {
     session: &mut Session,
     ctx: &mut Self::CTX,
   ) -> Result<bool, Box<pingora_core::Error>> {
+    // Gateway metrics are answered here, never proxied
+    let request = session.req_header();
+    if request.uri.path() == GATEWAY_METRICS_PATH && request.method == http::Method::GET {
+      self.write_metrics_response(session).await?;
+      return Ok(true);
+    }
+
     let path = session.req_header().uri.path();
     let method = session.req_header().method.as_str();
 
@@ -500,6 +639,14 @@ impl ProxyHttp for FechatterProxy {
     // Extract client IP for rate limiting and logging
     ctx.client_ip = self.extract_client_ip(session);
 
+    // Join the client's trace, or start one at the edge
+    let incoming_traceparent = session
+      .req_header()
+      .headers
+      .get(TRACEPARENT_HEADER)
+      .and_then(|h| h.to_str().ok());
+    self.resolve_trace_context(incoming_traceparent, ctx);
+
     // 1. Handle CORS preflight requests directly
     if self.is_preflight_request(method, &session.req_header().headers) {
       if let Some(origin) = session.req_header().headers.get("origin") {
@@ -539,10 +686,7 @@ impl ProxyHttp for FechatterProxy {
       return Err(pingora_core::Error::new_str("Rate limited"));
     }
 
-    debug!(
-      "[GATEWAY] Rate limit check passed: {} remaining",
-      remaining
-    );
+    debug!("[GATEWAY] Rate limit check passed: {} remaining", remaining);
 
     // 3. Enhanced CORS Validation for actual requests
     if let Some(origin) = session.req_header().headers.get("origin") {
@@ -583,8 +727,12 @@ impl ProxyHttp for FechatterProxy {
     ctx.upstream_name = Some(route.upstream.clone());
 
     // Select upstream peer with fallback logic
-    let peer = match self.upstream_manager.select_peer(&route.upstream, None) {
-      Some(peer) => peer,
+    let peer = match self.upstream_manager.acquire_peer(&route.upstream) {
+      Some((peer, permit)) => {
+        ctx.upstream_peer = Some(peer._address.to_string());
+        ctx.upstream_permit = Some(permit);
+        peer
+      }
       None => {
         error!("No healthy upstream found for: {}", route.upstream);
         // Try fallback logic
@@ -604,19 +752,17 @@ impl ProxyHttp for FechatterProxy {
   /// Modify request headers before sending to upstream
   async fn upstream_request_filter(
     &self,
-    _session: &mut Session,
+    session: &mut Session,
     upstream_request: &mut RequestHeader,
     ctx: &mut Self::CTX,
   ) -> Result<(), Box<pingora_core::Error>> {
     // Add essential Gateway headers
-    upstream_request.insert_header("x-request-id", &ctx.request_id)?;
-    upstream_request.insert_header("x-forwarded-by", "fechatter-gateway")?;
-    upstream_request.insert_header("x-gateway-version", env!("CARGO_PKG_VERSION"))?;
-
-    // Add client IP for upstream processing
-    if let Some(ip) = &ctx.client_ip {
-      upstream_request.insert_header("x-client-ip", ip)?;
-    }
+    self.add_upstream_headers(upstream_request, ctx)?;
+    let peer = session
+      .client_addr()
+      .and_then(|addr| addr.as_inet())
+      .map(|addr| addr.ip());
+    append_forwarded_for(upstream_request, peer)?;
 
     debug!("📤 [GATEWAY] Added comprehensive Gateway headers to upstream request");
     Ok(())
@@ -652,6 +798,30 @@ impl ProxyHttp for FechatterProxy {
       }
     }
 
+    // Rewrite textual bodies of routes with a body transform
+    if let Some(transform) = self
+      .match_route(path, method)
+      .and_then(|route| route.body_transform.as_ref())
+    {
+      let header = |name: &str| {
+        upstream_response
+          .headers
+          .get(name)
+          .and_then(|value| value.to_str().ok())
+      };
+      ctx.body_transform = BodyTransformer::for_response(
+        transform,
+        header("content-type"),
+        header("content-encoding"),
+        header("content-length").and_then(|length| length.parse().ok()),
+        &ctx.request_id,
+      );
+      if ctx.body_transform.is_some() {
+        // The rewritten length is only known once the whole body is in
+        upstream_response.remove_header("content-length");
+      }
+    }
+
     // Add standard Gateway headers for regular responses
     upstream_response.insert_header("x-response-time", &format!("{}ms", duration.as_millis()))?;
     upstream_response.insert_header("x-served-by", "fechatter-gateway")?;
@@ -674,7 +844,10 @@ impl ProxyHttp for FechatterProxy {
         "access-control-expose-headers",
         "x-request-id, x-ratelimit-remaining, x-ratelimit-limit, x-ratelimit-reset",
       )?;
-      upstream_response.insert_header("access-control-max-age", "86400")?;
+      upstream_response.insert_header(
+        "access-control-max-age",
+        &self.config.get_cors_max_age(path).to_string(),
+      )?;
     }
 
     // Add comprehensive rate limiting headers (IP-based)
@@ -713,6 +886,23 @@ impl ProxyHttp for FechatterProxy {
     Ok(())
   }
 
+  /// Apply the response's body transform, if it has one
+  fn response_body_filter(
+    &self,
+    _session: &mut Session,
+    body: &mut Option<bytes::Bytes>,
+    end_of_stream: bool,
+    ctx: &mut Self::CTX,
+  ) -> Result<Option<Duration>, Box<pingora_core::Error>>
+  where
+    Self::CTX: Send + Sync,
+  {
+    if let Some(transformer) = ctx.body_transform.as_mut() {
+      transformer.filter(body, end_of_stream);
+    }
+    Ok(None)
+  }
+
   /// Request completion logging and metrics
   async fn logging(
     &self,
@@ -725,11 +915,18 @@ impl ProxyHttp for FechatterProxy {
       .response_written()
       .map(|r| r.status.as_u16())
       .unwrap_or(0);
+    let (trace_id, span_id) = ctx
+      .trace_context
+      .as_ref()
+      .map(|trace| (trace.trace_id.as_str(), trace.span_id.as_str()))
+      .unwrap_or(("unknown", "unknown"));
 
     // Log request completion
     if let Some(error) = e {
       error!(
         request_id = %ctx.request_id,
+        trace_id = %trace_id,
+        span_id = %span_id,
         upstream = %ctx.upstream_name.as_ref().unwrap_or(&"unknown".to_string()),
         route = %ctx.matched_route.as_ref().unwrap_or(&"unknown".to_string()),
         status = status,
@@ -741,6 +938,8 @@ impl ProxyHttp for FechatterProxy {
     } else {
       info!(
         request_id = %ctx.request_id,
+        trace_id = %trace_id,
+        span_id = %span_id,
         upstream = %ctx.upstream_name.as_ref().unwrap_or(&"unknown".to_string()),
         route = %ctx.matched_route.as_ref().unwrap_or(&"unknown".to_string()),
         status = status,
@@ -751,13 +950,8 @@ impl ProxyHttp for FechatterProxy {
       );
     }
 
-    // Report upstream health
-    if let Some(upstream_name) = &ctx.upstream_name {
-      let healthy = status >= 200 && status < 500;
-      self
-        .upstream_manager
-        .report_health(upstream_name, "peer", healthy);
-    }
+    // Report upstream health and latency
+    self.record_upstream_outcome(ctx, status, duration);
   }
 }
 
@@ -773,6 +967,7 @@ impl Clone for FechatterProxy {
       rate_limiter: Arc::clone(&self.rate_limiter),
       cache: Arc::clone(&self.cache),
       audit_logger: Arc::clone(&self.audit_logger),
+      upstream_latency: Arc::clone(&self.upstream_latency),
     }
   }
 }
@@ -835,6 +1030,51 @@ mod tests {
     assert!(!proxy.test_cors_validation("https://evil.com", "/api/"));
   }
 
+  #[tokio::test]
+  async fn test_preflight_uses_configured_cors_max_age() {
+    let mut config = create_test_config();
+    config.server.cors_max_age = Some(600);
+    config.routes.push(RouteConfig {
+      path: "/api/dev".to_string(),
+      methods: vec!["GET".to_string()],
+      upstream: "test-server".to_string(),
+      strip_prefix: None,
+      cors_enabled: Some(true),
+      cors_origins: None,
+      cors_max_age: Some(30),
+      shadow_upstream: None,
+      body_transform: None,
+    });
+    // More specific routes are matched first
+    config.routes.rotate_right(1);
+    let config = Arc::new(config);
+    let upstream_manager = Arc::new(UpstreamManager::new(config.clone()).await.unwrap());
+    let proxy = FechatterProxy::new(config, upstream_manager);
+
+    let headers = proxy.get_preflight_headers("http://localhost:3000", "/api/users");
+    assert_eq!(
+      headers.get("access-control-max-age").map(String::as_str),
+      Some("600")
+    );
+
+    let headers = proxy.get_preflight_headers("http://localhost:3000", "/api/dev/tools");
+    assert_eq!(
+      headers.get("access-control-max-age").map(String::as_str),
+      Some("30")
+    );
+  }
+
+  #[test]
+  fn test_forwarded_for_gains_the_connecting_address() {
+    let mut request = RequestHeader::build("GET", b"/api/signin", None).unwrap();
+    append_forwarded_for(&mut request, Some("203.0.113.7".parse().unwrap())).unwrap();
+    assert_eq!(request.headers["x-forwarded-for"], "203.0.113.7");
+
+    // A client-sent chain is kept, with the real peer last
+    append_forwarded_for(&mut request, Some("10.0.0.2".parse().unwrap())).unwrap();
+    assert_eq!(request.headers["x-forwarded-for"], "203.0.113.7, 10.0.0.2");
+  }
+
   #[tokio::test]
   async fn test_client_ip_extraction() {
     let config = Arc::new(create_test_config());
@@ -854,6 +1094,52 @@ mod tests {
     assert_eq!(rate_key, "anon:default");
   }
 
+  #[tokio::test]
+  async fn test_proxied_requests_populate_upstream_latency_histogram() {
+    let config = Arc::new(create_test_config());
+    let upstream_manager = Arc::new(UpstreamManager::new(config.clone()).await.unwrap());
+    let proxy = FechatterProxy::new(config, upstream_manager);
+
+    let mut ctx = RequestContext::default();
+    ctx.upstream_name = Some("test-server".to_string());
+    proxy.record_upstream_outcome(&ctx, 200, Duration::from_millis(12));
+    proxy.record_upstream_outcome(&ctx, 201, Duration::from_millis(300));
+    proxy.record_upstream_outcome(&ctx, 503, Duration::from_secs(2));
+    // Requests answered by the gateway itself have no upstream
+    proxy.record_upstream_outcome(&RequestContext::default(), 200, Duration::from_millis(1));
+
+    let snapshots = proxy.upstream_latency().snapshot();
+    let classes: Vec<_> = snapshots
+      .iter()
+      .map(|s| (s.upstream.as_str(), s.status_class, s.count))
+      .collect();
+    assert_eq!(
+      classes,
+      vec![("test-server", "2xx", 2), ("test-server", "5xx", 1)]
+    );
+    assert!(proxy
+      .upstream_latency()
+      .render_prometheus()
+      .contains("upstream=\"test-server\",status_class=\"2xx\",le=\"0.025\"} 1"));
+  }
+
+  #[tokio::test]
+  async fn test_upstream_errors_are_reported_against_the_selected_peer() {
+    let config = Arc::new(create_test_config());
+    let upstream_manager = Arc::new(UpstreamManager::new(config.clone()).await.unwrap());
+    let proxy = FechatterProxy::new(config, upstream_manager.clone());
+    let healthy_peers = || upstream_manager.get_upstream_status()["test-server"].healthy_peers;
+
+    let mut ctx = RequestContext::default();
+    ctx.upstream_name = Some("test-server".to_string());
+    ctx.upstream_peer = Some("127.0.0.1:6688".to_string());
+    proxy.record_upstream_outcome(&ctx, 503, Duration::from_millis(5));
+    assert_eq!(healthy_peers(), 0);
+
+    proxy.record_upstream_outcome(&ctx, 200, Duration::from_millis(5));
+    assert_eq!(healthy_peers(), 1);
+  }
+
   #[tokio::test]
   async fn test_request_context_default() {
     let ctx = RequestContext::default();
@@ -866,6 +1152,46 @@ mod tests {
     assert_eq!(ctx.cors_origin, None);
     assert_eq!(ctx.cache_hit, false);
   }
+
+  #[tokio::test]
+  async fn test_incoming_traceparent_is_propagated_unchanged() {
+    let config = Arc::new(create_test_config());
+    let upstream_manager = Arc::new(UpstreamManager::new(config.clone()).await.unwrap());
+    let proxy = FechatterProxy::new(config, upstream_manager);
+    let incoming = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    let mut ctx = RequestContext::default();
+    proxy.resolve_trace_context(Some(incoming), &mut ctx);
+    let mut upstream_request = RequestHeader::build("GET", b"/api/users", None).unwrap();
+    proxy.add_upstream_headers(&mut upstream_request, &ctx).unwrap();
+
+    assert_eq!(
+      upstream_request.headers.get(TRACEPARENT_HEADER).unwrap(),
+      incoming
+    );
+    assert_eq!(
+      ctx.trace_context.unwrap().trace_id,
+      "4bf92f3577b34da6a3ce929d0e0e4736"
+    );
+  }
+
+  #[tokio::test]
+  async fn test_missing_or_malformed_traceparent_starts_new_trace() {
+    let config = Arc::new(create_test_config());
+    let upstream_manager = Arc::new(UpstreamManager::new(config.clone()).await.unwrap());
+    let proxy = FechatterProxy::new(config, upstream_manager);
+
+    for incoming in [None, Some("not-a-traceparent")] {
+      let mut ctx = RequestContext::default();
+      proxy.resolve_trace_context(incoming, &mut ctx);
+      let mut upstream_request = RequestHeader::build("GET", b"/api/users", None).unwrap();
+      proxy.add_upstream_headers(&mut upstream_request, &ctx).unwrap();
+
+      let forwarded = upstream_request.headers.get(TRACEPARENT_HEADER).unwrap();
+      let parsed = TraceContext::parse(forwarded.to_str().unwrap()).unwrap();
+      assert_eq!(Some(parsed), ctx.trace_context);
+    }
+  }
 }
 
 // ============================================================================
@@ -8,10 +8,14 @@
 //! - Comprehensive health checking with automatic failover
 //! - Full CORS support with preflight handling
 //! - Request/Response logging and metrics
+//! - Shadow traffic: GET/HEAD requests mirrored to a route's `shadow_upstream`
 //! - Graceful shutdown and error recovery
 //! - Circuit breaker pattern for resilience
 
-use crate::config::{GatewayConfig, HealthCheckConfig, LoadBalancingType, RouteConfig, UpstreamConfig};
+use crate::config::{
+    is_shadowable_method, GatewayConfig, HealthCheckConfig, LoadBalancingType, RouteConfig,
+    UpstreamConfig,
+};
 use anyhow::Result;
 use hyper::{
     service::{make_service_fn, service_fn},
@@ -22,7 +26,7 @@ use std::{
     convert::Infallible,
     net::SocketAddr,
     sync::{
-        atomic::{AtomicU64, AtomicUsize, Ordering},
+        atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering},
         Arc, RwLock,
     },
     time::{Duration, Instant},
@@ -89,6 +93,13 @@ pub struct ProxyMetrics {
     active_connections: AtomicUsize,
     upstream_errors: AtomicU64,
     cors_preflight_requests: AtomicU64,
+    shadow_requests: AtomicU64,
+    /// Shadow requests that failed or timed out
+    shadow_errors: AtomicU64,
+    /// Shadow responses whose status differed from the primary's
+    shadow_status_mismatches: AtomicU64,
+    /// Sum of shadow minus primary latency over answered shadow requests, in milliseconds
+    shadow_latency_delta_ms: AtomicI64,
 }
 
 impl ProductionProxy {
@@ -227,8 +238,13 @@ impl ProductionProxy {
             }
         };
 
+        // Copy safe requests for the shadow upstream before the original is consumed
+        let shadow = self.prepare_shadow(&req, route).await;
+
         // Proxy the request
+        let primary_started = Instant::now();
         let result = self.proxy_request(req, &upstream_server, &route).await;
+        let primary_latency = primary_started.elapsed();
         
         let response = match result {
             Ok(mut response) => {
@@ -249,6 +265,10 @@ impl ProductionProxy {
             }
         };
 
+        if let Some(shadow) = shadow {
+            self.spawn_shadow(shadow, response.status(), primary_latency);
+        }
+
         let duration = start_time.elapsed();
         debug!("Request completed in {:?}", duration);
         
@@ -469,6 +489,100 @@ impl ProductionProxy {
         Ok(response)
     }
 
+    /// Copy of a GET/HEAD request addressed to the route's shadow upstream, if it has a healthy
+    /// server. Other methods are never mirrored: the shadow may share the primary's data
+    async fn prepare_shadow(
+        &self,
+        req: &Request<Body>,
+        route: &RouteConfig,
+    ) -> Option<Request<Body>> {
+        let shadow_upstream = route.shadow_upstream.as_ref()?;
+        if !is_shadowable_method(req.method().as_str()) {
+            return None;
+        }
+        let server = self.get_healthy_upstream(shadow_upstream).await?;
+
+        let uri: Uri = format!(
+            "http://{}{}",
+            server.address,
+            req.uri()
+                .path_and_query()
+                .map(|pq| pq.as_str())
+                .unwrap_or("")
+        )
+        .parse()
+        .ok()?;
+        let mut shadow = Request::builder()
+            .method(req.method().clone())
+            .uri(uri)
+            .body(Body::empty())
+            .ok()?;
+        *shadow.headers_mut() = req.headers().clone();
+        shadow
+            .headers_mut()
+            .insert("Host", server.address.parse().ok()?);
+        shadow
+            .headers_mut()
+            .insert("X-Shadow-Request", "true".parse().ok()?);
+        Some(shadow)
+    }
+
+    /// Replay a shadow request in the background, discarding its response and recording how its
+    /// status and latency differ from the primary's
+    fn spawn_shadow(
+        &self,
+        shadow: Request<Body>,
+        primary_status: StatusCode,
+        primary_latency: Duration,
+    ) {
+        let client = self.client.clone();
+        let metrics = Arc::clone(&self.metrics);
+        let timeout = Duration::from_secs(self.config.server.request_timeout.unwrap_or(30));
+        let target = shadow.uri().to_string();
+
+        tokio::spawn(async move {
+            metrics.shadow_requests.fetch_add(1, Ordering::Relaxed);
+            let started = Instant::now();
+            let status = match tokio::time::timeout(timeout, client.request(shadow)).await {
+                Ok(Ok(response)) => {
+                    let status = response.status();
+                    // Drain the body so the connection can be reused
+                    let _ = hyper::body::to_bytes(response.into_body()).await;
+                    status
+                }
+                Ok(Err(e)) => {
+                    warn!("Shadow request to {} failed: {}", target, e);
+                    metrics.shadow_errors.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                Err(_) => {
+                    warn!("Shadow request to {} timed out", target);
+                    metrics.shadow_errors.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+            };
+
+            let latency_delta_ms =
+                started.elapsed().as_millis() as i64 - primary_latency.as_millis() as i64;
+            metrics
+                .shadow_latency_delta_ms
+                .fetch_add(latency_delta_ms, Ordering::Relaxed);
+            if status != primary_status {
+                metrics
+                    .shadow_status_mismatches
+                    .fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    "Shadow status {} differs from primary {} for {}",
+                    status, primary_status, target
+                );
+            }
+            debug!(
+                "Shadow request to {} answered {} ({:+}ms)",
+                target, status, latency_delta_ms
+            );
+        });
+    }
+
     /// Handle CORS preflight requests
     async fn handle_cors_preflight(&self, _req: &Request<Body>) -> Response<Body> {
         debug!("🔄 Handling CORS preflight request");
@@ -560,6 +674,10 @@ impl ProductionProxy {
             active_connections: AtomicUsize::new(self.metrics.active_connections.load(Ordering::Relaxed)),
             upstream_errors: AtomicU64::new(self.metrics.upstream_errors.load(Ordering::Relaxed)),
             cors_preflight_requests: AtomicU64::new(self.metrics.cors_preflight_requests.load(Ordering::Relaxed)),
+            shadow_requests: AtomicU64::new(self.metrics.shadow_requests.load(Ordering::Relaxed)),
+            shadow_errors: AtomicU64::new(self.metrics.shadow_errors.load(Ordering::Relaxed)),
+            shadow_status_mismatches: AtomicU64::new(self.metrics.shadow_status_mismatches.load(Ordering::Relaxed)),
+            shadow_latency_delta_ms: AtomicI64::new(self.metrics.shadow_latency_delta_ms.load(Ordering::Relaxed)),
         }
     }
 }
@@ -635,4 +753,101 @@ impl HealthChecker {
         
         *server.last_health_check.lock().await = Instant::now();
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    /// Upstream answering every request with `status` and `body`, reporting the methods it sees
+    fn spawn_upstream(
+        status: StatusCode,
+        body: &'static str,
+        seen: mpsc::UnboundedSender<Method>,
+    ) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let make_svc = make_service_fn(move |_conn| {
+            let seen = seen.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let _ = seen.send(req.method().clone());
+                    async move {
+                        Ok::<_, Infallible>(
+                            Response::builder()
+                                .status(status)
+                                .body(Body::from(body))
+                                .unwrap(),
+                        )
+                    }
+                }))
+            }
+        });
+        tokio::spawn(Server::from_tcp(listener).unwrap().serve(make_svc));
+        addr
+    }
+
+    #[tokio::test]
+    async fn get_should_be_mirrored_to_shadow_without_reaching_the_client() {
+        let (primary_seen, _primary_rx) = mpsc::unbounded_channel();
+        let (shadow_seen, mut shadow_rx) = mpsc::unbounded_channel();
+        let primary = spawn_upstream(StatusCode::OK, "primary", primary_seen);
+        let shadow = spawn_upstream(StatusCode::INTERNAL_SERVER_ERROR, "shadow", shadow_seen);
+
+        let mut config = crate::config::testing::create_test_config();
+        config.upstreams.get_mut("test-server").unwrap().servers = vec![primary];
+        config.upstreams.insert(
+            "server-v2".to_string(),
+            UpstreamConfig {
+                servers: vec![shadow],
+                health_check: None,
+                load_balancing: None,
+                pool: None,
+                weights: None,
+            },
+        );
+        let api = config
+            .routes
+            .iter_mut()
+            .find(|route| route.path == "/api/")
+            .unwrap();
+        api.shadow_upstream = Some("server-v2".to_string());
+        config.validate().unwrap();
+        let proxy = ProductionProxy::new(Arc::new(config)).await.unwrap();
+
+        let request = Request::get("/api/chats").body(Body::empty()).unwrap();
+        let response = proxy.handle_request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], b"primary");
+
+        let mirrored = tokio::time::timeout(Duration::from_secs(5), shadow_rx.recv())
+            .await
+            .expect("shadow request");
+        assert_eq!(mirrored, Some(Method::GET));
+        // The shadow's 500 is only visible in the metrics
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while proxy
+                .metrics
+                .shadow_status_mismatches
+                .load(Ordering::Relaxed)
+                == 0
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("status mismatch recorded");
+        assert_eq!(proxy.metrics.shadow_requests.load(Ordering::Relaxed), 1);
+
+        let request = Request::post("/api/chats").body(Body::from("{}")).unwrap();
+        let response = proxy.handle_request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(shadow_rx.try_recv().is_err(), "POST must not be mirrored");
+        assert_eq!(proxy.metrics.shadow_requests.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,222 @@
+//! # Response Body Transform
+//!
+//! **Edge rewrites of textual response bodies**
+//!
+//! Features:
+//! - Per-route find/replace rules with a `{{request_id}}` template
+//! - Textual content types only; binary, compressed and event-stream bodies pass through
+//! - Bodies are buffered up to the route's size limit, then passed through unchanged
+
+use bytes::{Bytes, BytesMut};
+use tracing::debug;
+
+use crate::config::BodyTransformConfig;
+
+/// Whether responses of this content type may be rewritten
+pub fn is_textual_content_type(content_type: &str) -> bool {
+  let mime = content_type
+    .split(';')
+    .next()
+    .unwrap_or("")
+    .trim()
+    .to_ascii_lowercase();
+
+  if mime == "text/event-stream" {
+    // Streamed; never buffered
+    return false;
+  }
+  mime.starts_with("text/")
+    || mime.ends_with("+json")
+    || mime.ends_with("+xml")
+    || matches!(
+      mime.as_str(),
+      "application/json" | "application/javascript" | "application/xml"
+    )
+}
+
+/// Rewrite state of one response
+#[derive(Debug)]
+pub struct BodyTransformer {
+  /// `(find, replace)` with templates already expanded
+  rules: Vec<(String, String)>,
+  max_body_bytes: usize,
+  buffer: BytesMut,
+  /// Set once the body outgrew the limit; the rest streams through untouched
+  passthrough: bool,
+}
+
+impl BodyTransformer {
+  /// Transformer for a response, or `None` when its headers rule out a rewrite
+  pub fn for_response(
+    config: &BodyTransformConfig,
+    content_type: Option<&str>,
+    content_encoding: Option<&str>,
+    content_length: Option<usize>,
+    request_id: &str,
+  ) -> Option<Self> {
+    if !content_type.is_some_and(is_textual_content_type) {
+      return None;
+    }
+    // Compressed bodies are binary to us
+    if content_encoding.is_some_and(|encoding| !encoding.eq_ignore_ascii_case("identity")) {
+      return None;
+    }
+    let max_body_bytes = config.max_body_bytes();
+    if content_length.is_some_and(|length| length > max_body_bytes) {
+      return None;
+    }
+
+    let rules = config
+      .rules
+      .iter()
+      .map(|rule| {
+        (
+          rule.find.clone(),
+          rule.replace.replace("{{request_id}}", request_id),
+        )
+      })
+      .collect();
+
+    Some(Self {
+      rules,
+      max_body_bytes,
+      buffer: BytesMut::new(),
+      passthrough: false,
+    })
+  }
+
+  /// Filter one body chunk: hold chunks back until the end of the stream, then emit the
+  /// rewritten body in their place
+  pub fn filter(&mut self, body: &mut Option<Bytes>, end_of_stream: bool) {
+    if self.passthrough {
+      return;
+    }
+
+    if let Some(chunk) = body.take() {
+      self.buffer.extend_from_slice(&chunk);
+    }
+    if self.buffer.len() > self.max_body_bytes {
+      debug!(
+        "Body over {} bytes, passing it through untransformed",
+        self.max_body_bytes
+      );
+      self.passthrough = true;
+      *body = Some(self.buffer.split().freeze());
+      return;
+    }
+    if end_of_stream {
+      *body = Some(self.apply());
+    }
+  }
+
+  /// Buffered body with every rule applied; non-UTF-8 bodies are returned unchanged
+  fn apply(&mut self) -> Bytes {
+    let buffered = self.buffer.split().freeze();
+    let Ok(text) = std::str::from_utf8(&buffered) else {
+      return buffered;
+    };
+
+    let mut text = text.to_string();
+    for (find, replace) in &self.rules {
+      text = text.replace(find.as_str(), replace);
+    }
+    Bytes::from(text)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::BodyRewriteRule;
+
+  fn config() -> BodyTransformConfig {
+    BodyTransformConfig {
+      rules: vec![
+        BodyRewriteRule {
+          find: "https://old.fechatter.com".to_string(),
+          replace: "https://fechatter.com".to_string(),
+        },
+        BodyRewriteRule {
+          find: "<script>".to_string(),
+          replace: r#"<script nonce="{{request_id}}">"#.to_string(),
+        },
+      ],
+      max_body_bytes: Some(64),
+    }
+  }
+
+  /// Feed `chunks` through the transformer and collect what would be sent
+  fn run(transformer: &mut BodyTransformer, chunks: &[&str]) -> String {
+    let mut sent = Vec::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+      let mut body = Some(Bytes::copy_from_slice(chunk.as_bytes()));
+      transformer.filter(&mut body, i + 1 == chunks.len());
+      if let Some(body) = body {
+        sent.extend_from_slice(&body);
+      }
+    }
+    String::from_utf8(sent).unwrap()
+  }
+
+  #[test]
+  fn html_and_json_bodies_are_rewritten() {
+    let mut html = BodyTransformer::for_response(
+      &config(),
+      Some("text/html; charset=utf-8"),
+      None,
+      None,
+      "r1",
+    )
+    .unwrap();
+    // The match spans a chunk boundary
+    assert_eq!(
+      run(&mut html, &["<p>hi</p><scr", "ipt>"]),
+      r#"<p>hi</p><script nonce="r1">"#
+    );
+
+    let mut json =
+      BodyTransformer::for_response(&config(), Some("application/json"), None, Some(40), "r2")
+        .unwrap();
+    assert_eq!(
+      run(&mut json, &[r#"{"url":"https://old.fechatter.com/a"}"#]),
+      r#"{"url":"https://fechatter.com/a"}"#
+    );
+  }
+
+  #[test]
+  fn binary_compressed_and_streaming_bodies_are_left_alone() {
+    let config = config();
+    for (content_type, encoding) in [
+      (Some("image/png"), None),
+      (Some("application/octet-stream"), None),
+      (Some("text/event-stream"), None),
+      (Some("text/html"), Some("gzip")),
+      (None, None),
+    ] {
+      assert!(
+        BodyTransformer::for_response(&config, content_type, encoding, None, "r").is_none(),
+        "{:?} {:?}",
+        content_type,
+        encoding
+      );
+    }
+  }
+
+  #[test]
+  fn bodies_over_the_limit_pass_through_unchanged() {
+    let config = config();
+    assert!(
+      BodyTransformer::for_response(&config, Some("text/html"), None, Some(65), "r").is_none()
+    );
+
+    // Without a content-length the limit is found while buffering
+    let mut html =
+      BodyTransformer::for_response(&config, Some("text/html"), None, None, "r").unwrap();
+    let first = "<script>".repeat(6);
+    let second = "<script>".repeat(4);
+    assert_eq!(
+      run(&mut html, &[&first, &second, "<script>"]),
+      "<script>".repeat(11)
+    );
+  }
+}
@@ -2,15 +2,20 @@
 //!
 //! Manages upstream services using Pingora's built-in load balancers and health checks
 
-use crate::config::{GatewayConfig, LoadBalancingType};
+use crate::config::{GatewayConfig, LoadBalancingType, PoolConfig, UpstreamConfig};
 use anyhow::Result;
 use pingora_core::upstreams::peer::HttpPeer;
 use pingora_load_balancing::Backend;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::net::SocketAddr;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn, error};
 
+/// How long a peer reported unhealthy sits out when its upstream has no `health_check` interval
+const DEFAULT_READMIT_AFTER: Duration = Duration::from_secs(10);
+
 /// Manages upstream services with Pingora load balancers
 pub struct UpstreamManager {
   upstreams: HashMap<String, UpstreamGroup>,
@@ -20,8 +25,43 @@ pub struct UpstreamManager {
 /// Represents upstream group with load balancer
 struct UpstreamGroup {
   name: String,
-  backends: Vec<Backend>,
+  backends: Vec<WeightedBackend>,
+  /// Smooth weighted round-robin state: each backend's current weight, by index
+  current_weights: Mutex<Vec<i64>>,
   load_balancing_type: LoadBalancingType,
+  pool: PoolConfig,
+  /// How long a peer reported unhealthy is left out before it is tried again
+  readmit_after: Duration,
+  /// Requests currently holding a `ConnectionPermit` for this upstream
+  active_connections: Arc<AtomicUsize>,
+}
+
+/// A backend with its configured weight and last reported health
+struct WeightedBackend {
+  backend: Backend,
+  weight: u32,
+  /// Set while the backend is out of selection after an unhealthy report
+  unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl WeightedBackend {
+  /// Whether the backend may be selected at `now`: never reported unhealthy, or its time out
+  /// has passed and the next request through it probes it again
+  fn is_available(&self, now: Instant) -> bool {
+    let unhealthy_until = self
+      .unhealthy_until
+      .lock()
+      .unwrap_or_else(|e| e.into_inner());
+    unhealthy_until.map_or(true, |until| now >= until)
+  }
+
+  fn set_health(&self, healthy: bool, readmit_after: Duration) {
+    let mut unhealthy_until = self
+      .unhealthy_until
+      .lock()
+      .unwrap_or_else(|e| e.into_inner());
+    *unhealthy_until = (!healthy).then(|| Instant::now() + readmit_after);
+  }
 }
 
 /// Upstream status for monitoring
@@ -30,6 +70,124 @@ pub struct UpstreamStatus {
   pub name: String,
   pub total_peers: usize,
   pub healthy_peers: usize,
+  pub active_connections: usize,
+  pub max_connections: Option<usize>,
+  pub max_idle_connections: Option<usize>,
+}
+
+impl UpstreamStatus {
+  /// Share of `max_connections` in use, when a limit is configured
+  pub fn pool_utilization(&self) -> Option<f64> {
+    self
+      .max_connections
+      .map(|max| self.active_connections as f64 / max as f64)
+  }
+}
+
+/// A request's slot against its upstream's `max_connections`, released on drop
+#[derive(Debug)]
+pub struct ConnectionPermit {
+  active_connections: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionPermit {
+  fn drop(&mut self) {
+    self.active_connections.fetch_sub(1, Ordering::AcqRel);
+  }
+}
+
+impl UpstreamGroup {
+  /// Group of `backends`, each paired with the server address it was created from
+  fn new(name: &str, backends: Vec<(String, Backend)>, upstream_config: &UpstreamConfig) -> Self {
+    let backends: Vec<WeightedBackend> = backends
+      .into_iter()
+      .map(|(server, backend)| WeightedBackend {
+        backend,
+        weight: upstream_config.weight_of(&server),
+        unhealthy_until: Mutex::new(None),
+      })
+      .collect();
+
+    Self {
+      name: name.to_string(),
+      current_weights: Mutex::new(vec![0; backends.len()]),
+      backends,
+      load_balancing_type: upstream_config
+        .load_balancing
+        .clone()
+        .unwrap_or(LoadBalancingType::RoundRobin),
+      pool: upstream_config.pool.clone().unwrap_or_default(),
+      readmit_after: upstream_config
+        .health_check
+        .as_ref()
+        .map_or(DEFAULT_READMIT_AFTER, |check| {
+          Duration::from_secs(check.interval)
+        }),
+      active_connections: Arc::new(AtomicUsize::new(0)),
+    }
+  }
+
+  /// Take a connection slot, or `None` if the upstream is at `max_connections`
+  fn try_acquire(&self) -> Option<ConnectionPermit> {
+    let max = self.pool.max_connections.unwrap_or(usize::MAX);
+    self
+      .active_connections
+      .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+        (active < max).then_some(active + 1)
+      })
+      .ok()?;
+
+    Some(ConnectionPermit {
+      active_connections: self.active_connections.clone(),
+    })
+  }
+
+  /// Pick a healthy backend by smooth weighted round-robin: every healthy backend gains its
+  /// weight, the one now highest is chosen and loses the total. Over any run of picks each
+  /// backend gets its share of the weights, interleaved rather than in bursts. `None` when no
+  /// backend is healthy
+  fn next_backend(&self) -> Option<&Backend> {
+    let now = Instant::now();
+    let mut current = self
+      .current_weights
+      .lock()
+      .unwrap_or_else(|e| e.into_inner());
+
+    let mut total = 0;
+    let mut chosen: Option<usize> = None;
+    for (i, backend) in self.backends.iter().enumerate() {
+      if !backend.is_available(now) {
+        continue;
+      }
+      current[i] += backend.weight as i64;
+      total += backend.weight as i64;
+      if chosen.map_or(true, |best| current[i] > current[best]) {
+        chosen = Some(i);
+      }
+    }
+
+    let chosen = chosen?;
+    current[chosen] -= total;
+    Some(&self.backends[chosen].backend)
+  }
+
+  fn healthy_backends(&self) -> usize {
+    let now = Instant::now();
+    self
+      .backends
+      .iter()
+      .filter(|backend| backend.is_available(now))
+      .count()
+  }
+
+  /// Build a peer carrying this upstream's pool settings
+  fn build_peer(&self, backend: &Backend) -> HttpPeer {
+    let mut peer = HttpPeer::new(backend.addr.clone(), false, "".to_string());
+    if let Some(idle_timeout) = self.pool.idle_timeout {
+      peer.options.idle_timeout = Some(Duration::from_secs(idle_timeout));
+    }
+    peer
+  }
 }
 
 impl UpstreamManager {
@@ -45,7 +203,7 @@ impl UpstreamManager {
       for server in &upstream_config.servers {
         match create_backend_safe(server) {
           Ok(backend) => {
-            backends.push(backend);
+            backends.push((server.clone(), backend));
             debug!("Backend created successfully for {}", server);
           }
           Err(e) => {
@@ -74,14 +232,7 @@ impl UpstreamManager {
 
       upstreams.insert(
         name.clone(),
-        UpstreamGroup {
-          name: name.clone(),
-          backends,
-          load_balancing_type: upstream_config
-            .load_balancing
-            .clone()
-            .unwrap_or(LoadBalancingType::RoundRobin),
-        },
+        UpstreamGroup::new(name, backends, upstream_config),
       );
     }
 
@@ -104,7 +255,7 @@ impl UpstreamManager {
           match create_backend_safe(server) {
             Ok(backend) => {
               debug!("Backend created for {} (basic mode)", server);
-              Some(backend)
+              Some((server.clone(), backend))
             }
             Err(e) => {
               warn!(
@@ -129,51 +280,65 @@ impl UpstreamManager {
 
       upstreams.insert(
         name.clone(),
-        UpstreamGroup {
-          name: name.clone(),
-          backends,
-          load_balancing_type: upstream_config
-            .load_balancing
-            .clone()
-            .unwrap_or(LoadBalancingType::RoundRobin),
-        },
+        UpstreamGroup::new(name, backends, upstream_config),
       );
     }
 
     Ok(Self { upstreams, config })
   }
 
-  /// Select peer from upstream group using simple round-robin
+  /// Select a healthy peer from upstream group by smooth weighted round-robin
   pub fn select_peer(&self, upstream_name: &str, _key: Option<u64>) -> Option<HttpPeer> {
     let upstream = self.upstreams.get(upstream_name)?;
+    self.select_backend(upstream_name, upstream)
+  }
 
-    if upstream.backends.is_empty() {
+  /// Select a peer and take a connection slot for it; `None` when the upstream has no
+  /// backends or is already at its `max_connections`
+  pub fn acquire_peer(&self, upstream_name: &str) -> Option<(HttpPeer, ConnectionPermit)> {
+    let upstream = self.upstreams.get(upstream_name)?;
+    let permit = match upstream.try_acquire() {
+      Some(permit) => permit,
+      None => {
+        warn!(
+          "Upstream {} is at its connection limit ({:?})",
+          upstream_name, upstream.pool.max_connections
+        );
+        return None;
+      }
+    };
+
+    let peer = self.select_backend(upstream_name, upstream)?;
+    Some((peer, permit))
+  }
+
+  fn select_backend(&self, upstream_name: &str, upstream: &UpstreamGroup) -> Option<HttpPeer> {
+    let Some(backend) = upstream.next_backend() else {
       debug!(
         "No healthy backends available for upstream: {}",
         upstream_name
       );
       return None;
-    }
-
-    // Simple round-robin selection based on current time
-    let index = (std::time::SystemTime::now()
-      .duration_since(std::time::UNIX_EPOCH)
-      .unwrap_or_default()
-      .as_secs() as usize)
-      % upstream.backends.len();
-
-    let backend = &upstream.backends[index];
+    };
     debug!("Selected backend: {:?}", backend.addr);
 
-    // Convert Backend to HttpPeer - clone the backend to avoid move
-    Some(HttpPeer::new(backend.addr.clone(), false, "".to_string()))
+    // Convert Backend to HttpPeer with the upstream's pool settings
+    Some(upstream.build_peer(backend))
   }
 
-  /// Report health status for upstream peer
-  pub fn report_health(&self, upstream_name: &str, _peer_id: &str, healthy: bool) {
-    if let Some(_upstream) = self.upstreams.get(upstream_name) {
-      debug!("Reporting health for {}: {}", upstream_name, healthy);
-      // Health reporting logic would go here
+  /// Report health status for upstream peer, identified by its address. Unhealthy peers are
+  /// left out of selection for the upstream's health check interval, then tried again
+  pub fn report_health(&self, upstream_name: &str, peer_id: &str, healthy: bool) {
+    if let Some(upstream) = self.upstreams.get(upstream_name) {
+      debug!(
+        "Reporting health for {} {}: {}",
+        upstream_name, peer_id, healthy
+      );
+      for backend in &upstream.backends {
+        if backend.backend.addr.to_string() == peer_id {
+          backend.set_health(healthy, upstream.readmit_after);
+        }
+      }
     }
   }
 
@@ -190,7 +355,10 @@ impl UpstreamManager {
           .get(name)
           .map(|c| c.servers.len())
           .unwrap_or(0),
-        healthy_peers: upstream.backends.len(), // Use actual healthy backends count
+        healthy_peers: upstream.healthy_backends(),
+        active_connections: upstream.active_connections.load(Ordering::Acquire),
+        max_connections: upstream.pool.max_connections,
+        max_idle_connections: upstream.pool.max_idle_connections,
       };
       statuses.insert(name.clone(), status);
     }
@@ -244,3 +412,150 @@ fn create_backend_safe(server: &str) -> Result<Backend> {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::HealthCheckConfig;
+
+  async fn manager_with_pool(pool: PoolConfig) -> UpstreamManager {
+    let mut config = GatewayConfig::for_testing();
+    config.upstreams.insert(
+      "pooled".to_string(),
+      UpstreamConfig {
+        servers: vec!["127.0.0.1:6688".to_string()],
+        health_check: None,
+        load_balancing: Some(LoadBalancingType::RoundRobin),
+        pool: Some(pool),
+        weights: None,
+      },
+    );
+    UpstreamManager::new_basic(Arc::new(config)).await.unwrap()
+  }
+
+  async fn manager_with_weights(weights: &[(&str, u32)]) -> UpstreamManager {
+    let mut config = GatewayConfig::for_testing();
+    config.upstreams.insert(
+      "weighted".to_string(),
+      UpstreamConfig {
+        servers: weights
+          .iter()
+          .map(|(server, _)| server.to_string())
+          .collect(),
+        health_check: None,
+        load_balancing: Some(LoadBalancingType::WeightedRoundRobin),
+        pool: None,
+        weights: Some(
+          weights
+            .iter()
+            .map(|(server, weight)| (server.to_string(), *weight))
+            .collect(),
+        ),
+      },
+    );
+    UpstreamManager::new_basic(Arc::new(config)).await.unwrap()
+  }
+
+  fn selection_counts(manager: &UpstreamManager, selections: usize) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for _ in 0..selections {
+      let peer = manager.select_peer("weighted", None).unwrap();
+      *counts.entry(peer._address.to_string()).or_insert(0) += 1;
+    }
+    counts
+  }
+
+  #[tokio::test]
+  async fn peers_should_be_selected_in_proportion_to_their_weights() {
+    let manager = manager_with_weights(&[("127.0.0.1:6688", 3), ("127.0.0.1:6689", 1)]).await;
+
+    let counts = selection_counts(&manager, 400);
+    assert_eq!(counts["127.0.0.1:6688"], 300);
+    assert_eq!(counts["127.0.0.1:6689"], 100);
+  }
+
+  #[tokio::test]
+  async fn unhealthy_peers_should_never_be_selected() {
+    let manager = manager_with_weights(&[("127.0.0.1:6688", 3), ("127.0.0.1:6689", 1)]).await;
+
+    manager.report_health("weighted", "127.0.0.1:6688", false);
+    assert_eq!(selection_counts(&manager, 10)["127.0.0.1:6689"], 10);
+    assert_eq!(manager.get_upstream_status()["weighted"].healthy_peers, 1);
+
+    // With every peer down the caller falls back elsewhere
+    manager.report_health("weighted", "127.0.0.1:6689", false);
+    assert!(manager.select_peer("weighted", None).is_none());
+
+    manager.report_health("weighted", "127.0.0.1:6688", true);
+    assert_eq!(selection_counts(&manager, 10)["127.0.0.1:6688"], 10);
+  }
+
+  #[tokio::test]
+  async fn unhealthy_peers_should_be_tried_again_after_the_check_interval() {
+    let mut config = GatewayConfig::for_testing();
+    config.upstreams.insert(
+      "checked".to_string(),
+      UpstreamConfig {
+        servers: vec!["127.0.0.1:6688".to_string()],
+        health_check: Some(HealthCheckConfig {
+          interval: 30,
+          timeout: 5,
+          path: "/health".to_string(),
+          expected_status: vec![200],
+          healthy_threshold: None,
+          unhealthy_threshold: None,
+        }),
+        load_balancing: None,
+        pool: None,
+        weights: None,
+      },
+    );
+    let manager = UpstreamManager::new_basic(Arc::new(config)).await.unwrap();
+
+    manager.report_health("checked", "127.0.0.1:6688", false);
+    assert!(manager.select_peer("checked", None).is_none());
+
+    let backend = &manager.upstreams["checked"].backends[0];
+    let now = Instant::now();
+    assert!(!backend.is_available(now + Duration::from_secs(29)));
+    assert!(backend.is_available(now + Duration::from_secs(31)));
+  }
+
+  #[tokio::test]
+  async fn peers_should_carry_configured_pool_settings() {
+    let manager = manager_with_pool(PoolConfig {
+      max_idle_connections: Some(16),
+      idle_timeout: Some(45),
+      max_connections: Some(2),
+    })
+    .await;
+
+    let (peer, _permit) = manager.acquire_peer("pooled").unwrap();
+    assert_eq!(peer.options.idle_timeout, Some(Duration::from_secs(45)));
+
+    // Upstreams without pool settings keep Pingora's defaults
+    let (peer, _other) = manager.acquire_peer("test-server").unwrap();
+    assert_eq!(peer.options.idle_timeout, None);
+  }
+
+  #[tokio::test]
+  async fn max_connections_should_be_enforced_and_reported() {
+    let manager = manager_with_pool(PoolConfig {
+      max_connections: Some(2),
+      ..PoolConfig::default()
+    })
+    .await;
+
+    let first = manager.acquire_peer("pooled").unwrap();
+    let _second = manager.acquire_peer("pooled").unwrap();
+    assert!(manager.acquire_peer("pooled").is_none());
+
+    let status = &manager.get_upstream_status()["pooled"];
+    assert_eq!(status.active_connections, 2);
+    assert_eq!(status.pool_utilization(), Some(1.0));
+
+    drop(first);
+    assert!(manager.acquire_peer("pooled").is_some());
+    assert_eq!(manager.get_upstream_status()["pooled"].active_connections, 1);
+  }
+}
@@ -104,6 +104,8 @@ async fn test_dns_resolution_failure() {
       servers: vec!["non-existent-host.invalid:8080".to_string()],
       health_check: None,
       load_balancing: Some(fechatter_gateway::config::LoadBalancingType::RoundRobin),
+      pool: None,
+      weights: None,
     },
   );
 
@@ -434,6 +436,9 @@ async fn test_invalid_configuration() {
         strip_prefix: None,
         cors_enabled: Some(false),
         cors_origins: None,
+        cors_max_age: None,
+        shadow_upstream: None,
+        body_transform: None,
       }],
     },
   ];
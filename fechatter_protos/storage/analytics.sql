@@ -26,6 +26,8 @@ CREATE TABLE IF NOT EXISTS fechatter_analytics.analytics_events
     geo_city Nullable(String),
     client_ts Int64,
     server_ts Int64,
+    request_id Nullable(String),
+    traceparent Nullable(String),
     
     -- Common fields
     event_type String,
@@ -113,13 +115,19 @@ CREATE TABLE IF NOT EXISTS fechatter_analytics.analytics_events
     -- Indexes
     INDEX idx_user_id user_id TYPE bloom_filter GRANULARITY 1,
     INDEX idx_session_id session_id TYPE bloom_filter GRANULARITY 1,
-    INDEX idx_event_type event_type TYPE bloom_filter GRANULARITY 1
+    INDEX idx_event_type event_type TYPE bloom_filter GRANULARITY 1,
+    INDEX idx_request_id request_id TYPE bloom_filter GRANULARITY 1
 )
 ENGINE = MergeTree()
 PARTITION BY toYYYYMM(toDateTime(server_ts / 1000))
 ORDER BY (event_type, server_ts, client_id)
 TTL toDateTime(server_ts / 1000) + INTERVAL 90 DAY;
 
+-- Request tracing columns for tables created before they existed
+ALTER TABLE fechatter_analytics.analytics_events ADD COLUMN IF NOT EXISTS request_id Nullable(String) AFTER server_ts;
+ALTER TABLE fechatter_analytics.analytics_events ADD COLUMN IF NOT EXISTS traceparent Nullable(String) AFTER request_id;
+ALTER TABLE fechatter_analytics.analytics_events ADD INDEX IF NOT EXISTS idx_request_id request_id TYPE bloom_filter GRANULARITY 1;
+
 -- Create materialized views for common analytics (fixed nullable column issues)
 
 -- Daily active users (using coalesce to handle nullable user_id)
@@ -6,10 +6,18 @@
 use anyhow::Result;
 use bytes::Bytes;
 use fechatter_core::models::jwt::TokenConfigProvider;
+use fechatter_core::services::concurrency::ConcurrencyLimitConfig;
+use fechatter_core::utils::outbound::OutboundAllowlist;
+use fechatter_core::utils::redact::{
+    redact_optional_secret, redact_secret, redact_url, RedactedSummary,
+};
+use fechatter_core::utils::schema::SchemaCheckMode;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, env, path::PathBuf, time::Duration};
+use std::{collections::HashMap, env, net::IpAddr, path::PathBuf, time::Duration};
 use thiserror::Error;
 
+use crate::domains::messaging::messaging_domain::ModerationAction;
+
 // ============================================================================
 // Core Configuration Structures - Pure data definitions
 // ============================================================================
@@ -33,6 +41,26 @@ pub struct ServerConfig {
     pub request_timeout_ms: u64,
     pub cors: Option<CorsConfig>,
     pub analytics: AnalyticsConfig,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+    /// Peers whose `X-Forwarded-For` / `X-Real-IP` headers are trusted for the client IP
+    #[serde(default = "fechatter_core::utils::default_trusted_proxies")]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Startup check of the applied migrations: `enforce` refuses to start on a mismatch,
+    /// `warn` only logs it
+    #[serde(default)]
+    pub schema_check: SchemaCheckMode,
+}
+
+/// Read-only maintenance mode configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MaintenanceConfig {
+    /// Start the server with writes rejected
+    #[serde(default)]
+    pub read_only: bool,
+    /// Users allowed to toggle maintenance mode at runtime
+    #[serde(default)]
+    pub admin_user_ids: Vec<i64>,
 }
 
 /// Authentication configuration
@@ -42,6 +70,19 @@ pub struct AuthConfig {
     pub sk: String,
     pub token_expiration: i64,
     pub refresh_token_expiration: i64,
+    /// Seconds a refresh token may go unused before it stops working, independent of its
+    /// expiry; unset disables the idle timeout
+    #[serde(default)]
+    pub refresh_idle_timeout: Option<i64>,
+}
+
+impl AuthConfig {
+    /// Inactivity window after which refresh tokens are rejected, if configured
+    pub fn refresh_idle_timeout(&self) -> Option<chrono::Duration> {
+        self.refresh_idle_timeout
+            .filter(|seconds| *seconds > 0)
+            .map(chrono::Duration::seconds)
+    }
 }
 
 /// Feature configurations
@@ -54,6 +95,80 @@ pub struct FeatureConfig {
     pub notifications: NotificationConfig,
     pub observability: ObservabilityConfig,
     pub rate_limiting: RateLimitConfig,
+    #[serde(default)]
+    pub moderation: ModerationConfig,
+    #[serde(default)]
+    pub routes: RouteFeatures,
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    #[serde(default)]
+    pub embedding_backfill: EmbeddingBackfillConfig,
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+    #[serde(default)]
+    pub link_previews: LinkPreviewConfig,
+    #[serde(default)]
+    pub outbound: OutboundConfig,
+    #[serde(default)]
+    pub chat_limits: ChatLimitsConfig,
+    #[serde(default)]
+    pub workspace_limits: WorkspaceLimitsConfig,
+    #[serde(default)]
+    pub message_limits: MessageLimitsConfig,
+    #[serde(default)]
+    pub admin_dashboard: AdminDashboardConfig,
+    #[serde(default)]
+    pub response_cache: ResponseCacheConfig,
+    #[serde(default)]
+    pub system_health: SystemHealthConfig,
+    #[serde(default)]
+    pub file_scanning: FileScanConfig,
+    #[serde(default)]
+    pub dead_letters: DeadLetterConfig,
+    #[serde(default)]
+    pub user_import: UserImportConfig,
+    /// Process-wide cap on concurrent calls to the AI provider
+    #[serde(default)]
+    pub ai_concurrency: ConcurrencyLimitConfig,
+}
+
+/// Optional route groups; a disabled group is not mounted and its paths return 404
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RouteFeatures {
+    #[serde(default = "default_route_enabled")]
+    pub search: bool,
+    #[serde(default = "default_route_enabled")]
+    pub bot: bool,
+    #[serde(default = "default_route_enabled")]
+    pub realtime: bool,
+}
+
+fn default_route_enabled() -> bool {
+    true
+}
+
+impl Default for RouteFeatures {
+    fn default() -> Self {
+        Self {
+            search: true,
+            bot: true,
+            realtime: true,
+        }
+    }
+}
+
+impl RouteFeatures {
+    /// Names of the mounted route groups, as reported by `/health`
+    pub fn enabled(&self) -> Vec<&'static str> {
+        [
+            ("search", self.search),
+            ("bot", self.bot),
+            ("realtime", self.realtime),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+    }
 }
 
 /// CORS configuration
@@ -97,6 +212,9 @@ pub struct CacheConfig {
     pub default_ttl: u64,
     pub pool_size: u32,
     pub connection_timeout_ms: u64,
+    /// Behavior while Redis is unreachable
+    #[serde(default)]
+    pub degradation: CacheDegradationConfig,
 
     // Middleware extension config (runtime only, not in chat.yml)
     #[serde(skip, default)]
@@ -113,6 +231,52 @@ pub struct CacheConfig {
     pub variants: Vec<CacheVariant>,
 }
 
+/// How the cache copes with an unreachable Redis. A circuit breaker stops sending commands
+/// after repeated connection failures, then lets one through now and then to probe for recovery
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CacheDegradationConfig {
+    /// Serve reads as misses and skip writes and invalidations instead of failing them; locks
+    /// and counters fail either way
+    #[serde(default = "default_cache_fail_open")]
+    pub fail_open: bool,
+    /// Consecutive connection failures that open the breaker
+    #[serde(default = "default_cache_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Seconds the open breaker turns commands away before probing
+    #[serde(default = "default_cache_open_secs")]
+    pub open_secs: u64,
+    /// Longest wait for a command, which then counts as a connection failure
+    #[serde(default = "default_cache_command_timeout_ms")]
+    pub command_timeout_ms: u64,
+}
+
+fn default_cache_fail_open() -> bool {
+    true
+}
+
+fn default_cache_failure_threshold() -> u32 {
+    5
+}
+
+fn default_cache_open_secs() -> u64 {
+    30
+}
+
+fn default_cache_command_timeout_ms() -> u64 {
+    2000
+}
+
+impl Default for CacheDegradationConfig {
+    fn default() -> Self {
+        Self {
+            fail_open: default_cache_fail_open(),
+            failure_threshold: default_cache_failure_threshold(),
+            open_secs: default_cache_open_secs(),
+            command_timeout_ms: default_cache_command_timeout_ms(),
+        }
+    }
+}
+
 /// Cache variant - defines how cache is segmented by request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CacheVariant {
@@ -224,6 +388,48 @@ pub struct RateLimitConfig {
     pub sliding_window: bool,
     /// Rate limiting strategy
     pub strategy: RateLimitStrategy,
+    /// Max login attempts per window for a single account, and signups for a single client
+    #[serde(default = "default_login_max_requests")]
+    pub login_max_requests: u32,
+    /// Max file uploads per window for a single user
+    #[serde(default = "default_upload_max_requests")]
+    pub upload_max_requests: u32,
+    /// Bot translations a single user may request per day
+    #[serde(default = "default_bot_daily_quota")]
+    pub bot_daily_quota: u32,
+    /// Max file downloads per window for a single user
+    #[serde(default = "default_download_max_requests")]
+    pub download_max_requests: u32,
+    /// Bytes per second sent to a single download; 0 leaves downloads unthrottled
+    #[serde(default)]
+    pub download_bytes_per_second: u64,
+    /// Shortest gap between typing events pushed for one user in one chat; starts within it
+    /// only refresh the typing TTL. 0 pushes every start
+    #[serde(default = "default_typing_interval_secs")]
+    pub typing_interval_secs: u64,
+    /// AI provider tokens a workspace may spend per UTC day; 0 leaves it unbudgeted
+    #[serde(default)]
+    pub ai_daily_token_budget: u64,
+}
+
+fn default_login_max_requests() -> u32 {
+    10
+}
+
+fn default_upload_max_requests() -> u32 {
+    20
+}
+
+fn default_bot_daily_quota() -> u32 {
+    20
+}
+
+fn default_download_max_requests() -> u32 {
+    120
+}
+
+fn default_typing_interval_secs() -> u64 {
+    1
 }
 
 /// Rate limiting strategy
@@ -247,6 +453,13 @@ impl Default for RateLimitConfig {
             max_requests: 100,
             sliding_window: true,
             strategy: RateLimitStrategy::IpBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 }
@@ -260,6 +473,13 @@ impl RateLimitConfig {
             max_requests: max_requests as u32,
             sliding_window: true,
             strategy: RateLimitStrategy::UserBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -271,6 +491,13 @@ impl RateLimitConfig {
             max_requests: max_requests as u32,
             sliding_window: true,
             strategy: RateLimitStrategy::ApiKeyBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -282,6 +509,13 @@ impl RateLimitConfig {
             max_requests: 30,
             sliding_window: true,
             strategy: RateLimitStrategy::UserBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -293,6 +527,13 @@ impl RateLimitConfig {
             max_requests: 200,
             sliding_window: true,
             strategy: RateLimitStrategy::UserBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -304,6 +545,13 @@ impl RateLimitConfig {
             max_requests: 100, // 100 messages per minute
             sliding_window: true,
             strategy: RateLimitStrategy::UserBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -315,6 +563,13 @@ impl RateLimitConfig {
             max_requests: 20,    // 20 files per 5 minutes
             sliding_window: true,
             strategy: RateLimitStrategy::UserBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -326,6 +581,13 @@ impl RateLimitConfig {
             max_requests: 50, // 50 searches per minute
             sliding_window: true,
             strategy: RateLimitStrategy::UserBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -337,6 +599,13 @@ impl RateLimitConfig {
             max_requests: 5,     // 5 attempts per 5 minutes
             sliding_window: true,
             strategy: RateLimitStrategy::IpBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -348,6 +617,13 @@ impl RateLimitConfig {
             max_requests: 10, // 10 connections per minute
             sliding_window: true,
             strategy: RateLimitStrategy::IpBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -365,6 +641,13 @@ impl RateLimitConfig {
             max_requests,
             sliding_window: true,
             strategy: RateLimitStrategy::UserBased,
+            login_max_requests: default_login_max_requests(),
+            upload_max_requests: default_upload_max_requests(),
+            bot_daily_quota: default_bot_daily_quota(),
+            download_max_requests: default_download_max_requests(),
+            download_bytes_per_second: 0,
+            typing_interval_secs: default_typing_interval_secs(),
+            ai_daily_token_budget: 0,
         }
     }
 
@@ -512,7 +795,538 @@ pub struct MessagingConfig {
     pub jetstream_enabled: bool,
 }
 
-/// Notification configuration
+/// Message content moderation configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModerationConfig {
+    /// Screen messages with the AI moderation backend before sending
+    #[serde(default)]
+    pub enabled: bool,
+    /// Reject flagged messages or send them and flag for review
+    #[serde(default)]
+    pub action: ModerationAction,
+}
+
+/// Message retention sweeper; the retention window itself is set per workspace
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetentionConfig {
+    /// Run the background sweep that tombstones expired messages
+    #[serde(default = "default_retention_enabled")]
+    pub enabled: bool,
+    /// Seconds between sweeps
+    #[serde(default = "default_retention_sweep_interval")]
+    pub sweep_interval_seconds: u64,
+    /// Messages tombstoned per batch
+    #[serde(default = "default_retention_batch_size")]
+    pub batch_size: i64,
+}
+
+fn default_retention_enabled() -> bool {
+    true
+}
+
+fn default_retention_sweep_interval() -> u64 {
+    3600
+}
+
+fn default_retention_batch_size() -> i64 {
+    500
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_retention_enabled(),
+            sweep_interval_seconds: default_retention_sweep_interval(),
+            batch_size: default_retention_batch_size(),
+        }
+    }
+}
+
+/// Admin-triggered embedding backfill for semantic search
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmbeddingBackfillConfig {
+    /// Messages embedded per AI request
+    #[serde(default = "default_embedding_batch_size")]
+    pub batch_size: i64,
+    /// Upper bound on AI embedding requests per minute, to cap cost
+    #[serde(default = "default_embedding_requests_per_minute")]
+    pub requests_per_minute: u32,
+    /// Retries of a failed embedding request before the job stops as failed
+    #[serde(default = "default_embedding_max_retries")]
+    pub max_retries: u32,
+}
+
+fn default_embedding_batch_size() -> i64 {
+    32
+}
+
+fn default_embedding_requests_per_minute() -> u32 {
+    20
+}
+
+fn default_embedding_max_retries() -> u32 {
+    3
+}
+
+impl Default for EmbeddingBackfillConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: default_embedding_batch_size(),
+            requests_per_minute: default_embedding_requests_per_minute(),
+            max_retries: default_embedding_max_retries(),
+        }
+    }
+}
+
+/// Outgoing webhook delivery; subscriptions themselves are registered per workspace
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookConfig {
+    /// Run the delivery worker consuming message and membership events
+    #[serde(default = "default_webhooks_enabled")]
+    pub enabled: bool,
+    /// Delivery attempts per event before it goes to the dead letter subject
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: u32,
+    /// Backoff before the first retry; doubled on every further retry
+    #[serde(default = "default_webhook_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Timeout of a single delivery request
+    #[serde(default = "default_webhook_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Consecutive failed deliveries after which a subscription is disabled
+    #[serde(default = "default_webhook_disable_after_failures")]
+    pub disable_after_failures: i32,
+    /// NATS subject that undeliverable events are published to
+    #[serde(default = "default_webhook_dead_letter_subject")]
+    pub dead_letter_subject: String,
+}
+
+fn default_webhooks_enabled() -> bool {
+    true
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    3
+}
+
+fn default_webhook_retry_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_webhook_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_webhook_disable_after_failures() -> i32 {
+    10
+}
+
+fn default_webhook_dead_letter_subject() -> String {
+    "fechatter.webhooks.dlq".to_string()
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_webhooks_enabled(),
+            max_attempts: default_webhook_max_attempts(),
+            retry_backoff_ms: default_webhook_retry_backoff_ms(),
+            timeout_ms: default_webhook_timeout_ms(),
+            disable_after_failures: default_webhook_disable_after_failures(),
+            dead_letter_subject: default_webhook_dead_letter_subject(),
+        }
+    }
+}
+
+/// Link previews (unfurls) generated in the background for URLs in sent messages
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LinkPreviewConfig {
+    #[serde(default = "default_link_previews_enabled")]
+    pub enabled: bool,
+    /// URLs unfurled per message; the rest are ignored
+    #[serde(default = "default_link_preview_max_urls")]
+    pub max_urls_per_message: usize,
+    /// Timeout of a page fetch, redirects included
+    #[serde(default = "default_link_preview_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Bytes of a page read; metadata lives in `<head>`, so the rest is never needed
+    #[serde(default = "default_link_preview_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// How long a URL's preview, or its lack of one, is cached in Redis
+    #[serde(default = "default_link_preview_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+}
+
+fn default_link_previews_enabled() -> bool {
+    true
+}
+
+fn default_link_preview_max_urls() -> usize {
+    3
+}
+
+fn default_link_preview_timeout_ms() -> u64 {
+    3000
+}
+
+fn default_link_preview_max_body_bytes() -> usize {
+    512 * 1024
+}
+
+fn default_link_preview_cache_ttl_secs() -> u64 {
+    24 * 3600
+}
+
+impl Default for LinkPreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_link_previews_enabled(),
+            max_urls_per_message: default_link_preview_max_urls(),
+            timeout_ms: default_link_preview_timeout_ms(),
+            max_body_bytes: default_link_preview_max_body_bytes(),
+            cache_ttl_secs: default_link_preview_cache_ttl_secs(),
+        }
+    }
+}
+
+/// Requests to user-supplied URLs (webhooks, link previews)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OutboundConfig {
+    /// Hosts that may be reached even though they resolve to internal addresses
+    #[serde(default)]
+    pub allowed_hosts: OutboundAllowlist,
+}
+
+/// Caps on chat creation and chat size
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatLimitsConfig {
+    /// Chats a single user may create
+    #[serde(default = "default_max_chats_per_user")]
+    pub max_chats_per_user: usize,
+    /// Members a single chat may hold, creator included
+    #[serde(default = "default_max_members_per_chat")]
+    pub max_members_per_chat: usize,
+}
+
+fn default_max_chats_per_user() -> usize {
+    100
+}
+
+fn default_max_members_per_chat() -> usize {
+    200
+}
+
+impl Default for ChatLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_chats_per_user: default_max_chats_per_user(),
+            max_members_per_chat: default_max_members_per_chat(),
+        }
+    }
+}
+
+/// Caps on workspace size
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkspaceLimitsConfig {
+    /// Users a workspace may hold before admins can add more, by import or otherwise. Open
+    /// signup isn't capped, so the shared default workspace keeps accepting new accounts
+    #[serde(default = "default_max_workspace_members")]
+    pub max_members: usize,
+}
+
+fn default_max_workspace_members() -> usize {
+    1000
+}
+
+impl Default for WorkspaceLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_members: default_max_workspace_members(),
+        }
+    }
+}
+
+/// Caps on a single message's content and attachments
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MessageLimitsConfig {
+    /// Longest message content, in bytes
+    #[serde(default = "default_max_content_length")]
+    pub max_content_length: usize,
+    /// Files a single message may carry
+    #[serde(default = "default_max_file_count")]
+    pub max_file_count: usize,
+    /// Combined size of a message's stored attachments, in bytes
+    #[serde(default = "default_max_total_attachment_bytes")]
+    pub max_total_attachment_bytes: u64,
+    /// Seconds after sending during which the author may edit a message; 0 allows edits forever
+    #[serde(default = "default_edit_window_secs")]
+    pub edit_window_secs: u64,
+    /// Let chat admins and owners edit their own messages past the window
+    #[serde(default = "default_edit_window_exempt_admins")]
+    pub edit_window_exempt_admins: bool,
+}
+
+fn default_max_content_length() -> usize {
+    16384
+}
+
+fn default_max_file_count() -> usize {
+    10
+}
+
+fn default_max_total_attachment_bytes() -> u64 {
+    50 * 1024 * 1024
+}
+
+fn default_edit_window_secs() -> u64 {
+    15 * 60
+}
+
+fn default_edit_window_exempt_admins() -> bool {
+    true
+}
+
+impl Default for MessageLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_content_length: default_max_content_length(),
+            max_file_count: default_max_file_count(),
+            max_total_attachment_bytes: default_max_total_attachment_bytes(),
+            edit_window_secs: default_edit_window_secs(),
+            edit_window_exempt_admins: default_edit_window_exempt_admins(),
+        }
+    }
+}
+
+/// `GET /api/admin/dashboard` sources
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdminDashboardConfig {
+    /// Seconds a snapshot is served before it is collected again
+    #[serde(default = "default_dashboard_cache_ttl")]
+    pub cache_ttl_seconds: u64,
+    /// notify_server base URL for connection counts; the admin's token is forwarded
+    #[serde(default)]
+    pub notify_url: Option<String>,
+    /// JetStream consumers whose backlog is reported as queue depth
+    #[serde(default = "default_dashboard_queues")]
+    pub queues: Vec<DashboardQueueConfig>,
+}
+
+/// A JetStream consumer to report
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DashboardQueueConfig {
+    pub stream: String,
+    pub consumer: String,
+}
+
+fn default_dashboard_cache_ttl() -> u64 {
+    10
+}
+
+fn default_dashboard_queues() -> Vec<DashboardQueueConfig> {
+    vec![DashboardQueueConfig {
+        stream: "fechatter_search_index".to_string(),
+        consumer: "search_indexer".to_string(),
+    }]
+}
+
+impl Default for AdminDashboardConfig {
+    fn default() -> Self {
+        Self {
+            cache_ttl_seconds: default_dashboard_cache_ttl(),
+            notify_url: None,
+            queues: default_dashboard_queues(),
+        }
+    }
+}
+
+/// Short-lived Redis copies of expensive read responses (`X-Cache: HIT|MISS`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResponseCacheConfig {
+    /// Seconds a cached response is served; `0` turns the cache off
+    #[serde(default = "default_response_cache_ttl")]
+    pub ttl_seconds: u64,
+}
+
+fn default_response_cache_ttl() -> u64 {
+    15
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: default_response_cache_ttl(),
+        }
+    }
+}
+
+/// Services probed by `GET /api/system/health` besides this server's own dependencies
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SystemHealthConfig {
+    /// How long each service gets to answer before it is reported as degraded
+    #[serde(default = "default_system_health_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default = "default_system_health_services")]
+    pub services: Vec<HealthEndpointConfig>,
+}
+
+/// A service health endpoint; any 2xx answer counts as healthy
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct HealthEndpointConfig {
+    pub name: String,
+    pub url: String,
+}
+
+fn default_system_health_timeout_ms() -> u64 {
+    2000
+}
+
+fn default_system_health_services() -> Vec<HealthEndpointConfig> {
+    vec![
+        HealthEndpointConfig {
+            name: "notify_server".to_string(),
+            url: "http://notify-server:6687/health".to_string(),
+        },
+        HealthEndpointConfig {
+            name: "analytics_server".to_string(),
+            url: "http://analytics-server:6690/health".to_string(),
+        },
+    ]
+}
+
+impl Default for SystemHealthConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: default_system_health_timeout_ms(),
+            services: default_system_health_services(),
+        }
+    }
+}
+
+/// Scanning of uploaded attachments; files are quarantined until their scan passes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileScanConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub scanner: FileScannerKind,
+    /// clamd TCP address, for the `clamav` scanner
+    #[serde(default = "default_clamav_address")]
+    pub clamav_address: String,
+    /// Endpoint receiving the file as the request body, for the `http` scanner
+    #[serde(default)]
+    pub http_url: Option<String>,
+    /// Longest a scan may take before the file is marked failed (and stays quarantined)
+    #[serde(default = "default_file_scan_timeout_ms")]
+    pub scan_timeout_ms: u64,
+    /// Longest an upload response waits for the verdict; slower scans finish in the background
+    #[serde(default = "default_file_scan_response_timeout_ms")]
+    pub response_timeout_ms: u64,
+}
+
+/// Backend that scans attachments
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileScannerKind {
+    /// Passes every file
+    #[default]
+    Noop,
+    Clamav,
+    Http,
+}
+
+fn default_clamav_address() -> String {
+    "127.0.0.1:3310".to_string()
+}
+
+fn default_file_scan_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_file_scan_response_timeout_ms() -> u64 {
+    2000
+}
+
+impl Default for FileScanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scanner: FileScannerKind::default(),
+            clamav_address: default_clamav_address(),
+            http_url: None,
+            scan_timeout_ms: default_file_scan_timeout_ms(),
+            response_timeout_ms: default_file_scan_response_timeout_ms(),
+        }
+    }
+}
+
+/// Storage of dead-lettered NATS events, which admins can replay via
+/// `POST /api/admin/events/replay`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeadLetterConfig {
+    /// Consume the dead letter subjects into the `event_dead_letters` table
+    #[serde(default)]
+    pub enabled: bool,
+    /// Subjects carrying `{"subject", "payload", "error"}` dead letter records
+    #[serde(default = "default_dead_letter_subjects")]
+    pub subjects: Vec<String>,
+    /// Replays allowed per event; further replays of it are skipped
+    #[serde(default = "default_dead_letter_max_replays")]
+    pub max_replays: i32,
+    /// Most events re-published by one replay request
+    #[serde(default = "default_dead_letter_replay_batch")]
+    pub replay_batch: i64,
+}
+
+fn default_dead_letter_subjects() -> Vec<String> {
+    vec!["fechatter.notify.dlq".to_string()]
+}
+
+fn default_dead_letter_max_replays() -> i32 {
+    3
+}
+
+fn default_dead_letter_replay_batch() -> i64 {
+    500
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            subjects: default_dead_letter_subjects(),
+            max_replays: default_dead_letter_max_replays(),
+            replay_batch: default_dead_letter_replay_batch(),
+        }
+    }
+}
+
+/// Bulk account creation via `POST /api/admin/users/import`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserImportConfig {
+    /// Most rows accepted by one import request
+    #[serde(default = "default_import_max_rows")]
+    pub max_rows: usize,
+}
+
+fn default_import_max_rows() -> usize {
+    200
+}
+
+impl Default for UserImportConfig {
+    fn default() -> Self {
+        Self {
+            max_rows: default_import_max_rows(),
+        }
+    }
+}
+
+impl SystemHealthConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NotificationConfig {
     pub in_app_enabled: bool,
@@ -538,17 +1352,78 @@ pub struct ObservabilityConfig {
     pub log_file_path: String,
     pub metrics_enabled: bool,
     pub metrics_bind_address: String,
+    /// How often DB/Redis pool gauges are refreshed
+    #[serde(default = "default_pool_metrics_interval_seconds")]
+    pub pool_metrics_interval_seconds: u64,
+    /// Labeled repository queries taking at least this long are logged; `0` turns it off
+    #[serde(default = "default_slow_query_threshold_ms")]
+    pub slow_query_threshold_ms: u64,
     pub tracing_enabled: bool,
     pub service_name: String,
     pub service_version: String,
     pub environment: String,
 }
 
+fn default_pool_metrics_interval_seconds() -> u64 {
+    15
+}
+
+fn default_slow_query_threshold_ms() -> u64 {
+    200
+}
+
 /// Storage configuration
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StorageConfig {
     pub path: String,
     pub url_prefix: String,
+    /// Where uploads are kept; replicas behind a load balancer need a shared `s3` bucket
+    #[serde(default)]
+    pub backend: StorageBackend,
+    /// Bucket settings, required for the `s3` backend
+    #[serde(default)]
+    pub s3: Option<S3StorageConfig>,
+}
+
+/// File storage backend
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// Files under `storage.path`, served by this instance
+    #[default]
+    Local,
+    /// An S3-compatible bucket (AWS S3, MinIO, R2); needs the `s3` build feature
+    S3,
+}
+
+/// S3-compatible bucket for uploads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3StorageConfig {
+    pub bucket: String,
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    /// Endpoint of a non-AWS store such as MinIO; path-style addressing is used with it
+    #[serde(default)]
+    pub endpoint_url: Option<String>,
+    /// Static credentials; the AWS default chain (environment, profile, instance role) when unset
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    /// Prepended to every object key, e.g. `uploads/`
+    #[serde(default)]
+    pub key_prefix: String,
+    /// How long a signed download URL stays valid
+    #[serde(default = "default_signed_url_ttl_secs")]
+    pub signed_url_ttl_secs: u64,
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_signed_url_ttl_secs() -> u64 {
+    3600
 }
 
 // ============================================================================
@@ -564,6 +1439,7 @@ impl Default for CacheConfig {
             default_ttl: 300,
             pool_size: 10,
             connection_timeout_ms: 5000,
+            degradation: CacheDegradationConfig::default(),
             ttl: Duration::from_secs(300),
             cache_private: false,
             user_specific: false,
@@ -661,6 +1537,70 @@ pub enum ConfigError {
     JsonParse(#[from] serde_json::Error),
 }
 
+impl RedactedSummary for AppConfig {
+    fn redacted_summary(&self) -> String {
+        let features = &self.features;
+        let state = |enabled: bool| if enabled { "Enabled" } else { "Disabled" };
+
+        [
+            "Configuration Summary:".to_string(),
+            format!(
+                "   Server: Port {}, DB: {}",
+                self.server.port,
+                redact_url(&self.server.db_url)
+            ),
+            format!(
+                "   Auth: Access token {}s, refresh token {}s, keys: sk {}, pk {}",
+                self.auth.token_expiration,
+                self.auth.refresh_token_expiration,
+                redact_secret(&self.auth.sk),
+                redact_secret(&self.auth.pk)
+            ),
+            format!(
+                "   Cache: {} ({})",
+                state(features.cache.enabled),
+                redact_url(&features.cache.redis_url)
+            ),
+            format!(
+                "   Search: {} ({} at {}, API key {})",
+                state(features.search.enabled),
+                features.search.provider,
+                redact_url(&features.search.meilisearch_url),
+                redact_secret(&features.search.meilisearch_api_key)
+            ),
+            format!(
+                "   Messaging: {} ({} at {})",
+                state(features.messaging.enabled),
+                features.messaging.provider,
+                redact_url(&features.messaging.nats_url)
+            ),
+            format!(
+                "   Email: {} ({}:{}, SMTP password {})",
+                state(features.notifications.email_enabled),
+                features.notifications.smtp_host,
+                features.notifications.smtp_port,
+                redact_secret(&features.notifications.smtp_password)
+            ),
+            match &self.storage.s3 {
+                Some(s3) if self.storage.backend == StorageBackend::S3 => format!(
+                    "   Storage: S3 (bucket {} at {}, secret key {})",
+                    s3.bucket,
+                    s3.endpoint_url
+                        .as_deref()
+                        .map(redact_url)
+                        .unwrap_or_else(|| s3.region.clone()),
+                    redact_optional_secret(s3.secret_access_key.as_deref())
+                ),
+                _ => format!(
+                    "   Storage: {:?} ({})",
+                    self.storage.backend, self.storage.path
+                ),
+            },
+        ]
+        .join("\n")
+    }
+}
+
 impl AppConfig {
     /// Production-grade configuration loading - enhanced search only
     pub fn load() -> Result<Self> {
@@ -670,7 +1610,7 @@ impl AppConfig {
             match Self::from_file(&config_path) {
                 Ok(config) => {
                     println!("Configuration loaded successfully!");
-                    Self::print_config_summary(&config);
+                    println!("{}", config.redacted_summary());
                     return Ok(config);
                 }
                 Err(e) => {
@@ -692,7 +1632,7 @@ impl AppConfig {
                 match Self::from_file(path.to_str().unwrap()) {
                     Ok(config) => {
                         println!("Configuration loaded successfully!");
-                        Self::print_config_summary(&config);
+                        println!("{}", config.redacted_summary());
                         return Ok(config);
                     }
                     Err(e) => {
@@ -708,6 +1648,18 @@ impl AppConfig {
         anyhow::bail!("💥 No valid configuration file found!")
     }
 
+    /// Path `load()` would read from: `FECHATTER_CONFIG`, else the first existing search location
+    pub fn locate_file() -> Option<PathBuf> {
+        if let Ok(config_path) = env::var("FECHATTER_CONFIG") {
+            return Some(PathBuf::from(config_path));
+        }
+
+        Self::get_production_search_locations()
+            .into_iter()
+            .map(|(_, path)| path)
+            .find(|path| path.exists())
+    }
+
     /// Get production-focused search locations - enhanced with Docker support
     fn get_production_search_locations() -> Vec<(String, PathBuf)> {
         let mut locations = Vec::new();
@@ -855,53 +1807,6 @@ impl AppConfig {
         }
     }
 
-    /// Print configuration summary
-    fn print_config_summary(config: &Self) {
-        println!("Configuration Summary:");
-        println!(
-            "   Server: Port {}, DB: {}",
-            config.server.port,
-            Self::mask_db_url(&config.server.db_url)
-        );
-        println!(
-            "   Cache: {}",
-            if config.features.cache.enabled {
-                "Enabled"
-            } else {
-                "ERROR: Disabled"
-            }
-        );
-        println!(
-            "   Search: {}",
-            if config.features.search.enabled {
-                "Enabled"
-            } else {
-                "ERROR: Disabled"
-            }
-        );
-        println!(
-            "   Messaging: {}",
-            if config.features.messaging.enabled {
-                "Enabled"
-            } else {
-                "ERROR: Disabled"
-            }
-        );
-    }
-
-    /// Mask sensitive parts of database URL
-    fn mask_db_url(url: &str) -> String {
-        if let Some(at_pos) = url.find('@') {
-            if let Some(colon_pos) = url[..at_pos].rfind(':') {
-                format!("{}:***@{}", &url[..colon_pos], &url[at_pos + 1..])
-            } else {
-                url.to_string()
-            }
-        } else {
-            url.to_string()
-        }
-    }
-
     /// Show helpful error message when no config is found (simplified)
     fn show_helpful_error(search_locations: &[(String, PathBuf)]) {
         println!("\nSearched for configuration in:");
@@ -1248,3 +2153,34 @@ impl MonitoringConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_summary_should_never_contain_secrets() {
+        let mut config = AppConfig::load().expect("config");
+        config.server.db_url = "postgres://fechatter:db-s3cret@db:5432/fechatter".to_string();
+        config.features.cache.redis_url = "redis://:redis-s3cret@cache:6379/0".to_string();
+        config.features.notifications.smtp_password = "smtp-s3cret".to_string();
+        config.storage.backend = StorageBackend::S3;
+        config.storage.s3 = Some(S3StorageConfig {
+            bucket: "uploads".to_string(),
+            region: default_s3_region(),
+            endpoint_url: Some("http://minio:9000".to_string()),
+            access_key_id: Some("minio".to_string()),
+            secret_access_key: Some("bucket-s3cret".to_string()),
+            key_prefix: String::new(),
+            signed_url_ttl_secs: default_signed_url_ttl_secs(),
+        });
+
+        let summary = config.redacted_summary();
+        assert!(!summary.contains(&config.auth.sk));
+        assert!(!summary.contains(&config.auth.pk));
+        for secret in ["db-s3cret", "redis-s3cret", "smtp-s3cret", "bucket-s3cret"] {
+            assert!(!summary.contains(secret), "summary leaks {secret}");
+        }
+        assert!(summary.contains("postgres://fechatter:***@db:5432/fechatter"));
+    }
+}
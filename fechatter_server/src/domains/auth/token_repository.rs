@@ -23,6 +23,8 @@ pub struct RefreshTokenEntity {
     pub user_agent: Option<String>,
     pub ip_address: Option<String>,
     pub absolute_expires_at: DateTime<Utc>,
+    /// When the session last presented a refresh token; a rotated token starts at its issue
+    pub last_used_at: DateTime<Utc>,
 }
 
 // Domain-specific payloads
@@ -100,7 +102,7 @@ impl RefreshTokenStorage {
       r#"
       INSERT INTO refresh_tokens (user_id, token_hash, expires_at, user_agent, ip_address, absolute_expires_at)
       VALUES ($1, $2, $3, $4, $5, $6)
-      RETURNING id, user_id, token_hash, expires_at, issued_at, revoked, replaced_by, user_agent, ip_address, absolute_expires_at
+      RETURNING id, user_id, token_hash, expires_at, issued_at, revoked, replaced_by, user_agent, ip_address, absolute_expires_at, last_used_at
       "#,
     )
     .bind(user_id)
@@ -123,7 +125,7 @@ impl RefreshTokenStorage {
 
         let refresh_token = sqlx::query_as::<_, RefreshTokenEntity>(
       r#"
-      SELECT id, user_id, token_hash, expires_at, issued_at, revoked, replaced_by, user_agent, ip_address, absolute_expires_at
+      SELECT id, user_id, token_hash, expires_at, issued_at, revoked, replaced_by, user_agent, ip_address, absolute_expires_at, last_used_at
       FROM refresh_tokens
       WHERE token_hash = $1 AND revoked = FALSE AND expires_at > NOW()
       "#,
@@ -171,6 +173,29 @@ impl RefreshTokenStorage {
         Ok(())
     }
 
+    /// Revokes every live refresh token of a user except `keep_token_id`, the session
+    /// being kept. Rotated-out ancestors of that session are already revoked, so this
+    /// only ends the user's other token families. Returns the number of tokens revoked.
+    pub async fn revoke_all_except(
+        user_id: i64,
+        keep_token_id: i64,
+        pool: &PgPool,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+      UPDATE refresh_tokens
+      SET revoked = TRUE
+      WHERE user_id = $1 AND id <> $2 AND revoked = FALSE
+      "#,
+        )
+        .bind(user_id)
+        .bind(keep_token_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     pub async fn replace(
         old_token_id: i64,
         user_id: i64,
@@ -230,7 +255,7 @@ impl RefreshTokenStorage {
         // Replace the old token with the new one
         let query = r#"
       UPDATE refresh_tokens
-      SET revoked = TRUE, replaced_by = $1
+      SET revoked = TRUE, replaced_by = $1, last_used_at = NOW()
       WHERE id = $2
     "#;
 
@@ -245,7 +270,7 @@ impl RefreshTokenStorage {
       r#"
       INSERT INTO refresh_tokens (user_id, token_hash, expires_at, user_agent, ip_address, absolute_expires_at)
       VALUES ($1, $2, $3, $4, $5, $6)
-      RETURNING id, user_id, token_hash, expires_at, issued_at, revoked, replaced_by, user_agent, ip_address, absolute_expires_at
+      RETURNING id, user_id, token_hash, expires_at, issued_at, revoked, replaced_by, user_agent, ip_address, absolute_expires_at, last_used_at
       "#,
     )
     .bind(user_id)
@@ -288,6 +313,17 @@ impl RefreshTokenRepositoryImpl {
     pub fn new(pool: Arc<PgPool>) -> Self {
         Self { pool }
     }
+
+    /// Revoke all of a user's refresh tokens except `keep_token_id`; returns the count revoked
+    pub async fn revoke_all_except(
+        &self,
+        user_id: UserId,
+        keep_token_id: i64,
+    ) -> Result<u64, CoreError> {
+        RefreshTokenStorage::revoke_all_except(user_id.into(), keep_token_id, &self.pool)
+            .await
+            .map_err(|e| CoreError::Internal(e.to_string()))
+    }
 }
 
 // Compatibility adapter for fechatter_core traits
@@ -577,6 +613,7 @@ mod tests {
                 user_agent: _payload.user_agent,
                 ip_address: _payload.ip_address,
                 absolute_expires_at: _payload.absolute_expires_at,
+                last_used_at: now,
             })
         }
     }
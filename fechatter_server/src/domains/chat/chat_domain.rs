@@ -2,13 +2,17 @@
 
 use async_trait::async_trait;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 
 use super::{chat_member_repository::ChatMemberRepository, repository::ChatRepository};
+use crate::config::ChatLimitsConfig;
+use crate::domains::permission::{ChatAction, PermissionService};
+use crate::services::infrastructure::event::ChatLifecycle;
 use crate::services::infrastructure::flows::SimplifiedEventPublisher;
 use fechatter_core::{
     error::CoreError,
     models::{Chat, ChatSidebar, CreateChat},
+    UserId,
 };
 use sqlx::Row;
 
@@ -48,6 +52,23 @@ pub trait ChatDomainService: Send + Sync {
         current_owner_id: i64,
         new_owner_id: i64,
     ) -> Result<(), CoreError>;
+
+    /// The direct message chat between two users of a workspace, created on first use
+    async fn get_or_create_direct_chat(
+        &self,
+        user_id: i64,
+        other_user_id: i64,
+        workspace_id: i64,
+    ) -> Result<Chat, CoreError>;
+
+    /// Turn a direct message into a group chat owned by `user_id`, so members can be added.
+    /// Either participant may promote it; members and history are kept
+    async fn promote_to_group(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        name: Option<String>,
+    ) -> Result<Chat, CoreError>;
 }
 
 #[derive(Debug, Clone)]
@@ -56,16 +77,57 @@ pub struct ChatConfig {
     pub cache_ttl: u64,
     pub max_name_length: usize,
     pub max_description_length: usize,
+    /// Chats a single user may create
+    pub max_chats_per_user: usize,
+    /// Members a single chat may hold, creator included
+    pub max_members_per_chat: usize,
 }
 
 impl Default for ChatConfig {
     fn default() -> Self {
+        let limits = ChatLimitsConfig::default();
         Self {
             cache_enabled: true,
             cache_ttl: 300, // 5 minutes
             max_name_length: 128,
             max_description_length: 500,
+            max_chats_per_user: limits.max_chats_per_user,
+            max_members_per_chat: limits.max_members_per_chat,
+        }
+    }
+}
+
+impl ChatConfig {
+    /// Default settings with the configured chat caps
+    pub fn with_limits(limits: &ChatLimitsConfig) -> Self {
+        Self {
+            max_chats_per_user: limits.max_chats_per_user,
+            max_members_per_chat: limits.max_members_per_chat,
+            ..Self::default()
+        }
+    }
+
+    /// Reject another chat from a user who has already created `created` chats
+    pub fn check_chat_quota(&self, created: i64) -> Result<(), CoreError> {
+        if created >= self.max_chats_per_user as i64 {
+            return Err(CoreError::Validation(format!(
+                "Chat limit reached: a user may create at most {} chats",
+                self.max_chats_per_user
+            )));
         }
+        Ok(())
+    }
+
+    /// Reject growing a chat of `current` members by `adding` past the member cap
+    pub fn check_member_capacity(&self, current: i64, adding: usize) -> Result<(), CoreError> {
+        let total = current + adding as i64;
+        if total > self.max_members_per_chat as i64 {
+            return Err(CoreError::Validation(format!(
+                "Member limit exceeded: a chat may hold at most {} members, got {}",
+                self.max_members_per_chat, total
+            )));
+        }
+        Ok(())
     }
 }
 
@@ -73,7 +135,8 @@ impl Default for ChatConfig {
 pub struct ChatDomainServiceImpl {
     chat_repository: Arc<ChatRepository>,
     chat_member_repository: Arc<ChatMemberRepository>,
-    _event_publisher: Arc<SimplifiedEventPublisher>, // 暂时不使用，避免编译错误
+    event_publisher: Arc<SimplifiedEventPublisher>,
+    permissions: Arc<PermissionService>,
     config: ChatConfig,
 }
 
@@ -82,12 +145,14 @@ impl ChatDomainServiceImpl {
         chat_repository: Arc<ChatRepository>,
         chat_member_repository: Arc<ChatMemberRepository>,
         event_publisher: Arc<SimplifiedEventPublisher>,
+        permissions: Arc<PermissionService>,
         config: ChatConfig,
     ) -> Self {
         Self {
             chat_repository,
             chat_member_repository,
-            _event_publisher: event_publisher,
+            event_publisher,
+            permissions,
             config,
         }
     }
@@ -143,18 +208,56 @@ impl ChatDomainServiceImpl {
         Ok(())
     }
 
-    /// Business logic for checking admin permissions
-    async fn check_admin_permissions(&self, chat_id: i64, user_id: i64) -> Result<(), CoreError> {
-        // Check if user is the creator of the chat
-        let is_creator = self
+    /// Active members of a chat, falling back to the member list stored on the chat
+    async fn member_ids(&self, chat: &Chat) -> Vec<i64> {
+        match self
             .chat_member_repository
-            .is_user_creator(chat_id, user_id)
-            .await?;
-        if !is_creator {
-            return Err(CoreError::Unauthorized(
-                "Only chat creator can perform this action".to_string(),
-            ));
+            .list_members(i64::from(chat.id))
+            .await
+        {
+            Ok(members) => members
+                .into_iter()
+                .map(|member| i64::from(member.user_id))
+                .collect(),
+            Err(e) => {
+                warn!("Failed to list members of chat {}: {}", chat.id, e);
+                chat.chat_members.iter().map(|&id| i64::from(id)).collect()
+            }
         }
+    }
+
+    /// Publish a chat event to its members; the change itself has already been committed, so a
+    /// failure is only logged
+    async fn publish_chat_event(
+        &self,
+        kind: ChatLifecycle,
+        chat: &Chat,
+        actor_id: i64,
+        members: &[i64],
+    ) {
+        if let Err(e) = self
+            .event_publisher
+            .publish_chat_event(kind, chat, actor_id, members)
+            .await
+        {
+            warn!(
+                "Failed to publish {:?} event for chat {}: {}",
+                kind, chat.id, e
+            );
+        }
+    }
+
+    /// Require the user's chat role to allow `action`
+    async fn check_chat_action(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        action: ChatAction,
+    ) -> Result<(), CoreError> {
+        self.permissions
+            .can_manage_chat(UserId(user_id), chat_id, action)
+            .await?
+            .check()?;
         Ok(())
     }
 }
@@ -170,14 +273,24 @@ impl ChatDomainService for ChatDomainServiceImpl {
         // Validate business rules
         self.validate_chat_creation(&input)?;
 
+        // Enforce chat caps: +1 member for the creator
+        let created = self
+            .chat_repository
+            .count_chats_created_by(created_by)
+            .await?;
+        self.config.check_chat_quota(created)?;
+        let initial_members = input.members.as_ref().map_or(0, |members| members.len());
+        self.config.check_member_capacity(1, initial_members)?;
+
         // Create through repository
         let chat = self
             .chat_repository
             .create_chat(input.clone(), created_by, workspace_id)
             .await?;
 
-        // TODO: 重新设计事件发布机制
-        // 暂时注释掉事件发布代码以解决编译错误
+        let members = self.member_ids(&chat).await;
+        self.publish_chat_event(ChatLifecycle::Created, &chat, created_by, &members)
+            .await;
         info!("Chat created: {}", chat.id);
 
         Ok(chat)
@@ -198,7 +311,8 @@ impl ChatDomainService for ChatDomainServiceImpl {
         new_name: String,
     ) -> Result<Chat, CoreError> {
         // Check permissions
-        self.check_admin_permissions(chat_id, user_id).await?;
+        self.check_chat_action(chat_id, user_id, ChatAction::UpdateDetails)
+            .await?;
 
         // Validate new name
         self.validate_chat_update(&new_name, None)?;
@@ -209,7 +323,9 @@ impl ChatDomainService for ChatDomainServiceImpl {
             .update_chat_name(chat_id, user_id, &new_name)
             .await?;
 
-        // TODO: 重新设计事件发布机制
+        let members = self.member_ids(&updated_chat).await;
+        self.publish_chat_event(ChatLifecycle::Updated, &updated_chat, user_id, &members)
+            .await;
         info!("Chat {} name updated by user {}", chat_id, user_id);
 
         Ok(updated_chat)
@@ -222,7 +338,8 @@ impl ChatDomainService for ChatDomainServiceImpl {
         new_description: String,
     ) -> Result<Chat, CoreError> {
         // Check permissions
-        self.check_admin_permissions(chat_id, user_id).await?;
+        self.check_chat_action(chat_id, user_id, ChatAction::UpdateDetails)
+            .await?;
 
         // Validate new description
         self.validate_chat_update("dummy", Some(&new_description))?; // Name validation will be skipped
@@ -233,23 +350,36 @@ impl ChatDomainService for ChatDomainServiceImpl {
             .update_chat_description(chat_id, user_id, &new_description)
             .await?;
 
-        // TODO: 重新设计事件发布机制
+        let members = self.member_ids(&updated_chat).await;
+        self.publish_chat_event(ChatLifecycle::Updated, &updated_chat, user_id, &members)
+            .await;
         info!("Chat {} description updated by user {}", chat_id, user_id);
 
         Ok(updated_chat)
     }
 
     async fn delete_chat(&self, chat_id: i64, user_id: i64) -> Result<bool, CoreError> {
-        // Check permissions (only creator can delete)
-        self.check_admin_permissions(chat_id, user_id).await?;
+        // Check permissions (only the owner can delete)
+        self.check_chat_action(chat_id, user_id, ChatAction::Delete)
+            .await?;
 
         // Additional business rule: Check if chat has active messages
         // Note: Message count check could be implemented if needed
 
+        // Members are gone with the chat, so collect them first
+        let chat = self.chat_repository.find_chat_by_id(chat_id).await?;
+        let members = match &chat {
+            Some(chat) => self.member_ids(chat).await,
+            None => Vec::new(),
+        };
+
         // Delete through core repository
         self.chat_repository.delete_chat(chat_id, user_id).await?;
 
-        // TODO: 重新设计事件发布机制
+        if let Some(chat) = chat {
+            self.publish_chat_event(ChatLifecycle::Deleted, &chat, user_id, &members)
+                .await;
+        }
         info!("Chat {} deleted by user {}", chat_id, user_id);
 
         Ok(true) // Always return true since delete_chat returns ()
@@ -269,7 +399,7 @@ impl ChatDomainService for ChatDomainServiceImpl {
         }
 
         // Check if current user is actually the owner
-        self.check_admin_permissions(chat_id, current_owner_id)
+        self.check_chat_action(chat_id, current_owner_id, ChatAction::TransferOwnership)
             .await?;
 
         // Check if new owner is a member of the chat
@@ -310,7 +440,18 @@ impl ChatDomainService for ChatDomainServiceImpl {
             ));
         }
 
-        // TODO: 重新设计事件发布机制
+        let chat = match self.chat_repository.find_chat_by_id(chat_id).await {
+            Ok(Some(transferred_chat)) => transferred_chat,
+            _ => chat,
+        };
+        let members = self.member_ids(&chat).await;
+        self.publish_chat_event(
+            ChatLifecycle::OwnershipTransferred,
+            &chat,
+            current_owner_id,
+            &members,
+        )
+        .await;
         info!(
             "Chat {} ownership transferred from user {} to user {}",
             chat_id, current_owner_id, new_owner_id
@@ -318,4 +459,95 @@ impl ChatDomainService for ChatDomainServiceImpl {
 
         Ok(())
     }
+
+    async fn get_or_create_direct_chat(
+        &self,
+        user_id: i64,
+        other_user_id: i64,
+        workspace_id: i64,
+    ) -> Result<Chat, CoreError> {
+        if user_id == other_user_id {
+            return Err(CoreError::Validation(
+                "Cannot start a direct message with yourself".to_string(),
+            ));
+        }
+
+        // Business rule: Direct messages stay within a workspace
+        match self
+            .chat_repository
+            .find_user_workspace(other_user_id)
+            .await?
+        {
+            Some(other_workspace) if other_workspace == workspace_id => {}
+            Some(_) => {
+                return Err(CoreError::Validation(
+                    "Direct messages are only possible within your workspace".to_string(),
+                ))
+            }
+            None => {
+                return Err(CoreError::NotFound(format!(
+                    "User {} not found",
+                    other_user_id
+                )))
+            }
+        }
+
+        let (chat, created) = self
+            .chat_repository
+            .get_or_create_direct_chat(user_id, other_user_id, workspace_id)
+            .await?;
+
+        if created {
+            let members = [user_id, other_user_id];
+            self.publish_chat_event(ChatLifecycle::Created, &chat, user_id, &members)
+                .await;
+            info!(
+                "Direct chat {} created between users {} and {}",
+                chat.id, user_id, other_user_id
+            );
+        }
+
+        Ok(chat)
+    }
+
+    async fn promote_to_group(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        name: Option<String>,
+    ) -> Result<Chat, CoreError> {
+        // Either participant may promote, and becomes the group's owner
+        self.check_chat_permissions(chat_id, user_id).await?;
+        if let Some(name) = &name {
+            self.validate_chat_update(name, None)?;
+        }
+
+        let chat = self
+            .chat_repository
+            .find_chat_by_id(chat_id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Chat {} not found", chat_id)))?;
+        let not_direct =
+            || CoreError::Validation("Only direct messages can be promoted to a group".to_string());
+        if !matches!(chat.chat_type, fechatter_core::models::ChatType::Single) {
+            return Err(not_direct());
+        }
+
+        // A concurrent promotion may have won since the chat was read
+        let promoted = self
+            .chat_repository
+            .promote_direct_chat(chat_id, user_id, name.as_deref())
+            .await?
+            .ok_or_else(not_direct)?;
+
+        let members = self.member_ids(&promoted).await;
+        self.publish_chat_event(ChatLifecycle::Updated, &promoted, user_id, &members)
+            .await;
+        info!(
+            "Direct chat {} promoted to a group by user {}",
+            chat_id, user_id
+        );
+
+        Ok(promoted)
+    }
 }
@@ -3,7 +3,9 @@ use std::future::Future;
 use std::sync::Arc;
 use tracing::{info, warn};
 
+use crate::domains::chat::chat_domain::ChatConfig;
 use fechatter_core::{
+    contracts::PresenceStatus,
     error::CoreError,
     models::ChatMemberRepository as CoreChatMemberRepository,
     models::{ChatId, ChatMember, ChatType, UserId},
@@ -88,6 +90,23 @@ impl ChatMembershipStatus {
     }
 }
 
+/// Values of the `chat_member_role` enum
+pub const CHAT_MEMBER_ROLES: &[&str] = &["owner", "admin", "moderator", "member"];
+
+/// Chat member with the profile fields needed for member lists
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMemberListing {
+    pub chat_id: i64,
+    pub user_id: i64,
+    pub username: String,
+    pub fullname: String,
+    pub role: String,
+    pub joined_at: chrono::DateTime<chrono::Utc>,
+    pub is_creator: bool,
+    /// Filled from the presence store by the caller; offline when loaded
+    pub presence: PresenceStatus,
+}
+
 pub struct ChatMemberRepository {
     pool: Arc<PgPool>,
 }
@@ -119,7 +138,8 @@ impl ChatMemberRepository {
         chat_id: ChatId,
         user_id: UserId,
         member_ids: Vec<UserId>,
-    ) -> Result<Vec<ChatMember>, CoreError> {
+        capacity: Option<&ChatConfig>,
+    ) -> Result<u64, CoreError> {
         let chat_id = i64::from(chat_id);
         let user_id = i64::from(user_id);
 
@@ -140,12 +160,31 @@ impl ChatMemberRepository {
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
+        // Lock the chat row so concurrent additions count each other's members
+        // before checking the cap
+        if let Some(config) = capacity {
+            sqlx::query("SELECT id FROM chats WHERE id = $1 FOR UPDATE")
+                .bind(chat_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| CoreError::from_database_error(e))?;
+            let current: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM chat_members WHERE chat_id = $1 AND left_at IS NULL",
+            )
+            .bind(chat_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| CoreError::from_database_error(e))?;
+            config.check_member_capacity(current, member_ids.len())?;
+        }
+
         // Add members to the chat
+        let mut added = 0;
         for &member_id in &member_ids {
             let member_id = i64::from(member_id);
 
-            // 1. Update the array in chats table
-            sqlx::query(
+            // 1. Update the array in chats table (no-op for existing members)
+            added += sqlx::query(
                 r#"UPDATE chats SET chat_members = array_append(chat_members, $1)
                    WHERE id = $2 AND NOT $1 = ANY(chat_members)"#,
             )
@@ -153,14 +192,18 @@ impl ChatMemberRepository {
             .bind(chat_id)
             .execute(&mut *tx)
             .await
-            .map_err(|e| CoreError::from_database_error(e))?;
+            .map_err(|e| CoreError::from_database_error(e))?
+            .rows_affected();
 
-            // 2. Insert into chat_members table with proper role, or reactivate if previously left
+            // 2. Insert into chat_members table with proper role, or reactivate if previously left;
+            //    a rejoin counts as joining again for delta sync
             sqlx::query(
                 r#"INSERT INTO chat_members (chat_id, user_id, role) 
            VALUES ($1, $2, 'member'::chat_member_role)
            ON CONFLICT (chat_id, user_id) 
-           DO UPDATE SET left_at = NULL, role = 'member'::chat_member_role"#,
+           DO UPDATE SET left_at = NULL, role = 'member'::chat_member_role,
+             joined_at = CASE WHEN chat_members.left_at IS NULL
+                              THEN chat_members.joined_at ELSE NOW() END"#,
             )
             .bind(chat_id)
             .bind(member_id)
@@ -174,8 +217,7 @@ impl ChatMemberRepository {
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
-        // Return the current member list
-        self.list_members_impl(ChatId(chat_id)).await
+        Ok(added)
     }
 
     /// Remove members implementation - FIXED: Use proper error mapping
@@ -184,7 +226,7 @@ impl ChatMemberRepository {
         chat_id: ChatId,
         user_id: UserId,
         member_ids: Vec<UserId>,
-    ) -> Result<bool, CoreError> {
+    ) -> Result<u64, CoreError> {
         let chat_id = i64::from(chat_id);
         let user_id = i64::from(user_id);
 
@@ -206,6 +248,7 @@ impl ChatMemberRepository {
             .map_err(|e| CoreError::from_database_error(e))?;
 
         // Remove members from the chat
+        let mut removed = 0;
         for &member_id in &member_ids {
             let member_id = i64::from(member_id);
 
@@ -214,16 +257,17 @@ impl ChatMemberRepository {
                 continue;
             }
 
-            // 1. Update the array in chats table
-            sqlx::query(
+            // 1. Update the array in chats table (no-op for non-members)
+            removed += sqlx::query(
                 r#"UPDATE chats SET chat_members = array_remove(chat_members, $1)
-                   WHERE id = $2"#,
+                   WHERE id = $2 AND $1 = ANY(chat_members)"#,
             )
             .bind(member_id)
             .bind(chat_id)
             .execute(&mut *tx)
             .await
-            .map_err(|e| CoreError::from_database_error(e))?;
+            .map_err(|e| CoreError::from_database_error(e))?
+            .rows_affected();
 
             // 2. Mark as left in chat_members table instead of deleting
             sqlx::query(
@@ -243,7 +287,7 @@ impl ChatMemberRepository {
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
-        Ok(true)
+        Ok(removed)
     }
 
     /// List members implementation - FIXED: Use proper error mapping
@@ -316,12 +360,13 @@ impl ChatMemberRepository {
     async fn count_members_impl(&self, chat_id: ChatId) -> Result<i64, CoreError> {
         let chat_id = i64::from(chat_id);
 
-        let count: i64 =
-            sqlx::query_scalar("SELECT array_length(chat_members, 1) FROM chats WHERE id = $1")
-                .bind(chat_id)
-                .fetch_one(&*self.pool)
-                .await
-                .map_err(|e| CoreError::from_database_error(e))?;
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(array_length(chat_members, 1), 0)::BIGINT FROM chats WHERE id = $1",
+        )
+        .bind(chat_id)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
 
         Ok(count)
     }
@@ -350,7 +395,7 @@ impl ChatMemberRepository {
     /// Add members to a chat (convenience method)
     pub async fn add_members(&self, chat_id: i64, member_ids: &[i64]) -> Result<(), CoreError> {
         let member_user_ids: Vec<UserId> = member_ids.iter().map(|&id| UserId(id)).collect();
-        self.add_members_impl(ChatId(chat_id), UserId(0), member_user_ids)
+        self.add_members_impl(ChatId(chat_id), UserId(0), member_user_ids, None)
             .await?;
         Ok(())
     }
@@ -368,6 +413,92 @@ impl ChatMemberRepository {
         self.list_members_impl(ChatId(chat_id)).await
     }
 
+    /// List one page of active members, creator first unless a `(field, descending)` sort is given
+    pub async fn list_members_page(
+        &self,
+        chat_id: i64,
+        role: Option<&str>,
+        limit: i64,
+        offset: i64,
+        sort: Option<(&str, bool)>,
+    ) -> Result<Vec<ChatMemberListing>, CoreError> {
+        // Column names come from a fixed match, never from the request
+        let order_by = match sort {
+            Some((field, descending)) => {
+                let column = match field {
+                    "username" => "username",
+                    "role" => "role",
+                    _ => "cm.joined_at",
+                };
+                let direction = if descending { "DESC" } else { "ASC" };
+                format!("{} {}, cm.user_id", column, direction)
+            }
+            None => "is_creator DESC, cm.joined_at, cm.user_id".to_string(),
+        };
+
+        let query = format!(
+            r#"SELECT cm.user_id,
+                COALESCE(u.username, u.fullname) AS username,
+                u.fullname,
+                cm.role::TEXT AS role,
+                cm.joined_at,
+                c.created_by = cm.user_id AS is_creator
+         FROM chat_members cm
+         INNER JOIN chats c ON c.id = cm.chat_id
+         INNER JOIN users u ON u.id = cm.user_id
+         WHERE cm.chat_id = $1 AND cm.left_at IS NULL
+           AND ($4::TEXT IS NULL OR cm.role::TEXT = $4)
+         ORDER BY {}
+         LIMIT $2 OFFSET $3"#,
+            order_by
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(chat_id)
+            .bind(limit)
+            .bind(offset)
+            .bind(role)
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| CoreError::from_database_error(e))?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(ChatMemberListing {
+                    chat_id,
+                    user_id: row.try_get("user_id")?,
+                    username: row.try_get("username")?,
+                    fullname: row.try_get("fullname")?,
+                    role: row.try_get("role")?,
+                    joined_at: row.try_get("joined_at")?,
+                    is_creator: row.try_get("is_creator")?,
+                    presence: PresenceStatus::Offline,
+                })
+            })
+            .collect::<Result<Vec<_>, sqlx::Error>>()
+            .map_err(|e| CoreError::from_database_error(e))
+    }
+
+    /// Count active members of a chat, optionally only those with `role`
+    pub async fn count_active_members(
+        &self,
+        chat_id: i64,
+        role: Option<&str>,
+    ) -> Result<i64, CoreError> {
+        let count: i64 = sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM chat_members
+         WHERE chat_id = $1 AND left_at IS NULL
+           AND ($2::TEXT IS NULL OR role::TEXT = $2)"#,
+        )
+        .bind(chat_id)
+        .bind(role)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(count)
+    }
+
     /// Transfer ownership (convenience method)
     pub async fn transfer_ownership(
         &self,
@@ -678,8 +809,9 @@ impl ChatMemberRepository {
         member_ids: Vec<i64>,
     ) -> Result<Vec<ChatMember>, CoreError> {
         let member_user_ids = member_ids.into_iter().map(UserId).collect();
-        self.add_members_impl(ChatId(chat_id), UserId(user_id), member_user_ids)
-            .await
+        self.add_members_impl(ChatId(chat_id), UserId(user_id), member_user_ids, None)
+            .await?;
+        self.list_members_impl(ChatId(chat_id)).await
     }
 
     /// Remove members (convenience method for server use)
@@ -691,6 +823,38 @@ impl ChatMemberRepository {
     ) -> Result<bool, CoreError> {
         let member_user_ids = member_ids.into_iter().map(UserId).collect();
         self.remove_members_impl(ChatId(chat_id), UserId(user_id), member_user_ids)
+            .await?;
+        Ok(true)
+    }
+
+    /// Add members on behalf of `added_by`, returning how many were actually added;
+    /// the member cap in `capacity` is checked inside the same transaction
+    pub async fn add_members_counted(
+        &self,
+        chat_id: i64,
+        added_by: i64,
+        member_ids: &[i64],
+        capacity: &ChatConfig,
+    ) -> Result<u64, CoreError> {
+        let member_user_ids = member_ids.iter().map(|&id| UserId(id)).collect();
+        self.add_members_impl(
+            ChatId(chat_id),
+            UserId(added_by),
+            member_user_ids,
+            Some(capacity),
+        )
+        .await
+    }
+
+    /// Remove members on behalf of `removed_by`, returning how many were actually removed
+    pub async fn remove_members_counted(
+        &self,
+        chat_id: i64,
+        removed_by: i64,
+        member_ids: &[i64],
+    ) -> Result<u64, CoreError> {
+        let member_user_ids = member_ids.iter().map(|&id| UserId(id)).collect();
+        self.remove_members_impl(ChatId(chat_id), UserId(removed_by), member_user_ids)
             .await
     }
 
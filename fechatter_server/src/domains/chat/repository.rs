@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use sqlx::{PgPool, Row};
 use std::sync::Arc;
 
+use crate::services::infrastructure::observability::slow_query::TimedQuery;
 use fechatter_core::{
     contracts::ChatRepository as CoreChatRepository,
     error::CoreError,
@@ -12,6 +13,25 @@ pub struct ChatRepository {
     pool: Arc<PgPool>,
 }
 
+/// Partial unique index over the names of chats in workspaces that require unique names
+const UNIQUE_CHAT_NAME_INDEX: &str = "chats_workspace_unique_name_key";
+
+/// `Conflict` when `name` is taken in a workspace that requires unique chat names
+fn name_conflict_or_database_error(e: sqlx::Error, name: &str) -> CoreError {
+    match e.as_database_error() {
+        Some(db_err)
+            if db_err.is_unique_violation()
+                && db_err.constraint() == Some(UNIQUE_CHAT_NAME_INDEX) =>
+        {
+            CoreError::Conflict(format!(
+                "A chat named '{}' already exists in this workspace",
+                name.trim()
+            ))
+        }
+        _ => CoreError::Database(e.to_string()),
+    }
+}
+
 impl ChatRepository {
     pub fn new(pool: Arc<PgPool>) -> Self {
         Self { pool }
@@ -41,6 +61,24 @@ impl ChatRepository {
             .await
             .map_err(|e| CoreError::Database(e.to_string()))?;
 
+        let chat = Self::insert_chat(&mut tx, input, created_by, workspace_id, &members).await?;
+
+        // Commit transaction
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(chat)
+    }
+
+    /// Insert a chat and its member records inside `tx`
+    async fn insert_chat(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        input: &CreateChat,
+        created_by: UserId,
+        workspace_id: Option<i64>,
+        members: &[UserId],
+    ) -> Result<Chat, CoreError> {
         // Create the chat
         let chat = sqlx::query_as::<_, Chat>(
       r#"INSERT INTO chats (chat_name, type, description, created_by, workspace_id, chat_members)
@@ -60,15 +98,16 @@ impl ChatRepository {
         .map(|&id| i64::from(id))
         .collect::<Vec<i64>>(),
     )
-    .fetch_one(&mut *tx)
+    .fetch_one(&mut **tx)
+    .timed("chat.insert_chat")
     .await
-    .map_err(|e| CoreError::Database(e.to_string()))?;
+    .map_err(|e| name_conflict_or_database_error(e, &input.name))?;
 
         // Insert all members into chat_members table with appropriate roles
         let chat_id = i64::from(chat.id);
         let created_by_id = i64::from(created_by);
 
-        for &member_id in &members {
+        for &member_id in members {
             let member_id_val = i64::from(member_id);
             let role = if member_id_val == created_by_id {
                 "owner" // Creator gets owner role
@@ -84,17 +123,168 @@ impl ChatRepository {
             .bind(chat_id)
             .bind(member_id_val)
             .bind(role)
+            .execute(&mut **tx)
+            .timed("chat.insert_chat")
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+        }
+
+        Ok(chat)
+    }
+
+    /// The single chat between two users, created in `workspace_id` if there is none yet.
+    /// Returns whether it was created. Concurrent calls for the same pair are serialized by a
+    /// transaction-scoped advisory lock, so they all get the same chat
+    pub async fn get_or_create_direct_chat(
+        &self,
+        user_id: i64,
+        other_user_id: i64,
+        workspace_id: i64,
+    ) -> Result<(Chat, bool), CoreError> {
+        let (low, high) = (user_id.min(other_user_id), user_id.max(other_user_id));
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "SELECT pg_advisory_xact_lock(hashtext(format('direct_chat:%s:%s', $1::BIGINT, $2::BIGINT)))",
+        )
+            .bind(low)
+            .bind(high)
             .execute(&mut *tx)
+            .timed("chat.get_or_create_direct_chat")
             .await
             .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        // Oldest first, so pairs that already have duplicates keep using the same one
+        let existing = sqlx::query_as::<_, Chat>(
+            r#"SELECT id, workspace_id, chat_name as name,
+                      type as chat_type, chat_members, description,
+                      created_by, created_at, updated_at
+               FROM chats
+               WHERE type = 'Single' AND chat_members @> ARRAY[$1::BIGINT, $2::BIGINT]
+                 AND cardinality(chat_members) = 2
+               ORDER BY id
+               LIMIT 1"#,
+        )
+        .bind(low)
+        .bind(high)
+        .fetch_optional(&mut *tx)
+        .timed("chat.get_or_create_direct_chat")
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+        if let Some(chat) = existing {
+            return Ok((chat, false));
         }
 
-        // Commit transaction
+        let input = CreateChat {
+            name: format!("dm-{}-{}", low, high),
+            chat_type: fechatter_core::ChatType::Single,
+            description: None,
+            members: Some(vec![UserId(other_user_id)]),
+        };
+        let members = fechatter_core::models::chat::process_chat_members(
+            &input.chat_type,
+            UserId(user_id),
+            input.members.as_ref(),
+        )?;
+        let chat = Self::insert_chat(
+            &mut tx,
+            &input,
+            UserId(user_id),
+            Some(workspace_id),
+            &members,
+        )
+        .await?;
+
         tx.commit()
             .await
             .map_err(|e| CoreError::Database(e.to_string()))?;
 
-        Ok(chat)
+        Ok((chat, true))
+    }
+
+    /// Turn the direct chat `chat_id` into a group owned by `owner_id`, keeping its members and
+    /// messages. Without a `name` the group is named after its members. `None` when the chat is
+    /// not (or no longer) a direct chat
+    pub async fn promote_direct_chat(
+        &self,
+        chat_id: i64,
+        owner_id: i64,
+        name: Option<&str>,
+    ) -> Result<Option<Chat>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => sqlx::query_scalar::<_, Option<String>>(
+                r#"SELECT string_agg(u.fullname, ', ' ORDER BY u.fullname)
+                   FROM chats c
+                   JOIN users u ON u.id = ANY(c.chat_members)
+                   WHERE c.id = $1"#,
+            )
+            .bind(chat_id)
+            .fetch_one(&mut *tx)
+            .timed("chat.promote_direct_chat")
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?
+            .unwrap_or_default(),
+        };
+
+        let chat = sqlx::query_as::<_, Chat>(
+            r#"UPDATE chats SET type = $2, chat_name = $3, created_by = $4, updated_at = NOW()
+               WHERE id = $1 AND type = 'Single'
+               RETURNING id, workspace_id, chat_name as name,
+                         type as chat_type, chat_members, description,
+                         created_by, created_at, updated_at"#,
+        )
+        .bind(chat_id)
+        .bind(&fechatter_core::ChatType::Group as &fechatter_core::ChatType)
+        .bind(&name)
+        .bind(owner_id)
+        .fetch_optional(&mut *tx)
+        .timed("chat.promote_direct_chat")
+        .await
+        .map_err(|e| name_conflict_or_database_error(e, &name))?;
+        let Some(chat) = chat else {
+            return Ok(None);
+        };
+
+        // Whoever promoted the chat owns the group; the other participant stays a member
+        sqlx::query(
+            r#"UPDATE chat_members
+               SET role = (CASE WHEN user_id = $2 THEN 'owner' ELSE 'member' END)::chat_member_role
+               WHERE chat_id = $1 AND left_at IS NULL"#,
+        )
+        .bind(chat_id)
+        .bind(owner_id)
+        .execute(&mut *tx)
+        .timed("chat.promote_direct_chat")
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(Some(chat))
+    }
+
+    /// Workspace a user belongs to, if the user exists
+    pub async fn find_user_workspace(&self, user_id: i64) -> Result<Option<i64>, CoreError> {
+        sqlx::query_scalar("SELECT workspace_id FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&*self.pool)
+            .timed("chat.find_user_workspace")
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))
     }
 
     /// Get sidebar chats for user (implementation for both trait and direct use)
@@ -139,6 +329,7 @@ impl ChatRepository {
     )
     .bind(user_id_i64)
     .fetch_all(&*self.pool)
+    .timed("chat.get_sidebar")
     .await
     .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -216,6 +407,7 @@ impl ChatRepository {
         )
         .bind(chat_id)
         .fetch_optional(&*self.pool)
+        .timed("chat.find_by_id")
         .await
         .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -287,6 +479,7 @@ impl CoreChatRepository for ChatRepository {
 
         let chat = query_builder
             .fetch_one(&*self.pool)
+            .timed("chat.update")
             .await
             .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -299,6 +492,7 @@ impl CoreChatRepository for ChatRepository {
         let result = sqlx::query("DELETE FROM chats WHERE id = $1")
             .bind(chat_id)
             .execute(&*self.pool)
+            .timed("chat.delete")
             .await
             .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -319,11 +513,86 @@ impl ChatRepository {
             .await
     }
 
+    /// Number of chats created by a user
+    pub async fn count_chats_created_by(&self, user_id: i64) -> Result<i64, CoreError> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM chats WHERE created_by = $1")
+            .bind(user_id)
+            .fetch_one(&*self.pool)
+            .timed("chat.count_chats_created_by")
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))
+    }
+
     /// Get sidebar chats for user (convenience method for server use)
     pub async fn get_sidebar_chats(&self, user_id: i64) -> Result<Vec<ChatSidebar>, CoreError> {
         self.get_sidebar_impl(UserId(user_id)).await
     }
 
+    /// Every chat of the user with its last message, member count and whether the user muted
+    /// it, newest activity first, in one query. Unread counts are left at 0 for the caller
+    pub async fn list_sidebar_summaries(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<(fechatter_core::chat::ChatSidebar, bool)>, CoreError> {
+        let rows = sqlx::query(
+            r#"SELECT
+            c.id,
+            CASE
+                WHEN c.type = 'Single' THEN COALESCE(other_user.fullname, c.chat_name)
+                ELSE c.chat_name
+            END AS name,
+            c.type::text AS chat_type,
+            c.created_by,
+            COALESCE(array_length(c.chat_members, 1), 0) AS members_count,
+            lm.content AS last_message,
+            lm.created_at AS last_message_time,
+            COALESCE(cm.muted_until > NOW(), false) AS is_muted
+        FROM chats c
+        LEFT JOIN LATERAL (
+            SELECT content, created_at
+            FROM messages
+            WHERE chat_id = c.id
+            ORDER BY created_at DESC, id DESC
+            LIMIT 1
+        ) lm ON true
+        LEFT JOIN chat_members cm ON cm.chat_id = c.id AND cm.user_id = $1
+        LEFT JOIN users other_user ON c.type = 'Single' AND other_user.id = (
+            SELECT mem_id FROM unnest(c.chat_members) AS mem_id WHERE mem_id != $1 LIMIT 1
+        )
+        WHERE $1 = ANY(c.chat_members)
+        ORDER BY COALESCE(lm.created_at, c.updated_at) DESC
+        "#,
+        )
+        .bind(user_id)
+        .fetch_all(&*self.pool)
+        .timed("chat.list_sidebar_summaries")
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let column = |e: sqlx::Error| CoreError::Database(e.to_string());
+                let chat_type = row
+                    .try_get::<String, _>("chat_type")
+                    .map_err(column)?
+                    .parse::<fechatter_core::ChatType>()
+                    .map_err(|e| CoreError::Internal(format!("Invalid chat type: {}", e)))?;
+
+                let chat = fechatter_core::chat::ChatSidebar {
+                    id: row.try_get::<i64, _>("id").map_err(column)?.into(),
+                    name: row.try_get("name").map_err(column)?,
+                    chat_type,
+                    last_message: row.try_get("last_message").map_err(column)?,
+                    last_message_time: row.try_get("last_message_time").map_err(column)?,
+                    unread_count: 0,
+                    members_count: row.try_get("members_count").map_err(column)?,
+                    created_by: UserId(row.try_get("created_by").map_err(column)?),
+                };
+                Ok((chat, row.try_get("is_muted").map_err(column)?))
+            })
+            .collect()
+    }
+
     /// Find chat by ID (convenience method for server use)
     pub async fn find_chat_by_id(&self, id: i64) -> Result<Option<Chat>, CoreError> {
         self.find_by_id_impl(ChatId(id)).await
@@ -350,8 +619,9 @@ impl ChatRepository {
         .bind(chat_id)
         .bind(user_id)
         .fetch_one(&*self.pool)
+        .timed("chat.update_chat_name")
         .await
-        .map_err(|e| CoreError::Database(e.to_string()))?;
+        .map_err(|e| name_conflict_or_database_error(e, new_name))?;
 
         Ok(chat)
     }
@@ -377,12 +647,44 @@ impl ChatRepository {
         .bind(chat_id)
         .bind(user_id)
         .fetch_one(&*self.pool)
+        .timed("chat.update_chat_description")
         .await
         .map_err(|e| CoreError::Database(e.to_string()))?;
 
         Ok(chat)
     }
 
+    /// Minimum seconds between a member's messages; 0 when slow mode is off
+    pub async fn get_slow_mode_secs(&self, chat_id: i64) -> Result<i32, CoreError> {
+        sqlx::query_scalar("SELECT slow_mode_secs FROM chats WHERE id = $1")
+            .bind(chat_id)
+            .fetch_optional(&*self.pool)
+            .timed("chat.get_slow_mode_secs")
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?
+            .ok_or_else(|| CoreError::ChatNotFound(format!("Chat {} not found", chat_id)))
+    }
+
+    /// Set the minimum seconds between a member's messages; 0 turns slow mode off
+    pub async fn set_slow_mode_secs(&self, chat_id: i64, secs: i32) -> Result<(), CoreError> {
+        let result =
+            sqlx::query("UPDATE chats SET slow_mode_secs = $2, updated_at = NOW() WHERE id = $1")
+                .bind(chat_id)
+                .bind(secs)
+                .execute(&*self.pool)
+                .timed("chat.set_slow_mode_secs")
+                .await
+                .map_err(|e| CoreError::Database(e.to_string()))?;
+        if result.rows_affected() == 0 {
+            return Err(CoreError::ChatNotFound(format!(
+                "Chat {} not found",
+                chat_id
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Delete chat
     pub async fn delete_chat(&self, chat_id: i64, user_id: i64) -> Result<(), CoreError> {
         let chat_id = i64::from(chat_id);
@@ -392,6 +694,7 @@ impl ChatRepository {
             .bind(chat_id)
             .bind(user_id)
             .execute(&*self.pool)
+            .timed("chat.delete_chat")
             .await
             .map_err(|e| CoreError::Database(e.to_string()))?;
 
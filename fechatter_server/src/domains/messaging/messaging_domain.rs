@@ -1,10 +1,20 @@
 // Messaging domain logic - business rules and orchestration
 
 use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tracing::{info, warn};
 
 use super::repository::MessageRepository;
-use fechatter_core::{error::CoreError, CreateMessage, ListMessages, Message};
+use crate::config::MessageLimitsConfig;
+use crate::domains::permission::{policy, PermissionService};
+use fechatter_core::{
+    error::CoreError,
+    models::message::{MessageSender, MAX_CLIENT_MESSAGE_ID_LEN},
+    CreateMessage, ListMessages, Message, UserId,
+};
 
 /// Domain service trait for messaging business logic
 #[async_trait]
@@ -15,6 +25,14 @@ pub trait MessageDomainService: Send + Sync {
         chat_id: i64,
         user_id: i64,
     ) -> Result<Message, CoreError>;
+
+    /// Run the send pipeline (validation, mentions, moderation) without persisting
+    async fn preview_message(
+        &self,
+        message: &CreateMessage,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<MessagePreview, CoreError>;
     async fn get_message(&self, id: i64) -> Result<Option<Message>, CoreError>;
     async fn list_messages(
         &self,
@@ -30,20 +48,56 @@ pub trait MessageDomainService: Send + Sync {
     ) -> Result<Message, CoreError>;
     async fn delete_message(&self, id: i64, user_id: i64) -> Result<(), CoreError>;
     async fn get_messages_count(&self, chat_id: i64) -> Result<i64, CoreError>;
+    async fn get_messages_page_counts(
+        &self,
+        chat_id: i64,
+        before: Option<i64>,
+    ) -> Result<(i64, i64), CoreError>;
     async fn get_chat_members(&self, chat_id: i64) -> Result<Vec<i64>, CoreError>;
 
+    /// Up to `before` older and `after` newer messages around `message_id`; `None` when the
+    /// message is not in the chat
+    async fn get_message_context(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        before: i64,
+        after: i64,
+    ) -> Result<Option<MessageContext>, CoreError>;
+
     async fn mark_message_delivered(&self, message_id: i64, user_id: i64) -> Result<(), CoreError>;
 
     async fn mark_message_read(&self, message_id: i64, user_id: i64) -> Result<(), CoreError>;
 
+    /// Mark the messages of `chat_id` among `message_ids` read for an active member of the
+    /// chat; returns the ids that were marked
     async fn mark_messages_read_batch(
         &self,
+        chat_id: i64,
         message_ids: &[i64],
         user_id: i64,
-    ) -> Result<(), CoreError>;
+    ) -> Result<Vec<i64>, CoreError>;
 
     async fn get_unread_count(&self, chat_id: i64, user_id: i64) -> Result<i64, CoreError>;
 
+    /// Authoritative unread count: messages after the user's last read receipt
+    async fn count_unread_after_last_read(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<i64, CoreError>;
+
+    /// Mark everything in the chat read for the user; returns the new read watermark, or
+    /// `None` when the user is not an active member
+    async fn mark_chat_read(&self, chat_id: i64, user_id: i64) -> Result<Option<i64>, CoreError>;
+
+    /// Newest message the user has read in the chat, `None` before their first read there
+    async fn get_read_cursor(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<Option<ChatReadCursor>, CoreError>;
+
     // =============================================================================
     // MENTIONS MANAGEMENT
     // =============================================================================
@@ -54,10 +108,14 @@ pub trait MessageDomainService: Send + Sync {
         message_id: i64,
     ) -> Result<Vec<(i64, String, String, String)>, CoreError>;
 
-    /// Get unread mentions for a user across all chats
+    /// A page of a user's unread mentions across all chats, newest first; `after` is the
+    /// `(created_at, message_id)` keyset of the previous page's last mention
     async fn get_unread_mentions_for_user(
         &self,
         user_id: i64,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64)>,
+        limit: i64,
     ) -> Result<
         Vec<(
             i64,
@@ -70,16 +128,47 @@ pub trait MessageDomainService: Send + Sync {
         CoreError,
     >;
 
+    /// Total unread mentions of a user and how many of them sort at or before `after`
+    async fn get_unread_mentions_page_counts(
+        &self,
+        user_id: i64,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64)>,
+    ) -> Result<(i64, i64), CoreError>;
+
     // =============================================================================
     // DETAILED RECEIPTS MANAGEMENT
     // =============================================================================
 
-    /// Get detailed read receipts for a message
+    /// A page of detailed receipts for a message, latest first; `after` is the
+    /// `(timestamp, user_id, status)` keyset of the previous page's last receipt
     async fn get_detailed_message_receipts(
         &self,
         message_id: i64,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64, String)>,
+        limit: i64,
     ) -> Result<Vec<(i64, String, String, String, chrono::DateTime<chrono::Utc>)>, CoreError>;
 
+    /// Total receipts of a message and how many of them sort at or before `after`
+    async fn get_message_receipts_page_counts(
+        &self,
+        message_id: i64,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64, String)>,
+    ) -> Result<(i64, i64), CoreError>;
+
+    /// Delivered and read state per recipient of a message
+    async fn get_message_receipt_states(
+        &self,
+        message_id: i64,
+    ) -> Result<Vec<MessageReceiptState>, CoreError>;
+
+    /// Number of users who read a message and the first `sample_size` of them
+    async fn get_message_seen_summary(
+        &self,
+        message_id: i64,
+        sample_size: i64,
+    ) -> Result<MessageSeenSummary, CoreError>;
+
     // =============================================================================
     // ENHANCED READ TRACKING
     // =============================================================================
@@ -91,6 +180,45 @@ pub trait MessageDomainService: Send + Sync {
         chat_id: i64,
         message_id: i64,
     ) -> Result<(), CoreError>;
+
+    /// `Some` exactly once per message: for the read receipt that completed it. Members who
+    /// joined after it was sent or have left since don't count
+    async fn claim_message_fully_read(
+        &self,
+        message_id: i64,
+    ) -> Result<Option<FullyReadMessage>, CoreError>;
+}
+
+/// Content screening backend used by the moderation gate
+#[async_trait]
+pub trait ContentModerator: Send + Sync {
+    /// Returns `true` when the content is acceptable
+    async fn is_allowed(&self, content: &str) -> Result<bool, CoreError>;
+}
+
+/// Size of stored attachments, used to enforce the per-message attachment budget
+#[async_trait]
+pub trait AttachmentSizeLookup: Send + Sync {
+    /// Size in bytes of the stored file behind `file`, `None` when it is not stored here
+    async fn attachment_size(&self, file: &str) -> Result<Option<u64>, CoreError>;
+}
+
+/// Batch lookup of sender profiles for rendering a page of messages
+#[async_trait]
+pub trait SenderProfileLookup: Send + Sync {
+    /// Returns the profiles found for `user_ids`; unknown ids are omitted
+    async fn find_senders(&self, user_ids: &[i64]) -> Result<Vec<MessageSender>, CoreError>;
+}
+
+/// What to do with a message the moderation backend flags
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// Refuse to send the message
+    Reject,
+    /// Send the message and record it for review
+    #[default]
+    Flag,
 }
 
 #[derive(Debug, Clone)]
@@ -99,28 +227,268 @@ pub struct MessageConfig {
     pub cache_ttl: u64,
     pub max_content_length: usize,
     pub max_file_count: usize,
+    /// Combined size of a message's stored attachments, in bytes
+    pub max_total_attachment_bytes: u64,
+    /// Seconds after sending during which a message may be edited, 0 for no limit
+    pub edit_window_secs: u64,
+    /// Chat admins and owners may edit past the window
+    pub edit_window_exempt_admins: bool,
+    /// Moderation action for flagged content, `None` disables moderation
+    pub moderation: Option<ModerationAction>,
 }
 
 impl Default for MessageConfig {
     fn default() -> Self {
+        let limits = MessageLimitsConfig::default();
         Self {
             cache_enabled: true,
             cache_ttl: 3600,
             max_content_length: 10000,
             max_file_count: 10,
+            max_total_attachment_bytes: limits.max_total_attachment_bytes,
+            edit_window_secs: limits.edit_window_secs,
+            edit_window_exempt_admins: limits.edit_window_exempt_admins,
+            moderation: None,
         }
     }
 }
 
+impl MessageConfig {
+    /// Production settings with the configured message caps
+    pub fn with_limits(limits: &MessageLimitsConfig) -> Self {
+        Self {
+            max_content_length: limits.max_content_length,
+            max_file_count: limits.max_file_count,
+            max_total_attachment_bytes: limits.max_total_attachment_bytes,
+            edit_window_secs: limits.edit_window_secs,
+            edit_window_exempt_admins: limits.edit_window_exempt_admins,
+            ..Self::production_optimized()
+        }
+    }
+}
+
+/// Seconds left to edit a message sent at `created_at` under a `window_secs` edit window;
+/// `None` when the window is 0, i.e. edits are unlimited
+pub fn edit_window_remaining(
+    created_at: chrono::DateTime<chrono::Utc>,
+    window_secs: u64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<u64> {
+    if window_secs == 0 {
+        return None;
+    }
+    let elapsed = (now - created_at).num_seconds().max(0) as u64;
+    Some(window_secs.saturating_sub(elapsed))
+}
+
+/// Mention pattern, kept in sync with `extract_and_store_mentions` (migration 0025)
+static MENTION_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"@(\w+)").expect("valid mention pattern"));
+
+/// Mentions parsed from message content before they are resolved against chat members
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ParsedMentions {
+    pub everyone: bool,
+    pub here: bool,
+    pub usernames: Vec<String>,
+}
+
+impl ParsedMentions {
+    /// Parse `@username`, `@everyone` and `@here` the same way the database trigger does
+    pub fn parse(content: &str) -> Self {
+        let mut parsed = Self::default();
+
+        for capture in MENTION_PATTERN.captures_iter(content) {
+            let name = &capture[1];
+            if name.eq_ignore_ascii_case("everyone") {
+                parsed.everyone = true;
+            } else if name.eq_ignore_ascii_case("here") {
+                parsed.here = true;
+            } else if !parsed.usernames.iter().any(|u| u == name) {
+                parsed.usernames.push(name.to_string());
+            }
+        }
+
+        parsed
+    }
+}
+
+/// A user mention resolved to an active chat member
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MentionPreview {
+    pub user_id: i64,
+    pub username: String,
+}
+
+/// Outcome of content moderation for an outgoing message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationVerdict {
+    /// Moderation is not enabled for message sending
+    NotChecked,
+    /// Content passed moderation
+    Allowed,
+    /// Content was flagged and the message is kept for review
+    Flagged,
+    /// Content was flagged and the message is refused
+    Rejected,
+    /// Moderation backend failed, message allowed (fail open)
+    Unavailable,
+}
+
+/// Delivery state of a message for one recipient, collapsed from its receipts
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MessageReceiptState {
+    pub user_id: i64,
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub read_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl MessageReceiptState {
+    /// `"read"` once read, otherwise `"delivered"`
+    pub fn status(&self) -> &'static str {
+        if self.read_at.is_some() {
+            "read"
+        } else {
+            "delivered"
+        }
+    }
+
+    /// Collapse `(user_id, status, timestamp)` receipt rows into one state per user, ordered by
+    /// user id; a read receipt implies delivery
+    pub fn from_receipts(
+        receipts: impl IntoIterator<Item = (i64, String, chrono::DateTime<chrono::Utc>)>,
+    ) -> Vec<Self> {
+        let mut states: std::collections::BTreeMap<i64, Self> = std::collections::BTreeMap::new();
+
+        for (user_id, status, timestamp) in receipts {
+            let state = states.entry(user_id).or_insert(Self {
+                user_id,
+                delivered_at: None,
+                read_at: None,
+            });
+            match status.as_str() {
+                "delivered" => state.delivered_at = Some(timestamp),
+                "read" => state.read_at = Some(timestamp),
+                _ => {}
+            }
+        }
+
+        states
+            .into_values()
+            .filter(|state| state.delivered_at.is_some() || state.read_at.is_some())
+            .map(|mut state| {
+                if state.delivered_at.is_none() {
+                    state.delivered_at = state.read_at;
+                }
+                state
+            })
+            .collect()
+    }
+}
+
+/// One reader shown in a "seen by" summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeenByUser {
+    pub user_id: i64,
+    pub username: String,
+    pub fullname: String,
+    pub avatar_url: Option<String>,
+    pub read_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// "Seen by N" summary of a message: the reader count plus the earliest few readers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageSeenSummary {
+    pub message_id: i64,
+    pub seen_count: i64,
+    pub sample: Vec<SeenByUser>,
+}
+
+/// A message every current member has read
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullyReadMessage {
+    pub message_id: i64,
+    pub chat_id: i64,
+    pub sender_id: i64,
+    /// Users with a read receipt, sender excluded, by id
+    pub readers: Vec<i64>,
+}
+
+/// Newest message a user has read in a chat; only ever moves forward
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct ChatReadCursor {
+    pub chat_id: i64,
+    pub user_id: i64,
+    pub last_read_message_id: i64,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A message with its neighbours, for jumping to it from a search result or link
+#[derive(Debug, Clone)]
+pub struct MessageContext {
+    /// Older messages, oldest first
+    pub before: Vec<Message>,
+    pub target: Message,
+    /// Newer messages, oldest first
+    pub after: Vec<Message>,
+    pub has_more_before: bool,
+    pub has_more_after: bool,
+}
+
+/// Result of a dry-run send: what would happen, without persisting anything
+#[derive(Debug, Clone, Serialize)]
+pub struct MessagePreview {
+    pub chat_id: i64,
+    pub sender_id: i64,
+    pub content_length: usize,
+    pub file_count: usize,
+    pub mentions_everyone: bool,
+    pub mentions_here: bool,
+    pub mentions: Vec<MentionPreview>,
+    /// Mentioned usernames that are not active members of the chat
+    pub unresolved_mentions: Vec<String>,
+    pub moderation: ModerationVerdict,
+}
+
 #[derive(Clone)]
 pub struct MessageDomainServiceImpl {
     repository: Arc<MessageRepository>,
+    permissions: PermissionService,
     config: MessageConfig,
+    moderator: Option<Arc<dyn ContentModerator>>,
+    attachment_sizes: Option<Arc<dyn AttachmentSizeLookup>>,
 }
 
 impl MessageDomainServiceImpl {
     pub fn new(repository: Arc<MessageRepository>, config: MessageConfig) -> Self {
-        Self { repository, config }
+        Self {
+            permissions: PermissionService::new(repository.pool()),
+            repository,
+            config,
+            moderator: None,
+            attachment_sizes: None,
+        }
+    }
+
+    /// Enforce `max_total_attachment_bytes` using sizes from the given lookup
+    pub fn with_attachment_sizes(
+        mut self,
+        attachment_sizes: Arc<dyn AttachmentSizeLookup>,
+    ) -> Self {
+        self.attachment_sizes = Some(attachment_sizes);
+        self
+    }
+
+    /// Enable the moderation gate with the given backend and action
+    pub fn with_moderation(
+        mut self,
+        moderator: Arc<dyn ContentModerator>,
+        action: ModerationAction,
+    ) -> Self {
+        self.config.moderation = Some(action);
+        self.moderator = Some(moderator);
+        self
     }
 
     /// Business logic for validating message content
@@ -156,8 +524,97 @@ impl MessageDomainServiceImpl {
             ));
         }
 
+        if let Some(client_message_id) = &message.client_message_id {
+            if client_message_id.is_empty() || client_message_id.len() > MAX_CLIENT_MESSAGE_ID_LEN {
+                return Err(CoreError::Validation(format!(
+                    "client_message_id must be between 1 and {} characters",
+                    MAX_CLIENT_MESSAGE_ID_LEN
+                )));
+            }
+        }
+
         Ok(())
     }
+
+    /// Reject a message whose stored attachments together exceed the byte budget. Files that
+    /// are not stored here have no known size and don't count
+    async fn check_attachment_budget(&self, message: &CreateMessage) -> Result<(), CoreError> {
+        let (Some(lookup), Some(files)) = (&self.attachment_sizes, &message.files) else {
+            return Ok(());
+        };
+
+        let mut total: u64 = 0;
+        for file in files {
+            total = total.saturating_add(lookup.attachment_size(file).await?.unwrap_or(0));
+        }
+
+        if total > self.config.max_total_attachment_bytes {
+            return Err(CoreError::Validation(format!(
+                "Attachments too large: {} bytes in total. Max {} bytes allowed",
+                total, self.config.max_total_attachment_bytes
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Reject editing a message whose edit window has closed, unless the editor is a chat admin
+    /// and admins are exempt
+    async fn check_edit_window(&self, id: i64, editor_id: i64) -> Result<(), CoreError> {
+        if self.config.edit_window_secs == 0 {
+            return Ok(());
+        }
+        let message = self
+            .repository
+            .get_message_by_id(id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Message {} not found", id)))?;
+        let remaining = edit_window_remaining(
+            message.created_at,
+            self.config.edit_window_secs,
+            chrono::Utc::now(),
+        );
+        if remaining != Some(0) {
+            return Ok(());
+        }
+
+        if self.config.edit_window_exempt_admins {
+            let role = self
+                .permissions
+                .chat_role(i64::from(message.chat_id), UserId(editor_id))
+                .await?;
+            if policy::is_edit_window_exempt(role) {
+                return Ok(());
+            }
+        }
+        Err(CoreError::Validation(format!(
+            "Messages can only be edited within {} seconds of sending",
+            self.config.edit_window_secs
+        )))
+    }
+
+    /// Moderation verdict for message content
+    async fn moderate(&self, content: &str) -> ModerationVerdict {
+        let (Some(action), Some(moderator)) = (self.config.moderation, &self.moderator) else {
+            return ModerationVerdict::NotChecked;
+        };
+
+        match moderator.is_allowed(content).await {
+            Ok(true) => ModerationVerdict::Allowed,
+            Ok(false) => match action {
+                ModerationAction::Reject => ModerationVerdict::Rejected,
+                ModerationAction::Flag => ModerationVerdict::Flagged,
+            },
+            Err(e) => {
+                // Fail open: an AI outage must not block all chat traffic
+                warn!(
+                    "WARNING: Moderation backend unavailable, allowing message: {}",
+                    e
+                );
+                ModerationVerdict::Unavailable
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -170,6 +627,18 @@ impl MessageDomainService for MessageDomainServiceImpl {
     ) -> Result<Message, CoreError> {
         // Validate business rules
         self.validate_message(&message)?;
+        self.check_attachment_budget(&message).await?;
+
+        let verdict = self.moderate(&message.content).await;
+        if verdict == ModerationVerdict::Rejected {
+            info!(
+                "Message from user {} in chat {} rejected by moderation",
+                user_id, chat_id
+            );
+            return Err(CoreError::Validation(
+                "Message rejected by content moderation".to_string(),
+            ));
+        }
 
         // Create through repository - now using core models directly
         let saved_message = self
@@ -177,12 +646,63 @@ impl MessageDomainService for MessageDomainServiceImpl {
             .create_message(message, chat_id, user_id)
             .await?;
 
-        // TODO: Publish MessageSent event
-        // This would be handled by infrastructure layer
+        if verdict == ModerationVerdict::Flagged {
+            // The message is already stored, so a failed flag must not fail the send
+            if let Err(e) = self
+                .repository
+                .record_moderation_flag(i64::from(saved_message.id), chat_id, user_id)
+                .await
+            {
+                warn!(
+                    "WARNING: Failed to record moderation flag for message {}: {}",
+                    i64::from(saved_message.id),
+                    e
+                );
+            }
+        }
+
+        // The message created event is published by the application service, which knows the
+        // chat members
 
         Ok(saved_message)
     }
 
+    async fn preview_message(
+        &self,
+        message: &CreateMessage,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<MessagePreview, CoreError> {
+        self.validate_message(message)?;
+        self.check_attachment_budget(message).await?;
+
+        let parsed = ParsedMentions::parse(&message.content);
+        let mentions: Vec<MentionPreview> = self
+            .repository
+            .find_active_members_by_username(chat_id, &parsed.usernames)
+            .await?
+            .into_iter()
+            .map(|(user_id, username)| MentionPreview { user_id, username })
+            .collect();
+        let unresolved_mentions = parsed
+            .usernames
+            .into_iter()
+            .filter(|name| !mentions.iter().any(|m| &m.username == name))
+            .collect();
+
+        Ok(MessagePreview {
+            chat_id,
+            sender_id: user_id,
+            content_length: message.content.len(),
+            file_count: message.files.as_ref().map_or(0, |files| files.len()),
+            mentions_everyone: parsed.everyone,
+            mentions_here: parsed.here,
+            mentions,
+            unresolved_mentions,
+            moderation: self.moderate(&message.content).await,
+        })
+    }
+
     async fn get_message(&self, id: i64) -> Result<Option<Message>, CoreError> {
         self.repository.get_message_by_id(id).await
     }
@@ -217,24 +737,35 @@ impl MessageDomainService for MessageDomainServiceImpl {
             ));
         }
 
+        self.permissions
+            .can_edit_message(UserId(editor_id), id)
+            .await?
+            .check()?;
+        self.check_edit_window(id, editor_id).await?;
+
         // Update through repository
         let updated_message = self
             .repository
             .update_message(id, content, editor_id)
             .await?;
 
-        // TODO: Publish MessageEdited event
-        // This would be handled by infrastructure layer
+        // The message updated event is published by the application service, which knows the
+        // chat members
 
         Ok(updated_message)
     }
 
     async fn delete_message(&self, id: i64, user_id: i64) -> Result<(), CoreError> {
+        self.permissions
+            .can_delete_message(UserId(user_id), id)
+            .await?
+            .check()?;
+
         // Delete through repository
-        self.repository.delete_message(id, user_id).await?;
+        self.repository.delete_message(id).await?;
 
-        // TODO: Publish MessageDeleted event
-        // This would be handled by infrastructure layer
+        // The message deleted event is published by the application service, which knows the
+        // chat members
 
         Ok(())
     }
@@ -243,10 +774,32 @@ impl MessageDomainService for MessageDomainServiceImpl {
         self.repository.get_messages_count(chat_id).await
     }
 
+    async fn get_messages_page_counts(
+        &self,
+        chat_id: i64,
+        before: Option<i64>,
+    ) -> Result<(i64, i64), CoreError> {
+        self.repository
+            .get_messages_page_counts(chat_id, before)
+            .await
+    }
+
     async fn get_chat_members(&self, chat_id: i64) -> Result<Vec<i64>, CoreError> {
         self.repository.get_chat_members(chat_id).await
     }
 
+    async fn get_message_context(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        before: i64,
+        after: i64,
+    ) -> Result<Option<MessageContext>, CoreError> {
+        self.repository
+            .get_message_context(chat_id, message_id, before, after)
+            .await
+    }
+
     async fn mark_message_delivered(&self, message_id: i64, user_id: i64) -> Result<(), CoreError> {
         self.repository
             .mark_message_delivered(message_id, user_id)
@@ -259,11 +812,12 @@ impl MessageDomainService for MessageDomainServiceImpl {
 
     async fn mark_messages_read_batch(
         &self,
+        chat_id: i64,
         message_ids: &[i64],
         user_id: i64,
-    ) -> Result<(), CoreError> {
+    ) -> Result<Vec<i64>, CoreError> {
         self.repository
-            .mark_messages_read_batch(message_ids, user_id)
+            .mark_messages_read_batch(chat_id, message_ids, user_id)
             .await
     }
 
@@ -271,6 +825,28 @@ impl MessageDomainService for MessageDomainServiceImpl {
         self.repository.get_unread_count(chat_id, user_id).await
     }
 
+    async fn count_unread_after_last_read(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<i64, CoreError> {
+        self.repository
+            .count_unread_after_last_read(chat_id, user_id)
+            .await
+    }
+
+    async fn mark_chat_read(&self, chat_id: i64, user_id: i64) -> Result<Option<i64>, CoreError> {
+        self.repository.mark_chat_read(chat_id, user_id).await
+    }
+
+    async fn get_read_cursor(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<Option<ChatReadCursor>, CoreError> {
+        self.repository.get_read_cursor(chat_id, user_id).await
+    }
+
     // =============================================================================
     // MENTIONS MANAGEMENT
     // =============================================================================
@@ -283,10 +859,12 @@ impl MessageDomainService for MessageDomainServiceImpl {
         self.repository.get_message_mentions(message_id).await
     }
 
-    /// Get unread mentions for a user across all chats
     async fn get_unread_mentions_for_user(
         &self,
         user_id: i64,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64)>,
+        limit: i64,
     ) -> Result<
         Vec<(
             i64,
@@ -298,23 +876,87 @@ impl MessageDomainService for MessageDomainServiceImpl {
         )>,
         CoreError,
     > {
-        self.repository.get_unread_mentions_for_user(user_id).await
+        self.repository
+            .get_unread_mentions_for_user(user_id, since, after, limit)
+            .await
+    }
+
+    async fn get_unread_mentions_page_counts(
+        &self,
+        user_id: i64,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64)>,
+    ) -> Result<(i64, i64), CoreError> {
+        self.repository
+            .get_unread_mentions_page_counts(user_id, since, after)
+            .await
     }
 
     // =============================================================================
     // DETAILED RECEIPTS MANAGEMENT
     // =============================================================================
 
-    /// Get detailed read receipts for a message
     async fn get_detailed_message_receipts(
         &self,
         message_id: i64,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64, String)>,
+        limit: i64,
     ) -> Result<Vec<(i64, String, String, String, chrono::DateTime<chrono::Utc>)>, CoreError> {
         self.repository
-            .get_detailed_message_receipts(message_id)
+            .get_detailed_message_receipts(message_id, after, limit)
             .await
     }
 
+    async fn get_message_receipts_page_counts(
+        &self,
+        message_id: i64,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64, String)>,
+    ) -> Result<(i64, i64), CoreError> {
+        self.repository
+            .get_message_receipts_page_counts(message_id, after)
+            .await
+    }
+
+    async fn get_message_receipt_states(
+        &self,
+        message_id: i64,
+    ) -> Result<Vec<MessageReceiptState>, CoreError> {
+        let receipts = self.repository.get_message_receipts(message_id).await?;
+        Ok(MessageReceiptState::from_receipts(receipts))
+    }
+
+    async fn get_message_seen_summary(
+        &self,
+        message_id: i64,
+        sample_size: i64,
+    ) -> Result<MessageSeenSummary, CoreError> {
+        let seen_count = self.repository.count_message_readers(message_id).await?;
+        let sample = if seen_count > 0 {
+            self.repository
+                .list_message_readers(message_id, sample_size)
+                .await?
+                .into_iter()
+                .map(
+                    |(user_id, username, fullname, avatar_url, read_at)| SeenByUser {
+                        user_id,
+                        username,
+                        fullname,
+                        avatar_url,
+                        read_at,
+                    },
+                )
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(MessageSeenSummary {
+            message_id,
+            seen_count,
+            sample,
+        })
+    }
+
     // =============================================================================
     // ENHANCED READ TRACKING
     // =============================================================================
@@ -330,6 +972,22 @@ impl MessageDomainService for MessageDomainServiceImpl {
             .mark_message_read_enhanced(user_id, chat_id, message_id)
             .await
     }
+
+    async fn claim_message_fully_read(
+        &self,
+        message_id: i64,
+    ) -> Result<Option<FullyReadMessage>, CoreError> {
+        Ok(self
+            .repository
+            .claim_message_fully_read(message_id)
+            .await?
+            .map(|(chat_id, sender_id, readers)| FullyReadMessage {
+                message_id,
+                chat_id,
+                sender_id,
+                readers,
+            }))
+    }
 }
 
 #[cfg(test)]
@@ -344,8 +1002,267 @@ mod tests {
         assert_eq!(config.cache_ttl, 3600);
         assert_eq!(config.max_content_length, 10000);
         assert_eq!(config.max_file_count, 10);
+        assert_eq!(config.max_total_attachment_bytes, 50 * 1024 * 1024);
+        assert_eq!(config.edit_window_secs, 15 * 60);
+        assert!(config.edit_window_exempt_admins);
+        assert_eq!(config.moderation, None);
+    }
+
+    /// Attachment sizes from a fixed table
+    struct FakeAttachmentSizes(Vec<(&'static str, u64)>);
+
+    #[async_trait]
+    impl AttachmentSizeLookup for FakeAttachmentSizes {
+        async fn attachment_size(&self, file: &str) -> Result<Option<u64>, CoreError> {
+            Ok(self
+                .0
+                .iter()
+                .find(|(name, _)| *name == file)
+                .map(|(_, size)| *size))
+        }
+    }
+
+    #[tokio::test]
+    async fn attachments_over_byte_budget_should_be_rejected_within_file_count() {
+        let config = MessageConfig {
+            max_file_count: 3,
+            max_total_attachment_bytes: 1000,
+            ..MessageConfig::default()
+        };
+        let service =
+            offline_service(config).with_attachment_sizes(Arc::new(FakeAttachmentSizes(vec![
+                ("/files/a.png", 600),
+                ("/files/b.png", 600),
+            ])));
+        let mut message = text_message("two big files");
+        message.files = Some(vec!["/files/a.png".to_string(), "/files/b.png".to_string()]);
+
+        let result = service.send_message(message.clone(), 1, 2).await;
+        match result {
+            Err(CoreError::Validation(msg)) => assert!(msg.contains("1200 bytes"), "{}", msg),
+            other => panic!("expected validation error, got {:?}", other),
+        }
+
+        // A single file fits the budget
+        message.files = Some(vec!["/files/a.png".to_string()]);
+        assert!(service.check_attachment_budget(&message).await.is_ok());
+    }
+
+    #[test]
+    fn parse_mentions_should_match_database_trigger() {
+        let parsed = ParsedMentions::parse("hey @alice and @bob_2, @alice again @EVERYONE @here");
+
+        assert!(parsed.everyone);
+        assert!(parsed.here);
+        assert_eq!(parsed.usernames, vec!["alice", "bob_2"]);
+    }
+
+    struct FakeModerator(Result<bool, ()>);
+
+    #[async_trait]
+    impl ContentModerator for FakeModerator {
+        async fn is_allowed(&self, _content: &str) -> Result<bool, CoreError> {
+            self.0
+                .map_err(|_| CoreError::Internal("moderation backend down".to_string()))
+        }
+    }
+
+    /// Service over a pool that fails every query, so any database access surfaces as an error
+    fn offline_service(config: MessageConfig) -> MessageDomainServiceImpl {
+        let pool = sqlx::PgPool::connect_lazy("postgres://preview@127.0.0.1:1/none").unwrap();
+        MessageDomainServiceImpl::new(Arc::new(MessageRepository::new(Arc::new(pool))), config)
+    }
+
+    fn moderated_service(
+        action: ModerationAction,
+        result: Result<bool, ()>,
+    ) -> MessageDomainServiceImpl {
+        offline_service(MessageConfig::default())
+            .with_moderation(Arc::new(FakeModerator(result)), action)
+    }
+
+    fn text_message(content: &str) -> CreateMessage {
+        CreateMessage {
+            content: content.to_string(),
+            files: None,
+            idempotency_key: None,
+            client_message_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn preview_should_return_mentions_and_verdict_without_touching_database() {
+        let service = offline_service(MessageConfig::default());
+        let message = text_message("@everyone release is out");
+
+        let preview = service.preview_message(&message, 1, 2).await.unwrap();
+
+        assert!(preview.mentions_everyone);
+        assert!(!preview.mentions_here);
+        assert!(preview.mentions.is_empty());
+        assert_eq!(preview.moderation, ModerationVerdict::NotChecked);
+        assert_eq!(preview.content_length, message.content.len());
+    }
+
+    #[tokio::test]
+    async fn preview_should_reject_invalid_message() {
+        let service = offline_service(MessageConfig::default());
+
+        let result = service.preview_message(&text_message("   "), 1, 2).await;
+
+        assert!(matches!(result, Err(CoreError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn flagged_message_should_be_rejected_before_persisting() {
+        let service = moderated_service(ModerationAction::Reject, Ok(false));
+
+        let result = service.send_message(text_message("abusive"), 1, 2).await;
+
+        assert!(matches!(result, Err(CoreError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn flagged_message_should_be_flagged_when_configured() {
+        let service = moderated_service(ModerationAction::Flag, Ok(false));
+
+        assert_eq!(
+            service.moderate("abusive").await,
+            ModerationVerdict::Flagged
+        );
+        let preview = service
+            .preview_message(&text_message("abusive"), 1, 2)
+            .await
+            .unwrap();
+        assert_eq!(preview.moderation, ModerationVerdict::Flagged);
+    }
+
+    #[tokio::test]
+    async fn moderation_outage_should_fail_open() {
+        let service = moderated_service(ModerationAction::Reject, Err(()));
+
+        assert_eq!(
+            service.moderate("hello").await,
+            ModerationVerdict::Unavailable
+        );
+        let preview = service
+            .preview_message(&text_message("hello"), 1, 2)
+            .await
+            .unwrap();
+        assert_eq!(preview.moderation, ModerationVerdict::Unavailable);
+    }
+
+    #[test]
+    fn pushed_message_should_show_delivered_then_read() {
+        let delivered_at = chrono::Utc::now();
+        let read_at = delivered_at + chrono::Duration::seconds(5);
+
+        // Pushed to user 2 but not opened yet
+        let mut receipts = vec![(2, "delivered".to_string(), delivered_at)];
+        let states = MessageReceiptState::from_receipts(receipts.clone());
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].status(), "delivered");
+        assert_eq!(states[0].delivered_at, Some(delivered_at));
+        assert_eq!(states[0].read_at, None);
+
+        // mark_message_read adds a read receipt next to the delivered one
+        receipts.push((2, "read".to_string(), read_at));
+        let states = MessageReceiptState::from_receipts(receipts);
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].status(), "read");
+        assert_eq!(states[0].delivered_at, Some(delivered_at));
+        assert_eq!(states[0].read_at, Some(read_at));
+    }
+
+    #[test]
+    fn read_without_delivery_receipt_should_count_as_delivered() {
+        let read_at = chrono::Utc::now();
+
+        let states = MessageReceiptState::from_receipts(vec![
+            (3, "read".to_string(), read_at),
+            (1, "sent".to_string(), read_at),
+        ]);
+
+        assert_eq!(
+            states,
+            vec![MessageReceiptState {
+                user_id: 3,
+                delivered_at: Some(read_at),
+                read_at: Some(read_at),
+            }]
+        );
     }
 
     // Note: Database-dependent tests are disabled for now
     // TODO: Implement proper mock repository for unit testing
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+
+        #[tokio::test]
+        async fn seen_summary_count_should_match_detailed_receipts() -> anyhow::Result<()> {
+            let (state, users) = crate::setup_test_users!(4).await;
+            let pool = state.pool();
+            let reader_ids: Vec<i64> = users[1..].iter().map(|user| i64::from(user.id)).collect();
+            let chat = state
+                .create_new_chat(
+                    fechatter_core::ChatType::Group,
+                    Some(format!("Seen {}", uuid::Uuid::new_v4())),
+                    None,
+                    users[0].id,
+                    users[1..].iter().map(|user| user.id).collect(),
+                )
+                .await?;
+            let chat_id = i64::from(chat.id);
+            let message_id: i64 = sqlx::query_scalar(
+                r#"INSERT INTO messages (chat_id, sender_id, content, created_at, updated_at)
+                   VALUES ($1, $2, 'seen?', NOW(), NOW()) RETURNING id"#,
+            )
+            .bind(chat_id)
+            .bind(i64::from(users[0].id))
+            .fetch_one(&*pool)
+            .await?;
+
+            // Two readers (one also delivered) and one delivery without a read
+            for (user_id, status) in [
+                (reader_ids[0], "delivered"),
+                (reader_ids[0], "read"),
+                (reader_ids[1], "read"),
+                (reader_ids[2], "delivered"),
+            ] {
+                sqlx::query(
+                    "INSERT INTO message_receipts (message_id, user_id, status, timestamp) VALUES ($1, $2, $3, NOW())",
+                )
+                .bind(message_id)
+                .bind(user_id)
+                .bind(status)
+                .execute(&*pool)
+                .await?;
+            }
+
+            let service = MessageDomainServiceImpl::new(
+                Arc::new(MessageRepository::new(pool.clone())),
+                MessageConfig::default(),
+            );
+            let detailed = service.get_detailed_message_receipts(message_id).await?;
+            let detailed_reads = detailed
+                .iter()
+                .filter(|(_, _, _, status, _)| status == "read")
+                .count();
+
+            let summary = service.get_message_seen_summary(message_id, 5).await?;
+            assert_eq!(summary.seen_count, detailed_reads as i64);
+            assert_eq!(summary.seen_count, 2);
+            let mut sampled: Vec<i64> = summary.sample.iter().map(|u| u.user_id).collect();
+            sampled.sort();
+            assert_eq!(sampled, vec![reader_ids[0], reader_ids[1]]);
+
+            // The sample is capped; the count is not
+            let capped = service.get_message_seen_summary(message_id, 1).await?;
+            assert_eq!(capped.seen_count, 2);
+            assert_eq!(capped.sample.len(), 1);
+            Ok(())
+        }
+    }
 }
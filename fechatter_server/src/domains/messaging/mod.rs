@@ -1,3 +1,5 @@
 pub mod events;
 pub mod messaging_domain;
 pub mod repository;
+pub mod slash_commands;
+pub mod sync;
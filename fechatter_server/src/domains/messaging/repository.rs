@@ -1,12 +1,22 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use futures::StreamExt;
+use sqlx::{FromRow, PgPool, Row};
 use std::sync::Arc;
+use tokio_stream::wrappers::ReceiverStream;
 
+use crate::services::infrastructure::observability::slow_query::TimedQuery;
+use super::messaging_domain::{ChatReadCursor, MessageContext, SenderProfileLookup};
 use fechatter_core::{
-    error::CoreError, models::CreateMessage, models::ListMessages, ChatId, Message, MessageId,
-    UserId,
+    error::CoreError,
+    models::message::{MessageSender, MessageView},
+    models::CreateMessage,
+    models::ListMessages,
+    ChatId, Message, MessageId, UserId,
 };
 
+/// Rows fetched ahead of a slow reader of `stream_messages`
+const STREAM_BUFFER: usize = 16;
+
 pub struct MessageRepository {
     pool: Arc<PgPool>,
 }
@@ -16,6 +26,10 @@ impl MessageRepository {
         Self { pool }
     }
 
+    pub(crate) fn pool(&self) -> Arc<PgPool> {
+        self.pool.clone()
+    }
+
     /// Static create message method for use in async move blocks
     async fn create_message_static(
         input: &CreateMessage,
@@ -26,11 +40,12 @@ impl MessageRepository {
         // Check for duplicate message using idempotency key
         let existing_message = sqlx::query_as::<_, Message>(
             r#"SELECT id, chat_id, sender_id, content, files,
-                      created_at, idempotency_key
+                      created_at, idempotency_key, client_message_id
                FROM messages WHERE idempotency_key = $1"#,
         )
         .bind(input.idempotency_key)
         .fetch_optional(&*pool)
+        .timed("message.create_message")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -43,15 +58,17 @@ impl MessageRepository {
         let sequence_number: i64 = sqlx::query_scalar("SELECT next_message_sequence($1)")
             .bind(chat_id)
             .fetch_one(&*pool)
+            .timed("message.create_message")
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
         // Create new message with sequence number
         let message = sqlx::query_as::<_, Message>(
-      r#"INSERT INTO messages (chat_id, sender_id, content, files, idempotency_key, sequence_number)
-               VALUES ($1, $2, $3, $4, $5, $6)
+      r#"INSERT INTO messages (chat_id, sender_id, content, files, idempotency_key, sequence_number,
+                                    client_message_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
                RETURNING id, chat_id, sender_id, content, files, 
-                         created_at, idempotency_key"#,
+                         created_at, idempotency_key, client_message_id"#,
     )
     .bind(chat_id)
     .bind(user_id)
@@ -59,7 +76,9 @@ impl MessageRepository {
     .bind(&input.files)
     .bind(input.idempotency_key)
     .bind(sequence_number)
+    .bind(&input.client_message_id)
     .fetch_one(&*pool)
+    .timed("message.create_message")
     .await
     .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -75,7 +94,7 @@ impl MessageRepository {
         let mut query_builder = sqlx::QueryBuilder::new(
             r#"SELECT id, chat_id, sender_id, content, files,
                       created_at, idempotency_key
-               FROM messages WHERE chat_id = "#,
+               FROM messages WHERE deleted_at IS NULL AND chat_id = "#,
         );
 
         query_builder.push_bind(chat_id);
@@ -92,12 +111,86 @@ impl MessageRepository {
         let messages = query_builder
             .build_query_as::<Message>()
             .fetch_all(&*pool)
+            .timed("message.list_messages")
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
         Ok(messages)
     }
 
+    /// The same page as `list_messages`, with senders joined in, fetched row by row as the
+    /// stream is read so the page is never held in memory
+    pub fn stream_messages(
+        &self,
+        input: ListMessages,
+        chat_id: i64,
+    ) -> ReceiverStream<Result<MessageView, CoreError>> {
+        let pool = self.pool.clone();
+        let (tx, rx) = tokio::sync::mpsc::channel(STREAM_BUFFER);
+
+        tokio::spawn(async move {
+            let mut query_builder = sqlx::QueryBuilder::new(
+                r#"SELECT m.id, m.chat_id, m.sender_id, m.content, m.files,
+                          m.created_at, m.idempotency_key,
+                          u.fullname, u.username, u.email
+                   FROM messages m LEFT JOIN users u ON u.id = m.sender_id
+                   WHERE m.deleted_at IS NULL AND m.chat_id = "#,
+            );
+            query_builder.push_bind(chat_id);
+            if let Some(last_id) = input.last_id {
+                query_builder.push(" AND m.id < ").push_bind(last_id);
+            }
+            query_builder
+                .push(" ORDER BY m.created_at DESC LIMIT ")
+                .push_bind(input.limit);
+
+            let mut rows = query_builder.build().fetch(&*pool);
+            while let Some(row) = rows.next().await {
+                let message = row
+                    .and_then(|row| message_view_from_row(&row))
+                    .map_err(CoreError::from_database_error);
+                let failed = message.is_err();
+                // Stop once the reader is gone or the query failed
+                if tx.send(message).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+
+    /// Id of the last message of the page `list_messages` returns for `input`, when the page
+    /// is full
+    pub async fn page_boundary(
+        &self,
+        input: &ListMessages,
+        chat_id: i64,
+    ) -> Result<Option<i64>, CoreError> {
+        if input.limit <= 0 {
+            return Ok(None);
+        }
+
+        let mut query_builder = sqlx::QueryBuilder::new(
+            "SELECT id FROM messages WHERE deleted_at IS NULL AND chat_id = ",
+        );
+        query_builder.push_bind(chat_id);
+        if let Some(last_id) = input.last_id {
+            query_builder.push(" AND id < ").push_bind(last_id);
+        }
+        query_builder
+            .push(" ORDER BY created_at DESC OFFSET ")
+            .push_bind(input.limit - 1)
+            .push(" LIMIT 1");
+
+        query_builder
+            .build_query_scalar::<i64>()
+            .fetch_optional(&*self.pool)
+            .timed("message.page_boundary")
+            .await
+            .map_err(|e| CoreError::from_database_error(e))
+    }
+
     /// Create a new message (implementation for both trait and direct use)
     async fn create_message_impl(
         &self,
@@ -118,6 +211,27 @@ impl MessageRepository {
     }
 }
 
+/// A `stream_messages` row; the sender is `None` when the user row is gone
+fn message_view_from_row(row: &sqlx::postgres::PgRow) -> Result<MessageView, sqlx::Error> {
+    let message = Message::from_row(row)?;
+    let sender = row
+        .try_get::<Option<String>, _>("fullname")?
+        .map(|fullname| {
+            Ok::<_, sqlx::Error>(MessageSender {
+                id: i64::from(message.sender_id),
+                fullname,
+                username: row.try_get("username")?,
+                email: row.try_get("email")?,
+            })
+        })
+        .transpose()?;
+
+    Ok(MessageView {
+        sender,
+        ..MessageView::from(message)
+    })
+}
+
 // Implement the core MessageRepository trait
 #[async_trait]
 impl fechatter_core::models::MessageRepository for MessageRepository {
@@ -185,11 +299,11 @@ impl MessageRepository {
     > {
         let mut query_builder = sqlx::QueryBuilder::new(
             r#"SELECT m.id, m.chat_id, m.sender_id, m.content, m.files,
-                m.created_at, m.idempotency_key,
+                m.created_at, m.idempotency_key, m.client_message_id,
                 u.id as user_id, u.fullname, u.email
          FROM messages m
          LEFT JOIN users u ON m.sender_id = u.id
-         WHERE m.chat_id = "#,
+         WHERE m.deleted_at IS NULL AND m.chat_id = "#,
         );
 
         query_builder.push_bind(chat_id);
@@ -212,6 +326,7 @@ impl MessageRepository {
             files: Option<Vec<String>>,
             created_at: chrono::DateTime<chrono::Utc>,
             idempotency_key: Option<uuid::Uuid>,
+            client_message_id: Option<String>,
             // User fields
             user_id: Option<i64>,
             fullname: Option<String>,
@@ -221,6 +336,7 @@ impl MessageRepository {
         let rows = query_builder
             .build_query_as::<MessageWithSender>()
             .fetch_all(&*self.pool)
+            .timed("message.list_messages_with_senders")
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -235,6 +351,7 @@ impl MessageRepository {
                     files: row.files,
                     created_at: row.created_at,
                     idempotency_key: row.idempotency_key,
+                    client_message_id: row.client_message_id,
                 };
 
                 // Include sender info if we have at least a user_id from the JOIN
@@ -265,6 +382,7 @@ impl MessageRepository {
         )
         .bind(message_id)
         .fetch_optional(&*self.pool)
+        .timed("message.get_message_by_id")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -279,7 +397,8 @@ impl MessageRepository {
         editor_id: i64,
     ) -> Result<Message, CoreError> {
         let message = sqlx::query_as::<_, Message>(
-            r#"UPDATE messages SET content = $1 WHERE id = $2 AND sender_id = $3
+            r#"UPDATE messages SET content = $1
+               WHERE id = $2 AND sender_id = $3 AND deleted_at IS NULL
                RETURNING id, chat_id, sender_id, content, files,
                          created_at, idempotency_key"#,
         )
@@ -287,24 +406,30 @@ impl MessageRepository {
         .bind(message_id)
         .bind(editor_id)
         .fetch_one(&*self.pool)
+        .timed("message.update_message")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
         Ok(message)
     }
 
-    /// Delete a message
-    pub async fn delete_message(&self, message_id: i64, user_id: i64) -> Result<(), CoreError> {
-        let result = sqlx::query("DELETE FROM messages WHERE id = $1 AND sender_id = $2")
-            .bind(message_id)
-            .bind(user_id)
-            .execute(&*self.pool)
-            .await
-            .map_err(|e| CoreError::from_database_error(e))?;
+    /// Delete a message by tombstoning it, like the retention sweep does: content and files
+    /// are cleared but the row stays, so delta sync can report the deletion. Callers check
+    /// `PermissionService::can_delete_message` first; moderators may delete others' messages
+    pub async fn delete_message(&self, message_id: i64) -> Result<(), CoreError> {
+        let result = sqlx::query(
+            r#"UPDATE messages SET content = '', files = '{}', deleted_at = NOW()
+               WHERE id = $1 AND deleted_at IS NULL"#,
+        )
+        .bind(message_id)
+        .execute(&*self.pool)
+        .timed("message.delete_message")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
 
         if result.rows_affected() == 0 {
             return Err(CoreError::NotFound(format!(
-                "Message {} not found or permission denied",
+                "Message {} not found",
                 message_id
             )));
         }
@@ -314,27 +439,203 @@ impl MessageRepository {
 
     /// Get messages count for a chat
     pub async fn get_messages_count(&self, chat_id: i64) -> Result<i64, CoreError> {
-        let count = sqlx::query_scalar("SELECT COUNT(*) FROM messages WHERE chat_id = $1")
-            .bind(chat_id)
-            .fetch_one(&*self.pool)
-            .await
-            .map_err(|e| CoreError::from_database_error(e))?;
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM messages WHERE chat_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(chat_id)
+        .fetch_one(&*self.pool)
+        .timed("message.get_messages_count")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
 
         Ok(count)
     }
 
+    /// Get the total message count for a chat and how many of them are newer than `before`
+    pub async fn get_messages_page_counts(
+        &self,
+        chat_id: i64,
+        before: Option<i64>,
+    ) -> Result<(i64, i64), CoreError> {
+        let (total, newer): (i64, i64) = sqlx::query_as(
+            r#"SELECT COUNT(*),
+                      COUNT(*) FILTER (WHERE $2::BIGINT IS NOT NULL AND id >= $2)
+               FROM messages WHERE chat_id = $1 AND deleted_at IS NULL"#,
+        )
+        .bind(chat_id)
+        .bind(before)
+        .fetch_one(&*self.pool)
+        .timed("message.get_messages_page_counts")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok((total, newer))
+    }
+
+    /// Messages around `message_id`, each side a keyset scan of the (chat_id, id) index that
+    /// reads one extra row to tell whether more messages follow.
+    /// `None` when the message doesn't exist, was deleted or belongs to another chat
+    pub async fn get_message_context(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        before: i64,
+        after: i64,
+    ) -> Result<Option<MessageContext>, CoreError> {
+        const COLUMNS: &str =
+            "id, chat_id, sender_id, content, files, created_at, idempotency_key, client_message_id";
+
+        let target = sqlx::query_as::<_, Message>(&format!(
+            "SELECT {} FROM messages WHERE id = $1 AND chat_id = $2 AND deleted_at IS NULL",
+            COLUMNS
+        ))
+        .bind(message_id)
+        .bind(chat_id)
+        .fetch_optional(&*self.pool)
+        .timed("message.get_message_context")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+        let Some(target) = target else {
+            return Ok(None);
+        };
+
+        let mut older = sqlx::query_as::<_, Message>(&format!(
+            r#"SELECT {} FROM messages WHERE chat_id = $1 AND id < $2 AND deleted_at IS NULL
+               ORDER BY id DESC LIMIT $3"#,
+            COLUMNS
+        ))
+        .bind(chat_id)
+        .bind(message_id)
+        .bind(before + 1)
+        .fetch_all(&*self.pool)
+        .timed("message.get_message_context")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        let mut newer = sqlx::query_as::<_, Message>(&format!(
+            r#"SELECT {} FROM messages WHERE chat_id = $1 AND id > $2 AND deleted_at IS NULL
+               ORDER BY id ASC LIMIT $3"#,
+            COLUMNS
+        ))
+        .bind(chat_id)
+        .bind(message_id)
+        .bind(after + 1)
+        .fetch_all(&*self.pool)
+        .timed("message.get_message_context")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        let has_more_before = older.len() as i64 > before;
+        older.truncate(before as usize);
+        older.reverse();
+        let has_more_after = newer.len() as i64 > after;
+        newer.truncate(after as usize);
+
+        Ok(Some(MessageContext {
+            before: older,
+            target,
+            after: newer,
+            has_more_before,
+            has_more_after,
+        }))
+    }
+
     /// Get chat members
     pub async fn get_chat_members(&self, chat_id: i64) -> Result<Vec<i64>, CoreError> {
         let members =
             sqlx::query_scalar::<_, i64>("SELECT user_id FROM chat_members WHERE chat_id = $1")
                 .bind(chat_id)
                 .fetch_all(&*self.pool)
+                .timed("message.get_chat_members")
                 .await
                 .map_err(|e| CoreError::from_database_error(e))?;
 
         Ok(members)
     }
 
+    /// Get sender profiles for a set of user ids in a single query
+    pub async fn find_senders(&self, user_ids: &[i64]) -> Result<Vec<MessageSender>, CoreError> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows =
+            sqlx::query("SELECT id, fullname, username, email FROM users WHERE id = ANY($1)")
+                .bind(user_ids)
+                .fetch_all(&*self.pool)
+                .timed("message.find_senders")
+                .await
+                .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MessageSender {
+                id: row.get("id"),
+                fullname: row.get("fullname"),
+                username: row.get("username"),
+                email: row.get("email"),
+            })
+            .collect())
+    }
+
+    /// Resolve usernames to active members of a chat (read-only)
+    pub async fn find_active_members_by_username(
+        &self,
+        chat_id: i64,
+        usernames: &[String],
+    ) -> Result<Vec<(i64, String)>, CoreError> {
+        if usernames.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(
+            r#"
+      SELECT u.id, u.username
+      FROM users u
+      JOIN chat_members cm ON cm.user_id = u.id
+      WHERE cm.chat_id = $1
+        AND cm.left_at IS NULL
+        AND u.username = ANY($2)
+      "#,
+        )
+        .bind(chat_id)
+        .bind(usernames)
+        .fetch_all(&*self.pool)
+        .timed("message.find_active_members_by_username")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("id"), row.get("username")))
+            .collect())
+    }
+
+    /// Record that a sent message was flagged by content moderation
+    pub async fn record_moderation_flag(
+        &self,
+        message_id: i64,
+        chat_id: i64,
+        sender_id: i64,
+    ) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"
+      INSERT INTO message_moderation_flags (message_id, chat_id, sender_id, verdict)
+      VALUES ($1, $2, $3, 'flagged')
+      ON CONFLICT (message_id) DO NOTHING
+      "#,
+        )
+        .bind(message_id)
+        .bind(chat_id)
+        .bind(sender_id)
+        .execute(&*self.pool)
+        .timed("message.record_moderation_flag")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(())
+    }
+
     /// Get the next sequence number for a chat
     pub async fn get_next_sequence(&self, chat_id: i64) -> Result<i64, CoreError> {
         let mut tx = self
@@ -347,6 +648,7 @@ impl MessageRepository {
         let sequence: i64 = sqlx::query_scalar("SELECT next_message_sequence($1)")
             .bind(chat_id)
             .fetch_one(&mut *tx)
+            .timed("message.get_next_sequence")
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -369,11 +671,12 @@ impl MessageRepository {
         if let Some(key) = input.idempotency_key {
             let existing_message = sqlx::query_as::<_, Message>(
                 r#"SELECT id, chat_id, sender_id, content, files,
-                        created_at, idempotency_key
+                        created_at, idempotency_key, client_message_id
                  FROM messages WHERE idempotency_key = $1"#,
             )
             .bind(key)
             .fetch_optional(&*self.pool)
+            .timed("message.create_message_with_sequence")
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -384,10 +687,11 @@ impl MessageRepository {
 
         // Create new message with sequence number
         let message = sqlx::query_as::<_, Message>(
-      r#"INSERT INTO messages (chat_id, sender_id, content, files, idempotency_key, sequence_number)
-               VALUES ($1, $2, $3, $4, $5, $6)
+      r#"INSERT INTO messages (chat_id, sender_id, content, files, idempotency_key, sequence_number,
+                                    client_message_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
                RETURNING id, chat_id, sender_id, content, files, 
-                         created_at, idempotency_key"#,
+                         created_at, idempotency_key, client_message_id"#,
     )
     .bind(chat_id)
     .bind(user_id)
@@ -395,7 +699,9 @@ impl MessageRepository {
     .bind(&input.files)
     .bind(input.idempotency_key)
     .bind(sequence_number)
+    .bind(&input.client_message_id)
     .fetch_one(&*self.pool)
+    .timed("message.create_message_with_sequence")
     .await
     .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -421,6 +727,7 @@ impl MessageRepository {
         .bind(after_sequence)
         .bind(limit as i64)
         .fetch_all(&*self.pool)
+        .timed("message.get_messages_after_sequence")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -445,6 +752,7 @@ impl MessageRepository {
         .bind(user_id)
         .bind(limit as i64)
         .fetch_all(&*self.pool)
+        .timed("message.get_user_recent_messages")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -457,6 +765,7 @@ impl MessageRepository {
             sqlx::query_scalar("SELECT last_sequence FROM chat_sequences WHERE chat_id = $1")
                 .bind(chat_id)
                 .fetch_optional(&*self.pool)
+                .timed("message.get_latest_sequence")
                 .await
                 .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -478,6 +787,7 @@ impl MessageRepository {
         .bind(message_id)
         .bind(user_id)
         .execute(&*self.pool)
+        .timed("message.mark_message_delivered")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -493,6 +803,7 @@ impl MessageRepository {
         )
         .bind(message_id)
         .execute(&*self.pool)
+        .timed("message.mark_message_delivered")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -516,6 +827,7 @@ impl MessageRepository {
         .bind(message_id)
         .bind(user_id)
         .execute(&mut *tx)
+        .timed("message.mark_message_read")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -531,9 +843,12 @@ impl MessageRepository {
         .bind(message_id)
         .bind(user_id)
         .execute(&mut *tx)
+        .timed("message.mark_message_read")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
+        Self::advance_read_cursors(&mut tx, user_id, &[message_id]).await?;
+
         tx.commit()
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
@@ -558,12 +873,161 @@ impl MessageRepository {
         .bind(chat_id)
         .bind(user_id)
         .fetch_one(&*self.pool)
+        .timed("message.get_unread_count")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
         Ok(count)
     }
 
+    /// Count messages from others after the user's last read receipt in a chat
+    pub async fn count_unread_after_last_read(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<i64, CoreError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"SELECT COUNT(*)
+         FROM messages m
+         WHERE m.chat_id = $1
+         AND m.sender_id != $2
+         AND m.id > COALESCE(GREATEST(
+           (SELECT MAX(mr.message_id)
+            FROM message_receipts mr
+            JOIN messages rm ON rm.id = mr.message_id
+            WHERE rm.chat_id = $1 AND mr.user_id = $2 AND mr.status = 'read'),
+           (SELECT cm.last_read_message_id
+            FROM chat_members cm
+            WHERE cm.chat_id = $1 AND cm.user_id = $2)
+         ), 0)"#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .fetch_one(&*self.pool)
+        .timed("message.count_unread_after_last_read")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(count)
+    }
+
+    /// Move the user's read watermark to the chat's latest message in a single statement.
+    /// Returns the new watermark (0 for a chat without messages), or `None` when the user
+    /// is not an active member of the chat
+    pub async fn mark_chat_read(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<Option<i64>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::from_database_error(e))?;
+
+        let last_read = sqlx::query_scalar::<_, i64>(
+            r#"UPDATE chat_members
+         SET last_read_message_id = GREATEST(
+               last_read_message_id,
+               (SELECT MAX(m.id) FROM messages m WHERE m.chat_id = $1)
+             ),
+             last_read_at = NOW()
+         WHERE chat_id = $1 AND user_id = $2 AND left_at IS NULL
+         RETURNING COALESCE(last_read_message_id, 0)"#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .timed("message.mark_chat_read")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        if let Some(last_read) = last_read.filter(|&id| id > 0) {
+            Self::advance_read_cursors(&mut tx, user_id, &[last_read]).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(last_read)
+    }
+
+    /// Move the user's read cursor in each chat of `message_ids` up to the newest of them,
+    /// skipping chats the user is not an active member of. Runs in the transaction recording
+    /// the reads so cursors and receipts stay in step; a cursor is never moved back to an
+    /// older message
+    async fn advance_read_cursors(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: i64,
+        message_ids: &[i64],
+    ) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"INSERT INTO chat_read_cursors (user_id, chat_id, last_read_message_id, updated_at)
+         SELECT $1, m.chat_id, MAX(m.id), NOW()
+         FROM messages m
+         JOIN chat_members cm ON cm.chat_id = m.chat_id
+           AND cm.user_id = $1 AND cm.left_at IS NULL
+         WHERE m.id = ANY($2)
+         GROUP BY m.chat_id
+         ON CONFLICT (user_id, chat_id) DO UPDATE
+         SET last_read_message_id = EXCLUDED.last_read_message_id,
+             updated_at = NOW()
+         WHERE chat_read_cursors.last_read_message_id < EXCLUDED.last_read_message_id"#,
+        )
+        .bind(user_id)
+        .bind(message_ids)
+        .execute(&mut **tx)
+        .timed("message.advance_read_cursors")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(())
+    }
+
+    /// The user's read cursor in a chat, `None` before their first read there
+    pub async fn get_read_cursor(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<Option<ChatReadCursor>, CoreError> {
+        sqlx::query_as::<_, ChatReadCursor>(
+            r#"SELECT chat_id, user_id, last_read_message_id, updated_at
+         FROM chat_read_cursors
+         WHERE chat_id = $1 AND user_id = $2"#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .fetch_optional(&*self.pool)
+        .timed("message.get_read_cursor")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))
+    }
+
+    /// Unread messages per chat after the user's read cursor, for several chats in one query.
+    /// Every requested chat is returned, with 0 when nothing is unread
+    pub async fn count_unread_by_chat(
+        &self,
+        user_id: i64,
+        chat_ids: &[i64],
+    ) -> Result<Vec<(i64, i64)>, CoreError> {
+        sqlx::query_as::<_, (i64, i64)>(
+            r#"SELECT c.chat_id, COUNT(m.id)
+         FROM unnest($2::BIGINT[]) AS c(chat_id)
+         LEFT JOIN chat_read_cursors rc ON rc.chat_id = c.chat_id AND rc.user_id = $1
+         LEFT JOIN messages m ON m.chat_id = c.chat_id
+           AND m.sender_id != $1
+           AND m.id > COALESCE(rc.last_read_message_id, 0)
+         GROUP BY c.chat_id"#,
+        )
+        .bind(user_id)
+        .bind(chat_ids)
+        .fetch_all(&*self.pool)
+        .timed("message.count_unread_by_chat")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))
+    }
+
     /// Get read status for messages (for private chat)
     pub async fn get_message_read_status(
         &self,
@@ -581,20 +1045,24 @@ impl MessageRepository {
     .bind(message_ids)
     .bind(chat_id)
     .fetch_all(&*self.pool)
+    .timed("message.get_message_read_status")
     .await
     .map_err(|e| CoreError::from_database_error(e))?;
 
         Ok(results)
     }
 
-    /// Mark multiple messages as read (batch operation)
+    /// Mark multiple messages of a chat as read (batch operation). Ids of other chats are
+    /// ignored, as are all of them unless the user is an active member of the chat; returns
+    /// the ids that were marked
     pub async fn mark_messages_read_batch(
         &self,
+        chat_id: i64,
         message_ids: &[i64],
         user_id: i64,
-    ) -> Result<(), CoreError> {
+    ) -> Result<Vec<i64>, CoreError> {
         if message_ids.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         let mut tx = self
@@ -603,6 +1071,25 @@ impl MessageRepository {
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
+        let message_ids: Vec<i64> = sqlx::query_scalar(
+            r#"SELECT m.id
+         FROM messages m
+         JOIN chat_members cm ON cm.chat_id = m.chat_id
+           AND cm.user_id = $1 AND cm.left_at IS NULL
+         WHERE m.chat_id = $2 AND m.id = ANY($3)
+         ORDER BY m.id"#,
+        )
+        .bind(user_id)
+        .bind(chat_id)
+        .bind(message_ids)
+        .fetch_all(&mut *tx)
+        .timed("message.mark_messages_read_batch")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+        if message_ids.is_empty() {
+            return Ok(message_ids);
+        }
+
         // Batch insert read receipts
         let values: Vec<String> = message_ids
             .iter()
@@ -618,6 +1105,7 @@ impl MessageRepository {
 
         sqlx::query(&query)
             .execute(&mut *tx)
+            .timed("message.mark_messages_read_batch")
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -631,16 +1119,19 @@ impl MessageRepository {
          AND NOT ($1 = ANY(COALESCE(read_by_users, ARRAY[]::BIGINT[])))"#,
         )
         .bind(user_id)
-        .bind(message_ids)
+        .bind(&message_ids)
         .execute(&mut *tx)
+        .timed("message.mark_messages_read_batch")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
+        Self::advance_read_cursors(&mut tx, user_id, &message_ids).await?;
+
         tx.commit()
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
-        Ok(())
+        Ok(message_ids)
     }
 
     // =============================================================================
@@ -667,6 +1158,7 @@ impl MessageRepository {
         )
         .bind(message_id)
         .fetch_all(&*self.pool)
+        .timed("message.get_message_mentions")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -685,10 +1177,15 @@ impl MessageRepository {
         Ok(mentions)
     }
 
-    /// Get unread mentions for a user across all chats
+    /// A page of a user's unread mentions across all chats, newest first and one entry per
+    /// message. `since` keeps only mentions sent after it; `after` is the
+    /// `(created_at, message_id)` of the last mention of the previous page
     pub async fn get_unread_mentions_for_user(
         &self,
         user_id: i64,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64)>,
+        limit: i64,
     ) -> Result<
         Vec<(
             i64,
@@ -700,9 +1197,11 @@ impl MessageRepository {
         )>,
         CoreError,
     > {
+        let (after_created_at, after_message_id) = after.unzip();
+        // A message can mention the user directly and through @everyone; the direct one wins
         let rows = sqlx::query(
             r#"
-      SELECT DISTINCT
+      SELECT DISTINCT ON (m.created_at, m.id)
         m.chat_id,
         m.id as message_id,
         m.content,
@@ -716,12 +1215,19 @@ impl MessageRepository {
       WHERE mm.mentioned_user_id = $1
       AND (cm.last_read_message_id IS NULL OR m.id > cm.last_read_message_id)
       AND cm.left_at IS NULL
-      ORDER BY m.created_at DESC
-      LIMIT 50
+      AND ($2::TIMESTAMPTZ IS NULL OR m.created_at > $2)
+      AND ($3::TIMESTAMPTZ IS NULL OR (m.created_at, m.id) < ($3, $4::BIGINT))
+      ORDER BY m.created_at DESC, m.id DESC, (mm.mention_type = 'user') DESC
+      LIMIT $5
       "#,
         )
         .bind(user_id)
+        .bind(since)
+        .bind(after_created_at)
+        .bind(after_message_id)
+        .bind(limit)
         .fetch_all(&*self.pool)
+        .timed("message.get_unread_mentions_for_user")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -742,15 +1248,59 @@ impl MessageRepository {
         Ok(mentions)
     }
 
+    /// Count a user's unread mentions (sent after `since`) and how many of them sort at or
+    /// before the `after` keyset
+    pub async fn get_unread_mentions_page_counts(
+        &self,
+        user_id: i64,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64)>,
+    ) -> Result<(i64, i64), CoreError> {
+        let (after_created_at, after_message_id) = after.unzip();
+        let (total, listed): (i64, i64) = sqlx::query_as(
+            r#"
+      SELECT
+        COUNT(DISTINCT m.id),
+        COUNT(DISTINCT m.id) FILTER (
+          WHERE $3::TIMESTAMPTZ IS NOT NULL AND (m.created_at, m.id) >= ($3, $4::BIGINT)
+        )
+      FROM message_mentions mm
+      JOIN messages m ON m.id = mm.message_id
+      JOIN chat_members cm ON cm.chat_id = m.chat_id AND cm.user_id = $1
+      WHERE mm.mentioned_user_id = $1
+      AND (cm.last_read_message_id IS NULL OR m.id > cm.last_read_message_id)
+      AND cm.left_at IS NULL
+      AND ($2::TIMESTAMPTZ IS NULL OR m.created_at > $2)
+      "#,
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(after_created_at)
+        .bind(after_message_id)
+        .fetch_one(&*self.pool)
+        .timed("message.get_unread_mentions_page_counts")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok((total, listed))
+    }
+
     // =============================================================================
     // DETAILED RECEIPTS MANAGEMENT
     // =============================================================================
 
-    /// Get detailed read receipts for a message
+    /// A page of a message's receipts, latest first. `after` is the
+    /// `(timestamp, user_id, status)` of the last receipt of the previous page
     pub async fn get_detailed_message_receipts(
         &self,
         message_id: i64,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64, String)>,
+        limit: i64,
     ) -> Result<Vec<(i64, String, String, String, chrono::DateTime<chrono::Utc>)>, CoreError> {
+        let (after_timestamp, after_user_id, after_status) = match after {
+            Some((timestamp, user_id, status)) => (Some(timestamp), Some(user_id), Some(status)),
+            None => (None, None, None),
+        };
         let rows = sqlx::query(
             r#"
       SELECT 
@@ -762,11 +1312,19 @@ impl MessageRepository {
       FROM message_receipts mr
       JOIN users u ON u.id = mr.user_id
       WHERE mr.message_id = $1
-      ORDER BY mr.timestamp DESC, mr.status DESC
+      AND ($2::TIMESTAMPTZ IS NULL
+        OR (mr.timestamp, mr.user_id, mr.status) < ($2, $3::BIGINT, $4::VARCHAR))
+      ORDER BY mr.timestamp DESC, mr.user_id DESC, mr.status DESC
+      LIMIT $5
       "#,
         )
         .bind(message_id)
+        .bind(after_timestamp)
+        .bind(after_user_id)
+        .bind(after_status)
+        .bind(limit)
         .fetch_all(&*self.pool)
+        .timed("message.get_detailed_message_receipts")
         .await
         .map_err(|e| CoreError::from_database_error(e))?;
 
@@ -786,6 +1344,212 @@ impl MessageRepository {
         Ok(receipts)
     }
 
+    /// Count a message's receipts and how many of them sort at or before the `after` keyset
+    pub async fn get_message_receipts_page_counts(
+        &self,
+        message_id: i64,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64, String)>,
+    ) -> Result<(i64, i64), CoreError> {
+        let (after_timestamp, after_user_id, after_status) = match after {
+            Some((timestamp, user_id, status)) => (Some(timestamp), Some(user_id), Some(status)),
+            None => (None, None, None),
+        };
+        let (total, listed): (i64, i64) = sqlx::query_as(
+            r#"SELECT COUNT(*),
+                      COUNT(*) FILTER (
+                        WHERE $2::TIMESTAMPTZ IS NOT NULL
+                        AND (timestamp, user_id, status) >= ($2, $3::BIGINT, $4::VARCHAR)
+                      )
+               FROM message_receipts WHERE message_id = $1"#,
+        )
+        .bind(message_id)
+        .bind(after_timestamp)
+        .bind(after_user_id)
+        .bind(after_status)
+        .fetch_one(&*self.pool)
+        .timed("message.get_message_receipts_page_counts")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok((total, listed))
+    }
+
+    /// Delivered and read receipts of a message as `(user_id, status, timestamp)`
+    pub async fn get_message_receipts(
+        &self,
+        message_id: i64,
+    ) -> Result<Vec<(i64, String, chrono::DateTime<chrono::Utc>)>, CoreError> {
+        let receipts = sqlx::query_as::<_, (i64, String, chrono::DateTime<chrono::Utc>)>(
+            r#"SELECT user_id, status, timestamp
+         FROM message_receipts
+         WHERE message_id = $1 AND status IN ('delivered', 'read')
+         ORDER BY user_id, timestamp"#,
+        )
+        .bind(message_id)
+        .fetch_all(&*self.pool)
+        .timed("message.get_message_receipts")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(receipts)
+    }
+
+    /// Number of users with a read receipt for a message
+    pub async fn count_message_readers(&self, message_id: i64) -> Result<i64, CoreError> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM message_receipts WHERE message_id = $1 AND status = 'read'",
+        )
+        .bind(message_id)
+        .fetch_one(&*self.pool)
+        .timed("message.count_message_readers")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(count)
+    }
+
+    /// First `limit` readers of a message in read order, as
+    /// `(user_id, username, fullname, avatar_url, read_at)`
+    pub async fn list_message_readers(
+        &self,
+        message_id: i64,
+        limit: i64,
+    ) -> Result<
+        Vec<(
+            i64,
+            String,
+            String,
+            Option<String>,
+            chrono::DateTime<chrono::Utc>,
+        )>,
+        CoreError,
+    > {
+        let readers = sqlx::query_as(
+            r#"SELECT mr.user_id,
+                COALESCE(u.username, u.fullname) AS username,
+                u.fullname,
+                u.avatar_url,
+                mr.timestamp
+         FROM message_receipts mr
+         JOIN users u ON u.id = mr.user_id
+         WHERE mr.message_id = $1 AND mr.status = 'read'
+         ORDER BY mr.timestamp, mr.user_id
+         LIMIT $2"#,
+        )
+        .bind(message_id)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .timed("message.list_message_readers")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(readers)
+    }
+
+    /// Tombstone up to `limit` live messages of a workspace created before `cutoff`,
+    /// clearing their content and files; returns `(message_id, chat_id)` of each tombstone
+    pub async fn tombstone_messages_before(
+        &self,
+        workspace_id: i64,
+        cutoff: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<(i64, i64)>, CoreError> {
+        let tombstoned = sqlx::query_as::<_, (i64, i64)>(
+            r#"UPDATE messages
+         SET content = '', files = '{}', deleted_at = NOW()
+         WHERE id IN (
+           SELECT m.id
+           FROM messages m
+           JOIN chats c ON c.id = m.chat_id
+           WHERE c.workspace_id = $1
+           AND m.created_at < $2
+           AND m.deleted_at IS NULL
+           ORDER BY m.id
+           LIMIT $3
+         )
+         RETURNING id, chat_id"#,
+        )
+        .bind(workspace_id)
+        .bind(cutoff)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .timed("message.tombstone_messages_before")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(tombstoned)
+    }
+
+    /// Tombstone up to `limit` messages of a sender that still hold content or files, dropping
+    /// their edit history and embeddings; rows keep their sender, chat and timestamps.
+    /// Returns (id, chat_id) of the messages cleared
+    pub async fn tombstone_messages_by_sender(
+        &self,
+        sender_id: i64,
+        limit: i64,
+    ) -> Result<Vec<(i64, i64)>, CoreError> {
+        let tombstoned = sqlx::query_as::<_, (i64, i64)>(
+            r#"WITH tombstoned AS (
+           UPDATE messages
+           SET content = '', files = '{}', deleted_at = COALESCE(deleted_at, NOW())
+           WHERE id IN (
+             SELECT id
+             FROM messages
+             WHERE sender_id = $1
+             AND (content <> '' OR files <> '{}')
+             ORDER BY id
+             LIMIT $2
+           )
+           RETURNING id, chat_id
+         ),
+         edits AS (
+           DELETE FROM message_edits WHERE message_id IN (SELECT id FROM tombstoned)
+         ),
+         embeddings AS (
+           DELETE FROM message_embeddings WHERE message_id IN (SELECT id FROM tombstoned)
+         )
+         SELECT id, chat_id FROM tombstoned"#,
+        )
+        .bind(sender_id)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .timed("message.tombstone_messages_by_sender")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(tombstoned)
+    }
+
+    /// Live messages after `after_id` that have no stored embedding yet, oldest first,
+    /// optionally limited to one chat; rows are (id, chat_id, sender_id, content, created_at)
+    pub async fn list_messages_without_embeddings(
+        &self,
+        chat_id: Option<i64>,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<(i64, i64, i64, String, chrono::DateTime<chrono::Utc>)>, CoreError> {
+        let rows = sqlx::query_as(
+            r#"SELECT m.id, m.chat_id, m.sender_id, m.content, m.created_at
+         FROM messages m
+         WHERE m.id > $1
+         AND ($2::BIGINT IS NULL OR m.chat_id = $2)
+         AND m.deleted_at IS NULL
+         AND m.content <> ''
+         AND NOT EXISTS (SELECT 1 FROM message_embeddings e WHERE e.message_id = m.id)
+         ORDER BY m.id
+         LIMIT $3"#,
+        )
+        .bind(after_id)
+        .bind(chat_id)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .timed("message.list_messages_without_embeddings")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(rows)
+    }
+
     // =============================================================================
     // ENHANCED READ TRACKING
     // =============================================================================
@@ -802,9 +1566,61 @@ impl MessageRepository {
             .bind(chat_id)
             .bind(message_id)
             .execute(&*self.pool)
+            .timed("message.mark_message_read_enhanced")
             .await
             .map_err(|e| CoreError::from_database_error(e))?;
 
         Ok(())
     }
+
+    /// Mark a message fully read once every member who was in the chat when it was sent, and
+    /// is still in it, has a read receipt for it. Returns `(chat_id, sender_id, readers)` only
+    /// to the single call that completed it
+    pub async fn claim_message_fully_read(
+        &self,
+        message_id: i64,
+    ) -> Result<Option<(i64, i64, Vec<i64>)>, CoreError> {
+        // The row lock on the message lets only one concurrent reader see fully_read_at unset
+        let claimed = sqlx::query_as(
+            r#"UPDATE messages m
+         SET fully_read_at = NOW()
+         WHERE m.id = $1
+         AND m.fully_read_at IS NULL
+         AND EXISTS (
+           SELECT 1 FROM message_receipts mr
+           WHERE mr.message_id = m.id AND mr.status = 'read' AND mr.user_id != m.sender_id
+         )
+         AND NOT EXISTS (
+           SELECT 1 FROM chat_members cm
+           WHERE cm.chat_id = m.chat_id
+           AND cm.left_at IS NULL
+           AND cm.joined_at <= m.created_at
+           AND cm.user_id != m.sender_id
+           AND NOT EXISTS (
+             SELECT 1 FROM message_receipts mr
+             WHERE mr.message_id = m.id AND mr.user_id = cm.user_id AND mr.status = 'read'
+           )
+         )
+         RETURNING m.chat_id, m.sender_id,
+           ARRAY(
+             SELECT mr.user_id FROM message_receipts mr
+             WHERE mr.message_id = m.id AND mr.status = 'read' AND mr.user_id != m.sender_id
+             ORDER BY mr.user_id
+           )"#,
+        )
+        .bind(message_id)
+        .fetch_optional(&*self.pool)
+        .timed("message.claim_message_fully_read")
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(claimed)
+    }
+}
+
+#[async_trait]
+impl SenderProfileLookup for MessageRepository {
+    async fn find_senders(&self, user_ids: &[i64]) -> Result<Vec<MessageSender>, CoreError> {
+        MessageRepository::find_senders(self, user_ids).await
+    }
 }
@@ -0,0 +1,295 @@
+//! # Slash Commands
+//!
+//! **Responsibility**: Recognise `/name args` messages and run the registered command
+//! **Scope**: A command replies either publicly, stored as a normal message from the sender, or
+//! ephemerally, shown only to the sender. `//text` escapes a literal leading `/`
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use fechatter_core::{error::CoreError, ChatId, UserId, WorkspaceId};
+
+/// Who sees a command's reply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandVisibility {
+    /// Only the sender, pushed over SSE and never stored
+    Ephemeral,
+    /// Everyone in the chat, sent as a regular message
+    Public,
+}
+
+/// Reply produced by a slash command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandResponse {
+    pub visibility: CommandVisibility,
+    pub content: String,
+}
+
+impl CommandResponse {
+    pub fn ephemeral(content: impl Into<String>) -> Self {
+        Self {
+            visibility: CommandVisibility::Ephemeral,
+            content: content.into(),
+        }
+    }
+
+    pub fn public(content: impl Into<String>) -> Self {
+        Self {
+            visibility: CommandVisibility::Public,
+            content: content.into(),
+        }
+    }
+}
+
+/// Where and by whom a command was invoked
+#[derive(Debug, Clone)]
+pub struct CommandContext {
+    pub chat_id: ChatId,
+    pub sender_id: UserId,
+    pub sender_name: String,
+    pub workspace_id: WorkspaceId,
+}
+
+/// A command invoked as `/name args`
+#[async_trait]
+pub trait SlashCommand: Send + Sync {
+    /// Name without the leading `/`; lowercase ASCII letters, digits, `-` and `_`
+    fn name(&self) -> &'static str;
+
+    /// One-line summary listed by `/help`
+    fn description(&self) -> &'static str;
+
+    async fn execute(&self, ctx: &CommandContext, args: &str)
+        -> Result<CommandResponse, CoreError>;
+}
+
+/// How message content is treated on send
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParsedContent<'a> {
+    /// Not a command; stored unchanged
+    Plain,
+    /// `//text`: stored as `/text`
+    Escaped(&'a str),
+    /// `/name args`, with surrounding whitespace trimmed from `args`
+    Command { name: &'a str, args: &'a str },
+}
+
+/// Classify message content. Only `/` directly followed by a command-shaped word counts as a
+/// command, so paths such as `/usr/bin` or a lone `/` stay plain text
+pub fn parse_command(content: &str) -> ParsedContent<'_> {
+    let Some(rest) = content.strip_prefix('/') else {
+        return ParsedContent::Plain;
+    };
+    if rest.starts_with('/') {
+        return ParsedContent::Escaped(rest);
+    }
+
+    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    let name = &rest[..end];
+    let is_command_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !is_command_name {
+        return ParsedContent::Plain;
+    }
+
+    ParsedContent::Command {
+        name,
+        args: rest[end..].trim(),
+    }
+}
+
+/// Slash commands available to `send_message`, keyed by name
+#[derive(Default, Clone)]
+pub struct SlashCommandRegistry {
+    commands: HashMap<&'static str, Arc<dyn SlashCommand>>,
+}
+
+impl SlashCommandRegistry {
+    /// Registry with the built-in `/help` and `/shrug`
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::default();
+        registry.register(Arc::new(ShrugCommand));
+        registry.register(Arc::new(HelpCommand {
+            commands: registry.summaries(),
+        }));
+        registry
+    }
+
+    /// Add a command, replacing any registered under the same name
+    pub fn register(&mut self, command: Arc<dyn SlashCommand>) {
+        self.commands.insert(command.name(), command);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn SlashCommand>> {
+        self.commands.get(name)
+    }
+
+    /// `(name, description)` of every command, sorted by name
+    pub fn summaries(&self) -> Vec<(&'static str, &'static str)> {
+        let mut summaries: Vec<_> = self
+            .commands
+            .values()
+            .map(|command| (command.name(), command.description()))
+            .collect();
+        summaries.sort_unstable();
+        summaries
+    }
+
+    /// Run the command `name`; unknown commands get an ephemeral error reply
+    pub async fn dispatch(
+        &self,
+        ctx: &CommandContext,
+        name: &str,
+        args: &str,
+    ) -> Result<CommandResponse, CoreError> {
+        match self.get(name) {
+            Some(command) => command.execute(ctx, args).await,
+            None => Ok(CommandResponse::ephemeral(format!(
+                "Unknown command /{}. Type /help for the available commands, or start the message with // to send it as text.",
+                name
+            ))),
+        }
+    }
+}
+
+/// `/shrug [text]`: posts the text followed by ¯\_(ツ)_/¯
+struct ShrugCommand;
+
+#[async_trait]
+impl SlashCommand for ShrugCommand {
+    fn name(&self) -> &'static str {
+        "shrug"
+    }
+
+    fn description(&self) -> &'static str {
+        "Append ¯\\_(ツ)_/¯ to your message"
+    }
+
+    async fn execute(
+        &self,
+        _ctx: &CommandContext,
+        args: &str,
+    ) -> Result<CommandResponse, CoreError> {
+        let shrug = "¯\\_(ツ)_/¯";
+        Ok(CommandResponse::public(if args.is_empty() {
+            shrug.to_string()
+        } else {
+            format!("{} {}", args, shrug)
+        }))
+    }
+}
+
+/// `/help`: lists the commands, visible to the sender only
+struct HelpCommand {
+    /// Commands registered before `/help` itself
+    commands: Vec<(&'static str, &'static str)>,
+}
+
+#[async_trait]
+impl SlashCommand for HelpCommand {
+    fn name(&self) -> &'static str {
+        "help"
+    }
+
+    fn description(&self) -> &'static str {
+        "List the available commands"
+    }
+
+    async fn execute(
+        &self,
+        _ctx: &CommandContext,
+        _args: &str,
+    ) -> Result<CommandResponse, CoreError> {
+        let mut lines = vec![format!("/{} - {}", self.name(), self.description())];
+        lines.extend(
+            self.commands
+                .iter()
+                .map(|(name, description)| format!("/{} - {}", name, description)),
+        );
+        lines.sort();
+        Ok(CommandResponse::ephemeral(lines.join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> CommandContext {
+        CommandContext {
+            chat_id: ChatId::new(1),
+            sender_id: UserId::new(2),
+            sender_name: "Alice".to_string(),
+            workspace_id: WorkspaceId::new(3),
+        }
+    }
+
+    struct EchoCommand;
+
+    #[async_trait]
+    impl SlashCommand for EchoCommand {
+        fn name(&self) -> &'static str {
+            "echo"
+        }
+
+        fn description(&self) -> &'static str {
+            "Echo the arguments back to the sender"
+        }
+
+        async fn execute(
+            &self,
+            ctx: &CommandContext,
+            args: &str,
+        ) -> Result<CommandResponse, CoreError> {
+            Ok(CommandResponse::ephemeral(format!(
+                "{}: {}",
+                ctx.sender_name, args
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn registered_command_should_receive_its_arguments() {
+        let mut registry = SlashCommandRegistry::with_defaults();
+        registry.register(Arc::new(EchoCommand));
+
+        let ParsedContent::Command { name, args } = parse_command("/echo  hello there ") else {
+            panic!("expected a command");
+        };
+        assert_eq!((name, args), ("echo", "hello there"));
+
+        let response = registry.dispatch(&context(), name, args).await.unwrap();
+        assert_eq!(response, CommandResponse::ephemeral("Alice: hello there"));
+
+        let response = registry.dispatch(&context(), "shrug", "ok").await.unwrap();
+        assert_eq!(response, CommandResponse::public("ok ¯\\_(ツ)_/¯"));
+
+        let help = registry.dispatch(&context(), "help", "").await.unwrap();
+        assert_eq!(help.visibility, CommandVisibility::Ephemeral);
+        assert!(help.content.contains("/shrug"));
+    }
+
+    #[tokio::test]
+    async fn unknown_command_should_get_an_ephemeral_error() {
+        let registry = SlashCommandRegistry::with_defaults();
+
+        let response = registry
+            .dispatch(&context(), "giphy", "cats")
+            .await
+            .unwrap();
+        assert_eq!(response.visibility, CommandVisibility::Ephemeral);
+        assert!(response.content.contains("Unknown command /giphy"));
+    }
+
+    #[test]
+    fn escaped_slash_should_be_sent_as_literal_text() {
+        assert_eq!(parse_command("//shrug"), ParsedContent::Escaped("/shrug"));
+        assert_eq!(parse_command("///"), ParsedContent::Escaped("//"));
+        assert_eq!(parse_command("/usr/bin is a path"), ParsedContent::Plain);
+        assert_eq!(parse_command("/ spaced"), ParsedContent::Plain);
+        assert_eq!(parse_command("hello /shrug"), ParsedContent::Plain);
+    }
+}
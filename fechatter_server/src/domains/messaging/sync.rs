@@ -0,0 +1,252 @@
+//! # Delta Sync
+//!
+//! **Responsibility**: Everything that changed in a user's chats since a cursor
+//! **Scope**: Messages (new, edited and tombstoned), membership changes and the user's own
+//! read state. Messages are paged in `(updated_at, id)` order; memberships and read state are
+//! bounded by the number of chats and returned whole on every page
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use fechatter_core::{error::CoreError, ChatId, Message, MessageId, UserId};
+
+/// Seconds a final cursor is rewound by. `updated_at` is the writing transaction's start time,
+/// so a change committed just after a sync can carry an earlier timestamp than the sync itself;
+/// the overlap makes the next sync see it. Clients apply changes idempotently
+const CURSOR_OVERLAP_SECS: i64 = 2;
+
+/// Position in the change stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncCursor {
+    pub at: DateTime<Utc>,
+    /// Last message returned at `at`, for paging through messages sharing a timestamp
+    pub message_id: i64,
+}
+
+impl SyncCursor {
+    /// Cursor for changes after `at`
+    pub fn at(at: DateTime<Utc>) -> Self {
+        Self { at, message_id: 0 }
+    }
+
+    /// Cursor for a sync finishing at `now`, rewound to also catch late commits
+    pub fn overlapping(now: DateTime<Utc>) -> Self {
+        Self::at(now - Duration::seconds(CURSOR_OVERLAP_SECS))
+    }
+}
+
+/// A deleted message; its content and files are already cleared
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MessageTombstone {
+    pub id: i64,
+    pub chat_id: i64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MembershipChangeKind {
+    Joined,
+    Left,
+}
+
+/// A member joining or leaving one of the user's chats, the user included
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MembershipChange {
+    pub chat_id: i64,
+    pub user_id: i64,
+    pub change: MembershipChangeKind,
+    pub at: DateTime<Utc>,
+}
+
+/// The user's read watermark in a chat, as moved from any of their devices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReadStateChange {
+    pub chat_id: i64,
+    pub last_read_message_id: Option<i64>,
+    pub read_at: DateTime<Utc>,
+}
+
+/// One page of changes
+#[derive(Debug, Clone)]
+pub struct SyncChanges {
+    /// Live messages created or edited since the cursor, oldest change first
+    pub messages: Vec<Message>,
+    pub tombstones: Vec<MessageTombstone>,
+    pub memberships: Vec<MembershipChange>,
+    pub read_states: Vec<ReadStateChange>,
+    pub next_cursor: SyncCursor,
+    /// More message changes are waiting; sync again with `next_cursor` right away
+    pub has_more: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct MessageChangeRow {
+    id: i64,
+    chat_id: i64,
+    sender_id: i64,
+    content: String,
+    files: Option<Vec<String>>,
+    created_at: DateTime<Utc>,
+    idempotency_key: Option<uuid::Uuid>,
+    client_message_id: Option<String>,
+    updated_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct MembershipRow {
+    chat_id: i64,
+    user_id: i64,
+    joined_at: DateTime<Utc>,
+    left_at: Option<DateTime<Utc>>,
+}
+
+pub struct SyncRepository {
+    pool: Arc<PgPool>,
+}
+
+impl SyncRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Database time, the starting cursor for a client that has just loaded everything
+    pub async fn now(&self) -> Result<DateTime<Utc>, CoreError> {
+        sqlx::query_scalar("SELECT NOW()")
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| CoreError::from_database_error(e))
+    }
+
+    /// Changes visible to `user_id` after `since`, with at most `limit` message changes
+    pub async fn changes_since(
+        &self,
+        user_id: UserId,
+        since: SyncCursor,
+        limit: i64,
+    ) -> Result<SyncChanges, CoreError> {
+        let user_id = i64::from(user_id);
+        let now = self.now().await?;
+
+        // Chats the user has left stop syncing messages; the leave itself is reported below
+        let mut rows = sqlx::query_as::<_, MessageChangeRow>(
+            r#"SELECT m.id, m.chat_id, m.sender_id, m.content, m.files, m.created_at,
+                      m.idempotency_key, m.client_message_id, m.updated_at, m.deleted_at
+               FROM messages m
+               JOIN chat_members cm ON cm.chat_id = m.chat_id
+               WHERE cm.user_id = $1 AND cm.left_at IS NULL
+               AND (m.updated_at, m.id) > ($2, $3)
+               ORDER BY m.updated_at, m.id
+               LIMIT $4"#,
+        )
+        .bind(user_id)
+        .bind(since.at)
+        .bind(since.message_id)
+        .bind(limit + 1)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+        let next_cursor = match rows.last() {
+            Some(last) if has_more => SyncCursor {
+                at: last.updated_at,
+                message_id: last.id,
+            },
+            _ => {
+                let cursor = SyncCursor::overlapping(now);
+                if cursor.at > since.at {
+                    cursor
+                } else {
+                    SyncCursor::at(since.at)
+                }
+            }
+        };
+
+        let mut messages = Vec::new();
+        let mut tombstones = Vec::new();
+        for row in rows {
+            match row.deleted_at {
+                Some(deleted_at) => tombstones.push(MessageTombstone {
+                    id: row.id,
+                    chat_id: row.chat_id,
+                    deleted_at,
+                }),
+                None => messages.push(Message {
+                    id: MessageId(row.id),
+                    chat_id: ChatId(row.chat_id),
+                    sender_id: UserId(row.sender_id),
+                    content: row.content,
+                    files: row.files,
+                    created_at: row.created_at,
+                    idempotency_key: row.idempotency_key,
+                    client_message_id: row.client_message_id,
+                }),
+            }
+        }
+
+        let memberships = sqlx::query_as::<_, MembershipRow>(
+            r#"SELECT cm.chat_id, cm.user_id, cm.joined_at, cm.left_at
+               FROM chat_members cm
+               WHERE cm.chat_id IN (
+                 SELECT chat_id FROM chat_members
+                 WHERE user_id = $1 AND (left_at IS NULL OR left_at > $2)
+               )
+               AND (cm.joined_at > $2 OR cm.left_at > $2)
+               ORDER BY GREATEST(cm.joined_at, cm.left_at), cm.chat_id, cm.user_id"#,
+        )
+        .bind(user_id)
+        .bind(since.at)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?
+        .into_iter()
+        .map(|row| match row.left_at {
+            Some(left_at) if left_at > since.at => MembershipChange {
+                chat_id: row.chat_id,
+                user_id: row.user_id,
+                change: MembershipChangeKind::Left,
+                at: left_at,
+            },
+            _ => MembershipChange {
+                chat_id: row.chat_id,
+                user_id: row.user_id,
+                change: MembershipChangeKind::Joined,
+                at: row.joined_at,
+            },
+        })
+        .collect();
+
+        let read_states = sqlx::query_as::<_, (i64, Option<i64>, DateTime<Utc>)>(
+            r#"SELECT chat_id, last_read_message_id, last_read_at
+               FROM chat_members
+               WHERE user_id = $1 AND left_at IS NULL AND last_read_at > $2
+               ORDER BY last_read_at, chat_id"#,
+        )
+        .bind(user_id)
+        .bind(since.at)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?
+        .into_iter()
+        .map(|(chat_id, last_read_message_id, read_at)| ReadStateChange {
+            chat_id,
+            last_read_message_id,
+            read_at,
+        })
+        .collect();
+
+        Ok(SyncChanges {
+            messages,
+            tombstones,
+            memberships,
+            read_states,
+            next_cursor,
+            has_more,
+        })
+    }
+}
@@ -2,5 +2,6 @@ pub mod auth;
 pub mod chat;
 pub mod messaging;
 pub mod notification;
+pub mod permission;
 pub mod user;
 pub mod workspace;
@@ -0,0 +1,11 @@
+//! # Permissions
+//!
+//! **Responsibility**: The single place that decides who can do what
+//! **Layout**: `policy` holds the rules as pure functions over roles; `service` loads the
+//! roles from the database and applies them
+
+pub mod policy;
+pub mod service;
+
+pub use policy::{ChatAction, ChatRole, Decision, Denial, WorkspaceRole};
+pub use service::PermissionService;
@@ -0,0 +1,380 @@
+//! # Permission Policy
+//!
+//! **Responsibility**: Decide who may do what, from roles alone
+//! **Principles**: Pure functions, no I/O; `PermissionService` loads the roles and asks here,
+//! so every rule can be tested as a table
+
+use std::fmt;
+
+use fechatter_core::{error::CoreError, UserId};
+
+/// Role of a user in their workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WorkspaceRole {
+    Member,
+    /// `users.role = 'admin'`
+    Admin,
+    /// `workspaces.owner_id`
+    Owner,
+}
+
+impl WorkspaceRole {
+    /// Parse a stored role; the owner is resolved by the caller
+    pub fn from_stored(role: &str) -> Self {
+        match role {
+            "owner" => Self::Owner,
+            "admin" => Self::Admin,
+            _ => Self::Member,
+        }
+    }
+}
+
+/// Role of an active member in a chat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChatRole {
+    Member,
+    Moderator,
+    Admin,
+    /// The chat's creator, or whoever ownership was transferred to
+    Owner,
+}
+
+impl ChatRole {
+    /// Parse `chat_members.role`; unknown values get the least privilege
+    pub fn from_stored(role: &str) -> Self {
+        match role {
+            "owner" => Self::Owner,
+            "admin" => Self::Admin,
+            "moderator" => Self::Moderator,
+            _ => Self::Member,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Member => "member",
+            Self::Moderator => "moderator",
+            Self::Admin => "admin",
+            Self::Owner => "owner",
+        }
+    }
+}
+
+/// Chat management operations, by increasing impact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatAction {
+    /// Rename or change the description
+    UpdateDetails,
+    /// Set the minimum interval between a member's messages
+    SetSlowMode,
+    /// Add or remove members
+    ManageMembers,
+    Delete,
+    TransferOwnership,
+}
+
+impl ChatAction {
+    /// Least chat role allowed to perform the action
+    fn required_role(self) -> ChatRole {
+        match self {
+            Self::UpdateDetails | Self::SetSlowMode => ChatRole::Admin,
+            Self::ManageMembers | Self::Delete | Self::TransferOwnership => ChatRole::Owner,
+        }
+    }
+}
+
+/// Why an action was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Denial {
+    NotChatMember,
+    NotWorkspaceMember,
+    NotAuthor,
+    ChatRoleRequired(ChatRole),
+    WorkspaceAdminRequired,
+    WorkspaceOwnerRequired,
+}
+
+impl fmt::Display for Denial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotChatMember => write!(f, "User is not a member of this chat"),
+            Self::NotWorkspaceMember => write!(f, "User is not a member of this workspace"),
+            Self::NotAuthor => write!(f, "Only the message sender can perform this action"),
+            Self::ChatRoleRequired(ChatRole::Owner) => {
+                write!(f, "Only the chat owner can perform this action")
+            }
+            Self::ChatRoleRequired(role) => write!(
+                f,
+                "Chat {} or higher required for this action",
+                role.as_str()
+            ),
+            Self::WorkspaceAdminRequired => write!(f, "Admin access required"),
+            Self::WorkspaceOwnerRequired => {
+                write!(f, "Only the workspace owner can perform this action")
+            }
+        }
+    }
+}
+
+impl From<Denial> for CoreError {
+    fn from(denial: Denial) -> Self {
+        CoreError::Unauthorized(denial.to_string())
+    }
+}
+
+/// Outcome of a permission check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Allow,
+    Deny(Denial),
+}
+
+impl Decision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allow)
+    }
+
+    /// `Err` with the reason when denied, for use with `?`
+    pub fn check(self) -> Result<(), Denial> {
+        match self {
+            Self::Allow => Ok(()),
+            Self::Deny(denial) => Err(denial),
+        }
+    }
+
+    fn allow_if(condition: bool, denial: Denial) -> Self {
+        if condition {
+            Self::Allow
+        } else {
+            Self::Deny(denial)
+        }
+    }
+}
+
+/// Only the author may change what a message says, and only while still in the chat
+pub fn can_edit_message(actor: UserId, author: UserId, chat_role: Option<ChatRole>) -> Decision {
+    if chat_role.is_none() {
+        return Decision::Deny(Denial::NotChatMember);
+    }
+    Decision::allow_if(actor == author, Denial::NotAuthor)
+}
+
+/// The author may delete their message; chat moderators and above may delete anyone's
+pub fn can_delete_message(actor: UserId, author: UserId, chat_role: Option<ChatRole>) -> Decision {
+    let Some(role) = chat_role else {
+        return Decision::Deny(Denial::NotChatMember);
+    };
+    if actor == author {
+        return Decision::Allow;
+    }
+    Decision::allow_if(
+        role >= ChatRole::Moderator,
+        Denial::ChatRoleRequired(ChatRole::Moderator),
+    )
+}
+
+/// Chat settings, membership and lifecycle are reserved to its admins and owner
+pub fn can_manage_chat(chat_role: Option<ChatRole>, action: ChatAction) -> Decision {
+    let Some(role) = chat_role else {
+        return Decision::Deny(Denial::NotChatMember);
+    };
+    let required = action.required_role();
+    Decision::allow_if(role >= required, Denial::ChatRoleRequired(required))
+}
+
+/// Chat moderators and above may post without waiting out slow mode
+pub fn is_slow_mode_exempt(chat_role: Option<ChatRole>) -> bool {
+    chat_role.is_some_and(|role| role >= ChatRole::Moderator)
+}
+
+/// Chat admins and above may edit their messages after the edit window closes
+pub fn is_edit_window_exempt(chat_role: Option<ChatRole>) -> bool {
+    chat_role.is_some_and(|role| role >= ChatRole::Admin)
+}
+
+/// Workspace-wide settings and user management are reserved to its admins and owner
+pub fn can_manage_workspace(workspace_role: Option<WorkspaceRole>) -> Decision {
+    let Some(role) = workspace_role else {
+        return Decision::Deny(Denial::NotWorkspaceMember);
+    };
+    Decision::allow_if(role >= WorkspaceRole::Admin, Denial::WorkspaceAdminRequired)
+}
+
+/// Only the owner may hand the workspace over; admins manage it but can't take it
+pub fn can_transfer_workspace(workspace_role: Option<WorkspaceRole>) -> Decision {
+    let Some(role) = workspace_role else {
+        return Decision::Deny(Denial::NotWorkspaceMember);
+    };
+    Decision::allow_if(role == WorkspaceRole::Owner, Denial::WorkspaceOwnerRequired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTHOR: UserId = UserId(1);
+    const OTHER: UserId = UserId(2);
+
+    const CHAT_ROLES: [Option<ChatRole>; 5] = [
+        None,
+        Some(ChatRole::Member),
+        Some(ChatRole::Moderator),
+        Some(ChatRole::Admin),
+        Some(ChatRole::Owner),
+    ];
+
+    #[test]
+    fn edit_message_matrix() {
+        for role in CHAT_ROLES {
+            let own = can_edit_message(AUTHOR, AUTHOR, role);
+            let others = can_edit_message(OTHER, AUTHOR, role);
+            match role {
+                None => {
+                    assert_eq!(own, Decision::Deny(Denial::NotChatMember));
+                    assert_eq!(others, Decision::Deny(Denial::NotChatMember));
+                }
+                Some(_) => {
+                    assert_eq!(own, Decision::Allow, "{:?} editing own message", role);
+                    // Nobody rewrites someone else's words, not even the owner
+                    assert_eq!(others, Decision::Deny(Denial::NotAuthor), "{:?}", role);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn delete_message_matrix() {
+        let expected_for_others = [
+            (None, false),
+            (Some(ChatRole::Member), false),
+            (Some(ChatRole::Moderator), true),
+            (Some(ChatRole::Admin), true),
+            (Some(ChatRole::Owner), true),
+        ];
+        for (role, allowed) in expected_for_others {
+            assert_eq!(
+                can_delete_message(OTHER, AUTHOR, role).is_allowed(),
+                allowed,
+                "{:?} deleting another user's message",
+                role
+            );
+            assert_eq!(
+                can_delete_message(AUTHOR, AUTHOR, role).is_allowed(),
+                role.is_some(),
+                "{:?} deleting own message",
+                role
+            );
+        }
+        assert_eq!(
+            can_delete_message(OTHER, AUTHOR, Some(ChatRole::Member)),
+            Decision::Deny(Denial::ChatRoleRequired(ChatRole::Moderator))
+        );
+    }
+
+    #[test]
+    fn manage_chat_matrix() {
+        use ChatAction::*;
+
+        // (action, [none, member, moderator, admin, owner])
+        let table = [
+            (UpdateDetails, [false, false, false, true, true]),
+            (SetSlowMode, [false, false, false, true, true]),
+            (ManageMembers, [false, false, false, false, true]),
+            (Delete, [false, false, false, false, true]),
+            (TransferOwnership, [false, false, false, false, true]),
+        ];
+        for (action, expected) in table {
+            for (role, allowed) in CHAT_ROLES.into_iter().zip(expected) {
+                assert_eq!(
+                    can_manage_chat(role, action).is_allowed(),
+                    allowed,
+                    "{:?} performing {:?}",
+                    role,
+                    action
+                );
+            }
+        }
+        assert_eq!(
+            can_manage_chat(None, Delete),
+            Decision::Deny(Denial::NotChatMember)
+        );
+        assert_eq!(
+            can_manage_chat(Some(ChatRole::Admin), Delete),
+            Decision::Deny(Denial::ChatRoleRequired(ChatRole::Owner))
+        );
+    }
+
+    #[test]
+    fn slow_mode_should_exempt_moderators_and_above() {
+        let exempt: Vec<bool> = CHAT_ROLES.into_iter().map(is_slow_mode_exempt).collect();
+        assert_eq!(exempt, [false, false, true, true, true]);
+    }
+
+    #[test]
+    fn edit_window_should_exempt_admins_and_above() {
+        let exempt: Vec<bool> = CHAT_ROLES.into_iter().map(is_edit_window_exempt).collect();
+        assert_eq!(exempt, [false, false, false, true, true]);
+    }
+
+    #[test]
+    fn manage_workspace_matrix() {
+        let table = [
+            (None, false),
+            (Some(WorkspaceRole::Member), false),
+            (Some(WorkspaceRole::Admin), true),
+            (Some(WorkspaceRole::Owner), true),
+        ];
+        for (role, allowed) in table {
+            assert_eq!(
+                can_manage_workspace(role).is_allowed(),
+                allowed,
+                "{:?}",
+                role
+            );
+        }
+        assert_eq!(
+            can_manage_workspace(None),
+            Decision::Deny(Denial::NotWorkspaceMember)
+        );
+    }
+
+    #[test]
+    fn transfer_workspace_matrix() {
+        let table = [
+            (None, false),
+            (Some(WorkspaceRole::Member), false),
+            (Some(WorkspaceRole::Admin), false),
+            (Some(WorkspaceRole::Owner), true),
+        ];
+        for (role, allowed) in table {
+            assert_eq!(
+                can_transfer_workspace(role).is_allowed(),
+                allowed,
+                "{:?}",
+                role
+            );
+        }
+        assert_eq!(
+            can_transfer_workspace(Some(WorkspaceRole::Admin)),
+            Decision::Deny(Denial::WorkspaceOwnerRequired)
+        );
+    }
+
+    #[test]
+    fn stored_roles_should_parse_with_least_privilege_fallback() {
+        assert_eq!(ChatRole::from_stored("owner"), ChatRole::Owner);
+        assert_eq!(ChatRole::from_stored("moderator"), ChatRole::Moderator);
+        assert_eq!(ChatRole::from_stored("superuser"), ChatRole::Member);
+        assert_eq!(WorkspaceRole::from_stored("admin"), WorkspaceRole::Admin);
+        assert_eq!(WorkspaceRole::from_stored(""), WorkspaceRole::Member);
+    }
+
+    #[test]
+    fn denial_should_surface_as_unauthorized() {
+        let error: CoreError = can_manage_chat(Some(ChatRole::Member), ChatAction::Delete)
+            .check()
+            .unwrap_err()
+            .into();
+        assert!(matches!(error, CoreError::Unauthorized(msg) if msg.contains("chat owner")));
+    }
+}
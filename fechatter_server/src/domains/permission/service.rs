@@ -0,0 +1,221 @@
+//! # Permission Service
+//!
+//! **Responsibility**: Load the roles a decision depends on and ask `policy`
+//! **Usage**: Handlers and domain services call the `can_*` methods and `check()` the result;
+//! a denial converts into `CoreError::Unauthorized` or `AppError::Forbidden` through `?`
+
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use super::policy::{self, ChatAction, ChatRole, Decision, WorkspaceRole};
+use fechatter_core::{error::CoreError, UserId, WorkspaceId};
+
+pub struct PermissionService {
+    pool: Arc<PgPool>,
+}
+
+impl PermissionService {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Role of the user in the chat; `None` unless they are an active member or its creator
+    pub async fn chat_role(
+        &self,
+        chat_id: i64,
+        user_id: UserId,
+    ) -> Result<Option<ChatRole>, CoreError> {
+        let role: Option<String> = sqlx::query_scalar(
+            r#"SELECT CASE WHEN c.created_by = $2 THEN 'owner' ELSE cm.role::TEXT END
+         FROM chats c
+         LEFT JOIN chat_members cm
+           ON cm.chat_id = c.id AND cm.user_id = $2 AND cm.left_at IS NULL
+         WHERE c.id = $1 AND (c.created_by = $2 OR cm.user_id IS NOT NULL)"#,
+        )
+        .bind(chat_id)
+        .bind(i64::from(user_id))
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(role.map(|role| ChatRole::from_stored(&role)))
+    }
+
+    /// Role of the user in the workspace; `None` if they belong to another one
+    pub async fn workspace_role(
+        &self,
+        workspace_id: WorkspaceId,
+        user_id: UserId,
+    ) -> Result<Option<WorkspaceRole>, CoreError> {
+        let role: Option<String> = sqlx::query_scalar(
+            r#"SELECT CASE WHEN w.owner_id = u.id THEN 'owner' ELSE u.role END
+         FROM users u
+         JOIN workspaces w ON w.id = u.workspace_id
+         WHERE u.id = $1 AND u.workspace_id = $2"#,
+        )
+        .bind(i64::from(user_id))
+        .bind(i64::from(workspace_id))
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        Ok(role.map(|role| WorkspaceRole::from_stored(&role)))
+    }
+
+    pub async fn can_edit_message(
+        &self,
+        user_id: UserId,
+        message_id: i64,
+    ) -> Result<Decision, CoreError> {
+        let (chat_id, author) = self.message_origin(message_id).await?;
+        let role = self.chat_role(chat_id, user_id).await?;
+        Ok(policy::can_edit_message(user_id, author, role))
+    }
+
+    pub async fn can_delete_message(
+        &self,
+        user_id: UserId,
+        message_id: i64,
+    ) -> Result<Decision, CoreError> {
+        let (chat_id, author) = self.message_origin(message_id).await?;
+        let role = self.chat_role(chat_id, user_id).await?;
+        Ok(policy::can_delete_message(user_id, author, role))
+    }
+
+    pub async fn can_manage_chat(
+        &self,
+        user_id: UserId,
+        chat_id: i64,
+        action: ChatAction,
+    ) -> Result<Decision, CoreError> {
+        let role = self.chat_role(chat_id, user_id).await?;
+        Ok(policy::can_manage_chat(role, action))
+    }
+
+    pub async fn can_manage_workspace(
+        &self,
+        user_id: UserId,
+        workspace_id: WorkspaceId,
+    ) -> Result<Decision, CoreError> {
+        let role = self.workspace_role(workspace_id, user_id).await?;
+        Ok(policy::can_manage_workspace(role))
+    }
+
+    pub async fn can_transfer_workspace(
+        &self,
+        user_id: UserId,
+        workspace_id: WorkspaceId,
+    ) -> Result<Decision, CoreError> {
+        let role = self.workspace_role(workspace_id, user_id).await?;
+        Ok(policy::can_transfer_workspace(role))
+    }
+
+    /// Chat and sender of a live message
+    async fn message_origin(&self, message_id: i64) -> Result<(i64, UserId), CoreError> {
+        let origin: Option<(i64, i64)> = sqlx::query_as(
+            "SELECT chat_id, sender_id FROM messages WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(message_id)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| CoreError::from_database_error(e))?;
+
+        origin
+            .map(|(chat_id, sender_id)| (chat_id, UserId(sender_id)))
+            .ok_or_else(|| CoreError::NotFound(format!("Message {} not found", message_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::models::requests::message::SendMessageRequest;
+    use fechatter_core::CreateMessage;
+
+    #[tokio::test]
+    async fn roles_should_be_loaded_from_chat_and_workspace() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(4).await;
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Permissions {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id, users[2].id],
+            )
+            .await?;
+        let chat_id = i64::from(chat.id);
+        sqlx::query(
+            "UPDATE chat_members SET role = 'moderator' WHERE chat_id = $1 AND user_id = $2",
+        )
+        .bind(chat_id)
+        .bind(i64::from(users[1].id))
+        .execute(&*state.pool())
+        .await?;
+
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": "written by a member" }))?;
+        let message = state
+            .application_services()
+            .message_service()
+            .send_message(users[2].id, chat.id, CreateMessage::from(request))
+            .await?;
+
+        let permissions = PermissionService::new(state.pool());
+        assert_eq!(
+            permissions.chat_role(chat_id, users[0].id).await?,
+            Some(ChatRole::Owner)
+        );
+        assert_eq!(
+            permissions.chat_role(chat_id, users[1].id).await?,
+            Some(ChatRole::Moderator)
+        );
+        assert_eq!(permissions.chat_role(chat_id, users[3].id).await?, None);
+
+        // Moderator may remove the message but not rewrite it; an outsider may do neither
+        assert!(permissions
+            .can_delete_message(users[1].id, message.id)
+            .await?
+            .is_allowed());
+        assert!(!permissions
+            .can_edit_message(users[1].id, message.id)
+            .await?
+            .is_allowed());
+        assert!(!permissions
+            .can_delete_message(users[3].id, message.id)
+            .await?
+            .is_allowed());
+        assert!(permissions
+            .can_edit_message(users[2].id, message.id)
+            .await?
+            .is_allowed());
+
+        assert!(permissions
+            .can_manage_chat(users[0].id, chat_id, ChatAction::Delete)
+            .await?
+            .is_allowed());
+        assert!(!permissions
+            .can_manage_chat(users[1].id, chat_id, ChatAction::UpdateDetails)
+            .await?
+            .is_allowed());
+
+        // Only the first user of a fresh workspace becomes its owner
+        assert_eq!(
+            permissions
+                .workspace_role(users[0].workspace_id, users[3].id)
+                .await?,
+            Some(WorkspaceRole::Member)
+        );
+        assert!(!permissions
+            .can_manage_workspace(users[3].id, users[3].workspace_id)
+            .await?
+            .is_allowed());
+        assert_eq!(
+            permissions
+                .workspace_role(WorkspaceId(i64::MAX), users[3].id)
+                .await?,
+            None
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+//! # Workspace User Administration
+//!
+//! **Responsibility**: Types behind the admin user search and management endpoints
+//! **Roles**: `users.role` is either `member` or `admin`; the workspace owner counts as an
+//! admin regardless of the column
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use fechatter_core::{UserId, UserStatus};
+
+/// Workspace-level role of a user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
+pub enum UserRole {
+    Member,
+    Admin,
+}
+
+/// Search criteria; unset fields match every user
+#[derive(Debug, Clone, Default)]
+pub struct AdminUserFilter {
+    /// Case-insensitive substring of the full name or email
+    pub q: Option<String>,
+    pub status: Option<UserStatus>,
+}
+
+impl AdminUserFilter {
+    /// `ILIKE` pattern for `q`, with the wildcards in it matched literally
+    pub(crate) fn pattern(&self) -> Option<String> {
+        let q = self.q.as_deref().map(str::trim).filter(|q| !q.is_empty())?;
+        let escaped = q
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        Some(format!("%{}%", escaped))
+    }
+}
+
+/// A user as seen by workspace admins
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AdminUserView {
+    pub id: UserId,
+    pub fullname: String,
+    pub email: String,
+    pub status: UserStatus,
+    pub role: UserRole,
+    pub created_at: DateTime<Utc>,
+    /// Latest of the last message sent and the last presence update, `null` if never seen
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+/// Changes an admin may make; unset fields are left as they are
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct AdminUserUpdate {
+    pub status: Option<UserStatus>,
+    pub role: Option<UserRole>,
+}
+
+impl AdminUserUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none() && self.role.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_pattern_should_match_wildcards_literally() {
+        let filter = AdminUserFilter {
+            q: Some(" 100%_done ".to_string()),
+            status: None,
+        };
+        assert_eq!(filter.pattern().as_deref(), Some("%100\\%\\_done%"));
+
+        let blank = AdminUserFilter {
+            q: Some("  ".to_string()),
+            status: None,
+        };
+        assert_eq!(blank.pattern(), None);
+    }
+}
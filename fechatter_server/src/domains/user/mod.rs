@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod password;
 pub mod repository;
 pub mod user_domain;
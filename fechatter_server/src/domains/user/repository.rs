@@ -1,8 +1,10 @@
 use async_trait::async_trait;
-use sqlx::{Acquire, PgPool, Row};
+use sqlx::{Acquire, PgConnection, PgPool};
 use std::{mem, sync::Arc};
 
+use super::admin::{AdminUserFilter, AdminUserUpdate, AdminUserView, UserRole};
 use crate::domains::workspace::repository::WorkspaceRepositoryImpl;
+use crate::domains::workspace::workspace_domain::WorkspaceConfig;
 use fechatter_core::{
     contracts::UserRepository, error::CoreError, CreateUser, SigninUser, User, UserId, WorkspaceId,
 };
@@ -13,6 +15,9 @@ use super::password::{hashed_password, verify_password};
 pub struct UserRepositoryImpl {
     pub pool: Arc<PgPool>,
     workspace_repo: Arc<WorkspaceRepositoryImpl>,
+    /// `WorkspaceConfig::max_members`, checked when an admin adds an account; uncapped until
+    /// a config is supplied
+    max_workspace_members: Option<usize>,
 }
 
 impl UserRepositoryImpl {
@@ -21,6 +26,7 @@ impl UserRepositoryImpl {
         Self {
             pool,
             workspace_repo,
+            max_workspace_members: None,
         }
     }
 
@@ -31,8 +37,46 @@ impl UserRepositoryImpl {
         Self {
             pool,
             workspace_repo,
+            max_workspace_members: None,
         }
     }
+
+    pub fn with_workspace_config(mut self, config: &WorkspaceConfig) -> Self {
+        self.max_workspace_members = Some(config.max_members);
+        self
+    }
+
+    /// Fail when the workspace already has `max_workspace_members` users. Locks the workspace
+    /// row until `conn`'s transaction ends, so concurrent additions count one at a time
+    async fn ensure_workspace_has_room(
+        &self,
+        conn: &mut PgConnection,
+        workspace_id: WorkspaceId,
+    ) -> Result<(), CoreError> {
+        let Some(max_members) = self.max_workspace_members else {
+            return Ok(());
+        };
+
+        sqlx::query("SELECT id FROM workspaces WHERE id = $1 FOR UPDATE")
+            .bind(i64::from(workspace_id))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        let members: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE workspace_id = $1")
+            .bind(i64::from(workspace_id))
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        if members >= max_members as i64 {
+            return Err(CoreError::Validation(format!(
+                "Workspace member limit of {} reached",
+                max_members
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -497,61 +541,197 @@ impl UserRepositoryImpl {
     // WORKSPACE MANAGEMENT
     // =============================================================================
 
-    /// Get all users in a workspace
+    /// Get one page of users in a workspace
     pub async fn get_workspace_users(
         &self,
         workspace_id: WorkspaceId,
-    ) -> Result<
-        Vec<(
-            i64,
-            String,
-            String,
-            Option<String>,
-            Option<String>,
-            Option<String>,
-            chrono::DateTime<chrono::Utc>,
-            chrono::DateTime<chrono::Utc>,
-        )>,
-        CoreError,
-    > {
-        let rows = sqlx::query(
-            r#"
-      SELECT 
-        id,
-        email,
-        fullname,
-        bio,
-        avatar_url,
-        status,
-        created_at,
-        updated_at
-      FROM users
-      WHERE workspace_id = $1
-      ORDER BY fullname ASC, email ASC
-      "#,
+        limit: i64,
+        offset: i64,
+        sort: Option<(&str, bool)>,
+    ) -> Result<Vec<User>, CoreError> {
+        // Column names come from a fixed match, never from the request
+        let order_by = match sort {
+            Some((field, descending)) => {
+                let column = match field {
+                    "email" => "email",
+                    "created_at" => "created_at",
+                    _ => "fullname",
+                };
+                let direction = if descending { "DESC" } else { "ASC" };
+                format!("{} {}, id ASC", column, direction)
+            }
+            None => "fullname ASC, email ASC, id ASC".to_string(),
+        };
+
+        let query = format!(
+            r#"SELECT id, fullname, email, status, created_at, workspace_id,
+         phone, title, department, avatar_url, bio, timezone, language, last_active_at
+         FROM users
+         WHERE workspace_id = $1
+         ORDER BY {}
+         LIMIT $2 OFFSET $3"#,
+            order_by
+        );
+
+        let users = sqlx::query_as::<_, User>(&query)
+            .bind(i64::from(workspace_id))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(users)
+    }
+
+    /// Ids of all users in a workspace
+    pub async fn list_workspace_user_ids(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Vec<i64>, CoreError> {
+        let ids = sqlx::query_scalar("SELECT id FROM users WHERE workspace_id = $1 ORDER BY id")
+            .bind(i64::from(workspace_id))
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(ids)
+    }
+
+    /// Count users in a workspace
+    pub async fn count_workspace_users(&self, workspace_id: WorkspaceId) -> Result<i64, CoreError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE workspace_id = $1")
+            .bind(i64::from(workspace_id))
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(count)
+    }
+
+    // =============================================================================
+    // WORKSPACE ADMINISTRATION
+    // =============================================================================
+
+    /// One page of workspace users matching `filter`, newest first, with the total match count
+    pub async fn search_workspace_users(
+        &self,
+        workspace_id: WorkspaceId,
+        filter: &AdminUserFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<AdminUserView>, i64), CoreError> {
+        let pattern = filter.pattern();
+
+        let total: i64 = sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM users u
+         WHERE u.workspace_id = $1
+           AND ($2::TEXT IS NULL OR u.fullname ILIKE $2 OR u.email ILIKE $2)
+           AND ($3::VARCHAR IS NULL OR u.status = $3)"#,
+        )
+        .bind(i64::from(workspace_id))
+        .bind(pattern.as_deref())
+        .bind(filter.status)
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        let users = sqlx::query_as::<_, AdminUserView>(
+            r#"SELECT u.id, u.fullname, u.email, u.status, u.role, u.created_at,
+                GREATEST(u.last_active_at, up.last_seen) AS last_seen_at
+         FROM users u
+         LEFT JOIN user_presence up ON up.user_id = u.id
+         WHERE u.workspace_id = $1
+           AND ($2::TEXT IS NULL OR u.fullname ILIKE $2 OR u.email ILIKE $2)
+           AND ($3::VARCHAR IS NULL OR u.status = $3)
+         ORDER BY u.created_at DESC, u.id DESC
+         LIMIT $4 OFFSET $5"#,
         )
         .bind(i64::from(workspace_id))
+        .bind(pattern.as_deref())
+        .bind(filter.status)
+        .bind(limit)
+        .bind(offset)
         .fetch_all(&*self.pool)
         .await
         .map_err(|e| CoreError::Database(e.to_string()))?;
 
-        let users = rows
-            .into_iter()
-            .map(|row| {
-                (
-                    row.get("id"),
-                    row.get("email"),
-                    row.get("fullname"),
-                    row.get("bio"),
-                    row.get("avatar_url"),
-                    row.get("status"),
-                    row.get("created_at"),
-                    row.get::<Option<chrono::DateTime<chrono::Utc>>, _>("updated_at")
-                        .unwrap_or_else(|| row.get("created_at")),
-                )
-            })
-            .collect();
+        Ok((users, total))
+    }
 
-        Ok(users)
+    /// Apply an admin's status/role change to a user of the workspace; `None` if the user
+    /// is not in it
+    pub async fn update_status_and_role(
+        &self,
+        workspace_id: WorkspaceId,
+        user_id: UserId,
+        update: &AdminUserUpdate,
+    ) -> Result<Option<AdminUserView>, CoreError> {
+        let user = sqlx::query_as::<_, AdminUserView>(
+            r#"WITH updated AS (
+           UPDATE users
+           SET status = COALESCE($3, status), role = COALESCE($4, role), updated_at = NOW()
+           WHERE id = $2 AND workspace_id = $1
+           RETURNING id, fullname, email, status, role, created_at, last_active_at
+         )
+         SELECT u.id, u.fullname, u.email, u.status, u.role, u.created_at,
+                GREATEST(u.last_active_at, up.last_seen) AS last_seen_at
+         FROM updated u
+         LEFT JOIN user_presence up ON up.user_id = u.id"#,
+        )
+        .bind(i64::from(workspace_id))
+        .bind(i64::from(user_id))
+        .bind(update.status)
+        .bind(update.role)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(user)
+    }
+
+    /// Add an account to an existing workspace with an already hashed password, within the
+    /// workspace member cap when one is configured
+    pub async fn create_workspace_member(
+        &self,
+        workspace_id: WorkspaceId,
+        email: &str,
+        fullname: &str,
+        password_hash: &str,
+        role: UserRole,
+    ) -> Result<AdminUserView, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::Internal(e.to_string()))?;
+
+        self.ensure_workspace_has_room(&mut tx, workspace_id)
+            .await?;
+
+        let user = sqlx::query_as::<_, AdminUserView>(
+            r#"INSERT INTO users (workspace_id, email, fullname, password_hash, role)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING id, fullname, email, status, role, created_at, last_active_at AS last_seen_at"#,
+        )
+        .bind(i64::from(workspace_id))
+        .bind(email)
+        .bind(fullname)
+        .bind(password_hash)
+        .bind(role)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(db_err) if db_err.is_unique_violation() => {
+                CoreError::UserAlreadyExists(format!("User with email {} already exists", email))
+            }
+            _ => CoreError::Database(e.to_string()),
+        })?;
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::Internal(e.to_string()))?;
+
+        Ok(user)
     }
 }
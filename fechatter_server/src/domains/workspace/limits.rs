@@ -0,0 +1,110 @@
+//! # Workspace Limits
+//!
+//! **Responsibility**: Per-workspace overrides of the global rate limits and bot quota
+//! **Fallback**: An unset override uses `features.rate_limiting`. Overrides are cached in
+//! process, so a change made on another instance applies here within `LIMITS_CACHE_TTL`
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use super::repository::WorkspaceRepositoryImpl;
+use fechatter_core::{error::CoreError, WorkspaceId};
+
+/// How long overrides are served from memory before being read again
+const LIMITS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Overrides stored with the workspace; `None` uses the global default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WorkspaceLimits {
+    /// Messages a user may send per rate limit window
+    pub message_send_limit: Option<i32>,
+    /// Files a user may upload per rate limit window
+    pub upload_limit: Option<i32>,
+    /// Bot translations a user may request per day
+    pub bot_daily_quota: Option<i32>,
+}
+
+/// Read-through cache of workspace overrides
+pub struct WorkspaceLimitsCache {
+    repository: WorkspaceRepositoryImpl,
+    entries: DashMap<i64, (Instant, WorkspaceLimits)>,
+}
+
+impl WorkspaceLimitsCache {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self {
+            repository: WorkspaceRepositoryImpl::new(pool),
+            entries: DashMap::new(),
+        }
+    }
+
+    /// Overrides of the workspace; the global defaults apply while they cannot be read
+    pub async fn get(&self, workspace_id: WorkspaceId) -> WorkspaceLimits {
+        let key = i64::from(workspace_id);
+        if let Some(entry) = self.entries.get(&key) {
+            let (cached_at, limits) = *entry;
+            if cached_at.elapsed() < LIMITS_CACHE_TTL {
+                return limits;
+            }
+        }
+
+        match self.repository.get_limits(workspace_id).await {
+            Ok(limits) => {
+                self.entries.insert(key, (Instant::now(), limits));
+                limits
+            }
+            Err(e) => {
+                warn!(
+                    "WARNING: Limits of workspace {} unavailable, using defaults: {}",
+                    workspace_id, e
+                );
+                WorkspaceLimits::default()
+            }
+        }
+    }
+
+    /// Store new overrides; they apply on this instance right away
+    pub async fn set(
+        &self,
+        workspace_id: WorkspaceId,
+        limits: WorkspaceLimits,
+    ) -> Result<(), CoreError> {
+        self.repository.set_limits(workspace_id, &limits).await?;
+        self.entries
+            .insert(i64::from(workspace_id), (Instant::now(), limits));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn overrides_should_be_stored_and_read_back_through_the_cache() -> anyhow::Result<()> {
+        let (state, _users) = crate::setup_test_users!(1).await;
+        let workspace = WorkspaceRepositoryImpl::new(state.pool())
+            .find_or_create_by_name(&format!("Limits {}", uuid::Uuid::new_v4()))
+            .await?;
+
+        let cache = WorkspaceLimitsCache::new(state.pool());
+        assert_eq!(cache.get(workspace.id).await, WorkspaceLimits::default());
+
+        let raised = WorkspaceLimits {
+            message_send_limit: Some(500),
+            upload_limit: None,
+            bot_daily_quota: Some(200),
+        };
+        cache.set(workspace.id, raised).await?;
+        assert_eq!(cache.get(workspace.id).await, raised);
+
+        // Another instance reads the stored overrides
+        let other_instance = WorkspaceLimitsCache::new(state.pool());
+        assert_eq!(other_instance.get(workspace.id).await, raised);
+        Ok(())
+    }
+}
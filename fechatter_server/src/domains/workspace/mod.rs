@@ -1,3 +1,5 @@
 pub mod events;
+pub mod limits;
 pub mod repository;
+pub mod webhooks;
 pub mod workspace_domain;
@@ -1,6 +1,9 @@
 use sqlx::{PgPool, Row};
 use std::sync::Arc;
 
+use crate::services::infrastructure::observability::slow_query::TimedQuery;
+use super::limits::WorkspaceLimits;
+
 use fechatter_core::{
     error::CoreError,
     models::{UserId, UserStatus, Workspace, WorkspaceId},
@@ -42,6 +45,7 @@ impl WorkspaceRepositoryImpl {
         )
         .bind(name)
         .fetch_one(&*self.pool)
+        .timed("workspace.find_or_create_by_name")
         .await
         .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -55,6 +59,7 @@ impl WorkspaceRepositoryImpl {
     )
     .bind(name)
     .fetch_optional(&*self.pool)
+    .timed("workspace.find_by_name")
     .await
     .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -68,6 +73,7 @@ impl WorkspaceRepositoryImpl {
     )
     .bind(id)
     .fetch_optional(&*self.pool)
+    .timed("workspace.find_by_id")
     .await
     .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -91,6 +97,7 @@ impl WorkspaceRepositoryImpl {
         .bind(i64::from(new_owner_id))
         .bind(i64::from(id))
         .fetch_one(&*self.pool)
+        .timed("workspace.update_owner")
         .await
         .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -112,6 +119,7 @@ impl WorkspaceRepositoryImpl {
         )
         .bind(i64::from(workspace_id))
         .fetch_all(&*self.pool)
+        .timed("workspace.list_users")
         .await
         .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -136,6 +144,7 @@ impl WorkspaceRepositoryImpl {
             .bind(i64::from(workspace_id))
             .bind(i64::from(*member_id))
             .execute(&*self.pool)
+            .timed("workspace.add_members")
             .await
             .map_err(|e| CoreError::Database(e.to_string()))?;
         }
@@ -144,6 +153,182 @@ impl WorkspaceRepositoryImpl {
         self.list_users(workspace_id).await
     }
 
+    /// Message retention window in days (`None` keeps messages forever)
+    pub async fn get_message_retention_days(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Option<i32>, CoreError> {
+        let days = sqlx::query_scalar::<_, Option<i32>>(
+            "SELECT message_retention_days FROM workspaces WHERE id = $1",
+        )
+        .bind(i64::from(workspace_id))
+        .fetch_optional(&*self.pool)
+        .timed("workspace.get_message_retention_days")
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?
+        .ok_or_else(|| CoreError::NotFound(format!("Workspace {} not found", workspace_id)))?;
+
+        Ok(days)
+    }
+
+    /// Set the message retention window in days (`None` keeps messages forever)
+    pub async fn set_message_retention_days(
+        &self,
+        workspace_id: WorkspaceId,
+        days: Option<i32>,
+    ) -> Result<(), CoreError> {
+        let result = sqlx::query("UPDATE workspaces SET message_retention_days = $1 WHERE id = $2")
+            .bind(days)
+            .bind(i64::from(workspace_id))
+            .execute(&*self.pool)
+            .timed("workspace.set_message_retention_days")
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::NotFound(format!(
+                "Workspace {} not found",
+                workspace_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Whether chats in the workspace must have distinct names
+    pub async fn get_unique_chat_names(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<bool, CoreError> {
+        sqlx::query_scalar::<_, bool>("SELECT unique_chat_names FROM workspaces WHERE id = $1")
+            .bind(i64::from(workspace_id))
+            .fetch_optional(&*self.pool)
+            .timed("workspace.get_unique_chat_names")
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?
+            .ok_or_else(|| CoreError::NotFound(format!("Workspace {} not found", workspace_id)))
+    }
+
+    /// Require distinct chat names in the workspace, or stop requiring them. Turning it on
+    /// fails with `Conflict`, changing nothing, while chats already share a name
+    pub async fn set_unique_chat_names(
+        &self,
+        workspace_id: WorkspaceId,
+        unique: bool,
+    ) -> Result<(), CoreError> {
+        let workspace_id = i64::from(workspace_id);
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        let result = sqlx::query("UPDATE workspaces SET unique_chat_names = $1 WHERE id = $2")
+            .bind(unique)
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .timed("workspace.set_unique_chat_names")
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))?;
+        if result.rows_affected() == 0 {
+            return Err(CoreError::NotFound(format!(
+                "Workspace {} not found",
+                workspace_id
+            )));
+        }
+
+        // Key existing chats the way the chats trigger keys new and renamed ones
+        sqlx::query(
+            r#"
+            UPDATE chats
+            SET unique_name_key = CASE
+                WHEN $1 AND type <> 'Single' THEN lower(btrim(chat_name))
+            END
+            WHERE workspace_id = $2
+            "#,
+        )
+        .bind(unique)
+        .bind(workspace_id)
+        .execute(&mut *tx)
+        .timed("workspace.set_unique_chat_names")
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(db_err) if db_err.is_unique_violation() => CoreError::Conflict(
+                "Some chats in this workspace share a name; rename them before requiring unique names"
+                    .to_string(),
+            ),
+            _ => CoreError::Database(e.to_string()),
+        })?;
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))
+    }
+
+    /// Overrides of the global rate limits and bot quota
+    pub async fn get_limits(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<WorkspaceLimits, CoreError> {
+        sqlx::query_as::<_, WorkspaceLimits>(
+            "SELECT message_send_limit, upload_limit, bot_daily_quota FROM workspaces WHERE id = $1",
+        )
+        .bind(i64::from(workspace_id))
+        .fetch_optional(&*self.pool)
+        .timed("workspace.get_limits")
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?
+        .ok_or_else(|| CoreError::NotFound(format!("Workspace {} not found", workspace_id)))
+    }
+
+    /// Replace the overrides; `None` fields fall back to the global defaults
+    pub async fn set_limits(
+        &self,
+        workspace_id: WorkspaceId,
+        limits: &WorkspaceLimits,
+    ) -> Result<(), CoreError> {
+        let result = sqlx::query(
+            r#"
+      UPDATE workspaces
+      SET message_send_limit = $1, upload_limit = $2, bot_daily_quota = $3
+      WHERE id = $4
+      "#,
+        )
+        .bind(limits.message_send_limit)
+        .bind(limits.upload_limit)
+        .bind(limits.bot_daily_quota)
+        .bind(i64::from(workspace_id))
+        .execute(&*self.pool)
+        .timed("workspace.set_limits")
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::NotFound(format!(
+                "Workspace {} not found",
+                workspace_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Workspaces with a retention window, as `(workspace_id, retention_days)`
+    pub async fn list_message_retention_policies(&self) -> Result<Vec<(i64, i32)>, CoreError> {
+        sqlx::query_as::<_, (i64, i32)>(
+            r#"
+      SELECT id, message_retention_days
+      FROM workspaces
+      WHERE message_retention_days IS NOT NULL
+      ORDER BY id
+      "#,
+        )
+        .fetch_all(&*self.pool)
+        .timed("workspace.list_message_retention_policies")
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))
+    }
+
     /// Check if users exist
     pub async fn check_users_exist(&self, user_ids: &[UserId]) -> Result<Vec<UserId>, CoreError> {
         let ids: Vec<i64> = user_ids.iter().map(|id| i64::from(*id)).collect();
@@ -157,6 +342,7 @@ impl WorkspaceRepositoryImpl {
         )
         .bind(&ids)
         .fetch_all(&*self.pool)
+        .timed("workspace.check_users_exist")
         .await
         .map_err(|e| CoreError::Database(e.to_string()))?;
 
@@ -0,0 +1,246 @@
+//! # Webhook Subscriptions
+//!
+//! **Responsibility**: Store the outgoing webhooks a workspace registered
+//! **Scope**: One row per URL; delivery outcomes keep a consecutive failure count that disables
+//! the subscription once it reaches the configured limit
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::sync::Arc;
+
+use fechatter_core::{error::CoreError, UserId, WorkspaceId};
+
+/// Events a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "message.created")]
+    MessageCreated,
+    #[serde(rename = "member.joined")]
+    MemberJoined,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MessageCreated => "message.created",
+            Self::MemberJoined => "member.joined",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "message.created" => Some(Self::MessageCreated),
+            "member.joined" => Some(Self::MemberJoined),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A registered webhook; the secret is only returned when the subscription is created
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookSubscription {
+    pub id: i64,
+    pub workspace_id: i64,
+    pub url: String,
+    pub event_types: Vec<WebhookEventType>,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub enabled: bool,
+    pub consecutive_failures: i32,
+    pub created_by: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    /// Enabled and subscribed to `event`
+    pub fn accepts(&self, event: WebhookEventType) -> bool {
+        self.enabled && self.event_types.contains(&event)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookRow {
+    id: i64,
+    workspace_id: i64,
+    url: String,
+    event_types: Vec<String>,
+    secret: String,
+    enabled: bool,
+    consecutive_failures: i32,
+    created_by: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl From<WebhookRow> for WebhookSubscription {
+    fn from(row: WebhookRow) -> Self {
+        Self {
+            id: row.id,
+            workspace_id: row.workspace_id,
+            url: row.url,
+            // Event types this build no longer knows are ignored rather than failing the row
+            event_types: row
+                .event_types
+                .iter()
+                .filter_map(|event| WebhookEventType::parse(event))
+                .collect(),
+            secret: row.secret,
+            enabled: row.enabled,
+            consecutive_failures: row.consecutive_failures,
+            created_by: row.created_by,
+            created_at: row.created_at,
+        }
+    }
+}
+
+const WEBHOOK_COLUMNS: &str = "id, workspace_id, url, event_types, secret, enabled, consecutive_failures, created_by, created_at";
+
+/// Webhook subscription repository
+pub struct WebhookRepository {
+    pool: Arc<PgPool>,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Register a webhook for the workspace
+    pub async fn create(
+        &self,
+        workspace_id: WorkspaceId,
+        url: &str,
+        event_types: &[WebhookEventType],
+        secret: &str,
+        created_by: UserId,
+    ) -> Result<WebhookSubscription, CoreError> {
+        let event_types: Vec<&str> = event_types.iter().map(|event| event.as_str()).collect();
+
+        sqlx::query_as::<_, WebhookRow>(&format!(
+            r#"
+      INSERT INTO webhook_subscriptions (workspace_id, url, event_types, secret, created_by)
+      VALUES ($1, $2, $3, $4, $5)
+      RETURNING {}
+      "#,
+            WEBHOOK_COLUMNS
+        ))
+        .bind(i64::from(workspace_id))
+        .bind(url)
+        .bind(&event_types)
+        .bind(secret)
+        .bind(i64::from(created_by))
+        .fetch_one(&*self.pool)
+        .await
+        .map(WebhookSubscription::from)
+        .map_err(|e| CoreError::Database(e.to_string()))
+    }
+
+    /// Every webhook of the workspace, disabled ones included
+    pub async fn list(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Vec<WebhookSubscription>, CoreError> {
+        let rows = sqlx::query_as::<_, WebhookRow>(&format!(
+            "SELECT {} FROM webhook_subscriptions WHERE workspace_id = $1 ORDER BY id",
+            WEBHOOK_COLUMNS
+        ))
+        .bind(i64::from(workspace_id))
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(WebhookSubscription::from).collect())
+    }
+
+    /// Enabled webhooks of the workspace subscribed to `event`
+    pub async fn list_active(
+        &self,
+        workspace_id: i64,
+        event: WebhookEventType,
+    ) -> Result<Vec<WebhookSubscription>, CoreError> {
+        let rows = sqlx::query_as::<_, WebhookRow>(&format!(
+            r#"
+      SELECT {}
+      FROM webhook_subscriptions
+      WHERE workspace_id = $1 AND enabled AND $2 = ANY(event_types)
+      ORDER BY id
+      "#,
+            WEBHOOK_COLUMNS
+        ))
+        .bind(workspace_id)
+        .bind(event.as_str())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(WebhookSubscription::from).collect())
+    }
+
+    /// Remove a webhook; `NotFound` if it isn't one of the workspace's
+    pub async fn delete(&self, workspace_id: WorkspaceId, id: i64) -> Result<(), CoreError> {
+        let result =
+            sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1 AND workspace_id = $2")
+                .bind(id)
+                .bind(i64::from(workspace_id))
+                .execute(&*self.pool)
+                .await
+                .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::NotFound(format!("Webhook {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Successful delivery resets the failure count
+    pub async fn record_success(&self, id: i64) -> Result<(), CoreError> {
+        sqlx::query(
+            "UPDATE webhook_subscriptions SET consecutive_failures = 0, updated_at = NOW() WHERE id = $1 AND consecutive_failures > 0",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Count a failed delivery and disable the webhook at `disable_after` consecutive failures.
+    /// Returns true when the webhook is now disabled
+    pub async fn record_failure(&self, id: i64, disable_after: i32) -> Result<bool, CoreError> {
+        let disabled = sqlx::query_scalar::<_, bool>(
+            r#"
+      UPDATE webhook_subscriptions
+      SET consecutive_failures = consecutive_failures + 1,
+          enabled = enabled AND consecutive_failures + 1 < $2,
+          updated_at = NOW()
+      WHERE id = $1
+      RETURNING NOT enabled
+      "#,
+        )
+        .bind(id)
+        .bind(disable_after.max(1))
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| CoreError::Database(e.to_string()))?;
+
+        Ok(disabled.unwrap_or(false))
+    }
+
+    /// Workspace a chat belongs to
+    pub async fn chat_workspace(&self, chat_id: i64) -> Result<Option<i64>, CoreError> {
+        sqlx::query_scalar::<_, i64>("SELECT workspace_id FROM chats WHERE id = $1")
+            .bind(chat_id)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| CoreError::Database(e.to_string()))
+    }
+}
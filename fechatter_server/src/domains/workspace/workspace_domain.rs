@@ -3,6 +3,8 @@ use std::sync::Arc;
 
 use fechatter_core::{error::CoreError, ChatUser, UserId, Workspace, WorkspaceId};
 
+use crate::config::WorkspaceLimitsConfig;
+use crate::domains::permission::PermissionService;
 use crate::handlers::workspaces::UpdateWorkspaceRequest;
 
 use super::repository::{WorkspaceRepositoryImpl, WorkspaceUser};
@@ -21,6 +23,7 @@ pub struct WorkspaceChatStats {
 pub struct WorkspaceConfig {
     pub max_name_length: usize,
     pub min_name_length: usize,
+    /// Users admins may grow the workspace to; open signup isn't capped
     pub max_members: usize,
     pub allow_duplicate_names: bool,
 }
@@ -30,12 +33,22 @@ impl Default for WorkspaceConfig {
         Self {
             max_name_length: 50,
             min_name_length: 2,
-            max_members: 1000,
+            max_members: WorkspaceLimitsConfig::default().max_members,
             allow_duplicate_names: false,
         }
     }
 }
 
+impl WorkspaceConfig {
+    /// Default settings with the configured workspace caps
+    pub fn with_limits(limits: &WorkspaceLimitsConfig) -> Self {
+        Self {
+            max_members: limits.max_members,
+            ..Self::default()
+        }
+    }
+}
+
 /// Workspace validation rules
 pub struct WorkspaceValidationRules {
     config: WorkspaceConfig,
@@ -83,20 +96,6 @@ impl WorkspaceValidationRules {
 
         Ok(())
     }
-
-    /// Validate user permissions
-    pub fn validate_user_permissions(
-        &self,
-        user_id: UserId,
-        workspace: &Workspace,
-    ) -> Result<(), CoreError> {
-        if workspace.owner_id != user_id {
-            return Err(CoreError::Unauthorized(
-                "Only workspace owner can perform this action".to_string(),
-            ));
-        }
-        Ok(())
-    }
 }
 
 /// Workspace aggregate
@@ -197,13 +196,19 @@ pub trait WorkspaceDomainService: Send + Sync {
 /// Workspace Domain Service implementation
 pub struct WorkspaceDomainServiceImpl {
     repository: Arc<WorkspaceRepositoryImpl>,
+    permissions: Arc<PermissionService>,
     validator: WorkspaceValidationRules,
 }
 
 impl WorkspaceDomainServiceImpl {
-    pub fn new(repository: Arc<WorkspaceRepositoryImpl>, config: WorkspaceConfig) -> Self {
+    pub fn new(
+        repository: Arc<WorkspaceRepositoryImpl>,
+        permissions: Arc<PermissionService>,
+        config: WorkspaceConfig,
+    ) -> Self {
         Self {
             repository,
+            permissions,
             validator: WorkspaceValidationRules::new(config),
         }
     }
@@ -248,9 +253,11 @@ impl WorkspaceDomainService for WorkspaceDomainServiceImpl {
             .await?
             .ok_or_else(|| CoreError::NotFound("Workspace not found".to_string()))?;
 
-        // Validate user permissions
-        self.validator
-            .validate_user_permissions(user_id, &workspace)?;
+        // Workspace owners and admins only
+        self.permissions
+            .can_manage_workspace(user_id, workspace_id)
+            .await?
+            .check()?;
 
         // Check if name is provided
         if let Some(ref new_name) = request.name {
@@ -305,15 +312,16 @@ impl WorkspaceDomainService for WorkspaceDomainServiceImpl {
         admin_user_id: UserId,
     ) -> Result<Workspace, CoreError> {
         // Verify workspace exists
-        let workspace = self
-            .repository
+        self.repository
             .find_by_id(workspace_id)
             .await?
             .ok_or_else(|| CoreError::NotFound("Workspace not found".to_string()))?;
 
-        // Validate admin permissions
-        self.validator
-            .validate_user_permissions(admin_user_id, &workspace)?;
+        // Workspace owners and admins only
+        self.permissions
+            .can_manage_workspace(admin_user_id, workspace_id)
+            .await?
+            .check()?;
 
         // WorkspaceRepositoryImpl doesn't have add_user_to_workspace method
         // This functionality needs to be implemented in the repository layer
@@ -329,15 +337,16 @@ impl WorkspaceDomainService for WorkspaceDomainServiceImpl {
         current_owner_id: UserId,
     ) -> Result<Workspace, CoreError> {
         // Verify workspace exists
-        let workspace = self
-            .repository
+        self.repository
             .find_by_id(workspace_id)
             .await?
             .ok_or_else(|| CoreError::NotFound("Workspace not found".to_string()))?;
 
-        // Validate current user permissions
-        self.validator
-            .validate_user_permissions(current_owner_id, &workspace)?;
+        // The owner only; admins may not hand the workspace to anyone, themselves included
+        self.permissions
+            .can_transfer_workspace(current_owner_id, workspace_id)
+            .await?
+            .check()?;
 
         // Transfer ownership
         self.repository
@@ -378,18 +387,16 @@ impl WorkspaceDomainService for WorkspaceDomainServiceImpl {
         admin_user_id: UserId,
     ) -> Result<Vec<WorkspaceUser>, CoreError> {
         // 1. Verify workspace exists
-        let workspace = self
-            .repository
+        self.repository
             .find_by_id(workspace_id)
             .await?
             .ok_or_else(|| CoreError::NotFound(format!("Workspace {} not found", workspace_id)))?;
 
-        // 2. Verify admin is the owner (business rule)
-        if workspace.owner_id != admin_user_id {
-            return Err(CoreError::Unauthorized(
-                "Only workspace owner can add members".to_string(),
-            ));
-        }
+        // 2. Workspace owners and admins only
+        self.permissions
+            .can_manage_workspace(admin_user_id, workspace_id)
+            .await?
+            .check()?;
 
         // 3. Check if all users exist
         let existing_users = self.repository.check_users_exist(&member_ids).await?;
@@ -526,25 +533,48 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn validate_user_permissions_should_check_ownership() {
-        let config = WorkspaceConfig::default();
-        let validator = WorkspaceValidationRules::new(config);
-        let workspace = create_test_workspace();
-
-        // Owner should have permissions
-        let owner_id = workspace.owner_id;
-        assert!(validator
-            .validate_user_permissions(owner_id, &workspace)
-            .is_ok());
-
-        // Non-owner should not have permissions
-        let non_owner_id = UserId(999);
-        let result = validator.validate_user_permissions(non_owner_id, &workspace);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Only workspace owner"));
+    async fn workspace_admins_should_manage_the_workspace() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(4).await;
+        let workspace_id = users[0].workspace_id;
+        sqlx::query("UPDATE users SET role = 'admin' WHERE id = $1")
+            .bind(i64::from(users[1].id))
+            .execute(&*state.pool())
+            .await?;
+        let service = WorkspaceDomainServiceImpl::new(
+            Arc::new(WorkspaceRepositoryImpl::new(state.pool())),
+            state.permissions().clone(),
+            WorkspaceConfig::default(),
+        );
+
+        // An admin who isn't the owner may manage the workspace, but not take it over
+        service
+            .update_workspace(
+                workspace_id,
+                &UpdateWorkspaceRequest { name: None },
+                users[1].id,
+            )
+            .await?;
+        let result = service
+            .transfer_ownership(workspace_id, users[1].id, users[1].id)
+            .await;
+        assert!(matches!(result, Err(CoreError::Unauthorized(_))));
+
+        // A plain member may not manage it at all
+        let result = service
+            .update_workspace(
+                workspace_id,
+                &UpdateWorkspaceRequest { name: None },
+                users[3].id,
+            )
+            .await;
+        assert!(matches!(result, Err(CoreError::Unauthorized(_))));
+
+        // The owner may hand it over
+        let workspace = service
+            .transfer_ownership(workspace_id, users[2].id, users[0].id)
+            .await?;
+        assert_eq!(workspace.owner_id, users[2].id);
+        Ok(())
     }
 
     #[tokio::test]
@@ -28,6 +28,30 @@ pub struct TranslateResponse {
     pub quota_limit: i32,
 }
 
+/// Request structure for summarizing a message
+#[derive(Debug, Deserialize)]
+pub struct SummarizeRequest {
+    /// ID of the message to summarize
+    pub message_id: i32,
+}
+
+/// Response structure for a streamed summary
+#[derive(Debug, Serialize)]
+pub struct SummarizeResponse {
+    /// Shared by the `AiStreamChunk` SSE events that delivered the summary as it was generated
+    pub stream_id: uuid::Uuid,
+    /// The whole summary
+    pub summary: String,
+    /// Provider tokens the summary took, charged to the workspace's daily AI token budget
+    pub tokens_used: u32,
+    /// Number of bot requests used today
+    pub quota_used: i32,
+    /// Number of bot requests remaining today
+    pub quota_remaining: i32,
+    /// Daily bot request limit
+    pub quota_limit: i32,
+}
+
 /// Request structure for language detection
 #[derive(Debug, Deserialize)]
 pub struct DetectLanguageRequest {
@@ -30,6 +30,11 @@ pub trait BaseDto: Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync {
     fn metadata(&self) -> DtoMetadata {
         DtoMetadata::default()
     }
+
+    /// 声明需要通过验证器注册表校验的字段
+    fn field_validators(&self) -> Vec<FieldValidators<'_>> {
+        Vec::new()
+    }
 }
 
 /// 请求DTO特征 - 所有请求DTO的统一接口
@@ -68,6 +73,28 @@ pub trait ResponseDto: BaseDto {
     }
 }
 
+/// 批量响应DTO特征 - 整页共享数据只预取一次，避免逐条转换造成的 N+1 查询
+#[async_trait::async_trait]
+pub trait BatchResponseDto: ResponseDto {
+    /// 预取数据的来源（如仓储）
+    type Source: ?Sized + Send + Sync;
+
+    /// 整页共享的预取结果
+    type Prefetched: Send + Sync;
+
+    /// 为整页领域模型一次性预取共享数据
+    async fn prefetch(
+        domains: &[Self::DomainModel],
+        source: &Self::Source,
+    ) -> Result<Self::Prefetched, ConversionError>;
+
+    /// 基于预取数据转换单个领域模型
+    fn from_domain_prefetched(
+        domain: &Self::DomainModel,
+        prefetched: &Self::Prefetched,
+    ) -> Result<Self, ConversionError>;
+}
+
 /// DTO元数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DtoMetadata {
@@ -3,10 +3,13 @@
 // 提供统一的分页、排序、过滤功能
 // 这些DTOs可以被所有需要列表查询的API重用
 
-use serde::{Deserialize, Serialize};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 use validator::Validate;
 
+use super::validation::{DtoValidationError, ValidationErrorType};
+
 /// 分页请求参数 - 所有列表查询的基础
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct PaginationRequest {
@@ -72,6 +75,10 @@ pub struct PaginationMeta {
 
     /// 下一页页码
     pub next_page: Option<u32>,
+
+    /// 下一页游标（键集分页的列表使用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// 排序字段定义
@@ -163,6 +170,18 @@ pub struct CursorPaginatedResponse<T> {
     pub stats: Option<QueryStats>,
 }
 
+/// 游标请求 - 键集分页，插入新数据时翻页结果保持稳定
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CursorRequest {
+    /// 上一页返回的 next_cursor，为空表示第一页
+    pub after: Option<String>,
+
+    /// 每页数量
+    #[validate(range(min = 1, max = 100, message = "每页数量必须在1-100之间"))]
+    #[serde(default = "default_page_size")]
+    pub limit: u32,
+}
+
 /// 游标查询方向
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub enum CursorDirection {
@@ -171,6 +190,28 @@ pub enum CursorDirection {
     Backward,
 }
 
+/// 将键集编码为不透明游标（JSON 后 base64）
+pub fn encode_cursor<K: Serialize>(keyset: &K) -> String {
+    let json = serde_json::to_vec(keyset).unwrap_or_default();
+    URL_SAFE_NO_PAD.encode(json)
+}
+
+/// 解码游标为键集
+pub fn decode_cursor<K: DeserializeOwned>(cursor: &str) -> Result<K, DtoValidationError> {
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(|| {
+            DtoValidationError::new(
+                ValidationErrorType::Format,
+                "Invalid pagination cursor".to_string(),
+                Some("after".to_string()),
+            )
+            .with_suggestion("Use the next_cursor value from the previous page".to_string())
+        })
+}
+
 // 默认值函数
 fn default_page() -> u32 {
     1
@@ -210,6 +251,13 @@ impl PaginationRequest {
         self
     }
 
+    /// 将页码和每页数量限制在允许范围内
+    pub fn clamp_page_size(mut self, max_page_size: u32) -> Self {
+        self.page = self.page.max(1);
+        self.page_size = self.page_size.clamp(1, max_page_size.max(1));
+        self
+    }
+
     /// 计算偏移量
     pub fn offset(&self) -> u32 {
         (self.page - 1) * self.page_size
@@ -221,6 +269,24 @@ impl PaginationRequest {
     }
 }
 
+impl CursorRequest {
+    /// 创建新的游标请求
+    pub fn new(after: Option<String>, limit: u32) -> Self {
+        Self { after, limit }
+    }
+
+    /// 将每页数量限制在允许范围内
+    pub fn clamp_limit(mut self, max_limit: u32) -> Self {
+        self.limit = self.limit.clamp(1, max_limit.max(1));
+        self
+    }
+
+    /// 解码 after 游标
+    pub fn decode_after<K: DeserializeOwned>(&self) -> Result<Option<K>, DtoValidationError> {
+        self.after.as_deref().map(decode_cursor::<K>).transpose()
+    }
+}
+
 impl<T> PaginatedResponse<T> {
     /// 创建分页响应
     pub fn new(data: Vec<T>, page: u32, page_size: u32, total_items: u64) -> Self {
@@ -241,11 +307,18 @@ impl<T> PaginatedResponse<T> {
                 has_next,
                 previous_page,
                 next_page,
+                next_cursor: None,
             },
             stats: None,
         }
     }
 
+    /// 设置下一页游标
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.pagination.next_cursor = next_cursor;
+        self
+    }
+
     /// 添加查询统计信息
     pub fn with_stats(mut self, stats: QueryStats) -> Self {
         self.stats = Some(stats);
@@ -264,3 +337,16 @@ impl<T> PaginatedResponse<T> {
         }
     }
 }
+
+impl<T> CursorPaginatedResponse<T> {
+    /// 创建游标分页响应
+    pub fn new(data: Vec<T>, next_cursor: Option<String>) -> Self {
+        Self {
+            has_more: next_cursor.is_some(),
+            data,
+            next_cursor,
+            previous_cursor: None,
+            stats: None,
+        }
+    }
+}
@@ -280,6 +280,15 @@ impl From<CoreError> for ApiError {
                 suggestion: Some("请确认资源ID是否正确".to_string()),
                 help_url: None,
             },
+            CoreError::UserAlreadyExists(msg) | CoreError::Conflict(msg) => Self {
+                code: "CONFLICT".to_string(),
+                message: msg,
+                details: None,
+                field: None,
+                stack: Vec::new(),
+                suggestion: Some("该资源已存在，请勿重复创建".to_string()),
+                help_url: None,
+            },
             CoreError::Database(msg) => Self {
                 code: "DATABASE_ERROR".to_string(),
                 message: "数据库操作失败".to_string(),
@@ -348,6 +357,7 @@ impl<T> BatchResponse<T> {
 pub type SuccessResponse<T> = ApiResponse<T>;
 pub type ErrorResponse = ApiResponse<()>;
 pub type ListResponse<T> = ApiResponse<super::PaginatedResponse<T>>;
+pub type CursorListResponse<T> = ApiResponse<super::CursorPaginatedResponse<T>>;
 pub type CreateResponse<T> = ApiResponse<OperationResponse<T>>;
 pub type UpdateResponse<T> = ApiResponse<OperationResponse<T>>;
 pub type DeleteResponse = ApiResponse<OperationResponse<()>>;
@@ -113,6 +113,29 @@ pub trait CustomValidator: Send + Sync {
     fn description(&self) -> &'static str;
 }
 
+/// 字段级验证声明 - DTO声明字段需要由注册表中哪些验证器校验
+#[derive(Debug, Clone)]
+pub struct FieldValidators<'a> {
+    /// 字段路径
+    pub field: &'static str,
+
+    /// 字段值
+    pub value: &'a str,
+
+    /// 注册表中的验证器名称
+    pub validators: &'static [&'static str],
+}
+
+impl<'a> FieldValidators<'a> {
+    pub fn new(field: &'static str, value: &'a str, validators: &'static [&'static str]) -> Self {
+        Self {
+            field,
+            value,
+            validators,
+        }
+    }
+}
+
 /// 组合验证器 - 可以组合多个验证规则
 pub struct CompositeValidator {
     validators: Vec<Box<dyn CustomValidator>>,
@@ -92,6 +92,28 @@ impl DtoManager {
         self
     }
 
+    /// Build a pagination request from query parameters, applying defaults and limits
+    pub fn pagination_request(
+        &self,
+        page: Option<u32>,
+        page_size: Option<u32>,
+    ) -> PaginationRequest {
+        PaginationRequest::new(
+            page.unwrap_or(1),
+            page_size.unwrap_or(self.pagination_config.default_page_size),
+        )
+        .clamp_page_size(self.pagination_config.max_page_size)
+    }
+
+    /// Build a cursor request from query parameters, applying defaults and limits
+    pub fn cursor_request(&self, after: Option<String>, limit: Option<u32>) -> CursorRequest {
+        CursorRequest::new(
+            after,
+            limit.unwrap_or(self.pagination_config.default_page_size),
+        )
+        .clamp_limit(self.pagination_config.max_page_size)
+    }
+
     /// Configure response builder
     pub fn with_response_builder(mut self, builder: ResponseBuilder) -> Self {
         self.response_builder = Arc::new(builder);
@@ -111,23 +133,77 @@ impl DtoManager {
         dto: &T,
         context: &ValidationContext,
     ) -> Result<(), Vec<DtoValidationError>> {
+        let mut errors = Vec::new();
+
         // 1. Basic validation
         if let Err(error) = dto.validate() {
-            return Err(vec![error]);
+            errors.push(error);
         }
 
-        // 2. Custom validation (if needed)
-        // More complex validation logic can be added here
+        // 2. Registered validators declared by the DTO, collecting every failure
+        for binding in dto.field_validators() {
+            for name in binding.validators {
+                let Some(custom) = self.validator_registry.get(name) else {
+                    errors.push(
+                        DtoValidationError::new(
+                            ValidationErrorType::Custom,
+                            format!("Unknown validator '{}'", name),
+                            Some(binding.field.to_string()),
+                        )
+                        .with_rule(name.to_string()),
+                    );
+                    continue;
+                };
+
+                if let Err(mut error) = custom.validate(binding.value, context) {
+                    error
+                        .field_path
+                        .get_or_insert_with(|| binding.field.to_string());
+                    error.rule.get_or_insert_with(|| name.to_string());
+                    errors.push(error);
+                }
+            }
+        }
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 
-    /// Convert request DTO to domain model
+    /// Validate request DTO, registered validators included, then convert it to domain model
     pub fn convert_request<R: RequestDto>(
         &self,
         request: &R,
         context: &ConversionContext,
     ) -> Result<R::DomainModel, ConversionError> {
+        let validation_context = ValidationContext {
+            user_id: context.user_id,
+            workspace_id: context.workspace_id,
+            operation: context.operation.clone(),
+            current_time: context.timestamp,
+            extensions: context.metadata.clone(),
+        };
+        if let Err(errors) = self.validate_dto(request, &validation_context) {
+            let mut error = ConversionError::new(
+                ConversionErrorType::BusinessRuleViolation,
+                format!("{} failed validation", R::dto_type()),
+                R::dto_type().to_string(),
+                std::any::type_name::<R::DomainModel>().to_string(),
+            )
+            .with_details(
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            )
+            .with_context(context.clone());
+            error.failed_field = errors.into_iter().find_map(|e| e.field_path);
+            return Err(error);
+        }
+
         request.to_domain()
     }
 
@@ -149,6 +225,7 @@ impl DtoManager {
         total_items: u64,
         request_id: String,
     ) -> Result<ListResponse<R>, ConversionError> {
+        let pagination = pagination.clamp_page_size(self.pagination_config.max_page_size);
         let response_dtos = R::from_domain_collection(domains)?;
         let paginated = PaginatedResponse::new(
             response_dtos,
@@ -159,6 +236,72 @@ impl DtoManager {
         Ok(ApiResponse::success(paginated, request_id))
     }
 
+    /// Create paginated response using the DTO's batch conversion fast path
+    ///
+    /// Shared data for the whole page is fetched once through `R::prefetch` instead of
+    /// once per item.
+    pub async fn create_paginated_response_batched<R>(
+        &self,
+        domains: &[R::DomainModel],
+        source: &R::Source,
+        pagination: PaginationRequest,
+        total_items: u64,
+        request_id: String,
+    ) -> Result<ListResponse<R>, ConversionError>
+    where
+        R: BatchResponseDto,
+    {
+        let pagination = pagination.clamp_page_size(self.pagination_config.max_page_size);
+        let prefetched = R::prefetch(domains, source).await?;
+        let response_dtos = domains
+            .iter()
+            .map(|domain| R::from_domain_prefetched(domain, &prefetched))
+            .collect::<Result<Vec<R>, ConversionError>>()?;
+        let paginated = PaginatedResponse::new(
+            response_dtos,
+            pagination.page,
+            pagination.page_size,
+            total_items,
+        );
+        Ok(ApiResponse::success(paginated, request_id))
+    }
+
+    /// Create cursor paginated response
+    ///
+    /// `domains` should hold up to `limit + 1` rows in keyset order; the extra row only
+    /// signals that another page exists. `keyset` extracts the ordering key that is
+    /// encoded into the opaque `next_cursor`.
+    pub fn create_cursor_paginated_response<R, K, F>(
+        &self,
+        domains: &[R::DomainModel],
+        request: &CursorRequest,
+        keyset: F,
+        request_id: String,
+    ) -> Result<CursorListResponse<R>, ConversionError>
+    where
+        R: ResponseDto,
+        K: serde::Serialize,
+        F: Fn(&R::DomainModel) -> K,
+    {
+        let request = request
+            .clone()
+            .clamp_limit(self.pagination_config.max_page_size);
+        let limit = request.limit as usize;
+        let page = &domains[..domains.len().min(limit)];
+
+        let next_cursor = if domains.len() > limit {
+            page.last().map(|last| encode_cursor(&keyset(last)))
+        } else {
+            None
+        };
+
+        let response_dtos = R::from_domain_collection(page)?;
+        Ok(ApiResponse::success(
+            CursorPaginatedResponse::new(response_dtos, next_cursor),
+            request_id,
+        ))
+    }
+
     /// Create batch operation response
     pub fn create_batch_response<R: ResponseDto>(
         &self,
@@ -454,3 +597,367 @@ mod example_usage {
     }
     */
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct NumberDto {
+        value: i64,
+    }
+
+    impl BaseDto for NumberDto {
+        fn dto_type() -> &'static str {
+            "NumberDto"
+        }
+
+        fn validate(&self) -> Result<(), DtoValidationError> {
+            Ok(())
+        }
+    }
+
+    impl ResponseDto for NumberDto {
+        type DomainModel = i64;
+
+        fn from_domain(domain: &Self::DomainModel) -> Result<Self, ConversionError> {
+            Ok(Self { value: *domain })
+        }
+    }
+
+    #[test]
+    fn paginated_response_should_report_page_position() {
+        let manager = DtoManager::new();
+        let pagination = manager.pagination_request(Some(2), Some(20));
+
+        let response = manager
+            .create_paginated_response::<NumberDto>(
+                &(20..40).collect::<Vec<i64>>(),
+                pagination,
+                95,
+                "test".to_string(),
+            )
+            .unwrap();
+
+        let page = response.data.expect("paginated data");
+        assert_eq!(page.data.len(), 20);
+        assert_eq!(page.data[0].value, 20);
+        assert_eq!(page.pagination.current_page, 2);
+        assert_eq!(page.pagination.total_items, 95);
+        assert_eq!(page.pagination.total_pages, 5);
+        assert_eq!(page.pagination.previous_page, Some(1));
+        assert_eq!(page.pagination.next_page, Some(3));
+        assert!(page.pagination.has_next);
+        assert!(page.pagination.next_cursor.is_none());
+    }
+
+    #[test]
+    fn last_page_should_have_no_next_page() {
+        let manager = DtoManager::new();
+        let pagination = manager.pagination_request(Some(5), Some(20));
+
+        let response = manager
+            .create_paginated_response::<NumberDto>(&[80, 81], pagination, 82, "test".to_string())
+            .unwrap();
+
+        let page = response
+            .data
+            .expect("paginated data")
+            .with_next_cursor(None);
+        assert_eq!(page.pagination.total_pages, 5);
+        assert!(!page.pagination.has_next);
+        assert_eq!(page.pagination.next_page, None);
+    }
+
+    #[test]
+    fn over_max_page_size_should_be_clamped() {
+        let manager = DtoManager::new().with_pagination_config(PaginationConfig {
+            default_page_size: 20,
+            max_page_size: 50,
+            enable_cursor_pagination: true,
+        });
+
+        let pagination = manager.pagination_request(Some(0), Some(10_000));
+        assert_eq!(pagination.page, 1);
+        assert_eq!(pagination.page_size, 50);
+        assert_eq!(pagination.limit(), 50);
+
+        let defaulted = manager.pagination_request(None, None);
+        assert_eq!(defaulted.page_size, 20);
+
+        // Requests built elsewhere are clamped when the envelope is created
+        let response = manager
+            .create_paginated_response::<NumberDto>(
+                &[1, 2, 3],
+                PaginationRequest::new(1, 500),
+                120,
+                "test".to_string(),
+            )
+            .unwrap();
+        let meta = response.data.expect("paginated data").pagination;
+        assert_eq!(meta.page_size, 50);
+        assert_eq!(meta.total_pages, 3);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct IdKeyset {
+        id: i64,
+    }
+
+    /// Newest-first keyset query over an in-memory table, fetching `limit + 1` rows
+    fn fetch_page(table: &[i64], request: &CursorRequest) -> Vec<i64> {
+        let after = request.decode_after::<IdKeyset>().unwrap();
+        let mut rows: Vec<i64> = table
+            .iter()
+            .copied()
+            .filter(|id| match &after {
+                Some(keyset) => *id < keyset.id,
+                None => true,
+            })
+            .collect();
+        rows.sort_unstable_by(|a, b| b.cmp(a));
+        rows.truncate(request.limit as usize + 1);
+        rows
+    }
+
+    #[test]
+    fn cursor_should_round_trip() {
+        let keyset = IdKeyset { id: 9_007_199_254 };
+        let cursor = encode_cursor(&keyset);
+
+        assert!(!cursor.contains('{'), "cursor should be opaque: {}", cursor);
+        assert_eq!(decode_cursor::<IdKeyset>(&cursor).unwrap(), keyset);
+
+        let request = CursorRequest::new(Some(cursor), 10);
+        assert_eq!(request.decode_after::<IdKeyset>().unwrap(), Some(keyset));
+        assert_eq!(
+            CursorRequest::new(None, 10)
+                .decode_after::<IdKeyset>()
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn malformed_cursor_should_be_rejected() {
+        let error = decode_cursor::<IdKeyset>("not a cursor!").unwrap_err();
+        assert_eq!(error.field_path.as_deref(), Some("after"));
+
+        let request = CursorRequest::new(Some(encode_cursor(&"unrelated")), 10);
+        assert!(request.decode_after::<IdKeyset>().is_err());
+    }
+
+    #[test]
+    fn cursor_paging_should_be_stable_across_inserts() {
+        let manager = DtoManager::new();
+        let mut table: Vec<i64> = (1..=10).collect();
+
+        let first_request = manager.cursor_request(None, Some(4));
+        let first = manager
+            .create_cursor_paginated_response::<NumberDto, _, _>(
+                &fetch_page(&table, &first_request),
+                &first_request,
+                |id| IdKeyset { id: *id },
+                "test".to_string(),
+            )
+            .unwrap()
+            .data
+            .unwrap();
+        let first_ids: Vec<i64> = first.data.iter().map(|dto| dto.value).collect();
+        assert_eq!(first_ids, vec![10, 9, 8, 7]);
+        assert!(first.has_more);
+
+        // New rows arrive at the head; offset paging would now repeat 7 and 8
+        table.extend([11, 12]);
+
+        let mut seen = first_ids;
+        let mut cursor = first.next_cursor;
+        while let Some(after) = cursor {
+            let request = manager.cursor_request(Some(after), Some(4));
+            let page = manager
+                .create_cursor_paginated_response::<NumberDto, _, _>(
+                    &fetch_page(&table, &request),
+                    &request,
+                    |id| IdKeyset { id: *id },
+                    "test".to_string(),
+                )
+                .unwrap()
+                .data
+                .unwrap();
+            seen.extend(page.data.iter().map(|dto| dto.value));
+            assert_eq!(page.has_more, page.next_cursor.is_some());
+            cursor = page.next_cursor;
+        }
+
+        assert_eq!(seen, (1..=10).rev().collect::<Vec<i64>>());
+    }
+
+    #[test]
+    fn cursor_limit_should_be_clamped() {
+        let manager = DtoManager::new();
+        assert_eq!(manager.cursor_request(None, Some(1_000)).limit, 100);
+        assert_eq!(manager.cursor_request(None, Some(0)).limit, 1);
+        assert_eq!(manager.cursor_request(None, None).limit, 20);
+    }
+
+    struct NoAdminValidator {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl CustomValidator for NoAdminValidator {
+        fn validate(
+            &self,
+            value: &str,
+            _context: &ValidationContext,
+        ) -> Result<(), DtoValidationError> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if value.eq_ignore_ascii_case("admin") {
+                return Err(DtoValidationError::new(
+                    ValidationErrorType::Business,
+                    "Reserved username".to_string(),
+                    None,
+                ));
+            }
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "no_admin"
+        }
+
+        fn description(&self) -> &'static str {
+            "Rejects reserved usernames"
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct SignupDto {
+        username: String,
+        email: String,
+        bio: String,
+    }
+
+    impl BaseDto for SignupDto {
+        fn dto_type() -> &'static str {
+            "SignupDto"
+        }
+
+        fn validate(&self) -> Result<(), DtoValidationError> {
+            if self.bio.len() > 10 {
+                return Err(DtoValidationError::new(
+                    ValidationErrorType::Length,
+                    "Bio too long".to_string(),
+                    Some("bio".to_string()),
+                ));
+            }
+            Ok(())
+        }
+
+        fn field_validators(&self) -> Vec<FieldValidators<'_>> {
+            vec![
+                FieldValidators::new("username", &self.username, &["no_admin"]),
+                FieldValidators::new("email", &self.email, &["email"]),
+            ]
+        }
+    }
+
+    #[test]
+    fn registered_validators_should_run_and_aggregate_errors() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut manager = DtoManager::new();
+        manager.register_validator(
+            "no_admin".to_string(),
+            Box::new(NoAdminValidator {
+                calls: calls.clone(),
+            }),
+        );
+
+        let dto = SignupDto {
+            username: "Admin".to_string(),
+            email: "not-an-email".to_string(),
+            bio: "far too long for this field".to_string(),
+        };
+        let errors = manager
+            .validate_dto(&dto, &ValidationContext::new())
+            .unwrap_err();
+
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].field_path.as_deref(), Some("bio"));
+        assert_eq!(errors[1].field_path.as_deref(), Some("username"));
+        assert_eq!(errors[1].rule.as_deref(), Some("no_admin"));
+        assert_eq!(errors[1].message, "Reserved username");
+        assert_eq!(errors[2].field_path.as_deref(), Some("email"));
+        assert!(matches!(errors[2].error_type, ValidationErrorType::Email));
+
+        let valid = SignupDto {
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            bio: "hi".to_string(),
+        };
+        assert!(manager
+            .validate_dto(&valid, &ValidationContext::new())
+            .is_ok());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn unknown_validator_name_should_be_reported() {
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        struct TypoDto {
+            name: String,
+        }
+
+        impl BaseDto for TypoDto {
+            fn dto_type() -> &'static str {
+                "TypoDto"
+            }
+
+            fn validate(&self) -> Result<(), DtoValidationError> {
+                Ok(())
+            }
+
+            fn field_validators(&self) -> Vec<FieldValidators<'_>> {
+                vec![FieldValidators::new("name", &self.name, &["emial"])]
+            }
+        }
+
+        let errors = DtoManager::new()
+            .validate_dto(
+                &TypoDto {
+                    name: "x".to_string(),
+                },
+                &ValidationContext::new(),
+            )
+            .unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule.as_deref(), Some("emial"));
+    }
+
+    #[test]
+    fn convert_request_should_run_declared_validators() {
+        use crate::dtos::models::requests::auth::RegisterRequest;
+
+        let manager = DtoManager::new();
+        let request = |password: &str| RegisterRequest {
+            email: "alice@example.com".to_string(),
+            password: password.to_string(),
+            fullname: "Alice".to_string(),
+            workspace_name: None,
+        };
+
+        // Long enough for the DTO's own rules, too weak for `password_strength`
+        let error = manager
+            .convert_request(&request("password"), &ConversionContext::new())
+            .unwrap_err();
+        assert_eq!(error.failed_field.as_deref(), Some("password"));
+
+        let user = manager
+            .convert_request(&request("Passw0rdOk"), &ConversionContext::new())
+            .unwrap();
+        assert_eq!(user.email, "alice@example.com");
+        assert_eq!(user.workspace, "Default");
+    }
+}
@@ -1,9 +1,13 @@
+use crate::dtos::core::{
+    BaseDto, ConversionError, DtoValidationError, FieldValidators, RequestDto, ValidationErrorType,
+};
+use fechatter_core::{CreateUser, SigninUser};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use validator::{Validate, ValidationError};
+use validator::{Validate, ValidationError, ValidationErrors};
 
 /// 用户登录请求
-#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
 pub struct LoginRequest {
     #[validate(email(message = "Invalid email format"))]
     #[schema(example = "user@example.com")]
@@ -18,7 +22,7 @@ pub struct LoginRequest {
 }
 
 /// 用户注册请求
-#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
 pub struct RegisterRequest {
     #[validate(email(message = "Invalid email format"))]
     #[schema(example = "newuser@example.com")]
@@ -40,6 +44,67 @@ pub struct RegisterRequest {
     pub workspace_name: Option<String>,
 }
 
+impl BaseDto for LoginRequest {
+    fn dto_type() -> &'static str {
+        "LoginRequest"
+    }
+
+    fn validate(&self) -> Result<(), DtoValidationError> {
+        Validate::validate(self).map_err(invalid_input)
+    }
+
+    /// 登录不校验密码强度，旧规则下创建的账号仍可登录
+    fn field_validators(&self) -> Vec<FieldValidators<'_>> {
+        vec![FieldValidators::new("email", &self.email, &["email"])]
+    }
+}
+
+impl RequestDto for LoginRequest {
+    type DomainModel = SigninUser;
+
+    fn to_domain(&self) -> Result<Self::DomainModel, ConversionError> {
+        Ok(SigninUser::new(&self.email, &self.password))
+    }
+}
+
+impl BaseDto for RegisterRequest {
+    fn dto_type() -> &'static str {
+        "RegisterRequest"
+    }
+
+    fn validate(&self) -> Result<(), DtoValidationError> {
+        Validate::validate(self).map_err(invalid_input)
+    }
+
+    fn field_validators(&self) -> Vec<FieldValidators<'_>> {
+        vec![
+            FieldValidators::new("email", &self.email, &["email"]),
+            FieldValidators::new("password", &self.password, &["password_strength"]),
+        ]
+    }
+}
+
+impl RequestDto for RegisterRequest {
+    type DomainModel = CreateUser;
+
+    fn to_domain(&self) -> Result<Self::DomainModel, ConversionError> {
+        Ok(CreateUser {
+            fullname: self.fullname.clone(),
+            email: self.email.clone(),
+            password: self.password.clone(),
+            workspace: self
+                .workspace_name
+                .clone()
+                .unwrap_or_else(|| "Default".to_string()),
+        })
+    }
+}
+
+/// `validator` 派生规则的失败转换为 DTO 验证错误
+fn invalid_input(errors: ValidationErrors) -> DtoValidationError {
+    DtoValidationError::new(ValidationErrorType::Format, errors.to_string(), None)
+}
+
 /// 刷新令牌请求
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RefreshTokenRequest {
@@ -20,6 +20,14 @@ pub struct SendMessageRequest {
     #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
     pub idempotency_key: Option<Uuid>,
 
+    #[validate(length(
+        min = 1,
+        max = 64,
+        message = "Client message ID must be between 1 and 64 characters"
+    ))]
+    #[schema(example = "local-1718000000000-1")]
+    pub client_message_id: Option<String>, // 客户端乐观消息ID，原样回传
+
     #[schema(example = 789)]
     pub reply_to: Option<i64>, // 回复的消息ID
 
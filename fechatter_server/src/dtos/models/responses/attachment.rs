@@ -0,0 +1,63 @@
+//! Attachment metadata as presented by the API
+//!
+//! Messages store one string per attachment. Older messages hold the bare file path returned
+//! by the upload endpoint; entries written with full metadata hold it as a JSON object of the
+//! shape below. Both are read into the same `Attachment`, so no data migration is needed.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A file attached to a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Attachment {
+    #[schema(example = "/files/2f5d1c.png")]
+    pub url: String,
+
+    #[schema(example = "2f5d1c.png")]
+    pub filename: String,
+
+    #[schema(example = "image/png")]
+    pub mime_type: String,
+
+    /// Size in bytes, `null` when it was not recorded
+    #[schema(example = 20480)]
+    pub size: Option<u64>,
+}
+
+impl Attachment {
+    /// Read a stored attachment entry, synthesizing metadata for legacy bare paths
+    pub fn from_stored(entry: &str) -> Self {
+        if entry.trim_start().starts_with('{') {
+            if let Ok(attachment) = serde_json::from_str::<Attachment>(entry) {
+                return attachment;
+            }
+        }
+        Self::from_legacy_path(entry)
+    }
+
+    /// Metadata derived from the path alone: the last segment is the filename and the
+    /// extension gives the MIME type; the size is unknown
+    pub fn from_legacy_path(path: &str) -> Self {
+        let without_query = path.split(['?', '#']).next().unwrap_or(path);
+        let filename = without_query
+            .rsplit('/')
+            .find(|segment| !segment.is_empty())
+            .unwrap_or(without_query)
+            .to_string();
+        let mime_type = mime_guess::from_path(&filename)
+            .first_or_octet_stream()
+            .to_string();
+
+        Self {
+            url: path.to_string(),
+            filename,
+            mime_type,
+            size: None,
+        }
+    }
+
+    /// Attachments of a message's stored `files`, in order
+    pub fn from_stored_files(files: &[String]) -> Vec<Self> {
+        files.iter().map(|entry| Self::from_stored(entry)).collect()
+    }
+}
@@ -134,6 +134,23 @@ pub struct LogoutResponse {
     pub logout_time: chrono::DateTime<chrono::Utc>,
 }
 
+/// 登出其他会话响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LogoutOthersResponse {
+    #[schema(example = "Logged out from other sessions successfully")]
+    pub message: String,
+
+    #[schema(example = true)]
+    pub success: bool,
+
+    /// Refresh tokens revoked; the current session is kept
+    #[schema(example = 2)]
+    pub revoked_sessions: u64,
+
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub logout_time: chrono::DateTime<chrono::Utc>,
+}
+
 /// 用户会话信息响应
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SessionInfoResponse {
@@ -1,5 +1,7 @@
+use crate::domains::chat::chat_member_repository::ChatMemberListing;
 use crate::dtos::core::{BaseDto, ConversionError, ResponseDto};
 use crate::services::application::ChatDetailView;
+use fechatter_core::contracts::PresenceStatus;
 use fechatter_core::{models::chat::ChatSidebar, ChatType};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -217,6 +219,10 @@ pub struct ChatMemberDto {
     #[schema(example = true)]
     pub is_online: bool,
 
+    /// Presence store status: online, away or offline
+    #[schema(example = "online")]
+    pub presence: String,
+
     #[schema(example = true)]
     pub is_creator: bool,
 }
@@ -231,8 +237,24 @@ impl BaseDto for ChatMemberDto {
     }
 }
 
-// Note: We'll need to define a domain model for ChatMember if it doesn't exist
-// For now, we'll implement a simple conversion
+impl ResponseDto for ChatMemberDto {
+    type DomainModel = ChatMemberListing;
+
+    fn from_domain(domain: &Self::DomainModel) -> Result<Self, ConversionError> {
+        Ok(Self {
+            user_id: domain.user_id,
+            chat_id: domain.chat_id,
+            username: domain.username.clone(),
+            display_name: Some(domain.fullname.clone()),
+            role: domain.role.clone(),
+            joined_at: domain.joined_at,
+            is_online: domain.presence != PresenceStatus::Offline,
+            presence: domain.presence.as_str().to_string(),
+            is_creator: domain.is_creator,
+        })
+    }
+}
+
 impl ChatMemberDto {
     pub fn new(
         user_id: i64,
@@ -252,6 +274,7 @@ impl ChatMemberDto {
             role,
             joined_at,
             is_online,
+            presence: if is_online { "online" } else { "offline" }.to_string(),
             is_creator,
         }
     }
@@ -178,4 +178,9 @@ pub struct UploadResponse {
     pub mime_type: String,
     pub size: u64,
     pub created_at: String,
+    /// `pending`, `clean`, `infected` or `failed` when attachment scanning is enabled; only
+    /// `clean` files can be downloaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "clean")]
+    pub scan_status: Option<String>,
 }
@@ -2,6 +2,7 @@
 // Used for HTTP API responses and structured data output
 
 // API Response DTOs
+pub mod attachment;
 pub mod auth;
 pub mod chat;
 pub mod common;
@@ -9,6 +10,7 @@ pub mod message;
 pub mod user;
 
 // Re-exports for convenience
+pub use attachment::*;
 pub use auth::*;
 pub use common::*;
 pub use message::*;
@@ -1,9 +1,10 @@
-use fechatter_core::models::UserStatus;
+use crate::dtos::core::{BaseDto, ConversionError, DtoValidationError, ResponseDto};
+use fechatter_core::models::{User, UserStatus};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 /// Complete user profile response
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserProfileResponse {
     pub id: i64,
     pub fullname: String,
@@ -22,8 +23,42 @@ pub struct UserProfileResponse {
     pub settings: Option<UserSettingsResponse>,
 }
 
+impl BaseDto for UserProfileResponse {
+    fn dto_type() -> &'static str {
+        "UserProfileResponse"
+    }
+
+    fn validate(&self) -> Result<(), DtoValidationError> {
+        Ok(())
+    }
+}
+
+impl ResponseDto for UserProfileResponse {
+    type DomainModel = User;
+
+    fn from_domain(domain: &Self::DomainModel) -> Result<Self, ConversionError> {
+        Ok(Self {
+            id: domain.id.into(),
+            fullname: domain.fullname.clone(),
+            email: domain.email.clone(),
+            status: domain.status,
+            created_at: domain.created_at,
+            workspace_id: domain.workspace_id.into(),
+            phone: domain.phone.clone(),
+            title: domain.title.clone(),
+            department: domain.department.clone(),
+            avatar_url: domain.avatar_url.clone(),
+            bio: domain.bio.clone(),
+            timezone: domain.timezone.clone(),
+            language: domain.language.clone(),
+            last_active_at: domain.last_active_at,
+            settings: None, // Settings are fetched separately
+        })
+    }
+}
+
 /// User settings response
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserSettingsResponse {
     pub email_notifications: bool,
     pub push_notifications: bool,
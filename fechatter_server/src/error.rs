@@ -7,6 +7,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::services::infrastructure::rate_limit::{apply_rate_limit_headers, RateLimitDecision};
 use fechatter_core::error::{ChatValidationError, CoreError, ErrorMapper};
 use thiserror::Error;
 
@@ -17,6 +18,9 @@ use validator::ValidationErrors;
 pub struct ErrorOutput {
     pub code: u16,
     pub error: String,
+    /// Machine-readable reason clients can branch on (see `AppError::error_code`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
 }
 
 impl ErrorOutput {
@@ -24,6 +28,7 @@ impl ErrorOutput {
         Self {
             code: StatusCode::BAD_REQUEST.as_u16(),
             error: error.into(),
+            error_code: None,
         }
     }
 }
@@ -133,6 +138,9 @@ pub enum AppError {
     #[error("service unavailable: {0}")]
     ServiceUnavailable(String),
 
+    #[error("read-only maintenance mode: {0}")]
+    Maintenance(String),
+
     #[error("Bad request: {0}")]
     BadRequest(String),
 
@@ -151,11 +159,8 @@ pub enum AppError {
     #[error("Operation timed out: {0}")]
     Timeout(String),
 
-    #[error("Rate limit exceeded: {0}")]
-    RateLimitExceeded(String),
-
-    #[error("Too many requests: {0}")]
-    TooManyRequests(String),
+    #[error("Rate limit exceeded, retry after {}s", .0.retry_after_secs())]
+    RateLimited(RateLimitDecision),
 
     #[error("Security threat detected: {0}")]
     SecurityThreatDetected(String),
@@ -255,59 +260,32 @@ pub fn map_core_error_to_app_error(core_error: CoreError) -> AppError {
             AppError::InvalidInput(format!("Vector database error: {}", e))
         }
         CoreError::PublishError(e) => AppError::EventPublishingError(e.to_string()),
-        CoreError::Unimplemented(e) => AppError::AnyError(anyhow::anyhow!(e)),
-    }
-}
-
-impl ErrorMapper for AppError {
-    type Error = AppError;
-    fn map_error(error: CoreError) -> Self::Error {
-        map_core_error_to_app_error(error)
-    }
-}
-
-/// Convert EventTransportError to AppError
-impl From<EventTransportError> for AppError {
-    fn from(error: EventTransportError) -> Self {
-        AppError::TransportError(error)
+        CoreError::Unimplemented(e) => AppError::NotImplemented(e),
     }
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response<Body> {
-        tracing::info!(
-            "[HTTP_RESPONSE] ========== Converting AppError to HTTP Response =========="
-        );
-        tracing::debug!("[HTTP_RESPONSE] Input AppError: {:?}", self);
-
-        let status = match &self {
-            AppError::UserAlreadyExists(_) => StatusCode::CONFLICT,
-            AppError::NotFound(_) => {
-                tracing::info!("[HTTP_RESPONSE] NotFound error -> HTTP 404");
-                StatusCode::NOT_FOUND
-            }
-            AppError::SqlxError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+impl AppError {
+    /// HTTP status for this error; every variant is listed so new ones can't default to 500
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::SqlxError(e) => sqlx_status_code(e),
+            AppError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::IOError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::PasswordHashError(_) => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::JwtError(_) => StatusCode::FORBIDDEN,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             AppError::AnyError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::HttpHeaderError(_) => StatusCode::UNPROCESSABLE_ENTITY,
-            AppError::ChatValidationError(_) => StatusCode::BAD_REQUEST,
+            AppError::UserAlreadyExists(_) => StatusCode::CONFLICT,
+            AppError::WorkspaceAlreadyExists(_) => StatusCode::CONFLICT,
             AppError::ChatAlreadyExists(_) => StatusCode::CONFLICT,
+            AppError::ChatValidationError(_) => StatusCode::BAD_REQUEST,
             AppError::ChatPermissionError(_) => StatusCode::FORBIDDEN,
-            AppError::WorkspaceAlreadyExists(_) => StatusCode::CONFLICT,
-            AppError::InvalidInput(_) => StatusCode::BAD_REQUEST,
-            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-            AppError::Conflict(_) => StatusCode::CONFLICT,
-            AppError::IOError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::ChatFileError(_) => StatusCode::NOT_FOUND,
-            AppError::ChatMembershipError { .. } => {
-                tracing::info!("[HTTP_RESPONSE] ChatMembershipError -> HTTP 403");
-                StatusCode::FORBIDDEN
-            }
-            AppError::ChatAccessDenied { .. } => {
-                tracing::info!("[HTTP_RESPONSE] ChatAccessDenied -> HTTP 403");
-                StatusCode::FORBIDDEN
-            }
+            AppError::ChatMembershipError { .. } => StatusCode::FORBIDDEN,
+            AppError::ChatAccessDenied { .. } => StatusCode::FORBIDDEN,
             AppError::NatsError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::EventPublishingError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::SearchError(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -321,20 +299,102 @@ impl IntoResponse for AppError {
             AppError::RedisError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Configuration(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Maintenance(_) => StatusCode::SERVICE_UNAVAILABLE,
             AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
             AppError::SerializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::EventPublishError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::ConfigError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::AuthenticationError(_) => StatusCode::UNAUTHORIZED,
             AppError::Timeout(_) => StatusCode::REQUEST_TIMEOUT,
-            AppError::RateLimitExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
-            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
             AppError::SecurityThreatDetected(_) => StatusCode::FORBIDDEN,
             AppError::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
             AppError::MultipartError(_) => StatusCode::BAD_REQUEST,
             AppError::FileUploadError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-        };
+        }
+    }
+
+    /// Machine-readable `error_code` returned in the response body
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            AppError::SqlxError(_) => "database_error",
+            AppError::InvalidInput(_) => "invalid_input",
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict(_) => "conflict",
+            AppError::IOError(_) => "io_error",
+            AppError::PasswordHashError(_) => "password_hash_error",
+            AppError::JwtError(_) => "invalid_token",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::AnyError(_) => "internal_error",
+            AppError::HttpHeaderError(_) => "invalid_header",
+            AppError::UserAlreadyExists(_) => "user_already_exists",
+            AppError::WorkspaceAlreadyExists(_) => "workspace_already_exists",
+            AppError::ChatAlreadyExists(_) => "chat_already_exists",
+            AppError::ChatValidationError(_) => "chat_validation_error",
+            AppError::ChatPermissionError(_) => "chat_permission_denied",
+            AppError::ChatFileError(_) => "chat_file_error",
+            AppError::ChatMembershipError { .. } => "chat_membership_error",
+            AppError::ChatAccessDenied { .. } => "chat_access_denied",
+            AppError::NatsError(_) => "nats_error",
+            AppError::EventPublishingError(_) => "event_publishing_error",
+            AppError::SearchError(_) => "search_error",
+            AppError::PermissionDenied(_) => "permission_denied",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::ValidationError(_) => "validation_error",
+            AppError::ServerError(_) => "server_error",
+            AppError::ExternalServiceError(_) => "external_service_error",
+            AppError::Internal(_) => "internal_error",
+            AppError::TransportError(_) => "transport_error",
+            AppError::RedisError(_) => "redis_error",
+            AppError::Configuration(_) => "configuration_error",
+            AppError::ServiceUnavailable(_) => "service_unavailable",
+            AppError::Maintenance(_) => "maintenance",
+            AppError::BadRequest(_) => "bad_request",
+            AppError::SerializationError(_) => "serialization_error",
+            AppError::EventPublishError(_) => "event_publish_error",
+            AppError::ConfigError(_) => "configuration_error",
+            AppError::AuthenticationError(_) => "authentication_error",
+            AppError::Timeout(_) => "timeout",
+            AppError::RateLimited(_) => "rate_limited",
+            AppError::SecurityThreatDetected(_) => "security_threat",
+            AppError::NotImplemented(_) => "not_implemented",
+            AppError::MultipartError(_) => "multipart_error",
+            AppError::FileUploadError(_) => "file_upload_error",
+        }
+    }
+}
+
+/// Constraint violations are client errors; any other database failure is a 500
+fn sqlx_status_code(error: &sqlx::Error) -> StatusCode {
+    match error.as_database_error() {
+        Some(db_err) if db_err.is_unique_violation() => StatusCode::CONFLICT,
+        Some(db_err) if db_err.is_foreign_key_violation() => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+impl ErrorMapper for AppError {
+    type Error = AppError;
+    fn map_error(error: CoreError) -> Self::Error {
+        map_core_error_to_app_error(error)
+    }
+}
+
+/// Convert EventTransportError to AppError
+impl From<EventTransportError> for AppError {
+    fn from(error: EventTransportError) -> Self {
+        AppError::TransportError(error)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response<Body> {
+        tracing::info!(
+            "[HTTP_RESPONSE] ========== Converting AppError to HTTP Response =========="
+        );
+        tracing::debug!("[HTTP_RESPONSE] Input AppError: {:?}", self);
 
+        let status = self.status_code();
         let code = status.as_u16();
         tracing::error!(
             "[HTTP_RESPONSE] Final HTTP Status: {} ({})",
@@ -346,10 +406,16 @@ impl IntoResponse for AppError {
         let body = Json(ErrorOutput {
             code,
             error: self.to_string(),
+            error_code: Some(self.error_code().to_string()),
         });
 
+        let mut response = (status, body).into_response();
+        if let AppError::RateLimited(decision) = &self {
+            apply_rate_limit_headers(response.headers_mut(), decision);
+        }
+
         tracing::info!("[HTTP_RESPONSE] ========== HTTP Response Generated ==========");
-        (status, body).into_response()
+        response
     }
 }
 impl From<sqlx::Error> for AppError {
@@ -394,6 +460,18 @@ impl From<CoreError> for AppError {
     }
 }
 
+impl From<crate::domains::permission::Denial> for AppError {
+    fn from(denial: crate::domains::permission::Denial) -> Self {
+        AppError::Forbidden(denial.to_string())
+    }
+}
+
+impl From<fechatter_core::services::concurrency::Saturated> for AppError {
+    fn from(saturated: fechatter_core::services::concurrency::Saturated) -> Self {
+        AppError::ServiceUnavailable(saturated.to_string())
+    }
+}
+
 impl From<std::time::SystemTimeError> for AppError {
     fn from(error: std::time::SystemTimeError) -> Self {
         AppError::Internal(format!("System time error: {}", error))
@@ -635,13 +713,7 @@ pub fn membership_status_to_app_error(
     tracing::info!("[ERROR_CONVERTER] Generated AppError: {:?}", app_error);
     tracing::debug!(
         "[ERROR_CONVERTER] Error will generate HTTP status: {}",
-        match &app_error {
-            AppError::NotFound(_) => 404,
-            AppError::ChatAccessDenied { .. } => 403,
-            AppError::ChatMembershipError { .. } => 403,
-            AppError::Internal(_) => 500,
-            _ => 500,
-        }
+        app_error.status_code().as_u16()
     );
 
     app_error
@@ -652,3 +724,244 @@ impl From<MultipartError> for AppError {
         AppError::MultipartError(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fechatter_core::error::{PublishError, TokenValidationError, VectorDbError};
+    use std::time::Duration;
+
+    /// Intended status for each variant, kept apart from `AppError::status_code`.
+    /// The match is exhaustive, so a new variant does not compile until it is given a status;
+    /// the index ties each arm to a sample in `one_of_each`.
+    fn intended_status(error: &AppError) -> (usize, StatusCode) {
+        match error {
+            AppError::SqlxError(_) => (0, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::InvalidInput(_) => (1, StatusCode::BAD_REQUEST),
+            AppError::NotFound(_) => (2, StatusCode::NOT_FOUND),
+            AppError::Conflict(_) => (3, StatusCode::CONFLICT),
+            AppError::IOError(_) => (4, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::PasswordHashError(_) => (5, StatusCode::UNPROCESSABLE_ENTITY),
+            AppError::JwtError(_) => (6, StatusCode::FORBIDDEN),
+            AppError::Unauthorized(_) => (7, StatusCode::UNAUTHORIZED),
+            AppError::AnyError(_) => (8, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::HttpHeaderError(_) => (9, StatusCode::UNPROCESSABLE_ENTITY),
+            AppError::UserAlreadyExists(_) => (10, StatusCode::CONFLICT),
+            AppError::WorkspaceAlreadyExists(_) => (11, StatusCode::CONFLICT),
+            AppError::ChatAlreadyExists(_) => (12, StatusCode::CONFLICT),
+            AppError::ChatValidationError(_) => (13, StatusCode::BAD_REQUEST),
+            AppError::ChatPermissionError(_) => (14, StatusCode::FORBIDDEN),
+            AppError::ChatFileError(_) => (15, StatusCode::NOT_FOUND),
+            AppError::ChatMembershipError { .. } => (16, StatusCode::FORBIDDEN),
+            AppError::ChatAccessDenied { .. } => (17, StatusCode::FORBIDDEN),
+            AppError::NatsError(_) => (18, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::EventPublishingError(_) => (19, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::SearchError(_) => (20, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::PermissionDenied(_) => (21, StatusCode::FORBIDDEN),
+            AppError::Forbidden(_) => (22, StatusCode::FORBIDDEN),
+            AppError::ValidationError(_) => (23, StatusCode::BAD_REQUEST),
+            AppError::ServerError(_) => (24, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::ExternalServiceError(_) => (25, StatusCode::BAD_GATEWAY),
+            AppError::Internal(_) => (26, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::TransportError(_) => (27, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::RedisError(_) => (28, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::Configuration(_) => (29, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::ServiceUnavailable(_) => (30, StatusCode::SERVICE_UNAVAILABLE),
+            AppError::Maintenance(_) => (31, StatusCode::SERVICE_UNAVAILABLE),
+            AppError::BadRequest(_) => (32, StatusCode::BAD_REQUEST),
+            AppError::SerializationError(_) => (33, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::EventPublishError(_) => (34, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::ConfigError(_) => (35, StatusCode::INTERNAL_SERVER_ERROR),
+            AppError::AuthenticationError(_) => (36, StatusCode::UNAUTHORIZED),
+            AppError::Timeout(_) => (37, StatusCode::REQUEST_TIMEOUT),
+            AppError::RateLimited(_) => (38, StatusCode::TOO_MANY_REQUESTS),
+            AppError::SecurityThreatDetected(_) => (39, StatusCode::FORBIDDEN),
+            AppError::NotImplemented(_) => (40, StatusCode::NOT_IMPLEMENTED),
+            AppError::MultipartError(_) => (41, StatusCode::BAD_REQUEST),
+            AppError::FileUploadError(_) => (42, StatusCode::INTERNAL_SERVER_ERROR),
+        }
+    }
+
+    fn one_of_each() -> Vec<AppError> {
+        let msg = || "boom".to_string();
+        vec![
+            AppError::SqlxError(sqlx::Error::PoolTimedOut),
+            AppError::InvalidInput(msg()),
+            AppError::NotFound(vec![msg()]),
+            AppError::Conflict(msg()),
+            AppError::IOError(std::io::Error::other("boom")),
+            AppError::PasswordHashError(argon2::password_hash::Error::Password),
+            AppError::JwtError(jsonwebtoken::errors::ErrorKind::InvalidToken.into()),
+            AppError::Unauthorized(msg()),
+            AppError::AnyError(anyhow::anyhow!("boom")),
+            AppError::HttpHeaderError(axum::http::HeaderValue::from_str("\n").unwrap_err()),
+            AppError::UserAlreadyExists(msg()),
+            AppError::WorkspaceAlreadyExists(msg()),
+            AppError::ChatAlreadyExists(msg()),
+            AppError::ChatValidationError(msg()),
+            AppError::ChatPermissionError(msg()),
+            AppError::ChatFileError(msg()),
+            AppError::ChatMembershipError {
+                message: msg(),
+                chat_id: 1,
+                user_id: 2,
+                membership_status: "inconsistent".to_string(),
+            },
+            AppError::ChatAccessDenied {
+                reason: msg(),
+                chat_id: 1,
+                user_id: 2,
+            },
+            AppError::NatsError(msg()),
+            AppError::EventPublishingError(msg()),
+            AppError::SearchError(msg()),
+            AppError::PermissionDenied(msg()),
+            AppError::Forbidden(msg()),
+            AppError::ValidationError(msg()),
+            AppError::ServerError(msg()),
+            AppError::ExternalServiceError(msg()),
+            AppError::Internal(msg()),
+            AppError::TransportError(EventTransportError::Timeout),
+            AppError::RedisError(msg()),
+            AppError::Configuration(msg()),
+            AppError::ServiceUnavailable(msg()),
+            AppError::Maintenance(msg()),
+            AppError::BadRequest(msg()),
+            AppError::SerializationError(msg()),
+            AppError::EventPublishError(msg()),
+            AppError::ConfigError(msg()),
+            AppError::AuthenticationError(msg()),
+            AppError::Timeout(msg()),
+            AppError::RateLimited(RateLimitDecision {
+                allowed: false,
+                limit: 10,
+                remaining: 0,
+                reset_after: Duration::from_secs(30),
+            }),
+            AppError::SecurityThreatDetected(msg()),
+            AppError::NotImplemented(msg()),
+            AppError::MultipartError(msg()),
+            AppError::FileUploadError(msg()),
+        ]
+    }
+
+    #[test]
+    fn samples_should_cover_every_app_error_variant() {
+        let indexes: Vec<usize> = one_of_each().iter().map(|e| intended_status(e).0).collect();
+        let expected: Vec<usize> = (0..indexes.len()).collect();
+        assert_eq!(indexes, expected);
+    }
+
+    #[test]
+    fn every_app_error_should_map_to_intended_status() {
+        for error in one_of_each() {
+            let (_, intended) = intended_status(&error);
+            assert_eq!(error.status_code(), intended, "{:?}", error);
+
+            let error_code = error.error_code();
+            assert!(!error_code.is_empty(), "{:?}", error);
+            let response = error.into_response();
+            assert_eq!(response.status(), intended, "{}", error_code);
+        }
+    }
+
+    #[tokio::test]
+    async fn response_body_should_carry_status_and_error_code() {
+        let response = AppError::ChatAccessDenied {
+            reason: "not a member".to_string(),
+            chat_id: 1,
+            user_id: 2,
+        }
+        .into_response();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(body["code"], 403);
+        assert_eq!(body["error_code"], "chat_access_denied");
+    }
+
+    /// Intended status once a `CoreError` reaches the HTTP layer; exhaustive like `intended_status`
+    fn intended_core_status(error: &CoreError) -> StatusCode {
+        match error {
+            CoreError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            CoreError::Validation(_) => StatusCode::BAD_REQUEST,
+            CoreError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            CoreError::ChatValidation(e) => match e {
+                ChatValidationError::InvalidName(_) => StatusCode::BAD_REQUEST,
+                ChatValidationError::InvalidMembers(_) => StatusCode::BAD_REQUEST,
+                ChatValidationError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+                ChatValidationError::MemberNotFound(_) => StatusCode::NOT_FOUND,
+                ChatValidationError::ChatNotFound(_) => StatusCode::NOT_FOUND,
+            },
+            CoreError::UserAlreadyExists(_) => StatusCode::CONFLICT,
+            CoreError::UserNotFound(_) => StatusCode::NOT_FOUND,
+            CoreError::ChatNotFound(_) => StatusCode::NOT_FOUND,
+            CoreError::ForeignKeyViolation(_) => StatusCode::BAD_REQUEST,
+            CoreError::UniqueViolation(_) => StatusCode::CONFLICT,
+            CoreError::NotFound(_) => StatusCode::NOT_FOUND,
+            CoreError::Conflict(_) => StatusCode::CONFLICT,
+            CoreError::Authentication(_) => StatusCode::UNAUTHORIZED,
+            CoreError::Unauthorized(_) => StatusCode::FORBIDDEN,
+            CoreError::InvalidToken(_) => StatusCode::UNAUTHORIZED,
+            CoreError::PublishError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            CoreError::VectorDbError(_) => StatusCode::BAD_REQUEST,
+            CoreError::Unimplemented(_) => StatusCode::NOT_IMPLEMENTED,
+            CoreError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    #[test]
+    fn every_core_error_should_map_to_intended_status() {
+        let msg = || "boom".to_string();
+        let core_errors = vec![
+            CoreError::Database(msg()),
+            CoreError::Validation(msg()),
+            CoreError::ValidationError(msg()),
+            CoreError::ChatValidation(ChatValidationError::InvalidName(msg())),
+            CoreError::ChatValidation(ChatValidationError::InvalidMembers(msg())),
+            CoreError::ChatValidation(ChatValidationError::PermissionDenied(msg())),
+            CoreError::ChatValidation(ChatValidationError::MemberNotFound(msg())),
+            CoreError::ChatValidation(ChatValidationError::ChatNotFound(msg())),
+            CoreError::UserAlreadyExists(msg()),
+            CoreError::UserNotFound(msg()),
+            CoreError::ChatNotFound(msg()),
+            CoreError::ForeignKeyViolation(msg()),
+            CoreError::UniqueViolation(msg()),
+            CoreError::NotFound(msg()),
+            CoreError::Conflict(msg()),
+            CoreError::Authentication(msg()),
+            CoreError::Unauthorized(msg()),
+            CoreError::InvalidToken(TokenValidationError::Expired),
+            CoreError::PublishError(PublishError::Network(msg())),
+            CoreError::VectorDbError(VectorDbError::Validation(msg())),
+            CoreError::Unimplemented(msg()),
+            CoreError::Internal(msg()),
+        ];
+
+        for core_error in core_errors {
+            let intended = intended_core_status(&core_error);
+            let app_error = AppError::from(core_error.clone());
+            assert_eq!(
+                app_error.status_code(),
+                intended,
+                "{:?} -> {:?}",
+                core_error,
+                app_error
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn saturated_ai_limiter_should_answer_503() {
+        use fechatter_core::services::concurrency::{ConcurrencyLimiter, SaturationPolicy};
+
+        let limiter = ConcurrencyLimiter::new(1, SaturationPolicy::Reject, Duration::from_secs(1));
+        let _held = limiter.acquire().await.unwrap();
+        let error = AppError::from(limiter.run(async {}).await.unwrap_err());
+
+        assert_eq!(error.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(error.error_code(), "service_unavailable");
+    }
+}
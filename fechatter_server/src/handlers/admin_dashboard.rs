@@ -0,0 +1,120 @@
+//! # Admin Dashboard Handler
+//!
+//! **Responsibility**: `GET /api/admin/dashboard`, a single JSON snapshot of system health
+//! **Scope**: Configured admins only; the snapshot is cached briefly, so values may lag by up
+//! to `features.admin_dashboard.cache_ttl_seconds`
+
+use axum::{
+    extract::Extension,
+    http::{header, HeaderMap},
+    response::Json,
+};
+use tracing::instrument;
+
+use crate::dtos::core::ApiResponse;
+use crate::handlers::maintenance::ensure_maintenance_admin;
+use crate::services::infrastructure::observability::dashboard::DashboardSnapshot;
+use crate::{AppError, AppState};
+use fechatter_core::AuthUser;
+
+/// Counts, connections, cache hit rate and queue depths (maintenance admins only)
+#[instrument(skip(state, headers), fields(admin_id = %user.id))]
+pub async fn get_dashboard_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<DashboardSnapshot>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+
+    // notify_server accepts the same tokens, so the admin's own is forwarded to it
+    let authorization = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    let snapshot = state
+        .admin_dashboard()
+        .snapshot(&state, authorization)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        snapshot,
+        "dashboard_retrieved".to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::models::requests::message::SendMessageRequest;
+    use crate::services::infrastructure::observability::dashboard::DashboardCache;
+    use fechatter_core::CreateMessage;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn dashboard_should_be_refused_to_non_admins() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(1).await;
+        let user = crate::auth_user!(&users[0]);
+
+        let result =
+            get_dashboard_handler(Extension(state), Extension(user), HeaderMap::new()).await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn snapshot_should_report_every_section_for_a_seeded_system() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(3).await;
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Dashboard {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id, users[2].id],
+            )
+            .await?;
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": "counted" }))?;
+        state
+            .application_services()
+            .message_service()
+            .send_message(users[0].id, chat.id, CreateMessage::from(request))
+            .await?;
+
+        let cache = DashboardCache::new(Duration::from_secs(60));
+        let snapshot = cache.snapshot(&state, None).await?;
+
+        let body = serde_json::to_value(&snapshot)?;
+        for section in ["generated_at", "counts", "connections", "cache", "queues"] {
+            assert!(body.get(section).is_some(), "missing section {}", section);
+        }
+        assert!(snapshot.counts.users >= 3);
+        assert!(snapshot.counts.workspaces >= 1);
+        assert!(snapshot.counts.chats >= 1);
+        assert!(snapshot.counts.messages >= 1);
+        // Without a token there is nothing to forward to notify_server
+        assert!(snapshot.connections.is_none());
+        if let Some(cache_stats) = &snapshot.cache {
+            assert!((0.0..=1.0).contains(&cache_stats.hit_rate));
+        }
+        let queues = &state.config.features.admin_dashboard.queues;
+        assert_eq!(snapshot.queues.len(), queues.len());
+        for (depth, queue) in snapshot.queues.iter().zip(queues) {
+            assert_eq!(depth.stream, queue.stream);
+            assert_eq!(depth.consumer, queue.consumer);
+        }
+
+        // Within the TTL the same snapshot is served, even after new activity
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": "not yet counted" }))?;
+        state
+            .application_services()
+            .message_service()
+            .send_message(users[1].id, chat.id, CreateMessage::from(request))
+            .await?;
+        let cached = cache.snapshot(&state, None).await?;
+        assert_eq!(cached.generated_at, snapshot.generated_at);
+        assert_eq!(cached.counts.messages, snapshot.counts.messages);
+        Ok(())
+    }
+}
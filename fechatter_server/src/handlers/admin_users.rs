@@ -0,0 +1,708 @@
+//! # Admin User Management Handlers
+//!
+//! **Responsibility**: Let workspace admins search users and change their status or role
+//! **Scope**: The admin's own workspace; admins are its owner and users with the `admin` role.
+//! Suspending a user also signs them out everywhere and refuses their signins. Search results are response-cached per
+//! admin and query; changes made here drop the workspace's cached responses. Erasing a user's
+//! data must be confirmed by repeating their id and can be re-sent to resume. Bulk imports
+//! report each row's outcome and return the new accounts' temporary passwords
+
+use axum::{
+    extract::{Extension, Path, Query},
+    response::{Json, Response},
+};
+use serde::Deserialize;
+use tracing::{info, instrument};
+
+use crate::domains::auth::token_repository::RefreshTokenStorage;
+use crate::domains::user::admin::{AdminUserFilter, AdminUserUpdate, AdminUserView};
+use crate::domains::user::repository::UserRepositoryImpl;
+use crate::domains::workspace::repository::WorkspaceRepositoryImpl;
+use crate::dtos::core::{ApiResponse, BatchResponse, PaginatedResponse};
+use crate::handlers::conditional::cached_json;
+use crate::handlers::page_params::{PageParams, SortFields};
+use crate::services::application::workers::profile::erasure::{UserErasure, UserErasureService};
+use crate::services::application::workers::profile::import::{
+    ImportedUser, UserImportRow, UserImportService,
+};
+use crate::{AppError, AppState};
+use fechatter_core::{AuthUser, UserId, UserStatus};
+
+/// Admin user list has a fixed order: newest first
+pub struct AdminUserSort;
+
+impl SortFields for AdminUserSort {
+    const ALLOWED: &'static [&'static str] = &[];
+}
+
+/// Filters of the admin user search
+#[derive(Debug, Default, Deserialize)]
+pub struct AdminUserSearchQuery {
+    /// Substring of the full name or email
+    pub q: Option<String>,
+    /// `Active` or `Suspended`
+    pub status: Option<UserStatus>,
+}
+
+/// Search users of the admin's workspace (workspace admins only)
+#[instrument(skip(state, query), fields(admin_id = %user.id))]
+pub async fn search_users_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Query(query): Query<AdminUserSearchQuery>,
+    page: PageParams<AdminUserSort>,
+) -> Result<Response, AppError> {
+    ensure_workspace_admin(&state, &user).await?;
+
+    let filter = AdminUserFilter {
+        q: query.q,
+        status: query.status,
+    };
+    let params = (&filter.q, filter.status, page.page, page.page_size);
+    let cache = state.response_cache();
+    let cached = cache
+        .get_or_load(
+            user.workspace_id,
+            "admin_users",
+            user.id,
+            &params,
+            || async {
+                let (users, total) = UserRepositoryImpl::new(state.pool())
+                    .search_workspace_users(
+                        user.workspace_id,
+                        &filter,
+                        page.limit(),
+                        page.offset(),
+                    )
+                    .await?;
+                Ok(PaginatedResponse::new(
+                    users,
+                    page.page,
+                    page.page_size,
+                    total.max(0) as u64,
+                ))
+            },
+        )
+        .await?;
+
+    Ok(cached_json(
+        cached.map(|users| ApiResponse::success(users, "admin_users_listed".to_string())),
+        cache.ttl_seconds(),
+    ))
+}
+
+/// Change a user's status and/or role (workspace admins only, audited)
+#[instrument(skip(state, update), fields(admin_id = %user.id, user_id = %user_id))]
+pub async fn update_user_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(user_id): Path<i64>,
+    Json(update): Json<AdminUserUpdate>,
+) -> Result<Json<ApiResponse<AdminUserView>>, AppError> {
+    ensure_workspace_admin(&state, &user).await?;
+
+    if update.is_empty() {
+        return Err(AppError::BadRequest(
+            "Nothing to change: provide status and/or role".to_string(),
+        ));
+    }
+    // An admin locking themselves out would leave no way back in
+    if user_id == i64::from(user.id) {
+        return Err(AppError::BadRequest(
+            "Admins cannot change their own status or role".to_string(),
+        ));
+    }
+    let workspace = WorkspaceRepositoryImpl::new(state.pool())
+        .find_by_id(user.workspace_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(vec!["Workspace not found".to_string()]))?;
+    if workspace.owner_id == UserId(user_id) {
+        return Err(AppError::Forbidden(
+            "The workspace owner cannot be changed here".to_string(),
+        ));
+    }
+
+    let updated = UserRepositoryImpl::new(state.pool())
+        .update_status_and_role(user.workspace_id, UserId(user_id), &update)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(vec![format!(
+                "User {} not found in your workspace",
+                user_id
+            )])
+        })?;
+
+    if update.status == Some(UserStatus::Suspended) {
+        RefreshTokenStorage::revoke_all_for_user(user_id, &state.pool()).await?;
+    }
+    state
+        .response_cache()
+        .invalidate_workspace(user.workspace_id)
+        .await;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      user_id = %user_id,
+      status = ?update.status,
+      role = ?update.role,
+      "[AUDIT] User status/role changed"
+    );
+
+    Ok(Json(ApiResponse::success(
+        updated,
+        "admin_user_updated".to_string(),
+    )))
+}
+
+/// Confirmation of a data erasure
+#[derive(Debug, Default, Deserialize)]
+pub struct EraseUserDataQuery {
+    /// Must be `erase-{user_id}`, naming the user a second time so a mistyped or replayed
+    /// URL can't erase someone else
+    pub confirm: Option<String>,
+}
+
+/// Erase a user's personal data: sessions, uploaded files, message contents and profile.
+/// Messages remain as tombstones from "Deleted User". Re-sending resumes an interrupted
+/// erasure (workspace admins only, audited)
+#[instrument(skip(state, query), fields(admin_id = %user.id, user_id = %user_id))]
+pub async fn erase_user_data_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(user_id): Path<i64>,
+    Query(query): Query<EraseUserDataQuery>,
+) -> Result<Json<ApiResponse<UserErasure>>, AppError> {
+    ensure_workspace_admin(&state, &user).await?;
+
+    if query.confirm.as_deref() != Some(format!("erase-{}", user_id).as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Confirm the erasure with ?confirm=erase-{}",
+            user_id
+        )));
+    }
+    if user_id == i64::from(user.id) {
+        return Err(AppError::BadRequest(
+            "Admins cannot erase their own data".to_string(),
+        ));
+    }
+    let workspace = WorkspaceRepositoryImpl::new(state.pool())
+        .find_by_id(user.workspace_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(vec!["Workspace not found".to_string()]))?;
+    if workspace.owner_id == UserId(user_id) {
+        return Err(AppError::Forbidden(
+            "The workspace owner cannot be erased".to_string(),
+        ));
+    }
+    let in_workspace = UserRepositoryImpl::new(state.pool())
+        .find_by_id_ext(UserId(user_id))
+        .await?
+        .is_some_and(|target| target.workspace_id == user.workspace_id);
+    if !in_workspace {
+        return Err(AppError::NotFound(vec![format!(
+            "User {} not found in your workspace",
+            user_id
+        )]));
+    }
+
+    let erasure = UserErasureService::from_state(&state)?
+        .erase(user_id, i64::from(user.workspace_id), i64::from(user.id))
+        .await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      user_id = %user_id,
+      files_deleted = erasure.files_deleted,
+      messages_tombstoned = erasure.messages_tombstoned,
+      "[AUDIT] User data erased"
+    );
+
+    Ok(Json(ApiResponse::success(
+        erasure,
+        "user_data_erased".to_string(),
+    )))
+}
+
+/// Create accounts in the admin's workspace from a list of `{email, fullname, initial_role}`,
+/// reporting each row by index. New accounts get a temporary password, returned only in this
+/// response (workspace admins only, audited)
+#[instrument(skip(state, rows), fields(admin_id = %user.id, rows = rows.len()))]
+pub async fn import_users_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(rows): Json<Vec<UserImportRow>>,
+) -> Result<Json<ApiResponse<BatchResponse<ImportedUser>>>, AppError> {
+    ensure_workspace_admin(&state, &user).await?;
+
+    let outcome = UserImportService::from_state(&state)
+        .import(user.workspace_id, rows)
+        .await?;
+    if !outcome.succeeded.is_empty() {
+        state
+            .response_cache()
+            .invalidate_workspace(user.workspace_id)
+            .await;
+    }
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      created = ?outcome.succeeded.iter().map(|item| item.data.user.id).collect::<Vec<_>>(),
+      failed = outcome.stats.failed,
+      "[AUDIT] Users imported"
+    );
+
+    Ok(Json(ApiResponse::success(
+        outcome,
+        "users_imported".to_string(),
+    )))
+}
+
+/// Requester must own their workspace or hold the `admin` role in it
+async fn ensure_workspace_admin(state: &AppState, user: &AuthUser) -> Result<(), AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::user::admin::UserRole;
+    use fechatter_core::User;
+
+    /// Moves the users into a fresh workspace owned by the first of them
+    async fn isolated_workspace(state: &AppState, users: &[User]) -> anyhow::Result<Vec<User>> {
+        let workspaces = WorkspaceRepositoryImpl::new(state.pool());
+        let workspace = workspaces
+            .find_or_create_by_name(&format!("Admin Users {}", uuid::Uuid::new_v4()))
+            .await?;
+        let user_repo = UserRepositoryImpl::new(state.pool());
+        let mut moved = Vec::with_capacity(users.len());
+        for user in users {
+            moved.push(user_repo.switch_workspace(user.id, workspace.id).await?);
+        }
+        workspaces.update_owner(workspace.id, users[0].id).await?;
+        Ok(moved)
+    }
+
+    /// Users found and the `X-Cache` header of the response
+    async fn search_with_cache_status(
+        state: &AppState,
+        admin: &User,
+        query: AdminUserSearchQuery,
+    ) -> Result<(Vec<AdminUserView>, String), AppError> {
+        let response = search_users_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(admin)),
+            Query(query),
+            PageParams::default(),
+        )
+        .await?;
+        let cache_status = response.headers()[crate::handlers::conditional::X_CACHE]
+            .to_str()
+            .expect("ascii header")
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body");
+        let response: crate::dtos::core::ListResponse<AdminUserView> =
+            serde_json::from_slice(&body).expect("admin user page");
+        Ok((response.data.expect("page").data, cache_status))
+    }
+
+    async fn search(
+        state: &AppState,
+        admin: &User,
+        query: AdminUserSearchQuery,
+    ) -> Result<Vec<AdminUserView>, AppError> {
+        Ok(search_with_cache_status(state, admin, query).await?.0)
+    }
+
+    #[tokio::test]
+    async fn search_should_filter_by_status_and_text() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(3).await;
+        let users = isolated_workspace(&state, &users).await?;
+        let (owner, member, suspended) = (&users[0], &users[1], &users[2]);
+
+        let Json(response) = update_user_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(owner)),
+            Path(i64::from(suspended.id)),
+            Json(AdminUserUpdate {
+                status: Some(UserStatus::Suspended),
+                role: None,
+            }),
+        )
+        .await?;
+        assert_eq!(response.data.expect("user").status, UserStatus::Suspended);
+
+        let found = search(
+            &state,
+            owner,
+            AdminUserSearchQuery {
+                q: None,
+                status: Some(UserStatus::Suspended),
+            },
+        )
+        .await?;
+        assert_eq!(
+            found.iter().map(|u| u.id).collect::<Vec<_>>(),
+            vec![suspended.id]
+        );
+
+        let active = search(
+            &state,
+            owner,
+            AdminUserSearchQuery {
+                q: None,
+                status: Some(UserStatus::Active),
+            },
+        )
+        .await?;
+        assert_eq!(active.len(), 2);
+        assert!(active.iter().all(|u| u.status == UserStatus::Active));
+
+        let by_email = search(
+            &state,
+            owner,
+            AdminUserSearchQuery {
+                q: Some(member.email.to_uppercase()),
+                status: None,
+            },
+        )
+        .await?;
+        assert_eq!(by_email.len(), 1);
+        assert_eq!(by_email[0].id, member.id);
+        assert_eq!(by_email[0].role, UserRole::Member);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn suspended_users_should_not_sign_in() -> anyhow::Result<()> {
+        use fechatter_core::{error::CoreError, SigninService, SigninUser};
+
+        let (state, users) = crate::setup_test_users!(2).await;
+        let users = isolated_workspace(&state, &users).await?;
+        let (owner, member) = (&users[0], &users[1]);
+        let signin = SigninUser {
+            email: member.email.clone(),
+            password: "password".to_string(),
+        };
+        assert!(state.signin(&signin, None).await?.is_some());
+
+        update_user_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(owner)),
+            Path(i64::from(member.id)),
+            Json(AdminUserUpdate {
+                status: Some(UserStatus::Suspended),
+                role: None,
+            }),
+        )
+        .await?;
+        assert!(matches!(
+            state.signin(&signin, None).await,
+            Err(CoreError::Unauthorized(_))
+        ));
+
+        update_user_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(owner)),
+            Path(i64::from(member.id)),
+            Json(AdminUserUpdate {
+                status: Some(UserStatus::Active),
+                role: None,
+            }),
+        )
+        .await?;
+        assert!(state.signin(&signin, None).await?.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn non_admins_should_be_rejected_until_promoted() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(3).await;
+        let users = isolated_workspace(&state, &users).await?;
+        let (owner, member, other) = (&users[0], &users[1], &users[2]);
+
+        let result = search(&state, member, AdminUserSearchQuery::default()).await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = update_user_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(member)),
+            Path(i64::from(other.id)),
+            Json(AdminUserUpdate {
+                status: Some(UserStatus::Suspended),
+                role: None,
+            }),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        update_user_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(owner)),
+            Path(i64::from(member.id)),
+            Json(AdminUserUpdate {
+                status: None,
+                role: Some(UserRole::Admin),
+            }),
+        )
+        .await?;
+        assert_eq!(
+            search(&state, member, AdminUserSearchQuery::default())
+                .await?
+                .len(),
+            3
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repeated_search_should_be_cached_until_a_user_changes() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let users = isolated_workspace(&state, &users).await?;
+        let (owner, member) = (&users[0], &users[1]);
+        let suspended_only = || AdminUserSearchQuery {
+            q: None,
+            status: Some(UserStatus::Suspended),
+        };
+        // Entries are only kept when Redis is available
+        let repeat_status = if state.cache_service().is_some() {
+            "HIT"
+        } else {
+            "MISS"
+        };
+
+        let (found, cache_status) = search_with_cache_status(&state, owner, suspended_only()).await?;
+        assert!(found.is_empty());
+        assert_eq!(cache_status, "MISS");
+        let (found, cache_status) = search_with_cache_status(&state, owner, suspended_only()).await?;
+        assert!(found.is_empty());
+        assert_eq!(cache_status, repeat_status);
+
+        update_user_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(owner)),
+            Path(i64::from(member.id)),
+            Json(AdminUserUpdate {
+                status: Some(UserStatus::Suspended),
+                role: None,
+            }),
+        )
+        .await?;
+
+        let (found, cache_status) = search_with_cache_status(&state, owner, suspended_only()).await?;
+        assert_eq!(cache_status, "MISS");
+        assert_eq!(
+            found.iter().map(|u| u.id).collect::<Vec<_>>(),
+            vec![member.id]
+        );
+        Ok(())
+    }
+
+    async fn erase(
+        state: &AppState,
+        admin: &User,
+        user_id: i64,
+        confirm: Option<String>,
+    ) -> Result<UserErasure, AppError> {
+        let Json(response) = erase_user_data_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(admin)),
+            Path(user_id),
+            Query(EraseUserDataQuery { confirm }),
+        )
+        .await?;
+        Ok(response.data.expect("erasure"))
+    }
+
+    #[tokio::test]
+    async fn erasing_a_user_should_remove_pii_and_keep_messages_as_tombstones() -> anyhow::Result<()>
+    {
+        use crate::services::application::workers::profile::erasure::{
+            ErasureStep, ERASED_USER_NAME,
+        };
+
+        let (state, users) = crate::setup_test_users!(2).await;
+        let users = isolated_workspace(&state, &users).await?;
+        let (owner, member) = (&users[0], &users[1]);
+        let member_id = i64::from(member.id);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Erasure {}", uuid::Uuid::new_v4())),
+                None,
+                owner.id,
+                vec![member.id],
+            )
+            .await?;
+        let send = |sender: i64, content: &'static str| {
+            let pool = state.pool();
+            async move {
+                sqlx::query_scalar::<_, i64>(
+                    r#"INSERT INTO messages (chat_id, sender_id, content, files)
+                       VALUES ($1, $2, $3, ARRAY['/files/not-stored.txt'])
+                       RETURNING id"#,
+                )
+                .bind(i64::from(chat.id))
+                .bind(sender)
+                .bind(content)
+                .fetch_one(&*pool)
+                .await
+            }
+        };
+        let erased_ids = [
+            send(member_id, "my phone is 555-0100").await?,
+            send(member_id, "see attached").await?,
+        ];
+        let kept_id = send(i64::from(owner.id), "noted").await?;
+
+        let unconfirmed = erase(&state, owner, member_id, Some("erase-0".to_string())).await;
+        assert!(matches!(unconfirmed, Err(AppError::BadRequest(_))));
+
+        let confirm = || Some(format!("erase-{}", member_id));
+        let erasure = erase(&state, owner, member_id, confirm()).await?;
+        assert_eq!(erasure.step, ErasureStep::Done);
+        assert_eq!(erasure.messages_tombstoned, 2);
+
+        let (fullname, email, phone): (String, String, Option<String>) =
+            sqlx::query_as("SELECT fullname, email, phone FROM users WHERE id = $1")
+                .bind(member_id)
+                .fetch_one(&*state.pool())
+                .await?;
+        assert_eq!(fullname, ERASED_USER_NAME);
+        assert_ne!(email, member.email);
+        assert_eq!(phone, None);
+
+        // Signing in as the erased account is refused like a wrong password, not a server error
+        let signin = fechatter_core::SigninUser::new(&email, "password");
+        assert!(fechatter_core::SigninService::signin(&state, &signin, None)
+            .await?
+            .is_none());
+
+        let messages: Vec<(i64, i64, String, Vec<String>, bool)> = sqlx::query_as(
+            r#"SELECT id, sender_id, content, files, deleted_at IS NOT NULL
+               FROM messages WHERE chat_id = $1 ORDER BY id"#,
+        )
+        .bind(i64::from(chat.id))
+        .fetch_all(&*state.pool())
+        .await?;
+        assert_eq!(messages.len(), 3);
+        for (id, sender_id, content, files, deleted) in &messages {
+            if *id == kept_id {
+                assert_eq!(content, "noted");
+                assert!(!deleted);
+            } else {
+                assert!(erased_ids.contains(id));
+                assert_eq!(*sender_id, member_id);
+                assert!(content.is_empty() && files.is_empty() && *deleted);
+            }
+        }
+
+        // Already erased: nothing more to do
+        let again = erase(&state, owner, member_id, confirm()).await?;
+        assert_eq!(again.step, ErasureStep::Done);
+        assert_eq!(again.messages_tombstoned, 2);
+        assert_eq!(again.completed_at, erasure.completed_at);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn importing_users_should_report_each_row() -> anyhow::Result<()> {
+        use crate::domains::user::password::verify_password;
+
+        let (state, users) = crate::setup_test_users!(2).await;
+        let users = isolated_workspace(&state, &users).await?;
+        let (owner, existing) = (&users[0], &users[1]);
+        let unique = uuid::Uuid::new_v4().simple().to_string();
+        let fresh = format!("new-{}@acme.test", unique);
+        let row = |email: &str, role: Option<UserRole>| UserImportRow {
+            email: email.to_string(),
+            fullname: "Imported User".to_string(),
+            initial_role: role,
+        };
+
+        let Json(response) = import_users_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(owner)),
+            Json(vec![
+                row(&fresh, Some(UserRole::Admin)),
+                row(&existing.email, None),
+                row(&fresh.to_uppercase(), None),
+                row("not-an-email", None),
+            ]),
+        )
+        .await?;
+        let outcome = response.data.expect("batch outcome");
+
+        assert_eq!(outcome.stats.total, 4);
+        assert_eq!(outcome.succeeded.len(), 1);
+        let created = &outcome.succeeded[0];
+        assert_eq!(created.index, 0);
+        assert_eq!(created.data.user.email, fresh);
+        assert_eq!(created.data.user.role, UserRole::Admin);
+
+        let failures: Vec<(usize, &str)> = outcome
+            .failed
+            .iter()
+            .map(|failure| (failure.index, failure.error.code.as_str()))
+            .collect();
+        assert_eq!(
+            failures,
+            vec![(1, "CONFLICT"), (2, "CONFLICT"), (3, "VALIDATION_ERROR")]
+        );
+
+        // The account joined the admin's workspace and signs in with the temporary password
+        let (workspace_id, password_hash): (i64, String) =
+            sqlx::query_as("SELECT workspace_id, password_hash FROM users WHERE email = $1")
+                .bind(&fresh)
+                .fetch_one(&*state.pool())
+                .await?;
+        assert_eq!(workspace_id, owner.workspace_id.0);
+        assert!(verify_password(
+            &created.data.temporary_password,
+            &password_hash
+        )?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn importing_past_the_member_cap_should_fail_the_extra_rows() -> anyhow::Result<()> {
+        use crate::domains::workspace::workspace_domain::WorkspaceConfig;
+
+        let (state, users) = crate::setup_test_users!(2).await;
+        let users = isolated_workspace(&state, &users).await?;
+        let config = WorkspaceConfig {
+            max_members: 3,
+            ..WorkspaceConfig::default()
+        };
+        let rows = (0..2)
+            .map(|n| UserImportRow {
+                email: format!("cap-{}-{}@acme.test", n, uuid::Uuid::new_v4().simple()),
+                fullname: format!("Cap {}", n),
+                initial_role: None,
+            })
+            .collect();
+
+        let outcome = UserImportService::from_state(&state)
+            .with_workspace_config(&config)
+            .import(users[0].workspace_id, rows)
+            .await?;
+
+        assert_eq!(outcome.succeeded.len(), 1);
+        assert_eq!(outcome.failed[0].index, 1);
+        assert!(outcome.failed[0].error.message.contains("member limit of 3"));
+        Ok(())
+    }
+}
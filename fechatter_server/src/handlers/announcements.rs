@@ -0,0 +1,116 @@
+//! # System Announcement Handler
+//!
+//! **Responsibility**: Let configured admins push announcements (e.g. maintenance banners)
+//! **Delivery**: Published to NATS as `fechatter.system.announcement`; notify_server fans it
+//! out to every connected client, or only to one workspace's users when targeted
+
+use axum::{extract::Extension, response::Json};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::dtos::core::ApiResponse;
+use crate::handlers::maintenance::ensure_maintenance_admin;
+use crate::{AppError, AppState};
+use fechatter_core::contracts::events::{
+    AnnouncementSeverity, EventVersion, SystemAnnouncementEvent,
+};
+use fechatter_core::{AuthUser, UserId, WorkspaceId};
+
+/// Longest announcement title accepted
+const MAX_TITLE_LEN: usize = 120;
+
+/// Longest announcement body accepted
+const MAX_MESSAGE_LEN: usize = 2000;
+
+/// Announcement request
+#[derive(Debug, Deserialize)]
+pub struct CreateAnnouncementRequest {
+    pub title: String,
+    pub message: String,
+    #[serde(default)]
+    pub severity: AnnouncementSeverity,
+    /// Limit delivery to this workspace; omit to reach every connected user
+    pub workspace_id: Option<i64>,
+}
+
+impl CreateAnnouncementRequest {
+    fn validate(&self) -> Result<(), AppError> {
+        let title = self.title.trim();
+        if title.is_empty() || title.chars().count() > MAX_TITLE_LEN {
+            return Err(AppError::ValidationError(format!(
+                "title must be 1-{} characters",
+                MAX_TITLE_LEN
+            )));
+        }
+        let message = self.message.trim();
+        if message.is_empty() || message.chars().count() > MAX_MESSAGE_LEN {
+            return Err(AppError::ValidationError(format!(
+                "message must be 1-{} characters",
+                MAX_MESSAGE_LEN
+            )));
+        }
+        if self.workspace_id.is_some_and(|id| id <= 0) {
+            return Err(AppError::ValidationError(
+                "workspace_id must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Published announcement
+#[derive(Debug, Serialize)]
+pub struct AnnouncementResponse {
+    pub id: Uuid,
+    pub workspace_id: Option<i64>,
+    pub published_at: chrono::DateTime<Utc>,
+}
+
+/// Publish a system announcement to connected clients (maintenance admins only, audited)
+#[instrument(skip(state, request), fields(admin_id = %user.id))]
+pub async fn create_announcement_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<CreateAnnouncementRequest>,
+) -> Result<Json<ApiResponse<AnnouncementResponse>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+    request.validate()?;
+
+    let publisher = state
+        .enhanced_event_publisher()
+        .filter(|publisher| publisher.is_connected())
+        .ok_or_else(|| {
+            AppError::ServiceUnavailable("Realtime notifications are not available".to_string())
+        })?;
+
+    let announcement = SystemAnnouncementEvent {
+        version: EventVersion::default(),
+        id: Uuid::new_v4(),
+        title: request.title.trim().to_string(),
+        message: request.message.trim().to_string(),
+        severity: request.severity,
+        workspace_id: request.workspace_id.map(WorkspaceId),
+        sent_by: UserId::from(user.id),
+        occurred_at: Utc::now(),
+    };
+    publisher.publish_system_announcement(&announcement).await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      announcement_id = %announcement.id,
+      workspace_id = ?request.workspace_id,
+      "[AUDIT] System announcement published"
+    );
+
+    Ok(Json(ApiResponse::success(
+        AnnouncementResponse {
+            id: announcement.id,
+            workspace_id: request.workspace_id,
+            published_at: announcement.occurred_at,
+        },
+        "announcement_published".to_string(),
+    )))
+}
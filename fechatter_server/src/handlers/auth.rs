@@ -3,11 +3,14 @@
 //! **Responsibility**: HTTP authentication handling, delegating to application services
 //! **Principles**: Simple design, single responsibility
 
-use crate::dtos::core::ApiResponse;
+use crate::dtos::core::{ApiResponse, ConversionContext};
+use crate::dtos::get_dto_manager;
 use crate::dtos::models::requests::auth::{LoginRequest, RegisterRequest};
 use crate::dtos::models::responses::auth::{
-    LoginResponse, LogoutResponse, RefreshTokenResponse, RegisterResponse,
+    LoginResponse, LogoutOthersResponse, LogoutResponse, RefreshTokenResponse, RegisterResponse,
 };
+use crate::handlers::auth_context::RequestAuthContext;
+use crate::services::infrastructure::cache::CacheKeyBuilder;
 use crate::{error::AppError, AppState};
 use axum::{
     extract::State,
@@ -17,16 +20,13 @@ use axum::{
 };
 use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
 use chrono::{DateTime, Utc};
-use fechatter_core::models::{AuthUser, CreateUser};
+use fechatter_core::models::AuthUser;
 use fechatter_core::{
-    contracts::AuthContext,
     models::jwt::ACCESS_TOKEN_EXPIRATION,
     models::jwt::{LogoutService, RefreshTokenService, SigninService, SignupService},
-    SigninUser,
 };
 use std::time::Instant;
 use tracing::instrument;
-use validator::Validate;
 
 // =============================================================================
 // UTILITY FUNCTIONS
@@ -114,23 +114,6 @@ fn clear_refresh_token_cookie(headers: &mut HeaderMap) -> Result<(), AppError> {
     }
 }
 
-/// Extract AuthContext from request headers
-fn extract_auth_context(headers: &HeaderMap) -> Option<AuthContext> {
-    let user_agent = headers
-        .get("user-agent")
-        .and_then(|h| h.to_str().ok())
-        .map(String::from);
-    let ip_address = headers
-        .get("x-forwarded-for")
-        .and_then(|h| h.to_str().ok())
-        .map(String::from);
-
-    Some(AuthContext {
-        user_agent,
-        ip_address,
-    })
-}
-
 // =============================================================================
 // HANDLERS
 // =============================================================================
@@ -140,38 +123,43 @@ fn extract_auth_context(headers: &HeaderMap) -> Option<AuthContext> {
 pub async fn signup_handler(
     Extension(state): Extension<AppState>,
     headers: HeaderMap,
+    RequestAuthContext(auth_context): RequestAuthContext,
     Json(request): Json<RegisterRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let start_time = Instant::now();
     let request_id = extract_request_id(&headers);
-    let auth_context = extract_auth_context(&headers);
 
-    if let Err(e) = request.validate() {
-        return Ok((
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<()>::error(
-                crate::dtos::core::ApiError {
-                    code: "VALIDATION_ERROR".to_string(),
-                    message: "Invalid input".to_string(),
-                    details: Some(e.to_string()),
-                    field: None,
-                    stack: vec![],
-                    suggestion: Some("Please check your input and try again".to_string()),
-                    help_url: Some("/docs/auth/signup".to_string()),
-                },
-                request_id,
-            )),
-        )
-            .into_response());
+    if let Some(limiter) = state.rate_limiters().signup() {
+        let client = auth_context
+            .ip_address
+            .clone()
+            .unwrap_or_else(|| request.email.to_lowercase());
+        limiter
+            .enforce(&format!("rate_limit:signup:{}", client))
+            .await?;
     }
+    let auth_context = Some(auth_context);
 
-    let create_user = CreateUser {
-        fullname: request.fullname,
-        email: request.email,
-        password: request.password,
-        workspace: request
-            .workspace_name
-            .unwrap_or_else(|| "Default".to_string()),
+    let create_user = match get_dto_manager().convert_request(&request, &ConversionContext::new()) {
+        Ok(create_user) => create_user,
+        Err(e) => {
+            return Ok((
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<()>::error(
+                    crate::dtos::core::ApiError {
+                        code: "VALIDATION_ERROR".to_string(),
+                        message: "Invalid input".to_string(),
+                        details: e.details,
+                        field: e.failed_field,
+                        stack: vec![],
+                        suggestion: Some("Please check your input and try again".to_string()),
+                        help_url: Some("/docs/auth/signup".to_string()),
+                    },
+                    request_id,
+                )),
+            )
+                .into_response());
+        }
     };
 
     let auth_service =
@@ -264,32 +252,40 @@ pub async fn signup_handler(
 pub async fn signin_handler(
     Extension(state): Extension<AppState>,
     headers: HeaderMap,
+    RequestAuthContext(auth_context): RequestAuthContext,
     Json(request): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let start_time = Instant::now();
     let request_id = extract_request_id(&headers);
-    let auth_context = extract_auth_context(&headers);
+    let auth_context = Some(auth_context);
 
-    if let Err(e) = request.validate() {
-        return Ok((
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<()>::error(
-                crate::dtos::core::ApiError {
-                    code: "VALIDATION_ERROR".to_string(),
-                    message: "Invalid input".to_string(),
-                    details: Some(e.to_string()),
-                    field: None,
-                    stack: vec![],
-                    suggestion: Some("Please check your email and password".to_string()),
-                    help_url: Some("/docs/auth/login".to_string()),
-                },
-                request_id,
-            )),
-        )
-            .into_response());
-    }
+    let signin_user = match get_dto_manager().convert_request(&request, &ConversionContext::new()) {
+        Ok(signin_user) => signin_user,
+        Err(e) => {
+            return Ok((
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<()>::error(
+                    crate::dtos::core::ApiError {
+                        code: "VALIDATION_ERROR".to_string(),
+                        message: "Invalid input".to_string(),
+                        details: e.details,
+                        field: e.failed_field,
+                        stack: vec![],
+                        suggestion: Some("Please check your email and password".to_string()),
+                        help_url: Some("/docs/auth/login".to_string()),
+                    },
+                    request_id,
+                )),
+            )
+                .into_response());
+        }
+    };
 
-    let signin_user = SigninUser::new(&request.email, &request.password);
+    if let Some(limiter) = state.rate_limiters().login() {
+        limiter
+            .enforce(&CacheKeyBuilder::login_rate_limit(&request.email))
+            .await?;
+    }
 
     let auth_service =
         crate::services::application::workers::auth::AuthUserService::from_app_state(&state);
@@ -393,12 +389,13 @@ pub async fn signin_handler(
 pub async fn refresh_token_handler(
     Extension(state): Extension<AppState>,
     headers: HeaderMap,
+    RequestAuthContext(auth_context): RequestAuthContext,
     cookies: CookieJar,
     auth_user: Option<Extension<AuthUser>>,
 ) -> Result<impl IntoResponse, AppError> {
     let start_time = Instant::now();
     let request_id = extract_request_id(&headers);
-    let auth_context = extract_auth_context(&headers);
+    let auth_context = Some(auth_context);
 
     let tokens = if let Some(Extension(user)) = auth_user {
         let refresh_token = extract_refresh_token(&cookies, &headers)?;
@@ -524,6 +521,39 @@ pub async fn logout_all_handler(
     Ok((StatusCode::OK, response_headers, Json(api_response)).into_response())
 }
 
+/// Logout Other Sessions Handler
+///
+/// Revokes every refresh token of the user except the one presented, keeping the
+/// current session signed in.
+pub async fn logout_others_handler(
+    Extension(state): Extension<AppState>,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let start_time = Instant::now();
+    let request_id = extract_request_id(&headers);
+    let refresh_token = extract_refresh_token(&cookies, &headers)?;
+
+    let auth_service =
+        crate::services::application::workers::auth::AuthUserService::from_app_state(&state);
+    let revoked_sessions = auth_service
+        .logout_others(auth_user.id, &refresh_token)
+        .await?;
+
+    let response = LogoutOthersResponse {
+        message: "Logged out from other sessions successfully".to_string(),
+        success: true,
+        revoked_sessions,
+        logout_time: chrono::Utc::now(),
+    };
+
+    let api_response = ApiResponse::success(response, request_id)
+        .with_duration(start_time.elapsed().as_millis() as u64);
+
+    Ok((StatusCode::OK, Json(api_response)).into_response())
+}
+
 /// Extract refresh token from cookies or Authorization header
 fn extract_refresh_token(cookies: &CookieJar, headers: &HeaderMap) -> Result<String, AppError> {
     if let Some(cookie) = cookies.get("refresh_token") {
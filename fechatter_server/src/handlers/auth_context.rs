@@ -0,0 +1,231 @@
+//! # Auth Context Extractor
+//!
+//! **Responsibility**: Build the `AuthContext` that binds refresh tokens to a client
+//! **Client IP**: The TCP peer address, unless the peer is one of `server.trusted_proxies`; then
+//! the nearest `X-Forwarded-For` hop that isn't a trusted proxy, as
+//! `fechatter_core::utils::client_ip` resolves it
+
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts},
+};
+use fechatter_core::{contracts::AuthContext, utils::client_ip};
+
+use crate::{AppError, AppState};
+
+/// `AuthContext` derived from the request's User-Agent and client IP
+#[derive(Debug, Clone)]
+pub struct RequestAuthContext(pub AuthContext);
+
+impl RequestAuthContext {
+    /// Derive the context from request parts, trusting forwarding headers only from `trusted_proxies`
+    pub fn from_parts_with(parts: &Parts, trusted_proxies: &[IpAddr]) -> Self {
+        let user_agent = parts
+            .headers
+            .get(header::USER_AGENT)
+            .and_then(|h| h.to_str().ok())
+            .map(String::from);
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let ip_address = client_ip(peer, &parts.headers, trusted_proxies).map(|ip| ip.to_string());
+
+        Self(AuthContext {
+            user_agent,
+            ip_address,
+        })
+    }
+
+    pub fn into_inner(self) -> AuthContext {
+        self.0
+    }
+}
+
+impl<S> FromRequestParts<S> for RequestAuthContext
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let state = parts
+            .extensions
+            .get::<AppState>()
+            .ok_or_else(|| AppError::Internal("AppState extension missing".to_string()))?;
+
+        Ok(Self::from_parts_with(
+            parts,
+            &state.config.server.trusted_proxies,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::auth::token_repository::auth_context_matches;
+    use crate::AppConfig;
+    use axum::http::Request;
+    use fechatter_core::models::jwt::{
+        RefreshToken, RefreshTokenRepository, ReplaceTokenPayload, StoreTokenPayload, TokenManager,
+        UserClaims,
+    };
+    use fechatter_core::{error::CoreError, UserId, UserStatus, WorkspaceId};
+    use std::sync::{Arc, Mutex};
+
+    const PROXY: &str = "10.0.0.2";
+    const CLIENT: &str = "203.0.113.7";
+
+    fn parts(peer: &str, forwarded_for: Option<&str>) -> Parts {
+        let mut request = Request::builder()
+            .uri("/api/signin")
+            .header(header::USER_AGENT, "fechatter-test/1.0");
+        if let Some(forwarded_for) = forwarded_for {
+            request = request.header("x-forwarded-for", forwarded_for);
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        let peer: SocketAddr = format!("{}:443", peer).parse().unwrap();
+        parts.extensions.insert(ConnectInfo(peer));
+        parts
+    }
+
+    fn trusted() -> Vec<IpAddr> {
+        vec![PROXY.parse().unwrap()]
+    }
+
+    /// Keeps the payload of every stored refresh token
+    #[derive(Default)]
+    struct RecordingTokenRepo {
+        stored: Mutex<Vec<StoreTokenPayload>>,
+    }
+
+    #[async_trait::async_trait]
+    impl RefreshTokenRepository for RecordingTokenRepo {
+        async fn find_by_token(&self, _raw_token: &str) -> Result<Option<RefreshToken>, CoreError> {
+            Ok(None)
+        }
+
+        async fn replace(&self, _payload: ReplaceTokenPayload) -> Result<RefreshToken, CoreError> {
+            Err(CoreError::Internal("Not implemented".to_string()))
+        }
+
+        async fn revoke(&self, _token_id: i64) -> Result<(), CoreError> {
+            Ok(())
+        }
+
+        async fn revoke_all_for_user(&self, _user_id: UserId) -> Result<(), CoreError> {
+            Ok(())
+        }
+
+        async fn create(&self, payload: StoreTokenPayload) -> Result<RefreshToken, CoreError> {
+            let entity = RefreshToken {
+                id: 1,
+                user_id: payload.user_id,
+                token_hash: "hash".to_string(),
+                expires_at: payload.expires_at,
+                issued_at: chrono::Utc::now(),
+                revoked: false,
+                replaced_by: None,
+                user_agent: payload.user_agent.clone(),
+                ip_address: payload.ip_address.clone(),
+                absolute_expires_at: payload.absolute_expires_at,
+            };
+            self.stored.lock().unwrap().push(payload);
+            Ok(entity)
+        }
+    }
+
+    #[test]
+    fn client_ip_should_come_from_forwarded_for_behind_trusted_proxy() {
+        let context = RequestAuthContext::from_parts_with(
+            &parts(PROXY, Some(&format!("{}, {}", CLIENT, PROXY))),
+            &trusted(),
+        )
+        .into_inner();
+
+        assert_eq!(context.ip_address.as_deref(), Some(CLIENT));
+        assert_eq!(context.user_agent.as_deref(), Some("fechatter-test/1.0"));
+    }
+
+    #[test]
+    fn client_written_forwarded_for_hops_should_be_ignored() {
+        let context = RequestAuthContext::from_parts_with(
+            &parts(PROXY, Some(&format!("198.51.100.9, {}, {}", CLIENT, PROXY))),
+            &trusted(),
+        )
+        .into_inner();
+
+        assert_eq!(context.ip_address.as_deref(), Some(CLIENT));
+    }
+
+    #[test]
+    fn forwarded_for_from_untrusted_peer_should_be_ignored() {
+        let context =
+            RequestAuthContext::from_parts_with(&parts("198.51.100.9", Some(CLIENT)), &trusted())
+                .into_inner();
+
+        assert_eq!(context.ip_address.as_deref(), Some("198.51.100.9"));
+    }
+
+    #[test]
+    fn trusted_proxy_without_forwarding_headers_should_fall_back_to_peer() {
+        let context =
+            RequestAuthContext::from_parts_with(&parts(PROXY, None), &trusted()).into_inner();
+
+        assert_eq!(context.ip_address.as_deref(), Some(PROXY));
+    }
+
+    #[tokio::test]
+    async fn issued_refresh_token_should_be_bound_to_extracted_client() {
+        let config = AppConfig::load().expect("config");
+        let repo = Arc::new(RecordingTokenRepo::default());
+        let token_manager = TokenManager::from_config(&config.auth, repo.clone()).unwrap();
+        let context = RequestAuthContext::from_parts_with(
+            &parts(PROXY, Some(&format!("{}, {}", CLIENT, PROXY))),
+            &trusted(),
+        )
+        .into_inner();
+        let claims = UserClaims {
+            id: UserId(1),
+            workspace_id: WorkspaceId(1),
+            fullname: "Test User".to_string(),
+            email: "test@acme.test".to_string(),
+            status: UserStatus::Active,
+            created_at: chrono::Utc::now(),
+        };
+
+        // Same call signup and signin make with the handler's context
+        token_manager
+            .internal_generate_auth_tokens(
+                &claims,
+                context.user_agent.clone(),
+                context.ip_address.clone(),
+            )
+            .await
+            .unwrap();
+
+        let stored = repo.stored.lock().unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].ip_address.as_deref(), Some(CLIENT));
+
+        // A refresh through the same proxy matches; a spoofed direct request does not
+        let direct =
+            RequestAuthContext::from_parts_with(&parts("198.51.100.9", Some(CLIENT)), &trusted())
+                .into_inner();
+        assert!(auth_context_matches(
+            stored[0].user_agent.as_deref(),
+            stored[0].ip_address.as_deref(),
+            context.user_agent.as_deref(),
+            context.ip_address.as_deref(),
+        ));
+        assert!(!auth_context_matches(
+            stored[0].user_agent.as_deref(),
+            stored[0].ip_address.as_deref(),
+            direct.user_agent.as_deref(),
+            direct.ip_address.as_deref(),
+        ));
+    }
+}
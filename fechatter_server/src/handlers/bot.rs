@@ -1,21 +1,22 @@
 use crate::{
     dtos::bot::{
-        DetectLanguageRequest, DetectLanguageResponse, Language, SupportedLanguagesResponse,
-        TranslateRequest, TranslateResponse,
+        DetectLanguageRequest, DetectLanguageResponse, Language, SummarizeRequest,
+        SummarizeResponse, SupportedLanguagesResponse, TranslateRequest, TranslateResponse,
     },
     error::AppError,
+    services::ai::core::AiServiceAdapter,
     AppState,
 };
 use axum::{extract::Extension, Json};
 use chrono;
+use fechatter_core::contracts::events::{AiStreamChunkEvent, EventVersion};
 use fechatter_core::models::AuthUser;
+use fechatter_core::MessageId;
+use futures::StreamExt;
 use reqwest;
 use serde_json::json;
 use sqlx::Row;
-use tracing::{debug, error, info};
-
-/// Daily quota limit per user
-const DAILY_QUOTA_LIMIT: i32 = 20;
+use tracing::{debug, error, info, warn};
 
 /// External translation service configuration
 const TRANSLATION_API_BASE: &str = "http://45.77.178.85:8000";
@@ -31,13 +32,15 @@ pub async fn translate_message_handler(
         auth_user.id, payload.message_id, payload.target_language
     );
 
-    // Check daily quota - convert UserId to i32
+    // Check daily quota (workspace override or global default) - convert UserId to i32
+    let limits = state.workspace_limits().get(auth_user.workspace_id).await;
+    let quota_limit = state.rate_limiters().bot_daily_quota_in(&limits) as i32;
     let user_id = i64::from(auth_user.id) as i32;
     let quota_used = get_user_daily_quota(&state, user_id).await?;
-    if quota_used >= DAILY_QUOTA_LIMIT {
+    if quota_used >= quota_limit {
         return Err(AppError::BadRequest(format!(
             "Daily translation limit exceeded. You have used {}/{} translations today.",
-            quota_used, DAILY_QUOTA_LIMIT
+            quota_used, quota_limit
         )));
     }
 
@@ -48,15 +51,20 @@ pub async fn translate_message_handler(
         return Err(AppError::BadRequest("Message content is empty".to_string()));
     }
 
-    // Call external translation API
-    let translation_result =
-        call_external_translation_api(&message_content, &payload.target_language).await?;
+    // Call external translation API, within the shared AI concurrency cap
+    let translation_result = state
+        .ai_limiter()
+        .run(call_external_translation_api(
+            &message_content,
+            &payload.target_language,
+        ))
+        .await??;
 
     // Increment user quota
     increment_user_quota(&state, user_id).await?;
 
     // Get updated quota info
-    let remaining_quota = DAILY_QUOTA_LIMIT - (quota_used + 1);
+    let remaining_quota = quota_limit - (quota_used + 1);
 
     info!(
         "🤖 [BOT] Translation successful for user {}. Remaining quota: {}",
@@ -70,7 +78,95 @@ pub async fn translate_message_handler(
         confidence: translation_result.confidence.unwrap_or(0.9),
         quota_used: quota_used + 1,
         quota_remaining: remaining_quota,
-        quota_limit: DAILY_QUOTA_LIMIT,
+        quota_limit,
+    }))
+}
+
+/// Summarize a message with the AI provider. The summary is streamed to the requester's SSE
+/// connections as `AiStreamChunk` events while it is generated and returned whole at the end.
+/// A client that disconnects drops this future and with it the stream, which cancels the
+/// provider request
+pub async fn summarize_message_handler(
+    Extension(state): Extension<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<SummarizeRequest>,
+) -> Result<Json<SummarizeResponse>, AppError> {
+    info!(
+        "🤖 [BOT] Summary request from user {} for message {}",
+        auth_user.id, payload.message_id
+    );
+
+    // Summaries count against the same daily bot quota as translations
+    let limits = state.workspace_limits().get(auth_user.workspace_id).await;
+    let quota_limit = state.rate_limiters().bot_daily_quota_in(&limits) as i32;
+    let user_id = i64::from(auth_user.id) as i32;
+    let quota_used = get_user_daily_quota(&state, user_id).await?;
+    if quota_used >= quota_limit {
+        return Err(AppError::BadRequest(format!(
+            "Daily bot limit exceeded. You have used {}/{} requests today.",
+            quota_used, quota_limit
+        )));
+    }
+
+    // Provider tokens also count against the workspace's daily AI token budget
+    let workspace_id = i64::from(auth_user.workspace_id);
+    state
+        .rate_limiters()
+        .ensure_ai_tokens_left(workspace_id)
+        .await?;
+
+    let message_content = get_message_content(&state, payload.message_id, user_id).await?;
+    if message_content.trim().is_empty() {
+        return Err(AppError::BadRequest("Message content is empty".to_string()));
+    }
+
+    // Holds a slot of the shared AI concurrency cap until the stream is done
+    let ai = AiServiceAdapter::from_env()?.with_concurrency_limit(state.ai_limiter().clone());
+    let (pieces, usage) = ai.summarize_stream(&message_content).await?;
+    let mut pieces = std::pin::pin!(pieces);
+
+    let mut chunk = AiStreamChunkEvent {
+        version: EventVersion::default(),
+        stream_id: uuid::Uuid::new_v4(),
+        user_id: auth_user.id,
+        message_id: MessageId::from(payload.message_id as i64),
+        sequence: 0,
+        delta: String::new(),
+        done: false,
+        occurred_at: chrono::Utc::now(),
+    };
+    let mut summary = String::new();
+    while let Some(piece) = pieces.next().await {
+        let piece = piece?;
+        summary.push_str(&piece);
+        chunk.delta = piece;
+        publish_stream_chunk(&state, &mut chunk).await;
+        chunk.sequence += 1;
+    }
+    chunk.delta.clear();
+    chunk.done = true;
+    publish_stream_chunk(&state, &mut chunk).await;
+
+    increment_user_quota(&state, user_id).await?;
+    let usage = usage.get();
+    if let Err(e) = state
+        .rate_limiters()
+        .charge_ai_tokens(workspace_id, u64::from(usage.total_tokens))
+        .await
+    {
+        warn!(
+            "Failed to charge {} AI tokens to workspace {}: {}",
+            usage.total_tokens, workspace_id, e
+        );
+    }
+
+    Ok(Json(SummarizeResponse {
+        stream_id: chunk.stream_id,
+        summary,
+        tokens_used: usage.total_tokens,
+        quota_used: quota_used + 1,
+        quota_remaining: quota_limit - (quota_used + 1),
+        quota_limit,
     }))
 }
 
@@ -170,7 +266,7 @@ pub async fn get_supported_languages_handler(
 
 /// Detect language of given text
 pub async fn detect_language_handler(
-    Extension(_state): Extension<AppState>,
+    Extension(state): Extension<AppState>,
     Extension(_auth_user): Extension<AuthUser>,
     Json(payload): Json<DetectLanguageRequest>,
 ) -> Result<Json<DetectLanguageResponse>, AppError> {
@@ -179,8 +275,11 @@ pub async fn detect_language_handler(
         payload.text
     );
 
-    // Call external language detection API
-    let detected_language = call_external_language_detection(&payload.text).await?;
+    // Call external language detection API, within the shared AI concurrency cap
+    let detected_language = state
+        .ai_limiter()
+        .run(call_external_language_detection(&payload.text))
+        .await??;
 
     Ok(Json(DetectLanguageResponse {
         language: detected_language.language,
@@ -207,6 +306,21 @@ struct LanguageDetectionResult {
     confidence: f32,
 }
 
+/// Forward a piece of a streamed AI response to the requester's SSE connections, stamped now
+async fn publish_stream_chunk(state: &AppState, chunk: &mut AiStreamChunkEvent) {
+    let Some(publisher) = state.enhanced_event_publisher() else {
+        return;
+    };
+
+    chunk.occurred_at = chrono::Utc::now();
+    if let Err(e) = publisher.publish_ai_stream_chunk(chunk).await {
+        warn!(
+            "🤖 [BOT] Failed to publish chunk {} of stream {}: {}",
+            chunk.sequence, chunk.stream_id, e
+        );
+    }
+}
+
 /// Get user's daily quota usage from database
 async fn get_user_daily_quota(state: &AppState, user_id: i32) -> Result<i32, AppError> {
     let pool = state.pool();
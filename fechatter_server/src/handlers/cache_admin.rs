@@ -0,0 +1,255 @@
+//! # Cache Admin Handlers
+//!
+//! **Responsibility**: Let workspace admins force-invalidate the caches of a user, chat or
+//! workspace when drift is suspected, or pre-warm them ahead of a busy event
+//! **Scope**: Redis keys cleared through `DistributedLockCacheInvalidator`, plus the in-memory
+//! chat lists held by the sync cache adapter; warming goes through `CacheWarmupStrategy`
+
+use axum::{extract::Extension, response::Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{info, instrument};
+
+use crate::domains::chat::repository::ChatRepository;
+use crate::domains::messaging::repository::MessageRepository;
+use crate::domains::user::repository::UserRepositoryImpl;
+use crate::dtos::core::ApiResponse;
+use crate::services::infrastructure::cache::{
+    DistributedLockCacheInvalidator, UnifiedCacheService, WarmupTarget,
+};
+use crate::{AppError, AppState};
+use fechatter_core::{AuthUser, UserId};
+
+/// Cache invalidation request; at least one target is required
+#[derive(Debug, Deserialize)]
+pub struct InvalidateCacheRequest {
+    pub user_id: Option<i64>,
+    pub chat_id: Option<i64>,
+    pub workspace_id: Option<i64>,
+}
+
+/// Cache invalidation result
+#[derive(Debug, Serialize)]
+pub struct InvalidateCacheResponse {
+    pub keys_cleared: u64,
+    pub redis_keys_cleared: u64,
+    pub memory_entries_evicted: u64,
+}
+
+/// Cache warm request; at least one target is required
+#[derive(Debug, Deserialize)]
+pub struct WarmCacheRequest {
+    pub chat_id: Option<i64>,
+    pub workspace_id: Option<i64>,
+    pub user_id: Option<i64>,
+}
+
+/// Cache warm result
+#[derive(Debug, Serialize)]
+pub struct WarmCacheResponse {
+    pub targets_warmed: usize,
+    pub duration_ms: u64,
+}
+
+/// Force-invalidate user, chat and/or workspace caches (workspace admin only, audited)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn invalidate_cache_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<InvalidateCacheRequest>,
+) -> Result<Json<ApiResponse<InvalidateCacheResponse>>, AppError> {
+    if request.user_id.is_none() && request.chat_id.is_none() && request.workspace_id.is_none() {
+        return Err(AppError::BadRequest(
+            "At least one of user_id, chat_id or workspace_id is required".to_string(),
+        ));
+    }
+
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+
+    // Resolve every target before clearing anything
+    let pool = state.pool();
+    if let Some(user_id) = request.user_id {
+        let target = UserRepositoryImpl::new(pool.clone())
+            .find_by_id_ext(UserId(user_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(vec![format!("User {} not found", user_id)]))?;
+        if target.workspace_id != user.workspace_id {
+            return Err(AppError::Forbidden(
+                "User is not a member of your workspace".to_string(),
+            ));
+        }
+    }
+    let chat_members = match request.chat_id {
+        Some(chat_id) => {
+            let chat = ChatRepository::new(pool.clone())
+                .find_chat_by_id(chat_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(vec![format!("Chat {} not found", chat_id)]))?;
+            if chat.workspace_id != user.workspace_id {
+                return Err(AppError::Forbidden(
+                    "Chat is not in your workspace".to_string(),
+                ));
+            }
+            MessageRepository::new(pool.clone())
+                .get_chat_members(chat_id)
+                .await?
+        }
+        None => Vec::new(),
+    };
+    let workspace_users = match request.workspace_id {
+        Some(workspace_id) => {
+            if workspace_id != i64::from(user.workspace_id) {
+                return Err(AppError::Forbidden(
+                    "Can only invalidate your own workspace".to_string(),
+                ));
+            }
+            UserRepositoryImpl::new(pool)
+                .list_workspace_user_ids(user.workspace_id)
+                .await?
+        }
+        None => Vec::new(),
+    };
+
+    let invalidator = state.cache_service().map(|redis| {
+        DistributedLockCacheInvalidator::new(Arc::new(UnifiedCacheService::new(redis.clone())))
+    });
+    let mut redis_keys_cleared = 0;
+    let mut memory_entries_evicted = 0;
+
+    if let Some(user_id) = request.user_id {
+        if let Some(invalidator) = &invalidator {
+            redis_keys_cleared += invalidator.handle_user_updated_with_lock(user_id).await?;
+        }
+        memory_entries_evicted += state.evict_memory_chat_lists(&[user_id]);
+    }
+    if let Some(chat_id) = request.chat_id {
+        if let Some(invalidator) = &invalidator {
+            redis_keys_cleared += invalidator
+                .invalidate_chat_related_with_lock(chat_id, &chat_members)
+                .await?;
+        }
+        memory_entries_evicted += state.evict_memory_chat_lists(&chat_members);
+    }
+    if let Some(workspace_id) = request.workspace_id {
+        if let Some(invalidator) = &invalidator {
+            redis_keys_cleared += invalidator
+                .invalidate_workspace_with_lock(workspace_id, &workspace_users)
+                .await?;
+        }
+        memory_entries_evicted += state.evict_memory_chat_lists(&workspace_users);
+    }
+
+    let keys_cleared = redis_keys_cleared + memory_entries_evicted;
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      target_user_id = ?request.user_id,
+      target_chat_id = ?request.chat_id,
+      target_workspace_id = ?request.workspace_id,
+      keys_cleared = %keys_cleared,
+      "[AUDIT] Caches force-invalidated"
+    );
+
+    Ok(Json(ApiResponse::success(
+        InvalidateCacheResponse {
+            keys_cleared,
+            redis_keys_cleared,
+            memory_entries_evicted,
+        },
+        "cache_invalidated".to_string(),
+    )))
+}
+
+/// Pre-warm chat, workspace and/or user caches from the repositories, returning once they are
+/// populated (workspace admin only, audited). Concurrent requests for a target share one warmup
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn warm_cache_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<WarmCacheRequest>,
+) -> Result<Json<ApiResponse<WarmCacheResponse>>, AppError> {
+    if request.chat_id.is_none() && request.workspace_id.is_none() && request.user_id.is_none() {
+        return Err(AppError::BadRequest(
+            "At least one of chat_id, workspace_id or user_id is required".to_string(),
+        ));
+    }
+
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+    let warmup = state.cache_warmup().ok_or_else(|| {
+        AppError::ServiceUnavailable("Caching is disabled, nothing to warm".to_string())
+    })?;
+
+    // Resolve every target before warming anything
+    let pool = state.pool();
+    let mut targets = Vec::new();
+    if let Some(chat_id) = request.chat_id {
+        let chat = ChatRepository::new(pool.clone())
+            .find_chat_by_id(chat_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(vec![format!("Chat {} not found", chat_id)]))?;
+        if chat.workspace_id != user.workspace_id {
+            return Err(AppError::Forbidden(
+                "Chat is not in your workspace".to_string(),
+            ));
+        }
+        targets.push(WarmupTarget::Chat(chat_id));
+    }
+    if let Some(workspace_id) = request.workspace_id {
+        if workspace_id != i64::from(user.workspace_id) {
+            return Err(AppError::Forbidden(
+                "Can only warm your own workspace".to_string(),
+            ));
+        }
+        targets.push(WarmupTarget::Workspace(workspace_id));
+    }
+    if let Some(user_id) = request.user_id {
+        let target = UserRepositoryImpl::new(pool.clone())
+            .find_by_id_ext(UserId(user_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(vec![format!("User {} not found", user_id)]))?;
+        if target.workspace_id != user.workspace_id {
+            return Err(AppError::Forbidden(
+                "User is not a member of your workspace".to_string(),
+            ));
+        }
+        targets.push(WarmupTarget::User {
+            user_id,
+            workspace_id: i64::from(target.workspace_id),
+        });
+    }
+
+    let started = Instant::now();
+    for &target in &targets {
+        warmup.warmup_target(target).await?;
+    }
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      target_chat_id = ?request.chat_id,
+      target_workspace_id = ?request.workspace_id,
+      target_user_id = ?request.user_id,
+      duration_ms = %duration_ms,
+      "[AUDIT] Caches warmed"
+    );
+
+    Ok(Json(ApiResponse::success(
+        WarmCacheResponse {
+            targets_warmed: targets.len(),
+            duration_ms,
+        },
+        "cache_warmed".to_string(),
+    )))
+}
@@ -172,7 +172,7 @@ pub async fn get_cache_config_handler(
         "enabled": cache_config.enabled,
         "redis_url": cache_config.redis_url,
         "key_prefix": cache_config.key_prefix,
-        "default_ttl": cache_config.default_ttl,
+        "default_ttl": state.runtime_config().current().cache_default_ttl,
         "connection_timeout_ms": cache_config.connection_timeout_ms,
         "ttl_settings": {
             "short": 300,    // 5 minutes
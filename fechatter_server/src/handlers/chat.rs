@@ -10,17 +10,37 @@
 //! -  All business logic delegated to Service layer
 //! -  Follow proper dependency chain
 
+use crate::domains::chat::repository::ChatRepository;
+use crate::domains::permission::ChatAction;
+use crate::handlers::conditional::{conditional_json, weak_etag};
 use crate::services::application::workers::chat::CreateChatInput;
 use crate::{AppError, AppState};
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
-    response::Json,
+    http::{HeaderMap, StatusCode},
+    response::{Json, Response},
     Extension,
 };
 use fechatter_core::{AuthUser, CreateChat, UpdateChat};
+use serde::Deserialize;
 use std::collections::HashMap;
 
+/// Longest configurable slow mode interval (6 hours)
+const MAX_SLOW_MODE_SECS: i32 = 6 * 60 * 60;
+
+/// Slow mode update request
+#[derive(Debug, Deserialize)]
+pub struct SetSlowModeRequest {
+    pub slow_mode_secs: i32,
+}
+
+/// Direct message promotion request; without a name the group is named after its members
+#[derive(Debug, Default, Deserialize)]
+pub struct PromoteToGroupRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
 // =============================================================================
 // HANDLERS - HTTP Coordination Layer (Using Concrete Services)
 // =============================================================================
@@ -63,6 +83,61 @@ pub async fn create_chat_handler(
     })))
 }
 
+/// Direct Message Handler
+///
+/// **Modern Architecture**: Handler → Concrete Application Service → Domain Service
+/// Returns the caller's 1:1 chat with `user_id`, creating it on first use.
+pub async fn direct_message_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(other_user_id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    // 1. Use Concrete Application Service
+    let chat_service = state.application_services().chat_application_service();
+
+    // 2. Delegate to Application Service - atomic get-or-create
+    let chat_detail = chat_service
+        .get_or_create_direct_chat(
+            i64::from(user.id),
+            other_user_id,
+            i64::from(user.workspace_id),
+        )
+        .await?;
+
+    // 3. Return the direct chat details
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": chat_detail,
+        "message": "Direct chat ready"
+    })))
+}
+
+/// Promote To Group Handler
+///
+/// **Modern Architecture**: Handler → Concrete Application Service → Domain Service
+/// Turns a 1:1 chat into a group owned by the caller, keeping its members and history.
+pub async fn promote_to_group_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(chat_id): Path<i64>,
+    Json(request): Json<PromoteToGroupRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    // 1. Use Concrete Application Service
+    let chat_service = state.application_services().chat_application_service();
+
+    // 2. Delegate to Application Service - permission checks, events and cache invalidation
+    let chat_detail = chat_service
+        .promote_to_group(chat_id, i64::from(user.id), request.name)
+        .await?;
+
+    // 3. Return the group chat details
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": chat_detail,
+        "message": "Direct chat promoted to a group"
+    })))
+}
+
 /// Update Chat Handler
 ///
 /// **Modern Architecture**: Handler → Concrete Application Service → Domain Service
@@ -84,6 +159,45 @@ pub async fn update_chat_handler(
     })))
 }
 
+/// Set Chat Slow Mode Handler
+///
+/// Chat admins and the owner set the minimum seconds between a member's messages; 0 turns
+/// slow mode off. Moderators and above are never throttled
+pub async fn set_slow_mode_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(chat_id): Path<i64>,
+    Json(request): Json<SetSlowModeRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    state
+        .permissions()
+        .can_manage_chat(user.id, chat_id, ChatAction::SetSlowMode)
+        .await?
+        .check()?;
+
+    if !(0..=MAX_SLOW_MODE_SECS).contains(&request.slow_mode_secs) {
+        return Err(AppError::BadRequest(format!(
+            "slow_mode_secs must be between 0 and {}",
+            MAX_SLOW_MODE_SECS
+        )));
+    }
+
+    ChatRepository::new(state.pool())
+        .set_slow_mode_secs(chat_id, request.slow_mode_secs)
+        .await?;
+    tracing::info!(
+        "Chat {} slow mode set to {}s by user {}",
+        chat_id,
+        request.slow_mode_secs,
+        user.id
+    );
+
+    Ok(Json(serde_json::json!({
+        "chat_id": chat_id,
+        "slow_mode_secs": request.slow_mode_secs
+    })))
+}
+
 /// List Chats Handler
 ///
 /// **Modern Architecture**: Handler → Concrete Application Service → Domain Service
@@ -142,11 +256,13 @@ pub async fn delete_chat_handler(
 /// Get Chat Details Handler
 ///
 /// **Modern Architecture**: Handler → Concrete Application Service → Domain Service
+/// Supports conditional GET: a matching `If-None-Match` returns 304 without a body.
 pub async fn get_chat_handler(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(chat_id): Path<i64>,
-) -> Result<Json<serde_json::Value>, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
     // 1. Use Concrete Application Service
     let chat_service = state.application_services().chat_application_service();
 
@@ -162,6 +278,12 @@ pub async fn get_chat_handler(
     };
 
     // 4. 构建响应数据 - Handler只负责响应格式化
+    let etag = weak_etag(&(
+        chat.id,
+        chat.updated_at,
+        chat.member_count,
+        i64::from(user.id),
+    ));
     let response = serde_json::json!({
       "success": true,
       "data": chat,
@@ -171,7 +293,7 @@ pub async fn get_chat_handler(
 
     // 5. 记录操作并返回结果
     tracing::info!("Chat {} details retrieved by user {}", chat_id, user.id);
-    Ok(Json(response))
+    Ok(conditional_json(&headers, etag, response))
 }
 
 /// Add Chat Members Handler
@@ -11,34 +11,31 @@
 //! - Simple response construction, no complex DTO mapping
 //! - Follow proper dependency chain
 
+use crate::domains::chat::chat_member_repository::{
+    ChatMemberListing, ChatMemberRepository, CHAT_MEMBER_ROLES,
+};
+use crate::dtos::core::ListResponse;
+use crate::dtos::get_dto_manager;
+use crate::dtos::models::responses::chat::ChatMemberDto;
+use crate::handlers::page_params::{PageParams, SortFields};
 use crate::{AppError, AppState};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Extension, Json,
 };
 use fechatter_core::models::AuthUser;
-use serde::Serialize;
-use tracing::info;
+use fechatter_core::UserId;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
 use utoipa::ToSchema;
 
 // =============================================================================
 // RESPONSE DTOs - Simple Response Types
 // =============================================================================
 
-#[derive(Debug, Serialize, ToSchema, serde::Deserialize)]
-pub struct ChatMemberDto {
-    pub user_id: i64,
-    pub chat_id: i64,
-    pub username: String,
-    pub role: String,
-    #[schema(value_type = String, format = DateTime)]
-    pub joined_at: chrono::DateTime<chrono::Utc>,
-    pub is_online: bool,
-    pub is_creator: bool,
-}
-
 #[derive(Debug, Serialize, ToSchema, serde::Deserialize)]
 pub struct ChatMemberOperationResponse {
     pub success: bool,
@@ -70,16 +67,75 @@ pub struct TransferOwnershipResponse {
 // HANDLERS - HTTP Coordination Layer (Using Modern Architecture)
 // =============================================================================
 
+/// Sortable member list fields
+#[derive(Debug)]
+pub struct ChatMemberSort;
+
+impl SortFields for ChatMemberSort {
+    const ALLOWED: &'static [&'static str] = &["joined_at", "username", "role"];
+}
+
+/// Member list filters
+#[derive(Debug, Default, Deserialize)]
+pub struct ChatMemberFilter {
+    pub role: Option<String>,
+}
+
+impl ChatMemberFilter {
+    /// Requested role, rejected with 400 unless it is a `chat_member_role` value
+    fn role(&self) -> Result<Option<&str>, AppError> {
+        match self
+            .role
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+        {
+            Some(role) if CHAT_MEMBER_ROLES.contains(&role) => Ok(Some(role)),
+            Some(role) => Err(AppError::BadRequest(format!(
+                "Invalid role '{}', expected one of: {}",
+                role,
+                CHAT_MEMBER_ROLES.join(", ")
+            ))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Fill in each member's presence; a presence store failure leaves them offline
+async fn with_presence(state: &AppState, members: &mut [ChatMemberListing]) {
+    let statuses = join_all(
+        members
+            .iter()
+            .map(|member| state.presence_store().get_status(UserId(member.user_id))),
+    )
+    .await;
+
+    for (member, status) in members.iter_mut().zip(statuses) {
+        match status {
+            Ok(status) => member.presence = status,
+            Err(e) => warn!("Presence lookup failed for user {}: {}", member.user_id, e),
+        }
+    }
+}
+
 /// List Chat Members Handler
 ///
-/// **Modern Architecture**: Handler → Application Service → Domain Service
+/// **Modern Architecture**: Handler → Repository (read model) → DtoManager pagination
+/// **Scope**: Active members only; each entry carries its role and presence status
 #[utoipa::path(
     get,
     path = "/api/chats/{chat_id}/members",
-    params(("chat_id" = i64, Path, description = "Chat ID")),
+    params(
+        ("chat_id" = i64, Path, description = "Chat ID"),
+        ("page" = Option<u32>, Query, description = "Page number, starting at 1"),
+        ("page_size" = Option<u32>, Query, description = "Members per page, capped at the configured maximum"),
+        ("sort" = Option<String>, Query, description = "joined_at, username or role; prefix with - for descending"),
+        ("role" = Option<String>, Query, description = "Only members with this role: owner, admin, moderator or member")
+    ),
     security(("access_token" = [])),
     responses(
         (status = 200, description = "Chat members retrieved successfully", body = Vec<ChatMemberDto>),
+        (status = 400, description = "Invalid role filter"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Permission denied"),
         (status = 404, description = "Chat not found")
@@ -90,17 +146,33 @@ pub(crate) async fn list_chat_members_handler(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(chat_id): Path<i64>,
-) -> Result<Json<Vec<ChatMemberDto>>, AppError> {
+    page: PageParams<ChatMemberSort>,
+    Query(filter): Query<ChatMemberFilter>,
+) -> Result<Json<ListResponse<ChatMemberDto>>, AppError> {
     info!("User {} listing members for chat {}", user.id, chat_id);
 
-    // 1. Use Concrete Application Service (better performance)
-    let _chat_service = state.application_services().chat_application_service();
+    let role = filter.role()?;
+    state
+        .ensure_user_is_chat_member(chat_id, i64::from(user.id))
+        .await?;
 
-    // 2. TODO: Implement list_chat_members method in ChatApplicationService
-    // For now, return empty list as placeholder
-    let member_dtos: Vec<ChatMemberDto> = vec![];
+    let member_repo = ChatMemberRepository::new(state.pool());
+    let total_items = member_repo.count_active_members(chat_id, role).await?;
+    let mut members = member_repo
+        .list_members_page(chat_id, role, page.limit(), page.offset(), page.sort_key())
+        .await?;
+    with_presence(&state, &mut members).await;
+
+    let response = get_dto_manager()
+        .create_paginated_response::<ChatMemberDto>(
+            &members,
+            page.pagination_request(),
+            total_items.max(0) as u64,
+            "chat_members_listed".to_string(),
+        )
+        .map_err(|e| AppError::Internal(e.to_string()))?;
 
-    Ok(Json(member_dtos))
+    Ok(Json(response))
 }
 
 /// Add Chat Members Handler
@@ -261,6 +333,7 @@ mod tests {
     };
     use anyhow::Result;
     use axum::{extract::Path, http::StatusCode, Json};
+    use fechatter_core::contracts::PresenceStatus;
     use fechatter_core::models::ChatType;
     use sqlx::Row;
     use uuid::Uuid;
@@ -291,6 +364,154 @@ mod tests {
         Ok(())
     }
 
+    async fn member_page(uri: &str) -> PageParams<ChatMemberSort> {
+        use axum::extract::FromRequestParts;
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri(uri)
+            .body(())
+            .unwrap()
+            .into_parts();
+        PageParams::from_request_parts(&mut parts, &())
+            .await
+            .unwrap()
+    }
+
+    fn role_filter(role: &str) -> Query<ChatMemberFilter> {
+        Query(ChatMemberFilter {
+            role: Some(role.to_string()),
+        })
+    }
+
+    #[tokio::test]
+    async fn list_chat_members_should_paginate_with_presence() -> Result<()> {
+        let (state, users) = setup_test_users!(3).await;
+        let creator = auth_user!(&users[0]);
+        let chat = state
+            .create_new_chat(
+                ChatType::Group,
+                Some(format!("Member Pages {}", Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id, users[2].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+        state
+            .presence_store()
+            .set_online(users[1].id, PresenceStatus::Online)
+            .await?;
+
+        let mut seen = Vec::new();
+        for (page, expected_len) in [(1, 2), (2, 1), (3, 0)] {
+            let Json(response) = list_chat_members_handler(
+                Extension(state.clone()),
+                Extension(creator.clone()),
+                Path(chat_id),
+                member_page(&format!(
+                    "/members?page={}&page_size=2&sort=joined_at",
+                    page
+                ))
+                .await,
+                Query(ChatMemberFilter::default()),
+            )
+            .await?;
+            let page = response.data.expect("member page");
+            assert_eq!(page.pagination.total_items, 3);
+            assert_eq!(page.pagination.total_pages, 2);
+            assert_eq!(page.data.len(), expected_len);
+            seen.extend(page.data);
+        }
+
+        let mut ids: Vec<i64> = seen.iter().map(|m| m.user_id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 3);
+
+        let online = seen
+            .iter()
+            .find(|m| m.user_id == i64::from(users[1].id))
+            .unwrap();
+        assert!(online.is_online);
+        assert_eq!(online.presence, "online");
+        let offline = seen
+            .iter()
+            .find(|m| m.user_id == i64::from(users[2].id))
+            .unwrap();
+        assert!(!offline.is_online);
+        assert_eq!(offline.presence, "offline");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_chat_members_should_filter_by_role() -> Result<()> {
+        let (state, users) = setup_test_users!(3).await;
+        let creator = auth_user!(&users[0]);
+        let chat = state
+            .create_new_chat(
+                ChatType::Group,
+                Some(format!("Member Roles {}", Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id, users[2].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+
+        let Json(response) = list_chat_members_handler(
+            Extension(state.clone()),
+            Extension(creator.clone()),
+            Path(chat_id),
+            PageParams::default(),
+            role_filter("owner"),
+        )
+        .await?;
+        let page = response.data.expect("member page");
+        assert_eq!(page.pagination.total_items, 1);
+        assert_eq!(page.data[0].user_id, i64::from(users[0].id));
+        assert_eq!(page.data[0].role, "owner");
+
+        assert_handler_error!(
+            list_chat_members_handler(
+                Extension(state.clone()),
+                Extension(creator),
+                Path(chat_id),
+                PageParams::default(),
+                role_filter("superuser"),
+            ),
+            AppError::BadRequest(_)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_chat_members_should_reject_non_members() -> Result<()> {
+        let (state, users) = setup_test_users!(3).await;
+        let outsider = auth_user!(&users[2]);
+        let chat = state
+            .create_new_chat(
+                ChatType::Group,
+                Some(format!("Private Members {}", Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+
+        assert_handler_error!(
+            list_chat_members_handler(
+                Extension(state),
+                Extension(outsider),
+                Path(chat_id),
+                PageParams::default(),
+                Query(ChatMemberFilter::default()),
+            ),
+            AppError::ChatAccessDenied { .. }
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn add_chat_members_batch_handler_should_work() -> Result<()> {
         let (state, users) = setup_test_users!(4).await;
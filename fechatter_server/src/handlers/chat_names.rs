@@ -0,0 +1,87 @@
+//! # Chat Name Uniqueness Admin Handlers
+//!
+//! **Responsibility**: Let workspace admins require distinct chat names in their workspace
+//! **Scope**: Per workspace; names compare trimmed and case-insensitively, direct messages are
+//! exempt, and taken names are rejected with 409 Conflict by a database index
+
+use axum::{extract::Extension, response::Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::domains::workspace::repository::WorkspaceRepositoryImpl;
+use crate::dtos::core::ApiResponse;
+use crate::{AppError, AppState};
+use fechatter_core::AuthUser;
+
+/// Chat name uniqueness update request
+#[derive(Debug, Deserialize)]
+pub struct SetUniqueChatNamesRequest {
+    pub unique_chat_names: bool,
+}
+
+/// Whether the workspace requires unique chat names
+#[derive(Debug, Serialize)]
+pub struct UniqueChatNamesResponse {
+    pub workspace_id: i64,
+    pub unique_chat_names: bool,
+}
+
+/// Get whether the workspace requires unique chat names (workspace admins only)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn get_unique_chat_names_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<ApiResponse<UniqueChatNamesResponse>>, AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+
+    let unique_chat_names = WorkspaceRepositoryImpl::new(state.pool())
+        .get_unique_chat_names(user.workspace_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        UniqueChatNamesResponse {
+            workspace_id: user.workspace_id.into(),
+            unique_chat_names,
+        },
+        "chat_names_retrieved".to_string(),
+    )))
+}
+
+/// Require unique chat names in the workspace, or stop requiring them (workspace admins only,
+/// audited). Fails with 409 while existing chats already share a name
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn set_unique_chat_names_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<SetUniqueChatNamesRequest>,
+) -> Result<Json<ApiResponse<UniqueChatNamesResponse>>, AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+
+    WorkspaceRepositoryImpl::new(state.pool())
+        .set_unique_chat_names(user.workspace_id, request.unique_chat_names)
+        .await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      unique_chat_names = request.unique_chat_names,
+      "[AUDIT] Chat name uniqueness changed"
+    );
+
+    Ok(Json(ApiResponse::success(
+        UniqueChatNamesResponse {
+            workspace_id: user.workspace_id.into(),
+            unique_chat_names: request.unique_chat_names,
+        },
+        "chat_names_updated".to_string(),
+    )))
+}
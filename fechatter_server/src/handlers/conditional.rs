@@ -0,0 +1,158 @@
+//! # Conditional GET Helpers
+//!
+//! **Responsibility**: Weak ETags and `If-None-Match` handling for cacheable reads, and the
+//! headers of responses served through the response cache
+//! **Usage**: Build the response body as usual, then return `conditional_json(&headers, etag, body)`,
+//! or `cached_json(cached, ttl)` for a body from `ResponseCache::get_or_load`
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::services::application::stores::Cached;
+
+/// Tells clients and ops whether a response came from the response cache
+pub const X_CACHE: &str = "x-cache";
+
+/// Weak ETag for any serializable version marker (e.g. `(id, updated_at)` or the view itself)
+pub fn weak_etag<V: Serialize>(version: &V) -> String {
+    let bytes = serde_json::to_vec(version).unwrap_or_default();
+    let digest = Sha256::digest(&bytes);
+    format!("W/\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// Whether the request's `If-None-Match` header matches `etag` (weak comparison)
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = strip_weak(etag);
+
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || strip_weak(candidate) == opaque)
+}
+
+/// 304 with the ETag when the client copy is current, otherwise 200 with body and ETag
+pub fn conditional_json<T: Serialize>(headers: &HeaderMap, etag: String, body: T) -> Response {
+    let mut response = if if_none_match(headers, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        Json(body).into_response()
+    };
+
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("private, no-cache"),
+    );
+
+    response
+}
+
+/// 200 with the body, `X-Cache: HIT|MISS` and a private `max-age` of the cache TTL
+pub fn cached_json<T: Serialize>(cached: Cached<T>, ttl_seconds: u64) -> Response {
+    let mut response = Json(cached.value).into_response();
+
+    let headers = response.headers_mut();
+    headers.insert(X_CACHE, HeaderValue::from_static(cached.outcome.as_str()));
+    if let Ok(value) = HeaderValue::from_str(&format!("private, max-age={}", ttl_seconds)) {
+        headers.insert(header::CACHE_CONTROL, value);
+    }
+
+    response
+}
+
+fn strip_weak(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with(if_none_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(if_none_match).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn etag_should_be_weak_and_track_version() {
+        let updated_at = chrono::Utc::now();
+        let etag = weak_etag(&(42_i64, updated_at));
+
+        assert!(etag.starts_with("W/\""));
+        assert_eq!(etag, weak_etag(&(42_i64, updated_at)));
+        assert_ne!(
+            etag,
+            weak_etag(&(42_i64, updated_at + chrono::Duration::seconds(1)))
+        );
+    }
+
+    #[test]
+    fn matching_if_none_match_should_return_304() {
+        let etag = weak_etag(&("chat", 1));
+        let response = conditional_json(&headers_with(&etag), etag.clone(), "body");
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    }
+
+    #[test]
+    fn strong_or_listed_if_none_match_should_still_match() {
+        let etag = weak_etag(&("chat", 1));
+        let strong = etag.trim_start_matches("W/").to_string();
+
+        assert!(if_none_match(&headers_with(&strong), &etag));
+        assert!(if_none_match(
+            &headers_with(&format!("W/\"other\", {}", etag)),
+            &etag
+        ));
+        assert!(if_none_match(&headers_with("*"), &etag));
+    }
+
+    #[test]
+    fn stale_if_none_match_should_return_200_with_new_etag() {
+        let stale = weak_etag(&("chat", 1));
+        let current = weak_etag(&("chat", 2));
+        let response = conditional_json(&headers_with(&stale), current.clone(), "body");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], current.as_str());
+
+        let response = conditional_json(&HeaderMap::new(), current.clone(), "body");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn cached_json_should_report_hit_or_miss() {
+        use crate::services::application::stores::CacheOutcome;
+
+        for (outcome, expected) in [(CacheOutcome::Hit, "HIT"), (CacheOutcome::Miss, "MISS")] {
+            let response = cached_json(
+                Cached {
+                    value: "body",
+                    outcome,
+                },
+                15,
+            );
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[X_CACHE], expected);
+            assert_eq!(
+                response.headers()[header::CACHE_CONTROL],
+                "private, max-age=15"
+            );
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! # Runtime Config Reload Handler
+//!
+//! **Responsibility**: Let configured admins re-read the config file without a restart
+//! **Scope**: Only the `RuntimeConfig` subset changes; a rejected file keeps the current settings
+
+use axum::{extract::Extension, response::Json};
+use tracing::{info, instrument, warn};
+
+use crate::dtos::core::ApiResponse;
+use crate::handlers::maintenance::ensure_maintenance_admin;
+use crate::services::infrastructure::runtime_config::RuntimeConfig;
+use crate::{AppError, AppState};
+use fechatter_core::AuthUser;
+
+/// Reload the hot-reloadable settings from the config file (maintenance admins only, audited)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn reload_config_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<ApiResponse<RuntimeConfig>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+
+    let config = state.runtime_config().reload().map_err(|e| {
+        warn!(admin_id = %user.id, "Runtime config reload rejected: {}", e);
+        e
+    })?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      max_requests = %config.rate_limiting.max_requests,
+      login_max_requests = %config.rate_limiting.login_max_requests,
+      window_seconds = %config.rate_limiting.window_seconds,
+      cache_default_ttl = %config.cache_default_ttl,
+      "[AUDIT] Runtime config reloaded"
+    );
+
+    Ok(Json(ApiResponse::success(
+        config,
+        "config_reloaded".to_string(),
+    )))
+}
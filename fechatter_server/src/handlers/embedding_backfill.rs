@@ -0,0 +1,82 @@
+//! # Embedding Backfill Admin Handlers
+//!
+//! **Responsibility**: Let configured admins start, resume and inspect embedding backfill jobs
+//! **Scope**: Process-wide; a job embeds every unembedded message, or those of one chat
+
+use axum::{
+    extract::{Extension, Path},
+    response::Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::dtos::core::ApiResponse;
+use crate::handlers::maintenance::ensure_maintenance_admin;
+use crate::services::ai::embedding_backfill::{EmbeddingBackfillJob, EmbeddingBackfillService};
+use crate::{AppError, AppState};
+use fechatter_core::AuthUser;
+
+/// Backfill start request; `job_id` resumes an earlier job instead of creating one
+#[derive(Debug, Deserialize)]
+pub struct StartBackfillRequest {
+    pub chat_id: Option<i64>,
+    pub job_id: Option<i64>,
+}
+
+/// Start or resume an embedding backfill job (maintenance admins only, audited)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn start_backfill_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<StartBackfillRequest>,
+) -> Result<Json<ApiResponse<EmbeddingBackfillJob>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+
+    let service = Arc::new(EmbeddingBackfillService::from_state(&state)?);
+    let job_id = match request.job_id {
+        Some(job_id) => job_id,
+        None => {
+            service
+                .create_job(request.chat_id, i64::from(user.id))
+                .await?
+                .id
+        }
+    };
+    let job = service.claim_job(job_id).await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      job_id = %job.id,
+      chat_id = ?job.chat_id,
+      resumed_after = %job.last_message_id,
+      "[AUDIT] Embedding backfill started"
+    );
+
+    service.spawn(job.clone());
+
+    Ok(Json(ApiResponse::success(
+        job,
+        "embedding_backfill_started".to_string(),
+    )))
+}
+
+/// Get embedding backfill job progress (maintenance admins only)
+#[instrument(skip(state), fields(job_id = %job_id, admin_id = %user.id))]
+pub async fn get_backfill_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(job_id): Path<i64>,
+) -> Result<Json<ApiResponse<EmbeddingBackfillJob>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+
+    let job = EmbeddingBackfillService::from_state(&state)?
+        .get_job(job_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        job,
+        "embedding_backfill_retrieved".to_string(),
+    )))
+}
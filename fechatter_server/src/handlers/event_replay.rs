@@ -0,0 +1,48 @@
+//! # Event Replay Admin Handler
+//!
+//! **Responsibility**: `POST /api/admin/events/replay`, re-publishing dead-lettered events
+//! **Scope**: Configured admins only; events are picked by original subject, dead-letter time
+//! range or dead letter ids, and each event is replayed at most `max_replays` times
+
+use axum::{extract::Extension, response::Json};
+use tracing::{info, instrument};
+
+use crate::dtos::core::ApiResponse;
+use crate::handlers::maintenance::ensure_maintenance_admin;
+use crate::services::infrastructure::dead_letters::{
+    DeadLetterService, ReplayFilter, ReplayOutcome,
+};
+use crate::{AppError, AppState};
+use fechatter_core::AuthUser;
+
+/// Replay the dead letters matching the filter (maintenance admins only, audited)
+#[instrument(skip(state, filter), fields(admin_id = %user.id))]
+pub async fn replay_events_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(filter): Json<ReplayFilter>,
+) -> Result<Json<ApiResponse<ReplayOutcome>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+
+    let outcome = DeadLetterService::from_state(&state)
+        .replay(&filter, i64::from(user.id))
+        .await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      subject = ?filter.subject,
+      from = ?filter.from,
+      to = ?filter.to,
+      ids = ?filter.ids,
+      replayed = outcome.replayed.len(),
+      exhausted = outcome.exhausted.len(),
+      failed = outcome.failed.len(),
+      "[AUDIT] Dead-lettered events replayed"
+    );
+
+    Ok(Json(ApiResponse::success(
+        outcome,
+        "events_replayed".to_string(),
+    )))
+}
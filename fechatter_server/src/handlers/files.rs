@@ -7,21 +7,25 @@ use crate::{
     dtos::core::ApiResponse,
     dtos::models::responses::UploadResponse,
     error::{AppError, ErrorOutput},
-    services::infrastructure::storage::{LocalStorage, StorageService},
+    services::infrastructure::cache::CacheKeyBuilder,
+    services::infrastructure::storage::parse_file_id,
     AppState,
 };
 use axum::{
     body::Body,
-    extract::{multipart::Multipart, Extension, Path as ExtractPath},
+    extract::{multipart::Multipart, Extension, Path as ExtractPath, State},
     http::{header, StatusCode},
-    response::{IntoResponse, Json, Response},
+    response::{IntoResponse, Json, Redirect, Response},
 };
+use bytes::Bytes;
 use chrono;
 use fechatter_core::models::AuthUser;
 use mime_guess;
 use serde::Serialize;
 use std::path::Path;
+use std::time::Duration;
 use tokio::fs;
+use tokio::time::MissedTickBehavior;
 use tracing::{debug, error, info, warn};
 use utoipa::ToSchema;
 
@@ -45,11 +49,21 @@ use utoipa::ToSchema;
 )]
 pub async fn upload_single_file_handler(
     Extension(app_state): Extension<AppState>,
-    Extension(_user): Extension<AuthUser>,
+    Extension(user): Extension<AuthUser>,
     mut multipart: Multipart,
 ) -> Result<Json<ApiResponse<UploadResponse>>, AppError> {
     debug!("📤 [FILE_UPLOAD] Starting file upload process");
 
+    let limits = app_state.workspace_limits().get(user.workspace_id).await;
+    if let Some(limiter) = app_state.rate_limiters().file_upload_in(&limits) {
+        limiter
+            .enforce(&CacheKeyBuilder::rate_limit(
+                i64::from(user.id),
+                "file_upload",
+            ))
+            .await?;
+    }
+
     if let Some(field) = multipart.next_field().await? {
         let filename = field.file_name().unwrap_or("unknown").to_string();
         let data = field.bytes().await?;
@@ -77,25 +91,10 @@ pub async fn upload_single_file_handler(
             )));
         }
 
-        // Access storage config and create LocalStorage instance
-        let storage_config = &app_state.config.storage;
-        debug!(
-            "📤 [FILE_UPLOAD] Using storage config - path: {}, prefix: {}",
-            storage_config.path, storage_config.url_prefix
-        );
-
-        let storage =
-            LocalStorage::new(&storage_config.path, &storage_config.url_prefix).map_err(|e| {
-                error!(
-                    "ERROR: [FILE_UPLOAD] Failed to create storage instance: {}",
-                    e
-                );
-                e
-            })?;
-
         // Upload file using storage service
-        let file_url = storage
-            .upload(filename.clone(), data.to_vec())
+        let file_id = app_state
+            .file_storage()
+            .put(&filename, data.to_vec())
             .await
             .map_err(|e| {
                 error!(
@@ -104,28 +103,24 @@ pub async fn upload_single_file_handler(
                 );
                 e
             })?;
+        let file_url = format!("{}/{}", app_state.config.storage.url_prefix, file_id);
 
         info!(
             "[FILE_UPLOAD] File uploaded successfully: {} -> {}",
             filename, file_url
         );
 
-        // Extract hash.ext from file_url for symlink creation
-        if let Some(file_id) = file_url.strip_prefix(&format!("{}/", storage_config.url_prefix)) {
-            debug!("📤 [FILE_UPLOAD] Creating symlink for file_id: {}", file_id);
-            if let Err(e) = create_symlink_for_file(&storage_config.path, file_id).await {
-                warn!(
-                    "WARNING: [FILE_UPLOAD] Symlink creation failed (non-critical): {}",
-                    e
-                );
-                // Don't fail the upload for symlink issues
-            }
-        } else {
-            warn!(
-                "WARNING: [FILE_UPLOAD] Could not extract file_id from URL: {}",
-                file_url
-            );
-        }
+        // Quarantined until its scan is clean; the response waits a bounded time for the verdict
+        let scan_status = match app_state.file_scans() {
+            Some(scans) => Some(
+                scans
+                    .submit(&file_id, data.to_vec(), user.id, user.workspace_id)
+                    .await?
+                    .as_str()
+                    .to_string(),
+            ),
+            None => None,
+        };
 
         // Guess MIME type from filename extension
         let mime_type = mime_guess::from_path(&filename)
@@ -144,6 +139,7 @@ pub async fn upload_single_file_handler(
             mime_type,
             size: file_size,
             created_at: chrono::Utc::now().to_rfc3339(),
+            scan_status,
         };
 
         info!(
@@ -162,68 +158,6 @@ pub async fn upload_single_file_handler(
     ))
 }
 
-/// Create symlink in root directory for ServeDir compatibility
-async fn create_symlink_for_file(storage_path: &str, file_id: &str) -> Result<(), AppError> {
-    use std::os::unix::fs::symlink;
-
-    // Parse file_id to extract hash and extension
-    let parts: Vec<&str> = file_id.split('.').collect();
-    if parts.len() != 2 {
-        return Ok(()); // Skip if invalid format
-    }
-
-    let hash = parts[0];
-    let extension = parts[1];
-
-    // Generate hash directory path
-    let (part1, part2) = hash.split_at(3);
-    let (part2, part3) = part2.split_at(3);
-    let hash_path = format!(
-        "{}/{}/{}/{}.{}",
-        storage_path, part1, part2, part3, extension
-    );
-    let symlink_path = format!("{}/{}", storage_path, file_id);
-
-    // Create relative symlink path
-    let relative_target = format!("./{}/{}/{}.{}", part1, part2, part3, extension);
-
-    // Remove existing symlink if it exists
-    if std::path::Path::new(&symlink_path).exists() {
-        let _ = tokio::fs::remove_file(&symlink_path).await;
-    }
-
-    // Create symlink using blocking task
-    let symlink_path_clone = symlink_path.clone();
-    let relative_str_clone = relative_target.clone();
-    let filename_clone = file_id.to_string();
-
-    match tokio::task::spawn_blocking(move || symlink(&relative_str_clone, &symlink_path_clone))
-        .await
-    {
-        Ok(Ok(_)) => {
-            debug!(
-                "🔗 [SYMLINK] Created: {} -> {}",
-                filename_clone, relative_target
-            );
-            Ok(())
-        }
-        Ok(Err(e)) => {
-            warn!(
-                "WARNING: [SYMLINK] Failed to create symlink for {}: {}",
-                filename_clone, e
-            );
-            Ok(()) // Don't fail upload for symlink issues
-        }
-        Err(e) => {
-            warn!(
-                "WARNING: [SYMLINK] Task error for {}: {}",
-                filename_clone, e
-            );
-            Ok(()) // Don't fail upload for symlink issues
-        }
-    }
-}
-
 /// **Production-grade File Download Handler**
 ///
 /// Handles file downloads with proper error handling and logging.
@@ -236,16 +170,28 @@ async fn create_symlink_for_file(storage_path: &str, file_id: &str) -> Result<()
     summary = "Download a file",
     responses(
         (status = 200, description = "File downloaded successfully"),
+        (status = 403, description = "File is quarantined until its scan is clean", body = ErrorOutput),
         (status = 404, description = "File not found", body = ErrorOutput),
+        (status = 429, description = "Too many downloads, retry after the indicated delay", body = ErrorOutput),
         (status = 500, description = "Internal server error", body = ErrorOutput)
     )
 )]
 pub async fn download_file_handler(
     Extension(app_state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
     ExtractPath(file_id): ExtractPath<String>,
 ) -> Result<Response<Body>, AppError> {
     debug!("📥 [FILE_DOWNLOAD] Starting download for file: {}", file_id);
 
+    if let Some(limiter) = app_state.rate_limiters().file_download() {
+        limiter
+            .enforce(&CacheKeyBuilder::rate_limit(
+                i64::from(user.id),
+                "file_download",
+            ))
+            .await?;
+    }
+
     // Validate file_id format
     if file_id.is_empty() {
         warn!("ERROR: [FILE_DOWNLOAD] Empty file_id provided");
@@ -253,8 +199,7 @@ pub async fn download_file_handler(
     }
 
     // Check file_id format (should be hash.extension)
-    let parts: Vec<&str> = file_id.split('.').collect();
-    if parts.len() != 2 {
+    let Some((hash, extension)) = parse_file_id(&file_id) else {
         warn!(
             "ERROR: [FILE_DOWNLOAD] Invalid file_id format: {} (expected: hash.extension)",
             file_id
@@ -263,38 +208,18 @@ pub async fn download_file_handler(
             "Invalid file ID format: {}",
             file_id
         )));
-    }
-
-    let hash = parts[0];
-    let extension = parts[1];
-
-    if hash.len() < 6 {
-        warn!(
-            "ERROR: [FILE_DOWNLOAD] Hash too short in file_id: {}",
-            file_id
-        );
-        return Err(AppError::BadRequest("Invalid file hash".to_string()));
-    }
+    };
 
     debug!(
         "📥 [FILE_DOWNLOAD] Parsed file_id - hash: {}, extension: {}",
         hash, extension
     );
 
-    let storage_config = &app_state.config.storage;
-    debug!(
-        "📥 [FILE_DOWNLOAD] Using storage config - path: {}, prefix: {}",
-        storage_config.path, storage_config.url_prefix
-    );
+    if let Some(scans) = app_state.file_scans() {
+        scans.ensure_downloadable(&file_id).await?;
+    }
 
-    let storage =
-        LocalStorage::new(&storage_config.path, &storage_config.url_prefix).map_err(|e| {
-            error!(
-                "ERROR: [FILE_DOWNLOAD] Failed to create storage instance: {}",
-                e
-            );
-            AppError::ChatFileError(format!("Storage initialization failed: {}", e))
-        })?;
+    let storage = app_state.file_storage();
 
     // Check if file exists first
     match storage.exists(&file_id).await {
@@ -321,7 +246,7 @@ pub async fn download_file_handler(
     }
 
     // Download file data
-    match storage.download(&file_id).await {
+    match storage.get(&file_id).await {
         Ok(file_data) => {
             let file_size = file_data.len();
             info!(
@@ -337,6 +262,12 @@ pub async fn download_file_handler(
                 mime_type, file_id
             );
 
+            // One large download must not monopolize outbound bandwidth
+            let body = match app_state.rate_limiters().download_bytes_per_second() {
+                Some(bytes_per_second) => throttled_body(file_data, bytes_per_second),
+                None => Body::from(file_data),
+            };
+
             // Build response with appropriate headers
             let response = Response::builder()
                 .status(StatusCode::OK)
@@ -347,7 +278,7 @@ pub async fn download_file_handler(
                     header::CONTENT_DISPOSITION,
                     format!("inline; filename=\"{}\"", file_id),
                 )
-                .body(Body::from(file_data))
+                .body(body)
                 .map_err(|e| {
                     error!(
                         "ERROR: [FILE_DOWNLOAD] Failed to build HTTP response: {}",
@@ -378,6 +309,55 @@ pub async fn download_file_handler(
     }
 }
 
+/// Send `/files/{file_id}` on to a short-lived signed URL when files are kept in a bucket, so
+/// the bytes never pass through this server
+pub async fn signed_file_redirect_handler(
+    State(app_state): State<AppState>,
+    ExtractPath(file_id): ExtractPath<String>,
+) -> Result<Redirect, AppError> {
+    if parse_file_id(&file_id).is_none() {
+        return Err(AppError::NotFound(vec![format!(
+            "File not found: {}",
+            file_id
+        )]));
+    }
+
+    let ttl_secs = app_state
+        .config
+        .storage
+        .s3
+        .as_ref()
+        .map_or(3600, |s3| s3.signed_url_ttl_secs);
+    let url = app_state
+        .file_storage()
+        .signed_url(&file_id, Duration::from_secs(ttl_secs))
+        .await?;
+    Ok(Redirect::temporary(&url))
+}
+
+/// Stream `data` at no more than `bytes_per_second`, a tenth of a second's worth at a time
+fn throttled_body(data: Vec<u8>, bytes_per_second: u64) -> Body {
+    let chunk_size = usize::try_from(bytes_per_second / 10)
+        .unwrap_or(usize::MAX)
+        .max(1);
+    let mut ticker = tokio::time::interval(Duration::from_millis(100));
+    // A client that reads slowly does not earn a burst afterwards
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let chunks = futures::stream::unfold(
+        (Bytes::from(data), ticker),
+        move |(mut rest, mut ticker)| async move {
+            if rest.is_empty() {
+                return None;
+            }
+            ticker.tick().await;
+            let chunk = rest.split_to(chunk_size.min(rest.len()));
+            Some((Ok::<_, std::io::Error>(chunk), (rest, ticker)))
+        },
+    );
+    Body::from_stream(chunks)
+}
+
 /// Initialize symlinks for existing files
 pub async fn initialize_file_symlinks(storage_path: &str) -> Result<(), AppError> {
     info!(
@@ -499,3 +479,21 @@ async fn scan_directory_recursive(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn throttled_download_should_deliver_every_byte_at_the_capped_rate() {
+        let data: Vec<u8> = (0..=255).cycle().take(300).collect();
+        let started = std::time::Instant::now();
+
+        // 1000 B/s sends 100-byte chunks a tenth of a second apart
+        let body = throttled_body(data.clone(), 1000);
+        let received = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+
+        assert_eq!(received.as_ref(), data.as_slice());
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -1,16 +1,25 @@
 use async_trait::async_trait;
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
-use tracing::{error, info};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
 use utoipa::ToSchema;
 
+use crate::config::{HealthEndpointConfig, SystemHealthConfig};
 use crate::{AppError, AppState};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SystemHealth {
     pub status: HealthStatus,
     pub services: Vec<ServiceHealth>,
+    /// Optional feature groups enabled in this deployment
+    #[serde(default)]
+    pub features: Vec<String>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
@@ -22,7 +31,7 @@ pub struct ServiceHealth {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     Healthy,
@@ -316,10 +325,17 @@ impl SystemHealth {
         Self {
             status,
             services,
+            features: Vec::new(),
             timestamp: chrono::Utc::now(),
         }
     }
 
+    /// Report the enabled feature groups
+    pub fn with_features<S: Into<String>>(mut self, features: impl IntoIterator<Item = S>) -> Self {
+        self.features = features.into_iter().map(Into::into).collect();
+        self
+    }
+
     fn calculate_overall_status(services: &[ServiceHealth]) -> HealthStatus {
         if services
             .iter()
@@ -349,6 +365,26 @@ impl SystemHealth {
 pub async fn health_check(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
     info!("Health check requested");
 
+    let service_healths = local_service_healths(&state).await;
+
+    let mut features = state.config.features.routes.enabled();
+    if state.config.server.analytics.enabled {
+        features.push("analytics");
+    }
+    let system_health = SystemHealth::new(service_healths).with_features(features);
+
+    let status_code = status_code(system_health.status);
+
+    info!(
+        "Health check completed with status {:?}",
+        system_health.status
+    );
+
+    Ok((status_code, Json(system_health)))
+}
+
+/// Health of this server's own dependencies
+async fn local_service_healths(state: &AppState) -> Vec<ServiceHealth> {
     // Create health checkers
     let checkers: Vec<Box<dyn HealthChecker>> = vec![
         Box::new(DatabaseChecker::new(state.pool().as_ref().clone())),
@@ -362,21 +398,84 @@ pub async fn health_check(State(state): State<AppState>) -> Result<impl IntoResp
     for checker in checkers {
         service_healths.push(checker.check_health().await);
     }
+    service_healths
+}
 
-    let system_health = SystemHealth::new(service_healths);
-
-    let status_code = match system_health.status {
+fn status_code(status: HealthStatus) -> StatusCode {
+    match status {
         HealthStatus::Healthy => StatusCode::OK,
         HealthStatus::Degraded => StatusCode::OK,
         HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+/// Probe another service's health endpoint; an error answer, a timeout or a refused
+/// connection reports it as degraded rather than failing the caller
+pub async fn check_http_service(
+    client: &reqwest::Client,
+    endpoint: &HealthEndpointConfig,
+    timeout: Duration,
+) -> ServiceHealth {
+    let start = Instant::now();
+    let result = client.get(&endpoint.url).timeout(timeout).send().await;
+    let latency = start.elapsed().as_millis() as u64;
+
+    let error = match result {
+        Ok(response) if response.status().is_success() => None,
+        Ok(response) => Some(format!("Health endpoint answered {}", response.status())),
+        Err(e) if e.is_timeout() => Some(format!("No answer within {} ms", timeout.as_millis())),
+        Err(e) => Some(format!("Unreachable: {}", e)),
     };
+    if let Some(error) = &error {
+        warn!("System health: {} degraded: {}", endpoint.name, error);
+    }
+
+    ServiceHealth {
+        name: endpoint.name.clone(),
+        status: if error.is_none() {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Degraded
+        },
+        latency_ms: Some(latency),
+        error,
+    }
+}
+
+/// This server's dependencies followed by every configured service, probed concurrently
+pub async fn collect_system_health(state: &AppState, config: &SystemHealthConfig) -> SystemHealth {
+    let client = reqwest::Client::new();
+    let remote_checks = config
+        .services
+        .iter()
+        .map(|endpoint| check_http_service(&client, endpoint, config.timeout()));
+    let (mut services, remote) = tokio::join!(
+        local_service_healths(state),
+        futures::future::join_all(remote_checks)
+    );
+    services.extend(remote);
+
+    SystemHealth::new(services)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/system/health",
+    responses(
+        (status = 200, description = "Stack healthy or degraded", body = SystemHealth),
+        (status = 503, description = "This server's own dependencies are unhealthy")
+    ),
+    tag = "health"
+)]
+pub async fn system_health_handler(Extension(state): Extension<AppState>) -> impl IntoResponse {
+    let system_health = collect_system_health(&state, &state.config.features.system_health).await;
 
     info!(
-        "Health check completed with status {:?}",
+        "System health check completed with status {:?}",
         system_health.status
     );
 
-    Ok((status_code, Json(system_health)))
+    (status_code(system_health.status), Json(system_health))
 }
 
 #[utoipa::path(
@@ -394,3 +493,63 @@ pub async fn simple_health_check(State(state): State<AppState>) -> impl IntoResp
         Err(_) => StatusCode::SERVICE_UNAVAILABLE,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tokio::net::TcpListener;
+
+    /// A service whose health endpoint always answers 200
+    async fn spawn_healthy_service() -> String {
+        let app = Router::new().route("/health", get(|| async { "ok" }));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        base
+    }
+
+    /// An address nothing listens on
+    async fn unused_address() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        base
+    }
+
+    #[tokio::test]
+    async fn unreachable_service_should_be_reported_as_degraded() -> anyhow::Result<()> {
+        let (state, _users) = crate::setup_test_users!(1).await;
+        let config = SystemHealthConfig {
+            timeout_ms: 500,
+            services: vec![
+                HealthEndpointConfig {
+                    name: "notify_server".to_string(),
+                    url: format!("{}/health", spawn_healthy_service().await),
+                },
+                HealthEndpointConfig {
+                    name: "analytics_server".to_string(),
+                    url: format!("{}/health", unused_address().await),
+                },
+            ],
+        };
+
+        let health = collect_system_health(&state, &config).await;
+
+        let service = |name: &str| {
+            health
+                .services
+                .iter()
+                .find(|service| service.name == name)
+                .unwrap_or_else(|| panic!("{} missing", name))
+        };
+        assert_eq!(service("notify_server").status, HealthStatus::Healthy);
+        let analytics = service("analytics_server");
+        assert_eq!(analytics.status, HealthStatus::Degraded);
+        assert!(analytics.error.is_some());
+        // This server's own dependencies are still reported
+        assert_eq!(service("database").status, HealthStatus::Healthy);
+        assert_ne!(health.status, HealthStatus::Healthy);
+        Ok(())
+    }
+}
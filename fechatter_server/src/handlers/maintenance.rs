@@ -0,0 +1,82 @@
+//! # Maintenance Mode Admin Handlers
+//!
+//! **Responsibility**: Let configured admins inspect and toggle read-only maintenance mode
+//! **Scope**: Process-wide; writes get 503 `maintenance` while reads keep working
+
+use axum::{extract::Extension, response::Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::dtos::core::ApiResponse;
+use crate::{AppError, AppState};
+use fechatter_core::AuthUser;
+
+/// Maintenance mode toggle request
+#[derive(Debug, Deserialize)]
+pub struct SetMaintenanceModeRequest {
+    pub read_only: bool,
+}
+
+/// Current maintenance mode state
+#[derive(Debug, Serialize)]
+pub struct MaintenanceModeResponse {
+    pub read_only: bool,
+}
+
+/// Get maintenance mode state (maintenance admins only)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn get_maintenance_mode_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<ApiResponse<MaintenanceModeResponse>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+
+    Ok(Json(ApiResponse::success(
+        MaintenanceModeResponse {
+            read_only: state.maintenance().is_read_only(),
+        },
+        "maintenance_mode_retrieved".to_string(),
+    )))
+}
+
+/// Enable or disable read-only maintenance mode (maintenance admins only, audited)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn set_maintenance_mode_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<SetMaintenanceModeRequest>,
+) -> Result<Json<ApiResponse<MaintenanceModeResponse>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+
+    let previous = state.maintenance().set_read_only(request.read_only);
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      previous = %previous,
+      read_only = %request.read_only,
+      "[AUDIT] Maintenance mode changed"
+    );
+
+    Ok(Json(ApiResponse::success(
+        MaintenanceModeResponse {
+            read_only: request.read_only,
+        },
+        "maintenance_mode_updated".to_string(),
+    )))
+}
+
+/// Requester must be listed in `server.maintenance.admin_user_ids`
+pub(crate) fn ensure_maintenance_admin(state: &AppState, user: &AuthUser) -> Result<(), AppError> {
+    if !state
+        .config
+        .server
+        .maintenance
+        .admin_user_ids
+        .contains(&i64::from(user.id))
+    {
+        return Err(AppError::Forbidden("Admin access required".to_string()));
+    }
+
+    Ok(())
+}
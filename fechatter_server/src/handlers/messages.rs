@@ -3,39 +3,99 @@
 //! **Responsibility**: HTTP message processing, delegated to application services
 //! **Principle**: Clean design, single responsibility
 
+use async_trait::async_trait;
 use axum::{
+    body::Body,
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tracing::instrument;
 use validator::Validate;
 
-use crate::dtos::core::ApiResponse;
+use crate::domains::chat::repository::ChatRepository;
+use crate::domains::messaging::messaging_domain::{
+    edit_window_remaining, MessagePreview, MessageSeenSummary, SenderProfileLookup,
+};
+use crate::domains::messaging::repository::MessageRepository;
+use crate::domains::messaging::slash_commands::{
+    parse_command, CommandContext, CommandVisibility, ParsedContent,
+};
+use crate::domains::permission::policy;
+use crate::dtos::core::{
+    decode_cursor, encode_cursor, ApiResponse, BaseDto, BatchResponseDto, ConversionError,
+    DtoValidationError, PaginatedResponse, PaginationRequest, ResponseDto,
+};
+use crate::dtos::get_dto_manager;
 use crate::dtos::models::requests::message::{EditMessageRequest, SendMessageRequest};
+use crate::dtos::models::responses::Attachment;
+use crate::handlers::page_params::{PageParams, SortFields};
+use crate::services::application::stores::UnreadCountStore;
 use crate::services::application::workers::message::MessageView;
+use crate::services::infrastructure::cache::CacheKeyBuilder;
 use crate::{AppError, AppState};
+use fechatter_core::contracts::events::{
+    EphemeralMessageEvent, EventVersion, MessagePreviewsEvent,
+};
 use fechatter_core::{AuthUser, ChatId, CreateMessage, ListMessages, MessageId, UserId};
 
 // =============================================================================
 // LOCAL DTOs - Local data transfer objects
 // =============================================================================
 
-/// Message List Query DTO
+/// Message List Query DTO (page size comes from `PageParams`)
 #[derive(Debug, Deserialize)]
 pub struct ListMessagesQuery {
-    #[serde(default = "default_limit")]
-    pub limit: i64,
     pub before: Option<i64>,
+    /// Opaque cursor from a previous page's `next_cursor`; takes precedence over `before`
+    pub after: Option<String>,
 }
 
-fn default_limit() -> i64 {
-    50
+/// Keyset encoded into message list cursors
+#[derive(Debug, Serialize, Deserialize)]
+struct MessageCursor {
+    id: i64,
 }
 
-/// Sender Response DTO
+/// Messages keep a fixed newest-first keyset order
+pub struct MessageSort;
+
+impl SortFields for MessageSort {
+    const ALLOWED: &'static [&'static str] = &[];
+    const DEFAULT_PAGE_SIZE: Option<u32> = Some(50);
+}
+
+/// Neighbours returned around a message when the query doesn't say
+const DEFAULT_CONTEXT_SIZE: u32 = 25;
+/// Most neighbours returned on either side of a message
+const MAX_CONTEXT_SIZE: u32 = 100;
+
+/// Message Context Query DTO
+#[derive(Debug, Default, Deserialize)]
+pub struct MessageContextQuery {
+    /// Older messages to include
+    pub before: Option<u32>,
+    /// Newer messages to include
+    pub after: Option<u32>,
+}
+
+/// A message and its neighbours, oldest first
 #[derive(Debug, Serialize)]
+pub struct MessageContextResponse {
+    pub chat_id: i64,
+    pub target_id: i64,
+    /// Position of the target in `messages`
+    pub target_index: usize,
+    pub messages: Vec<MessageResponse>,
+    pub has_more_before: bool,
+    pub has_more_after: bool,
+}
+
+/// Sender Response DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SenderResponse {
     pub id: i64,
     pub fullname: String,
@@ -44,15 +104,29 @@ pub struct SenderResponse {
 }
 
 /// Message Response DTO
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageResponse {
     pub id: i64,
     pub chat_id: i64,
     pub sender_id: i64,
     pub sender: Option<SenderResponse>, // Added sender information
     pub content: String,
+    /// Stored attachment entries, kept for older clients; prefer `attachments`
     pub files: Vec<String>,
+    /// Attachment metadata, the same shape for old and new messages
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Sender-generated id echoed back so the client can reconcile its optimistic copy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_message_id: Option<String>,
+    /// Slash command reply shown only to the sender; never stored
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ephemeral: bool,
+    /// Seconds the author has left to edit the message, for a countdown; absent when edits
+    /// are unlimited. Chat admins may be allowed past it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editable_for_secs: Option<u64>,
 }
 
 // =============================================================================
@@ -65,21 +139,14 @@ impl From<SendMessageRequest> for CreateMessage {
             content: request.content,
             files: Some(request.files.unwrap_or_default()),
             idempotency_key: Some(request.idempotency_key.unwrap_or_else(uuid::Uuid::now_v7)),
-        }
-    }
-}
-
-impl From<ListMessagesQuery> for ListMessages {
-    fn from(query: ListMessagesQuery) -> Self {
-        Self {
-            limit: query.limit,
-            last_id: query.before,
+            client_message_id: request.client_message_id,
         }
     }
 }
 
 impl From<MessageView> for MessageResponse {
     fn from(view: MessageView) -> Self {
+        let files = view.files.unwrap_or_default();
         Self {
             id: view.id,
             chat_id: view.chat_id,
@@ -91,23 +158,131 @@ impl From<MessageView> for MessageResponse {
                 email: s.email,
             }),
             content: view.content,
-            files: view.files.unwrap_or_default(),
+            attachments: Attachment::from_stored_files(&files),
+            files,
             created_at: view.created_at,
+            client_message_id: view.client_message_id,
+            ephemeral: false,
+            editable_for_secs: None,
         }
     }
 }
 
+impl MessageResponse {
+    /// Fill in `editable_for_secs` from the configured edit window
+    pub fn with_edit_window(mut self, state: &AppState) -> Self {
+        self.editable_for_secs = edit_window_remaining(
+            self.created_at,
+            state.config.features.message_limits.edit_window_secs,
+            chrono::Utc::now(),
+        );
+        self
+    }
+}
+
+impl BaseDto for MessageResponse {
+    fn dto_type() -> &'static str {
+        "MessageResponse"
+    }
+
+    fn validate(&self) -> Result<(), DtoValidationError> {
+        Ok(())
+    }
+}
+
+impl ResponseDto for MessageResponse {
+    type DomainModel = MessageView;
+
+    fn from_domain(domain: &Self::DomainModel) -> Result<Self, ConversionError> {
+        Ok(Self::from(domain.clone()))
+    }
+}
+
+#[async_trait]
+impl BatchResponseDto for MessageResponse {
+    type Source = dyn SenderProfileLookup;
+    type Prefetched = HashMap<i64, SenderResponse>;
+
+    /// One profile query per page, keyed by sender id
+    async fn prefetch(
+        domains: &[MessageView],
+        source: &Self::Source,
+    ) -> Result<Self::Prefetched, ConversionError> {
+        let mut sender_ids: Vec<i64> = domains
+            .iter()
+            .filter(|message| message.sender.is_none())
+            .map(|message| message.sender_id)
+            .collect();
+        sender_ids.sort_unstable();
+        sender_ids.dedup();
+
+        if sender_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let senders = source.find_senders(&sender_ids).await?;
+        Ok(senders
+            .into_iter()
+            .map(|sender| {
+                (
+                    sender.id,
+                    SenderResponse {
+                        id: sender.id,
+                        fullname: sender.fullname,
+                        username: sender.username,
+                        email: sender.email,
+                    },
+                )
+            })
+            .collect())
+    }
+
+    fn from_domain_prefetched(
+        domain: &MessageView,
+        prefetched: &Self::Prefetched,
+    ) -> Result<Self, ConversionError> {
+        let mut response = Self::from(domain.clone());
+        if response.sender.is_none() {
+            response.sender = prefetched.get(&domain.sender_id).cloned();
+        }
+        Ok(response)
+    }
+}
+
 // =============================================================================
 // HANDLERS
 // =============================================================================
 
+/// One message per slow mode interval for members below chat moderator, failing with a 429
+/// carrying the remaining cooldown
+async fn enforce_slow_mode(
+    state: &AppState,
+    user: &AuthUser,
+    chat_id: i64,
+) -> Result<(), AppError> {
+    let interval_secs = ChatRepository::new(state.pool())
+        .get_slow_mode_secs(chat_id)
+        .await?;
+    let Some(limiter) = state.rate_limiters().slow_mode(interval_secs) else {
+        return Ok(());
+    };
+    let role = state.permissions().chat_role(chat_id, user.id).await?;
+    if policy::is_slow_mode_exempt(role) {
+        return Ok(());
+    }
+
+    Ok(limiter
+        .enforce(&CacheKeyBuilder::slow_mode(chat_id, i64::from(user.id)))
+        .await?)
+}
+
 /// Send Message Handler
 #[instrument(skip(state), fields(chat_id = %chat_id, user_id = %user.id))]
 pub async fn send_message_handler(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(chat_id): Path<i64>,
-    Json(request): Json<SendMessageRequest>,
+    Json(mut request): Json<SendMessageRequest>,
 ) -> Result<Json<ApiResponse<MessageResponse>>, AppError> {
     // VALIDATION: Validate request data
     request
@@ -121,6 +296,55 @@ pub async fn send_message_handler(
         ));
     }
 
+    let limits = state.workspace_limits().get(user.workspace_id).await;
+    if let Some(limiter) = state.rate_limiters().message_send_in(&limits) {
+        limiter
+            .enforce(&CacheKeyBuilder::rate_limit(
+                i64::from(user.id),
+                "message_send",
+            ))
+            .await?;
+    }
+    enforce_slow_mode(&state, &user, chat_id).await?;
+
+    // Slash commands: `/name args` runs a command, `//text` is sent as `/text`
+    let command = match parse_command(&request.content) {
+        ParsedContent::Plain => None,
+        ParsedContent::Escaped(literal) => {
+            request.content = literal.to_string();
+            None
+        }
+        ParsedContent::Command { name, args } => Some((name.to_string(), args.to_string())),
+    };
+    if let Some((name, args)) = command {
+        let ctx = CommandContext {
+            chat_id: ChatId::from(chat_id),
+            sender_id: user.id,
+            sender_name: user.fullname.clone(),
+            workspace_id: user.workspace_id,
+        };
+        let reply = state.slash_commands().dispatch(&ctx, &name, &args).await?;
+        match reply.visibility {
+            // Public replies are sent on the sender's behalf like any other message
+            CommandVisibility::Public => request.content = reply.content,
+            CommandVisibility::Ephemeral => {
+                let response = send_ephemeral_reply(
+                    &state,
+                    &user,
+                    chat_id,
+                    name,
+                    reply.content,
+                    request.client_message_id,
+                )
+                .await;
+                return Ok(Json(ApiResponse::success(
+                    response,
+                    "command_executed".to_string(),
+                )));
+            }
+        }
+    }
+
     let create_message = CreateMessage::from(request.clone());
     let message_service = state.application_services().message_service();
 
@@ -168,6 +392,7 @@ pub async fn send_message_handler(
                 files: message_view.files.clone(),
                 created_at: message_view.created_at,
                 idempotency_key: request.idempotency_key,
+                client_message_id: message_view.client_message_id.clone(),
             },
             user.fullname.clone(),
         );
@@ -204,6 +429,7 @@ pub async fn send_message_handler(
             files: message_view.files.clone(),
             created_at: message_view.created_at,
             idempotency_key: request.idempotency_key,
+            client_message_id: message_view.client_message_id.clone(),
         };
 
         // Get chat members (simplified - in production, this should come from chat service)
@@ -228,55 +454,333 @@ pub async fn send_message_handler(
         }
     }
 
-    let response = MessageResponse::from(message_view);
+    spawn_link_previews(&state, &message_view);
+
+    let response = MessageResponse::from(message_view).with_edit_window(&state);
     Ok(Json(ApiResponse::success(
         response,
         "message_sent".to_string(),
     )))
 }
 
-/// List Messages Handler
+/// Unfurl the URLs of a sent message off the request path; the previews reach the chat as a
+/// follow-up event once fetched
+fn spawn_link_previews(state: &AppState, message: &MessageView) {
+    let (Some(link_previews), Some(publisher)) =
+        (state.link_previews(), state.enhanced_event_publisher())
+    else {
+        return;
+    };
+    let link_previews = link_previews.clone();
+    let publisher = publisher.clone();
+    let (message_id, chat_id, content) = (message.id, message.chat_id, message.content.clone());
+
+    tokio::spawn(async move {
+        let previews = link_previews.unfurl(&content).await;
+        if previews.is_empty() {
+            return;
+        }
+
+        let event = MessagePreviewsEvent {
+            version: EventVersion::default(),
+            message_id: MessageId::from(message_id),
+            chat_id: ChatId::from(chat_id),
+            previews,
+            occurred_at: chrono::Utc::now(),
+        };
+        if let Err(e) = publisher.publish_link_previews(&event).await {
+            tracing::warn!(
+                "Failed to publish link previews for message {}: {}",
+                message_id,
+                e
+            );
+        }
+    });
+}
+
+/// Push a slash command reply to its sender only. Nothing is stored, so the returned message
+/// has no id and is flagged `ephemeral`; the HTTP response carries the reply too, for clients
+/// without an open SSE connection
+async fn send_ephemeral_reply(
+    state: &AppState,
+    user: &AuthUser,
+    chat_id: i64,
+    command: String,
+    content: String,
+    client_message_id: Option<String>,
+) -> MessageResponse {
+    let event = EphemeralMessageEvent {
+        version: EventVersion::default(),
+        chat_id: ChatId::from(chat_id),
+        user_id: user.id,
+        command,
+        content,
+        occurred_at: chrono::Utc::now(),
+    };
+
+    if let Some(enhanced_publisher) = state.enhanced_event_publisher() {
+        if let Err(e) = enhanced_publisher.publish_ephemeral_message(&event).await {
+            tracing::warn!(
+                "Failed to publish /{} reply to notify_server: {}",
+                event.command,
+                e
+            );
+        }
+    }
+
+    MessageResponse {
+        id: 0,
+        chat_id,
+        sender_id: i64::from(user.id),
+        sender: None,
+        content: event.content,
+        files: Vec::new(),
+        attachments: Vec::new(),
+        created_at: event.occurred_at,
+        client_message_id,
+        ephemeral: true,
+        editable_for_secs: None,
+    }
+}
+
+/// Preview Message Handler - dry-run of send: validation, mentions and moderation
+/// are computed but nothing is persisted, published or counted against quotas
 #[instrument(skip(state), fields(chat_id = %chat_id, user_id = %user.id))]
+pub async fn preview_message_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(chat_id): Path<i64>,
+    Json(request): Json<SendMessageRequest>,
+) -> Result<Json<ApiResponse<MessagePreview>>, AppError> {
+    request
+        .validate()
+        .map_err(|e| AppError::InvalidInput(format!("Message validation failed: {}", e)))?;
+
+    let create_message = CreateMessage::from(request);
+    let message_service = state.application_services().message_service();
+
+    let preview = message_service
+        .preview_message(
+            UserId::from(user.id),
+            ChatId::from(chat_id),
+            &create_message,
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        preview,
+        "message_previewed".to_string(),
+    )))
+}
+
+/// Media type of the newline-delimited JSON message list
+pub const NDJSON: &str = "application/x-ndjson";
+/// Total messages in the chat before the cursor, sent with ndjson lists
+pub const X_TOTAL_COUNT: &str = "x-total-count";
+/// Cursor of the next page, sent with ndjson lists that have more
+pub const X_NEXT_CURSOR: &str = "x-next-cursor";
+
+/// List Messages Handler
+///
+/// Messages are paged newest-first by keyset: pass `pagination.next_cursor` back as `after`.
+/// With `Accept: application/x-ndjson` the same page is streamed one message per line, with
+/// the total and next cursor in `X-Total-Count` and `X-Next-Cursor`.
+#[instrument(skip(state, headers), fields(chat_id = %chat_id, user_id = %user.id))]
 pub async fn list_messages_handler(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(chat_id): Path<i64>,
     Query(query): Query<ListMessagesQuery>,
-) -> Result<Json<ApiResponse<Vec<MessageResponse>>>, AppError> {
-    let list_query = ListMessages::from(query);
+    page: PageParams<MessageSort>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let dto_manager = get_dto_manager();
+    let page_size = page.page_size;
+    let before = match query.after.as_deref() {
+        Some(cursor) => Some(
+            decode_cursor::<MessageCursor>(cursor)
+                .map_err(|e| AppError::InvalidInput(e.message))?
+                .id,
+        ),
+        None => query.before,
+    };
 
     // Use service layer instead of direct database access
     let message_service = state.application_services().message_service();
 
+    let (total_items, newer_items) = message_service
+        .count_messages(ChatId::from(chat_id), before)
+        .await?;
+
+    // Keyset position expressed as a page number so clients can show "page N of M"
+    let page = (newer_items / u64::from(page_size)) as u32 + 1;
+    let pagination = dto_manager.pagination_request(Some(page), Some(page_size));
+
+    let list_query = ListMessages {
+        last_id: before,
+        limit: i64::from(pagination.page_size),
+    };
+
+    if accepts_ndjson(&headers) {
+        let has_more = newer_items + u64::from(pagination.page_size) < total_items;
+        return ndjson_messages(&state, chat_id, list_query, total_items, has_more).await;
+    }
+
     let messages = message_service
-        .list_messages(
-            fechatter_core::UserId::from(user.id),
-            fechatter_core::ChatId::from(chat_id),
-            list_query,
+        .list_messages(UserId::from(user.id), ChatId::from(chat_id), list_query)
+        .await?;
+
+    let has_more = newer_items + (messages.len() as u64) < total_items;
+    let next_cursor = messages
+        .last()
+        .filter(|_| has_more)
+        .map(|message| encode_cursor(&MessageCursor { id: message.id }));
+
+    let sender_lookup = MessageRepository::new(state.pool());
+    let mut response = dto_manager
+        .create_paginated_response_batched::<MessageResponse>(
+            &messages,
+            &sender_lookup,
+            pagination,
+            total_items,
+            "messages_listed".to_string(),
         )
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    response.data = response.data.map(|mut page| {
+        page.data = page
+            .data
+            .into_iter()
+            .map(|message| message.with_edit_window(&state))
+            .collect();
+        page.with_next_cursor(next_cursor)
+    });
+
+    Ok(Json(response).into_response())
+}
+
+/// Whether the `Accept` header asks for newline-delimited JSON
+fn accepts_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|range| range.split(';').next())
+        .any(|media_type| media_type.trim().eq_ignore_ascii_case(NDJSON))
+}
+
+/// Streams a page one message per line as the rows come back from the database, so the page
+/// is never held in memory; the total and next cursor go in the headers
+async fn ndjson_messages(
+    state: &AppState,
+    chat_id: i64,
+    query: ListMessages,
+    total_items: u64,
+    has_more: bool,
+) -> Result<Response, AppError> {
+    let repository = MessageRepository::new(state.pool());
+    let next_cursor = if has_more {
+        repository
+            .page_boundary(&query, chat_id)
+            .await?
+            .map(|id| encode_cursor(&MessageCursor { id }))
+    } else {
+        None
+    };
+
+    let state = state.clone();
+    let lines = repository
+        .stream_messages(query, chat_id)
+        .map(move |message| {
+            let message = MessageResponse::from(message?).with_edit_window(&state);
+            let mut line = serde_json::to_vec(&message)?;
+            line.push(b'\n');
+            Ok::<_, axum::BoxError>(line)
+        });
+
+    let mut response = Body::from_stream(lines).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(NDJSON));
+    headers.insert(X_TOTAL_COUNT, HeaderValue::from(total_items));
+    if let Some(value) = next_cursor.and_then(|cursor| HeaderValue::from_str(&cursor).ok()) {
+        headers.insert(X_NEXT_CURSOR, value);
+    }
+    Ok(response)
+}
+
+/// Message Context Handler - the window around a message, e.g. to jump to a search result.
+///
+/// Continue scrolling with the regular list endpoint, using the first message's id as `before`
+#[instrument(skip(state), fields(chat_id = %chat_id, message_id = %message_id, user_id = %user.id))]
+pub async fn get_message_context_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((chat_id, message_id)): Path<(i64, i64)>,
+    Query(query): Query<MessageContextQuery>,
+) -> Result<Json<ApiResponse<MessageContextResponse>>, AppError> {
+    let before = query.before.unwrap_or(DEFAULT_CONTEXT_SIZE);
+    let after = query.after.unwrap_or(DEFAULT_CONTEXT_SIZE);
+    if before > MAX_CONTEXT_SIZE || after > MAX_CONTEXT_SIZE {
+        return Err(AppError::InvalidInput(format!(
+            "before and after must be at most {}",
+            MAX_CONTEXT_SIZE
+        )));
+    }
+
+    state
+        .ensure_user_is_chat_member(chat_id, i64::from(user.id))
         .await?;
 
-    let responses: Vec<MessageResponse> = messages
+    let context = state
+        .application_services()
+        .message_service()
+        .get_message_context(
+            ChatId::from(chat_id),
+            MessageId::from(message_id),
+            before,
+            after,
+        )
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(vec![format!(
+                "Message {} not found in chat {}",
+                message_id, chat_id
+            )])
+        })?;
+
+    let target_index = context.before.len();
+    let views: Vec<MessageView> = context
+        .before
         .into_iter()
-        .map(|message_view| MessageResponse {
-            id: message_view.id,
-            chat_id: message_view.chat_id,
-            sender_id: message_view.sender_id,
-            sender: message_view.sender.map(|s| SenderResponse {
-                id: s.id,
-                fullname: s.fullname,
-                username: s.username,
-                email: s.email,
-            }),
-            content: message_view.content,
-            files: message_view.files.unwrap_or_default(),
-            created_at: message_view.created_at,
-        })
+        .chain(std::iter::once(context.target))
+        .chain(context.after)
+        .map(MessageView::from)
         .collect();
 
+    let sender_lookup = MessageRepository::new(state.pool());
+    let senders = MessageResponse::prefetch(&views, &sender_lookup)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    let messages = views
+        .iter()
+        .map(|view| {
+            MessageResponse::from_domain_prefetched(view, &senders)
+                .map(|message| message.with_edit_window(&state))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
     Ok(Json(ApiResponse::success(
-        responses,
-        "messages_listed".to_string(),
+        MessageContextResponse {
+            chat_id,
+            target_id: message_id,
+            target_index,
+            messages,
+            has_more_before: context.has_more_before,
+            has_more_after: context.has_more_after,
+        },
+        "message_context_retrieved".to_string(),
     )))
 }
 
@@ -301,8 +805,7 @@ pub async fn edit_message_handler(
     message_service
         .domain_service()
         .edit_message(message_id, request.content, i64::from(user.id))
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+        .await?;
 
     // ========================================================================
     // NEW: notify_server SSE Integration for Message Edit
@@ -357,6 +860,7 @@ pub async fn edit_message_handler(
             files: None,                    // TODO: Get actual files from database
             created_at: chrono::Utc::now(), // TODO: Get actual created_at from database
             idempotency_key: None,
+            client_message_id: None,
         };
 
         if let Err(e) = event_publisher
@@ -438,6 +942,7 @@ pub async fn delete_message_handler(
             files: None,             // TODO: Get actual files from database if needed
             created_at: chrono::Utc::now(), // TODO: Get actual created_at from database
             idempotency_key: None,
+            client_message_id: None,
         };
 
         if let Err(e) = event_publisher
@@ -466,6 +971,14 @@ pub struct MarkReadRequest {
     pub message_ids: Vec<i64>,
 }
 
+/// Unread count query
+#[derive(Debug, Default, Deserialize)]
+pub struct UnreadCountQuery {
+    /// Recompute from the database instead of trusting the cached counter
+    #[serde(default)]
+    pub refresh: bool,
+}
+
 /// Unread count response
 #[derive(Debug, Serialize)]
 pub struct UnreadCountResponse {
@@ -473,23 +986,31 @@ pub struct UnreadCountResponse {
     pub unread_count: i64,
 }
 
-/// Mark messages as read
-#[instrument(skip(state), fields(chat_id = %chat_id, user_id = %user.id))]
-pub async fn mark_messages_read_handler(
-    Extension(state): Extension<AppState>,
-    Extension(user): Extension<AuthUser>,
-    Path(chat_id): Path<i64>,
-    Json(request): Json<MarkReadRequest>,
-) -> Result<Json<ApiResponse<()>>, AppError> {
-    let message_service = state.application_services().message_service();
+/// Mark chat read response
+#[derive(Debug, Serialize)]
+pub struct MarkChatReadResponse {
+    pub chat_id: i64,
+    /// Latest message covered by the read; 0 when the chat has no messages
+    pub last_read_message_id: i64,
+    pub unread_count: i64,
+}
 
-    // Mark messages as read in database
-    message_service
-        .domain_service()
-        .mark_messages_read_batch(&request.message_ids, user.id.into())
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+/// Read cursor response
+#[derive(Debug, Serialize)]
+pub struct ReadCursorResponse {
+    pub chat_id: i64,
+    /// Newest message read; 0 before the first read in the chat
+    pub last_read_message_id: i64,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
 
+/// Publish read receipts for `message_ids` to notify_server (SSE) and the unified event stream
+async fn publish_read_receipts(
+    state: &AppState,
+    user: &AuthUser,
+    chat_id: i64,
+    message_ids: &[i64],
+) {
     // ========================================================================
     // NEW: notify_server SSE Integration for Read Receipts
     // ========================================================================
@@ -497,7 +1018,7 @@ pub async fn mark_messages_read_handler(
     // Publish to notify_server for real-time SSE broadcasting
     if let Some(enhanced_publisher) = state.enhanced_event_publisher() {
         if let Err(e) = enhanced_publisher
-            .publish_read_receipts_for_sse(chat_id, i64::from(user.id), request.message_ids.clone())
+            .publish_read_receipts_for_sse(chat_id, i64::from(user.id), message_ids.to_vec())
             .await
         {
             tracing::warn!(
@@ -515,19 +1036,16 @@ pub async fn mark_messages_read_handler(
     // LEGACY: Unified Event Publishing for Read Receipts
     // ========================================================================
 
-    if let Some(event_publisher) = get_unified_event_publisher(&state) {
+    if let Some(event_publisher) = get_unified_event_publisher(state) {
         let workspace_id = user.workspace_id.into();
-        let message_ids: Vec<MessageId> = request
-            .message_ids
-            .iter()
-            .map(|&id| MessageId::from(id))
-            .collect();
+        let receipt_ids: Vec<MessageId> =
+            message_ids.iter().map(|&id| MessageId::from(id)).collect();
 
         if let Err(e) = event_publisher
             .publish_unified_message_read_receipt(
                 &ChatId::from(chat_id),
                 &UserId::from(user.id),
-                message_ids,
+                receipt_ids,
                 workspace_id,
             )
             .await
@@ -536,33 +1054,132 @@ pub async fn mark_messages_read_handler(
         } else {
             tracing::info!(
                 "Successfully published unified read receipt event for {} messages in chat {}",
-                request.message_ids.len(),
+                message_ids.len(),
                 chat_id
             );
         }
     }
+}
+
+/// Mark messages as read
+#[instrument(skip(state), fields(chat_id = %chat_id, user_id = %user.id))]
+pub async fn mark_messages_read_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(chat_id): Path<i64>,
+    Json(request): Json<MarkReadRequest>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let message_service = state.application_services().message_service();
+
+    // Mark messages as read in database; only ids of this chat, for an active member, are kept
+    let marked = message_service
+        .domain_service()
+        .mark_messages_read_batch(chat_id, &request.message_ids, user.id.into())
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    // Cached unread counter is recomputed on the next read
+    UnreadCountStore::new_optional(state.cache_service().cloned())
+        .invalidate(user.id.into(), chat_id)
+        .await;
+
+    if !marked.is_empty() {
+        publish_read_receipts(&state, &user, chat_id, &marked).await;
+    }
 
     Ok(Json(ApiResponse::success(
         (),
-        format!("marked_{}_messages_as_read", request.message_ids.len()),
+        format!("marked_{}_messages_as_read", marked.len()),
     )))
 }
 
-/// Get unread message count
+/// Mark every message in the chat as read and clear its unread count
 #[instrument(skip(state), fields(chat_id = %chat_id, user_id = %user.id))]
-pub async fn get_unread_count_handler(
+pub async fn mark_chat_read_handler(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(chat_id): Path<i64>,
-) -> Result<Json<ApiResponse<UnreadCountResponse>>, AppError> {
-    let message_service = state.application_services().message_service();
+) -> Result<Json<ApiResponse<MarkChatReadResponse>>, AppError> {
+    let user_id: i64 = user.id.into();
+    let last_read_message_id = state
+        .application_services()
+        .message_service()
+        .domain_service()
+        .mark_chat_read(chat_id, user_id)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .ok_or_else(|| AppError::ChatAccessDenied {
+            reason: "Not a member of this chat".to_string(),
+            chat_id,
+            user_id,
+        })?;
+
+    // Dropped rather than zeroed, so a message arriving meanwhile is still counted
+    UnreadCountStore::new_optional(state.cache_service().cloned())
+        .invalidate(user_id, chat_id)
+        .await;
+
+    if last_read_message_id > 0 {
+        publish_read_receipts(&state, &user, chat_id, &[last_read_message_id]).await;
+    }
+
+    Ok(Json(ApiResponse::success(
+        MarkChatReadResponse {
+            chat_id,
+            last_read_message_id,
+            unread_count: 0,
+        },
+        "chat_marked_as_read".to_string(),
+    )))
+}
 
-    let unread_count = message_service
+/// Get the newest message the user has read in the chat
+#[instrument(skip(state), fields(chat_id = %chat_id, user_id = %user.id))]
+pub async fn get_read_cursor_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(chat_id): Path<i64>,
+) -> Result<Json<ApiResponse<ReadCursorResponse>>, AppError> {
+    let cursor = state
+        .application_services()
+        .message_service()
         .domain_service()
-        .get_unread_count(chat_id, user.id.into())
+        .get_read_cursor(chat_id, user.id.into())
         .await
         .map_err(|e| AppError::Internal(e.to_string()))?;
 
+    Ok(Json(ApiResponse::success(
+        ReadCursorResponse {
+            chat_id,
+            last_read_message_id: cursor.as_ref().map_or(0, |c| c.last_read_message_id),
+            updated_at: cursor.map(|c| c.updated_at),
+        },
+        "read_cursor_retrieved".to_string(),
+    )))
+}
+
+/// Get unread message count
+#[instrument(skip(state), fields(chat_id = %chat_id, user_id = %user.id))]
+pub async fn get_unread_count_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(chat_id): Path<i64>,
+    Query(query): Query<UnreadCountQuery>,
+) -> Result<Json<ApiResponse<UnreadCountResponse>>, AppError> {
+    let message_service = state.application_services().message_service();
+    let unread_counts = UnreadCountStore::new_optional(state.cache_service().cloned());
+    let user_id: i64 = user.id.into();
+
+    let unread_count = unread_counts
+        .get_unread_count(user_id, chat_id, query.refresh, || async {
+            message_service
+                .domain_service()
+                .count_unread_after_last_read(chat_id, user_id)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))
+        })
+        .await?;
+
     Ok(Json(ApiResponse::success(
         UnreadCountResponse {
             chat_id,
@@ -690,19 +1307,78 @@ pub struct UnreadMentionResponse {
     pub mention_type: String,
 }
 
+/// Unread mentions query (page size comes from `PageParams`)
+#[derive(Debug, Deserialize)]
+pub struct UnreadMentionsQuery {
+    /// Only mentions sent after this time, for fetching just the new ones
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Opaque cursor from a previous page's `next_cursor`
+    pub after: Option<String>,
+}
+
+/// Keyset encoded into unread mention cursors
+#[derive(Debug, Serialize, Deserialize)]
+struct MentionCursor {
+    created_at: chrono::DateTime<chrono::Utc>,
+    message_id: i64,
+}
+
+/// Unread mentions keep a fixed newest-first keyset order
+pub struct MentionSort;
+
+impl SortFields for MentionSort {
+    const ALLOWED: &'static [&'static str] = &[];
+    const DEFAULT_PAGE_SIZE: Option<u32> = Some(50);
+}
+
 /// Get unread mentions for the current user across all chats
+///
+/// Mentions are paged newest-first by keyset: pass `pagination.next_cursor` back as `after`,
+/// with the same `since`.
 #[instrument(skip(state), fields(user_id = %user.id))]
 pub async fn get_unread_mentions_handler(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
-) -> Result<Json<ApiResponse<Vec<UnreadMentionResponse>>>, AppError> {
+    Query(query): Query<UnreadMentionsQuery>,
+    page: PageParams<MentionSort>,
+) -> Result<Json<ApiResponse<PaginatedResponse<UnreadMentionResponse>>>, AppError> {
+    let after = query
+        .after
+        .as_deref()
+        .map(decode_cursor::<MentionCursor>)
+        .transpose()
+        .map_err(|e| AppError::InvalidInput(e.message))?
+        .map(|cursor| (cursor.created_at, cursor.message_id));
+
     // Use service layer instead of direct database access
     let message_service = state.application_services().message_service();
 
+    let (total_items, listed_items) = message_service
+        .count_unread_mentions(i64::from(user.id), query.since, after)
+        .await?;
+    let pagination = keyset_page(listed_items, page.page_size);
+
     let mentions_data = message_service
-        .get_unread_mentions_for_user(i64::from(user.id))
+        .get_unread_mentions_for_user(
+            i64::from(user.id),
+            query.since,
+            after,
+            i64::from(pagination.page_size),
+        )
         .await?;
 
+    let has_more = listed_items + (mentions_data.len() as u64) < total_items;
+    let next_cursor =
+        mentions_data
+            .last()
+            .filter(|_| has_more)
+            .map(|(_, message_id, _, _, created_at, _)| {
+                encode_cursor(&MentionCursor {
+                    created_at: *created_at,
+                    message_id: *message_id,
+                })
+            });
+
     let mentions: Vec<UnreadMentionResponse> = mentions_data
         .into_iter()
         .map(
@@ -720,11 +1396,19 @@ pub async fn get_unread_mentions_handler(
         .collect();
 
     Ok(Json(ApiResponse::success(
-        mentions,
+        PaginatedResponse::new(mentions, pagination.page, pagination.page_size, total_items)
+            .with_next_cursor(next_cursor),
         "unread_mentions_retrieved".to_string(),
     )))
 }
 
+/// Page request of a keyset page that starts after `listed_items` rows, expressed as a page
+/// number so clients can show "page N of M"
+fn keyset_page(listed_items: u64, page_size: u32) -> PaginationRequest {
+    let page = (listed_items / u64::from(page_size)) as u32 + 1;
+    get_dto_manager().pagination_request(Some(page), Some(page_size))
+}
+
 /// Enhanced read receipts with detailed info
 #[derive(Debug, Serialize)]
 pub struct DetailedReceiptResponse {
@@ -735,20 +1419,73 @@ pub struct DetailedReceiptResponse {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// Detailed receipts query (page size comes from `PageParams`)
+#[derive(Debug, Deserialize)]
+pub struct DetailedReceiptsQuery {
+    /// Opaque cursor from a previous page's `next_cursor`
+    pub after: Option<String>,
+}
+
+/// Keyset encoded into detailed receipt cursors
+#[derive(Debug, Serialize, Deserialize)]
+struct ReceiptCursor {
+    timestamp: chrono::DateTime<chrono::Utc>,
+    user_id: i64,
+    status: String,
+}
+
+/// Detailed receipts keep a fixed latest-first keyset order
+pub struct ReceiptSort;
+
+impl SortFields for ReceiptSort {
+    const ALLOWED: &'static [&'static str] = &[];
+    const DEFAULT_PAGE_SIZE: Option<u32> = Some(50);
+}
+
 /// Get detailed read receipts for a message
+///
+/// Receipts are paged latest-first by keyset: pass `pagination.next_cursor` back as `after`.
 #[instrument(skip(state), fields(message_id = %message_id))]
 pub async fn get_detailed_message_receipts_handler(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
     Path(message_id): Path<i64>,
-) -> Result<Json<ApiResponse<Vec<DetailedReceiptResponse>>>, AppError> {
+    Query(query): Query<DetailedReceiptsQuery>,
+    page: PageParams<ReceiptSort>,
+) -> Result<Json<ApiResponse<PaginatedResponse<DetailedReceiptResponse>>>, AppError> {
+    let after = query
+        .after
+        .as_deref()
+        .map(decode_cursor::<ReceiptCursor>)
+        .transpose()
+        .map_err(|e| AppError::InvalidInput(e.message))?
+        .map(|cursor| (cursor.timestamp, cursor.user_id, cursor.status));
+
     // Use service layer instead of direct database access
     let message_service = state.application_services().message_service();
 
+    let (total_items, listed_items) = message_service
+        .count_message_receipts(message_id, after.clone())
+        .await?;
+    let pagination = keyset_page(listed_items, page.page_size);
+
     let receipts_data = message_service
-        .get_detailed_message_receipts(message_id)
+        .get_detailed_message_receipts(message_id, after, i64::from(pagination.page_size))
         .await?;
 
+    let has_more = listed_items + (receipts_data.len() as u64) < total_items;
+    let next_cursor =
+        receipts_data
+            .last()
+            .filter(|_| has_more)
+            .map(|(user_id, _, _, status, timestamp)| {
+                encode_cursor(&ReceiptCursor {
+                    timestamp: *timestamp,
+                    user_id: *user_id,
+                    status: status.clone(),
+                })
+            });
+
     let receipts: Vec<DetailedReceiptResponse> = receipts_data
         .into_iter()
         .map(
@@ -763,11 +1500,69 @@ pub async fn get_detailed_message_receipts_handler(
         .collect();
 
     Ok(Json(ApiResponse::success(
-        receipts,
+        PaginatedResponse::new(receipts, pagination.page, pagination.page_size, total_items)
+            .with_next_cursor(next_cursor),
         "detailed_receipts_retrieved".to_string(),
     )))
 }
 
+/// Readers listed in a receipts summary
+const SEEN_SUMMARY_SAMPLE_SIZE: i64 = 5;
+/// Receipts summaries are cached briefly so new reads show up quickly
+const SEEN_SUMMARY_TTL_SECONDS: u64 = 10;
+
+/// Get a "seen by N" summary for a message: reader count plus the first few readers
+#[instrument(skip(state), fields(message_id = %message_id, user_id = %user.id))]
+pub async fn get_message_receipts_summary_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(message_id): Path<i64>,
+) -> Result<Json<ApiResponse<MessageSeenSummary>>, AppError> {
+    let message_service = state.application_services().message_service();
+    let message = message_service
+        .get_message(MessageId(message_id), user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(vec![format!("Message {} not found", message_id)]))?;
+    state
+        .application_services()
+        .chat_application_service()
+        .ensure_user_is_chat_member(user.id.into(), message.chat_id)
+        .await?;
+
+    let cache_key = CacheKeyBuilder::message_seen_summary(message_id);
+    if let Some(cache) = state.cache_service() {
+        match cache.get::<MessageSeenSummary>(&cache_key).await {
+            Ok(Some(summary)) => {
+                return Ok(Json(ApiResponse::success(
+                    summary,
+                    "receipts_summary_retrieved".to_string(),
+                )))
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Receipts summary cache read failed: {}", e),
+        }
+    }
+
+    let summary = message_service
+        .domain_service()
+        .get_message_seen_summary(message_id, SEEN_SUMMARY_SAMPLE_SIZE)
+        .await?;
+
+    if let Some(cache) = state.cache_service() {
+        if let Err(e) = cache
+            .set(&cache_key, &summary, SEEN_SUMMARY_TTL_SECONDS)
+            .await
+        {
+            tracing::warn!("Receipts summary cache write failed: {}", e);
+        }
+    }
+
+    Ok(Json(ApiResponse::success(
+        summary,
+        "receipts_summary_retrieved".to_string(),
+    )))
+}
+
 /// Mark message as read with enhanced tracking
 #[instrument(skip(state), fields(chat_id = %chat_id, message_id = %message_id, user_id = %user.id))]
 pub async fn mark_message_read_enhanced_handler(
@@ -807,3 +1602,877 @@ fn extract_mentioned_users(content: &str) -> Vec<UserId> {
 
     mentioned_users
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+    use fechatter_core::error::CoreError;
+    use fechatter_core::models::message::MessageSender;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingLookup {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SenderProfileLookup for CountingLookup {
+        async fn find_senders(&self, user_ids: &[i64]) -> Result<Vec<MessageSender>, CoreError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(user_ids
+                .iter()
+                .map(|id| MessageSender {
+                    id: *id,
+                    fullname: format!("User {}", id),
+                    username: Some(format!("user{}", id)),
+                    email: None,
+                })
+                .collect())
+        }
+    }
+
+    fn message_view(id: i64, sender_id: i64) -> MessageView {
+        MessageView {
+            id,
+            chat_id: 1,
+            sender_id,
+            sender: None,
+            content: format!("message {}", id),
+            files: None,
+            created_at: chrono::Utc::now(),
+            reply_to: None,
+            mentions: None,
+            is_edited: false,
+            sequence_number: None,
+            idempotency_key: None,
+            client_message_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn message_page_should_prefetch_sender_profiles_once() {
+        let lookup = CountingLookup::default();
+        let dto_manager = get_dto_manager();
+
+        for page_size in [5_i64, 50] {
+            lookup.calls.store(0, Ordering::SeqCst);
+            let messages: Vec<MessageView> = (1..=page_size)
+                .map(|id| message_view(id, id % 4 + 1))
+                .collect();
+
+            let response = dto_manager
+                .create_paginated_response_batched::<MessageResponse>(
+                    &messages,
+                    &lookup,
+                    dto_manager.pagination_request(Some(1), Some(page_size as u32)),
+                    page_size as u64,
+                    "messages_listed".to_string(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(lookup.calls.load(Ordering::SeqCst), 1);
+            let page = response.data.unwrap();
+            assert_eq!(page.data.len(), page_size as usize);
+            for message in &page.data {
+                let sender = message.sender.as_ref().expect("sender populated");
+                assert_eq!(sender.id, message.sender_id);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_page_should_skip_profile_lookup() {
+        let lookup = CountingLookup::default();
+        let dto_manager = get_dto_manager();
+
+        dto_manager
+            .create_paginated_response_batched::<MessageResponse>(
+                &[],
+                &lookup,
+                dto_manager.pagination_request(None, None),
+                0,
+                "messages_listed".to_string(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(lookup.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn client_message_id_should_round_trip_to_response_and_realtime_event() {
+        use crate::services::application::workers::message::RealtimeEvent;
+        use fechatter_core::models::message::StreamMessage;
+
+        let request: SendMessageRequest = serde_json::from_value(serde_json::json!({
+            "content": "hello",
+            "client_message_id": "local-42"
+        }))
+        .unwrap();
+        request.validate().unwrap();
+
+        let create_message = CreateMessage::from(request);
+        assert_eq!(
+            create_message.client_message_id.as_deref(),
+            Some("local-42")
+        );
+
+        // What the repository hands back after persisting the message
+        let saved = fechatter_core::Message {
+            id: MessageId(7),
+            chat_id: ChatId(1),
+            sender_id: UserId(2),
+            content: create_message.content,
+            files: create_message.files,
+            created_at: chrono::Utc::now(),
+            idempotency_key: create_message.idempotency_key,
+            client_message_id: create_message.client_message_id,
+        };
+
+        let response =
+            serde_json::to_value(MessageResponse::from(MessageView::from(saved.clone()))).unwrap();
+        assert_eq!(response["client_message_id"], "local-42");
+
+        let event = serde_json::to_value(RealtimeEvent::MessageReceived {
+            message: StreamMessage::from(&saved),
+            chat_id: 1,
+            recipients: vec![2, 3],
+        })
+        .unwrap();
+        assert_eq!(
+            event["MessageReceived"]["message"]["client_message_id"],
+            "local-42"
+        );
+    }
+
+    #[test]
+    fn absent_client_message_id_should_be_omitted_from_response() {
+        let response = serde_json::to_value(MessageResponse::from(message_view(1, 2))).unwrap();
+        assert!(response.get("client_message_id").is_none());
+    }
+
+    async fn unread_count(state: &AppState, user: &AuthUser, chat_id: i64, refresh: bool) -> i64 {
+        let Json(response) = get_unread_count_handler(
+            Extension(state.clone()),
+            Extension(user.clone()),
+            Path(chat_id),
+            Query(UnreadCountQuery { refresh }),
+        )
+        .await
+        .unwrap();
+        response.data.unwrap().unread_count
+    }
+
+    async fn read_cursor(state: &AppState, user: &AuthUser, chat_id: i64) -> i64 {
+        let Json(response) = get_read_cursor_handler(
+            Extension(state.clone()),
+            Extension(user.clone()),
+            Path(chat_id),
+        )
+        .await
+        .unwrap();
+        response.data.unwrap().last_read_message_id
+    }
+
+    async fn send(state: &AppState, sender: UserId, chat_id: i64, content: &str) -> i64 {
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": content })).unwrap();
+        state
+            .application_services()
+            .message_service()
+            .send_message(sender, ChatId::from(chat_id), CreateMessage::from(request))
+            .await
+            .unwrap()
+            .id
+    }
+
+    #[tokio::test]
+    async fn read_all_should_clear_unread_until_the_next_message() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(3).await;
+        let reader = crate::auth_user!(&users[1]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Read All {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id, users[2].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+
+        for content in ["one", "two", "three"] {
+            send(&state, users[0].id, chat_id, content).await;
+        }
+        let latest = send(&state, users[2].id, chat_id, "four").await;
+        // Sends invalidate cached counters asynchronously, so counts after a send are refreshed
+        assert_eq!(unread_count(&state, &reader, chat_id, true).await, 4);
+
+        let Json(response) = mark_chat_read_handler(
+            Extension(state.clone()),
+            Extension(reader.clone()),
+            Path(chat_id),
+        )
+        .await?;
+        let marked = response.data.unwrap();
+        assert_eq!(marked.last_read_message_id, latest);
+        assert_eq!(unread_count(&state, &reader, chat_id, false).await, 0);
+
+        send(&state, users[0].id, chat_id, "five").await;
+        assert_eq!(unread_count(&state, &reader, chat_id, true).await, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_cursor_should_advance_with_reads_and_never_move_back() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let reader = crate::auth_user!(&users[1]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Read Cursor {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+        let mark_read = |message_ids: Vec<i64>| {
+            mark_messages_read_handler(
+                Extension(state.clone()),
+                Extension(reader.clone()),
+                Path(chat_id),
+                Json(MarkReadRequest { message_ids }),
+            )
+        };
+
+        let mut ids = Vec::new();
+        for content in ["one", "two", "three", "four"] {
+            ids.push(send(&state, users[0].id, chat_id, content).await);
+        }
+        assert_eq!(read_cursor(&state, &reader, chat_id).await, 0);
+
+        mark_read(vec![ids[0], ids[2]]).await?;
+        assert_eq!(read_cursor(&state, &reader, chat_id).await, ids[2]);
+
+        // Reading an older message later leaves the cursor where it is
+        mark_read(vec![ids[1]]).await?;
+        assert_eq!(read_cursor(&state, &reader, chat_id).await, ids[2]);
+
+        mark_chat_read_handler(
+            Extension(state.clone()),
+            Extension(reader.clone()),
+            Path(chat_id),
+        )
+        .await?;
+        assert_eq!(read_cursor(&state, &reader, chat_id).await, ids[3]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_by_ids_should_only_mark_messages_of_the_path_chat() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(3).await;
+        let reader = crate::auth_user!(&users[1]);
+        let group = |name: &str, members: Vec<UserId>| {
+            state.create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("{} {}", name, uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                members,
+            )
+        };
+        let chat_id: i64 = group("Read Path", vec![users[1].id]).await?.id.into();
+        let other_id: i64 = group("Read Other", vec![users[1].id]).await?.id.into();
+        let private_id: i64 = group("Read Private", vec![users[2].id]).await?.id.into();
+
+        let own = send(&state, users[0].id, chat_id, "here").await;
+        let other = send(&state, users[0].id, other_id, "elsewhere").await;
+        let private = send(&state, users[0].id, private_id, "not yours").await;
+
+        let Json(response) = mark_messages_read_handler(
+            Extension(state.clone()),
+            Extension(reader.clone()),
+            Path(chat_id),
+            Json(MarkReadRequest {
+                message_ids: vec![own, other, private],
+            }),
+        )
+        .await?;
+        assert_eq!(response.meta.request_id, "marked_1_messages_as_read");
+        assert_eq!(read_cursor(&state, &reader, chat_id).await, own);
+
+        // Neither another chat of the reader nor a chat they aren't in gets a cursor
+        let domain = state
+            .application_services()
+            .message_service()
+            .domain_service();
+        for foreign in [other_id, private_id] {
+            let cursor = domain
+                .get_read_cursor(foreign, i64::from(reader.id))
+                .await?;
+            assert!(cursor.is_none());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn message_context_should_be_centered_on_the_target() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let member = crate::auth_user!(&users[1]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Context {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+
+        let mut ids = Vec::new();
+        for n in 0..9 {
+            ids.push(send(&state, users[0].id, chat_id, &format!("message {}", n)).await);
+        }
+        let context = |message_id: i64, before: u32, after: u32| {
+            get_message_context_handler(
+                Extension(state.clone()),
+                Extension(member.clone()),
+                Path((chat_id, message_id)),
+                Query(MessageContextQuery {
+                    before: Some(before),
+                    after: Some(after),
+                }),
+            )
+        };
+
+        let Json(response) = context(ids[4], 2, 3).await?;
+        let window = response.data.unwrap();
+        let window_ids: Vec<i64> = window.messages.iter().map(|m| m.id).collect();
+        assert_eq!(window_ids, ids[2..=7]);
+        assert_eq!(window.target_index, 2);
+        assert_eq!(window.messages[window.target_index].content, "message 4");
+        assert!(window.has_more_before && window.has_more_after);
+
+        // Near the end of the chat the window is cut short on that side only
+        let Json(response) = context(ids[7], 2, 5).await?;
+        let window = response.data.unwrap();
+        let window_ids: Vec<i64> = window.messages.iter().map(|m| m.id).collect();
+        assert_eq!(window_ids, ids[5..]);
+        assert!(window.has_more_before && !window.has_more_after);
+
+        // A message of another chat is not found through this one
+        let other = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Context Other {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let foreign = send(&state, users[0].id, other.id.into(), "elsewhere").await;
+        assert!(matches!(
+            context(foreign, 2, 2).await,
+            Err(AppError::NotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unread_mentions_should_page_newest_first_and_filter_by_since() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let reader = crate::auth_user!(&users[1]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Mentions {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+
+        let mut ids = Vec::new();
+        for n in 0..5 {
+            ids.push(send(&state, users[0].id, chat_id, &format!("@everyone {}", n)).await);
+        }
+        send(&state, users[0].id, chat_id, "no mention").await;
+        let mentions = |since: Option<chrono::DateTime<chrono::Utc>>, after: Option<String>| {
+            let mut page = PageParams::<MentionSort>::default();
+            page.page_size = 2;
+            get_unread_mentions_handler(
+                Extension(state.clone()),
+                Extension(reader.clone()),
+                Query(UnreadMentionsQuery { since, after }),
+                page,
+            )
+        };
+
+        let mut pages = Vec::new();
+        let mut after = None;
+        loop {
+            let Json(response) = mentions(None, after).await?;
+            let page = response.data.unwrap();
+            assert_eq!(page.pagination.total_items, 5);
+            assert_eq!(page.pagination.current_page, pages.len() as u32 + 1);
+            after = page.pagination.next_cursor.clone();
+            pages.push(page.data);
+            if after.is_none() {
+                break;
+            }
+        }
+        let sizes: Vec<usize> = pages.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [2, 2, 1]);
+        let listed: Vec<i64> = pages.iter().flatten().map(|m| m.message_id).collect();
+        assert_eq!(listed, ids.iter().rev().copied().collect::<Vec<_>>());
+
+        // Only mentions newer than `since`, still paged
+        let since = pages[1][0].created_at;
+        let Json(response) = mentions(Some(since), None).await?;
+        let page = response.data.unwrap();
+        assert_eq!(page.pagination.total_items, 2);
+        let newer: Vec<i64> = page.data.iter().map(|m| m.message_id).collect();
+        assert_eq!(newer, [ids[4], ids[3]]);
+        assert!(page.pagination.next_cursor.is_none());
+
+        assert!(matches!(
+            mentions(None, Some("not a cursor!".to_string())).await,
+            Err(AppError::InvalidInput(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn detailed_receipts_should_page_through_every_receipt_once() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(4).await;
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Receipts {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id, users[2].id, users[3].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+        let message_id = send(&state, users[0].id, chat_id, "read me").await;
+        for reader in &users[1..] {
+            mark_messages_read_handler(
+                Extension(state.clone()),
+                Extension(crate::auth_user!(reader)),
+                Path(chat_id),
+                Json(MarkReadRequest {
+                    message_ids: vec![message_id],
+                }),
+            )
+            .await?;
+        }
+
+        let owner = crate::auth_user!(&users[0]);
+        let mut receipts = Vec::new();
+        let mut after = None;
+        let total = loop {
+            let mut page = PageParams::<ReceiptSort>::default();
+            page.page_size = 2;
+            let Json(response) = get_detailed_message_receipts_handler(
+                Extension(state.clone()),
+                Extension(owner.clone()),
+                Path(message_id),
+                Query(DetailedReceiptsQuery { after }),
+                page,
+            )
+            .await?;
+            let page = response.data.unwrap();
+            assert!(page.data.len() <= 2);
+            receipts.extend(page.data);
+            after = page.pagination.next_cursor;
+            if after.is_none() {
+                break page.pagination.total_items;
+            }
+        };
+
+        assert_eq!(receipts.len() as u64, total);
+        let mut keys: Vec<(i64, String)> = receipts
+            .iter()
+            .map(|r| (r.user_id, r.status.clone()))
+            .collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), receipts.len());
+        assert!(receipts
+            .windows(2)
+            .all(|w| w[0].timestamp >= w[1].timestamp));
+        for reader in &users[1..] {
+            assert!(keys.contains(&(i64::from(reader.id), "read".to_string())));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_all_should_reject_non_members() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(4).await;
+        let outsider = crate::auth_user!(&users[3]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Read All Private {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id, users[2].id],
+            )
+            .await?;
+
+        let result =
+            mark_chat_read_handler(Extension(state), Extension(outsider), Path(chat.id.into()))
+                .await;
+        assert!(matches!(result, Err(AppError::ChatAccessDenied { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn oversized_client_message_id_should_fail_validation() {
+        let request: SendMessageRequest = serde_json::from_value(serde_json::json!({
+            "content": "hello",
+            "client_message_id": "x".repeat(65)
+        }))
+        .unwrap();
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn legacy_and_new_attachments_should_share_one_shape() {
+        let mut legacy = message_view(1, 7);
+        legacy.files = Some(vec!["/files/2f5d1c.png".to_string()]);
+        let mut current = message_view(2, 7);
+        current.files = Some(vec![serde_json::json!({
+            "url": "/files/9a0b3e.pdf",
+            "filename": "quarterly report.pdf",
+            "mime_type": "application/pdf",
+            "size": 20480
+        })
+        .to_string()]);
+
+        let legacy = MessageResponse::from(legacy);
+        assert_eq!(
+            legacy.attachments,
+            vec![Attachment {
+                url: "/files/2f5d1c.png".to_string(),
+                filename: "2f5d1c.png".to_string(),
+                mime_type: "image/png".to_string(),
+                size: None,
+            }]
+        );
+        let current = MessageResponse::from(current);
+        assert_eq!(current.attachments[0].filename, "quarterly report.pdf");
+        assert_eq!(current.attachments[0].size, Some(20480));
+
+        for response in [&legacy, &current] {
+            let body = serde_json::to_value(response).unwrap();
+            let attachment = body["attachments"][0].as_object().unwrap();
+            let mut keys: Vec<&str> = attachment.keys().map(String::as_str).collect();
+            keys.sort_unstable();
+            assert_eq!(keys, ["filename", "mime_type", "size", "url"]);
+        }
+    }
+
+    async fn slow_mode_group(
+        state: &AppState,
+        users: &[fechatter_core::User],
+        slow_mode_secs: i32,
+    ) -> i64 {
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Slow {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                users[1..].iter().map(|user| user.id).collect(),
+            )
+            .await
+            .unwrap();
+        let chat_id = i64::from(chat.id);
+        crate::handlers::chat::set_slow_mode_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(&users[0])),
+            Path(chat_id),
+            Json(crate::handlers::chat::SetSlowModeRequest { slow_mode_secs }),
+        )
+        .await
+        .unwrap();
+        chat_id
+    }
+
+    async fn send_as(
+        state: &AppState,
+        user: &fechatter_core::User,
+        chat_id: i64,
+    ) -> Result<Json<ApiResponse<MessageResponse>>, AppError> {
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": "slow down" })).unwrap();
+        send_message_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(user)),
+            Path(chat_id),
+            Json(request),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn slow_mode_should_throttle_members_until_the_cooldown_expires() {
+        let (state, users) = crate::setup_test_users!(3).await;
+        let chat_id = slow_mode_group(&state, &users, 1).await;
+
+        send_as(&state, &users[1], chat_id).await.unwrap();
+        let error = send_as(&state, &users[1], chat_id).await.unwrap_err();
+        let AppError::RateLimited(decision) = &error else {
+            panic!("expected a slow mode 429, got {:?}", error);
+        };
+        assert_eq!(decision.retry_after_secs(), 1);
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["retry-after"], "1");
+        // The cooldown is per member
+        send_as(&state, &users[2], chat_id).await.unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        send_as(&state, &users[1], chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn slow_mode_should_not_throttle_chat_admins() {
+        let (state, users) = crate::setup_test_users!(3).await;
+        let chat_id = slow_mode_group(&state, &users, 60).await;
+        sqlx::query("UPDATE chat_members SET role = 'admin' WHERE chat_id = $1 AND user_id = $2")
+            .bind(chat_id)
+            .bind(i64::from(users[1].id))
+            .execute(&*state.pool())
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            send_as(&state, &users[0], chat_id).await.unwrap();
+            send_as(&state, &users[1], chat_id).await.unwrap();
+        }
+        send_as(&state, &users[2], chat_id).await.unwrap();
+        assert!(matches!(
+            send_as(&state, &users[2], chat_id).await,
+            Err(AppError::RateLimited(_))
+        ));
+
+        // Setting slow mode is reserved to chat admins
+        let denied = crate::handlers::chat::set_slow_mode_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(&users[2])),
+            Path(chat_id),
+            Json(crate::handlers::chat::SetSlowModeRequest { slow_mode_secs: 0 }),
+        )
+        .await;
+        assert!(denied.is_err());
+    }
+
+    async fn edit_as(
+        state: &AppState,
+        user: &fechatter_core::User,
+        chat_id: i64,
+        message_id: i64,
+    ) -> Result<Json<ApiResponse<()>>, AppError> {
+        edit_message_handler(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(user)),
+            Path((chat_id, message_id)),
+            Json(EditMessageRequest {
+                content: "edited".to_string(),
+            }),
+        )
+        .await
+    }
+
+    /// Group owned by `users[0]`, sent to by `users[1]`; returns (chat, message sent by each)
+    async fn edit_window_chat(state: &AppState, users: &[fechatter_core::User]) -> (i64, i64, i64) {
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Edit Window {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                users[1..].iter().map(|user| user.id).collect(),
+            )
+            .await
+            .unwrap();
+        let chat_id = i64::from(chat.id);
+        let owner_message = send(state, users[0].id, chat_id, "from the owner").await;
+        let member_message = send(state, users[1].id, chat_id, "from a member").await;
+        (chat_id, owner_message, member_message)
+    }
+
+    /// Move a message's send time past the edit window
+    async fn close_edit_window(state: &AppState, message_id: i64) {
+        let window = state.config.features.message_limits.edit_window_secs as i32;
+        sqlx::query(
+            "UPDATE messages SET created_at = created_at - ($2 * INTERVAL '1 second') WHERE id = $1",
+        )
+        .bind(message_id)
+        .bind(window + 60)
+        .execute(&*state.pool())
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn edit_window_should_count_down_to_zero() {
+        let sent = chrono::Utc::now();
+        let at = |secs| sent + chrono::Duration::seconds(secs);
+
+        assert_eq!(edit_window_remaining(sent, 900, at(0)), Some(900));
+        assert_eq!(edit_window_remaining(sent, 900, at(600)), Some(300));
+        assert_eq!(edit_window_remaining(sent, 900, at(3600)), Some(0));
+        assert_eq!(edit_window_remaining(sent, 0, at(3600)), None);
+    }
+
+    #[tokio::test]
+    async fn edit_within_the_window_should_succeed_and_report_the_time_left() {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let (chat_id, _, member_message) = edit_window_chat(&state, &users).await;
+        let window = state.config.features.message_limits.edit_window_secs;
+
+        let Json(sent) = send_as(&state, &users[1], chat_id).await.unwrap();
+        let left = sent.data.unwrap().editable_for_secs.unwrap();
+        assert!(
+            left <= window && left + 5 >= window,
+            "{} of {}",
+            left,
+            window
+        );
+
+        edit_as(&state, &users[1], chat_id, member_message)
+            .await
+            .unwrap();
+        let content: String = sqlx::query_scalar("SELECT content FROM messages WHERE id = $1")
+            .bind(member_message)
+            .fetch_one(&*state.pool())
+            .await
+            .unwrap();
+        assert_eq!(content, "edited");
+    }
+
+    #[tokio::test]
+    async fn edit_past_the_window_should_be_rejected() {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let (chat_id, _, member_message) = edit_window_chat(&state, &users).await;
+        close_edit_window(&state, member_message).await;
+
+        let error = edit_as(&state, &users[1], chat_id, member_message)
+            .await
+            .unwrap_err();
+
+        let AppError::InvalidInput(reason) = &error else {
+            panic!("expected an edit window validation error, got {:?}", error);
+        };
+        assert!(reason.contains("can only be edited within"), "{}", reason);
+        assert_eq!(error.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn chat_admins_should_edit_past_the_window() {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let (chat_id, owner_message, _) = edit_window_chat(&state, &users).await;
+        close_edit_window(&state, owner_message).await;
+
+        edit_as(&state, &users[0], chat_id, owner_message)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn ndjson_should_be_chosen_only_when_accepted() {
+        let accept = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, HeaderValue::from_static(value));
+            accepts_ndjson(&headers)
+        };
+
+        assert!(accept("application/x-ndjson"));
+        assert!(accept(
+            "application/json;q=0.5, application/x-ndjson; charset=utf-8"
+        ));
+        assert!(!accept("application/json"));
+        assert!(!accepts_ndjson(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn ndjson_list_should_stream_the_same_messages_line_by_line() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let member = crate::auth_user!(&users[1]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Ndjson {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+        for n in 0..3 {
+            send(&state, users[0].id, chat_id, &format!("message {}", n)).await;
+        }
+
+        let list = |accept: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, HeaderValue::from_static(accept));
+            let mut page = PageParams::<MessageSort>::default();
+            page.page_size = 2;
+            list_messages_handler(
+                Extension(state.clone()),
+                Extension(member.clone()),
+                Path(chat_id),
+                Query(ListMessagesQuery {
+                    before: None,
+                    after: None,
+                }),
+                page,
+                headers,
+            )
+        };
+        // The time left to edit keeps counting down between the two calls
+        let without_edit_window = |mut message: serde_json::Value| {
+            message.as_object_mut().unwrap().remove("editable_for_secs");
+            message
+        };
+
+        let array = list("application/json").await?;
+        let body = axum::body::to_bytes(array.into_body(), usize::MAX).await?;
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        let expected: Vec<serde_json::Value> = body["data"]["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .cloned()
+            .map(without_edit_window)
+            .collect();
+        assert_eq!(expected.len(), 2);
+
+        let ndjson = list(NDJSON).await?;
+        assert_eq!(ndjson.headers()[header::CONTENT_TYPE], NDJSON);
+        assert_eq!(ndjson.headers()[X_TOTAL_COUNT], "3");
+        assert_eq!(
+            ndjson.headers()[X_NEXT_CURSOR],
+            body["data"]["pagination"]["next_cursor"].as_str().unwrap()
+        );
+        let body = axum::body::to_bytes(ndjson.into_body(), usize::MAX).await?;
+        let body = std::str::from_utf8(&body)?;
+        assert!(body.ends_with('\n'));
+        let lines = body
+            .lines()
+            .map(|line| serde_json::from_str(line).map(without_edit_window))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(lines, expected);
+        Ok(())
+    }
+}
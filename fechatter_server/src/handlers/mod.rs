@@ -1,14 +1,31 @@
+pub mod admin_dashboard;
+pub mod admin_users;
+pub mod announcements;
 pub mod auth;
+pub mod auth_context;
 pub mod bot;
+pub mod cache_admin;
 pub mod cache_stats;
 pub mod chat;
 pub mod chat_members;
+pub mod chat_names;
+pub mod conditional;
+pub mod config_reload;
+pub mod embedding_backfill;
+pub mod event_replay;
 pub mod files;
 pub mod health;
+pub mod maintenance;
 pub mod messages;
+pub mod page_params;
+pub mod rate_limits;
 pub mod realtime;
+pub mod retention;
 pub mod search;
+pub mod sync;
 pub mod users;
+pub mod webhooks;
+pub mod workspace_limits;
 pub mod workspaces;
 
 pub use health::*;
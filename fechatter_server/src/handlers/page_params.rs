@@ -0,0 +1,263 @@
+//! # Pagination Query Extractor
+//!
+//! **Responsibility**: Parse `page`/`page_size` (or `limit`) and `sort`/`order` uniformly
+//! **Limits**: Page size is clamped to the global `PaginationConfig`; sort fields are checked
+//! against a per-endpoint allowlist and rejected with 400 otherwise
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use serde::Deserialize;
+
+use crate::dtos::core::{PaginationRequest, SortDirection, SortField};
+use crate::dtos::get_dto_manager;
+use crate::AppError;
+
+/// Per-endpoint sort allowlist and defaults
+pub trait SortFields: Send + Sync + 'static {
+    /// Fields clients may sort by; empty means the endpoint has a fixed order
+    const ALLOWED: &'static [&'static str];
+
+    /// Page size when the client sends none (falls back to `PaginationConfig`)
+    const DEFAULT_PAGE_SIZE: Option<u32> = None;
+}
+
+/// Validated pagination and sort parameters
+pub struct PageParams<F> {
+    pub page: u32,
+    pub page_size: u32,
+    pub sort: Option<SortField>,
+    _fields: PhantomData<F>,
+}
+
+impl<F: SortFields> Default for PageParams<F> {
+    /// First page at the endpoint's default size, unsorted
+    fn default() -> Self {
+        let pagination = get_dto_manager().pagination_request(None, F::DEFAULT_PAGE_SIZE);
+        Self {
+            page: pagination.page,
+            page_size: pagination.page_size,
+            sort: None,
+            _fields: PhantomData,
+        }
+    }
+}
+
+impl<F> fmt::Debug for PageParams<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PageParams")
+            .field("page", &self.page)
+            .field("page_size", &self.page_size)
+            .field("sort", &self.sort)
+            .finish()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPageParams {
+    page: Option<u32>,
+    page_size: Option<u32>,
+    limit: Option<u32>,
+    sort: Option<String>,
+    order: Option<String>,
+}
+
+impl<F: SortFields> PageParams<F> {
+    fn from_raw(raw: RawPageParams) -> Result<Self, AppError> {
+        let requested_size = raw.page_size.or(raw.limit).or(F::DEFAULT_PAGE_SIZE);
+        let pagination = get_dto_manager().pagination_request(raw.page, requested_size);
+        let sort = parse_sort::<F>(raw.sort.as_deref(), raw.order.as_deref())?;
+
+        Ok(Self {
+            page: pagination.page,
+            page_size: pagination.page_size,
+            sort,
+            _fields: PhantomData,
+        })
+    }
+
+    /// Row offset of the requested page
+    pub fn offset(&self) -> i64 {
+        i64::from(self.page - 1) * i64::from(self.page_size)
+    }
+
+    /// Row limit of the requested page
+    pub fn limit(&self) -> i64 {
+        i64::from(self.page_size)
+    }
+
+    /// Requested sort as `(field, descending)` for repository queries
+    pub fn sort_key(&self) -> Option<(&str, bool)> {
+        self.sort.as_ref().map(|sort| {
+            (
+                sort.field.as_str(),
+                matches!(sort.direction, SortDirection::Descending),
+            )
+        })
+    }
+
+    /// Equivalent `PaginationRequest` for `DtoManager` responses
+    pub fn pagination_request(&self) -> PaginationRequest {
+        let request = PaginationRequest::new(self.page, self.page_size);
+        match &self.sort {
+            Some(sort) => request.with_sort(sort.field.clone(), sort.direction.clone()),
+            None => request,
+        }
+    }
+}
+
+fn parse_sort<F: SortFields>(
+    sort: Option<&str>,
+    order: Option<&str>,
+) -> Result<Option<SortField>, AppError> {
+    let Some(sort) = sort.map(str::trim).filter(|sort| !sort.is_empty()) else {
+        return Ok(None);
+    };
+
+    // `-field` is shorthand for descending
+    let (field, mut direction) = match sort.strip_prefix('-') {
+        Some(field) => (field, SortDirection::Descending),
+        None => (sort, SortDirection::Ascending),
+    };
+
+    if !F::ALLOWED.contains(&field) {
+        return Err(AppError::InvalidInput(if F::ALLOWED.is_empty() {
+            "Sorting is not supported for this endpoint".to_string()
+        } else {
+            format!(
+                "Invalid sort field '{}', expected one of: {}",
+                field,
+                F::ALLOWED.join(", ")
+            )
+        }));
+    }
+
+    if let Some(order) = order {
+        direction = match order.to_ascii_lowercase().as_str() {
+            "asc" => SortDirection::Ascending,
+            "desc" => SortDirection::Descending,
+            other => {
+                return Err(AppError::InvalidInput(format!(
+                    "Invalid sort order '{}', expected 'asc' or 'desc'",
+                    other
+                )))
+            }
+        };
+    }
+
+    Ok(Some(SortField {
+        field: field.to_string(),
+        direction,
+    }))
+}
+
+impl<S, F> FromRequestParts<S> for PageParams<F>
+where
+    S: Send + Sync,
+    F: SortFields,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Query(raw) = Query::<RawPageParams>::try_from_uri(&parts.uri)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid pagination parameters: {}", e)))?;
+
+        Self::from_raw(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{Request, StatusCode};
+    use axum::response::IntoResponse;
+
+    struct UserSort;
+
+    impl SortFields for UserSort {
+        const ALLOWED: &'static [&'static str] = &["fullname", "created_at"];
+    }
+
+    struct FixedOrder;
+
+    impl SortFields for FixedOrder {
+        const ALLOWED: &'static [&'static str] = &[];
+        const DEFAULT_PAGE_SIZE: Option<u32> = Some(50);
+    }
+
+    async fn extract<F: SortFields>(uri: &str) -> Result<PageParams<F>, AppError> {
+        let (mut parts, _) = Request::builder().uri(uri).body(()).unwrap().into_parts();
+        PageParams::<F>::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn defaults_should_apply_without_params() {
+        let params = extract::<UserSort>("/users").await.unwrap();
+        assert_eq!(params.page, 1);
+        assert_eq!(params.page_size, 20);
+        assert!(params.sort.is_none());
+        assert_eq!(params.offset(), 0);
+
+        let params = extract::<FixedOrder>("/messages").await.unwrap();
+        assert_eq!(params.page_size, 50);
+    }
+
+    #[tokio::test]
+    async fn page_size_should_be_clamped() {
+        let params = extract::<UserSort>("/users?page=3&page_size=5000")
+            .await
+            .unwrap();
+        assert_eq!(params.page_size, 100);
+        assert_eq!(params.offset(), 200);
+
+        let params = extract::<FixedOrder>("/messages?limit=0&page=0")
+            .await
+            .unwrap();
+        assert_eq!(params.page, 1);
+        assert_eq!(params.page_size, 1);
+    }
+
+    #[tokio::test]
+    async fn allowed_sort_should_parse_direction() {
+        let params = extract::<UserSort>("/users?sort=-created_at")
+            .await
+            .unwrap();
+        let sort = params.sort.unwrap();
+        assert_eq!(sort.field, "created_at");
+        assert!(matches!(sort.direction, SortDirection::Descending));
+
+        let params = extract::<UserSort>("/users?sort=fullname&order=DESC")
+            .await
+            .unwrap();
+        assert!(matches!(
+            params.sort.unwrap().direction,
+            SortDirection::Descending
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_sort_should_be_rejected_with_400() {
+        for uri in [
+            "/users?sort=password_hash",
+            "/users?sort=fullname&order=sideways",
+            "/users?page_size=-1",
+        ] {
+            let error = extract::<UserSort>(uri).await.unwrap_err();
+            assert_eq!(
+                error.into_response().status(),
+                StatusCode::BAD_REQUEST,
+                "{}",
+                uri
+            );
+        }
+
+        let error = extract::<FixedOrder>("/messages?sort=created_at")
+            .await
+            .unwrap_err();
+        assert_eq!(error.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+}
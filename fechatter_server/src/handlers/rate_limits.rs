@@ -0,0 +1,109 @@
+//! # Rate Limit Admin Handlers
+//!
+//! **Responsibility**: Let workspace admins inspect and clear a user's rate-limit buckets
+//! **Scope**: Per-user Redis keys (`rate_limit:{user_id}:*`) and the user's login bucket
+//! (`rate_limit:login:{email}`), which is keyed by email because signin runs before the user is
+//! known
+
+use axum::{
+    extract::{Extension, Path},
+    response::Json,
+};
+use serde::Serialize;
+use tracing::{info, instrument};
+
+use crate::dtos::core::ApiResponse;
+use crate::services::infrastructure::rate_limit::RateLimitBucket;
+use crate::{AppError, AppState};
+use fechatter_core::{AuthUser, User, UserId};
+
+/// Rate limit state for one user
+#[derive(Debug, Serialize)]
+pub struct UserRateLimitsResponse {
+    pub user_id: i64,
+    pub buckets: Vec<RateLimitBucket>,
+}
+
+/// Rate limit reset result
+#[derive(Debug, Serialize)]
+pub struct RateLimitResetResponse {
+    pub user_id: i64,
+    pub buckets_cleared: u64,
+}
+
+/// Get a user's rate limit buckets (workspace admin only)
+#[instrument(skip(state), fields(user_id = %user_id, admin_id = %user.id))]
+pub async fn get_user_rate_limits_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(user_id): Path<i64>,
+) -> Result<Json<ApiResponse<UserRateLimitsResponse>>, AppError> {
+    let target = ensure_workspace_admin_for(&state, &user, user_id).await?;
+
+    let limiters = state.rate_limiters();
+    let mut buckets = limiters.user_buckets(user_id).await?;
+    buckets.extend(limiters.login_buckets(&target.email).await?);
+
+    Ok(Json(ApiResponse::success(
+        UserRateLimitsResponse { user_id, buckets },
+        "rate_limits_retrieved".to_string(),
+    )))
+}
+
+/// Reset a user's rate limit buckets (workspace admin only, audited)
+#[instrument(skip(state), fields(user_id = %user_id, admin_id = %user.id))]
+pub async fn reset_user_rate_limits_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(user_id): Path<i64>,
+) -> Result<Json<ApiResponse<RateLimitResetResponse>>, AppError> {
+    let target = ensure_workspace_admin_for(&state, &user, user_id).await?;
+
+    let limiters = state.rate_limiters();
+    let buckets_cleared =
+        limiters.reset_user(user_id).await? + limiters.reset_login(&target.email).await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      user_id = %user_id,
+      buckets_cleared = %buckets_cleared,
+      "[AUDIT] Rate limit buckets reset"
+    );
+
+    Ok(Json(ApiResponse::success(
+        RateLimitResetResponse {
+            user_id,
+            buckets_cleared,
+        },
+        "rate_limits_reset".to_string(),
+    )))
+}
+
+/// Requester must administer their workspace and the target user must belong to it; returns
+/// the target user
+async fn ensure_workspace_admin_for(
+    state: &AppState,
+    user: &AuthUser,
+    target_user_id: i64,
+) -> Result<User, AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+
+    let user_repo = crate::domains::user::repository::UserRepositoryImpl::new(state.pool());
+    let target = user_repo
+        .find_by_id_ext(UserId(target_user_id))
+        .await?
+        .ok_or_else(|| AppError::NotFound(vec![format!("User {} not found", target_user_id)]))?;
+    if target.workspace_id != user.workspace_id {
+        return Err(AppError::Forbidden(
+            "User is not a member of your workspace".to_string(),
+        ));
+    }
+
+    Ok(target)
+}
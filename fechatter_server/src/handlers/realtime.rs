@@ -2,6 +2,7 @@
 //!
 //! These handlers manage ephemeral state and broadcast events to notify-server
 
+use crate::services::infrastructure::cache::CacheKeyBuilder;
 use crate::{AppError, AppState};
 use axum::{
     extract::{Path, State},
@@ -9,7 +10,7 @@ use axum::{
     Extension,
 };
 use chrono::Utc;
-use fechatter_core::AuthUser;
+use fechatter_core::{AuthUser, PresenceStatus};
 use serde::Deserialize;
 use serde_json::{json, Value};
 
@@ -18,7 +19,8 @@ pub struct PresenceUpdate {
     pub status: String, // "online", "away", "offline"
 }
 
-/// Start typing indicator
+/// Start typing indicator. Starts repeated within the typing interval refresh the indicator
+/// but are not pushed again; `pushed` tells whether this one went out
 pub async fn start_typing(
     Extension(state): Extension<AppState>,
     Extension(auth): Extension<AuthUser>,
@@ -30,26 +32,38 @@ pub async fn start_typing(
         .ensure_user_is_chat_member(auth.id.into(), chat_id)
         .await?;
 
-    // 2. Update typing state using TypingIndicatorService
-    let typing_service = state.application_services().typing_indicator_service();
-    typing_service
-        .set_typing(chat_id, auth.id.into(), auth.fullname.clone(), true)
+    // 2. Update typing state in the shared presence store
+    state
+        .presence_store()
+        .set_typing(fechatter_core::ChatId(chat_id), auth.id, &auth.fullname)
         .await
         .map_err(|e| AppError::Internal(format!("Failed to set typing state: {}", e)))?;
 
-    // 3. Publish typing event through message service
-    let message_service = state.application_services().message_service();
-    message_service
-        .start_typing(
-            fechatter_core::ChatId(chat_id),
-            fechatter_core::UserId(auth.id.into()),
-            auth.fullname.clone(),
-        )
-        .await
-        .map_err(|e| AppError::Internal(format!("Failed to publish typing event: {}", e)))?;
+    // 3. Coalesce into the event already pushed within the typing interval, if any
+    let pushed = match state.rate_limiters().typing_push() {
+        Some(limiter) => limiter
+            .enforce(&CacheKeyBuilder::typing_push(chat_id, i64::from(auth.id)))
+            .await
+            .is_ok(),
+        None => true,
+    };
+
+    // 4. Publish typing event through message service
+    if pushed {
+        let message_service = state.application_services().message_service();
+        message_service
+            .start_typing(
+                fechatter_core::ChatId(chat_id),
+                fechatter_core::UserId(auth.id.into()),
+                auth.fullname.clone(),
+            )
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to publish typing event: {}", e)))?;
+    }
 
     Ok(Json(json!({
-        "status": "ok"
+        "status": "ok",
+        "pushed": pushed
     })))
 }
 
@@ -66,14 +80,9 @@ pub async fn stop_typing(
         .await?;
 
     // 2. Update typing state
-    let typing_service = state.application_services().typing_indicator_service();
-    typing_service
-        .set_typing(
-            chat_id,
-            auth.id.into(),
-            String::new(), // Name not needed for stop
-            false,
-        )
+    state
+        .presence_store()
+        .clear_typing(fechatter_core::ChatId(chat_id), auth.id)
         .await
         .map_err(|e| AppError::Internal(format!("Failed to clear typing state: {}", e)))?;
 
@@ -128,15 +137,19 @@ pub async fn update_presence(
     Json(req): Json<PresenceUpdate>,
 ) -> Result<Json<Value>, AppError> {
     // 1. Validate status
-    let valid_statuses = ["online", "away", "offline"];
-    if !valid_statuses.contains(&req.status.as_str()) {
-        return Err(AppError::BadRequest(format!(
-            "Invalid status. Must be one of: {:?}",
-            valid_statuses
-        )));
-    }
+    let status = req
+        .status
+        .parse::<PresenceStatus>()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
 
-    // 2. Publish presence event through message service (no local state management)
+    // 2. Record presence in the shared store
+    state
+        .presence_store()
+        .set_online(auth.id, status)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to update presence: {}", e)))?;
+
+    // 3. Publish presence event through message service
     let message_service = state.application_services().message_service();
     message_service
         .update_user_presence(
@@ -165,20 +178,23 @@ pub async fn get_typing_users(
         .ensure_user_is_chat_member(auth.id.into(), chat_id)
         .await?;
 
-    // Get typing users from typing service
-    let typing_service = state.application_services().typing_indicator_service();
-    let typing_users = typing_service.get_typing_users(chat_id).await;
+    // Get typing users from the shared presence store
+    let typing_users = state
+        .presence_store()
+        .get_typing(fechatter_core::ChatId(chat_id))
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to get typing state: {}", e)))?;
 
     // Filter out self and format response
-    let auth_user_id: i64 = auth.id.into();
+    let now = Utc::now();
     let typing_data: Vec<_> = typing_users
         .into_iter()
-        .filter(|u| u.user_id != auth_user_id)
+        .filter(|u| u.user_id != auth.id)
         .map(|u| {
             json!({
                 "user_id": u.user_id,
                 "user_name": u.user_name,
-                "started_at": u.started_at.elapsed().as_secs()
+                "started_at": (now - u.started_at).num_seconds()
             })
         })
         .collect();
@@ -189,34 +205,44 @@ pub async fn get_typing_users(
     })))
 }
 
-/// Get read receipts for a message
+/// Get delivery and read receipts for a message, one entry per recipient
 pub async fn get_message_receipts(
     Extension(state): Extension<AppState>,
     Extension(auth): Extension<AuthUser>,
     Path(message_id): Path<i64>,
 ) -> Result<Json<Value>, AppError> {
-    // Skip message and chat validation for now
-    // TODO: Add proper message access validation
-
-    // Get read receipts from message service
-    // Note: This requires implementing get_read_receipts in the domain service
-    // For now, we'll return empty until the domain service is updated
-    let receipts = Vec::<Value>::new();
-
-    // TODO: When domain service supports get_read_receipts:
-    // let receipts = message_service
-    //     .get_read_receipts(message_id)
-    //     .await
-    //     .map_err(|e| AppError::Internal(e.to_string()))?
-    //     .into_iter()
-    //     .map(|(user_id, read_at)| json!({
-    //         "user_id": user_id,
-    //         "read_at": read_at.to_rfc3339()
-    //     }))
-    //     .collect();
+    // 1. Resolve the message and validate chat membership
+    let message_service = state.application_services().message_service();
+    let message = message_service
+        .get_message(fechatter_core::MessageId(message_id), auth.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(vec![format!("Message {} not found", message_id)]))?;
+
+    let chat_service = state.application_services().chat_application_service();
+    chat_service
+        .ensure_user_is_chat_member(auth.id.into(), message.chat_id)
+        .await?;
+
+    // 2. Collapse delivered/read receipts per user
+    let receipts: Vec<Value> = message_service
+        .domain_service()
+        .get_message_receipt_states(message_id)
+        .await
+        .map_err(AppError::from)?
+        .into_iter()
+        .map(|receipt| {
+            json!({
+                "user_id": receipt.user_id,
+                "status": receipt.status(),
+                "delivered_at": receipt.delivered_at.map(|at| at.to_rfc3339()),
+                "read_at": receipt.read_at.map(|at| at.to_rfc3339())
+            })
+        })
+        .collect();
 
     Ok(Json(json!({
         "message_id": message_id,
+        "chat_id": message.chat_id,
         "receipts": receipts
     })))
 }
@@ -231,4 +257,43 @@ mod tests {
         let req: PresenceUpdate = serde_json::from_str(json).unwrap();
         assert_eq!(req.status, "online");
     }
+
+    #[tokio::test]
+    async fn burst_of_typing_starts_should_push_a_single_event() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Typing Burst {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let chat_id = i64::from(chat.id);
+
+        let mut pushed = 0;
+        for _ in 0..5 {
+            let Json(response) = start_typing(
+                Extension(state.clone()),
+                Extension(crate::auth_user!(&users[0])),
+                Path(chat_id),
+            )
+            .await?;
+            if response["pushed"] == json!(true) {
+                pushed += 1;
+            }
+        }
+        assert_eq!(pushed, 1);
+
+        // Coalesced starts still keep the indicator up for the other member
+        let Json(typing) = get_typing_users(
+            Extension(state.clone()),
+            Extension(crate::auth_user!(&users[1])),
+            Path(chat_id),
+        )
+        .await?;
+        assert_eq!(typing["typing_users"][0]["user_id"], json!(users[0].id));
+        Ok(())
+    }
 }
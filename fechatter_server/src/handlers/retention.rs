@@ -0,0 +1,98 @@
+//! # Message Retention Admin Handlers
+//!
+//! **Responsibility**: Let workspace admins read and set their message retention window
+//! **Scope**: Per workspace; `null` keeps messages forever, expired ones are tombstoned by the
+//! background retention sweep
+
+use axum::{extract::Extension, response::Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::domains::workspace::repository::WorkspaceRepositoryImpl;
+use crate::dtos::core::ApiResponse;
+use crate::{AppError, AppState};
+use fechatter_core::AuthUser;
+
+/// Longest configurable retention window (10 years)
+const MAX_RETENTION_DAYS: i32 = 3650;
+
+/// Retention update request; `null` means unlimited
+#[derive(Debug, Deserialize)]
+pub struct SetRetentionRequest {
+    pub retention_days: Option<i32>,
+}
+
+/// Current retention window of the workspace
+#[derive(Debug, Serialize)]
+pub struct RetentionResponse {
+    pub workspace_id: i64,
+    pub retention_days: Option<i32>,
+}
+
+/// Get the workspace message retention window (workspace admins only)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn get_retention_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<ApiResponse<RetentionResponse>>, AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+
+    let retention_days = WorkspaceRepositoryImpl::new(state.pool())
+        .get_message_retention_days(user.workspace_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        RetentionResponse {
+            workspace_id: user.workspace_id.into(),
+            retention_days,
+        },
+        "retention_retrieved".to_string(),
+    )))
+}
+
+/// Set the workspace message retention window (workspace admins only, audited)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn set_retention_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<SetRetentionRequest>,
+) -> Result<Json<ApiResponse<RetentionResponse>>, AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+
+    if let Some(days) = request.retention_days {
+        if !(1..=MAX_RETENTION_DAYS).contains(&days) {
+            return Err(AppError::BadRequest(format!(
+                "retention_days must be between 1 and {}, or null for unlimited",
+                MAX_RETENTION_DAYS
+            )));
+        }
+    }
+
+    WorkspaceRepositoryImpl::new(state.pool())
+        .set_message_retention_days(user.workspace_id, request.retention_days)
+        .await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      retention_days = ?request.retention_days,
+      "[AUDIT] Message retention changed"
+    );
+
+    Ok(Json(ApiResponse::success(
+        RetentionResponse {
+            workspace_id: user.workspace_id.into(),
+            retention_days: request.retention_days,
+        },
+        "retention_updated".to_string(),
+    )))
+}
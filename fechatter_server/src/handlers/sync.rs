@@ -0,0 +1,250 @@
+//! # Delta Sync Handler
+//!
+//! **Responsibility**: One call that brings an offline client up to date across all its chats
+//! **Scope**: `GET /api/sync?since={cursor}` returns what changed since the cursor and a
+//! `next_cursor` for the following call. Without `since` the delta is empty and the cursor
+//! marks the current state, so a client takes one right before a full load
+
+use axum::{
+    extract::{Extension, Query},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::domains::messaging::repository::MessageRepository;
+use crate::domains::messaging::sync::{
+    MembershipChange, MessageTombstone, ReadStateChange, SyncCursor, SyncRepository,
+};
+use crate::dtos::core::{decode_cursor, encode_cursor, ApiResponse, BatchResponseDto};
+use crate::handlers::messages::MessageResponse;
+use crate::services::application::workers::message::MessageView;
+use crate::{AppError, AppState};
+use fechatter_core::{AuthUser, UserId};
+
+/// Message changes per call when the query doesn't say
+const DEFAULT_SYNC_LIMIT: u32 = 500;
+/// Most message changes per call
+const MAX_SYNC_LIMIT: u32 = 1000;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SyncQuery {
+    /// `next_cursor` of the previous sync
+    pub since: Option<String>,
+    /// Most message changes to return
+    pub limit: Option<u32>,
+}
+
+/// Changes since the cursor. Apply them in order: upsert `messages`, drop `deleted_messages`,
+/// then apply `memberships` and `read_states`; the same change may arrive twice
+#[derive(Debug, Serialize)]
+pub struct SyncResponse {
+    /// Messages created or edited since the cursor, in their current state
+    pub messages: Vec<MessageResponse>,
+    /// Tombstones of messages deleted since the cursor
+    pub deleted_messages: Vec<MessageTombstone>,
+    pub memberships: Vec<MembershipChange>,
+    /// The caller's own read watermarks
+    pub read_states: Vec<ReadStateChange>,
+    pub next_cursor: String,
+    /// More changes are waiting; sync again with `next_cursor` right away
+    pub has_more: bool,
+}
+
+/// Delta Sync Handler
+#[instrument(skip(state, query), fields(user_id = %user.id))]
+pub async fn sync_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Query(query): Query<SyncQuery>,
+) -> Result<Json<ApiResponse<SyncResponse>>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_SYNC_LIMIT);
+    if limit == 0 || limit > MAX_SYNC_LIMIT {
+        return Err(AppError::InvalidInput(format!(
+            "limit must be between 1 and {}",
+            MAX_SYNC_LIMIT
+        )));
+    }
+
+    let repository = SyncRepository::new(state.pool());
+    let Some(since) = query.since.as_deref() else {
+        let now = repository.now().await?;
+        return Ok(Json(ApiResponse::success(
+            SyncResponse {
+                messages: Vec::new(),
+                deleted_messages: Vec::new(),
+                memberships: Vec::new(),
+                read_states: Vec::new(),
+                next_cursor: encode_cursor(&SyncCursor::overlapping(now)),
+                has_more: false,
+            },
+            "sync_cursor_created".to_string(),
+        )));
+    };
+    let since =
+        decode_cursor::<SyncCursor>(since).map_err(|e| AppError::InvalidInput(e.message))?;
+
+    let changes = repository
+        .changes_since(UserId::from(user.id), since, i64::from(limit))
+        .await?;
+
+    let views: Vec<MessageView> = changes
+        .messages
+        .into_iter()
+        .map(MessageView::from)
+        .collect();
+    let sender_lookup = MessageRepository::new(state.pool());
+    let senders = MessageResponse::prefetch(&views, &sender_lookup)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    let messages = views
+        .iter()
+        .map(|view| {
+            MessageResponse::from_domain_prefetched(view, &senders)
+                .map(|message| message.with_edit_window(&state))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success(
+        SyncResponse {
+            messages,
+            deleted_messages: changes.tombstones,
+            memberships: changes.memberships,
+            read_states: changes.read_states,
+            next_cursor: encode_cursor(&changes.next_cursor),
+            has_more: changes.has_more,
+        },
+        "sync_delta_retrieved".to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::models::requests::message::SendMessageRequest;
+    use fechatter_core::{ChatId, CreateMessage, MessageId};
+
+    async fn send(state: &AppState, sender: UserId, chat_id: i64, content: &str) -> i64 {
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": content })).unwrap();
+        state
+            .application_services()
+            .message_service()
+            .send_message(sender, ChatId::from(chat_id), CreateMessage::from(request))
+            .await
+            .unwrap()
+            .id
+    }
+
+    #[tokio::test]
+    async fn edits_and_deletes_after_the_cursor_should_be_in_the_delta() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let member = crate::auth_user!(&users[1]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Sync {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+
+        let edited = send(&state, users[0].id, chat_id, "first draft").await;
+        let deleted = send(&state, users[0].id, chat_id, "regrettable").await;
+        let untouched = send(&state, users[0].id, chat_id, "unchanged").await;
+
+        // Exact cursor rather than the overlapping one handed to clients, so that the messages
+        // above are strictly older than it
+        let now = SyncRepository::new(state.pool()).now().await?;
+        let since = encode_cursor(&SyncCursor::at(now));
+
+        let message_service = state.application_services().message_service();
+        message_service
+            .edit_message(
+                MessageId::from(edited),
+                users[0].id,
+                "final draft".to_string(),
+            )
+            .await?;
+        message_service
+            .delete_message(MessageId::from(deleted), users[0].id)
+            .await?;
+        let created = send(&state, users[0].id, chat_id, "new while offline").await;
+
+        let Json(response) = sync_handler(
+            Extension(state.clone()),
+            Extension(member.clone()),
+            Query(SyncQuery {
+                since: Some(since),
+                limit: None,
+            }),
+        )
+        .await?;
+        let delta = response.data.unwrap();
+
+        let message_ids: Vec<i64> = delta.messages.iter().map(|m| m.id).collect();
+        assert_eq!(message_ids, vec![edited, created]);
+        assert_eq!(delta.messages[0].content, "final draft");
+        assert!(!message_ids.contains(&untouched));
+        assert_eq!(delta.deleted_messages.len(), 1);
+        assert_eq!(delta.deleted_messages[0].id, deleted);
+        assert_eq!(delta.deleted_messages[0].chat_id, chat_id);
+        assert!(!delta.has_more);
+
+        // A deleted message is gone from history, not just emptied
+        let history = message_service
+            .get_message_context(ChatId::from(chat_id), MessageId::from(deleted), 1, 1)
+            .await?;
+        assert!(history.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn limit_should_page_through_message_changes() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let member = crate::auth_user!(&users[1]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Sync Paging {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await?;
+        let chat_id: i64 = chat.id.into();
+
+        let now = SyncRepository::new(state.pool()).now().await?;
+        let mut since = encode_cursor(&SyncCursor::at(now));
+        let mut sent = Vec::new();
+        for n in 0..5 {
+            sent.push(send(&state, users[0].id, chat_id, &format!("message {}", n)).await);
+        }
+
+        let mut synced = Vec::new();
+        loop {
+            let Json(response) = sync_handler(
+                Extension(state.clone()),
+                Extension(member.clone()),
+                Query(SyncQuery {
+                    since: Some(since),
+                    limit: Some(2),
+                }),
+            )
+            .await?;
+            let delta = response.data.unwrap();
+            assert!(delta.messages.len() <= 2);
+            synced.extend(delta.messages.iter().map(|m| m.id));
+            since = delta.next_cursor;
+            if !delta.has_more {
+                break;
+            }
+        }
+
+        assert_eq!(synced, sent);
+        Ok(())
+    }
+}
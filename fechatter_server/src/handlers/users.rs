@@ -9,7 +9,7 @@
 use axum::{
     extract::{Extension, Path},
     http::{HeaderMap, StatusCode},
-    response::{IntoResponse, Json},
+    response::{IntoResponse, Json, Response},
 };
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, instrument};
@@ -18,7 +18,8 @@ use validator::Validate;
 
 use crate::{
     dtos::{
-        core::{ApiError, ApiResponse},
+        core::{ApiError, ApiResponse, ListResponse},
+        get_dto_manager,
         models::{
             requests::{auth::ChangePasswordRequest, user::UpdateUserProfileRequest},
             responses::{
@@ -28,6 +29,8 @@ use crate::{
         },
     },
     error::AppError,
+    handlers::conditional::{conditional_json, weak_etag},
+    handlers::page_params::{PageParams, SortFields},
     services::application::workers::profile::service::{
         UserProfileService, UserProfileServiceTrait,
     },
@@ -47,6 +50,7 @@ use fechatter_core::{AuthUser, UserId};
   path = "/api/users/profile",
   responses(
     (status = 200, description = "User profile retrieved successfully", body = UserProfileResponse),
+    (status = 304, description = "Profile unchanged since the ETag in If-None-Match"),
     (status = 401, description = "Unauthorized"),
     (status = 404, description = "User not found"),
     (status = 500, description = "Internal server error")
@@ -55,11 +59,12 @@ use fechatter_core::{AuthUser, UserId};
   summary = "Get user profile",
   description = "Get the profile information for the currently authenticated user."
 )]
-#[instrument(skip(state), fields(user_id = %user.id))]
+#[instrument(skip(state, headers), fields(user_id = %user.id))]
 pub async fn get_user_profile(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
-) -> Result<Json<UserProfileResponse>, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
     info!(user_id = %user.id, "Getting user profile");
 
     // Create profile service
@@ -72,7 +77,9 @@ pub async fn get_user_profile(
     let profile = profile_service.get_user_profile(user.id).await?;
 
     info!(user_id = %user.id, "User profile retrieved successfully");
-    Ok(Json(profile))
+    // Profiles carry no updated_at, so the representation itself is the version
+    let etag = weak_etag(&profile);
+    Ok(conditional_json(&headers, etag, profile))
 }
 
 /// Get user profile by ID
@@ -167,6 +174,10 @@ pub async fn update_user_profile(
     let response = profile_service
         .update_user_profile(user.id, request)
         .await?;
+    state
+        .response_cache()
+        .invalidate_workspace(user.workspace_id)
+        .await;
 
     info!(user_id = %user.id, updated_fields = ?response.updated_fields, "User profile updated successfully");
     Ok(Json(response))
@@ -231,6 +242,10 @@ pub async fn update_user_profile_by_id(
     let response = profile_service
         .update_user_profile(UserId(user_id), request)
         .await?;
+    state
+        .response_cache()
+        .invalidate_workspace(user.workspace_id)
+        .await;
 
     info!(
       user_id = %user_id,
@@ -334,74 +349,74 @@ pub async fn change_password(
     Ok(StatusCode::OK)
 }
 
+/// Sortable columns of the workspace user list
+pub struct WorkspaceUserSort;
+
+impl SortFields for WorkspaceUserSort {
+    const ALLOWED: &'static [&'static str] = &["fullname", "email", "created_at"];
+}
+
 /// List all users in the current workspace
 ///
-/// Returns a list of all users who belong to the same workspace as the authenticated user.
+/// Returns a page of users who belong to the same workspace as the authenticated user.
 #[utoipa::path(
   get,
   path = "/api/users",
+  params(
+    ("page" = Option<u32>, Query, description = "Page number, starting at 1"),
+    ("page_size" = Option<u32>, Query, description = "Users per page, capped at the configured maximum"),
+    ("sort" = Option<String>, Query, description = "fullname, email or created_at; prefix with '-' for descending")
+  ),
   responses(
     (status = 200, description = "Users retrieved successfully", body = Vec<UserProfileResponse>),
+    (status = 400, description = "Invalid pagination or sort parameters"),
     (status = 401, description = "Unauthorized"),
     (status = 500, description = "Internal server error")
   ),
   tag = "users",
   summary = "List workspace users",
-  description = "Get all users in the current workspace."
+  description = "Get a page of users in the current workspace, with pagination metadata."
 )]
 #[instrument(skip(state), fields(workspace_id = %user.workspace_id))]
 pub async fn list_workspace_users_handler(
     Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
-) -> Result<Json<Vec<UserProfileResponse>>, AppError> {
+    page: PageParams<WorkspaceUserSort>,
+) -> Result<Json<ListResponse<UserProfileResponse>>, AppError> {
     info!(workspace_id = %user.workspace_id, "Listing workspace users");
 
     // Use domain layer instead of direct database access
-    let user_repo = std::sync::Arc::new(crate::domains::user::repository::UserRepositoryImpl::new(
-        state.pool().clone(),
-    ));
+    let user_repo = crate::domains::user::repository::UserRepositoryImpl::new(state.pool());
 
-    let users_data = user_repo
-        .get_workspace_users(user.workspace_id.into())
+    let total_items = user_repo
+        .count_workspace_users(user.workspace_id)
         .await
-        .map_err(|e| AppError::Internal(format!("Failed to fetch workspace users: {}", e)))?;
-
-    let users: Vec<UserProfileResponse> = users_data
-        .into_iter()
-        .map(
-            |(id, email, fullname, bio, avatar_url, status, created_at, _updated_at)| {
-                // Convert status string to UserStatus
-                let user_status = match status.as_deref().unwrap_or("Active") {
-                    "Suspended" => fechatter_core::models::UserStatus::Suspended,
-                    _ => fechatter_core::models::UserStatus::Active,
-                };
-
-                UserProfileResponse {
-                    id,
-                    email,
-                    fullname,
-                    status: user_status,
-                    created_at,
-                    workspace_id: i64::from(user.workspace_id),
-                    phone: None,      // Not available in this query
-                    title: None,      // Not available in this query
-                    department: None, // Not available in this query
-                    avatar_url,
-                    bio,
-                    timezone: None,       // Not available in this query
-                    language: None,       // Not available in this query
-                    last_active_at: None, // Not available in this query
-                    settings: None,       // Not retrieved in this query
-                }
-            },
+        .map_err(|e| AppError::Internal(format!("Failed to count workspace users: {}", e)))?;
+    let users = user_repo
+        .get_workspace_users(
+            user.workspace_id,
+            page.limit(),
+            page.offset(),
+            page.sort_key(),
         )
-        .collect();
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to fetch workspace users: {}", e)))?;
 
     info!(
       workspace_id = %user.workspace_id,
       user_count = users.len(),
+      total_items = total_items,
       "Workspace users retrieved successfully"
     );
 
-    Ok(Json(users))
+    let response = get_dto_manager()
+        .create_paginated_response::<UserProfileResponse>(
+            &users,
+            page.pagination_request(),
+            total_items.max(0) as u64,
+            "workspace_users_listed".to_string(),
+        )
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    Ok(Json(response))
 }
@@ -0,0 +1,168 @@
+//! # Webhook Subscription Handlers
+//!
+//! **Responsibility**: Let workspace admins register, list and remove outgoing webhooks
+//! **Delivery**: Matching events are POSTed with an `X-Fechatter-Signature` HMAC of the body,
+//! keyed with the subscription secret, which is only returned when the webhook is created.
+//! URLs resolving to internal addresses are refused unless allowlisted under `features.outbound`
+
+use axum::{
+    extract::{Extension, Path},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::domains::workspace::webhooks::{
+    WebhookEventType, WebhookRepository, WebhookSubscription,
+};
+use crate::dtos::core::ApiResponse;
+use crate::{AppError, AppState};
+use fechatter_core::utils::check_outbound_url;
+use fechatter_core::AuthUser;
+
+/// Shortest caller-chosen secret accepted
+const MIN_SECRET_LEN: usize = 16;
+
+/// Webhook registration request
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub event_types: Vec<WebhookEventType>,
+    /// Signing secret; generated when omitted
+    pub secret: Option<String>,
+}
+
+impl CreateWebhookRequest {
+    async fn validate(&self, state: &AppState) -> Result<(), AppError> {
+        check_outbound_url(&self.url, &state.config.features.outbound.allowed_hosts)
+            .await
+            .map_err(|e| {
+                AppError::ValidationError(format!("url must be a public http(s) URL: {}", e))
+            })?;
+        if self.event_types.is_empty() {
+            return Err(AppError::ValidationError(
+                "event_types must not be empty".to_string(),
+            ));
+        }
+        if self
+            .secret
+            .as_ref()
+            .is_some_and(|secret| secret.len() < MIN_SECRET_LEN)
+        {
+            return Err(AppError::ValidationError(format!(
+                "secret must be at least {} characters",
+                MIN_SECRET_LEN
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Newly registered webhook, including its signing secret
+#[derive(Debug, Serialize)]
+pub struct CreateWebhookResponse {
+    #[serde(flatten)]
+    pub webhook: WebhookSubscription,
+    pub secret: String,
+}
+
+/// Register a webhook for the workspace (workspace admins only, audited)
+#[instrument(skip(state, request), fields(admin_id = %user.id))]
+pub async fn create_webhook_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<Json<ApiResponse<CreateWebhookResponse>>, AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+    request.validate(&state).await?;
+
+    let mut event_types = request.event_types;
+    event_types.sort_by_key(|event| event.as_str());
+    event_types.dedup();
+    let secret = request
+        .secret
+        .unwrap_or_else(|| format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()));
+
+    let webhook = WebhookRepository::new(state.pool())
+        .create(
+            user.workspace_id,
+            &request.url,
+            &event_types,
+            &secret,
+            user.id,
+        )
+        .await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      webhook_id = webhook.id,
+      url = %webhook.url,
+      event_types = ?webhook.event_types,
+      "[AUDIT] Webhook registered"
+    );
+
+    Ok(Json(ApiResponse::success(
+        CreateWebhookResponse { webhook, secret },
+        "webhook_created".to_string(),
+    )))
+}
+
+/// List the workspace's webhooks, without their secrets (workspace admins only)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn list_webhooks_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<ApiResponse<Vec<WebhookSubscription>>>, AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+
+    let webhooks = WebhookRepository::new(state.pool())
+        .list(user.workspace_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        webhooks,
+        "webhooks_retrieved".to_string(),
+    )))
+}
+
+/// Remove a webhook (workspace admins only, audited)
+#[instrument(skip(state), fields(admin_id = %user.id))]
+pub async fn delete_webhook_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(webhook_id): Path<i64>,
+) -> Result<Json<ApiResponse<i64>>, AppError> {
+    state
+        .permissions()
+        .can_manage_workspace(user.id, user.workspace_id)
+        .await?
+        .check()?;
+
+    WebhookRepository::new(state.pool())
+        .delete(user.workspace_id, webhook_id)
+        .await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %user.workspace_id,
+      webhook_id,
+      "[AUDIT] Webhook deleted"
+    );
+
+    Ok(Json(ApiResponse::success(
+        webhook_id,
+        "webhook_deleted".to_string(),
+    )))
+}
@@ -0,0 +1,110 @@
+//! # Workspace Limits Admin Handlers
+//!
+//! **Responsibility**: Let configured admins raise or lower a workspace's rate limits and bot
+//! quota, e.g. for premium workspaces
+//! **Scope**: The caller's workspace unless `?workspace_id=` names another; `null` fields use
+//! the global `features.rate_limiting` defaults
+
+use axum::{
+    extract::{Extension, Query},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::domains::workspace::limits::WorkspaceLimits;
+use crate::domains::workspace::repository::WorkspaceRepositoryImpl;
+use crate::dtos::core::ApiResponse;
+use crate::handlers::maintenance::ensure_maintenance_admin;
+use crate::{AppError, AppState};
+use fechatter_core::{AuthUser, WorkspaceId};
+
+/// Largest accepted override
+const MAX_LIMIT: i32 = 1_000_000;
+
+/// Workspace the limits apply to
+#[derive(Debug, Default, Deserialize)]
+pub struct WorkspaceLimitsQuery {
+    /// Defaults to the caller's workspace
+    pub workspace_id: Option<i64>,
+}
+
+impl WorkspaceLimitsQuery {
+    fn workspace_id(&self, user: &AuthUser) -> WorkspaceId {
+        self.workspace_id.map(WorkspaceId).unwrap_or(user.workspace_id)
+    }
+}
+
+/// Current overrides of the workspace
+#[derive(Debug, Serialize)]
+pub struct WorkspaceLimitsResponse {
+    pub workspace_id: i64,
+    #[serde(flatten)]
+    pub limits: WorkspaceLimits,
+}
+
+/// Get a workspace's limit overrides (maintenance admins only)
+#[instrument(skip(state, query), fields(admin_id = %user.id))]
+pub async fn get_workspace_limits_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Query(query): Query<WorkspaceLimitsQuery>,
+) -> Result<Json<ApiResponse<WorkspaceLimitsResponse>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+    let workspace_id = query.workspace_id(&user);
+
+    let limits = WorkspaceRepositoryImpl::new(state.pool())
+        .get_limits(workspace_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        WorkspaceLimitsResponse {
+            workspace_id: workspace_id.into(),
+            limits,
+        },
+        "workspace_limits_retrieved".to_string(),
+    )))
+}
+
+/// Replace a workspace's limit overrides (maintenance admins only, audited)
+#[instrument(skip(state, query), fields(admin_id = %user.id))]
+pub async fn set_workspace_limits_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Query(query): Query<WorkspaceLimitsQuery>,
+    Json(limits): Json<WorkspaceLimits>,
+) -> Result<Json<ApiResponse<WorkspaceLimitsResponse>>, AppError> {
+    ensure_maintenance_admin(&state, &user)?;
+    let workspace_id = query.workspace_id(&user);
+
+    for (field, value) in [
+        ("message_send_limit", limits.message_send_limit),
+        ("upload_limit", limits.upload_limit),
+        ("bot_daily_quota", limits.bot_daily_quota),
+    ] {
+        if value.is_some_and(|value| !(1..=MAX_LIMIT).contains(&value)) {
+            return Err(AppError::BadRequest(format!(
+                "{} must be between 1 and {}, or null for the default",
+                field, MAX_LIMIT
+            )));
+        }
+    }
+
+    state.workspace_limits().set(workspace_id, limits).await?;
+
+    info!(
+      target: "audit",
+      admin_id = %user.id,
+      workspace_id = %workspace_id,
+      limits = ?limits,
+      "[AUDIT] Workspace limits changed"
+    );
+
+    Ok(Json(ApiResponse::success(
+        WorkspaceLimitsResponse {
+            workspace_id: workspace_id.into(),
+            limits,
+        },
+        "workspace_limits_updated".to_string(),
+    )))
+}
@@ -14,14 +14,17 @@
 use axum::{
     extract::{Extension, Path, State},
     http::StatusCode,
-    response::Json,
+    response::{Json, Response},
 };
 use serde::{Deserialize, Serialize};
 
+use crate::dtos::core::ApiResponse;
+use crate::handlers::conditional::cached_json;
 use crate::{AppError, AppState};
 use fechatter_core::AuthUser;
 
 // Use types and traits from application service layer
+use crate::services::application::workers::chat::{ChatSidebarService, SidebarChat};
 use crate::services::application::workers::workspace::{
     create_workspace_application_service, InviteUserCommand, UpdateWorkspaceCommand,
 };
@@ -38,7 +41,7 @@ pub struct UserSummary {
     pub status: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct WorkspaceInfo {
     pub id: i64,
     pub name: String,
@@ -121,6 +124,10 @@ pub async fn update_workspace_handler(
             UpdateWorkspaceCommand { name: payload.name },
         )
         .await?;
+    state
+        .response_cache()
+        .invalidate_workspace(workspace_id.into())
+        .await;
 
     // 4. Transform response
     let response = WorkspaceInfo {
@@ -135,33 +142,38 @@ pub async fn update_workspace_handler(
     Ok(Json(response))
 }
 
-/// Get current workspace info - For /api/workspaces endpoint
+/// Get current workspace info - For /api/workspace endpoint, served from the response cache
 pub async fn get_current_workspace_handler(
-    State(state): State<AppState>,
+    Extension(state): Extension<AppState>,
     Extension(user): Extension<AuthUser>,
-) -> Result<Json<WorkspaceInfo>, AppError> {
+) -> Result<Response, AppError> {
     // 1. Get Application Service with proper Result handling
     let workspace_service = create_workspace_application_service(&state)?;
 
     // 2. Use current user's workspace ID
     let workspace_id = i64::from(user.workspace_id);
 
-    // 3. Delegate business logic to get workspace details
-    let workspace = workspace_service
-        .get_workspace_details(workspace_id.into())
+    // 3. Delegate business logic to get workspace details, unless a recent copy is cached
+    let cache = state.response_cache();
+    let cached = cache
+        .get_or_load(user.workspace_id, "workspace", user.id, &(), || async {
+            let workspace = workspace_service
+                .get_workspace_details(workspace_id.into())
+                .await?;
+
+            // 4. Transform response
+            Ok(WorkspaceInfo {
+                id: workspace_id,
+                name: workspace.name,
+                owner_id: workspace.owner_id,
+                member_count: workspace.member_count,
+                created_at: workspace.created_at,
+            })
+        })
         .await?;
 
-    // 4. Transform response
-    let response = WorkspaceInfo {
-        id: workspace_id,
-        name: workspace.name,
-        owner_id: workspace.owner_id,
-        member_count: workspace.member_count,
-        created_at: workspace.created_at,
-    };
-
-    // 5. Return response
-    Ok(Json(response))
+    // 5. Return response with cache headers
+    Ok(cached_json(cached, cache.ttl_seconds()))
 }
 
 /// List workspace chats - For /api/workspace/chats endpoint
@@ -202,6 +214,22 @@ pub async fn list_workspace_chats_handler(
     Ok(Json(response))
 }
 
+/// Sidebar of the current user - For /api/workspace/sidebar endpoint, every chat with its
+/// last message, unread count, member count and mute state in one response
+pub async fn get_sidebar_handler(
+    Extension(state): Extension<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<ApiResponse<Vec<SidebarChat>>>, AppError> {
+    let chats = ChatSidebarService::from_state(&state)
+        .list(user.id.into())
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        chats,
+        "sidebar_retrieved".to_string(),
+    )))
+}
+
 /// Invite user to workspace - With workspace permission validation
 pub async fn invite_user_handler(
     State(state): State<AppState>,
@@ -229,6 +257,10 @@ pub async fn invite_user_handler(
             },
         )
         .await?;
+    state
+        .response_cache()
+        .invalidate_workspace(workspace_id.into())
+        .await;
 
     // 4. Return status
     Ok(StatusCode::OK)
@@ -286,6 +318,10 @@ pub async fn add_members_handler(
     let users = workspace_service
         .add_members(workspace_id.into(), user.id, command)
         .await?;
+    state
+        .response_cache()
+        .invalidate_workspace(workspace_id.into())
+        .await;
 
     // 5. Transform response
     let response: Vec<UserSummary> = users
@@ -300,3 +336,106 @@ pub async fn add_members_handler(
 
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::models::requests::message::SendMessageRequest;
+    use fechatter_core::{ChatId, ChatType, CreateMessage, UserId};
+
+    async fn send(state: &AppState, sender: UserId, chat_id: ChatId, content: &str) {
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": content })).unwrap();
+        state
+            .application_services()
+            .message_service()
+            .send_message(sender, chat_id, CreateMessage::from(request))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn sidebar_should_list_every_chat_with_its_summary() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(4).await;
+        let viewer = crate::auth_user!(&users[0]);
+        let busy = state
+            .create_new_chat(
+                ChatType::Group,
+                Some(format!("Sidebar Busy {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id, users[2].id, users[3].id],
+            )
+            .await?;
+        let muted = state
+            .create_new_chat(
+                ChatType::Group,
+                Some(format!("Sidebar Muted {}", uuid::Uuid::new_v4())),
+                None,
+                users[1].id,
+                vec![users[0].id, users[2].id],
+            )
+            .await?;
+        let quiet = state
+            .create_new_chat(
+                ChatType::Group,
+                Some(format!("Sidebar Quiet {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[3].id],
+            )
+            .await?;
+
+        send(&state, users[1].id, busy.id, "first").await;
+        send(&state, users[2].id, busy.id, "second").await;
+        send(&state, users[0].id, busy.id, "my reply").await;
+        send(&state, users[1].id, muted.id, "quiet please").await;
+        sqlx::query(
+            "UPDATE chat_members SET muted_until = NOW() + INTERVAL '1 hour'
+             WHERE chat_id = $1 AND user_id = $2",
+        )
+        .bind(i64::from(muted.id))
+        .bind(i64::from(users[0].id))
+        .execute(&*state.pool())
+        .await?;
+
+        let Json(response) =
+            get_sidebar_handler(Extension(state.clone()), Extension(viewer)).await?;
+        let sidebar = response.data.unwrap();
+        let entry = |chat_id: ChatId| {
+            sidebar
+                .iter()
+                .find(|entry| entry.chat.id == chat_id)
+                .expect("chat listed in the sidebar")
+        };
+
+        let entry_busy = entry(busy.id);
+        assert_eq!(entry_busy.chat.name, busy.name);
+        assert_eq!(entry_busy.chat.last_message.as_deref(), Some("my reply"));
+        assert!(entry_busy.chat.last_message_time.is_some());
+        // The viewer's own reply is not unread
+        assert_eq!(entry_busy.chat.unread_count, 2);
+        assert_eq!(entry_busy.chat.members_count, 4);
+        assert_eq!(entry_busy.chat.created_by, users[0].id);
+        assert!(!entry_busy.is_muted);
+
+        let entry_muted = entry(muted.id);
+        assert_eq!(
+            entry_muted.chat.last_message.as_deref(),
+            Some("quiet please")
+        );
+        assert_eq!(entry_muted.chat.unread_count, 1);
+        assert_eq!(entry_muted.chat.members_count, 3);
+        assert!(entry_muted.is_muted);
+
+        let entry_quiet = entry(quiet.id);
+        assert_eq!(entry_quiet.chat.last_message, None);
+        assert_eq!(entry_quiet.chat.unread_count, 0);
+        assert_eq!(entry_quiet.chat.members_count, 2);
+
+        // Most recently active first
+        let position = |chat_id: ChatId| sidebar.iter().position(|e| e.chat.id == chat_id);
+        assert!(position(muted.id) < position(busy.id));
+        Ok(())
+    }
+}
@@ -25,7 +25,7 @@ use axum::{
     extract::Request,
     middleware::Next,
     response::Response,
-    routing::{get, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::{fmt, ops::Deref, sync::Arc};
@@ -82,6 +82,9 @@ pub struct AppStateInner {
         Option<Arc<crate::services::infrastructure::event::EnhancedEventPublisher>>,
     pub(crate) cache_service:
         Option<Arc<crate::services::infrastructure::cache::RedisCacheService>>,
+    // Repository-backed cache preloading, if caching is enabled
+    pub(crate) cache_warmup:
+        Option<Arc<crate::services::infrastructure::cache::CacheWarmupStrategy>>,
     pub(crate) sync_cache_adapter: crate::services::infrastructure::cache::SyncCacheAdapter,
     // Unified analytics publisher using NATS + Protobuf
     pub(crate) analytics_publisher:
@@ -89,6 +92,34 @@ pub struct AppStateInner {
     // Cached auth service wrapper for middleware performance
     pub(crate) cached_auth_service:
         std::sync::RwLock<Option<Arc<crate::state::ProductionAuthServiceWrapper>>>,
+    // Rate limiters for throttled endpoints
+    pub(crate) rate_limiters: crate::services::infrastructure::rate_limit::EndpointRateLimiters,
+    // Hot-reloadable settings read at request time
+    pub(crate) runtime_config: crate::services::infrastructure::runtime_config::RuntimeConfigHandle,
+    // Runtime read-only switch for migrations
+    pub(crate) maintenance: Arc<crate::services::infrastructure::maintenance::MaintenanceMode>,
+    // Typing/presence state shared with notify_server
+    pub(crate) presence_store: Arc<dyn fechatter_core::contracts::PresenceStore>,
+    // Background unfurling of URLs in sent messages, if enabled
+    pub(crate) link_previews:
+        Option<Arc<crate::services::infrastructure::link_preview::LinkPreviewService>>,
+    // Commands recognised by send_message
+    pub(crate) slash_commands: Arc<crate::domains::messaging::slash_commands::SlashCommandRegistry>,
+    // Per-workspace overrides of the global limits
+    pub(crate) workspace_limits: Arc<crate::domains::workspace::limits::WorkspaceLimitsCache>,
+    // Short-lived admin dashboard snapshot
+    pub(crate) admin_dashboard:
+        Arc<crate::services::infrastructure::observability::dashboard::DashboardCache>,
+    // Who can do what
+    pub(crate) permissions: Arc<crate::domains::permission::PermissionService>,
+    // Short-lived copies of expensive read responses
+    pub(crate) response_cache: Arc<crate::services::application::stores::ResponseCache>,
+    // Attachment scanning and quarantine, if enabled
+    pub(crate) file_scans: Option<Arc<crate::services::infrastructure::file_scan::FileScanService>>,
+    // Where uploaded files are kept, local or shared by every replica
+    pub(crate) file_storage: Arc<dyn crate::services::infrastructure::storage::FileStorage>,
+    // Process-wide cap on concurrent AI provider calls
+    pub(crate) ai_limiter: fechatter_core::services::concurrency::ConcurrencyLimiter,
 }
 
 // ============================================================================
@@ -116,6 +147,114 @@ impl AppState {
         self.inner.cache_service.as_ref()
     }
 
+    /// Get cache warmup, if caching is enabled
+    #[inline]
+    pub fn cache_warmup(
+        &self,
+    ) -> Option<&Arc<crate::services::infrastructure::cache::CacheWarmupStrategy>> {
+        self.inner.cache_warmup.as_ref()
+    }
+
+    /// Evict in-memory chat lists of `user_ids`, returning how many entries were dropped
+    #[inline]
+    pub fn evict_memory_chat_lists(&self, user_ids: &[i64]) -> u64 {
+        self.inner.sync_cache_adapter.evict_chat_lists(user_ids)
+    }
+
+    /// Get endpoint rate limiters
+    #[inline]
+    pub fn rate_limiters(
+        &self,
+    ) -> &crate::services::infrastructure::rate_limit::EndpointRateLimiters {
+        &self.inner.rate_limiters
+    }
+
+    /// Get hot-reloadable runtime config
+    #[inline]
+    pub fn runtime_config(
+        &self,
+    ) -> &crate::services::infrastructure::runtime_config::RuntimeConfigHandle {
+        &self.inner.runtime_config
+    }
+
+    /// Get per-workspace limit overrides
+    #[inline]
+    pub fn workspace_limits(
+        &self,
+    ) -> &Arc<crate::domains::workspace::limits::WorkspaceLimitsCache> {
+        &self.inner.workspace_limits
+    }
+
+    /// Get admin dashboard snapshot cache
+    #[inline]
+    pub fn admin_dashboard(
+        &self,
+    ) -> &Arc<crate::services::infrastructure::observability::dashboard::DashboardCache> {
+        &self.inner.admin_dashboard
+    }
+
+    /// Get permission checks
+    #[inline]
+    pub fn permissions(&self) -> &Arc<crate::domains::permission::PermissionService> {
+        &self.inner.permissions
+    }
+
+    /// Get cached read responses
+    #[inline]
+    pub fn response_cache(&self) -> &Arc<crate::services::application::stores::ResponseCache> {
+        &self.inner.response_cache
+    }
+
+    /// Get maintenance mode switch
+    #[inline]
+    pub fn maintenance(
+        &self,
+    ) -> &Arc<crate::services::infrastructure::maintenance::MaintenanceMode> {
+        &self.inner.maintenance
+    }
+
+    /// Get typing/presence store
+    #[inline]
+    pub fn presence_store(&self) -> &Arc<dyn fechatter_core::contracts::PresenceStore> {
+        &self.inner.presence_store
+    }
+
+    /// Get link preview service, if enabled
+    #[inline]
+    pub fn link_previews(
+        &self,
+    ) -> Option<&Arc<crate::services::infrastructure::link_preview::LinkPreviewService>> {
+        self.inner.link_previews.as_ref()
+    }
+
+    /// Get attachment scanning service, if enabled
+    #[inline]
+    pub fn file_scans(
+        &self,
+    ) -> Option<&Arc<crate::services::infrastructure::file_scan::FileScanService>> {
+        self.inner.file_scans.as_ref()
+    }
+
+    /// Get file storage backend
+    #[inline]
+    pub fn file_storage(&self) -> &Arc<dyn crate::services::infrastructure::storage::FileStorage> {
+        &self.inner.file_storage
+    }
+
+    /// Get the AI provider concurrency limiter
+    #[inline]
+    pub fn ai_limiter(&self) -> &fechatter_core::services::concurrency::ConcurrencyLimiter {
+        &self.inner.ai_limiter
+    }
+
+    /// Get slash command registry
+    #[inline]
+    pub fn slash_commands(
+        &self,
+    ) -> &Arc<crate::domains::messaging::slash_commands::SlashCommandRegistry> {
+        &self.inner.slash_commands
+    }
+
     /// Get application services
     #[inline]
     pub fn application_services(&self) -> &crate::services::application::builders::ServiceProvider {
@@ -204,16 +343,28 @@ impl AppState {
     // State Creation - Core Public API
     // ========================================================================
 
-    /// Create AppState for tests
+    /// Create AppState for tests, with a recorder of the events it publishes
     #[cfg(test)]
-    pub fn default_for_tests() -> Self {
-        crate::state::create_test_state()
+    pub async fn default_for_tests() -> Result<
+        (
+            Self,
+            crate::services::infrastructure::event::RecordingEventPublisher,
+        ),
+        AppError,
+    > {
+        crate::state::create_test_state().await
     }
 
     /// Create AppState - Main initialization method
     pub async fn try_new(config: AppConfig) -> Result<Self, AppError> {
         crate::state::create_production_state(config).await
     }
+
+    /// AppState with injected cache, event publisher, search or AI; anything not supplied
+    /// is wired as in `try_new`
+    pub fn builder(config: AppConfig) -> crate::state::AppStateBuilder {
+        crate::state::AppStateBuilder::new(config)
+    }
 }
 
 impl Deref for AppState {
@@ -259,6 +410,104 @@ async fn route_debug_middleware(req: Request, next: Next) -> Response {
     response
 }
 
+// ============================================================================
+// Optional Route Groups - mounted according to `features.routes`
+// ============================================================================
+
+/// Mount `group` onto `router` only when its feature is enabled
+fn mount_if(router: Router, enabled: bool, group: fn(Router) -> Router) -> Router {
+    if enabled {
+        group(router)
+    } else {
+        router
+    }
+}
+
+/// Search routes that only require auth
+fn user_search_routes(router: Router) -> Router {
+    router
+        // Global search routes
+        .route(
+            "/search/messages",
+            post(handlers::search::global_search_messages),
+        )
+        // Simplified chat search route (only requires auth, not chat membership)
+        .route(
+            "/search/chat/{chat_id}/messages",
+            get(handlers::search::simple_search_messages_in_chat),
+        )
+}
+
+/// Search routes that require chat membership
+fn chat_search_routes(router: Router) -> Router {
+    router
+        // Chat search operations
+        .route(
+            "/chat/{id}/messages/search",
+            get(handlers::search::simple_search_messages_in_chat)
+                .post(handlers::search::search_messages_in_chat),
+        )
+        // Admin operations
+        .route(
+            "/admin/chat/{id}/reindex",
+            post(handlers::search::reindex_chat_messages),
+        )
+}
+
+/// Realtime presence routes (user-level)
+fn user_realtime_routes(router: Router) -> Router {
+    router.route(
+        "/realtime/presence",
+        post(handlers::realtime::update_presence),
+    )
+}
+
+/// Realtime operations that require chat membership
+fn chat_realtime_routes(router: Router) -> Router {
+    router
+        .route(
+            "/chat/{id}/typing/start",
+            post(handlers::realtime::start_typing),
+        )
+        .route(
+            "/chat/{id}/typing/stop",
+            post(handlers::realtime::stop_typing),
+        )
+        .route(
+            "/chat/{id}/typing/users",
+            get(handlers::realtime::get_typing_users),
+        )
+        .route(
+            "/chat/{id}/messages/{message_id}/read",
+            post(handlers::realtime::mark_message_read),
+        )
+        .route(
+            "/messages/{message_id}/receipts",
+            get(handlers::realtime::get_message_receipts),
+        )
+}
+
+/// Bot routes (require authentication and quota check)
+fn bot_routes(router: Router) -> Router {
+    router
+        .route(
+            "/bot/translate",
+            post(handlers::bot::translate_message_handler),
+        )
+        .route(
+            "/bot/summarize",
+            post(handlers::bot::summarize_message_handler),
+        )
+        .route(
+            "/bot/languages",
+            get(handlers::bot::get_supported_languages_handler),
+        )
+        .route(
+            "/bot/detect-language",
+            post(handlers::bot::detect_language_handler),
+        )
+}
+
 /// This implementation uses ONLY Extension-based middleware to avoid Axum 0.7.9 with_state() bugs
 /// All handlers use Extension<AppState> instead of State<AppState>
 /// Returns Router<()> for complete type unification
@@ -267,6 +516,8 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
         create_extension_middleware_builder, create_stateless_router_with_routes,
     };
 
+    let features = state.config.features.routes.clone();
+
     // ============================================================================
     // Public routes (no auth required, but still need state)
     // ============================================================================
@@ -275,6 +526,11 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
             .route("/signup", post(handlers::auth::signup_handler))
             .route("/signin", post(handlers::auth::signin_handler))
             .route("/refresh", post(handlers::auth::refresh_token_handler))
+            // Whole-stack health: own dependencies plus notify/analytics servers
+            .route(
+                "/system/health",
+                get(handlers::health::system_health_handler),
+            )
     });
 
     let public_routes = create_extension_middleware_builder(public_routes, state.clone())
@@ -288,6 +544,10 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
         router
             .route("/logout", post(handlers::auth::logout_handler))
             .route("/logout-all", post(handlers::auth::logout_all_handler))
+            .route(
+                "/logout-others",
+                post(handlers::auth::logout_others_handler),
+            )
             .route(
                 "/cache/stats",
                 get(handlers::cache_stats::get_cache_stats_handler),
@@ -305,21 +565,6 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
                 "/files/download/{file_id}",
                 get(handlers::files::download_file_handler),
             )
-            // Global search routes
-            .route(
-                "/search/messages",
-                post(handlers::search::global_search_messages),
-            )
-            // Simplified chat search route (only requires auth, not chat membership)
-            .route(
-                "/search/chat/{chat_id}/messages",
-                get(handlers::search::simple_search_messages_in_chat),
-            )
-            // Realtime presence routes (user-level)
-            .route(
-                "/realtime/presence",
-                post(handlers::realtime::update_presence),
-            )
             // Unread counts routes
             .route(
                 "/unread-counts",
@@ -330,20 +575,10 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
                 "/mentions/unread",
                 get(handlers::messages::get_unread_mentions_handler),
             )
-            // Bot routes (require authentication and quota check)
-            .route(
-                "/bot/translate",
-                post(handlers::bot::translate_message_handler),
-            )
-            .route(
-                "/bot/languages",
-                get(handlers::bot::get_supported_languages_handler),
-            )
-            .route(
-                "/bot/detect-language",
-                post(handlers::bot::detect_language_handler),
-            )
     });
+    let auth_routes = mount_if(auth_routes, features.search, user_search_routes);
+    let auth_routes = mount_if(auth_routes, features.realtime, user_realtime_routes);
+    let auth_routes = mount_if(auth_routes, features.bot, bot_routes);
 
     let auth_routes = create_extension_middleware_builder(auth_routes, state.clone())
         .with_state_extension()
@@ -355,10 +590,25 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
     // ============================================================================
     let workspace_routes = create_stateless_router_with_routes(|router| {
         router
+            // Current workspace details (response-cached)
+            .route(
+                "/workspace",
+                get(handlers::workspaces::get_current_workspace_handler),
+            )
             .route(
                 "/workspace/chats",
                 get(handlers::chat::list_chats_handler).post(handlers::chat::create_chat_handler),
             )
+            // Every chat with its last message, unread and member counts
+            .route(
+                "/workspace/sidebar",
+                get(handlers::workspaces::get_sidebar_handler),
+            )
+            // Get-or-create the 1:1 chat with another workspace user
+            .route(
+                "/dm/{user_id}",
+                post(handlers::chat::direct_message_handler),
+            )
             // User routes
             .route("/users", get(handlers::users::list_workspace_users_handler))
             .route(
@@ -375,11 +625,110 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
                 "/presence/status",
                 get(handlers::users::list_workspace_users_handler),
             )
+            // Delta sync for clients coming back online
+            .route("/sync", get(handlers::sync::sync_handler))
             // Password management
             .route(
                 "/users/change-password",
                 post(handlers::users::change_password_handler),
             )
+            // Maintenance mode toggle (configured admins only)
+            .route(
+                "/admin/maintenance",
+                get(handlers::maintenance::get_maintenance_mode_handler)
+                    .put(handlers::maintenance::set_maintenance_mode_handler),
+            )
+            // Message retention window (workspace admins only)
+            .route(
+                "/workspace/retention",
+                get(handlers::retention::get_retention_handler)
+                    .put(handlers::retention::set_retention_handler),
+            )
+            // Chat name uniqueness (workspace admins only)
+            .route(
+                "/workspace/chat-names",
+                get(handlers::chat_names::get_unique_chat_names_handler)
+                    .put(handlers::chat_names::set_unique_chat_names_handler),
+            )
+            // Per-workspace rate limit and bot quota overrides (configured admins only)
+            .route(
+                "/workspace/limits",
+                get(handlers::workspace_limits::get_workspace_limits_handler)
+                    .put(handlers::workspace_limits::set_workspace_limits_handler),
+            )
+            // Outgoing webhook subscriptions (workspace admins only)
+            .route(
+                "/workspace/webhooks",
+                get(handlers::webhooks::list_webhooks_handler)
+                    .post(handlers::webhooks::create_webhook_handler),
+            )
+            .route(
+                "/workspace/webhooks/{webhook_id}",
+                delete(handlers::webhooks::delete_webhook_handler),
+            )
+            // Admin cache invalidation and warming (workspace admins only)
+            .route(
+                "/admin/cache/invalidate",
+                post(handlers::cache_admin::invalidate_cache_handler),
+            )
+            .route(
+                "/admin/cache/warm",
+                post(handlers::cache_admin::warm_cache_handler),
+            )
+            // Semantic search embedding backfill (configured admins only)
+            .route(
+                "/admin/embeddings/backfill",
+                post(handlers::embedding_backfill::start_backfill_handler),
+            )
+            .route(
+                "/admin/embeddings/backfill/{job_id}",
+                get(handlers::embedding_backfill::get_backfill_handler),
+            )
+            // Dead-lettered event replay (configured admins only)
+            .route(
+                "/admin/events/replay",
+                post(handlers::event_replay::replay_events_handler),
+            )
+            // Runtime config reload (configured admins only)
+            .route(
+                "/admin/config/reload",
+                post(handlers::config_reload::reload_config_handler),
+            )
+            // System announcements to connected clients (configured admins only)
+            .route(
+                "/admin/announcements",
+                post(handlers::announcements::create_announcement_handler),
+            )
+            // System health snapshot (configured admins only)
+            .route(
+                "/admin/dashboard",
+                get(handlers::admin_dashboard::get_dashboard_handler),
+            )
+            // User search (response-cached) and status/role management (workspace admins only)
+            .route(
+                "/admin/users",
+                get(handlers::admin_users::search_users_handler),
+            )
+            .route(
+                "/admin/users/{user_id}",
+                patch(handlers::admin_users::update_user_handler),
+            )
+            // Bulk account creation for onboarding (workspace admins only)
+            .route(
+                "/admin/users/import",
+                post(handlers::admin_users::import_users_handler),
+            )
+            // GDPR erasure of a user's personal data (workspace admins only, confirmed)
+            .route(
+                "/admin/users/{user_id}/data",
+                delete(handlers::admin_users::erase_user_data_handler),
+            )
+            // Admin rate limit management (workspace admins only)
+            .route(
+                "/admin/rate-limits/{user_id}",
+                get(handlers::rate_limits::get_user_rate_limits_handler)
+                    .delete(handlers::rate_limits::reset_user_rate_limits_handler),
+            )
     });
 
     let workspace_routes = create_extension_middleware_builder(workspace_routes, state.clone())
@@ -400,6 +749,14 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
                     .patch(handlers::chat::update_chat_handler)
                     .delete(handlers::chat::delete_chat_handler),
             )
+            .route(
+                "/chat/{id}/slow-mode",
+                put(handlers::chat::set_slow_mode_handler),
+            )
+            .route(
+                "/chat/{id}/promote-to-group",
+                post(handlers::chat::promote_to_group_handler),
+            )
             // Chat members operations
             .route(
                 "/chat/{id}/members",
@@ -412,32 +769,13 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
                 get(handlers::messages::list_messages_handler)
                     .post(handlers::messages::send_message_handler),
             )
-            // Chat search operations
-            .route(
-                "/chat/{id}/messages/search",
-                get(handlers::search::simple_search_messages_in_chat)
-                    .post(handlers::search::search_messages_in_chat),
-            )
-            // Realtime operations (require chat membership)
-            .route(
-                "/chat/{id}/typing/start",
-                post(handlers::realtime::start_typing),
-            )
-            .route(
-                "/chat/{id}/typing/stop",
-                post(handlers::realtime::stop_typing),
-            )
             .route(
-                "/chat/{id}/typing/users",
-                get(handlers::realtime::get_typing_users),
+                "/chat/{id}/messages/preview",
+                post(handlers::messages::preview_message_handler),
             )
             .route(
-                "/chat/{id}/messages/{message_id}/read",
-                post(handlers::realtime::mark_message_read),
-            )
-            .route(
-                "/messages/{message_id}/receipts",
-                get(handlers::realtime::get_message_receipts),
+                "/chat/{id}/messages/{message_id}/context",
+                get(handlers::messages::get_message_context_handler),
             )
             // Enhanced message operations
             .route(
@@ -448,6 +786,10 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
                 "/messages/{message_id}/receipts/detailed",
                 get(handlers::messages::get_detailed_message_receipts_handler),
             )
+            .route(
+                "/messages/{message_id}/receipts/summary",
+                get(handlers::messages::get_message_receipts_summary_handler),
+            )
             .route(
                 "/chat/{chat_id}/messages/{message_id}/read/enhanced",
                 post(handlers::messages::mark_message_read_enhanced_handler),
@@ -457,12 +799,17 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
                 "/chat/{id}/unread",
                 get(handlers::messages::get_unread_count_handler),
             )
-            // Admin operations
             .route(
-                "/admin/chat/{id}/reindex",
-                post(handlers::search::reindex_chat_messages),
+                "/chat/{id}/read-all",
+                post(handlers::messages::mark_chat_read_handler),
+            )
+            .route(
+                "/chat/{id}/read-cursor",
+                get(handlers::messages::get_read_cursor_handler),
             )
     });
+    let chat_routes = mount_if(chat_routes, features.search, chat_search_routes);
+    let chat_routes = mount_if(chat_routes, features.realtime, chat_realtime_routes);
 
     let chat_routes = create_extension_middleware_builder(chat_routes, state.clone())
         .with_chat_membership() // Apply chat middleware first (executes fourth)
@@ -489,7 +836,11 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
         .merge(public_routes)
         .merge(auth_routes)
         .merge(workspace_routes)
-        .merge(chat_routes);
+        .merge(chat_routes)
+        .layer(axum::middleware::from_fn_with_state(
+            state.maintenance().clone(),
+            crate::services::infrastructure::maintenance::read_only_guard,
+        ));
 
     // ============================================================================
     // Static Files Service - Use config storage path
@@ -501,45 +852,121 @@ pub async fn get_router(state: AppState) -> Result<Router, AppError> {
     info!("🗂️ [STATIC_FILES] - Storage path: {}", storage_path);
     info!("🗂️ [STATIC_FILES] - URL prefix: {}", url_prefix);
 
-    // Verify storage directory exists
-    if !std::path::Path::new(storage_path).exists() {
-        warn!(
-            "WARNING: [STATIC_FILES] Storage directory does not exist: {}",
-            storage_path
-        );
-        warn!("WARNING: [STATIC_FILES] Attempting to create directory...");
-        if let Err(e) = std::fs::create_dir_all(storage_path) {
-            return Err(AppError::ChatFileError(format!(
-                "Failed to create storage directory {}: {}",
-                storage_path, e
-            )));
-        }
-        info!("[STATIC_FILES] Created storage directory: {}", storage_path);
-    } else {
-        info!("[STATIC_FILES] Storage directory exists: {}", storage_path);
-
-        // Log directory contents for debugging
-        if let Ok(entries) = std::fs::read_dir(storage_path) {
-            let count = entries.count();
-            debug!("🗂️ [STATIC_FILES] Directory contains {} items", count);
+    let files_service = match state.config.storage.backend {
+        crate::config::StorageBackend::Local => {
+            // Verify storage directory exists
+            if !std::path::Path::new(storage_path).exists() {
+                warn!(
+                    "WARNING: [STATIC_FILES] Storage directory does not exist: {}",
+                    storage_path
+                );
+                warn!("WARNING: [STATIC_FILES] Attempting to create directory...");
+                if let Err(e) = std::fs::create_dir_all(storage_path) {
+                    return Err(AppError::ChatFileError(format!(
+                        "Failed to create storage directory {}: {}",
+                        storage_path, e
+                    )));
+                }
+                info!("[STATIC_FILES] Created storage directory: {}", storage_path);
+            } else {
+                info!("[STATIC_FILES] Storage directory exists: {}", storage_path);
+
+                // Log directory contents for debugging
+                if let Ok(entries) = std::fs::read_dir(storage_path) {
+                    let count = entries.count();
+                    debug!("🗂️ [STATIC_FILES] Directory contains {} items", count);
+                }
+            }
+
+            // Initialize symlinks for existing files
+            crate::handlers::files::initialize_file_symlinks(storage_path)
+                .await
+                .map_err(|e| {
+                    AppError::ChatFileError(format!("Failed to initialize file symlinks: {}", e))
+                })?;
+
+            Router::new().fallback_service(
+                ServeDir::new(storage_path).append_index_html_on_directories(false),
+            )
         }
-    }
-
-    let files_service = ServeDir::new(storage_path).append_index_html_on_directories(false);
-
-    // Initialize symlinks for existing files
-    crate::handlers::files::initialize_file_symlinks(storage_path)
-        .await
-        .map_err(|e| {
-            AppError::ChatFileError(format!("Failed to initialize file symlinks: {}", e))
-        })?;
+        // Files live in a bucket shared by every replica; clients fetch them from there
+        crate::config::StorageBackend::S3 => Router::new()
+            .route(
+                "/{file_id}",
+                get(crate::handlers::files::signed_file_redirect_handler),
+            )
+            .with_state(state.clone()),
+    };
+    // Quarantined attachments are not served until their scan is clean
+    let files_service = match state.file_scans() {
+        Some(scans) => files_service.layer(axum::middleware::from_fn_with_state(
+            scans.clone(),
+            crate::services::infrastructure::file_scan::quarantine_guard,
+        )),
+        None => files_service,
+    };
 
     // Build final application - NO with_state() calls!
     let app = Router::new()
         .nest("/api", api_routes)
         .merge(health_routes)
         .nest_service("/files", files_service)
-        .layer(axum::middleware::from_fn(route_debug_middleware));
+        .layer(axum::middleware::from_fn(route_debug_middleware))
+        .layer(axum::middleware::from_fn(
+            fechatter_core::middlewares::trace_context_middleware,
+        ));
 
     Ok(app)
 }
+
+#[cfg(test)]
+mod route_feature_tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Method, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    async fn status(router: Router, method: Method, uri: &str) -> StatusCode {
+        router
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn disabled_feature_routes_should_be_absent() {
+        let cases: [(fn(Router) -> Router, Method, &str); 3] = [
+            (bot_routes, Method::GET, "/bot/languages"),
+            (chat_search_routes, Method::GET, "/chat/1/messages/search"),
+            (chat_realtime_routes, Method::GET, "/chat/1/typing/users"),
+        ];
+
+        for (group, method, uri) in cases {
+            let disabled = mount_if(Router::new(), false, group);
+            assert_eq!(
+                status(disabled, method.clone(), uri).await,
+                StatusCode::NOT_FOUND,
+                "{}",
+                uri
+            );
+
+            // Mounted handlers fail on the missing state extension instead of 404
+            let enabled = mount_if(Router::new(), true, group);
+            assert_ne!(
+                status(enabled, method, uri).await,
+                StatusCode::NOT_FOUND,
+                "{}",
+                uri
+            );
+        }
+    }
+}
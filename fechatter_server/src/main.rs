@@ -2,8 +2,13 @@
 //!
 //! **Responsibility**: Initializes and runs the Axum web server.
 
+use fechatter_server::services::application::workers::message::MessageRetentionService;
+use fechatter_server::services::infrastructure::dead_letters::DeadLetterConsumer;
+use fechatter_server::services::infrastructure::observability::{metrics, slow_query};
+use fechatter_server::services::infrastructure::webhooks::WebhookDeliveryWorker;
 use fechatter_server::{config::AppConfig, error::AppError, get_router, AppState};
 use std::net::SocketAddr;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -21,9 +26,62 @@ async fn main() -> Result<(), AppError> {
         ))
         .init();
 
+    // Repository queries slower than this are logged with their label
+    slow_query::set_slow_query_threshold(Duration::from_millis(
+        config.features.observability.slow_query_threshold_ms,
+    ));
+
     // Create AppState
     let app_state = AppState::try_new(config.clone()).await?;
 
+    // Prometheus endpoint plus periodic DB/Redis pool gauges
+    let observability = &config.features.observability;
+    if observability.metrics_enabled {
+        let _metrics_guard = metrics::init_metrics(observability).await?;
+        metrics::collectors::PoolMetrics::spawn_updater(
+            app_state.clone(),
+            Duration::from_secs(observability.pool_metrics_interval_seconds.max(1)),
+        );
+        info!(
+            "Prometheus metrics available at: http://{}/metrics",
+            observability.metrics_bind_address
+        );
+    }
+
+    // Background sweep tombstoning messages past their workspace retention window
+    let retention = &config.features.retention;
+    if retention.enabled {
+        MessageRetentionService::from_state(&app_state, retention)
+            .spawn(Duration::from_secs(retention.sweep_interval_seconds.max(1)));
+    }
+
+    // Delivers message and membership events to workspace webhooks
+    let webhooks = &config.features.webhooks;
+    if webhooks.enabled {
+        match WebhookDeliveryWorker::from_state(&app_state, webhooks) {
+            Some(worker) => {
+                worker.spawn();
+            }
+            None => tracing::warn!("Webhook delivery disabled: worker could not be started"),
+        }
+    }
+
+    // Keeps dead-lettered events so admins can replay them
+    if config.features.dead_letters.enabled {
+        match DeadLetterConsumer::from_state(&app_state) {
+            Some(consumer) => {
+                consumer.spawn();
+            }
+            None => tracing::warn!("Dead letter storage disabled: no NATS connection"),
+        }
+    }
+
+    // SIGHUP re-reads the config file and swaps in the hot-reloadable settings
+    #[cfg(unix)]
+    if let Err(e) = app_state.runtime_config().spawn_sighup_reloader() {
+        tracing::warn!("SIGHUP config reload unavailable: {}", e);
+    }
+
     // Get the application router
     let app = get_router(app_state).await?;
 
@@ -32,7 +90,11 @@ async fn main() -> Result<(), AppError> {
     info!("Server listening on {}", addr);
 
     let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app.into_make_service()).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
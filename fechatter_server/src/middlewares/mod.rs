@@ -57,6 +57,9 @@ pub async fn auth_extension_middleware(
         .token_manager()
         .verify_jwt_token(token)
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    if claims.status == fechatter_core::UserStatus::Suspended {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
 
     let auth_user: AuthUser = claims.into();
 
@@ -1,13 +1,28 @@
-use ai_sdk::{AiAdapter, AiService, Message as AiMessage, OpenaiAdapter, Role as AiRole};
+use ai_sdk::{
+    AiAdapter, AiService, EmbeddingCache, Message as AiMessage, OpenaiAdapter, Role as AiRole,
+    UsageReport,
+};
 use anyhow;
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
 
+use super::RedisEmbeddingStore;
+use crate::services::infrastructure::cache::RedisCacheService;
+
+use crate::domains::messaging::messaging_domain::ContentModerator;
 use crate::{error::AppError, services::infrastructure::third_party_manager::OpenAIConfig};
 use fechatter_core::contracts::infrastructure::{AIService, ChatMessage, Sentiment};
+use fechatter_core::error::CoreError;
+use fechatter_core::services::concurrency::{ConcurrencyLimiter, ConcurrencyPermit, Saturated};
 
 /// Adapter that wraps ai_sdk to implement fechatter's AIService trait
 pub struct AiServiceAdapter {
     adapter: AiAdapter,
+    /// Shared cap on concurrent provider calls; unlimited when unset
+    limiter: Option<ConcurrencyLimiter>,
+    /// Where embeddings are cached; every text is embedded again when unset
+    embedding_store: Option<RedisEmbeddingStore>,
 }
 
 impl AiServiceAdapter {
@@ -20,6 +35,8 @@ impl AiServiceAdapter {
 
         Ok(Self {
             adapter: openai_adapter.into(),
+            limiter: None,
+            embedding_store: None,
         })
     }
 
@@ -29,6 +46,45 @@ impl AiServiceAdapter {
         Self::from_openai_config(config)
     }
 
+    /// Hold a slot of `limiter` for every provider call
+    pub fn with_concurrency_limit(mut self, limiter: ConcurrencyLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Cache embeddings in `cache`, so text embedded before costs no provider call
+    pub fn with_embedding_cache(mut self, cache: Arc<RedisCacheService>) -> Self {
+        self.embedding_store = Some(RedisEmbeddingStore::new(cache));
+        self
+    }
+
+    /// Embeddings of `texts`, through the cache when there is one
+    async fn embed(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
+        match &self.embedding_store {
+            Some(store) => {
+                EmbeddingCache::new(&self.adapter, store.clone(), OpenaiAdapter::EMBEDDING_MODEL)
+                    .embed_texts(texts)
+                    .await
+            }
+            None => self.adapter.embed_texts(texts).await,
+        }
+    }
+
+    /// Slot for one provider call, held until the returned permit is dropped
+    async fn slot(&self) -> Result<Option<ConcurrencyPermit>, Saturated> {
+        match &self.limiter {
+            Some(limiter) => limiter.acquire().await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// `slot` for the `AIService` methods, which can only surface a `CoreError`
+    async fn core_slot(&self) -> Result<Option<ConcurrencyPermit>, CoreError> {
+        self.slot()
+            .await
+            .map_err(|e| CoreError::Internal(e.to_string()))
+    }
+
     /// Convert fechatter ChatMessage to ai_sdk Message
     fn convert_chat_message(message: ChatMessage) -> AiMessage {
         let role = match message.role.as_str() {
@@ -57,6 +113,7 @@ impl AIService for AiServiceAdapter {
         messages: Vec<ChatMessage>,
     ) -> Result<String, fechatter_core::error::CoreError> {
         let ai_messages = Self::convert_chat_messages(messages);
+        let _slot = self.core_slot().await?;
 
         self.adapter
             .complete(&ai_messages)
@@ -68,6 +125,7 @@ impl AIService for AiServiceAdapter {
         &self,
         text: &str,
     ) -> Result<String, fechatter_core::error::CoreError> {
+        let _slot = self.core_slot().await?;
         self.adapter
             .generate_summary(text)
             .await
@@ -83,6 +141,7 @@ impl AIService for AiServiceAdapter {
             AiMessage::user(format!("Analyze the sentiment of: {}", text)),
         ];
 
+        let _slot = self.core_slot().await?;
         let response = self
             .adapter
             .complete(&messages)
@@ -111,6 +170,7 @@ impl AIService for AiServiceAdapter {
         &self,
         context: &str,
     ) -> Result<Vec<String>, fechatter_core::error::CoreError> {
+        let _slot = self.core_slot().await?;
         self.adapter
             .suggest_replies(context)
             .await
@@ -118,26 +178,77 @@ impl AIService for AiServiceAdapter {
     }
 }
 
+#[async_trait]
+impl ContentModerator for AiServiceAdapter {
+    async fn is_allowed(&self, content: &str) -> Result<bool, fechatter_core::error::CoreError> {
+        let _slot = self.core_slot().await?;
+        self.adapter
+            .moderate_content(content)
+            .await
+            .map_err(|e| fechatter_core::error::CoreError::Internal(e.to_string()))
+    }
+}
+
 /// Extended AI service with additional utility methods
 impl AiServiceAdapter {
     /// Generate embeddings for texts
     pub async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AppError> {
-        self.adapter
-            .embed_texts(texts)
+        let _slot = self.slot().await?;
+        self.embed(texts)
             .await
             .map_err(|e| AppError::AnyError(anyhow::anyhow!("Embedding generation failed: {}", e)))
     }
 
     /// Generate single embedding
     pub async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>, AppError> {
-        self.adapter
-            .generate_embedding(text)
+        let _slot = self.slot().await?;
+        self.embed(vec![text.to_string()])
             .await
+            .and_then(|embeddings| {
+                embeddings
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("Failed to generate embedding"))
+            })
             .map_err(|e| AppError::AnyError(anyhow::anyhow!("Embedding generation failed: {}", e)))
     }
 
+    /// Summarize `text`, yielding the summary piece by piece as the provider generates it, with
+    /// the tokens it took once the stream has ended. The concurrency slot is held until the
+    /// stream ends or is dropped; dropping it also cancels the provider request
+    pub async fn summarize_stream(
+        &self,
+        text: &str,
+    ) -> Result<
+        (
+            impl Stream<Item = Result<String, AppError>> + Send + 'static,
+            UsageReport,
+        ),
+        AppError,
+    > {
+        let messages = vec![
+            AiMessage::system("You are a helpful assistant that creates concise summaries."),
+            AiMessage::user(format!("Please summarize the following text:\n\n{}", text)),
+        ];
+
+        let slot = self.slot().await?;
+        let (pieces, usage) = self
+            .adapter
+            .complete_stream_with_usage(&messages)
+            .await
+            .map_err(|e| AppError::AnyError(anyhow::anyhow!("Summary generation failed: {}", e)))?;
+        let pieces = pieces.map(move |piece| {
+            let _slot = &slot;
+            piece.map_err(|e| {
+                AppError::AnyError(anyhow::anyhow!("Summary generation failed: {}", e))
+            })
+        });
+        Ok((pieces, usage))
+    }
+
     /// Moderate content
     pub async fn moderate_content(&self, content: &str) -> Result<bool, AppError> {
+        let _slot = self.slot().await?;
         self.adapter
             .moderate_content(content)
             .await
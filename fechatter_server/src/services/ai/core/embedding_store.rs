@@ -0,0 +1,39 @@
+//! Redis store for ai_sdk's `EmbeddingCache`, so reindexing reuses the embeddings of text it
+//! has already seen
+
+use ai_sdk::EmbeddingStore;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::services::infrastructure::cache::RedisCacheService;
+
+/// Cached embeddings in Redis, under the cache service's prefix
+#[derive(Clone)]
+pub struct RedisEmbeddingStore {
+    cache: Arc<RedisCacheService>,
+}
+
+impl RedisEmbeddingStore {
+    pub fn new(cache: Arc<RedisCacheService>) -> Self {
+        Self { cache }
+    }
+}
+
+impl EmbeddingStore for RedisEmbeddingStore {
+    async fn get_many(&self, keys: &[String]) -> anyhow::Result<Vec<Option<Vec<f32>>>> {
+        // One key is read with GET, whose reply `mget` can't parse as a list
+        if let [key] = keys {
+            return Ok(vec![self.cache.get(key).await?]);
+        }
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        Ok(self.cache.mget(&keys).await?)
+    }
+
+    async fn set_many(&self, entries: &[(String, Vec<f32>)], ttl: Duration) -> anyhow::Result<()> {
+        let mut batch = self.cache.batch();
+        for (key, embedding) in entries {
+            batch = batch.set(key, embedding, ttl.as_secs().max(1))?;
+        }
+        Ok(batch.run().await?)
+    }
+}
@@ -4,10 +4,13 @@
 //! serving as the foundation for more complex AI features in fechatter_server.
 
 pub mod ai_service_adapter;
+pub mod embedding_store;
 
 pub use ai_service_adapter::AiServiceAdapter;
+pub use embedding_store::RedisEmbeddingStore;
 
 /// Re-export ai_sdk types for convenience
 pub use ai_sdk::{
     AiAdapter, AiService, Message as AiMessage, OllamaAdapter, OpenaiAdapter, Role as AiRole,
+    Usage as AiUsage, UsageReport as AiUsageReport,
 };
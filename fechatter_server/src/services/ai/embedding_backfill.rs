@@ -0,0 +1,460 @@
+//! # Embedding Backfill
+//!
+//! **Responsibility**: Embed historical messages that predate semantic search
+//! **Scope**: Admin-triggered jobs walk unembedded messages in id order, throttled to a request
+//! budget; progress is stored per job so a failed or interrupted job resumes where it stopped
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use fechatter_core::models::vector_db::MessageVectorRepository;
+use fechatter_core::{ChatId, MessageId, UserId};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
+
+use crate::config::EmbeddingBackfillConfig;
+use crate::domains::messaging::repository::MessageRepository;
+use crate::services::ai::AiServiceAdapter;
+use crate::services::infrastructure::vector_db::pgvector::VectorConfig;
+use crate::services::infrastructure::vector_db::PgVectorDatabase;
+use crate::{AppError, AppState};
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_RUNNING: &str = "running";
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_FAILED: &str = "failed";
+
+/// A running job that hasn't reported progress for this long is treated as interrupted
+const STALE_RUNNING_MINUTES: i32 = 10;
+
+/// Source of text embeddings
+#[async_trait]
+pub trait TextEmbedder: Send + Sync {
+    async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AppError>;
+}
+
+#[async_trait]
+impl TextEmbedder for AiServiceAdapter {
+    async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AppError> {
+        AiServiceAdapter::embed_texts(self, texts).await
+    }
+}
+
+/// Progress of one backfill job
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct EmbeddingBackfillJob {
+    pub id: i64,
+    /// `None` covers every chat
+    pub chat_id: Option<i64>,
+    pub status: String,
+    /// Messages up to this id have been handled
+    pub last_message_id: i64,
+    pub embedded_count: i64,
+    pub error: Option<String>,
+    pub created_by: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Minimum spacing between embedding requests for a per-minute budget
+pub fn batch_interval(requests_per_minute: u32) -> Duration {
+    Duration::from_millis(60_000 / u64::from(requests_per_minute.max(1)))
+}
+
+/// Wait before retry `attempt` (0-based) of a failed embedding request
+pub fn retry_backoff(interval: Duration, attempt: u32) -> Duration {
+    interval.max(Duration::from_secs(1)) * 2u32.pow(attempt.min(6))
+}
+
+/// Runs embedding backfill jobs
+pub struct EmbeddingBackfillService {
+    pool: Arc<PgPool>,
+    messages: MessageRepository,
+    vectors: Arc<dyn MessageVectorRepository>,
+    embedder: Arc<dyn TextEmbedder>,
+    config: EmbeddingBackfillConfig,
+}
+
+impl EmbeddingBackfillService {
+    pub fn new(
+        pool: Arc<PgPool>,
+        vectors: Arc<dyn MessageVectorRepository>,
+        embedder: Arc<dyn TextEmbedder>,
+        config: EmbeddingBackfillConfig,
+    ) -> Self {
+        Self {
+            messages: MessageRepository::new(pool.clone()),
+            pool,
+            vectors,
+            embedder,
+            config,
+        }
+    }
+
+    /// Backfill service storing vectors in pgvector and embedding with the configured AI backend
+    pub fn from_state(state: &AppState) -> Result<Self, AppError> {
+        let pool = state.pool();
+        let vectors = Arc::new(PgVectorDatabase::new(
+            (*pool).clone(),
+            VectorConfig::default(),
+        ));
+        // Reindexing the same messages again reuses their cached embeddings
+        let mut embedder =
+            AiServiceAdapter::from_env()?.with_concurrency_limit(state.ai_limiter().clone());
+        if let Some(cache) = state.cache_service() {
+            embedder = embedder.with_embedding_cache(cache.clone());
+        }
+        let embedder = Arc::new(embedder);
+
+        Ok(Self::new(
+            pool,
+            vectors,
+            embedder,
+            state.runtime_config().current().embedding_backfill,
+        ))
+    }
+
+    /// Create a pending job
+    pub async fn create_job(
+        &self,
+        chat_id: Option<i64>,
+        created_by: i64,
+    ) -> Result<EmbeddingBackfillJob, AppError> {
+        let job = sqlx::query_as::<_, EmbeddingBackfillJob>(
+            r#"INSERT INTO embedding_backfill_jobs (chat_id, status, created_by)
+         VALUES ($1, $2, $3)
+         RETURNING *"#,
+        )
+        .bind(chat_id)
+        .bind(STATUS_PENDING)
+        .bind(created_by)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    pub async fn get_job(&self, job_id: i64) -> Result<EmbeddingBackfillJob, AppError> {
+        sqlx::query_as::<_, EmbeddingBackfillJob>(
+            "SELECT * FROM embedding_backfill_jobs WHERE id = $1",
+        )
+        .bind(job_id)
+        .fetch_optional(&*self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(vec![format!("Backfill job {} not found", job_id)]))
+    }
+
+    /// Mark a pending, failed or interrupted job as running; a job that is actively running
+    /// or already completed is a conflict
+    pub async fn claim_job(&self, job_id: i64) -> Result<EmbeddingBackfillJob, AppError> {
+        let claimed = sqlx::query_as::<_, EmbeddingBackfillJob>(
+            r#"UPDATE embedding_backfill_jobs
+         SET status = $2, error = NULL, updated_at = NOW()
+         WHERE id = $1
+         AND (status IN ($3, $4)
+              OR (status = $2 AND updated_at < NOW() - make_interval(mins => $5)))
+         RETURNING *"#,
+        )
+        .bind(job_id)
+        .bind(STATUS_RUNNING)
+        .bind(STATUS_PENDING)
+        .bind(STATUS_FAILED)
+        .bind(STALE_RUNNING_MINUTES)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        match claimed {
+            Some(job) => Ok(job),
+            None => {
+                let job = self.get_job(job_id).await?;
+                Err(AppError::Conflict(format!(
+                    "Backfill job {} is {}",
+                    job_id, job.status
+                )))
+            }
+        }
+    }
+
+    /// Claim and run a job to completion
+    pub async fn run_job(&self, job_id: i64) -> Result<EmbeddingBackfillJob, AppError> {
+        let job = self.claim_job(job_id).await?;
+        self.run_claimed(job).await
+    }
+
+    /// Run a claimed job, recording completion or the error it stopped on
+    pub async fn run_claimed(
+        &self,
+        job: EmbeddingBackfillJob,
+    ) -> Result<EmbeddingBackfillJob, AppError> {
+        let job_id = job.id;
+        match self.process(job).await {
+            Ok(()) => {
+                let job = self.set_status(job_id, STATUS_COMPLETED, None).await?;
+                info!(
+                    "Embedding backfill job {} completed: {} messages embedded",
+                    job_id, job.embedded_count
+                );
+                Ok(job)
+            }
+            Err(e) => {
+                error!("Embedding backfill job {} failed: {}", job_id, e);
+                self.set_status(job_id, STATUS_FAILED, Some(&e.to_string()))
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Run a claimed job in the background
+    pub fn spawn(self: Arc<Self>, job: EmbeddingBackfillJob) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            // Outcome is recorded on the job row
+            let _ = self.run_claimed(job).await;
+        })
+    }
+
+    async fn process(&self, mut job: EmbeddingBackfillJob) -> Result<(), AppError> {
+        let interval = batch_interval(self.config.requests_per_minute);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            let batch = self
+                .messages
+                .list_messages_without_embeddings(
+                    job.chat_id,
+                    job.last_message_id,
+                    self.config.batch_size.max(1),
+                )
+                .await?;
+            let Some(&(last_message_id, ..)) = batch.last() else {
+                return Ok(());
+            };
+
+            ticker.tick().await;
+            let texts = batch
+                .iter()
+                .map(|(_, _, _, content, _)| content.clone())
+                .collect();
+            let embeddings = self.embed_with_retry(texts, interval).await?;
+
+            for ((message_id, chat_id, sender_id, content, created_at), embedding) in
+                batch.iter().zip(&embeddings)
+            {
+                self.vectors
+                    .store_message_embedding(
+                        MessageId(*message_id),
+                        ChatId(*chat_id),
+                        UserId(*sender_id),
+                        content,
+                        embedding,
+                        *created_at,
+                    )
+                    .await?;
+            }
+
+            job = self
+                .record_progress(job.id, last_message_id, batch.len() as i64)
+                .await?;
+        }
+    }
+
+    async fn embed_with_retry(
+        &self,
+        texts: Vec<String>,
+        interval: Duration,
+    ) -> Result<Vec<Vec<f32>>, AppError> {
+        let mut attempt = 0;
+        loop {
+            match self.embedder.embed_texts(texts.clone()).await {
+                Ok(embeddings) if embeddings.len() == texts.len() => return Ok(embeddings),
+                Ok(embeddings) => {
+                    return Err(AppError::Internal(format!(
+                        "Embedder returned {} vectors for {} messages",
+                        embeddings.len(),
+                        texts.len()
+                    )))
+                }
+                Err(e) if attempt < self.config.max_retries => {
+                    let backoff = retry_backoff(interval, attempt);
+                    warn!(
+                        "Embedding request failed (attempt {}), retrying in {:?}: {}",
+                        attempt + 1,
+                        backoff,
+                        e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn record_progress(
+        &self,
+        job_id: i64,
+        last_message_id: i64,
+        embedded: i64,
+    ) -> Result<EmbeddingBackfillJob, AppError> {
+        let job = sqlx::query_as::<_, EmbeddingBackfillJob>(
+            r#"UPDATE embedding_backfill_jobs
+         SET last_message_id = $2, embedded_count = embedded_count + $3, updated_at = NOW()
+         WHERE id = $1
+         RETURNING *"#,
+        )
+        .bind(job_id)
+        .bind(last_message_id)
+        .bind(embedded)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn set_status(
+        &self,
+        job_id: i64,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<EmbeddingBackfillJob, AppError> {
+        let job = sqlx::query_as::<_, EmbeddingBackfillJob>(
+            r#"UPDATE embedding_backfill_jobs
+         SET status = $2, error = $3, updated_at = NOW()
+         WHERE id = $1
+         RETURNING *"#,
+        )
+        .bind(job_id)
+        .bind(status)
+        .bind(error)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(job)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_interval_should_spread_requests_over_a_minute() {
+        assert_eq!(batch_interval(20), Duration::from_secs(3));
+        assert_eq!(batch_interval(0), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn retry_backoff_should_double_from_at_least_a_second() {
+        let interval = Duration::from_millis(10);
+        assert_eq!(retry_backoff(interval, 0), Duration::from_secs(1));
+        assert_eq!(retry_backoff(interval, 2), Duration::from_secs(4));
+    }
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+        use std::sync::Mutex;
+
+        const DIMENSION: usize = 1536;
+
+        /// Records every text it is asked to embed
+        #[derive(Default)]
+        struct MockEmbedder {
+            seen: Mutex<Vec<String>>,
+        }
+
+        #[async_trait]
+        impl TextEmbedder for MockEmbedder {
+            async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AppError> {
+                self.seen.lock().unwrap().extend(texts.iter().cloned());
+                Ok(texts.iter().map(|_| vec![0.25; DIMENSION]).collect())
+            }
+        }
+
+        #[tokio::test]
+        async fn job_should_embed_previously_unembedded_messages() -> anyhow::Result<()> {
+            let (state, users) = crate::setup_test_users!(3).await;
+            let pool = state.pool();
+            let chat = state
+                .create_new_chat(
+                    fechatter_core::ChatType::Group,
+                    Some(format!("Backfill {}", uuid::Uuid::new_v4())),
+                    None,
+                    users[0].id,
+                    vec![users[1].id, users[2].id],
+                )
+                .await?;
+            let chat_id = i64::from(chat.id);
+            let sender_id = i64::from(users[0].id);
+            let mut message_ids = Vec::new();
+            for content in ["first", "second", "third"] {
+                let id: i64 = sqlx::query_scalar(
+                    r#"INSERT INTO messages (chat_id, sender_id, content, created_at, updated_at)
+                       VALUES ($1, $2, $3, NOW(), NOW()) RETURNING id"#,
+                )
+                .bind(chat_id)
+                .bind(sender_id)
+                .bind(content)
+                .fetch_one(&*pool)
+                .await?;
+                message_ids.push(id);
+            }
+
+            let vectors = Arc::new(PgVectorDatabase::new(
+                (*pool).clone(),
+                VectorConfig::default(),
+            ));
+            // The first message was embedded when it was sent
+            vectors
+                .store_message_embedding(
+                    MessageId(message_ids[0]),
+                    ChatId(chat_id),
+                    users[0].id,
+                    "first",
+                    &[0.5; DIMENSION],
+                    Utc::now(),
+                )
+                .await?;
+
+            let embedder = Arc::new(MockEmbedder::default());
+            let service = EmbeddingBackfillService::new(
+                pool.clone(),
+                vectors,
+                embedder.clone(),
+                EmbeddingBackfillConfig {
+                    batch_size: 1,
+                    requests_per_minute: 60_000,
+                    max_retries: 0,
+                },
+            );
+
+            let job = service.create_job(Some(chat_id), sender_id).await?;
+            let job = service.run_job(job.id).await?;
+            assert_eq!(job.status, STATUS_COMPLETED);
+            assert_eq!(job.embedded_count, 2);
+            assert_eq!(job.last_message_id, message_ids[2]);
+            assert_eq!(*embedder.seen.lock().unwrap(), vec!["second", "third"]);
+
+            let embedded: i64 = sqlx::query_scalar(
+                "SELECT COUNT(DISTINCT message_id) FROM message_embeddings WHERE message_id = ANY($1)",
+            )
+            .bind(&message_ids)
+            .fetch_one(&*pool)
+            .await?;
+            assert_eq!(embedded, 3);
+
+            // A completed job can't be claimed again
+            assert!(matches!(
+                service.run_job(job.id).await,
+                Err(AppError::Conflict(_))
+            ));
+
+            Ok(())
+        }
+    }
+}
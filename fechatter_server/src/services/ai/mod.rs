@@ -9,6 +9,7 @@ pub mod simple_workflow;
 // Specialized AI services (chat-specific features)
 pub mod agents;
 pub mod cohere;
+pub mod embedding_backfill;
 pub mod huggingface;
 pub mod hybrid_search;
 pub mod openai;
@@ -28,6 +29,7 @@ pub use simple_workflow::SimpleWorkflow;
 
 // Re-export main types
 pub use cohere::CohereClient;
+pub use embedding_backfill::EmbeddingBackfillService;
 pub use huggingface::HuggingFaceClient;
 pub use hybrid_search::{HybridSearchConfig, HybridSearchResult, HybridSearchService};
 pub use openai::OpenAIClient;
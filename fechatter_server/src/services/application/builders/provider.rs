@@ -3,12 +3,17 @@
 //! **Responsibility**: High-availability, high-performance service creation and lifecycle management
 //! **Features**: Circuit breakers, connection pooling, caching, monitoring, graceful degradation
 
+use crate::domains::chat::chat_domain::ChatConfig;
+use crate::domains::messaging::messaging_domain::{
+    AttachmentSizeLookup, ContentModerator, MessageConfig, MessageDomainServiceImpl,
+    ModerationAction,
+};
+use crate::domains::messaging::repository::MessageRepository;
+use crate::domains::workspace::workspace_domain::WorkspaceConfig;
 use crate::services::application::workers::chat::ChatApplicationService;
 use crate::services::application::workers::message::MessageApplicationService;
 use crate::services::infrastructure::cache::redis::RedisCacheService;
-use crate::services::infrastructure::flows::{
-    create_typing_indicator_service, RealtimeStreamService, TypingIndicatorService,
-};
+use crate::services::infrastructure::flows::RealtimeStreamService;
 use crate::services::infrastructure::search::InfraSearchService;
 use fechatter_core::models::jwt::TokenManager;
 use serde::Serialize;
@@ -89,6 +94,19 @@ pub struct ServiceProvider {
 
     /// NATS config for message service
     nats_url: Option<String>,
+
+    /// Content moderation for message sending
+    moderation: Option<(Arc<dyn ContentModerator>, ModerationAction)>,
+
+    /// Chat caps for chat creation and member additions
+    chat_config: ChatConfig,
+
+    /// Workspace caps
+    workspace_config: WorkspaceConfig,
+
+    /// Message caps, and where attachment sizes for the byte budget come from
+    message_config: MessageConfig,
+    attachment_sizes: Option<Arc<dyn AttachmentSizeLookup>>,
 }
 
 impl ServiceProvider {
@@ -110,6 +128,11 @@ impl ServiceProvider {
             circuit_breaker_threshold: 5,
             circuit_breaker_timeout: Duration::from_secs(60),
             nats_url: None,
+            moderation: None,
+            chat_config: ChatConfig::default(),
+            workspace_config: WorkspaceConfig::default(),
+            message_config: MessageConfig::production_optimized(),
+            attachment_sizes: None,
         }
     }
 
@@ -165,7 +188,12 @@ impl ServiceProvider {
     pub fn chat_application_service(&self) -> Arc<ChatApplicationService> {
         self.get_or_create_cached_service("chat_service", || {
             debug!("Creating new ChatApplicationService instance");
-            Arc::new(ChatApplicationService::new_with_pool(self.pool.clone()))
+            let service = ChatApplicationService::new_with_pool(self.pool.clone())
+                .with_config(self.chat_config.clone());
+            Arc::new(match &self.event_publisher {
+                Some(publisher) => service.with_event_publisher(publisher.clone()),
+                None => service,
+            })
         })
     }
 
@@ -180,15 +208,17 @@ impl ServiceProvider {
 
       // Create workspace service directly without AppState dependency
       use crate::domains::workspace::{
-        repository::WorkspaceRepositoryImpl,
-        workspace_domain::{WorkspaceConfig, WorkspaceDomainServiceImpl},
+        repository::WorkspaceRepositoryImpl, workspace_domain::WorkspaceDomainServiceImpl,
       };
       use crate::services::application::workers::workspace::service::WorkspaceApplicationService;
 
       let workspace_repository = Arc::new(WorkspaceRepositoryImpl::new(self.pool.clone()));
       let workspace_domain_service = Arc::new(WorkspaceDomainServiceImpl::new(
         workspace_repository,
-        WorkspaceConfig::default(),
+        Arc::new(crate::domains::permission::PermissionService::new(
+          self.pool.clone(),
+        )),
+        self.workspace_config.clone(),
       ));
 
       // Create the service using the same pattern as from_app_state but without circular dependency
@@ -208,15 +238,6 @@ impl ServiceProvider {
         })
     }
 
-    /// Create cached typing indicator service
-    #[instrument(skip(self))]
-    pub fn typing_indicator_service(&self) -> Arc<TypingIndicatorService> {
-        self.get_or_create_cached_service("typing_indicator", || {
-            debug!("Creating new TypingIndicatorService instance");
-            create_typing_indicator_service()
-        })
-    }
-
     /// Get service health status
     #[instrument(skip(self))]
     pub fn get_service_health(&self, service_name: &str) -> ServiceHealth {
@@ -365,10 +386,6 @@ impl ServiceProvider {
     fn create_optimized_realtime_stream(
         &self,
     ) -> Arc<RealtimeStreamService<crate::services::infrastructure::event::InMemoryTransport>> {
-        use crate::domains::messaging::{
-            messaging_domain::{MessageConfig, MessageDomainServiceImpl},
-            repository::MessageRepository,
-        };
         use crate::services::infrastructure::event::{
             InMemoryTransport, LegacyEventPublisher as EventPublisher,
         };
@@ -376,8 +393,7 @@ impl ServiceProvider {
         let transport = InMemoryTransport::new();
         let event_publisher = Arc::new(EventPublisher::with_transport(transport));
         let repository = Arc::new(MessageRepository::new(self.pool.clone()));
-        let config = MessageConfig::production_optimized();
-        let message_domain_service = Arc::new(MessageDomainServiceImpl::new(repository, config));
+        let message_domain_service = Arc::new(self.message_domain_service(repository));
 
         Arc::new(RealtimeStreamService::new(
             message_domain_service,
@@ -385,6 +401,18 @@ impl ServiceProvider {
         ))
     }
 
+    /// Message domain service with the configured caps and attachment size lookup
+    fn message_domain_service(
+        &self,
+        repository: Arc<MessageRepository>,
+    ) -> MessageDomainServiceImpl {
+        let service = MessageDomainServiceImpl::new(repository, self.message_config.clone());
+        match &self.attachment_sizes {
+            Some(attachment_sizes) => service.with_attachment_sizes(attachment_sizes.clone()),
+            None => service,
+        }
+    }
+
     /// Check if circuit breaker is open
     fn is_circuit_breaker_open(&self, service_name: &str) -> bool {
         let breakers = self.circuit_breakers.read().unwrap();
@@ -466,9 +494,6 @@ impl ServiceProvider {
     fn create_optimized_message_service(
         &self,
     ) -> crate::services::application::workers::message::MessageApplicationService {
-        use crate::domains::messaging::{
-            messaging_domain::MessageDomainServiceImpl, repository::MessageRepository,
-        };
         use crate::services::application::workers::message::{
             AppStateEventPublisher, DualStreamDispatcher, DualStreamMessageService,
         };
@@ -481,9 +506,11 @@ impl ServiceProvider {
 
         // Create repository with our pool
         let repository = Arc::new(MessageRepository::new(self.pool.clone()));
-        let config =
-            crate::domains::messaging::messaging_domain::MessageConfig::production_optimized();
-        let domain_service = Arc::new(MessageDomainServiceImpl::new(repository, config));
+        let mut domain_service = self.message_domain_service(repository);
+        if let Some((moderator, action)) = &self.moderation {
+            domain_service = domain_service.with_moderation(moderator.clone(), *action);
+        }
+        let domain_service = Arc::new(domain_service);
 
         // CRITICAL FIX: Use real NATS connection instead of in-memory
         let dispatcher = if let Some(ref nats_url) = self.nats_url {
@@ -557,6 +584,11 @@ pub struct ServiceProviderBuilder {
     circuit_breaker_threshold: u32,
     circuit_breaker_timeout: Duration,
     nats_url: Option<String>,
+    moderation: Option<(Arc<dyn ContentModerator>, ModerationAction)>,
+    chat_config: ChatConfig,
+    workspace_config: WorkspaceConfig,
+    message_config: MessageConfig,
+    attachment_sizes: Option<Arc<dyn AttachmentSizeLookup>>,
 }
 
 impl ServiceProviderBuilder {
@@ -612,6 +644,43 @@ impl ServiceProviderBuilder {
         self
     }
 
+    /// Configure content moderation for message sending
+    pub fn with_moderation(
+        mut self,
+        moderator: Arc<dyn ContentModerator>,
+        action: ModerationAction,
+    ) -> Self {
+        self.moderation = Some((moderator, action));
+        self
+    }
+
+    /// Configure chat caps (chats per user, members per chat)
+    pub fn with_chat_config(mut self, chat_config: ChatConfig) -> Self {
+        self.chat_config = chat_config;
+        self
+    }
+
+    /// Configure workspace caps (members per workspace)
+    pub fn with_workspace_config(mut self, workspace_config: WorkspaceConfig) -> Self {
+        self.workspace_config = workspace_config;
+        self
+    }
+
+    /// Configure message caps (content length, file count, attachment bytes)
+    pub fn with_message_config(mut self, message_config: MessageConfig) -> Self {
+        self.message_config = message_config;
+        self
+    }
+
+    /// Configure where attachment sizes come from; without it the byte budget is not enforced
+    pub fn with_attachment_sizes(
+        mut self,
+        attachment_sizes: Arc<dyn AttachmentSizeLookup>,
+    ) -> Self {
+        self.attachment_sizes = Some(attachment_sizes);
+        self
+    }
+
     /// Build the production-grade service provider
     pub fn build(self) -> ServiceProvider {
         info!(
@@ -635,19 +704,29 @@ impl ServiceProviderBuilder {
             circuit_breaker_threshold: self.circuit_breaker_threshold,
             circuit_breaker_timeout: self.circuit_breaker_timeout,
             nats_url: self.nats_url,
+            moderation: self.moderation,
+            chat_config: self.chat_config,
+            workspace_config: self.workspace_config,
+            message_config: self.message_config,
+            attachment_sizes: self.attachment_sizes,
         }
     }
 }
 
 /// Message configuration optimized for production
-impl crate::domains::messaging::messaging_domain::MessageConfig {
+impl MessageConfig {
     /// Create production-optimized configuration
     pub fn production_optimized() -> Self {
+        let limits = crate::config::MessageLimitsConfig::default();
         Self {
             cache_enabled: true,
             cache_ttl: 300, // 5 minutes for production
-            max_content_length: 16384,
-            max_file_count: 10,
+            max_content_length: limits.max_content_length,
+            max_file_count: limits.max_file_count,
+            max_total_attachment_bytes: limits.max_total_attachment_bytes,
+            edit_window_secs: limits.edit_window_secs,
+            edit_window_exempt_admins: limits.edit_window_exempt_admins,
+            moderation: None,
         }
     }
 }
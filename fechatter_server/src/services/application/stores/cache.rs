@@ -51,6 +51,11 @@ impl CacheStrategyService {
         Self { cache: None }
     }
 
+    /// Member count counters backed by the same cache
+    pub fn member_counts(&self) -> super::MemberCountStore {
+        super::MemberCountStore::new_optional(self.cache.clone())
+    }
+
     // ============================================================================
     // TTL Policy Constants
     // ============================================================================
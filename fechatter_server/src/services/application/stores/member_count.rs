@@ -0,0 +1,369 @@
+//! # Chat Member Count Store
+//!
+//! **Responsibility**: Cached per-chat member counters that stay equal to the database count
+//! **Principles**: The database is authoritative; a missing counter is reseeded from it, and
+//! join/leave adjust an existing counter under the same lock as the membership change. The
+//! cache never gates the change itself: without the lock the counter is dropped instead, and
+//! dropped again once the holder releases it, in case that was a reseed of the old count
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::services::infrastructure::cache::RedisCacheService;
+use crate::AppError;
+
+/// Cached chat member counts
+pub struct MemberCountStore {
+    cache: Option<Arc<RedisCacheService>>,
+}
+
+impl MemberCountStore {
+    /// Member count cache TTL (1 day)
+    pub const MEMBER_COUNT_TTL: u64 = 86400;
+
+    /// Lock TTL for a membership change (seconds)
+    const LOCK_TTL: u64 = 30;
+
+    /// Wait between attempts to take a lock held by someone else
+    const LOCK_RETRY: Duration = Duration::from_millis(20);
+
+    /// Create a member count store with optional cache
+    pub fn new_optional(cache: Option<Arc<RedisCacheService>>) -> Self {
+        Self { cache }
+    }
+
+    /// Generate chat member count cache key
+    pub fn member_count_key(chat_id: i64) -> String {
+        format!("chat:member:count:{}", chat_id)
+    }
+
+    fn lock_resource(chat_id: i64) -> String {
+        format!("chat_membership:{}", chat_id)
+    }
+
+    /// Cached member count, falling back to `count_from_db` and reseeding the cache when missing
+    pub async fn get_member_count<F, Fut>(
+        &self,
+        chat_id: i64,
+        count_from_db: F,
+    ) -> Result<i64, AppError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<i64, AppError>>,
+    {
+        let Some(cache) = &self.cache else {
+            return count_from_db().await;
+        };
+
+        let key = Self::member_count_key(chat_id);
+        match cache.get::<i64>(&key).await {
+            Ok(Some(count)) => return Ok(count),
+            Ok(None) => {}
+            Err(e) => {
+                warn!(
+                    "Failed to read member count cache for chat {}: {}",
+                    chat_id, e
+                );
+                return count_from_db().await;
+            }
+        }
+
+        // Reseed under the membership lock so a concurrent join/leave can't be lost
+        let seeded = cache
+            .with_lock(&Self::lock_resource(chat_id), Self::LOCK_TTL, async {
+                if let Some(count) = cache.get::<i64>(&key).await? {
+                    return Ok(count);
+                }
+                let count = count_from_db().await?;
+                cache.set(&key, &count, Self::MEMBER_COUNT_TTL).await?;
+                Ok(count)
+            })
+            .await;
+
+        match seeded {
+            Ok(count) => Ok(count),
+            Err(e) => {
+                warn!("Failed to reseed member count for chat {}: {}", chat_id, e);
+                count_from_db().await
+            }
+        }
+    }
+
+    /// Run a membership change and apply its signed member delta to the cached counter under
+    /// the chat's membership lock. The change runs whether or not the lock can be taken; when
+    /// it can't (another change or a reseed holds it, or Redis is down) the counter is dropped
+    /// instead, before and after the holder releases the lock
+    pub async fn apply_membership_change<F, Fut>(
+        &self,
+        chat_id: i64,
+        change: F,
+    ) -> Result<i64, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<i64, AppError>>,
+    {
+        let Some(cache) = &self.cache else {
+            return change().await;
+        };
+
+        let resource = Self::lock_resource(chat_id);
+        let token = uuid::Uuid::new_v4().to_string();
+        let locked = match cache.try_lock(&resource, Self::LOCK_TTL, &token).await {
+            Ok(locked) => locked,
+            Err(e) => {
+                warn!("Failed to lock member count for chat {}: {}", chat_id, e);
+                false
+            }
+        };
+
+        let result = change().await;
+        match result {
+            Ok(delta) if delta != 0 && locked => self.adjust(cache, chat_id, delta).await,
+            Ok(delta) if delta != 0 => self.drop_count_past_holder(cache, chat_id).await,
+            _ => {}
+        }
+
+        if locked {
+            if let Err(e) = cache.release_lock(&resource, &token).await {
+                warn!("Failed to unlock member count for chat {}: {}", chat_id, e);
+            }
+        }
+        result
+    }
+
+    /// Adjust an existing counter; on failure drop it so the next read reseeds from the database
+    async fn adjust(&self, cache: &RedisCacheService, chat_id: i64, delta: i64) {
+        let key = Self::member_count_key(chat_id);
+        if let Err(e) = cache
+            .incr_if_exists(&key, delta, Self::MEMBER_COUNT_TTL)
+            .await
+        {
+            warn!(
+                "Failed to adjust member count for chat {}: {}, dropping cached count",
+                chat_id, e
+            );
+            self.drop_count(cache, chat_id).await;
+        }
+    }
+
+    /// Drop the counter after a change made without the lock. The holder may be a reseed that
+    /// counted the database before the change committed and is about to store that count, so
+    /// drop it again once the lock is released or has expired
+    async fn drop_count_past_holder(&self, cache: &RedisCacheService, chat_id: i64) {
+        self.drop_count(cache, chat_id).await;
+
+        let resource = Self::lock_resource(chat_id);
+        let token = uuid::Uuid::new_v4().to_string();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(Self::LOCK_TTL);
+        let locked = loop {
+            match cache.try_lock(&resource, Self::LOCK_TTL, &token).await {
+                Ok(true) => break true,
+                Ok(false) if tokio::time::Instant::now() < deadline => {
+                    tokio::time::sleep(Self::LOCK_RETRY).await
+                }
+                Ok(false) => break false,
+                // Redis is down, so there is no counter to drop
+                Err(_) => return,
+            }
+        };
+
+        self.drop_count(cache, chat_id).await;
+        if locked {
+            if let Err(e) = cache.release_lock(&resource, &token).await {
+                warn!("Failed to unlock member count for chat {}: {}", chat_id, e);
+            }
+        }
+    }
+
+    /// Drop the counter so the next read reseeds it from the database
+    async fn drop_count(&self, cache: &RedisCacheService, chat_id: i64) {
+        if let Err(e) = cache.del(&Self::member_count_key(chat_id)).await {
+            warn!("Failed to drop member count for chat {}: {}", chat_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn member_count_key_should_be_per_chat() {
+        assert_eq!(
+            MemberCountStore::member_count_key(42),
+            "chat:member:count:42"
+        );
+    }
+
+    #[tokio::test]
+    async fn without_cache_should_use_database_count() {
+        let store = MemberCountStore::new_optional(None);
+
+        let count = store.get_member_count(1, || async { Ok(3) }).await.unwrap();
+        assert_eq!(count, 3);
+
+        let delta = store
+            .apply_membership_change(1, || async { Ok(-1) })
+            .await
+            .unwrap();
+        assert_eq!(delta, -1);
+    }
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+        use std::sync::atomic::{AtomicI64, Ordering};
+
+        #[tokio::test]
+        async fn join_leave_should_keep_cached_count_equal_to_db_count() {
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let cache = Arc::new(
+                RedisCacheService::new(&redis_url, "test")
+                    .await
+                    .expect("Redis down?"),
+            );
+            let store = MemberCountStore::new_optional(Some(cache.clone()));
+            let chat_id = 9_000_000 + i64::from(std::process::id() % 100_000);
+            let key = MemberCountStore::member_count_key(chat_id);
+            cache.del(&key).await.unwrap();
+
+            // Stand-in for the chat_members table
+            let db_count = Arc::new(AtomicI64::new(2));
+            let count_from_db = || {
+                let db_count = db_count.clone();
+                async move { Ok(db_count.load(Ordering::SeqCst)) }
+            };
+            let change = |delta: i64| {
+                let db_count = db_count.clone();
+                move || async move {
+                    db_count.fetch_add(delta, Ordering::SeqCst);
+                    Ok(delta)
+                }
+            };
+
+            // A change before seeding leaves the counter unseeded rather than starting at 0
+            store
+                .apply_membership_change(chat_id, change(1))
+                .await
+                .unwrap();
+            assert_eq!(cache.get::<i64>(&key).await.unwrap(), None);
+            assert_eq!(
+                store
+                    .get_member_count(chat_id, count_from_db)
+                    .await
+                    .unwrap(),
+                3
+            );
+
+            for delta in [1, 1, -1, 1, -1, -1] {
+                store
+                    .apply_membership_change(chat_id, change(delta))
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    store
+                        .get_member_count(chat_id, count_from_db)
+                        .await
+                        .unwrap(),
+                    db_count.load(Ordering::SeqCst)
+                );
+            }
+
+            // Cache loss (e.g. Redis restart) reseeds from the database
+            cache.del(&key).await.unwrap();
+            assert_eq!(
+                store
+                    .get_member_count(chat_id, count_from_db)
+                    .await
+                    .unwrap(),
+                db_count.load(Ordering::SeqCst)
+            );
+
+            cache.del(&key).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn contended_change_should_go_ahead_and_drop_the_count() {
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let cache = Arc::new(
+                RedisCacheService::new(&redis_url, "test")
+                    .await
+                    .expect("Redis down?"),
+            );
+            let store = MemberCountStore::new_optional(Some(cache.clone()));
+            let chat_id = 9_100_000 + i64::from(std::process::id() % 100_000);
+            let key = MemberCountStore::member_count_key(chat_id);
+            cache.set(&key, &5i64, 60).await.unwrap();
+
+            // Another change is holding the chat's lock
+            let resource = MemberCountStore::lock_resource(chat_id);
+            assert!(cache.try_lock(&resource, 30, "other").await.unwrap());
+
+            let (delta, _) = tokio::join!(
+                store.apply_membership_change(chat_id, || async { Ok(1) }),
+                async {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    cache.release_lock(&resource, "other").await.unwrap();
+                }
+            );
+            assert_eq!(delta.unwrap(), 1);
+            assert_eq!(cache.get::<i64>(&key).await.unwrap(), None);
+        }
+
+        #[tokio::test]
+        async fn change_during_a_reseed_should_not_leave_the_old_count() {
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let cache = Arc::new(
+                RedisCacheService::new(&redis_url, "test")
+                    .await
+                    .expect("Redis down?"),
+            );
+            let store = MemberCountStore::new_optional(Some(cache.clone()));
+            let chat_id = 9_200_000 + i64::from(std::process::id() % 100_000);
+            let key = MemberCountStore::member_count_key(chat_id);
+            cache.del(&key).await.unwrap();
+            let db_count = Arc::new(AtomicI64::new(2));
+
+            // A reseed takes the lock and counts 2 members, then a join commits and drops the
+            // counter before the reseed stores its count
+            let resource = MemberCountStore::lock_resource(chat_id);
+            assert!(cache.try_lock(&resource, 30, "reseed").await.unwrap());
+            let seeded = db_count.load(Ordering::SeqCst);
+            let joined = db_count.clone();
+            let (delta, _) = tokio::join!(
+                store.apply_membership_change(chat_id, move || async move {
+                    joined.fetch_add(1, Ordering::SeqCst);
+                    Ok(1)
+                }),
+                async {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    cache
+                        .set(&key, &seeded, MemberCountStore::MEMBER_COUNT_TTL)
+                        .await
+                        .unwrap();
+                    cache.release_lock(&resource, "reseed").await.unwrap();
+                }
+            );
+            assert_eq!(delta.unwrap(), 1);
+
+            let count_from_db = || {
+                let db_count = db_count.clone();
+                async move { Ok(db_count.load(Ordering::SeqCst)) }
+            };
+            assert_eq!(
+                store
+                    .get_member_count(chat_id, count_from_db)
+                    .await
+                    .unwrap(),
+                3
+            );
+
+            cache.del(&key).await.unwrap();
+        }
+    }
+}
@@ -4,6 +4,12 @@
 //! **原则**: 简化职责，专注核心缓存功能
 
 pub mod cache;
+pub mod member_count;
+pub mod response_cache;
+pub mod unread_count;
 
 // 重新导出核心缓存服务
 pub use cache::{CacheDataType, CacheStrategyService, InvalidationPattern};
+pub use member_count::MemberCountStore;
+pub use response_cache::{CacheOutcome, Cached, ResponseCache};
+pub use unread_count::UnreadCountStore;
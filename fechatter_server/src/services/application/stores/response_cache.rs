@@ -0,0 +1,289 @@
+//! # Response Cache
+//!
+//! **Responsibility**: Short-lived copies of expensive read responses, per user, endpoint and
+//! query parameters
+//! **Principles**: Entries live in a versioned namespace per workspace, so a write invalidates
+//! every cached read of that workspace at once by bumping its version; without Redis every
+//! read is a miss and goes to the loader
+
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::services::infrastructure::cache::{CacheNamespaces, RedisCacheService};
+use crate::AppError;
+use fechatter_core::{UserId, WorkspaceId};
+
+/// Whether a response came from the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheOutcome {
+    Hit,
+    Miss,
+}
+
+impl CacheOutcome {
+    /// Value of the `X-Cache` header
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hit => "HIT",
+            Self::Miss => "MISS",
+        }
+    }
+}
+
+/// A response body and where it came from
+#[derive(Debug)]
+pub struct Cached<T> {
+    pub value: T,
+    pub outcome: CacheOutcome,
+}
+
+impl<T> Cached<T> {
+    /// Wrap or convert the body, keeping the outcome
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Cached<U> {
+        Cached {
+            value: f(self.value),
+            outcome: self.outcome,
+        }
+    }
+}
+
+/// Cached read responses
+pub struct ResponseCache {
+    cache: Option<Arc<RedisCacheService>>,
+    namespaces: Option<CacheNamespaces>,
+    ttl_seconds: u64,
+}
+
+impl ResponseCache {
+    /// Create a response cache; a zero TTL disables it
+    pub fn new_optional(cache: Option<Arc<RedisCacheService>>, ttl_seconds: u64) -> Self {
+        let cache = cache.filter(|_| ttl_seconds > 0);
+        Self {
+            namespaces: cache.clone().map(CacheNamespaces::new),
+            cache,
+            ttl_seconds,
+        }
+    }
+
+    pub fn ttl_seconds(&self) -> u64 {
+        self.ttl_seconds
+    }
+
+    /// Namespace holding every cached response of the workspace
+    fn namespace(workspace_id: WorkspaceId) -> String {
+        format!("response:{}", i64::from(workspace_id))
+    }
+
+    /// Key of one user's response to `endpoint` with `params`, within the workspace namespace
+    pub fn response_key<P: Serialize>(endpoint: &str, user_id: UserId, params: &P) -> String {
+        let bytes = serde_json::to_vec(params).unwrap_or_default();
+        let digest = Sha256::digest(&bytes);
+        format!(
+            "{}:{}:{}",
+            endpoint,
+            i64::from(user_id),
+            hex::encode(&digest[..8])
+        )
+    }
+
+    /// Cached response, or the result of `load` stored for the next identical request.
+    /// Errors are never cached, and a failing cache falls back to `load`
+    pub async fn get_or_load<T, P, F, Fut>(
+        &self,
+        workspace_id: WorkspaceId,
+        endpoint: &str,
+        user_id: UserId,
+        params: &P,
+        load: F,
+    ) -> Result<Cached<T>, AppError>
+    where
+        T: Serialize + DeserializeOwned,
+        P: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let (Some(cache), Some(namespaces)) = (&self.cache, &self.namespaces) else {
+            return Ok(Cached {
+                value: load().await?,
+                outcome: CacheOutcome::Miss,
+            });
+        };
+
+        let suffix = Self::response_key(endpoint, user_id, params);
+        let key = match namespaces
+            .key(&Self::namespace(workspace_id), &suffix)
+            .await
+        {
+            Ok(key) => key,
+            Err(e) => {
+                warn!("Unreadable response cache version: {}", e);
+                return Ok(Cached {
+                    value: load().await?,
+                    outcome: CacheOutcome::Miss,
+                });
+            }
+        };
+        match cache.get::<T>(&key).await {
+            Ok(Some(value)) => {
+                return Ok(Cached {
+                    value,
+                    outcome: CacheOutcome::Hit,
+                })
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Unreadable cached response {}: {}", key, e),
+        }
+
+        let value = load().await?;
+        if let Err(e) = cache.set(&key, &value, self.ttl_seconds).await {
+            warn!("Failed to cache response {}: {}", key, e);
+        }
+        Ok(Cached {
+            value,
+            outcome: CacheOutcome::Miss,
+        })
+    }
+
+    /// Retire every cached response of the workspace; call after writes that change what its
+    /// reads return. Retired entries are not deleted, they expire with their TTL
+    pub async fn invalidate_workspace(&self, workspace_id: WorkspaceId) {
+        let Some(namespaces) = &self.namespaces else {
+            return;
+        };
+
+        if let Err(e) = namespaces.invalidate(&Self::namespace(workspace_id)).await {
+            warn!(
+                "Failed to invalidate cached responses of workspace {}: {}",
+                i64::from(workspace_id),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn response_key_should_differ_per_user_and_params() {
+        let key = |user: i64, q: &str| ResponseCache::response_key("admin_users", UserId(user), &q);
+
+        assert!(key(7, "ann").starts_with("admin_users:7:"));
+        // Before the first invalidation keys keep the pre-versioning layout
+        assert!(CacheNamespaces::versioned_key(
+            &ResponseCache::namespace(WorkspaceId(3)),
+            0,
+            &key(7, "ann")
+        )
+        .starts_with("response:3:admin_users:7:"));
+        assert_eq!(key(7, "ann"), key(7, "ann"));
+        assert_ne!(key(7, "ann"), key(8, "ann"));
+        assert_ne!(key(7, "ann"), key(7, "bob"));
+    }
+
+    #[tokio::test]
+    async fn without_cache_every_read_should_load() {
+        let cache = ResponseCache::new_optional(None, 15);
+        let loads = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let cached = cache
+                .get_or_load(WorkspaceId(1), "workspace", UserId(1), &(), || async {
+                    loads.fetch_add(1, Ordering::SeqCst);
+                    Ok(42i64)
+                })
+                .await
+                .unwrap();
+            assert_eq!(cached.value, 42);
+            assert_eq!(cached.outcome, CacheOutcome::Miss);
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+
+        #[tokio::test]
+        async fn second_read_should_hit_until_a_write_invalidates() {
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let redis = Arc::new(
+                RedisCacheService::new(&redis_url, "test")
+                    .await
+                    .expect("Redis down?"),
+            );
+            let cache = ResponseCache::new_optional(Some(redis), 60);
+            let workspace_id = WorkspaceId(9_000_000 + i64::from(std::process::id() % 100_000));
+            let loads = AtomicUsize::new(0);
+            // Stand-in for the workspace aggregate, counting how often it is really read
+            let read = || {
+                cache.get_or_load(workspace_id, "workspace", UserId(1), &(), || async {
+                    Ok(loads.fetch_add(1, Ordering::SeqCst) as i64)
+                })
+            };
+
+            let first = read().await.unwrap();
+            assert_eq!((first.value, first.outcome), (0, CacheOutcome::Miss));
+            let second = read().await.unwrap();
+            assert_eq!((second.value, second.outcome), (0, CacheOutcome::Hit));
+
+            // Another workspace's write leaves this one cached
+            cache.invalidate_workspace(WorkspaceId(workspace_id.0 + 1)).await;
+            assert_eq!(read().await.unwrap().outcome, CacheOutcome::Hit);
+
+            cache.invalidate_workspace(workspace_id).await;
+            let after_write = read().await.unwrap();
+            assert_eq!(
+                (after_write.value, after_write.outcome),
+                (1, CacheOutcome::Miss)
+            );
+            assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+            cache.invalidate_workspace(workspace_id).await;
+        }
+
+        #[tokio::test]
+        async fn reads_should_load_from_the_database_while_redis_is_down() -> anyhow::Result<()> {
+            use crate::config::CacheDegradationConfig;
+            use crate::services::application::workers::workspace::create_workspace_application_service;
+
+            let (state, users) = crate::setup_test_users!(2).await;
+            let (url, server) = crate::tests::test_utils::fake_redis().await;
+            let redis = Arc::new(
+                RedisCacheService::new(&url, "test")
+                    .await?
+                    .with_degradation(CacheDegradationConfig {
+                        fail_open: true,
+                        failure_threshold: 2,
+                        open_secs: 60,
+                        command_timeout_ms: 200,
+                    }),
+            );
+            server.abort();
+            let _ = server.await;
+
+            let cache = ResponseCache::new_optional(Some(redis.clone()), 60);
+            let workspaces = create_workspace_application_service(&state)?;
+            let workspace_id = users[0].workspace_id;
+            for _ in 0..3 {
+                let cached = cache
+                    .get_or_load(workspace_id, "workspace", users[0].id, &(), || async {
+                        Ok(workspaces
+                            .get_workspace_details(workspace_id)
+                            .await?
+                            .member_count)
+                    })
+                    .await?;
+                assert_eq!((cached.value, cached.outcome), (2, CacheOutcome::Miss));
+            }
+            assert!(redis.is_circuit_open());
+            Ok(())
+        }
+    }
+}
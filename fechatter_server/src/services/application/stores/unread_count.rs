@@ -0,0 +1,302 @@
+//! # Unread Count Store
+//!
+//! **Responsibility**: Cached per-user, per-chat unread counters with an authoritative recompute path
+//! **Principles**: The database is authoritative; event scripts maintain or invalidate the counter,
+//! and a missing, unreadable or explicitly refreshed counter is recomputed and overwritten
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::services::infrastructure::cache::RedisCacheService;
+use crate::AppError;
+
+/// Cached unread counts
+pub struct UnreadCountStore {
+    cache: Option<Arc<RedisCacheService>>,
+}
+
+impl UnreadCountStore {
+    /// Unread count cache TTL (7 days, same as the INCR/DECR path)
+    pub const UNREAD_COUNT_TTL: u64 = 604800;
+
+    /// Create an unread count store with optional cache
+    pub fn new_optional(cache: Option<Arc<RedisCacheService>>) -> Self {
+        Self { cache }
+    }
+
+    /// Generate unread count cache key
+    pub fn unread_key(user_id: i64, chat_id: i64) -> String {
+        format!("unread:{}:{}", user_id, chat_id)
+    }
+
+    /// Recount unread messages with `count_from_db` and overwrite the cached counter
+    pub async fn recompute_unread<F, Fut>(
+        &self,
+        user_id: i64,
+        chat_id: i64,
+        count_from_db: F,
+    ) -> Result<i64, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<i64, AppError>>,
+    {
+        let count = count_from_db().await?;
+
+        if let Some(cache) = &self.cache {
+            let key = Self::unread_key(user_id, chat_id);
+            if let Err(e) = cache.set(&key, &count, Self::UNREAD_COUNT_TTL).await {
+                warn!(
+                    "Failed to overwrite unread count for user {} in chat {}: {}",
+                    user_id, chat_id, e
+                );
+            } else {
+                debug!(
+                    "Recomputed unread count for user {} in chat {}: {}",
+                    user_id, chat_id, count
+                );
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Cached unread count; recomputed when `refresh` is set or the cached value is missing
+    /// or unusable
+    pub async fn get_unread_count<F, Fut>(
+        &self,
+        user_id: i64,
+        chat_id: i64,
+        refresh: bool,
+        count_from_db: F,
+    ) -> Result<i64, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<i64, AppError>>,
+    {
+        let Some(cache) = &self.cache else {
+            return count_from_db().await;
+        };
+
+        if !refresh {
+            let key = Self::unread_key(user_id, chat_id);
+            match cache.get::<i64>(&key).await {
+                Ok(Some(count)) if count >= 0 => return Ok(count),
+                Ok(Some(count)) => warn!(
+                    "Negative unread count {} for user {} in chat {}, recomputing",
+                    count, user_id, chat_id
+                ),
+                Ok(None) => {}
+                Err(e) => warn!(
+                    "Unreadable unread count for user {} in chat {}: {}, recomputing",
+                    user_id, chat_id, e
+                ),
+            }
+        }
+
+        self.recompute_unread(user_id, chat_id, count_from_db).await
+    }
+
+    /// Cached unread counts for several chats, in `chat_ids` order. Chats without a usable
+    /// counter are passed to `count_from_db` together, and their counters reseeded
+    pub async fn get_unread_counts<F, Fut>(
+        &self,
+        user_id: i64,
+        chat_ids: &[i64],
+        count_from_db: F,
+    ) -> Result<Vec<i64>, AppError>
+    where
+        F: FnOnce(Vec<i64>) -> Fut,
+        Fut: Future<Output = Result<HashMap<i64, i64>, AppError>>,
+    {
+        let keys: Vec<String> = chat_ids
+            .iter()
+            .map(|chat_id| Self::unread_key(user_id, *chat_id))
+            .collect();
+        let cached = match &self.cache {
+            Some(cache) if !keys.is_empty() => {
+                let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                match cache.mget::<i64>(&keys).await {
+                    Ok(counts) => counts
+                        .into_iter()
+                        .map(|count| count.filter(|count| *count >= 0))
+                        .collect(),
+                    Err(e) => {
+                        warn!(
+                            "Failed to read unread counts for user {}: {}, recomputing",
+                            user_id, e
+                        );
+                        vec![None; chat_ids.len()]
+                    }
+                }
+            }
+            _ => vec![None; chat_ids.len()],
+        };
+
+        let missing: Vec<i64> = chat_ids
+            .iter()
+            .zip(&cached)
+            .filter(|(_, count)| count.is_none())
+            .map(|(chat_id, _)| *chat_id)
+            .collect();
+        if missing.is_empty() {
+            return Ok(cached.into_iter().flatten().collect());
+        }
+        let counted = count_from_db(missing.clone()).await?;
+
+        if let Some(cache) = &self.cache {
+            for chat_id in &missing {
+                let count = counted.get(chat_id).copied().unwrap_or(0);
+                let key = Self::unread_key(user_id, *chat_id);
+                if let Err(e) = cache.set(&key, &count, Self::UNREAD_COUNT_TTL).await {
+                    warn!(
+                        "Failed to overwrite unread count for user {} in chat {}: {}",
+                        user_id, chat_id, e
+                    );
+                }
+            }
+        }
+
+        Ok(chat_ids
+            .iter()
+            .zip(cached)
+            .map(|(chat_id, count)| {
+                count.unwrap_or_else(|| counted.get(chat_id).copied().unwrap_or(0))
+            })
+            .collect())
+    }
+
+    /// Drop the cached counter so the next read recomputes it
+    pub async fn invalidate(&self, user_id: i64, chat_id: i64) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+
+        if let Err(e) = cache.del(&Self::unread_key(user_id, chat_id)).await {
+            warn!(
+                "Failed to drop unread count for user {} in chat {}: {}",
+                user_id, chat_id, e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unread_key_should_be_per_user_and_chat() {
+        assert_eq!(UnreadCountStore::unread_key(7, 42), "unread:7:42");
+    }
+
+    #[tokio::test]
+    async fn without_cache_should_use_database_count() {
+        let store = UnreadCountStore::new_optional(None);
+
+        let count = store
+            .get_unread_count(1, 2, false, || async { Ok(4) })
+            .await
+            .unwrap();
+        assert_eq!(count, 4);
+
+        let count = store
+            .recompute_unread(1, 2, || async { Ok(5) })
+            .await
+            .unwrap();
+        assert_eq!(count, 5);
+    }
+
+    #[tokio::test]
+    async fn without_cache_should_count_every_chat_in_one_call() {
+        let store = UnreadCountStore::new_optional(None);
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+
+        let counts = store
+            .get_unread_counts(1, &[10, 11, 12], |chat_ids| {
+                calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async move {
+                    assert_eq!(chat_ids, vec![10, 11, 12]);
+                    Ok(HashMap::from([(10, 3), (12, 1)]))
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(counts, vec![3, 0, 1]);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+
+        #[tokio::test]
+        async fn recompute_should_correct_a_corrupted_cached_count() {
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let cache = Arc::new(
+                RedisCacheService::new(&redis_url, "test")
+                    .await
+                    .expect("Redis down?"),
+            );
+            let store = UnreadCountStore::new_optional(Some(cache.clone()));
+            let user_id = 9_000_000 + i64::from(std::process::id() % 100_000);
+            let chat_id = 1;
+            let key = UnreadCountStore::unread_key(user_id, chat_id);
+
+            // Stand-in for counting messages after the last read receipt
+            let db_count = || async { Ok(3) };
+
+            // A drifted counter is served as-is until a refresh is requested
+            cache.set(&key, &42i64, 60).await.unwrap();
+            assert_eq!(
+                store
+                    .get_unread_count(user_id, chat_id, false, db_count)
+                    .await
+                    .unwrap(),
+                42
+            );
+            assert_eq!(
+                store
+                    .get_unread_count(user_id, chat_id, true, db_count)
+                    .await
+                    .unwrap(),
+                3
+            );
+            assert_eq!(cache.get::<i64>(&key).await.unwrap(), Some(3));
+
+            // Unparseable and negative values are recomputed without a refresh
+            cache.set(&key, &"garbage", 60).await.unwrap();
+            assert_eq!(
+                store
+                    .get_unread_count(user_id, chat_id, false, db_count)
+                    .await
+                    .unwrap(),
+                3
+            );
+            cache.set(&key, &-5i64, 60).await.unwrap();
+            assert_eq!(
+                store
+                    .get_unread_count(user_id, chat_id, false, db_count)
+                    .await
+                    .unwrap(),
+                3
+            );
+
+            // A missing counter is recomputed and reseeded
+            store.invalidate(user_id, chat_id).await;
+            assert_eq!(
+                store
+                    .get_unread_count(user_id, chat_id, false, db_count)
+                    .await
+                    .unwrap(),
+                3
+            );
+            assert_eq!(cache.get::<i64>(&key).await.unwrap(), Some(3));
+
+            cache.del(&key).await.unwrap();
+        }
+    }
+}
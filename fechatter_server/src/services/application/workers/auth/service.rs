@@ -8,7 +8,7 @@
 //! - Full production features (ProductionAuthService)
 //! - Circuit breaker pattern for fault tolerance
 //! - In-memory and distributed caching
-//! - Rate limiting and account lockout protection
+//! - Account lockout protection (signup and signin are throttled by the shared HTTP limiters)
 //! - Security hardening and audit logging
 //! - Connection pooling and retry mechanisms
 
@@ -45,6 +45,8 @@ pub struct AuthUserService {
     user_repository: Arc<dyn UserRepository>,
     token_manager: Arc<TokenManager>,
     pool: Option<Arc<sqlx::PgPool>>,
+    /// Refresh tokens unused for longer than this are rejected
+    refresh_idle_timeout: Option<chrono::Duration>,
 }
 
 impl AuthUserService {
@@ -53,6 +55,7 @@ impl AuthUserService {
             user_repository,
             token_manager,
             pool: None,
+            refresh_idle_timeout: None,
         }
     }
 
@@ -64,9 +67,16 @@ impl AuthUserService {
             )),
             token_manager: app_state.token_manager().clone(),
             pool: Some(app_state.pool().clone()),
+            refresh_idle_timeout: app_state.inner.config.auth.refresh_idle_timeout(),
         }
     }
 
+    /// Reject refresh tokens that went unused for longer than `timeout`
+    pub fn with_refresh_idle_timeout(mut self, timeout: Option<chrono::Duration>) -> Self {
+        self.refresh_idle_timeout = timeout;
+        self
+    }
+
     // ============================================================================
     // User Management Functions
     // ============================================================================
@@ -163,6 +173,43 @@ impl AuthUserService {
         Ok(())
     }
 
+    /// Revoke every session of `user_id` except the one holding `refresh_token`
+    ///
+    /// Returns the number of refresh tokens revoked.
+    #[instrument(skip(self, refresh_token))]
+    pub async fn logout_others(
+        &self,
+        user_id: UserId,
+        refresh_token: &str,
+    ) -> Result<u64, AppError> {
+        use crate::domains::auth::token_repository::RefreshTokenRepository;
+        let user_repo = self
+            .user_repository
+            .as_any()
+            .downcast_ref::<crate::domains::user::repository::UserRepositoryImpl>()
+            .ok_or_else(|| {
+                AppError::Internal(
+                    "Failed to access token repository for logout_others".to_string(),
+                )
+            })?;
+        let token_repo = crate::domains::auth::token_repository::RefreshTokenRepositoryImpl::new(
+            user_repo.pool.clone(),
+        );
+
+        // The presented token must be a live session of the same user
+        let current = token_repo
+            .find_by_token(refresh_token)
+            .await?
+            .filter(|token| token.user_id == i64::from(user_id))
+            .ok_or_else(|| {
+                AppError::Unauthorized("Invalid or expired refresh token".to_string())
+            })?;
+
+        let revoked = token_repo.revoke_all_except(user_id, current.id).await?;
+        info!(user_id = %user_id, kept_token_id = %current.id, revoked = %revoked, "Other sessions logged out");
+        Ok(revoked)
+    }
+
     #[instrument(skip(self))]
     pub async fn list_users(&self, limit: i64, offset: i64) -> Result<Vec<User>, AppError> {
         // TODO: UserRepository doesn't have list method
@@ -235,6 +282,12 @@ impl SigninService for AuthUserService {
             return Ok(None);
         }
 
+        // Suspended accounts keep their password but may not start sessions
+        if user.status == UserStatus::Suspended {
+            info!(user_id = %user.id, "Signin refused for suspended user");
+            return Err(CoreError::Unauthorized("Account is suspended".to_string()));
+        }
+
         // Create user claims from the authenticated user
         let user_claims = fechatter_core::models::jwt::UserClaims {
             id: user.id,
@@ -317,6 +370,19 @@ impl RefreshTokenService for AuthUserService {
             ));
         }
 
+        if let Some(idle_timeout) = self.refresh_idle_timeout {
+            if now - token_record.last_used_at > idle_timeout {
+                info!(
+                  user_id = %token_record.user_id,
+                  last_used_at = %token_record.last_used_at,
+                  "Refresh token rejected after inactivity"
+                );
+                return Err(CoreError::Unauthorized(
+                    "Session expired after inactivity".to_string(),
+                ));
+            }
+        }
+
         // Verify auth context if provided
         if let Some(ctx) = &auth_context {
             let context_matches = crate::domains::auth::token_repository::auth_context_matches(
@@ -343,6 +409,9 @@ impl RefreshTokenService for AuthUserService {
             .find_by_id(fechatter_core::UserId(token_record.user_id))
             .await?
             .ok_or_else(|| CoreError::Unauthorized("User not found".to_string()))?;
+        if user.status == UserStatus::Suspended {
+            return Err(CoreError::Unauthorized("Account is suspended".to_string()));
+        }
 
         // Generate new tokens
         let new_raw_refresh_token = uuid::Uuid::new_v4().to_string();
@@ -787,7 +856,6 @@ pub struct AuthServiceConfig {
     pub max_retries: u32,
     pub initial_retry_delay: Duration,
     pub max_concurrent_operations: usize,
-    pub rate_limit_per_minute: u32,
     pub lockout_threshold: u32,
     pub lockout_duration: Duration,
     pub password_min_length: usize,
@@ -805,7 +873,6 @@ impl Default for AuthServiceConfig {
             max_retries: 3,
             initial_retry_delay: Duration::from_millis(100),
             max_concurrent_operations: 100,
-            rate_limit_per_minute: 60,
             lockout_threshold: 5,
             lockout_duration: Duration::from_secs(900),
             password_min_length: 8,
@@ -815,58 +882,6 @@ impl Default for AuthServiceConfig {
     }
 }
 
-// ============================================================================
-// Rate Limiter
-// ============================================================================
-
-struct RateLimiter {
-    limits: Arc<DashMap<String, RateLimitEntry>>,
-    max_requests: u32,
-    window: Duration,
-}
-
-struct RateLimitEntry {
-    count: u32,
-    window_start: Instant,
-}
-
-impl RateLimiter {
-    fn new(max_requests: u32) -> Self {
-        Self {
-            limits: Arc::new(DashMap::new()),
-            max_requests,
-            window: Duration::from_secs(60),
-        }
-    }
-
-    fn check_rate_limit(&self, key: &str) -> Result<(), CoreError> {
-        let now = Instant::now();
-
-        let mut entry = self
-            .limits
-            .entry(key.to_string())
-            .or_insert(RateLimitEntry {
-                count: 0,
-                window_start: now,
-            });
-
-        if now.duration_since(entry.window_start) > self.window {
-            *entry = RateLimitEntry {
-                count: 1,
-                window_start: now,
-            };
-        } else {
-            entry.count += 1;
-        }
-
-        if entry.count > self.max_requests {
-            Err(CoreError::Validation("Rate limit exceeded".to_string()))
-        } else {
-            Ok(())
-        }
-    }
-}
-
 // ============================================================================
 // Production Auth Service (Full-Featured)
 // ============================================================================
@@ -881,11 +896,11 @@ pub struct ProductionAuthService {
     // Production infrastructure
     redis_pool: Option<Arc<Mutex<redis::aio::MultiplexedConnection>>>,
     circuit_breaker: Arc<CircuitBreaker>,
-    rate_limiter: Arc<RateLimiter>,
     concurrency_limiter: Arc<Semaphore>,
 
     // Configuration
     config: AuthServiceConfig,
+    refresh_idle_timeout: Option<chrono::Duration>,
 
     // Metrics
     metrics: Arc<AuthServiceMetrics>,
@@ -935,9 +950,9 @@ impl ProductionAuthService {
                 config.circuit_breaker_threshold,
                 config.circuit_breaker_timeout,
             )),
-            rate_limiter: Arc::new(RateLimiter::new(config.rate_limit_per_minute)),
             concurrency_limiter: Arc::new(Semaphore::new(config.max_concurrent_operations)),
             config,
+            refresh_idle_timeout: app_state.inner.config.auth.refresh_idle_timeout(),
             metrics: Arc::new(AuthServiceMetrics::default()),
         };
 
@@ -951,16 +966,6 @@ impl ProductionAuthService {
         payload: &CreateUser,
         auth_context: Option<AuthContext>,
     ) -> Result<AuthTokens, CoreError> {
-        // Rate limiting
-        self.rate_limiter
-            .check_rate_limit(&format!("signup:{}", payload.email))
-            .map_err(|_| {
-                self.metrics
-                    .rate_limit_rejections
-                    .fetch_add(1, Ordering::Relaxed);
-                CoreError::Validation("Rate limit exceeded for signup".to_string())
-            })?;
-
         // Acquire concurrency permit
         let _permit = self
             .concurrency_limiter
@@ -1078,11 +1083,10 @@ impl SigninService for ProductionAuthService {
     ) -> Result<Option<AuthTokens>, CoreError> {
         // For now, delegate to basic implementation
         // TODO: Add full production signin with caching, lockout protection, etc.
-        let auth_service = AuthUserService {
-            user_repository: Arc::clone(&self.user_repository),
-            token_manager: Arc::clone(&self.token_manager),
-            pool: None,
-        };
+        let auth_service = AuthUserService::new(
+            Arc::clone(&self.user_repository),
+            Arc::clone(&self.token_manager),
+        );
         auth_service.signin(payload, auth_context).await
     }
 }
@@ -1096,11 +1100,11 @@ impl RefreshTokenService for ProductionAuthService {
     ) -> Result<AuthTokens, CoreError> {
         // For now, delegate to basic implementation
         // TODO: Add full production refresh with rotation, validation, etc.
-        let auth_service = AuthUserService {
-            user_repository: Arc::clone(&self.user_repository),
-            token_manager: Arc::clone(&self.token_manager),
-            pool: None,
-        };
+        let auth_service = AuthUserService::new(
+            Arc::clone(&self.user_repository),
+            Arc::clone(&self.token_manager),
+        )
+        .with_refresh_idle_timeout(self.refresh_idle_timeout);
         auth_service
             .refresh_token(refresh_token, auth_context)
             .await
@@ -1160,11 +1164,6 @@ impl AuthServiceBuilder {
         self
     }
 
-    pub fn with_rate_limit(mut self, per_minute: u32) -> Self {
-        self.config.rate_limit_per_minute = per_minute;
-        self
-    }
-
     pub fn enable_audit_logging(mut self, enabled: bool) -> Self {
         self.config.enable_audit_logging = enabled;
         self
@@ -1198,3 +1197,103 @@ pub async fn create_custom_auth_service(
 ) -> Result<ProductionAuthService, AppError> {
     ProductionAuthService::new(app_state, config).await
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::super::*;
+        use crate::domains::auth::token_repository::CoreRefreshTokenRepositoryAdapter;
+        use crate::domains::user::repository::UserRepositoryImpl;
+        use fechatter_core::{User, UserClaims};
+
+        /// Token manager and auth service over the test state's database
+        fn auth_service(state: &AppState) -> anyhow::Result<(Arc<TokenManager>, AuthUserService)> {
+            let token_manager = Arc::new(TokenManager::from_config(
+                &state.config.auth,
+                Arc::new(CoreRefreshTokenRepositoryAdapter::new(state.pool())),
+            )?);
+            let service = AuthUserService::new(
+                Arc::new(UserRepositoryImpl::new(state.pool())),
+                token_manager.clone(),
+            );
+            Ok((token_manager, service))
+        }
+
+        fn claims(user: &User) -> UserClaims {
+            UserClaims {
+                id: user.id,
+                workspace_id: user.workspace_id,
+                fullname: user.fullname.clone(),
+                email: user.email.clone(),
+                status: user.status.clone(),
+                created_at: user.created_at,
+            }
+        }
+
+        #[tokio::test]
+        async fn logout_others_should_keep_current_session_only() -> anyhow::Result<()> {
+            let (state, users) = crate::setup_test_users!(1).await;
+            let (token_manager, service) = auth_service(&state)?;
+            let claims = claims(&users[0]);
+
+            let earlier = token_manager
+                .internal_generate_auth_tokens(&claims, None, None)
+                .await?;
+            let current = token_manager
+                .internal_generate_auth_tokens(&claims, None, None)
+                .await?;
+
+            // The fixture's own sign-up session is revoked along with `earlier`
+            let revoked = service
+                .logout_others(users[0].id, &current.refresh_token.token)
+                .await?;
+            assert_eq!(revoked, 2);
+
+            assert!(service
+                .refresh_token(&current.refresh_token.token, None)
+                .await
+                .is_ok());
+            assert!(matches!(
+                service
+                    .refresh_token(&earlier.refresh_token.token, None)
+                    .await,
+                Err(CoreError::Unauthorized(_))
+            ));
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn refresh_token_unused_past_idle_window_should_be_rejected() -> anyhow::Result<()> {
+            let (state, users) = crate::setup_test_users!(1).await;
+            let (token_manager, service) = auth_service(&state)?;
+            let service = service.with_refresh_idle_timeout(Some(chrono::Duration::seconds(60)));
+            let claims = claims(&users[0]);
+
+            let idle = token_manager
+                .internal_generate_auth_tokens(&claims, None, None)
+                .await?;
+            sqlx::query(
+                "UPDATE refresh_tokens SET last_used_at = NOW() - INTERVAL '2 minutes' WHERE user_id = $1",
+            )
+            .bind(i64::from(users[0].id))
+            .execute(&*state.pool())
+            .await?;
+            let active = token_manager
+                .internal_generate_auth_tokens(&claims, None, None)
+                .await?;
+
+            // Still within its absolute lifetime, but unused for longer than the idle window
+            assert!(idle.refresh_token.expires_at > chrono::Utc::now());
+            assert!(matches!(
+                service.refresh_token(&idle.refresh_token.token, None).await,
+                Err(CoreError::Unauthorized(_))
+            ));
+            assert!(service
+                .refresh_token(&active.refresh_token.token, None)
+                .await
+                .is_ok());
+            Ok(())
+        }
+    }
+}
@@ -1,6 +1,8 @@
 pub mod service;
+pub mod sidebar;
 
 pub use service::{
     ChatApplicationService, ChatBusinessRules, ChatDetailView, ChatService, ChatServiceTrait,
     CreateChatInput,
 };
+pub use sidebar::{ChatSidebarService, SidebarChat};
@@ -7,9 +7,12 @@
 //! - Event publishing is delegated to event publisher
 //! - Clear layering between upper and lower levels
 
-use crate::domains::chat::chat_domain::ChatDomainService;
+use crate::domains::chat::chat_domain::{ChatConfig, ChatDomainService, ChatDomainServiceImpl};
 use crate::services::application::cache::CacheStrategyService;
-use crate::services::infrastructure::flows::{ChatDomainEvent, DomainEvent};
+use crate::services::infrastructure::event::DynEventPublisher;
+use crate::services::infrastructure::flows::{
+    ChatDomainEvent, DomainEvent, SimplifiedEventPublisher,
+};
 use crate::AppError;
 use crate::AppState;
 use async_trait::async_trait;
@@ -81,6 +84,14 @@ pub trait ChatServiceTrait: Send + Sync {
         new_owner_id: i64,
     ) -> Result<bool, AppError>;
 
+    /// Use case: Promote direct message to group - Lets a 1:1 chat gain members
+    async fn promote_to_group(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        name: Option<String>,
+    ) -> Result<ChatDetailView, AppError>;
+
     /// Use case: Check if user is in chat - Access control
     async fn is_user_in_chat(&self, user_id: i64, chat_id: ChatId) -> Result<bool, AppError>;
 
@@ -222,6 +233,8 @@ pub enum MemberOperation {
 pub struct ChatService {
     pool: Arc<PgPool>,
     cache_strategy: Arc<CacheStrategyService>,
+    config: ChatConfig,
+    event_publisher: Option<Arc<DynEventPublisher>>,
 }
 
 impl ChatService {
@@ -230,6 +243,8 @@ impl ChatService {
         Self {
             pool,
             cache_strategy,
+            config: ChatConfig::default(),
+            event_publisher: None,
         }
     }
 
@@ -239,6 +254,8 @@ impl ChatService {
         Self {
             pool,
             cache_strategy: Arc::new(CacheStrategyService::new_noop()),
+            config: ChatConfig::default(),
+            event_publisher: None,
         }
     }
 
@@ -247,8 +264,45 @@ impl ChatService {
         Self {
             pool,
             cache_strategy: Arc::new(CacheStrategyService::new_noop()),
+            config: ChatConfig::default(),
+            event_publisher: None,
         }
     }
+
+    /// Use the given chat settings (caps on chats per user and members per chat)
+    pub fn with_config(mut self, config: ChatConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Publish chat lifecycle events through the given publisher
+    pub fn with_event_publisher(mut self, event_publisher: Arc<DynEventPublisher>) -> Self {
+        self.event_publisher = Some(event_publisher);
+        self
+    }
+
+    /// Domain service sharing this service's pool, cache strategy, settings and publisher
+    pub(crate) fn domain_service(&self) -> ChatDomainServiceImpl {
+        let mut event_publisher = SimplifiedEventPublisher::new(self.cache_strategy.clone());
+        if let Some(publisher) = &self.event_publisher {
+            event_publisher = event_publisher.with_event_publisher(publisher.clone());
+        }
+        ChatDomainServiceImpl::new(
+            Arc::new(crate::domains::chat::repository::ChatRepository::new(
+                self.pool.clone(),
+            )),
+            Arc::new(
+                crate::domains::chat::chat_member_repository::ChatMemberRepository::new(
+                    self.pool.clone(),
+                ),
+            ),
+            Arc::new(event_publisher),
+            Arc::new(crate::domains::permission::PermissionService::new(
+                self.pool.clone(),
+            )),
+            self.config.clone(),
+        )
+    }
 }
 
 #[async_trait]
@@ -262,32 +316,15 @@ impl ChatServiceTrait for ChatService {
         // 2. 转换为核心层数据结构
         let create_data = self.build_create_chat_data(&input)?;
 
-        // 3. 通过仓库创建聊天 - 使用convenience方法来正确传递参数
-        let repository = crate::domains::chat::repository::ChatRepository::new(self.pool.clone());
-        let chat = repository
+        // 3. 通过领域服务创建聊天（配额检查、缓存失效和事件发布）
+        let chat = self
+            .domain_service()
             .create_chat(create_data, input.created_by, input.workspace_id)
             .await?;
 
         // 4. 构建返回视图
         let detail_view = ChatDetailView::from_chat(chat, input.initial_members.len() as i32 + 1);
 
-        // 5. 发布领域事件（异步缓存失效和通知）
-        let event = DomainEvent::Chat(ChatDomainEvent::ChatCreated {
-            chat_id: detail_view.id,
-            creator_id: input.created_by,
-            chat_name: detail_view.name.clone(),
-            chat_type: format!("{:?}", input.chat_type),
-            workspace_id: input.workspace_id.unwrap_or(0),
-            initial_members: input.initial_members.clone(),
-            created_at: chrono::Utc::now().to_rfc3339(),
-            privacy_settings: serde_json::Value::Null, // TODO: Add privacy settings
-            sig: None,
-        });
-
-        if let Err(e) = self.publish_domain_event(event).await {
-            warn!("Failed to publish chat created event: {}", e);
-        }
-
         info!(
           chat_id = %detail_view.id,
           chat_type = ?input.chat_type,
@@ -349,65 +386,55 @@ impl ChatServiceTrait for ChatService {
         user_id: UserId,
         payload: UpdateChat,
     ) -> Result<ChatDetailView, AppError> {
-        // 1. 权限和业务验证
+        // 1. 业务验证
         if let Some(name) = &payload.name {
             ChatBusinessRules::validate_chat_name(name)?;
         }
 
-        // 2. 通过仓库更新
-        let repository = crate::domains::chat::repository::ChatRepository::new(self.pool.clone());
-        let updated_chat = repository
-            .update(chat_id, &payload)
-            .await
-            .map_err(AppError::from)?;
+        // 2. 通过领域服务更新（权限检查、缓存失效和事件发布）
+        let domain_service = self.domain_service();
+        let mut updated_chat = None;
+        if let Some(name) = payload.name {
+            updated_chat = Some(
+                domain_service
+                    .update_chat_name(chat_id.0, user_id.0, name)
+                    .await?,
+            );
+        }
+        if let Some(description) = payload.description {
+            updated_chat = Some(
+                domain_service
+                    .update_chat_description(chat_id.0, user_id.0, description)
+                    .await?,
+            );
+        }
+        let updated_chat = match updated_chat {
+            Some(chat) => chat,
+            None => domain_service
+                .get_chat(chat_id.0)
+                .await?
+                .ok_or_else(|| AppError::NotFound(vec![format!("chat {}", chat_id.0)]))?,
+        };
 
         // 3. 构建视图和缓存
-        let detail_view = ChatDetailView::from_chat(updated_chat, {
-            // count members for view
-            let repo = crate::domains::chat::chat_member_repository::ChatMemberRepository::new(
-                self.pool.clone(),
-            );
-            repo.get_member_count(chat_id.0)
-                .await
-                .map_err(AppError::from)? as i32
-        });
+        let member_count = self.get_member_count(chat_id.0).await?;
+        let detail_view = ChatDetailView::from_chat(updated_chat, member_count as i32);
         let key = CacheStrategyService::chat_detail_key(chat_id.0);
         let _ = self
             .cache_strategy
             .set(&key, &detail_view, CacheStrategyService::CHAT_DETAIL_TTL)
             .await;
 
-        // 4. 发布事件
-        let event = DomainEvent::Chat(ChatDomainEvent::ChatUpdated {
-            chat_id: chat_id.0,
-            updated_by: user_id.0,
-            chat_name: detail_view.name.clone(),
-            updated_at: chrono::Utc::now().to_rfc3339(),
-            sig: None,
-        });
-        let _ = self.publish_domain_event(event).await;
-
         Ok(detail_view)
     }
 
     /// Use case: Delete chat - Chat removal
     async fn delete_chat(&self, chat_id: ChatId, user_id: UserId) -> Result<bool, AppError> {
-        // 1. 通过仓库删除
-        let repository = crate::domains::chat::repository::ChatRepository::new(self.pool.clone());
-        let deleted = repository.delete(chat_id).await.map_err(AppError::from)?;
-        // 2. 使缓存失效
-        let key = CacheStrategyService::chat_detail_key(chat_id.0);
-        let _ = self.cache_strategy.del(&key).await;
-        // 3. 发布事件
-        if deleted {
-            let event = DomainEvent::Chat(ChatDomainEvent::ChatDeleted {
-                chat_id: chat_id.0,
-                deleted_by: user_id.0,
-                deleted_at: chrono::Utc::now().to_rfc3339(),
-                sig: None,
-            });
-            let _ = self.publish_domain_event(event).await;
-        }
+        // 通过领域服务删除（权限检查、缓存失效和事件发布）
+        let deleted = self
+            .domain_service()
+            .delete_chat(chat_id.0, user_id.0)
+            .await?;
         Ok(deleted)
     }
 
@@ -445,14 +472,35 @@ impl ChatServiceTrait for ChatService {
             );
         }
 
-        // 2. 执行添加操作 (TODO: 实现实际的添加逻辑)
+        // 业务规则：私聊只有两名成员，需先升级为群聊
+        let chat = self
+            .domain_service()
+            .get_chat(chat_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(vec![format!("chat {}", chat_id)]))?;
+        if chat.chat_type == ChatType::Single {
+            return Err(CoreError::Validation(
+                "Direct messages cannot gain members; promote the chat to a group first"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        // 2. 执行添加操作：成员上限在数据库事务内（锁定 chats 行）检查，随后调整成员计数
         let member_repo = crate::domains::chat::chat_member_repository::ChatMemberRepository::new(
             self.pool.clone(),
         );
-        member_repo
-            .add_members(chat_id, &member_ids)
-            .await
-            .map_err(AppError::from)?;
+        let (repo, ids, config) = (&member_repo, &member_ids, &self.config);
+        self.cache_strategy
+            .member_counts()
+            .apply_membership_change(chat_id, move || async move {
+                let added = repo
+                    .add_members_counted(chat_id, user_id, ids, config)
+                    .await
+                    .map_err(AppError::from)?;
+                Ok(added as i64)
+            })
+            .await?;
 
         // 3. 为每个成员发布领域事件
         for member_id in &member_ids {
@@ -496,14 +544,21 @@ impl ChatServiceTrait for ChatService {
             );
         }
 
-        // 2. 执行移除操作
+        // 2. 执行移除操作，并在同一把锁内调整成员计数
         let member_repo = crate::domains::chat::chat_member_repository::ChatMemberRepository::new(
             self.pool.clone(),
         );
-        member_repo
-            .remove_members(chat_id, &member_ids)
-            .await
-            .map_err(AppError::from)?;
+        let (repo, ids) = (&member_repo, &member_ids);
+        self.cache_strategy
+            .member_counts()
+            .apply_membership_change(chat_id, move || async move {
+                let removed = repo
+                    .remove_members_counted(chat_id, user_id, ids)
+                    .await
+                    .map_err(AppError::from)?;
+                Ok(-(removed as i64))
+            })
+            .await?;
 
         // 3. 为每个被移除的成员发布领域事件
         for member_id in &member_ids {
@@ -536,29 +591,8 @@ impl ChatServiceTrait for ChatService {
         current_owner_id: i64,
         new_owner_id: i64,
     ) -> Result<bool, AppError> {
-        // Create domain service with necessary dependencies
-        let chat_repo = Arc::new(crate::domains::chat::repository::ChatRepository::new(
-            self.pool.clone(),
-        ));
-        let member_repo = Arc::new(
-            crate::domains::chat::chat_member_repository::ChatMemberRepository::new(
-                self.pool.clone(),
-            ),
-        );
-        let event_publisher = Arc::new(
-            crate::services::infrastructure::flows::SimplifiedEventPublisher::new(
-                self.cache_strategy.clone(),
-            ),
-        );
-        let domain_service = crate::domains::chat::chat_domain::ChatDomainServiceImpl::new(
-            chat_repo,
-            member_repo,
-            event_publisher,
-            crate::domains::chat::chat_domain::ChatConfig::default(),
-        );
-
-        // Use domain service for business logic and validation
-        domain_service
+        // Use domain service for business logic, validation and events
+        self.domain_service()
             .transfer_ownership(chat_id, current_owner_id, new_owner_id)
             .await
             .map_err(AppError::from)?;
@@ -573,6 +607,33 @@ impl ChatServiceTrait for ChatService {
         Ok(true)
     }
 
+    /// Use case: Promote direct message to group - Lets a 1:1 chat gain members
+    #[instrument(skip(self))]
+    async fn promote_to_group(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        name: Option<String>,
+    ) -> Result<ChatDetailView, AppError> {
+        if let Some(name) = &name {
+            ChatBusinessRules::validate_chat_name(name)?;
+        }
+
+        // 通过领域服务升级（权限检查和事件发布）
+        let chat = self
+            .domain_service()
+            .promote_to_group(chat_id, user_id, name)
+            .await?;
+
+        // 缓存中的详情和侧边栏仍是私聊
+        let members: Vec<i64> = chat.chat_members.iter().map(|&id| i64::from(id)).collect();
+        self.cache_strategy
+            .invalidate_on_chat_updated(chat_id, &members)
+            .await;
+
+        Ok(ChatDetailView::from_chat(chat, members.len() as i32))
+    }
+
     /// Use case: Check if user is in chat - Access control
     async fn is_user_in_chat(&self, user_id: i64, chat_id: ChatId) -> Result<bool, AppError> {
         self.is_user_chat_member(user_id, chat_id.0).await
@@ -661,6 +722,23 @@ impl ChatServiceTrait for ChatService {
 //==============================================================================
 
 impl ChatService {
+    /// Get member count - Cached counter, reseeded from the database when missing
+    pub async fn get_member_count(&self, chat_id: i64) -> Result<i64, AppError> {
+        let member_repo = crate::domains::chat::chat_member_repository::ChatMemberRepository::new(
+            self.pool.clone(),
+        );
+        let member_repo = &member_repo;
+        self.cache_strategy
+            .member_counts()
+            .get_member_count(chat_id, move || async move {
+                member_repo
+                    .get_member_count(chat_id)
+                    .await
+                    .map_err(AppError::from)
+            })
+            .await
+    }
+
     /// Build create chat data - Single responsibility: Data conversion
     fn build_create_chat_data(&self, input: &CreateChatInput) -> Result<CreateChat, CoreError> {
         Ok(CreateChat {
@@ -858,6 +936,300 @@ mod tests {
         };
         assert!(ChatBusinessRules::validate_chat_config(&valid_group).is_ok());
     }
+
+    fn capped_config(max_chats_per_user: usize, max_members_per_chat: usize) -> ChatConfig {
+        ChatConfig {
+            max_chats_per_user,
+            max_members_per_chat,
+            ..ChatConfig::default()
+        }
+    }
+
+    #[test]
+    fn chat_quota_should_reject_creation_at_cap() {
+        let config = capped_config(2, 10);
+        assert!(config.check_chat_quota(1).is_ok());
+        assert!(matches!(
+            config.check_chat_quota(2),
+            Err(CoreError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn member_capacity_should_reject_additions_beyond_cap() {
+        let config = capped_config(10, 3);
+        assert!(config.check_member_capacity(1, 2).is_ok());
+        assert!(matches!(
+            config.check_member_capacity(3, 1),
+            Err(CoreError::Validation(_))
+        ));
+        assert!(matches!(
+            config.check_member_capacity(1, 3),
+            Err(CoreError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn chat_operations_should_each_publish_one_chat_event() -> anyhow::Result<()> {
+        use fechatter_core::contracts::events::ChatLifecycle;
+
+        let (state, users, events) = crate::setup_test_users_with_events!(3).await;
+        let (owner, heir) = (i64::from(users[0].id), i64::from(users[1].id));
+        let service =
+            ChatService::new_with_pool(state.pool()).with_event_publisher(events.publisher());
+
+        let chat = service
+            .create_chat(CreateChatInput {
+                name: format!("Events {}", uuid::Uuid::new_v4()),
+                chat_type: ChatType::Group,
+                description: None,
+                created_by: owner,
+                workspace_id: Some(i64::from(users[0].workspace_id)),
+                initial_members: vec![heir, i64::from(users[2].id)],
+                members: None,
+            })
+            .await?;
+        let created = events.expect_chat_event(ChatLifecycle::Created).await;
+        assert_eq!(i64::from(created.chat.id), chat.id);
+        assert_eq!(created.actor_id, users[0].id);
+        assert_eq!(created.members.len(), 3);
+
+        let renamed = format!("Renamed {}", uuid::Uuid::new_v4());
+        service
+            .update_chat(
+                ChatId::new(chat.id),
+                users[0].id,
+                UpdateChat {
+                    name: Some(renamed.clone()),
+                    description: None,
+                },
+            )
+            .await?;
+        let updated = events.expect_chat_event(ChatLifecycle::Updated).await;
+        assert_eq!(updated.chat.name, renamed);
+
+        service
+            .transfer_chat_ownership(chat.id, owner, heir)
+            .await?;
+        let transferred = events
+            .expect_chat_event(ChatLifecycle::OwnershipTransferred)
+            .await;
+        assert_eq!(transferred.chat.created_by, users[1].id);
+
+        assert!(
+            service
+                .delete_chat(ChatId::new(chat.id), users[1].id)
+                .await?
+        );
+        let deleted = events.expect_chat_event(ChatLifecycle::Deleted).await;
+        assert_eq!(i64::from(deleted.chat.id), chat.id);
+        assert_eq!(deleted.members.len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn concurrent_direct_chat_requests_should_share_one_chat() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(2).await;
+        let (alice, bob) = (i64::from(users[0].id), i64::from(users[1].id));
+        let workspace_id = i64::from(users[0].workspace_id);
+        let service = state.application_services().chat_application_service();
+
+        let (from_alice, from_bob) = tokio::join!(
+            service.get_or_create_direct_chat(alice, bob, workspace_id),
+            service.get_or_create_direct_chat(bob, alice, workspace_id),
+        );
+        let (from_alice, from_bob) = (from_alice?, from_bob?);
+        assert_eq!(from_alice.id, from_bob.id);
+        assert_eq!(from_alice.chat_type, ChatType::Single);
+
+        let again = service
+            .get_or_create_direct_chat(alice, bob, workspace_id)
+            .await?;
+        assert_eq!(again.id, from_alice.id);
+
+        let to_self = service
+            .get_or_create_direct_chat(alice, alice, workspace_id)
+            .await;
+        assert!(matches!(to_self, Err(AppError::InvalidInput(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn promoted_direct_chat_should_accept_new_members() -> anyhow::Result<()> {
+        let (state, users) = crate::setup_test_users!(3).await;
+        let (alice, bob, carol) = (
+            i64::from(users[0].id),
+            i64::from(users[1].id),
+            i64::from(users[2].id),
+        );
+        let workspace_id = i64::from(users[0].workspace_id);
+        let service = state.application_services().chat_application_service();
+
+        let direct = service
+            .get_or_create_direct_chat(alice, bob, workspace_id)
+            .await?;
+        let added = service.add_members(direct.id, alice, vec![carol]).await;
+        assert!(matches!(added, Err(AppError::InvalidInput(_))));
+        let outsider = service.promote_to_group(direct.id, carol, None).await;
+        assert!(outsider.is_err());
+
+        // Either participant may promote, becoming the owner
+        let group = service.promote_to_group(direct.id, bob, None).await?;
+        assert_eq!(group.id, direct.id);
+        assert_eq!(group.chat_type, ChatType::Group);
+        assert_eq!(group.created_by, bob);
+        assert_eq!(group.member_count, 2);
+
+        service.add_members(direct.id, bob, vec![carol]).await?;
+        assert!(service.is_user_chat_member(carol, direct.id).await?);
+        assert!(service.is_user_chat_member(alice, direct.id).await?);
+
+        // The group stays a group, and the pair's next direct message is a new chat
+        let again = service.promote_to_group(direct.id, bob, None).await;
+        assert!(matches!(again, Err(AppError::InvalidInput(_))));
+        let next = service
+            .get_or_create_direct_chat(alice, bob, workspace_id)
+            .await?;
+        assert_ne!(next.id, direct.id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unique_chat_names_should_conflict_once_required() -> anyhow::Result<()> {
+        use crate::domains::workspace::repository::WorkspaceRepositoryImpl;
+
+        let (state, users) = crate::setup_test_users!(3).await;
+        let owner = i64::from(users[0].id);
+        let service = ChatService::new_with_pool(state.pool());
+        let group = |name: &str| CreateChatInput {
+            name: name.to_string(),
+            chat_type: ChatType::Group,
+            description: None,
+            created_by: owner,
+            workspace_id: Some(i64::from(users[0].workspace_id)),
+            initial_members: vec![i64::from(users[1].id), i64::from(users[2].id)],
+            members: None,
+        };
+        let name = format!("Planning {}", uuid::Uuid::new_v4());
+
+        // Duplicates are allowed until the workspace requires unique names, which cannot be
+        // turned on while they remain
+        service.create_chat(group(&name)).await?;
+        let duplicate = service.create_chat(group(&name)).await?;
+        let workspaces = WorkspaceRepositoryImpl::new(state.pool());
+        assert!(matches!(
+            workspaces
+                .set_unique_chat_names(users[0].workspace_id, true)
+                .await,
+            Err(CoreError::Conflict(_))
+        ));
+        assert!(
+            !workspaces
+                .get_unique_chat_names(users[0].workspace_id)
+                .await?
+        );
+
+        let taken = format!("Roadmap {}", uuid::Uuid::new_v4());
+        service
+            .update_chat(
+                ChatId::new(duplicate.id),
+                users[0].id,
+                UpdateChat {
+                    name: Some(taken.clone()),
+                    description: None,
+                },
+            )
+            .await?;
+        workspaces
+            .set_unique_chat_names(users[0].workspace_id, true)
+            .await?;
+
+        // Names compare case-insensitively
+        let created = service.create_chat(group(&taken.to_uppercase())).await;
+        assert!(matches!(created, Err(AppError::Conflict(_))));
+        let updated = service
+            .update_chat(
+                ChatId::new(duplicate.id),
+                users[0].id,
+                UpdateChat {
+                    name: Some(name.to_lowercase()),
+                    description: None,
+                },
+            )
+            .await;
+        assert!(matches!(updated, Err(AppError::Conflict(_))));
+        Ok(())
+    }
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+
+        fn group_input(
+            name: &str,
+            workspace_id: i64,
+            created_by: i64,
+            initial_members: Vec<i64>,
+        ) -> CreateChatInput {
+            CreateChatInput {
+                name: name.to_string(),
+                chat_type: ChatType::Group,
+                description: None,
+                created_by,
+                workspace_id: Some(workspace_id),
+                initial_members,
+                members: None,
+            }
+        }
+
+        #[tokio::test]
+        async fn chat_and_member_caps_should_reject_excess() -> anyhow::Result<()> {
+            let (state, users) = crate::setup_test_users!(4).await;
+            let workspace_id = i64::from(users[0].workspace_id);
+            let users: Vec<i64> = users.iter().map(|user| i64::from(user.id)).collect();
+            let service = ChatApplicationService::new_with_pool(state.pool())
+                .with_config(capped_config(2, 3));
+            let group =
+                |name: &str| group_input(name, workspace_id, users[0], vec![users[1], users[2]]);
+
+            let first = service.create_chat(group("cap-1")).await?;
+            service.create_chat(group("cap-2")).await?;
+            let over_chat_cap = service.create_chat(group("cap-3")).await;
+            assert!(matches!(over_chat_cap, Err(AppError::InvalidInput(_))));
+
+            let over_member_cap = service
+                .add_members(first.id, users[0], vec![users[3]])
+                .await;
+            assert!(matches!(over_member_cap, Err(AppError::InvalidInput(_))));
+            assert_eq!(service.get_member_count(first.id).await?, 3);
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn concurrent_additions_should_not_overshoot_member_cap() -> anyhow::Result<()> {
+            let (state, users) = crate::setup_test_users!(5).await;
+            let workspace_id = i64::from(users[0].workspace_id);
+            let users: Vec<i64> = users.iter().map(|user| i64::from(user.id)).collect();
+            let service = ChatApplicationService::new_with_pool(state.pool())
+                .with_config(capped_config(10, 4));
+            let chat = service
+                .create_chat(group_input(
+                    "cap-race",
+                    workspace_id,
+                    users[0],
+                    vec![users[1], users[2]],
+                ))
+                .await?;
+
+            let (left, right) = tokio::join!(
+                service.add_members(chat.id, users[0], vec![users[3]]),
+                service.add_members(chat.id, users[0], vec![users[4]]),
+            );
+            assert!(left.is_ok() != right.is_ok(), "exactly one addition fits");
+            assert_eq!(service.get_member_count(chat.id).await?, 4);
+            Ok(())
+        }
+    }
 }
 
 //==============================================================================
@@ -868,6 +1240,8 @@ mod tests {
 pub struct ChatApplicationService {
     pool: Arc<PgPool>,
     cache_strategy: Arc<CacheStrategyService>,
+    config: ChatConfig,
+    event_publisher: Option<Arc<DynEventPublisher>>,
 }
 
 impl ChatApplicationService {
@@ -878,6 +1252,8 @@ impl ChatApplicationService {
             cache_strategy: Arc::new(CacheStrategyService::new_optional(
                 app_state.cache_service().map(|c| c.clone()),
             )),
+            config: ChatConfig::default(),
+            event_publisher: None,
         }
     }
 
@@ -886,13 +1262,36 @@ impl ChatApplicationService {
         Self {
             pool,
             cache_strategy: Arc::new(CacheStrategyService::new_noop()),
+            config: ChatConfig::default(),
+            event_publisher: None,
+        }
+    }
+
+    /// Use the given chat settings (caps on chats per user and members per chat)
+    pub fn with_config(mut self, config: ChatConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Publish chat lifecycle events through the given publisher
+    pub fn with_event_publisher(mut self, event_publisher: Arc<DynEventPublisher>) -> Self {
+        self.event_publisher = Some(event_publisher);
+        self
+    }
+
+    /// ChatService sharing this service's pool, cache strategy, settings and publisher
+    fn chat_service(&self) -> ChatService {
+        let service = ChatService::new(self.pool.clone(), self.cache_strategy.clone())
+            .with_config(self.config.clone());
+        match &self.event_publisher {
+            Some(publisher) => service.with_event_publisher(publisher.clone()),
+            None => service,
         }
     }
 
     /// Create chat - Delegate to ChatService  
     pub async fn create_chat(&self, input: CreateChatInput) -> Result<ChatDetailView, AppError> {
-        let chat_service = ChatService::new(self.pool.clone(), self.cache_strategy.clone());
-        chat_service.create_chat(input).await
+        self.chat_service().create_chat(input).await
     }
 
     /// Chat membership validation through repository layer
@@ -976,29 +1375,16 @@ impl ChatApplicationService {
         }
     }
 
-    /// Add members to chat - For handlers
+    /// Add members to chat - Delegate to ChatService (admin only, capped by `max_members_per_chat`)
     pub async fn add_members(
         &self,
         chat_id: i64,
         user_id: i64,
         member_ids: Vec<i64>,
     ) -> Result<(), AppError> {
-        // TODO: Implement proper add members logic
-        // For now, just validate that the user has permission and return success
-        if !self.is_chat_admin(user_id, chat_id).await? {
-            return Err(
-                CoreError::Unauthorized("Only chat admin can add members".to_string()).into(),
-            );
-        }
-
-        // Log the operation for now
-        tracing::info!(
-            "User {} adding members {:?} to chat {}",
-            user_id,
-            member_ids,
-            chat_id
-        );
-        Ok(())
+        self.chat_service()
+            .add_members(chat_id, user_id, member_ids)
+            .await
     }
 
     /// Remove members from chat - For handlers
@@ -1049,29 +1435,9 @@ impl ChatApplicationService {
         current_owner_id: i64,
         new_owner_id: i64,
     ) -> Result<bool, AppError> {
-        // Create domain service with necessary dependencies
-        let chat_repo = std::sync::Arc::new(crate::domains::chat::repository::ChatRepository::new(
-            self.pool.clone(),
-        ));
-        let member_repo = std::sync::Arc::new(
-            crate::domains::chat::chat_member_repository::ChatMemberRepository::new(
-                self.pool.clone(),
-            ),
-        );
-        let event_publisher = std::sync::Arc::new(
-            crate::services::infrastructure::flows::SimplifiedEventPublisher::new(
-                self.cache_strategy.clone(),
-            ),
-        );
-        let domain_service = crate::domains::chat::chat_domain::ChatDomainServiceImpl::new(
-            chat_repo,
-            member_repo,
-            event_publisher,
-            crate::domains::chat::chat_domain::ChatConfig::default(),
-        );
-
-        // Use domain service for business logic and validation
-        domain_service
+        // Use domain service for business logic, validation and events
+        self.chat_service()
+            .domain_service()
             .transfer_ownership(chat_id, current_owner_id, new_owner_id)
             .await
             .map_err(AppError::from)?;
@@ -1095,34 +1461,15 @@ impl ChatApplicationService {
             );
         }
 
-        // 2. 使用Domain Service进行业务逻辑处理
-        let chat_repo = std::sync::Arc::new(crate::domains::chat::repository::ChatRepository::new(
-            self.pool.clone(),
-        ));
-        let member_repo = std::sync::Arc::new(
-            crate::domains::chat::chat_member_repository::ChatMemberRepository::new(
-                self.pool.clone(),
-            ),
-        );
-        let event_publisher = std::sync::Arc::new(
-            crate::services::infrastructure::flows::SimplifiedEventPublisher::new(
-                self.cache_strategy.clone(),
-            ),
-        );
-        let domain_service = crate::domains::chat::chat_domain::ChatDomainServiceImpl::new(
-            chat_repo,
-            member_repo,
-            event_publisher,
-            crate::domains::chat::chat_domain::ChatConfig::default(),
-        );
-
-        // 3. 执行删除操作
-        let deleted = domain_service
+        // 2. 通过Domain Service执行删除操作
+        let deleted = self
+            .chat_service()
+            .domain_service()
             .delete_chat(chat_id, user_id)
             .await
             .map_err(AppError::from)?;
 
-        // 4. 记录操作日志
+        // 3. 记录操作日志
         if deleted {
             tracing::info!("Chat {} deleted by user {}", chat_id, user_id);
         }
@@ -1130,6 +1477,39 @@ impl ChatApplicationService {
         Ok(deleted)
     }
 
+    /// Get or create the direct message chat with another workspace user - For handlers
+    pub async fn get_or_create_direct_chat(
+        &self,
+        user_id: i64,
+        other_user_id: i64,
+        workspace_id: i64,
+    ) -> Result<ChatDetailView, AppError> {
+        let chat = self
+            .chat_service()
+            .domain_service()
+            .get_or_create_direct_chat(user_id, other_user_id, workspace_id)
+            .await?;
+
+        Ok(ChatDetailView::from_chat(chat, 2))
+    }
+
+    /// Promote a direct message to a group chat owned by the caller - For handlers
+    pub async fn promote_to_group(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        name: Option<String>,
+    ) -> Result<ChatDetailView, AppError> {
+        self.chat_service()
+            .promote_to_group(chat_id, user_id, name)
+            .await
+    }
+
+    /// Get member count - For handlers
+    pub async fn get_member_count(&self, chat_id: i64) -> Result<i64, AppError> {
+        self.chat_service().get_member_count(chat_id).await
+    }
+
     /// Get chat details - For handlers
     pub async fn get_chat(
         &self,
@@ -0,0 +1,74 @@
+//! # Chat Sidebar
+//!
+//! **Responsibility**: Every chat of a user with what the sidebar shows for it, in one pass
+//! **Queries**: One query for the chats with their last message, member count and mute state,
+//! then unread counts from the cache, with a single batched count for chats not cached
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+use crate::domains::chat::repository::ChatRepository;
+use crate::domains::messaging::repository::MessageRepository;
+use crate::services::application::stores::UnreadCountStore;
+use crate::{AppError, AppState};
+use fechatter_core::chat::ChatSidebar;
+
+/// A chat as listed in the sidebar
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SidebarChat {
+    #[serde(flatten)]
+    pub chat: ChatSidebar,
+    /// Whether the user muted the chat; muted chats stay listed
+    pub is_muted: bool,
+}
+
+pub struct ChatSidebarService {
+    chats: ChatRepository,
+    messages: MessageRepository,
+    unread_counts: UnreadCountStore,
+}
+
+impl ChatSidebarService {
+    pub fn from_state(state: &AppState) -> Self {
+        Self {
+            chats: ChatRepository::new(state.pool()),
+            messages: MessageRepository::new(state.pool()),
+            unread_counts: UnreadCountStore::new_optional(state.cache_service().cloned()),
+        }
+    }
+
+    /// The user's chats, most recently active first
+    pub async fn list(&self, user_id: i64) -> Result<Vec<SidebarChat>, AppError> {
+        let summaries = self
+            .chats
+            .list_sidebar_summaries(user_id)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        let chat_ids: Vec<i64> = summaries
+            .iter()
+            .map(|(chat, _)| i64::from(chat.id))
+            .collect();
+
+        let unread_counts = self
+            .unread_counts
+            .get_unread_counts(user_id, &chat_ids, |missing| async move {
+                let counts = self
+                    .messages
+                    .count_unread_by_chat(user_id, &missing)
+                    .await
+                    .map_err(|e| AppError::Internal(e.to_string()))?;
+                Ok(counts.into_iter().collect::<HashMap<_, _>>())
+            })
+            .await?;
+
+        Ok(summaries
+            .into_iter()
+            .zip(unread_counts)
+            .map(|((mut chat, is_muted), unread_count)| {
+                chat.unread_count = i32::try_from(unread_count).unwrap_or(i32::MAX);
+                SidebarChat { chat, is_muted }
+            })
+            .collect())
+    }
+}
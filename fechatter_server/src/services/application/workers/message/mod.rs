@@ -4,6 +4,7 @@
 //! **Principle**: Use unified models from fechatter_core
 
 pub mod consistency_monitor;
+pub mod retention;
 mod service;
 
 // Re-export service components
@@ -15,6 +16,8 @@ pub use service::{
 // Re-export models from fechatter_core for backward compatibility
 pub use fechatter_core::models::message::MessageView;
 
+pub use retention::MessageRetentionService;
+
 pub use consistency_monitor::{
     create_consistency_monitor, ConsistencyCheckResult, MessageConsistencyMonitor,
     MessageConsistencyMonitorImpl,
@@ -0,0 +1,263 @@
+//! # Message Retention
+//!
+//! **Responsibility**: Tombstone messages older than their workspace's retention window
+//! **Scope**: Background sweep over workspaces with `message_retention_days` set; each batch
+//! clears content in the database, then drops the messages from caches and the search index
+
+use chrono::{DateTime, Duration, Utc};
+use fechatter_core::MessageId;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::RetentionConfig;
+use crate::domains::messaging::repository::MessageRepository;
+use crate::domains::workspace::repository::WorkspaceRepositoryImpl;
+use crate::services::application::workers::search::SearchApplicationServiceTrait;
+use crate::services::infrastructure::cache::RedisCacheService;
+use crate::{AppError, AppState};
+
+/// Oldest `created_at` kept by a retention window ending at `now`
+pub fn retention_cutoff(now: DateTime<Utc>, window: Duration) -> DateTime<Utc> {
+    now - window
+}
+
+/// Drop tombstoned `(message_id, chat_id)`s from caches and the search index (best effort)
+pub async fn invalidate_tombstoned(
+    cache: Option<&Arc<RedisCacheService>>,
+    search: Option<&Arc<dyn SearchApplicationServiceTrait>>,
+    tombstoned: &[(i64, i64)],
+) {
+    if let Some(cache) = cache {
+        let chat_ids: BTreeSet<i64> = tombstoned.iter().map(|(_, chat_id)| *chat_id).collect();
+        let mut batch = cache.batch();
+        for (message_id, _) in tombstoned {
+            batch = batch.del(&format!("message:{}", message_id));
+        }
+        for chat_id in chat_ids {
+            batch = batch
+                .del(&format!("chat:{}:messages", chat_id))
+                .del(&format!("recent_messages:{}", chat_id));
+        }
+        if let Err(e) = batch.run().await {
+            warn!("Failed to invalidate caches for tombstoned messages: {}", e);
+        }
+    }
+
+    if let Some(search) = search {
+        let message_ids: Vec<MessageId> = tombstoned
+            .iter()
+            .map(|(message_id, _)| MessageId(*message_id))
+            .collect();
+        if let Err(e) = search.remove_messages_from_index_batch(&message_ids).await {
+            warn!(
+                "Failed to remove {} tombstoned messages from search index: {}",
+                message_ids.len(),
+                e
+            );
+        }
+    }
+}
+
+/// Sweeps expired messages into tombstones
+pub struct MessageRetentionService {
+    messages: Arc<MessageRepository>,
+    workspaces: Arc<WorkspaceRepositoryImpl>,
+    cache: Option<Arc<RedisCacheService>>,
+    search: Option<Arc<dyn SearchApplicationServiceTrait>>,
+    batch_size: i64,
+}
+
+impl MessageRetentionService {
+    pub fn new(
+        messages: Arc<MessageRepository>,
+        workspaces: Arc<WorkspaceRepositoryImpl>,
+        cache: Option<Arc<RedisCacheService>>,
+        search: Option<Arc<dyn SearchApplicationServiceTrait>>,
+        batch_size: i64,
+    ) -> Self {
+        Self {
+            messages,
+            workspaces,
+            cache,
+            search,
+            batch_size: batch_size.max(1),
+        }
+    }
+
+    pub fn from_state(state: &AppState, config: &RetentionConfig) -> Self {
+        let pool = state.pool();
+        Self::new(
+            Arc::new(MessageRepository::new(pool.clone())),
+            Arc::new(WorkspaceRepositoryImpl::new(pool)),
+            state.cache_service().cloned(),
+            state.search_application_service(),
+            config.batch_size,
+        )
+    }
+
+    /// One sweep over every workspace with a retention window; returns messages tombstoned
+    pub async fn run_once(&self) -> Result<u64, AppError> {
+        let policies = self.workspaces.list_message_retention_policies().await?;
+        let mut total = 0;
+
+        for (workspace_id, days) in policies {
+            match self
+                .purge_workspace(workspace_id, Duration::days(i64::from(days)))
+                .await
+            {
+                Ok(0) => {}
+                Ok(count) => {
+                    info!(
+                        "Retention tombstoned {} messages older than {} days in workspace {}",
+                        count, days, workspace_id
+                    );
+                    total += count;
+                }
+                Err(e) => error!(
+                    "Retention sweep failed for workspace {}: {}",
+                    workspace_id, e
+                ),
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// Tombstone all messages of a workspace older than `window`
+    pub async fn purge_workspace(
+        &self,
+        workspace_id: i64,
+        window: Duration,
+    ) -> Result<u64, AppError> {
+        let cutoff = retention_cutoff(Utc::now(), window);
+        let mut total = 0;
+
+        loop {
+            let tombstoned = self
+                .messages
+                .tombstone_messages_before(workspace_id, cutoff, self.batch_size)
+                .await?;
+            if tombstoned.is_empty() {
+                break;
+            }
+
+            total += tombstoned.len() as u64;
+            invalidate_tombstoned(self.cache.as_ref(), self.search.as_ref(), &tombstoned).await;
+
+            if (tombstoned.len() as i64) < self.batch_size {
+                break;
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// Sweep every `interval` for the life of the process
+    pub fn spawn(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once().await {
+                    error!("Retention sweep failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cutoff_should_trail_now_by_the_window() {
+        let now = Utc::now();
+        assert_eq!(
+            retention_cutoff(now, Duration::days(30)),
+            now - Duration::days(30)
+        );
+    }
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+
+        #[tokio::test]
+        async fn short_window_should_tombstone_old_messages_and_keep_recent_ones(
+        ) -> anyhow::Result<()> {
+            let (state, users) = crate::setup_test_users!(3).await;
+            let pool = state.pool();
+            let workspace_id = i64::from(users[0].workspace_id);
+            let chat = state
+                .create_new_chat(
+                    fechatter_core::ChatType::Group,
+                    Some(format!("Retention {}", uuid::Uuid::new_v4())),
+                    None,
+                    users[0].id,
+                    vec![users[1].id, users[2].id],
+                )
+                .await?;
+            let chat_id = i64::from(chat.id);
+            let sender_id = i64::from(users[0].id);
+            let insert_message = |age_minutes: i32, content: &'static str| {
+                let pool = pool.clone();
+                async move {
+                    sqlx::query_scalar::<_, i64>(
+                        r#"INSERT INTO messages (chat_id, sender_id, content, created_at, updated_at)
+                           VALUES ($1, $2, $3, NOW() - make_interval(mins => $4), NOW())
+                           RETURNING id"#,
+                    )
+                    .bind(chat_id)
+                    .bind(sender_id)
+                    .bind(content)
+                    .bind(age_minutes)
+                    .fetch_one(&*pool)
+                    .await
+                    .unwrap()
+                }
+            };
+            let old_id = insert_message(30, "old").await;
+            let recent_id = insert_message(1, "recent").await;
+
+            let service = MessageRetentionService::new(
+                Arc::new(MessageRepository::new(pool.clone())),
+                Arc::new(WorkspaceRepositoryImpl::new(pool.clone())),
+                None,
+                None,
+                1,
+            );
+            let tombstoned = service
+                .purge_workspace(workspace_id, Duration::minutes(10))
+                .await?;
+            assert_eq!(tombstoned, 1);
+
+            let stored = |id: i64| {
+                let pool = pool.clone();
+                async move {
+                    sqlx::query_as::<_, (Option<String>, bool)>(
+                        "SELECT content, deleted_at IS NOT NULL FROM messages WHERE id = $1",
+                    )
+                    .bind(id)
+                    .fetch_one(&*pool)
+                    .await
+                    .unwrap()
+                }
+            };
+            assert_eq!(stored(old_id).await, (Some(String::new()), true));
+            assert_eq!(stored(recent_id).await, (Some("recent".to_string()), false));
+
+            // A second sweep finds nothing new
+            assert_eq!(
+                service
+                    .purge_workspace(workspace_id, Duration::minutes(10))
+                    .await?,
+                0
+            );
+
+            Ok(())
+        }
+    }
+}
@@ -5,9 +5,11 @@
 //! **Principle**: Stream Separation - Search indexing async, realtime notifications low-latency
 
 use std::sync::Arc;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
-use crate::domains::messaging::messaging_domain::MessageDomainService;
+use crate::domains::messaging::messaging_domain::{
+    FullyReadMessage, MessageContext, MessageDomainService, MessagePreview,
+};
 use crate::services::application::tools::indexer::ChatInfo;
 use crate::services::infrastructure::flows::notifications::{
     create_notification_flow_service_with_nats, create_notification_service,
@@ -284,6 +286,21 @@ pub trait EventPublisherTrait: Send + Sync {
         message: &fechatter_core::Message,
         chat_members: Vec<i64>,
     ) -> Result<(), AppError>;
+
+    async fn publish_message_updated(
+        &self,
+        message: &fechatter_core::Message,
+        chat_members: Vec<i64>,
+    ) -> Result<(), AppError>;
+
+    /// `message` is the message as it was before deletion
+    async fn publish_message_deleted(
+        &self,
+        message: &fechatter_core::Message,
+        chat_members: Vec<i64>,
+    ) -> Result<(), AppError>;
+
+    async fn publish_message_fully_read(&self, message: &FullyReadMessage) -> Result<(), AppError>;
 }
 
 /// Adapter for AppState EventPublisher
@@ -297,6 +314,29 @@ impl AppStateEventPublisher {
     ) -> Self {
         Self { publisher }
     }
+
+    async fn publish_lifecycle(
+        &self,
+        kind: fechatter_core::contracts::events::MessageLifecycle,
+        message: &fechatter_core::Message,
+        chat_members: Vec<i64>,
+    ) -> Result<(), AppError> {
+        let Some(publisher) = &self.publisher else {
+            debug!(
+                "Event publisher not available, skipping {:?} event for message {}",
+                kind, message.id
+            );
+            return Ok(());
+        };
+
+        let user_ids: Vec<fechatter_core::UserId> = chat_members
+            .into_iter()
+            .map(fechatter_core::UserId)
+            .collect();
+        publisher
+            .publish_message_event(kind, message, &user_ids)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -344,6 +384,52 @@ impl EventPublisherTrait for AppStateEventPublisher {
             Ok(())
         }
     }
+
+    async fn publish_message_updated(
+        &self,
+        message: &fechatter_core::Message,
+        chat_members: Vec<i64>,
+    ) -> Result<(), AppError> {
+        self.publish_lifecycle(
+            fechatter_core::contracts::events::MessageLifecycle::Updated,
+            message,
+            chat_members,
+        )
+        .await
+    }
+
+    async fn publish_message_deleted(
+        &self,
+        message: &fechatter_core::Message,
+        chat_members: Vec<i64>,
+    ) -> Result<(), AppError> {
+        self.publish_lifecycle(
+            fechatter_core::contracts::events::MessageLifecycle::Deleted,
+            message,
+            chat_members,
+        )
+        .await
+    }
+
+    async fn publish_message_fully_read(&self, message: &FullyReadMessage) -> Result<(), AppError> {
+        let Some(publisher) = &self.publisher else {
+            debug!(
+                "Event publisher not available, skipping fully read event for message {}",
+                message.message_id
+            );
+            return Ok(());
+        };
+
+        let readers: Vec<UserId> = message.readers.iter().map(|&id| UserId(id)).collect();
+        publisher
+            .publish_message_fully_read(
+                MessageId(message.message_id),
+                ChatId(message.chat_id),
+                UserId(message.sender_id),
+                &readers,
+            )
+            .await
+    }
 }
 
 // ── Dual Stream Message Service ─────────────────────────────────────────────────────
@@ -477,6 +563,53 @@ impl DualStreamMessageService {
         Ok(messages.into_iter().map(MessageView::from).collect())
     }
 
+    /// Messages around `message_id`; `None` when it is not a message of the chat
+    pub async fn get_message_context(
+        &self,
+        chat_id: ChatId,
+        message_id: MessageId,
+        before: u32,
+        after: u32,
+    ) -> Result<Option<MessageContext>, AppError> {
+        self.domain_service
+            .get_message_context(
+                i64::from(chat_id),
+                i64::from(message_id),
+                i64::from(before),
+                i64::from(after),
+            )
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Count messages for pagination - returns (total, newer than `before`)
+    pub async fn count_messages(
+        &self,
+        chat_id: ChatId,
+        before: Option<i64>,
+    ) -> Result<(u64, u64), AppError> {
+        let (total, newer) = self
+            .domain_service
+            .get_messages_page_counts(i64::from(chat_id), before)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok((total.max(0) as u64, newer.max(0) as u64))
+    }
+
+    /// Preview message - runs domain validation only, no persistence and no streams
+    pub async fn preview_message(
+        &self,
+        sender_id: UserId,
+        chat_id: ChatId,
+        create_message: &CreateMessage,
+    ) -> Result<MessagePreview, AppError> {
+        self.domain_service
+            .preview_message(create_message, i64::from(chat_id), i64::from(sender_id))
+            .await
+            .map_err(AppError::from)
+    }
+
     /// Send message - triggers both streams (async index + realtime push)
     pub async fn send_message(
         &self,
@@ -554,14 +687,7 @@ impl DualStreamMessageService {
         let realtime_message = saved_message.clone();
         let realtime_members = chat_members.clone();
         tokio::spawn(async move {
-            let stream_message = StreamMessage {
-                id: realtime_message.id.to_string(),
-                chat_id: i64::from(realtime_message.chat_id),
-                sender_id: i64::from(realtime_message.sender_id),
-                content: realtime_message.content.clone(),
-                files: realtime_message.files.clone().unwrap_or_default(),
-                timestamp: realtime_message.created_at.timestamp(),
-            };
+            let stream_message = StreamMessage::from(&realtime_message);
 
             let realtime_event = RealtimeEvent::MessageReceived {
                 message: stream_message,
@@ -674,7 +800,20 @@ impl DualStreamMessageService {
             }
         }
 
-        // 4. Async index stream - update index @indexer.rs (in parallel)
+        // 4. Publish message updated event (in parallel)
+        let event_publisher = Arc::clone(&self.event_publisher);
+        let event_message = updated_message.clone();
+        let event_members = chat_members.clone();
+        tokio::spawn(async move {
+            if let Err(e) = event_publisher
+                .publish_message_updated(&event_message, event_members)
+                .await
+            {
+                warn!("Failed to publish message updated event: {}", e);
+            }
+        });
+
+        // 5. Async index stream - update index @indexer.rs (in parallel)
         let dispatcher = Arc::clone(&self.dispatcher);
         let index_message = updated_message.clone();
         tokio::spawn(async move {
@@ -694,19 +833,12 @@ impl DualStreamMessageService {
             }
         });
 
-        // 5. Realtime push stream - push update to notify-server (in parallel)
+        // 6. Realtime push stream - push update to notify-server (in parallel)
         let dispatcher = Arc::clone(&self.dispatcher);
         let realtime_message = updated_message.clone();
         let realtime_members = chat_members;
         tokio::spawn(async move {
-            let stream_message = StreamMessage {
-                id: realtime_message.id.to_string(),
-                chat_id: i64::from(realtime_message.chat_id),
-                sender_id: i64::from(realtime_message.sender_id),
-                content: realtime_message.content.clone(),
-                files: realtime_message.files.clone().unwrap_or_default(),
-                timestamp: realtime_message.created_at.timestamp(),
-            };
+            let stream_message = StreamMessage::from(&realtime_message);
 
             let realtime_event = RealtimeEvent::MessageReceived {
                 message: stream_message,
@@ -743,19 +875,11 @@ impl DualStreamMessageService {
 
         // Store necessary info before deletion
         let chat_id = message.chat_id;
-        let sender_id = message.sender_id;
-
-        // Verify user has permission to delete (must be sender)
-        if i64::from(sender_id) != i64::from(user_id) {
-            return Err(AppError::Unauthorized(
-                "Only message sender can delete the message".to_string(),
-            ));
-        }
 
         // 2. Begin transaction-like operation with compensating actions
         let mut rollback_actions = Vec::new();
 
-        // 3. Delete from database (primary source of truth)
+        // 3. Tombstone in database (primary source of truth; delta sync reports the tombstone)
         if let Err(e) = self
             .domain_service
             .delete_message(i64::from(message_id), i64::from(user_id))
@@ -811,7 +935,31 @@ impl DualStreamMessageService {
             }
         }
 
-        // 7. Publish async events for additional processing (non-critical path)
+        // 7. Publish message deleted event (non-critical path)
+        match self
+            .domain_service
+            .get_chat_members(i64::from(chat_id))
+            .await
+        {
+            Ok(chat_members) => {
+                let event_publisher = Arc::clone(&self.event_publisher);
+                let event_message = message.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = event_publisher
+                        .publish_message_deleted(&event_message, chat_members)
+                        .await
+                    {
+                        warn!("Failed to publish message deleted event: {}", e);
+                    }
+                });
+            }
+            Err(e) => warn!(
+                "Failed to load members of chat {} for message deleted event: {}",
+                chat_id, e
+            ),
+        }
+
+        // 8. Publish async events for additional processing (non-critical path)
         let dispatcher = Arc::clone(&self.dispatcher);
         let delete_message = message.clone();
         tokio::spawn(async move {
@@ -844,7 +992,7 @@ impl DualStreamMessageService {
             }
         });
 
-        // 8. Audit log the deletion
+        // 9. Audit log the deletion
         info!(
             "Message {} successfully deleted by user {} from chat {}",
             message_id, user_id, chat_id
@@ -977,10 +1125,13 @@ impl DualStreamMessageService {
             .map_err(AppError::from)
     }
 
-    /// Get unread mentions for a user across all chats
+    /// A page of a user's unread mentions across all chats, newest first
     pub async fn get_unread_mentions_for_user(
         &self,
         user_id: i64,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64)>,
+        limit: i64,
     ) -> Result<
         Vec<(
             i64,
@@ -993,26 +1144,59 @@ impl DualStreamMessageService {
         AppError,
     > {
         self.domain_service
-            .get_unread_mentions_for_user(user_id)
+            .get_unread_mentions_for_user(user_id, since, after, limit)
             .await
             .map_err(AppError::from)
     }
 
+    /// Count unread mentions for pagination - returns (total, listed up to `after`)
+    pub async fn count_unread_mentions(
+        &self,
+        user_id: i64,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64)>,
+    ) -> Result<(u64, u64), AppError> {
+        let (total, listed) = self
+            .domain_service
+            .get_unread_mentions_page_counts(user_id, since, after)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok((total.max(0) as u64, listed.max(0) as u64))
+    }
+
     // =============================================================================
     // DETAILED RECEIPTS MANAGEMENT
     // =============================================================================
 
-    /// Get detailed read receipts for a message
+    /// A page of detailed read receipts for a message, latest first
     pub async fn get_detailed_message_receipts(
         &self,
         message_id: i64,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64, String)>,
+        limit: i64,
     ) -> Result<Vec<(i64, String, String, String, chrono::DateTime<chrono::Utc>)>, AppError> {
         self.domain_service
-            .get_detailed_message_receipts(message_id)
+            .get_detailed_message_receipts(message_id, after, limit)
             .await
             .map_err(AppError::from)
     }
 
+    /// Count receipts for pagination - returns (total, listed up to `after`)
+    pub async fn count_message_receipts(
+        &self,
+        message_id: i64,
+        after: Option<(chrono::DateTime<chrono::Utc>, i64, String)>,
+    ) -> Result<(u64, u64), AppError> {
+        let (total, listed) = self
+            .domain_service
+            .get_message_receipts_page_counts(message_id, after)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok((total.max(0) as u64, listed.max(0) as u64))
+    }
+
     // =============================================================================
     // ENHANCED READ TRACKING
     // =============================================================================
@@ -1041,6 +1225,25 @@ impl DualStreamMessageService {
             .publish_realtime_event(realtime_event)
             .await?;
 
+        // The receipt that completes the message tells its sender everyone has read it
+        if let Some(fully_read) = self
+            .domain_service
+            .claim_message_fully_read(message_id)
+            .await
+            .map_err(AppError::from)?
+        {
+            if let Err(e) = self
+                .event_publisher
+                .publish_message_fully_read(&fully_read)
+                .await
+            {
+                warn!(
+                    "Failed to publish fully read event for message {}: {}",
+                    message_id, e
+                );
+            }
+        }
+
         Ok(())
     }
 
@@ -1054,7 +1257,7 @@ impl DualStreamMessageService {
         let pool = state.pool().clone();
         let repository =
             Arc::new(crate::domains::messaging::repository::MessageRepository::new(pool));
-        let config = MessageConfig::production_optimized();
+        let config = MessageConfig::with_limits(&state.config.features.message_limits);
         let domain_service = Arc::new(MessageDomainServiceImpl::new(repository, config));
 
         // Create dispatcher with NATS client if available
@@ -1102,7 +1305,7 @@ pub fn create_dual_stream_message_service(state: &AppState) -> DualStreamMessage
     // 1. Create domain service
     let pool = state.pool().clone();
     let repository = Arc::new(MessageRepository::new(pool));
-    let config = MessageConfig::default();
+    let config = MessageConfig::with_limits(&state.config.features.message_limits);
     let domain_impl = Arc::new(MessageDomainServiceImpl::new(repository, config));
     let domain_service: Arc<dyn MessageDomainService> = domain_impl;
 
@@ -1162,3 +1365,225 @@ pub fn create_complete_message_service(state: &AppState) -> CompleteMessageServi
 pub fn create_message_service(state: &AppState) -> MessageApplicationService {
     create_dual_stream_message_service(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::models::requests::message::SendMessageRequest;
+    use crate::services::infrastructure::event::{subjects, MessageEvent, MessageLifecycle};
+
+    async fn group_with_message(
+        state: &AppState,
+        users: &[fechatter_core::User],
+        content: &str,
+    ) -> MessageView {
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Events {}", uuid::Uuid::new_v4())),
+                None,
+                users[0].id,
+                vec![users[1].id],
+            )
+            .await
+            .unwrap();
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": content })).unwrap();
+        state
+            .application_services()
+            .message_service()
+            .send_message(
+                users[0].id,
+                ChatId::from(i64::from(chat.id)),
+                CreateMessage::from(request),
+            )
+            .await
+            .unwrap()
+    }
+
+    fn sorted_members(event: &MessageEvent) -> Vec<i64> {
+        let mut members: Vec<i64> = event.members.iter().map(|&id| i64::from(id)).collect();
+        members.sort();
+        members
+    }
+
+    #[tokio::test]
+    async fn send_message_should_publish_one_created_event() {
+        let (state, users, events) = crate::setup_test_users_with_events!(2).await;
+        let sent = group_with_message(&state, &users, "hello events").await;
+
+        let event = events.expect_message_event(MessageLifecycle::Created).await;
+        assert_eq!(i64::from(event.msg.id), sent.id);
+        assert_eq!(i64::from(event.msg.chat_id), sent.chat_id);
+        assert_eq!(event.msg.sender_id, users[0].id);
+        assert_eq!(event.msg.content, "hello events");
+        let mut expected = vec![i64::from(users[0].id), i64::from(users[1].id)];
+        expected.sort();
+        assert_eq!(sorted_members(&event), expected);
+        events
+            .expect_published::<MessageEvent>(subjects::MESSAGE_UPDATED, 0)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn edit_message_should_publish_one_updated_event() {
+        let (state, users, events) = crate::setup_test_users_with_events!(2).await;
+        let sent = group_with_message(&state, &users, "first draft").await;
+        events.expect_message_event(MessageLifecycle::Created).await;
+        events.clear().await;
+
+        state
+            .application_services()
+            .message_service()
+            .edit_message(
+                MessageId::from(sent.id),
+                users[0].id,
+                "final draft".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let event = events.expect_message_event(MessageLifecycle::Updated).await;
+        assert_eq!(event.kind, MessageLifecycle::Updated);
+        assert_eq!(i64::from(event.msg.id), sent.id);
+        assert_eq!(event.msg.content, "final draft");
+        assert_eq!(sorted_members(&event).len(), 2);
+        events
+            .expect_published::<MessageEvent>(subjects::MESSAGE_CREATED, 0)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn delete_message_should_publish_one_deleted_event() {
+        let (state, users, events) = crate::setup_test_users_with_events!(2).await;
+        let sent = group_with_message(&state, &users, "regrettable").await;
+        events.expect_message_event(MessageLifecycle::Created).await;
+        events.clear().await;
+
+        state
+            .application_services()
+            .message_service()
+            .delete_message(MessageId::from(sent.id), users[0].id)
+            .await
+            .unwrap();
+
+        let event = events.expect_message_event(MessageLifecycle::Deleted).await;
+        assert_eq!(i64::from(event.msg.id), sent.id);
+        assert_eq!(i64::from(event.msg.chat_id), sent.chat_id);
+        assert_eq!(sorted_members(&event).len(), 2);
+        events
+            .expect_published::<MessageEvent>(subjects::MESSAGE_UPDATED, 0)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn rejected_delete_should_publish_nothing() {
+        let (state, users, events) = crate::setup_test_users_with_events!(2).await;
+        let sent = group_with_message(&state, &users, "not yours").await;
+        events.expect_message_event(MessageLifecycle::Created).await;
+
+        let result = state
+            .application_services()
+            .message_service()
+            .delete_message(MessageId::from(sent.id), users[1].id)
+            .await;
+
+        assert!(result.is_err());
+        events
+            .expect_published::<MessageEvent>(subjects::MESSAGE_DELETED, 0)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn chat_moderator_should_delete_another_members_message() {
+        let (state, users, events) = crate::setup_test_users_with_events!(2).await;
+        let sent = group_with_message(&state, &users, "off topic").await;
+        events.expect_message_event(MessageLifecycle::Created).await;
+        sqlx::query(
+            "UPDATE chat_members SET role = 'moderator' WHERE chat_id = $1 AND user_id = $2",
+        )
+        .bind(sent.chat_id)
+        .bind(i64::from(users[1].id))
+        .execute(&*state.pool())
+        .await
+        .unwrap();
+
+        state
+            .application_services()
+            .message_service()
+            .delete_message(MessageId::from(sent.id), users[1].id)
+            .await
+            .unwrap();
+
+        let event = events.expect_message_event(MessageLifecycle::Deleted).await;
+        assert_eq!(i64::from(event.msg.id), sent.id);
+    }
+
+    #[tokio::test]
+    async fn fully_reading_a_message_should_publish_one_fully_read_event() {
+        use crate::services::infrastructure::event::MessageFullyReadEvent;
+
+        let (state, users, events) = crate::setup_test_users_with_events!(5).await;
+        let (sender, early, last, leaver, late) =
+            (&users[0], &users[1], &users[2], &users[3], &users[4]);
+        let chat = state
+            .create_new_chat(
+                fechatter_core::ChatType::Group,
+                Some(format!("Fully read {}", uuid::Uuid::new_v4())),
+                None,
+                sender.id,
+                vec![early.id, last.id, leaver.id],
+            )
+            .await
+            .unwrap();
+        let chat_id = i64::from(chat.id);
+        let request: SendMessageRequest =
+            serde_json::from_value(serde_json::json!({ "content": "announcement" })).unwrap();
+        let service = state.application_services().message_service();
+        let sent = service
+            .send_message(
+                sender.id,
+                ChatId::from(chat_id),
+                CreateMessage::from(request),
+            )
+            .await
+            .unwrap();
+
+        // Joining after the send doesn't block completion, and leaving unblocks it
+        let members = crate::domains::chat::ChatMemberRepository::new(state.pool());
+        members
+            .add_members(chat_id, &[i64::from(late.id)])
+            .await
+            .unwrap();
+        sqlx::query("UPDATE chat_members SET left_at = NOW() WHERE chat_id = $1 AND user_id = $2")
+            .bind(chat_id)
+            .bind(i64::from(leaver.id))
+            .execute(&*state.pool())
+            .await
+            .unwrap();
+
+        let read = |user: &fechatter_core::User| {
+            service.mark_message_read_enhanced(i64::from(user.id), chat_id, sent.id)
+        };
+        read(sender).await.unwrap();
+        read(early).await.unwrap();
+        events
+            .expect_published::<MessageFullyReadEvent>(subjects::MESSAGE_FULLY_READ, 0)
+            .await;
+
+        read(last).await.unwrap();
+        read(last).await.unwrap();
+        read(late).await.unwrap();
+
+        let event = events
+            .expect_published::<MessageFullyReadEvent>(subjects::MESSAGE_FULLY_READ, 1)
+            .await
+            .remove(0);
+        assert_eq!(i64::from(event.message_id), sent.id);
+        assert_eq!(i64::from(event.chat_id), chat_id);
+        assert_eq!(event.sender_id, sender.id);
+        assert!(event.readers.contains(&early.id));
+        assert!(event.readers.contains(&last.id));
+        assert!(!event.readers.contains(&sender.id));
+    }
+}
@@ -0,0 +1,434 @@
+//! # User Data Erasure
+//!
+//! **Responsibility**: Erase a user's personal data on an admin's request (GDPR erasure)
+//! **Flow**: Steps run in order and each is safe to run again, so an interrupted erasure resumes
+//! from the step recorded in `user_erasures`: revoke their sessions, delete the files they uploaded
+//! that nobody else references, tombstone their messages, scrub the profile, drop cached data and
+//! finally ask analytics to forget them. Messages stay as tombstones from "Deleted User", so chats
+//! keep their shape
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::domains::auth::token_repository::RefreshTokenStorage;
+use crate::domains::messaging::repository::MessageRepository;
+use crate::dtos::models::responses::attachment::Attachment;
+use crate::services::application::stores::ResponseCache;
+use crate::services::application::workers::message::retention::invalidate_tombstoned;
+use crate::services::application::workers::search::SearchApplicationServiceTrait;
+use crate::services::infrastructure::cache::{DistributedLockCacheInvalidator, RedisCacheService};
+use crate::services::infrastructure::event::DynEventPublisher;
+use crate::services::infrastructure::storage::FileStorage;
+use crate::{AppError, AppState};
+use fechatter_core::{UserId, UserStatus, WorkspaceId};
+
+/// Name an erased user is shown under
+pub const ERASED_USER_NAME: &str = "Deleted User";
+
+/// Messages tombstoned per statement
+const MESSAGE_BATCH: i64 = 500;
+
+/// Steps of an erasure, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErasureStep {
+    Tokens,
+    Files,
+    Messages,
+    Profile,
+    Caches,
+    Analytics,
+    Done,
+}
+
+impl ErasureStep {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tokens => "tokens",
+            Self::Files => "files",
+            Self::Messages => "messages",
+            Self::Profile => "profile",
+            Self::Caches => "caches",
+            Self::Analytics => "analytics",
+            Self::Done => "done",
+        }
+    }
+
+    pub fn parse(step: &str) -> Option<Self> {
+        match step {
+            "tokens" => Some(Self::Tokens),
+            "files" => Some(Self::Files),
+            "messages" => Some(Self::Messages),
+            "profile" => Some(Self::Profile),
+            "caches" => Some(Self::Caches),
+            "analytics" => Some(Self::Analytics),
+            "done" => Some(Self::Done),
+            _ => None,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::Tokens => Self::Files,
+            Self::Files => Self::Messages,
+            Self::Messages => Self::Profile,
+            Self::Profile => Self::Caches,
+            Self::Caches => Self::Analytics,
+            Self::Analytics | Self::Done => Self::Done,
+        }
+    }
+}
+
+/// Progress of a user's erasure
+#[derive(Debug, Clone, Serialize)]
+pub struct UserErasure {
+    pub user_id: i64,
+    pub workspace_id: i64,
+    pub requested_by: Option<i64>,
+    /// Next step to run, `done` once erased
+    pub step: ErasureStep,
+    pub files_deleted: i32,
+    pub messages_tombstoned: i32,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(FromRow)]
+struct UserErasureRow {
+    user_id: i64,
+    workspace_id: i64,
+    requested_by: Option<i64>,
+    step: String,
+    files_deleted: i32,
+    messages_tombstoned: i32,
+    started_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<UserErasureRow> for UserErasure {
+    type Error = AppError;
+
+    fn try_from(row: UserErasureRow) -> Result<Self, AppError> {
+        let step = ErasureStep::parse(&row.step)
+            .ok_or_else(|| AppError::Internal(format!("Unknown erasure step {}", row.step)))?;
+        Ok(Self {
+            user_id: row.user_id,
+            workspace_id: row.workspace_id,
+            requested_by: row.requested_by,
+            step,
+            files_deleted: row.files_deleted,
+            messages_tombstoned: row.messages_tombstoned,
+            started_at: row.started_at,
+            updated_at: row.updated_at,
+            completed_at: row.completed_at,
+        })
+    }
+}
+
+/// Runs erasures step by step
+pub struct UserErasureService {
+    pool: Arc<PgPool>,
+    messages: MessageRepository,
+    cache: Option<Arc<RedisCacheService>>,
+    search: Option<Arc<dyn SearchApplicationServiceTrait>>,
+    response_cache: Arc<ResponseCache>,
+    event_publisher: Option<Arc<DynEventPublisher>>,
+    storage: Arc<dyn FileStorage>,
+    url_prefix: String,
+}
+
+impl UserErasureService {
+    pub fn from_state(state: &AppState) -> Result<Self, AppError> {
+        let pool = state.pool();
+        Ok(Self {
+            messages: MessageRepository::new(pool.clone()),
+            pool,
+            cache: state.cache_service().cloned(),
+            search: state.search_application_service(),
+            response_cache: state.response_cache().clone(),
+            event_publisher: state.event_publisher_dyn().cloned(),
+            storage: state.file_storage().clone(),
+            url_prefix: state.config.storage.url_prefix.clone(),
+        })
+    }
+
+    pub async fn get(&self, user_id: i64) -> Result<Option<UserErasure>, AppError> {
+        sqlx::query_as::<_, UserErasureRow>(
+            r#"
+            SELECT user_id, workspace_id, requested_by, step, files_deleted, messages_tombstoned,
+                   started_at, updated_at, completed_at
+            FROM user_erasures
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?
+        .map(UserErasure::try_from)
+        .transpose()
+    }
+
+    /// Erase the user, resuming an earlier erasure that stopped part way. Erasing an
+    /// already erased user changes nothing and returns its record
+    pub async fn erase(
+        &self,
+        user_id: i64,
+        workspace_id: i64,
+        requested_by: i64,
+    ) -> Result<UserErasure, AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_erasures (user_id, workspace_id, requested_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(workspace_id)
+        .bind(requested_by)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        let mut erasure = self.get(user_id).await?.ok_or_else(|| {
+            AppError::Internal(format!("Erasure of user {} was not recorded", user_id))
+        })?;
+        while erasure.step != ErasureStep::Done {
+            let (files_deleted, messages_tombstoned) = match erasure.step {
+                ErasureStep::Tokens => {
+                    RefreshTokenStorage::revoke_all_for_user(user_id, &self.pool).await?;
+                    (0, 0)
+                }
+                ErasureStep::Files => (self.delete_files(user_id).await?, 0),
+                ErasureStep::Messages => (0, self.tombstone_messages(user_id).await?),
+                ErasureStep::Profile => {
+                    self.scrub_profile(user_id).await?;
+                    (0, 0)
+                }
+                ErasureStep::Caches => {
+                    self.drop_caches(user_id, erasure.workspace_id).await;
+                    (0, 0)
+                }
+                ErasureStep::Analytics => {
+                    self.forget_in_analytics(user_id, erasure.workspace_id)
+                        .await?;
+                    (0, 0)
+                }
+                ErasureStep::Done => unreachable!(),
+            };
+            erasure = self
+                .advance(
+                    user_id,
+                    erasure.step.next(),
+                    files_deleted,
+                    messages_tombstoned,
+                )
+                .await?;
+        }
+
+        info!(
+            "Erased user {}: {} files deleted, {} messages tombstoned",
+            user_id, erasure.files_deleted, erasure.messages_tombstoned
+        );
+        Ok(erasure)
+    }
+
+    /// Record a finished step and the counts it added
+    async fn advance(
+        &self,
+        user_id: i64,
+        step: ErasureStep,
+        files_deleted: i32,
+        messages_tombstoned: i32,
+    ) -> Result<UserErasure, AppError> {
+        let row = sqlx::query_as::<_, UserErasureRow>(
+            r#"
+            UPDATE user_erasures
+            SET step = $2,
+                files_deleted = files_deleted + $3,
+                messages_tombstoned = messages_tombstoned + $4,
+                updated_at = NOW(),
+                completed_at = CASE WHEN $2 = 'done' THEN NOW() END
+            WHERE user_id = $1
+            RETURNING user_id, workspace_id, requested_by, step, files_deleted,
+                      messages_tombstoned, started_at, updated_at, completed_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(step.as_str())
+        .bind(files_deleted)
+        .bind(messages_tombstoned)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        row.try_into()
+    }
+
+    /// Delete the stored files attached to the user's messages and their avatar, unless another
+    /// user's message or avatar references them too (storage is content addressed)
+    async fn delete_files(&self, user_id: i64) -> Result<i32, AppError> {
+        let entries: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT entry FROM messages, unnest(files) AS f(entry) WHERE sender_id = $1
+            UNION
+            SELECT avatar_url FROM users WHERE id = $1 AND avatar_url IS NOT NULL
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let mut deleted = 0;
+        for entry in entries {
+            let url = Attachment::from_stored(&entry).url;
+            // Only files in our storage; external avatars are just URLs on the profile
+            let Some(file_id) = url
+                .strip_prefix(&format!("{}/", self.url_prefix))
+                .filter(|file_id| !file_id.contains('/'))
+            else {
+                continue;
+            };
+
+            let shared: bool = sqlx::query_scalar(
+                r#"
+                SELECT EXISTS (
+                  SELECT 1 FROM messages, unnest(files) AS f(entry)
+                  WHERE sender_id <> $1 AND strpos(entry, $2) > 0
+                ) OR EXISTS (
+                  SELECT 1 FROM users WHERE id <> $1 AND strpos(avatar_url, $2) > 0
+                )
+                "#,
+            )
+            .bind(user_id)
+            .bind(file_id)
+            .fetch_one(self.pool.as_ref())
+            .await?;
+            if shared {
+                continue;
+            }
+
+            match self.storage.delete(file_id).await {
+                Ok(()) => deleted += 1,
+                // Deleted by an earlier run of this step
+                Err(AppError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+            sqlx::query("DELETE FROM file_scans WHERE file_id = $1")
+                .bind(file_id)
+                .execute(self.pool.as_ref())
+                .await?;
+        }
+
+        Ok(deleted)
+    }
+
+    async fn tombstone_messages(&self, user_id: i64) -> Result<i32, AppError> {
+        let mut total = 0;
+        loop {
+            let tombstoned = self
+                .messages
+                .tombstone_messages_by_sender(user_id, MESSAGE_BATCH)
+                .await?;
+            total += tombstoned.len() as i32;
+            invalidate_tombstoned(self.cache.as_ref(), self.search.as_ref(), &tombstoned).await;
+
+            if (tombstoned.len() as i64) < MESSAGE_BATCH {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// Replace the profile with a placeholder and delete per-user records. The user row stays
+    /// so their messages keep a sender
+    async fn scrub_profile(&self, user_id: i64) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Without a password hash signin fails as bad credentials, and the account stays suspended
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET fullname = $2, email = $3, password_hash = NULL, status = $4,
+                username = NULL, phone = NULL, title = NULL, department = NULL,
+                avatar_url = NULL, bio = NULL, last_active_at = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(ERASED_USER_NAME)
+        .bind(format!("deleted-{}@erased.invalid", user_id))
+        .bind(UserStatus::Suspended)
+        .execute(&mut *tx)
+        .await?;
+
+        for table in [
+            "user_settings",
+            "user_presence",
+            "user_activity_log",
+            "user_embeddings",
+        ] {
+            sqlx::query(&format!("DELETE FROM {} WHERE user_id = $1", table))
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        // Revoked tokens are kept for reuse detection, without the device they came from
+        sqlx::query(
+            "UPDATE refresh_tokens SET user_agent = NULL, ip_address = NULL WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Cached profile data expires with its TTL anyway, so failures here only warn
+    async fn drop_caches(&self, user_id: i64, workspace_id: i64) {
+        if let Some(cache) = &self.cache {
+            let keys = DistributedLockCacheInvalidator::user_cache_keys(user_id);
+            if let Err(e) = cache.del_many(&keys).await {
+                warn!("Failed to drop caches of erased user {}: {}", user_id, e);
+            }
+        }
+        self.response_cache
+            .invalidate_workspace(WorkspaceId(workspace_id))
+            .await;
+    }
+
+    async fn forget_in_analytics(&self, user_id: i64, workspace_id: i64) -> Result<(), AppError> {
+        let Some(publisher) = &self.event_publisher else {
+            warn!(
+                "Event publishing is not configured; analytics keep events of erased user {}",
+                user_id
+            );
+            return Ok(());
+        };
+        publisher
+            .publish_user_erased(UserId(user_id), WorkspaceId(workspace_id))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_should_round_trip_and_end_at_done() {
+        let mut step = ErasureStep::Tokens;
+        let mut seen = vec![step];
+        while step != ErasureStep::Done {
+            step = step.next();
+            seen.push(step);
+        }
+        assert_eq!(seen.len(), 7);
+        for step in seen {
+            assert_eq!(ErasureStep::parse(step.as_str()), Some(step));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! # Workspace User Import
+//!
+//! **Responsibility**: Create accounts in an admin's workspace in bulk for onboarding
+//! **Outcome**: Each row succeeds or fails on its own and is reported by index in a
+//! `BatchResponse`. Created accounts get a temporary password that is returned only in that
+//! response. Rows with an invalid email or name, an email already registered or repeated in
+//! the import, or past the workspace member cap (`WorkspaceConfig::max_members`) fail without
+//! stopping the rest
+
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+use validator::Validate;
+
+use crate::config::UserImportConfig;
+use crate::domains::user::admin::{AdminUserView, UserRole};
+use crate::domains::user::password::hashed_password;
+use crate::domains::user::repository::UserRepositoryImpl;
+use crate::domains::workspace::workspace_domain::WorkspaceConfig;
+use crate::dtos::core::response::{ApiError, BatchResponse};
+use crate::{AppError, AppState};
+use fechatter_core::{error::CoreError, WorkspaceId};
+
+/// Length of generated temporary passwords
+const TEMPORARY_PASSWORD_LENGTH: usize = 16;
+
+/// One account to create
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct UserImportRow {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    #[validate(length(min = 1, max = 100, message = "Full name must be 1 to 100 characters"))]
+    pub fullname: String,
+    /// `member` when omitted
+    #[serde(default)]
+    pub initial_role: Option<UserRole>,
+}
+
+/// A created account with the credentials to hand to its owner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedUser {
+    #[serde(flatten)]
+    pub user: AdminUserView,
+    /// Only ever returned here; meets the strong password rules
+    pub temporary_password: String,
+}
+
+pub struct UserImportService {
+    repository: UserRepositoryImpl,
+    config: UserImportConfig,
+}
+
+impl UserImportService {
+    pub fn new(pool: Arc<PgPool>, config: UserImportConfig) -> Self {
+        Self {
+            repository: UserRepositoryImpl::new(pool),
+            config,
+        }
+    }
+
+    pub fn from_state(state: &AppState) -> Self {
+        Self::new(state.pool(), state.config.features.user_import.clone()).with_workspace_config(
+            &WorkspaceConfig::with_limits(&state.config.features.workspace_limits),
+        )
+    }
+
+    pub fn with_workspace_config(mut self, config: &WorkspaceConfig) -> Self {
+        self.repository = self.repository.with_workspace_config(config);
+        self
+    }
+
+    /// Create the rows' accounts in `workspace_id`, in order
+    pub async fn import(
+        &self,
+        workspace_id: WorkspaceId,
+        rows: Vec<UserImportRow>,
+    ) -> Result<BatchResponse<ImportedUser>, AppError> {
+        if rows.is_empty() {
+            return Err(AppError::BadRequest("No users to import".to_string()));
+        }
+        if rows.len() > self.config.max_rows {
+            return Err(AppError::BadRequest(format!(
+                "At most {} users can be imported at once",
+                self.config.max_rows
+            )));
+        }
+
+        let started = Instant::now();
+        let mut seen = HashSet::new();
+        let mut outcome = BatchResponse::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            let id = Some(row.email.trim().to_string());
+            let created = if !seen.insert(row.email.trim().to_lowercase()) {
+                Err(CoreError::UserAlreadyExists(format!(
+                    "{} appears more than once in this import",
+                    row.email.trim()
+                )))
+            } else {
+                self.create(workspace_id, row).await
+            };
+
+            match created {
+                Ok(user) => outcome.add_success(index, id, user),
+                Err(e) => outcome.add_failure(index, id, ApiError::from(e)),
+            }
+        }
+        outcome.stats.processing_time_ms = started.elapsed().as_millis() as u64;
+
+        Ok(outcome)
+    }
+
+    async fn create(
+        &self,
+        workspace_id: WorkspaceId,
+        row: UserImportRow,
+    ) -> Result<ImportedUser, CoreError> {
+        let row = UserImportRow {
+            email: row.email.trim().to_string(),
+            fullname: row.fullname.trim().to_string(),
+            ..row
+        };
+        row.validate()
+            .map_err(|e| CoreError::Validation(e.to_string()))?;
+
+        let temporary_password = temporary_password();
+        let password_hash = hashed_password(&temporary_password)?;
+        let user = self
+            .repository
+            .create_workspace_member(
+                workspace_id,
+                &row.email,
+                &row.fullname,
+                &password_hash,
+                row.initial_role.unwrap_or(UserRole::Member),
+            )
+            .await?;
+
+        Ok(ImportedUser {
+            user,
+            temporary_password,
+        })
+    }
+}
+
+/// Random alphanumeric password containing upper and lower case letters and a digit
+pub fn temporary_password() -> String {
+    let mut rng = rand::thread_rng();
+    loop {
+        let password: String = (&mut rng)
+            .sample_iter(&Alphanumeric)
+            .take(TEMPORARY_PASSWORD_LENGTH)
+            .map(char::from)
+            .collect();
+        let has = |class: fn(&char) -> bool| password.chars().any(|c| class(&c));
+        if has(char::is_ascii_uppercase)
+            && has(char::is_ascii_lowercase)
+            && has(char::is_ascii_digit)
+        {
+            return password;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temporary_passwords_should_meet_the_strong_password_rules() {
+        let first = temporary_password();
+        assert_eq!(first.len(), TEMPORARY_PASSWORD_LENGTH);
+        assert!(first.chars().any(|c| c.is_ascii_uppercase()));
+        assert!(first.chars().any(|c| c.is_ascii_lowercase()));
+        assert!(first.chars().any(|c| c.is_ascii_digit()));
+        assert_ne!(first, temporary_password());
+    }
+}
@@ -1 +1,3 @@
+pub mod erasure;
+pub mod import;
 pub mod service;
@@ -84,7 +84,8 @@ impl WorkspaceApplicationService {
         let workspace_repository = Arc::new(WorkspaceRepositoryImpl::new(pool.clone()));
         let workspace_domain_service = Arc::new(WorkspaceDomainServiceImpl::new(
             workspace_repository,
-            WorkspaceConfig::default(),
+            state.permissions().clone(),
+            WorkspaceConfig::with_limits(&state.config.features.workspace_limits),
         )) as Arc<dyn WorkspaceDomainService>;
 
         Self {
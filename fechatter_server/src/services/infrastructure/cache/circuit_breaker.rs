@@ -0,0 +1,124 @@
+//! # Circuit Breaker
+//!
+//! **Responsibility**: Stop calling a dependency that keeps failing, and probe it now and then
+//! **States**: Closed counts consecutive failures; enough of them open the breaker, which turns
+//! every call away until its open period passes. Then one probe call goes through: success
+//! closes the breaker, failure opens it for another period
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Circuit breaker shared by every caller of one dependency
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_for: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    /// End of the current open period; `None` while closed
+    open_until: Option<Instant>,
+    /// Start of the probe let through after the open period. A probe that never reports back
+    /// is given up on after another open period
+    probe_started: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Breaker opening after `failure_threshold` consecutive failures, for `open_for` at a time
+    pub fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, BreakerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Whether a call may go through now
+    pub fn allow(&self) -> bool {
+        let mut state = self.state();
+        let Some(open_until) = state.open_until else {
+            return true;
+        };
+
+        let now = Instant::now();
+        let probe_pending = state
+            .probe_started
+            .is_some_and(|started| now.duration_since(started) < self.open_for);
+        if now < open_until || probe_pending {
+            return false;
+        }
+        state.probe_started = Some(now);
+        true
+    }
+
+    /// Report a call that succeeded; true when this closed the breaker
+    pub fn record_success(&self) -> bool {
+        let mut state = self.state();
+        let was_open = state.open_until.is_some();
+        *state = BreakerState::default();
+        was_open
+    }
+
+    /// Report a call that failed; true when this opened the breaker
+    pub fn record_failure(&self) -> bool {
+        let mut state = self.state();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+
+        let was_open = state.open_until.is_some();
+        if was_open || state.consecutive_failures >= self.failure_threshold {
+            state.open_until = Some(Instant::now() + self.open_for);
+            state.probe_started = None;
+        }
+        !was_open && state.open_until.is_some()
+    }
+
+    /// Whether calls are being turned away, or only a probe let through
+    pub fn is_open(&self) -> bool {
+        self.state().open_until.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaker_should_open_after_threshold_and_close_after_probe() {
+        let breaker = CircuitBreaker::new(2, Duration::from_millis(50));
+
+        assert!(!breaker.record_failure());
+        assert!(breaker.allow());
+        assert!(breaker.record_failure());
+        assert!(breaker.is_open());
+        assert!(!breaker.allow());
+
+        // One probe once the open period has passed; a failed probe reopens it
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow());
+        assert!(!breaker.allow());
+        assert!(!breaker.record_failure());
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow());
+        assert!(breaker.record_success());
+        assert!(!breaker.is_open());
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn success_should_reset_consecutive_failures() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        breaker.record_failure();
+        assert!(!breaker.record_success());
+        breaker.record_failure();
+        assert!(!breaker.is_open());
+    }
+}
@@ -0,0 +1,117 @@
+//! # In-Flight Load Coalescing
+//!
+//! **Responsibility**: Let concurrent loads of the same key share one underlying fetch
+//! **Lifetime**: A key is pending only while its load runs; the next call after it
+//! completes starts a fresh load, so nothing here acts as a cache
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Pending loads keyed by `K`, each shared by every caller that asks while it runs
+pub struct InFlight<K, V> {
+    pending: Arc<DashMap<K, Shared<BoxFuture<'static, V>>>>,
+}
+
+impl<K, V> Clone for InFlight<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for InFlight<K, V>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        Self {
+            pending: Arc::new(DashMap::new()),
+        }
+    }
+}
+
+impl<K, V> InFlight<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Await the pending load for `key`, or start one with `load` if none is running
+    pub async fn run<F, Fut>(&self, key: K, load: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V> + Send + 'static,
+    {
+        let shared = match self.pending.entry(key.clone()) {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => entry.insert(load().boxed().shared()).clone(),
+        };
+
+        let value = shared.clone().await;
+        // Whoever sees it finish first clears it; a newer load under the same key is kept
+        self.pending
+            .remove_if(&key, |_, pending| pending.ptr_eq(&shared));
+        value
+    }
+
+    /// Number of keys with a load still running
+    pub fn in_flight(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Stand-in for the chat-list fetch, counting how often it really runs
+    fn counting_load(loads: &Arc<AtomicUsize>) -> impl Future<Output = usize> + Send + 'static {
+        let loads = loads.clone();
+        async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            loads.fetch_add(1, Ordering::SeqCst) + 1
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_warmups_for_one_user_should_share_one_load() {
+        let chat_list_loads = InFlight::<i64, usize>::new();
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let warmups = (0..16).map(|_| {
+            let chat_list_loads = chat_list_loads.clone();
+            let loads = loads.clone();
+            tokio::spawn(async move { chat_list_loads.run(7, || counting_load(&loads)).await })
+        });
+        let results = futures::future::join_all(warmups).await;
+
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert!(results.into_iter().all(|result| result.unwrap() == 1));
+        assert_eq!(chat_list_loads.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn loads_for_different_users_or_later_calls_should_not_be_shared() {
+        let chat_list_loads = InFlight::<i64, usize>::new();
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let (first, other) = tokio::join!(
+            chat_list_loads.run(7, || counting_load(&loads)),
+            chat_list_loads.run(8, || counting_load(&loads)),
+        );
+        assert_ne!(first, other);
+
+        chat_list_loads.run(7, || counting_load(&loads)).await;
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,6 +1,12 @@
+pub mod circuit_breaker;
+pub mod in_flight;
+pub mod namespace;
 pub mod redis;
 pub mod strategy;
 
+pub use circuit_breaker::CircuitBreaker;
+pub use in_flight::InFlight;
+pub use namespace::CacheNamespaces;
 pub use redis::RedisCacheService;
 pub use strategy::{CacheKeys, CacheStrategyService};
 
@@ -9,13 +15,23 @@ pub type Cache = RedisCacheService;
 // 缓存一致性风险评估和改进
 pub mod consistency_checker;
 
+use crate::config::{ChatLimitsConfig, MessageLimitsConfig};
+use crate::domains::chat::chat_member_repository::ChatMemberRepository;
+use crate::domains::chat::repository::ChatRepository;
+use crate::domains::messaging::repository::MessageRepository;
+use crate::domains::user::repository::UserRepositoryImpl;
+use crate::services::application::workers::chat::ChatDetailView;
 use crate::AppError;
 use async_trait::async_trait;
 use chrono;
 use dashmap;
 use fechatter_core::chat::ChatSidebar;
+use fechatter_core::contracts::ChatRepository as CoreChatRepository;
+use fechatter_core::{ListMessages, UserId, WorkspaceId};
 use serde::{Deserialize, Serialize};
 use serde_json;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -73,6 +89,35 @@ impl CacheKeyBuilder {
     pub fn rate_limit(user_id: i64, endpoint: &str) -> String {
         format!("rate_limit:{}:{}", user_id, endpoint)
     }
+
+    /// Login attempts against one account; signin is throttled before the user is known
+    pub fn login_rate_limit(email: &str) -> String {
+        format!("rate_limit:login:{}", email.to_lowercase())
+    }
+
+    pub fn slow_mode(chat_id: i64, user_id: i64) -> String {
+        format!("slowmode:{}:{}", chat_id, user_id)
+    }
+
+    pub fn typing_push(chat_id: i64, user_id: i64) -> String {
+        format!("typing_push:{}:{}", chat_id, user_id)
+    }
+
+    pub fn ai_tokens(workspace_id: i64, day: &str) -> String {
+        format!("ai_tokens:{}:{}", workspace_id, day)
+    }
+
+    pub fn message_seen_summary(message_id: i64) -> String {
+        format!("message:seen:{}", message_id)
+    }
+
+    /// URLs are hashed to keep keys short and free of delimiters
+    pub fn link_preview(url: &str) -> String {
+        format!(
+            "link_preview:{}",
+            hex::encode(Sha256::digest(url.as_bytes()))
+        )
+    }
 }
 
 /// Unified cache service adapter - Single entry point for all cache operations
@@ -200,6 +245,8 @@ pub struct SyncCacheAdapter {
     cache_service: Option<Arc<UnifiedCacheService>>,
     // Use an in-memory fallback cache for sync contexts
     memory_cache: Arc<dashmap::DashMap<String, (serde_json::Value, Instant)>>,
+    // Background Redis fetches per user, so repeated misses share one fetch
+    chat_list_fetches: InFlight<i64, ()>,
 }
 
 impl SyncCacheAdapter {
@@ -208,6 +255,7 @@ impl SyncCacheAdapter {
         Self {
             cache_service: unified_cache,
             memory_cache: Arc::new(dashmap::DashMap::new()),
+            chat_list_fetches: InFlight::new(),
         }
     }
 
@@ -241,37 +289,17 @@ impl SyncCacheAdapter {
 
         // 2. If we have Redis cache, spawn background task to populate memory cache
         if let Some(cache) = &self.cache_service {
-            let cache_clone = cache.clone();
-            let memory_cache_clone = self.memory_cache.clone();
-            let cache_key_clone = cache_key.clone();
+            let cache = cache.clone();
+            let memory_cache = self.memory_cache.clone();
+            let chat_list_fetches = self.chat_list_fetches.clone();
 
-            // Fire-and-forget background fetch (no blocking!)
+            // Fire-and-forget background fetch (no blocking!), joining one already running
             tokio::task::spawn(async move {
-                match cache_clone.get_chat_list(user_id).await {
-                    Ok(Some(chats)) => {
-                        // Store in memory cache for future sync access
-                        if let Ok(json_value) = serde_json::to_value(&chats) {
-                            memory_cache_clone
-                                .insert(cache_key_clone, (json_value, Instant::now()));
-                            debug!(
-                                "[SYNC_CACHE] Background populated memory cache for user:{}",
-                                user_id
-                            );
-                        }
-                    }
-                    Ok(None) => {
-                        debug!(
-                            "ERROR: [SYNC_CACHE] Background MISS for chat_list:{}",
-                            user_id
-                        );
-                    }
-                    Err(e) => {
-                        error!(
-                            "ERROR: [SYNC_CACHE] Background ERROR for chat_list:{}: {}",
-                            user_id, e
-                        );
-                    }
-                }
+                chat_list_fetches
+                    .run(user_id, move || {
+                        Self::fetch_chat_list_into_memory(cache, memory_cache, user_id)
+                    })
+                    .await;
             });
         }
 
@@ -283,6 +311,41 @@ impl SyncCacheAdapter {
         None
     }
 
+    /// Background fetch of a chat list from Redis into the memory cache
+    async fn fetch_chat_list_into_memory(
+        cache: Arc<UnifiedCacheService>,
+        memory_cache: Arc<dashmap::DashMap<String, (serde_json::Value, Instant)>>,
+        user_id: i64,
+    ) {
+        match cache.get_chat_list(user_id).await {
+            Ok(Some(chats)) => {
+                // Store in memory cache for future sync access
+                if let Ok(json_value) = serde_json::to_value(&chats) {
+                    memory_cache.insert(
+                        format!("chat_list:{}", user_id),
+                        (json_value, Instant::now()),
+                    );
+                    debug!(
+                        "[SYNC_CACHE] Background populated memory cache for user:{}",
+                        user_id
+                    );
+                }
+            }
+            Ok(None) => {
+                debug!(
+                    "ERROR: [SYNC_CACHE] Background MISS for chat_list:{}",
+                    user_id
+                );
+            }
+            Err(e) => {
+                error!(
+                    "ERROR: [SYNC_CACHE] Background ERROR for chat_list:{}: {}",
+                    user_id, e
+                );
+            }
+        }
+    }
+
     /// Sync set chat list cache - Performance-optimized approach
     pub fn set_chat_list_sync(&self, user_id: i64, chats: Vec<ChatSidebar>, ttl_seconds: u64) {
         let cache_key = format!("chat_list:{}", user_id);
@@ -335,6 +398,21 @@ impl SyncCacheAdapter {
         }
     }
 
+    /// Drop in-memory chat lists of `user_ids`, returning how many entries were evicted
+    pub fn evict_chat_lists(&self, user_ids: &[i64]) -> u64 {
+        let evicted = user_ids
+            .iter()
+            .filter(|user_id| {
+                self.memory_cache
+                    .remove(&format!("chat_list:{}", user_id))
+                    .is_some()
+            })
+            .count() as u64;
+
+        debug!("[SYNC_CACHE] Memory EVICT for {} chat lists", evicted);
+        evicted
+    }
+
     /// Clean expired entries from memory cache (call periodically)
     pub fn cleanup_expired_entries(&self) {
         let now = Instant::now();
@@ -360,18 +438,31 @@ impl SyncCacheAdapter {
 pub struct DistributedLockCacheInvalidator {
     cache: Arc<UnifiedCacheService>,
     redis: Arc<RedisCacheService>,
+    /// Source of chat members for message fan-out; without it cached member keys are scanned
+    pool: Option<Arc<PgPool>>,
 }
 
 impl DistributedLockCacheInvalidator {
+    /// Members whose keys are deleted per Redis command in message fan-out
+    pub const MEMBER_FANOUT_BATCH: usize = 500;
+
     pub fn new(cache: Arc<UnifiedCacheService>) -> Self {
         Self {
             redis: cache.redis().clone(),
             cache,
+            pool: None,
         }
     }
 
-    /// User updated event - uses distributed lock to prevent race conditions
-    pub async fn handle_user_updated_with_lock(&self, user_id: i64) -> Result<(), AppError> {
+    /// Look chat members up in the database when fanning out message invalidations
+    pub fn with_pool(mut self, pool: Arc<PgPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// User updated event - uses distributed lock to prevent race conditions;
+    /// returns the number of keys deleted
+    pub async fn handle_user_updated_with_lock(&self, user_id: i64) -> Result<u64, AppError> {
         let lock_resource = format!("user_update:{}", user_id);
 
         self.redis
@@ -381,55 +472,39 @@ impl DistributedLockCacheInvalidator {
             .await
     }
 
+    /// Fixed cache keys owned by a user
+    pub fn user_cache_keys(user_id: i64) -> Vec<String> {
+        vec![
+            CacheKeyBuilder::user_profile(user_id),
+            CacheKeys::user_profile(user_id),
+            format!("user:settings:{}", user_id),
+            format!("user:permissions:{}", user_id),
+            format!("user:status:{}", user_id),
+            CacheKeys::chat_list(user_id),
+        ]
+    }
+
     /// Internal user update handler - protected by distributed lock
-    async fn handle_user_updated_internal(&self, user_id: i64) -> Result<(), AppError> {
+    async fn handle_user_updated_internal(&self, user_id: i64) -> Result<u64, AppError> {
         let start_time = Instant::now();
         info!(
             "🔒 [LOCKED] Start processing user {} update, distributed lock acquired",
             user_id
         );
 
-        // Use atomic operation for cache invalidation
-        let invalidation_script = r#"
-      local keys_to_delete = {}
-
-      -- User basic info caches
-      table.insert(keys_to_delete, "user:profile:" .. ARGV[1])
-      table.insert(keys_to_delete, "user:" .. ARGV[1])
-      table.insert(keys_to_delete, "user:settings:" .. ARGV[1])
-      table.insert(keys_to_delete, "user:permissions:" .. ARGV[1])
-      table.insert(keys_to_delete, "user:status:" .. ARGV[1])
-      table.insert(keys_to_delete, "chat_list:" .. ARGV[1])
-
-      -- Batch delete
-      local deleted_count = 0
-      for i, key in ipairs(keys_to_delete) do
-        deleted_count = deleted_count + redis.call("DEL", key)
-      end
-
-      -- Scan and delete related pattern keys
-      local workspace_keys = redis.call("SCAN", 0, "MATCH", "workspace:*:users:*", "COUNT", 50)
-      if workspace_keys[2] then
-        for i, key in ipairs(workspace_keys[2]) do
-          deleted_count = deleted_count + redis.call("DEL", key)
-        end
-      end
-
-      local session_keys = redis.call("SCAN", 0, "MATCH", "session:user:" .. ARGV[1] .. ":*", "COUNT", 50)
-      if session_keys[2] then
-        for i, key in ipairs(session_keys[2]) do
-          deleted_count = deleted_count + redis.call("DEL", key)
-        end
-      end
-
-      return deleted_count
-    "#;
+        // User basic info caches, plus workspace user lists and sessions found by pattern
+        let mut keys_to_delete = Self::user_cache_keys(user_id);
+        for pattern in [
+            "workspace:*:users:*".to_string(),
+            format!("session:user:{}:*", user_id),
+        ] {
+            keys_to_delete.extend(self.redis.scan_keys(&pattern).await?);
+        }
 
-        let deleted_count: i32 = self
-            .redis
-            .execute_script_with_arg(invalidation_script, user_id)
-            .await
-            .map_err(|e| AppError::Internal(format!("Cache invalidation script failed: {}", e)))?;
+        let deleted_count =
+            self.redis.del_many(&keys_to_delete).await.map_err(|e| {
+                AppError::Internal(format!("User cache invalidation failed: {}", e))
+            })?;
 
         let elapsed = start_time.elapsed();
         info!(
@@ -437,7 +512,7 @@ impl DistributedLockCacheInvalidator {
             user_id, deleted_count, elapsed
         );
 
-        Ok(())
+        Ok(deleted_count)
     }
 
     /// Message sent event - uses distributed lock to prevent race conditions
@@ -516,37 +591,25 @@ impl DistributedLockCacheInvalidator {
         let deleted_count = result.get(0).unwrap_or(&0);
         let new_message_count = result.get(1).unwrap_or(&0);
 
-        // Asynchronously invalidate chat list caches for chat members
-        let redis_clone = self.redis.clone();
-        let chat_id_clone = chat_id;
+        // Asynchronously invalidate chat list and unread caches of the chat members
+        let redis = self.redis.clone();
+        let pool = self.pool.clone();
         tokio::spawn(async move {
-            if let Ok(member_keys) = redis_clone
-                .scan_keys(&format!("chat:{}:member:*", chat_id_clone))
-                .await
-            {
-                let mut batch = redis_clone.batch();
-
-                for member_key in member_keys {
-                    if let Some(user_id_str) = member_key.split(':').last() {
-                        if let Ok(user_id) = user_id_str.parse::<i64>() {
-                            let user_chat_list_key = format!("chat_list:{}", user_id);
-                            batch = batch.del(&user_chat_list_key);
-
-                            // Invalidate unread count
-                            let unread_key = format!("unread:{}:{}", user_id, chat_id_clone);
-                            batch = batch.del(&unread_key);
+            match Self::chat_member_ids(&redis, pool, chat_id).await {
+                Ok(member_ids) => {
+                    match Self::fan_out_member_invalidation(&redis, chat_id, &member_ids).await {
+                        Ok(deleted) => debug!(
+                            "Async chat {} member cache invalidation complete: {} members, {} keys",
+                            chat_id,
+                            member_ids.len(),
+                            deleted
+                        ),
+                        Err(e) => {
+                            tracing::warn!("Async chat member cache invalidation failed: {}", e)
                         }
                     }
                 }
-
-                if let Err(e) = batch.run().await {
-                    tracing::warn!("Async chat member cache invalidation failed: {}", e);
-                } else {
-                    debug!(
-                        "Async chat {} member cache invalidation complete",
-                        chat_id_clone
-                    );
-                }
+                Err(e) => tracing::warn!("Failed to load members of chat {}: {}", chat_id, e),
             }
         });
 
@@ -559,6 +622,68 @@ impl DistributedLockCacheInvalidator {
         Ok(())
     }
 
+    /// Members of the chat, from the database when available, otherwise from cached member keys
+    async fn chat_member_ids(
+        redis: &RedisCacheService,
+        pool: Option<Arc<PgPool>>,
+        chat_id: i64,
+    ) -> Result<Vec<i64>, AppError> {
+        if let Some(pool) = pool {
+            return Ok(MessageRepository::new(pool)
+                .get_chat_members(chat_id)
+                .await?);
+        }
+
+        let member_keys = redis
+            .scan_keys(&format!("chat:{}:member:*", chat_id))
+            .await?;
+        Ok(member_keys
+            .iter()
+            .filter_map(|key| key.rsplit(':').next()?.parse().ok())
+            .collect())
+    }
+
+    /// Chat list and unread keys of each member, once per member, split into batches of at
+    /// most `batch_size` members
+    pub fn member_fanout_batches(
+        chat_id: i64,
+        member_ids: &[i64],
+        batch_size: usize,
+    ) -> Vec<Vec<String>> {
+        let mut member_ids = member_ids.to_vec();
+        member_ids.sort_unstable();
+        member_ids.dedup();
+
+        member_ids
+            .chunks(batch_size.max(1))
+            .map(|members| {
+                members
+                    .iter()
+                    .flat_map(|&user_id| {
+                        [
+                            CacheKeys::chat_list(user_id),
+                            CacheKeys::unread_count(user_id, chat_id),
+                        ]
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Delete the members' chat list and unread keys, one DEL per batch; returns the number
+    /// of keys deleted
+    async fn fan_out_member_invalidation(
+        redis: &RedisCacheService,
+        chat_id: i64,
+        member_ids: &[i64],
+    ) -> Result<u64, AppError> {
+        let mut deleted = 0;
+        for keys in Self::member_fanout_batches(chat_id, member_ids, Self::MEMBER_FANOUT_BATCH) {
+            deleted += redis.del_many(&keys).await?;
+        }
+        Ok(deleted)
+    }
+
     /// Chat member joined event - uses distributed lock to prevent race conditions
     pub async fn handle_member_joined_with_lock(
         &self,
@@ -596,7 +721,6 @@ impl DistributedLockCacheInvalidator {
       local keys_to_delete = {
         "chat_list:" .. user_id,
         "chat_members:" .. chat_id,
-        "chat:member:count:" .. chat_id,
         "chat:detail:" .. chat_id,
         "chat:online:members:" .. chat_id,
         "user:chat:count:" .. user_id
@@ -610,16 +734,13 @@ impl DistributedLockCacheInvalidator {
       local member_status_key = "is_member:" .. user_id .. ":" .. chat_id
       redis.call("SET", member_status_key, "true", "EX", 86400)
 
-      -- Atomically increment member count
-      local count_key = "chat:member:count:" .. chat_id
-      local new_count = redis.call("INCR", count_key)
-      redis.call("EXPIRE", count_key, 86400)
+      -- Member count is owned by MemberCountStore, adjusted with the DB change itself
 
       -- Initialize unread count
       local unread_key = "unread:" .. user_id .. ":" .. chat_id
       redis.call("SET", unread_key, "0", "EX", 604800) -- 7 days expiration
 
-      return {deleted_count, new_count}
+      return {deleted_count}
     "#;
 
         let result: Vec<i32> = self
@@ -631,23 +752,23 @@ impl DistributedLockCacheInvalidator {
             })?;
 
         let deleted_count = result.get(0).unwrap_or(&0);
-        let new_member_count = result.get(1).unwrap_or(&0);
 
         let elapsed = start_time.elapsed();
         info!(
-      "[LOCKED] User {} joined chat {} cache update complete: deleted {} keys, new member count: {}, took {:?}",
-      user_id, chat_id, deleted_count, new_member_count, elapsed
-    );
+            "[LOCKED] User {} joined chat {} cache update complete: deleted {} keys, took {:?}",
+            user_id, chat_id, deleted_count, elapsed
+        );
 
         Ok(())
     }
 
-    /// Batch cache invalidation - protects the entire batch operation with a distributed lock
+    /// Batch cache invalidation - protects the entire batch operation with a distributed lock;
+    /// returns the number of keys deleted
     pub async fn invalidate_chat_related_with_lock(
         &self,
         chat_id: i64,
         affected_user_ids: &[i64],
-    ) -> Result<(), AppError> {
+    ) -> Result<u64, AppError> {
         let lock_resource = format!("chat_batch_invalidate:{}", chat_id);
 
         let user_ids = affected_user_ids.to_vec();
@@ -659,12 +780,31 @@ impl DistributedLockCacheInvalidator {
             .await
     }
 
+    /// Fixed cache keys of a chat and of its affected users
+    pub fn chat_cache_keys(chat_id: i64, affected_user_ids: &[i64]) -> Vec<String> {
+        let mut keys = vec![
+            CacheKeys::recent_messages(chat_id),
+            CacheKeyBuilder::chat_detail(chat_id),
+            format!("chat_detail:{}", chat_id),
+            CacheKeyBuilder::chat_members(chat_id),
+            format!("chat_members:{}", chat_id),
+            format!("chat:online:members:{}", chat_id),
+        ];
+
+        for &user_id in affected_user_ids {
+            keys.push(CacheKeys::chat_list(user_id));
+            keys.push(CacheKeys::unread_count(user_id, chat_id));
+        }
+
+        keys
+    }
+
     /// Internal batch invalidation handler - protected by distributed lock
     async fn invalidate_chat_related_internal(
         &self,
         chat_id: i64,
         affected_user_ids: &[i64],
-    ) -> Result<(), AppError> {
+    ) -> Result<u64, AppError> {
         let start_time = Instant::now();
         info!(
             "🔒 [LOCKED] Start batch invalidation for chat {} related caches, affecting {} users",
@@ -672,59 +812,17 @@ impl DistributedLockCacheInvalidator {
             affected_user_ids.len()
         );
 
-        // Build atomic batch invalidation script
-        let batch_invalidation_script = format!(
-            r#"
-      local chat_id = ARGV[1]
-      local user_ids = {{{}}}
-      local deleted_count = 0
-
-      -- Delete chat-level caches
-      local chat_keys = {{
-        "recent_messages:" .. chat_id,
-        "chat_detail:" .. chat_id,
-        "chat_members:" .. chat_id,
-        "chat:member:count:" .. chat_id,
-        "chat:online:members:" .. chat_id
-      }}
-
-      for i, key in ipairs(chat_keys) do
-        deleted_count = deleted_count + redis.call("DEL", key)
-      end
-
-      -- Delete message pagination caches
-      for page = 0, 9 do
-        local page_key = "messages:" .. chat_id .. ":page:" .. page
-        deleted_count = deleted_count + redis.call("DEL", page_key)
-      end
-
-      -- Delete user-level caches
-      for i, user_id in ipairs(user_ids) do
-        local user_keys = {{
-          "chat_list:" .. user_id,
-          "unread:" .. user_id .. ":" .. chat_id
-        }}
-
-        for j, key in ipairs(user_keys) do
-          deleted_count = deleted_count + redis.call("DEL", key)
-        end
-      end
-
-      return deleted_count
-    "#,
-            affected_user_ids
-                .iter()
-                .map(|id| id.to_string())
-                .collect::<Vec<_>>()
-                .join(",")
+        // Chat-level and user-level caches, plus every cached message page
+        let mut keys_to_delete = Self::chat_cache_keys(chat_id, affected_user_ids);
+        keys_to_delete.extend(
+            self.redis
+                .scan_keys(&format!("messages:{}:page:*", chat_id))
+                .await?,
         );
 
-        let deleted_count: i32 = self
-            .redis
-            .execute_script_with_arg(&batch_invalidation_script, chat_id)
-            .await
-            .map_err(|e| {
-                AppError::Internal(format!("Batch cache invalidation script failed: {}", e))
+        let deleted_count =
+            self.redis.del_many(&keys_to_delete).await.map_err(|e| {
+                AppError::Internal(format!("Batch cache invalidation failed: {}", e))
             })?;
 
         let elapsed = start_time.elapsed();
@@ -733,7 +831,42 @@ impl DistributedLockCacheInvalidator {
       chat_id, deleted_count, affected_user_ids.len(), elapsed
     );
 
-        Ok(())
+        Ok(deleted_count)
+    }
+
+    /// Workspace-wide invalidation - drops workspace user lists and the chat lists of
+    /// `user_ids`; returns the number of keys deleted
+    pub async fn invalidate_workspace_with_lock(
+        &self,
+        workspace_id: i64,
+        user_ids: &[i64],
+    ) -> Result<u64, AppError> {
+        let lock_resource = format!("workspace_invalidate:{}", workspace_id);
+
+        let user_ids = user_ids.to_vec();
+        self.redis
+            .with_lock(&lock_resource, 60, async move {
+                let mut keys_to_delete = vec![
+                    CacheKeyBuilder::workspace_users(workspace_id),
+                    CacheKeys::workspace_users(workspace_id),
+                ];
+                keys_to_delete.extend(
+                    self.redis
+                        .scan_keys(&format!("workspace:{}:*", workspace_id))
+                        .await?,
+                );
+                keys_to_delete.extend(user_ids.iter().map(|&id| CacheKeys::chat_list(id)));
+
+                let deleted_count = self.redis.del_many(&keys_to_delete).await?;
+                info!(
+                    "[LOCKED] Workspace {} cache invalidation complete: deleted {} keys, {} users",
+                    workspace_id,
+                    deleted_count,
+                    user_ids.len()
+                );
+                Ok(deleted_count)
+            })
+            .await
     }
 
     /// Handle message updated event
@@ -1051,10 +1184,36 @@ impl DistributedLockCacheInvalidator {
     }
 }
 
-/// Cache warmup strategy - Preload hot data on system startup or user login
+/// Most recently active chats whose recent messages are warmed on login
+const WARMUP_ACTIVE_CHATS: usize = 5;
+/// Recent messages warmed per chat
+const WARMUP_RECENT_MESSAGES: i64 = 20;
+/// Workspace users warmed per workspace
+const WARMUP_WORKSPACE_USERS_LIMIT: i64 = 200;
+
+/// What an on-demand warmup preloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarmupTarget {
+    /// Chat detail, members and recent messages
+    Chat(i64),
+    /// Workspace user list
+    Workspace(i64),
+    /// Everything warmed on the user's login
+    User { user_id: i64, workspace_id: i64 },
+}
+
+/// Cache warmup strategy - Preload hot data on system startup, user login or admin request
+#[derive(Clone)]
 pub struct CacheWarmupStrategy {
     cache: Arc<UnifiedCacheService>,
     redis: Arc<RedisCacheService>,
+    chat_limits: ChatLimitsConfig,
+    message_limits: MessageLimitsConfig,
+    pool: Option<Arc<PgPool>>,
+    // Concurrent logins of one user share a single chat-list warmup
+    chat_list_warmups: InFlight<i64, Vec<i64>>,
+    // Repeated requests for one target share a single warmup
+    target_warmups: InFlight<WarmupTarget, Result<(), String>>,
 }
 
 impl CacheWarmupStrategy {
@@ -1062,11 +1221,41 @@ impl CacheWarmupStrategy {
         Self {
             redis: cache.redis().clone(),
             cache,
+            chat_limits: ChatLimitsConfig::default(),
+            message_limits: MessageLimitsConfig::default(),
+            pool: None,
+            chat_list_warmups: InFlight::new(),
+            target_warmups: InFlight::new(),
         }
     }
 
-    /// Cache warmup on user login - PRODUCTION IMPLEMENTATION
+    /// Publish the configured chat caps under `system:settings`
+    pub fn with_chat_limits(mut self, chat_limits: ChatLimitsConfig) -> Self {
+        self.chat_limits = chat_limits;
+        self
+    }
+
+    /// Publish the configured per-message caps under `system:settings`
+    pub fn with_message_limits(mut self, message_limits: MessageLimitsConfig) -> Self {
+        self.message_limits = message_limits;
+        self
+    }
+
+    /// Load warmup data from the database; without a pool, login warmup is skipped
+    pub fn with_pool(mut self, pool: Arc<PgPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Cache warmup on user login from the user, chat and message repositories
     pub async fn warmup_on_user_login(&self, user_id: i64, workspace_id: i64) {
+        let Some(pool) = self.pool.clone() else {
+            debug!(
+                "[WARMUP] No database pool, skipping login warmup for user:{}",
+                user_id
+            );
+            return;
+        };
         info!(
             "[WARMUP] Starting comprehensive cache warmup for user:{}",
             user_id
@@ -1074,27 +1263,38 @@ impl CacheWarmupStrategy {
         let start_time = Instant::now();
 
         // 1. Preload user profile info
-        if let Err(e) = self.warmup_user_profile(user_id).await {
+        if let Err(e) = self.warmup_user_profile(&pool, user_id).await {
             error!("ERROR: [WARMUP] Failed to warmup user profile: {}", e);
         }
 
-        // 2. Preload user's chat list
-        if let Err(e) = self.warmup_user_chats(user_id).await {
-            error!("ERROR: [WARMUP] Failed to warmup chat list: {}", e);
-        }
+        // 2. Preload user's chat list, joining a warmup already running for this user
+        let redis = self.redis.clone();
+        let chat_list_pool = pool.clone();
+        let chat_ids = self
+            .chat_list_warmups
+            .run(user_id, move || async move {
+                match Self::warmup_user_chats(&redis, chat_list_pool, user_id).await {
+                    Ok(chats) => chats.iter().map(|chat| i64::from(chat.id)).collect(),
+                    Err(e) => {
+                        error!("ERROR: [WARMUP] Failed to warmup chat list: {}", e);
+                        Vec::new()
+                    }
+                }
+            })
+            .await;
 
         // 3. Preload workspace user list (if workspace exists)
-        if let Err(e) = self.warmup_workspace_users(workspace_id).await {
+        if let Err(e) = self.warmup_workspace_users(&pool, workspace_id).await {
             error!("ERROR: [WARMUP] Failed to warmup workspace users: {}", e);
         }
 
         // 4. Preload recent messages from active chats
-        if let Err(e) = self.warmup_recent_messages(user_id).await {
+        if let Err(e) = self.warmup_recent_messages(&pool, &chat_ids).await {
             error!("ERROR: [WARMUP] Failed to warmup recent messages: {}", e);
         }
 
         // 5. Preload unread counts
-        if let Err(e) = self.warmup_unread_counts(user_id).await {
+        if let Err(e) = self.warmup_unread_counts(&pool, user_id, &chat_ids).await {
             error!("ERROR: [WARMUP] Failed to warmup unread counts: {}", e);
         }
 
@@ -1105,9 +1305,98 @@ impl CacheWarmupStrategy {
         );
     }
 
+    /// Warm the caches of `target` before returning, joining a warmup of the same target that
+    /// is already running
+    pub async fn warmup_target(&self, target: WarmupTarget) -> Result<(), AppError> {
+        let strategy = self.clone();
+        self.target_warmups
+            .run(target, move || async move {
+                strategy
+                    .warmup_target_now(target)
+                    .await
+                    .map_err(|e| e.to_string())
+            })
+            .await
+            .map_err(AppError::Internal)
+    }
+
+    async fn warmup_target_now(&self, target: WarmupTarget) -> Result<(), AppError> {
+        let Some(pool) = self.pool.clone() else {
+            return Err(AppError::ServiceUnavailable(
+                "Cache warmup has no database pool".to_string(),
+            ));
+        };
+        info!("[WARMUP] Warming {:?} on request", target);
+
+        match target {
+            WarmupTarget::Chat(chat_id) => {
+                self.warmup_chat_detail(&pool, chat_id).await?;
+                self.warmup_chat_members(&pool, chat_id).await?;
+                self.warmup_recent_messages(&pool, &[chat_id]).await
+            }
+            WarmupTarget::Workspace(workspace_id) => {
+                self.warmup_workspace_users(&pool, workspace_id).await
+            }
+            WarmupTarget::User {
+                user_id,
+                workspace_id,
+            } => {
+                self.warmup_on_user_login(user_id, workspace_id).await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Warmup chat detail as served by the chat service
+    async fn warmup_chat_detail(&self, pool: &Arc<PgPool>, chat_id: i64) -> Result<(), AppError> {
+        let key = CacheKeyBuilder::chat_detail(chat_id);
+
+        // Check if already cached
+        if self.redis.exists(&key).await? {
+            return Ok(());
+        }
+
+        let chat = ChatRepository::new(pool.clone())
+            .find_chat_by_id(chat_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(vec![format!("Chat {} not found", chat_id)]))?;
+        let member_count = ChatMemberRepository::new(pool.clone())
+            .get_member_count(chat_id)
+            .await?;
+        let detail = ChatDetailView::from_chat(chat, member_count as i32);
+
+        self.redis
+            .set(&key, &detail, CacheStrategyService::CHAT_DETAIL_TTL)
+            .await?;
+        debug!("[WARMUP] Chat detail cached for chat:{}", chat_id);
+        Ok(())
+    }
+
+    /// Warmup chat member list
+    async fn warmup_chat_members(&self, pool: &Arc<PgPool>, chat_id: i64) -> Result<(), AppError> {
+        let key = CacheKeyBuilder::chat_members(chat_id);
+
+        // Check if already cached
+        if self.redis.exists(&key).await? {
+            return Ok(());
+        }
+
+        let members = ChatMemberRepository::new(pool.clone())
+            .list_members(chat_id)
+            .await?;
+
+        self.redis.set(&key, &members, 1800).await?; // 30 minutes TTL
+        debug!(
+            "[WARMUP] Chat members cached for chat:{} ({} members)",
+            chat_id,
+            members.len()
+        );
+        Ok(())
+    }
+
     /// Warmup user profile information
-    async fn warmup_user_profile(&self, user_id: i64) -> Result<(), AppError> {
-        let key = format!("user:profile:{}", user_id);
+    async fn warmup_user_profile(&self, pool: &Arc<PgPool>, user_id: i64) -> Result<(), AppError> {
+        let key = CacheKeyBuilder::user_profile(user_id);
 
         // Check if already cached
         if self.redis.exists(&key).await? {
@@ -1115,61 +1404,53 @@ impl CacheWarmupStrategy {
             return Ok(());
         }
 
-        // Simulate loading user profile (in real implementation, load from database)
-        let user_profile = serde_json::json!({
-          "id": user_id,
-          "name": format!("User {}", user_id),
-          "status": "online",
-          "last_seen": chrono::Utc::now().to_rfc3339()
-        });
+        let Some(user) = UserRepositoryImpl::new(pool.clone())
+            .find_by_id_ext(UserId(user_id))
+            .await?
+        else {
+            debug!("[WARMUP] No user profile to cache for user:{}", user_id);
+            return Ok(());
+        };
 
-        self.redis.set(&key, &user_profile, 3600).await?; // 1 hour TTL
+        self.redis.set(&key, &user, 3600).await?; // 1 hour TTL
         debug!("[WARMUP] User profile cached for user:{}", user_id);
         Ok(())
     }
 
-    /// Warmup user's chat list
-    async fn warmup_user_chats(&self, user_id: i64) -> Result<(), AppError> {
-        let key = format!("chat_list:{}", user_id);
-
+    /// Warmup user's chat list, returning the cached or freshly loaded list
+    async fn warmup_user_chats(
+        redis: &RedisCacheService,
+        pool: Arc<PgPool>,
+        user_id: i64,
+    ) -> Result<Vec<ChatSidebar>, AppError> {
         // Check if already cached
-        if self.redis.exists(&key).await? {
+        if let Some(chat_list) = redis.get_chat_list(user_id).await? {
             debug!("[WARMUP] Chat list already cached for user:{}", user_id);
-            return Ok(());
+            return Ok(chat_list);
         }
 
-        // Simulate loading chat list (in real implementation, load from database)
-        let chat_list = vec![
-            serde_json::json!({
-              "id": 1,
-              "name": "General Chat",
-              "type": "group",
-              "last_message": "Welcome to the chat!",
-              "unread_count": 0,
-              "updated_at": chrono::Utc::now().to_rfc3339()
-            }),
-            serde_json::json!({
-              "id": 2,
-              "name": "Project Discussion",
-              "type": "group",
-              "last_message": "Let's discuss the latest updates",
-              "unread_count": 2,
-              "updated_at": chrono::Utc::now().to_rfc3339()
-            }),
-        ];
+        let chat_list = ChatRepository::new(pool)
+            .get_user_chats(UserId(user_id))
+            .await?;
 
-        self.redis.set(&key, &chat_list, 300).await?; // 5 minutes TTL
+        redis
+            .set(&CacheKeys::chat_list(user_id), &chat_list, 300)
+            .await?; // 5 minutes TTL
         debug!(
             "[WARMUP] Chat list cached for user:{} ({} chats)",
             user_id,
             chat_list.len()
         );
-        Ok(())
+        Ok(chat_list)
     }
 
     /// Warmup workspace users
-    async fn warmup_workspace_users(&self, workspace_id: i64) -> Result<(), AppError> {
-        let key = format!("workspace:users:{}", workspace_id);
+    async fn warmup_workspace_users(
+        &self,
+        pool: &Arc<PgPool>,
+        workspace_id: i64,
+    ) -> Result<(), AppError> {
+        let key = CacheKeyBuilder::workspace_users(workspace_id);
 
         // Check if already cached
         if self.redis.exists(&key).await? {
@@ -1180,21 +1461,14 @@ impl CacheWarmupStrategy {
             return Ok(());
         }
 
-        // Simulate loading workspace users (in real implementation, load from database)
-        let workspace_users = vec![
-            serde_json::json!({
-              "id": 1,
-              "name": "Alice Johnson",
-              "role": "admin",
-              "status": "online"
-            }),
-            serde_json::json!({
-              "id": 2,
-              "name": "Bob Smith",
-              "role": "member",
-              "status": "away"
-            }),
-        ];
+        let workspace_users = UserRepositoryImpl::new(pool.clone())
+            .get_workspace_users(
+                WorkspaceId(workspace_id),
+                WARMUP_WORKSPACE_USERS_LIMIT,
+                0,
+                None,
+            )
+            .await?;
 
         self.redis.set(&key, &workspace_users, 1800).await?; // 30 minutes TTL
         debug!(
@@ -1205,27 +1479,32 @@ impl CacheWarmupStrategy {
         Ok(())
     }
 
-    /// Warmup recent messages from active chats
-    async fn warmup_recent_messages(&self, user_id: i64) -> Result<(), AppError> {
-        // Get user's active chat IDs (top 5 most recent)
-        let active_chat_ids = vec![1i64, 2i64]; // In real implementation, query from database
+    /// Warmup recent messages of the most recently active chats
+    async fn warmup_recent_messages(
+        &self,
+        pool: &Arc<PgPool>,
+        chat_ids: &[i64],
+    ) -> Result<(), AppError> {
+        let messages = MessageRepository::new(pool.clone());
 
-        for chat_id in active_chat_ids {
-            let key = format!("recent_messages:{}", chat_id);
+        // The chat list is ordered by last activity
+        for &chat_id in chat_ids.iter().take(WARMUP_ACTIVE_CHATS) {
+            let key = CacheKeys::recent_messages(chat_id);
 
             // Check if already cached
             if self.redis.exists(&key).await? {
                 continue;
             }
 
-            // Simulate loading recent messages (in real implementation, load from database)
-            let recent_messages = vec![serde_json::json!({
-              "id": 1,
-              "chat_id": chat_id,
-              "sender_id": user_id,
-              "content": "Hello everyone!",
-              "created_at": chrono::Utc::now().to_rfc3339()
-            })];
+            let recent_messages = messages
+                .list_messages(
+                    ListMessages {
+                        last_id: None,
+                        limit: WARMUP_RECENT_MESSAGES,
+                    },
+                    chat_id,
+                )
+                .await?;
 
             self.redis.set(&key, &recent_messages, 1800).await?; // 30 minutes TTL
             debug!("[WARMUP] Recent messages cached for chat:{}", chat_id);
@@ -1235,28 +1514,32 @@ impl CacheWarmupStrategy {
     }
 
     /// Warmup unread counts
-    async fn warmup_unread_counts(&self, user_id: i64) -> Result<(), AppError> {
-        // Get user's chat IDs
-        let chat_ids = vec![1i64, 2i64]; // In real implementation, query from database
+    async fn warmup_unread_counts(
+        &self,
+        pool: &Arc<PgPool>,
+        user_id: i64,
+        chat_ids: &[i64],
+    ) -> Result<(), AppError> {
+        let messages = MessageRepository::new(pool.clone());
 
-        for chat_id in chat_ids {
-            let key = format!("unread:{}:{}", user_id, chat_id);
+        for &chat_id in chat_ids {
+            let key = CacheKeys::unread_count(user_id, chat_id);
 
             // Check if already cached
             if self.redis.exists(&key).await? {
                 continue;
             }
 
-            // Simulate loading unread count (in real implementation, query from database)
-            let unread_count = if chat_id == 2 { 2 } else { 0 }; // Chat 2 has 2 unread messages
+            // Same count the unread endpoint treats as authoritative
+            let unread_count = messages
+                .count_unread_after_last_read(chat_id, user_id)
+                .await?;
 
-            if unread_count > 0 {
-                self.redis.set(&key, &unread_count, 86400).await?; // 24 hours TTL
-                debug!(
-                    "[WARMUP] Unread count cached for user:{} chat:{} ({})",
-                    user_id, chat_id, unread_count
-                );
-            }
+            self.redis.set(&key, &unread_count, 86400).await?; // 24 hours TTL
+            debug!(
+                "[WARMUP] Unread count cached for user:{} chat:{} ({})",
+                user_id, chat_id, unread_count
+            );
         }
 
         Ok(())
@@ -1289,6 +1572,15 @@ impl CacheWarmupStrategy {
           "rate_limits": {
             "messages": 100,
             "uploads": 20
+          },
+          "chat_limits": {
+            "max_chats_per_user": self.chat_limits.max_chats_per_user,
+            "max_members_per_chat": self.chat_limits.max_members_per_chat
+          },
+          "message_limits": {
+            "max_content_length": self.message_limits.max_content_length,
+            "max_file_count": self.message_limits.max_file_count,
+            "max_total_attachment_bytes": self.message_limits.max_total_attachment_bytes
           }
         });
 
@@ -1336,3 +1628,326 @@ impl CacheWarmupStrategy {
 // 导出分布式锁缓存失效器
 // 注意：由于重复定义问题，在模块末尾重新导出
 // pub use self::DistributedLockCacheInvalidator;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_cache_keys_should_cover_detail_members_and_member_lists() {
+        let keys = DistributedLockCacheInvalidator::chat_cache_keys(42, &[7, 8]);
+
+        for expected in [
+            "chat:detail:42",
+            "chat:members:42",
+            "chat_list:7",
+            "chat_list:8",
+            "unread:7:42",
+        ] {
+            assert!(keys.iter().any(|k| k == expected), "missing {}", expected);
+        }
+    }
+
+    #[test]
+    fn member_fanout_should_cover_every_member_once_in_bounded_batches() {
+        // A large channel, with members repeated as a stale source might list them
+        let members: Vec<i64> = (1..=1200).chain(1..=300).collect();
+
+        let batches = DistributedLockCacheInvalidator::member_fanout_batches(42, &members, 500);
+
+        assert_eq!(batches.len(), 3);
+        assert!(batches.iter().all(|keys| keys.len() <= 2 * 500));
+        let mut chat_lists = std::collections::HashMap::new();
+        for key in batches.iter().flatten() {
+            if key.starts_with("chat_list:") {
+                *chat_lists.entry(key.clone()).or_insert(0) += 1;
+            }
+        }
+        assert_eq!(chat_lists.len(), 1200);
+        assert!(chat_lists.values().all(|&count| count == 1));
+        assert!(batches
+            .iter()
+            .flatten()
+            .any(|key| key == &CacheKeys::unread_count(1200, 42)));
+    }
+
+    #[test]
+    fn evict_chat_lists_should_drop_memory_entries_once() {
+        let adapter = SyncCacheAdapter::new(None);
+        adapter.set_chat_list_sync(7, Vec::new(), 60);
+
+        assert_eq!(adapter.evict_chat_lists(&[7, 8]), 1);
+        assert!(adapter.get_chat_list_sync(7).is_none());
+        assert_eq!(adapter.evict_chat_lists(&[7]), 0);
+    }
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+
+        #[tokio::test]
+        async fn invalidating_a_chat_should_clear_detail_members_and_list_caches() {
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let redis = Arc::new(
+                RedisCacheService::new(&redis_url, "test")
+                    .await
+                    .expect("Redis down?"),
+            );
+            let invalidator = DistributedLockCacheInvalidator::new(Arc::new(
+                UnifiedCacheService::new(redis.clone()),
+            ));
+            let chat_id = 9_000_000 + i64::from(std::process::id() % 100_000);
+            let member_id = chat_id + 1;
+
+            let keys = [
+                CacheKeyBuilder::chat_detail(chat_id),
+                CacheKeyBuilder::chat_members(chat_id),
+                CacheKeys::chat_list(member_id),
+            ];
+            for key in &keys {
+                redis.set(key, &"cached", 60).await.unwrap();
+            }
+
+            let cleared = invalidator
+                .invalidate_chat_related_with_lock(chat_id, &[member_id])
+                .await
+                .unwrap();
+            assert_eq!(cleared, keys.len() as u64);
+            for key in &keys {
+                assert!(!redis.exists(key).await.unwrap(), "{} survived", key);
+            }
+
+            // Nothing left to clear on a second pass
+            assert_eq!(
+                invalidator
+                    .invalidate_chat_related_with_lock(chat_id, &[member_id])
+                    .await
+                    .unwrap(),
+                0
+            );
+        }
+
+        #[tokio::test]
+        async fn message_fanout_should_clear_every_member_chat_list() {
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let redis = RedisCacheService::new(&redis_url, "test")
+                .await
+                .expect("Redis down?");
+            let chat_id = 9_100_000 + i64::from(std::process::id() % 100_000);
+            let members: Vec<i64> = (0..1200).map(|i| chat_id * 10_000 + i).collect();
+            for &user_id in &members {
+                redis
+                    .set(&CacheKeys::chat_list(user_id), &"cached", 60)
+                    .await
+                    .unwrap();
+            }
+
+            let deleted = DistributedLockCacheInvalidator::fan_out_member_invalidation(
+                &redis, chat_id, &members,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(deleted, members.len() as u64);
+            for &user_id in &members {
+                assert!(!redis.exists(&CacheKeys::chat_list(user_id)).await.unwrap());
+            }
+        }
+
+        #[tokio::test]
+        async fn login_warmup_should_cache_what_the_repositories_return() -> anyhow::Result<()> {
+            let (state, fixtures) = crate::setup_test_users!(2).await;
+            let pool = state.pool();
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            // A fresh key prefix, so nothing cached by earlier runs is mistaken for a warmup
+            let redis = Arc::new(
+                RedisCacheService::new(&redis_url, &format!("warmup-{}", uuid::Uuid::new_v4()))
+                    .await
+                    .expect("Redis down?"),
+            );
+
+            let user_id = i64::from(fixtures[0].id);
+            let workspace_id = i64::from(fixtures[0].workspace_id);
+            let chat = state
+                .create_new_chat(
+                    fechatter_core::ChatType::Group,
+                    Some(format!("Warmup {}", uuid::Uuid::new_v4())),
+                    None,
+                    fixtures[0].id,
+                    vec![fixtures[1].id],
+                )
+                .await?;
+            let chat_id = i64::from(chat.id);
+            sqlx::query(
+                r#"INSERT INTO messages (chat_id, sender_id, content, created_at, updated_at)
+                   VALUES ($1, $2, 'hi', NOW(), NOW())"#,
+            )
+            .bind(chat_id)
+            .bind(i64::from(fixtures[1].id))
+            .execute(&*pool)
+            .await?;
+
+            CacheWarmupStrategy::new(Arc::new(UnifiedCacheService::new(redis.clone())))
+                .with_pool(pool.clone())
+                .warmup_on_user_login(user_id, workspace_id)
+                .await;
+
+            let cached = |key: String| {
+                let redis = redis.clone();
+                async move {
+                    redis
+                        .get::<serde_json::Value>(&key)
+                        .await
+                        .unwrap()
+                        .unwrap_or_else(|| panic!("{} not warmed", key))
+                }
+            };
+            let users = UserRepositoryImpl::new(pool.clone());
+            let messages = MessageRepository::new(pool.clone());
+
+            let profile = users.find_by_id_ext(UserId(user_id)).await.unwrap();
+            assert_eq!(
+                cached(CacheKeyBuilder::user_profile(user_id)).await,
+                serde_json::to_value(profile).unwrap()
+            );
+
+            let chat_list = ChatRepository::new(pool.clone())
+                .get_user_chats(UserId(user_id))
+                .await
+                .unwrap();
+            assert!(chat_list.iter().any(|chat| i64::from(chat.id) == chat_id));
+            assert_eq!(
+                cached(CacheKeys::chat_list(user_id)).await,
+                serde_json::to_value(&chat_list).unwrap()
+            );
+
+            let recent = messages
+                .list_messages(
+                    ListMessages {
+                        last_id: None,
+                        limit: WARMUP_RECENT_MESSAGES,
+                    },
+                    chat_id,
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                cached(CacheKeys::recent_messages(chat_id)).await,
+                serde_json::to_value(&recent).unwrap()
+            );
+
+            let unread = messages
+                .count_unread_after_last_read(chat_id, user_id)
+                .await
+                .unwrap();
+            assert_eq!(unread, 1);
+            assert_eq!(
+                cached(CacheKeys::unread_count(user_id, chat_id)).await,
+                serde_json::json!(unread)
+            );
+
+            let workspace_users: Vec<fechatter_core::User> = redis
+                .get(&CacheKeyBuilder::workspace_users(workspace_id))
+                .await?
+                .expect("workspace users not warmed");
+            let mut warmed: Vec<UserId> = workspace_users.iter().map(|user| user.id).collect();
+            warmed.sort();
+            assert_eq!(warmed, vec![fixtures[0].id, fixtures[1].id]);
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn warming_a_chat_should_cache_its_detail_members_and_recent_messages(
+        ) -> anyhow::Result<()> {
+            let (state, users) = crate::setup_test_users!(2).await;
+            let pool = state.pool();
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let redis = Arc::new(
+                RedisCacheService::new(&redis_url, &format!("warm-chat-{}", uuid::Uuid::new_v4()))
+                    .await
+                    .expect("Redis down?"),
+            );
+
+            let chat = state
+                .create_new_chat(
+                    fechatter_core::ChatType::Group,
+                    Some(format!("Warm Chat {}", uuid::Uuid::new_v4())),
+                    None,
+                    users[0].id,
+                    vec![users[1].id],
+                )
+                .await?;
+            let chat_id = i64::from(chat.id);
+            let members = ChatMemberRepository::new(pool.clone());
+            sqlx::query(
+                r#"INSERT INTO messages (chat_id, sender_id, content, created_at, updated_at)
+                   VALUES ($1, $2, 'all hands at noon', NOW(), NOW())"#,
+            )
+            .bind(chat_id)
+            .bind(i64::from(users[0].id))
+            .execute(&*pool)
+            .await?;
+
+            // Two admins asking at once share one warmup
+            let strategy =
+                CacheWarmupStrategy::new(Arc::new(UnifiedCacheService::new(redis.clone())))
+                    .with_pool(pool.clone());
+            let (first, second) = tokio::join!(
+                strategy.warmup_target(WarmupTarget::Chat(chat_id)),
+                strategy.warmup_target(WarmupTarget::Chat(chat_id)),
+            );
+            first.unwrap();
+            second.unwrap();
+
+            let cached = |key: String| {
+                let redis = redis.clone();
+                async move {
+                    redis
+                        .get::<serde_json::Value>(&key)
+                        .await
+                        .unwrap()
+                        .unwrap_or_else(|| panic!("{} not warmed", key))
+                }
+            };
+
+            let chat = ChatRepository::new(pool.clone())
+                .find_chat_by_id(chat_id)
+                .await
+                .unwrap()
+                .unwrap();
+            let member_count = members.get_member_count(chat_id).await.unwrap();
+            assert_eq!(
+                cached(CacheKeyBuilder::chat_detail(chat_id)).await,
+                serde_json::to_value(ChatDetailView::from_chat(chat, member_count as i32)).unwrap()
+            );
+
+            let member_list = members.list_members(chat_id).await.unwrap();
+            assert!(!member_list.is_empty());
+            assert_eq!(
+                cached(CacheKeyBuilder::chat_members(chat_id)).await,
+                serde_json::to_value(&member_list).unwrap()
+            );
+
+            let recent = MessageRepository::new(pool.clone())
+                .list_messages(
+                    ListMessages {
+                        last_id: None,
+                        limit: WARMUP_RECENT_MESSAGES,
+                    },
+                    chat_id,
+                )
+                .await
+                .unwrap();
+            assert_eq!(recent.len(), 1);
+            assert_eq!(
+                cached(CacheKeys::recent_messages(chat_id)).await,
+                serde_json::to_value(&recent).unwrap()
+            );
+            Ok(())
+        }
+    }
+}
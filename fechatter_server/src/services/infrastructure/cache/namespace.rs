@@ -0,0 +1,115 @@
+//! # Versioned Cache Namespaces
+//!
+//! **Responsibility**: Invalidate a whole group of cache keys without finding them
+//! **Scheme**: Each namespace (e.g. `response:42`) has a version counter in Redis and its keys
+//! embed the current version. Invalidating bumps the counter, so older keys are never read
+//! again and expire with their TTL; no SCAN, so it works the same on a Redis cluster
+//! **Migration**: Version 0, before a namespace's first bump, is the pre-versioning layout
+//! `{namespace}:{suffix}`. Moving a cache from pattern deletes onto a namespace keeps its
+//! existing entries readable until the first invalidation, which then retires them all
+
+use std::sync::Arc;
+
+use super::RedisCacheService;
+use crate::AppError;
+
+/// Versioned key groups over Redis
+#[derive(Clone)]
+pub struct CacheNamespaces {
+    cache: Arc<RedisCacheService>,
+}
+
+impl CacheNamespaces {
+    pub fn new(cache: Arc<RedisCacheService>) -> Self {
+        Self { cache }
+    }
+
+    /// Key of the namespace's version counter. It has no TTL: with an eviction policy that
+    /// can drop keys without one (`allkeys-*`), a lost counter would resurrect version 0
+    fn version_key(namespace: &str) -> String {
+        format!("ns_version:{}", namespace)
+    }
+
+    /// Key of `suffix` at `version` of the namespace
+    pub fn versioned_key(namespace: &str, version: i64, suffix: &str) -> String {
+        if version == 0 {
+            format!("{}:{}", namespace, suffix)
+        } else {
+            format!("{}:v{}:{}", namespace, version, suffix)
+        }
+    }
+
+    /// Current version, 0 until the namespace is first invalidated. Unlike cached values it
+    /// never fails open: reading a missing version as 0 would serve the retired v0 keys
+    pub async fn version(&self, namespace: &str) -> Result<i64, AppError> {
+        Ok(self
+            .cache
+            .get_guarded::<i64>(&Self::version_key(namespace))
+            .await?
+            .unwrap_or(0))
+    }
+
+    /// Key of `suffix` in the current version of the namespace
+    pub async fn key(&self, namespace: &str, suffix: &str) -> Result<String, AppError> {
+        let version = self.version(namespace).await?;
+        Ok(Self::versioned_key(namespace, version, suffix))
+    }
+
+    /// Retire every key of the namespace, returning the new version
+    pub async fn invalidate(&self, namespace: &str) -> Result<i64, AppError> {
+        self.cache.incr(&Self::version_key(namespace), 1).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_zero_should_keep_the_unversioned_layout() {
+        assert_eq!(
+            CacheNamespaces::versioned_key("response:3", 0, "admin_users:7:ab"),
+            "response:3:admin_users:7:ab"
+        );
+        assert_eq!(
+            CacheNamespaces::versioned_key("response:3", 2, "admin_users:7:ab"),
+            "response:3:v2:admin_users:7:ab"
+        );
+    }
+
+    #[cfg(feature = "integration_tests")]
+    mod integration {
+        use super::*;
+
+        #[tokio::test]
+        async fn bumping_a_version_should_miss_old_entries_without_deleting_them() {
+            let redis_url = std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://:fechatter_redis_pass@localhost:6379".to_string());
+            let redis = Arc::new(
+                RedisCacheService::new(&redis_url, "test")
+                    .await
+                    .expect("Redis down?"),
+            );
+            let namespaces = CacheNamespaces::new(redis.clone());
+            let namespace = format!("chat_list:{}", uuid::Uuid::new_v4().simple());
+
+            let old_keys: Vec<String> = futures::future::try_join_all(
+                (1..=3).map(|user| namespaces.key(&namespace, &user.to_string())),
+            )
+            .await
+            .unwrap();
+            for key in &old_keys {
+                redis.set(key, &"cached", 60).await.unwrap();
+            }
+
+            assert_eq!(namespaces.invalidate(&namespace).await.unwrap(), 1);
+            for (user, old_key) in (1..=3).zip(&old_keys) {
+                let key = namespaces.key(&namespace, &user.to_string()).await.unwrap();
+                assert_ne!(&key, old_key);
+                assert_eq!(redis.get::<String>(&key).await.unwrap(), None);
+                // Retired, not deleted: the old entry waits out its TTL
+                assert!(redis.exists(old_key).await.unwrap());
+            }
+        }
+    }
+}
@@ -1,10 +1,15 @@
 use redis::aio::MultiplexedConnection;
 use redis::{AsyncCommands, Client, Pipeline};
 use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
-use tracing::warn;
+use tracing::{debug, info, warn};
 
+use super::CircuitBreaker;
+use crate::config::CacheDegradationConfig;
+use crate::services::infrastructure::observability::metrics::collectors::CacheMetrics;
 use crate::AppError;
 use fechatter_core::chat::ChatSidebar;
 use fechatter_core::models::Message;
@@ -16,10 +21,15 @@ pub mod ttl {
     pub const DAY: u64 = 86400;
 }
 
+/// Redis cache. Commands go through a circuit breaker, so a dead Redis is left alone until it
+/// may have recovered. While Redis is unreachable, reads and writes that can do without it
+/// fail open (see `CacheDegradationConfig`); locks and counters return `ServiceUnavailable`
 pub struct RedisCacheService {
     client: Arc<Client>,
     conn: Arc<RwLock<MultiplexedConnection>>,
     prefix: String,
+    breaker: Arc<CircuitBreaker>,
+    degradation: CacheDegradationConfig,
 }
 
 pub struct BatchOp<'a> {
@@ -42,12 +52,22 @@ impl<'a> BatchOp<'a> {
     }
 
     pub async fn run(self) -> Result<(), AppError> {
-        let mut conn = self.cache.conn.write().await;
-        let _: redis::Value = self.pipeline.query_async(&mut *conn).await?;
-        Ok(())
+        let cache = self.cache;
+        cache
+            .fail_open("batch", (), async {
+                let mut conn = cache.conn.write().await;
+                let _: redis::Value = self.pipeline.query_async(&mut *conn).await?;
+                Ok::<_, redis::RedisError>(())
+            })
+            .await
     }
 }
 
+/// Whether a command failed because Redis couldn't be reached, rather than rejecting it
+fn is_connection_failure(e: &redis::RedisError) -> bool {
+    e.is_io_error() || e.is_connection_dropped() || e.is_connection_refusal() || e.is_timeout()
+}
+
 impl RedisCacheService {
     pub async fn new(url: &str, prefix: &str) -> Result<Self, AppError> {
         let client = Arc::new(Client::open(url)?);
@@ -57,26 +77,143 @@ impl RedisCacheService {
             client,
             conn: Arc::new(RwLock::new(conn)),
             prefix: prefix.to_string(),
+            breaker: Self::breaker_for(&CacheDegradationConfig::default()),
+            degradation: CacheDegradationConfig::default(),
         })
     }
 
+    /// Degrade as `config` says while Redis is unreachable
+    pub fn with_degradation(mut self, config: CacheDegradationConfig) -> Self {
+        self.breaker = Self::breaker_for(&config);
+        self.degradation = config;
+        self
+    }
+
+    fn breaker_for(config: &CacheDegradationConfig) -> Arc<CircuitBreaker> {
+        Arc::new(CircuitBreaker::new(
+            config.failure_threshold,
+            Duration::from_secs(config.open_secs),
+        ))
+    }
+
+    /// Whether the circuit breaker is keeping commands from Redis
+    pub fn is_circuit_open(&self) -> bool {
+        self.breaker.is_open()
+    }
+
     fn make_key(&self, key: &str) -> String {
         format!("{}:{}", self.prefix, key)
     }
 
+    /// Report a command's outcome to the circuit breaker; only connection failures count
+    /// against Redis
+    fn observe<T>(&self, result: redis::RedisResult<T>) -> Result<T, AppError> {
+        match result {
+            Ok(value) => {
+                if self.breaker.record_success() {
+                    CacheMetrics::set_circuit_open(false);
+                    info!("Redis is reachable again, circuit breaker closed");
+                }
+                Ok(value)
+            }
+            Err(e) if is_connection_failure(&e) => {
+                self.record_connection_failure();
+                Err(AppError::ServiceUnavailable(format!(
+                    "Redis unreachable: {}",
+                    e
+                )))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn record_connection_failure(&self) {
+        if self.breaker.record_failure() {
+            CacheMetrics::set_circuit_open(true);
+            warn!(
+                "Redis unreachable, circuit breaker open for {}s",
+                self.degradation.open_secs
+            );
+        }
+    }
+
+    /// Run `command` unless the circuit breaker is open, within the command timeout.
+    /// `ServiceUnavailable` when Redis can't be reached
+    async fn guarded<T>(
+        &self,
+        command: impl Future<Output = redis::RedisResult<T>>,
+    ) -> Result<T, AppError> {
+        if !self.breaker.allow() {
+            return Err(AppError::ServiceUnavailable(
+                "Redis circuit breaker is open".to_string(),
+            ));
+        }
+
+        let timeout = Duration::from_millis(self.degradation.command_timeout_ms);
+        match tokio::time::timeout(timeout, command).await {
+            Ok(result) => self.observe(result),
+            Err(_) => {
+                self.record_connection_failure();
+                Err(AppError::ServiceUnavailable(
+                    "Redis command timed out".to_string(),
+                ))
+            }
+        }
+    }
+
+    /// `guarded` for a command the server can do without: while Redis is unreachable and the
+    /// cache fails open, `fallback` stands in for its reply, a miss for reads and nothing done
+    /// for writes
+    async fn fail_open<T>(
+        &self,
+        operation: &str,
+        fallback: T,
+        command: impl Future<Output = redis::RedisResult<T>>,
+    ) -> Result<T, AppError> {
+        match self.guarded(command).await {
+            Err(AppError::ServiceUnavailable(reason)) if self.degradation.fail_open => {
+                CacheMetrics::record_degraded(operation);
+                debug!("Cache {} skipped: {}", operation, reason);
+                Ok(fallback)
+            }
+            result => result,
+        }
+    }
+
     pub async fn set<T: Serialize>(&self, key: &str, val: &T, ttl: u64) -> Result<(), AppError> {
-        let mut conn = self.conn.write().await;
         let full_key = self.make_key(key);
         let data = serde_json::to_vec(val)?;
-        conn.set_ex::<_, _, ()>(&full_key, data, ttl).await?;
-        Ok(())
+        self.fail_open("set", (), async {
+            let mut conn = self.conn.write().await;
+            conn.set_ex(&full_key, data, ttl).await
+        })
+        .await
     }
 
     pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, AppError> {
-        let mut conn = self.conn.write().await;
         let full_key = self.make_key(key);
+        let data: Option<Vec<u8>> = self
+            .fail_open("get", None, async {
+                let mut conn = self.conn.write().await;
+                conn.get(&full_key).await
+            })
+            .await?;
+        match data {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
 
-        let data: Option<Vec<u8>> = conn.get(&full_key).await?;
+    /// `get` for a value whose absence means something, such as a version counter: never
+    /// fails open, so an unreachable Redis is `ServiceUnavailable` rather than a miss
+    pub async fn get_guarded<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, AppError> {
+        let full_key = self.make_key(key);
+        let data: Option<Vec<u8>> = self
+            .guarded(async {
+                let mut conn = self.conn.write().await;
+                conn.get(&full_key).await
+            })
+            .await?;
         match data {
             Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
             None => Ok(None),
@@ -91,9 +228,13 @@ impl RedisCacheService {
             return Ok(vec![]);
         }
 
-        let mut conn = self.conn.write().await;
         let full_keys: Vec<String> = keys.iter().map(|k| self.make_key(k)).collect();
-        let values: Vec<Option<Vec<u8>>> = conn.get(&full_keys).await?;
+        let values: Vec<Option<Vec<u8>>> = self
+            .fail_open("mget", vec![None; keys.len()], async {
+                let mut conn = self.conn.write().await;
+                conn.get(&full_keys).await
+            })
+            .await?;
 
         let mut results = Vec::new();
         for val in values {
@@ -112,96 +253,225 @@ impl RedisCacheService {
     }
 
     pub async fn del(&self, key: &str) -> Result<bool, AppError> {
-        let mut conn = self.conn.write().await;
         let full_key = self.make_key(key);
-        let deleted: bool = conn.del(&full_key).await?;
-        Ok(deleted)
+        self.fail_open("del", false, async {
+            let mut conn = self.conn.write().await;
+            conn.del(&full_key).await
+        })
+        .await
+    }
+
+    /// Delete several keys in one command, returning how many existed
+    pub async fn del_many(&self, keys: &[String]) -> Result<u64, AppError> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
+        let full_keys: Vec<String> = keys.iter().map(|k| self.make_key(k)).collect();
+        self.fail_open("del_many", 0, async {
+            let mut conn = self.conn.write().await;
+            conn.del(&full_keys).await
+        })
+        .await
     }
 
     pub async fn exists(&self, key: &str) -> Result<bool, AppError> {
-        let mut conn = self.conn.write().await;
         let full_key = self.make_key(key);
-        let exists: bool = conn.exists(&full_key).await?;
-        Ok(exists)
+        self.fail_open("exists", false, async {
+            let mut conn = self.conn.write().await;
+            conn.exists(&full_key).await
+        })
+        .await
     }
 
     pub async fn del_pattern(&self, pattern: &str) -> Result<u64, AppError> {
-        let mut conn = self.conn.write().await;
         let full_pattern = self.make_key(pattern);
-
-        // Use SCAN to find all matching keys
-        let mut keys_to_delete = Vec::new();
-        let mut cursor = "0".to_string();
-
-        loop {
-            let result: (String, Vec<String>) = redis::cmd("SCAN")
-                .arg(&cursor)
-                .arg("MATCH")
-                .arg(&full_pattern)
-                .arg("COUNT")
-                .arg(100)
-                .query_async(&mut *conn)
-                .await?;
-
-            cursor = result.0;
-            keys_to_delete.extend(result.1);
-
-            if cursor == "0" {
-                break;
+        self.fail_open("del_pattern", 0, async {
+            let mut conn = self.conn.write().await;
+
+            // Use SCAN to find all matching keys
+            let mut keys_to_delete = Vec::new();
+            let mut cursor = "0".to_string();
+
+            loop {
+                let result: (String, Vec<String>) = redis::cmd("SCAN")
+                    .arg(&cursor)
+                    .arg("MATCH")
+                    .arg(&full_pattern)
+                    .arg("COUNT")
+                    .arg(100)
+                    .query_async(&mut *conn)
+                    .await?;
+
+                cursor = result.0;
+                keys_to_delete.extend(result.1);
+
+                if cursor == "0" {
+                    break;
+                }
             }
-        }
 
-        if keys_to_delete.is_empty() {
-            return Ok(0);
-        }
+            if keys_to_delete.is_empty() {
+                return Ok(0);
+            }
 
-        // Delete all matching keys
-        let deleted: u64 = conn.del(&keys_to_delete).await?;
-        Ok(deleted)
+            // Delete all matching keys
+            conn.del(&keys_to_delete).await
+        })
+        .await
     }
 
     pub async fn incr(&self, key: &str, by: i64) -> Result<i64, AppError> {
+        let full_key = self.make_key(key);
+        self.guarded(async {
+            let mut conn = self.conn.write().await;
+            conn.incr(&full_key, by).await
+        })
+        .await
+    }
+
+    /// Increment a counter, setting its expiry when the key is created.
+    /// Returns the new value and the remaining TTL in seconds.
+    pub async fn incr_with_expiry(&self, key: &str, ttl: u64) -> Result<(i64, i64), AppError> {
+        const SCRIPT: &str = r#"
+            local count = redis.call('INCR', KEYS[1])
+            if count == 1 then
+                redis.call('EXPIRE', KEYS[1], ARGV[1])
+            end
+            return {count, redis.call('TTL', KEYS[1])}
+        "#;
+
+        let full_key = self.make_key(key);
+        self.guarded(async {
+            let mut conn = self.conn.write().await;
+            redis::Script::new(SCRIPT)
+                .key(&full_key)
+                .arg(ttl)
+                .invoke_async(&mut *conn)
+                .await
+        })
+        .await
+    }
+
+    /// Add `by` to a counter, setting its expiry when the key is created.
+    /// Returns the new value.
+    pub async fn incr_by_with_expiry(&self, key: &str, by: i64, ttl: u64) -> Result<i64, AppError> {
+        const SCRIPT: &str = r#"
+            local count = redis.call('INCRBY', KEYS[1], ARGV[1])
+            if redis.call('TTL', KEYS[1]) == -1 then
+                redis.call('EXPIRE', KEYS[1], ARGV[2])
+            end
+            return count
+        "#;
+
+        let full_key = self.make_key(key);
+        self.guarded(async {
+            let mut conn = self.conn.write().await;
+            redis::Script::new(SCRIPT)
+                .key(&full_key)
+                .arg(by)
+                .arg(ttl)
+                .invoke_async(&mut *conn)
+                .await
+        })
+        .await
+    }
+
+    /// Add `delta` to a counter only if it already exists, refreshing its expiry.
+    /// Returns `None` for a missing counter so it can be reseeded from the source of truth,
+    /// which is also what an unreachable Redis reads as
+    pub async fn incr_if_exists(
+        &self,
+        key: &str,
+        delta: i64,
+        ttl: u64,
+    ) -> Result<Option<i64>, AppError> {
+        const SCRIPT: &str = r#"
+            if redis.call('EXISTS', KEYS[1]) == 0 then
+                return nil
+            end
+            local count = redis.call('INCRBY', KEYS[1], ARGV[1])
+            redis.call('EXPIRE', KEYS[1], ARGV[2])
+            return count
+        "#;
+
+        let full_key = self.make_key(key);
+        self.fail_open("incr_if_exists", None, async {
+            let mut conn = self.conn.write().await;
+            redis::Script::new(SCRIPT)
+                .key(&full_key)
+                .arg(delta)
+                .arg(ttl)
+                .invoke_async(&mut *conn)
+                .await
+        })
+        .await
+    }
+
+    /// Round-trip a PING, including any wait for the shared connection. It goes out even while
+    /// the circuit breaker is open, and a reply closes it
+    pub async fn ping(&self) -> Result<std::time::Duration, AppError> {
+        let start = std::time::Instant::now();
         let mut conn = self.conn.write().await;
+        let pong: redis::RedisResult<String> = redis::cmd("PING").query_async(&mut *conn).await;
+        drop(conn);
+        self.observe(pong)?;
+        Ok(start.elapsed())
+    }
+
+    /// Whether the shared multiplexed connection is currently held by an operation
+    pub fn is_connection_busy(&self) -> bool {
+        self.conn.try_write().is_err()
+    }
+
+    /// Remaining TTL in seconds (-1 without expiry, -2 when the key is missing)
+    pub async fn ttl(&self, key: &str) -> Result<i64, AppError> {
         let full_key = self.make_key(key);
-        let result: i64 = conn.incr(&full_key, by).await?;
-        Ok(result)
+        self.fail_open("ttl", -2, async {
+            let mut conn = self.conn.write().await;
+            conn.ttl(&full_key).await
+        })
+        .await
     }
 
     /// Scan for keys matching a pattern
     pub async fn scan_keys(&self, pattern: &str) -> Result<Vec<String>, AppError> {
-        let mut conn = self.conn.write().await;
         let full_pattern = self.make_key(pattern);
-
-        let mut all_keys = Vec::new();
-        let mut cursor = "0".to_string();
-
-        loop {
-            let result: (String, Vec<String>) = redis::cmd("SCAN")
-                .arg(&cursor)
-                .arg("MATCH")
-                .arg(&full_pattern)
-                .arg("COUNT")
-                .arg(100)
-                .query_async(&mut *conn)
-                .await?;
-
-            cursor = result.0;
-
-            // Strip the prefix from keys before returning
-            for key in result.1 {
-                if let Some(stripped) = key.strip_prefix(&format!("{}:", self.prefix)) {
-                    all_keys.push(stripped.to_string());
-                } else {
-                    all_keys.push(key);
+        self.fail_open("scan_keys", Vec::new(), async {
+            let mut conn = self.conn.write().await;
+
+            let mut all_keys = Vec::new();
+            let mut cursor = "0".to_string();
+
+            loop {
+                let result: (String, Vec<String>) = redis::cmd("SCAN")
+                    .arg(&cursor)
+                    .arg("MATCH")
+                    .arg(&full_pattern)
+                    .arg("COUNT")
+                    .arg(100)
+                    .query_async(&mut *conn)
+                    .await?;
+
+                cursor = result.0;
+
+                // Strip the prefix from keys before returning
+                for key in result.1 {
+                    if let Some(stripped) = key.strip_prefix(&format!("{}:", self.prefix)) {
+                        all_keys.push(stripped.to_string());
+                    } else {
+                        all_keys.push(key);
+                    }
                 }
-            }
 
-            if cursor == "0" {
-                break;
+                if cursor == "0" {
+                    break;
+                }
             }
-        }
 
-        Ok(all_keys)
+            Ok::<_, redis::RedisError>(all_keys)
+        })
+        .await
     }
 
     pub fn batch(&self) -> BatchOp<'_> {
@@ -211,6 +481,8 @@ impl RedisCacheService {
         }
     }
 
+    /// Take a distributed lock. Locks can't do without Redis, so an unreachable Redis is an
+    /// error whether or not the cache fails open
     pub async fn try_lock(&self, resource: &str, ttl: u64, token: &str) -> Result<bool, AppError> {
         let key = format!("lock:{}", resource);
         let full_key = self.make_key(&key);
@@ -223,12 +495,16 @@ impl RedisCacheService {
             end
         "#;
 
-        let mut conn = self.conn.write().await;
-        let result: i32 = redis::Script::new(script)
-            .key(&full_key)
-            .arg(token)
-            .arg(ttl as i64)
-            .invoke_async(&mut *conn)
+        let result: i32 = self
+            .guarded(async {
+                let mut conn = self.conn.write().await;
+                redis::Script::new(script)
+                    .key(&full_key)
+                    .arg(token)
+                    .arg(ttl as i64)
+                    .invoke_async(&mut *conn)
+                    .await
+            })
             .await?;
 
         Ok(result == 1)
@@ -248,11 +524,15 @@ impl RedisCacheService {
             end
         "#;
 
-        let mut conn = self.conn.write().await;
-        let result: i32 = redis::Script::new(script)
-            .key(&full_key)
-            .arg(token)
-            .invoke_async(&mut *conn)
+        let result: i32 = self
+            .guarded(async {
+                let mut conn = self.conn.write().await;
+                redis::Script::new(script)
+                    .key(&full_key)
+                    .arg(token)
+                    .invoke_async(&mut *conn)
+                    .await
+            })
             .await?;
 
         Ok(result == 1)
@@ -362,37 +642,6 @@ impl RedisCacheService {
         batch.run().await
     }
 
-    pub async fn set_users_online(&self, user_ids: &[i64]) -> Result<(), AppError> {
-        let mut batch = self.batch();
-
-        for &user_id in user_ids {
-            let key = format!("online:{}", user_id);
-            batch = batch.set(&key, &true, ttl::SHORT)?;
-        }
-
-        batch.run().await
-    }
-
-    pub async fn who_typing(&self, chat_id: i64) -> Result<Vec<i64>, AppError> {
-        let pattern = format!("typing:{}:*", chat_id);
-        let full_pattern = self.make_key(&pattern);
-
-        let mut conn = self.conn.write().await;
-        let mut users = Vec::new();
-
-        let mut iter: redis::AsyncIter<String> = conn.scan_match(&full_pattern).await?;
-
-        while let Some(key) = iter.next_item().await {
-            if let Some(user_str) = key.split(':').last() {
-                if let Ok(user_id) = user_str.parse::<i64>() {
-                    users.push(user_id);
-                }
-            }
-        }
-
-        Ok(users)
-    }
-
     pub async fn invalidate_recent_messages(&self, chat_id: i64) -> Result<(), AppError> {
         let key = format!("recent_messages:{}", chat_id);
         self.del(&key).await?;
@@ -436,13 +685,16 @@ impl RedisCacheService {
         let key = format!("unread:{}:{}", user_id, chat_id);
         let full_key = self.make_key(&key);
 
-        let mut conn = self.conn.write().await;
-        let new_count: i64 = conn.incr(&full_key, increment).await?;
+        self.guarded(async {
+            let mut conn = self.conn.write().await;
+            let new_count: i64 = conn.incr(&full_key, increment).await?;
 
-        // Set expiration time (7 days)
-        let _: bool = conn.expire(&full_key, (ttl::DAY * 7) as i64).await?;
+            // Set expiration time (7 days)
+            let _: bool = conn.expire(&full_key, (ttl::DAY * 7) as i64).await?;
 
-        Ok(new_count)
+            Ok::<_, redis::RedisError>(new_count)
+        })
+        .await
     }
 
     /// Decrement unread count
@@ -455,16 +707,19 @@ impl RedisCacheService {
         let key = format!("unread:{}:{}", user_id, chat_id);
         let full_key = self.make_key(&key);
 
-        let mut conn = self.conn.write().await;
-        let new_count: i64 = conn.decr(&full_key, decrement).await?;
+        self.guarded(async {
+            let mut conn = self.conn.write().await;
+            let new_count: i64 = conn.decr(&full_key, decrement).await?;
 
-        // Delete key if count is 0 or negative
-        if new_count <= 0 {
-            let _: () = conn.del(&full_key).await?;
-            Ok(0)
-        } else {
-            Ok(new_count)
-        }
+            // Delete key if count is 0 or negative
+            if new_count <= 0 {
+                let _: () = conn.del(&full_key).await?;
+                Ok::<_, redis::RedisError>(0)
+            } else {
+                Ok(new_count)
+            }
+        })
+        .await
     }
 
     /// Reset unread count to 0
@@ -618,12 +873,15 @@ impl RedisCacheService {
 
     /// Get cache statistics
     pub async fn get_cache_stats(&self) -> Result<CacheStats, AppError> {
-        let mut conn = self.conn.write().await;
-
         // Use INFO stats command to get statistics
-        let info: String = redis::cmd("INFO")
-            .arg("stats")
-            .query_async(&mut *conn)
+        let info: String = self
+            .guarded(async {
+                let mut conn = self.conn.write().await;
+                redis::cmd("INFO")
+                    .arg("stats")
+                    .query_async(&mut *conn)
+                    .await
+            })
             .await?;
 
         // Parse statistics
@@ -663,12 +921,12 @@ impl RedisCacheService {
         &self,
         script: &str,
     ) -> Result<T, AppError> {
-        let mut conn = self.conn.write().await;
-        let result = redis::Script::new(script)
-            .invoke_async(&mut *conn)
-            .await
-            .map_err(|e| AppError::Internal(format!("Redis script execution failed: {}", e)))?;
-        Ok(result)
+        self.guarded(async {
+            let mut conn = self.conn.write().await;
+            redis::Script::new(script).invoke_async(&mut *conn).await
+        })
+        .await
+        .map_err(script_error)
     }
 
     /// Execute Redis script with single argument - Optimized for common use cases
@@ -677,13 +935,15 @@ impl RedisCacheService {
         script: &str,
         arg: impl redis::ToRedisArgs,
     ) -> Result<T, AppError> {
-        let mut conn = self.conn.write().await;
-        let result = redis::Script::new(script)
-            .arg(arg)
-            .invoke_async(&mut *conn)
-            .await
-            .map_err(|e| AppError::Internal(format!("Redis script execution failed: {}", e)))?;
-        Ok(result)
+        self.guarded(async {
+            let mut conn = self.conn.write().await;
+            redis::Script::new(script)
+                .arg(arg)
+                .invoke_async(&mut *conn)
+                .await
+        })
+        .await
+        .map_err(script_error)
     }
 
     /// Execute Redis script with two arguments - Type-safe script execution
@@ -693,14 +953,26 @@ impl RedisCacheService {
         arg1: impl redis::ToRedisArgs,
         arg2: impl redis::ToRedisArgs,
     ) -> Result<T, AppError> {
-        let mut conn = self.conn.write().await;
-        let result = redis::Script::new(script)
-            .arg(arg1)
-            .arg(arg2)
-            .invoke_async(&mut *conn)
-            .await
-            .map_err(|e| AppError::Internal(format!("Redis script execution failed: {}", e)))?;
-        Ok(result)
+        self.guarded(async {
+            let mut conn = self.conn.write().await;
+            redis::Script::new(script)
+                .arg(arg1)
+                .arg(arg2)
+                .invoke_async(&mut *conn)
+                .await
+        })
+        .await
+        .map_err(script_error)
+    }
+}
+
+/// A script Redis rejected is an internal error; an unreachable Redis stays `ServiceUnavailable`
+fn script_error(e: AppError) -> AppError {
+    match e {
+        AppError::RedisError(e) => {
+            AppError::Internal(format!("Redis script execution failed: {}", e))
+        }
+        e => e,
     }
 }
 
@@ -736,6 +1008,43 @@ mod tests {
         assert_eq!(key, "test:user:123");
     }
 
+    #[tokio::test]
+    async fn cache_should_fail_open_while_redis_is_unreachable() {
+        let (url, server) = crate::tests::test_utils::fake_redis().await;
+        let cache = RedisCacheService::new(&url, "test")
+            .await
+            .unwrap()
+            .with_degradation(CacheDegradationConfig {
+                fail_open: true,
+                failure_threshold: 2,
+                open_secs: 60,
+                command_timeout_ms: 200,
+            });
+        cache.set("greeting", &"hi", 60).await.unwrap();
+
+        server.abort();
+        let _ = server.await;
+
+        // Reads miss and writes are skipped, and the breaker soon stops trying Redis
+        for _ in 0..3 {
+            let cached: Option<String> = cache.get("greeting").await.unwrap();
+            assert_eq!(cached, None);
+            assert!(matches!(
+                cache.get_guarded::<String>("greeting").await,
+                Err(AppError::ServiceUnavailable(_))
+            ));
+            cache.set("greeting", &"hi", 60).await.unwrap();
+            assert!(!cache.del("greeting").await.unwrap());
+        }
+        assert!(cache.is_circuit_open());
+
+        // Locks can't do without Redis
+        assert!(matches!(
+            cache.try_lock("resource", 10, "token").await,
+            Err(AppError::ServiceUnavailable(_))
+        ));
+    }
+
     #[cfg(feature = "integration_tests")]
     mod integration {
         use super::*;
@@ -0,0 +1,349 @@
+//! # Dead Letter Replay
+//!
+//! **Responsibility**: Keep dead-lettered NATS events in Postgres and let admins re-publish them
+//! to their original subject
+//! **Flow**: `DeadLetterConsumer` stores every record arriving on the configured dead letter
+//! subjects; `DeadLetterService::replay` re-publishes the selected events. Each replay bumps the
+//! event's `replay_count` and events at `max_replays` are skipped. An event that dead-letters
+//! again updates its existing row, so its count carries over and replays can't loop forever
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::DeadLetterConfig;
+use crate::services::infrastructure::event::DynEventPublisher;
+use crate::{AppError, AppState};
+
+/// A stored dead letter
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct DeadLetter {
+    pub id: i64,
+    pub dead_letter_subject: String,
+    pub source_subject: String,
+    pub payload: String,
+    pub error: String,
+    pub failure_count: i32,
+    pub failed_at: DateTime<Utc>,
+    pub replay_count: i32,
+    pub last_replayed_at: Option<DateTime<Utc>>,
+}
+
+/// Dead letters to replay; every given criterion must match
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReplayFilter {
+    /// Original subject of the event
+    pub subject: Option<String>,
+    /// Dead-lettered at or after
+    pub from: Option<DateTime<Utc>>,
+    /// Dead-lettered before
+    pub to: Option<DateTime<Utc>>,
+    pub ids: Option<Vec<i64>>,
+}
+
+impl ReplayFilter {
+    fn is_empty(&self) -> bool {
+        self.subject.is_none() && self.from.is_none() && self.to.is_none() && self.ids.is_none()
+    }
+}
+
+/// Dead letter ids by what happened to them
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplayOutcome {
+    pub replayed: Vec<i64>,
+    /// Already replayed `max_replays` times
+    pub exhausted: Vec<i64>,
+    /// Publishing failed; the attempt still counts as a replay
+    pub failed: Vec<i64>,
+}
+
+/// Fields of a dead letter record needed to replay it
+#[derive(Debug, Deserialize)]
+struct DeadLetterRecord {
+    subject: String,
+    payload: String,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Stores dead letters and replays them
+pub struct DeadLetterService {
+    pool: Arc<PgPool>,
+    event_publisher: Option<Arc<DynEventPublisher>>,
+    max_replays: i32,
+    replay_batch: i64,
+}
+
+impl DeadLetterService {
+    pub fn new(
+        pool: Arc<PgPool>,
+        event_publisher: Option<Arc<DynEventPublisher>>,
+        config: &DeadLetterConfig,
+    ) -> Self {
+        Self {
+            pool,
+            event_publisher,
+            max_replays: config.max_replays,
+            replay_batch: config.replay_batch,
+        }
+    }
+
+    pub fn from_state(state: &AppState) -> Self {
+        Self::new(
+            state.pool(),
+            state.event_publisher_dyn().cloned(),
+            &state.config.features.dead_letters,
+        )
+    }
+
+    /// Store a record from `dead_letter_subject`, returning its dead letter id. An event that is
+    /// already stored keeps its id and replay count
+    pub async fn record(&self, dead_letter_subject: &str, record: &[u8]) -> Result<i64, AppError> {
+        let record: DeadLetterRecord = serde_json::from_slice(record).map_err(|e| {
+            AppError::InvalidInput(format!(
+                "Invalid dead letter on {}: {}",
+                dead_letter_subject, e
+            ))
+        })?;
+
+        let id = sqlx::query_scalar(
+            r#"
+            INSERT INTO event_dead_letters (dead_letter_subject, source_subject, payload, error)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (source_subject, md5(payload)) DO UPDATE
+            SET dead_letter_subject = EXCLUDED.dead_letter_subject,
+                error = EXCLUDED.error,
+                failure_count = event_dead_letters.failure_count + 1,
+                failed_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(dead_letter_subject)
+        .bind(&record.subject)
+        .bind(&record.payload)
+        .bind(record.error.unwrap_or_default())
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn get(&self, id: i64) -> Result<DeadLetter, AppError> {
+        sqlx::query_as::<_, DeadLetter>(
+            r#"
+            SELECT id, dead_letter_subject, source_subject, payload, error, failure_count,
+                   failed_at, replay_count, last_replayed_at
+            FROM event_dead_letters
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound(vec![format!("Dead letter {} not found", id)]))
+    }
+
+    /// Re-publish the matching dead letters, oldest first, up to `replay_batch` of them
+    pub async fn replay(
+        &self,
+        filter: &ReplayFilter,
+        replayed_by: i64,
+    ) -> Result<ReplayOutcome, AppError> {
+        if filter.is_empty() {
+            return Err(AppError::InvalidInput(
+                "A subject, time range or ids are required to replay events".to_string(),
+            ));
+        }
+        let publisher = self.event_publisher.as_ref().ok_or_else(|| {
+            AppError::ServiceUnavailable("Event publishing is not configured".to_string())
+        })?;
+
+        let ids: Vec<i64> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM event_dead_letters
+            WHERE ($1::text IS NULL OR source_subject = $1)
+              AND ($2::timestamptz IS NULL OR failed_at >= $2)
+              AND ($3::timestamptz IS NULL OR failed_at < $3)
+              AND ($4::bigint[] IS NULL OR id = ANY($4))
+            ORDER BY failed_at, id
+            LIMIT $5
+            "#,
+        )
+        .bind(&filter.subject)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(&filter.ids)
+        .bind(self.replay_batch)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let mut outcome = ReplayOutcome::default();
+        for id in ids {
+            // Counting the replay before publishing keeps concurrent replays within max_replays
+            let claimed: Option<(String, String)> = sqlx::query_as(
+                r#"
+                UPDATE event_dead_letters
+                SET replay_count = replay_count + 1,
+                    last_replayed_at = NOW(),
+                    last_replayed_by = $2
+                WHERE id = $1 AND replay_count < $3
+                RETURNING source_subject, payload
+                "#,
+            )
+            .bind(id)
+            .bind(replayed_by)
+            .bind(self.max_replays)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+
+            let Some((subject, payload)) = claimed else {
+                outcome.exhausted.push(id);
+                continue;
+            };
+            match publisher
+                .transport()
+                .publish(&subject, Bytes::from(payload))
+                .await
+            {
+                Ok(()) => outcome.replayed.push(id),
+                Err(e) => {
+                    warn!("Failed to replay dead letter {} to {}: {}", id, subject, e);
+                    outcome.failed.push(id);
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+}
+
+/// Consumes the dead letter subjects into `event_dead_letters`
+pub struct DeadLetterConsumer {
+    service: Arc<DeadLetterService>,
+    nats: async_nats::Client,
+    subjects: Vec<String>,
+}
+
+impl DeadLetterConsumer {
+    /// `None` without a NATS connection, as there is nothing to consume
+    pub fn from_state(state: &AppState) -> Option<Self> {
+        let nats = state.nats_client()?;
+        Some(Self {
+            service: Arc::new(DeadLetterService::from_state(state)),
+            nats,
+            subjects: state.config.features.dead_letters.subjects.clone(),
+        })
+    }
+
+    /// Consume dead letters for the life of the process
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.run().await {
+                error!("Dead letter consumer stopped: {}", e);
+            }
+        })
+    }
+
+    async fn run(self) -> Result<(), AppError> {
+        let mut subscriptions = Vec::with_capacity(self.subjects.len());
+        for subject in &self.subjects {
+            let subscription = self.nats.subscribe(subject.clone()).await.map_err(|e| {
+                AppError::ServiceUnavailable(format!("Failed to subscribe to {}: {}", subject, e))
+            })?;
+            subscriptions.push(subscription);
+        }
+        let mut dead_letters = futures::stream::select_all(subscriptions);
+        info!("Dead letter consumer started on {:?}", self.subjects);
+
+        while let Some(message) = dead_letters.next().await {
+            if let Err(e) = self
+                .service
+                .record(&message.subject, &message.payload)
+                .await
+            {
+                warn!(
+                    "Failed to store dead letter from {}: {}",
+                    message.subject, e
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn replaying_a_dead_letter_should_republish_it_and_count_the_replay() -> anyhow::Result<()>
+    {
+        let (state, users, events) = crate::setup_test_users_with_events!(1).await;
+        let config = DeadLetterConfig {
+            max_replays: 2,
+            ..DeadLetterConfig::default()
+        };
+        let dead_letters = DeadLetterService::new(state.pool(), Some(events.publisher()), &config);
+        let subject = format!("fechatter.test.replay.{}", uuid::Uuid::new_v4().simple());
+        let event = json!({ "chat_id": 7, "content": "stuck" });
+        let record = json!({
+            "subject": subject,
+            "payload": event.to_string(),
+            "error": "notify rejected it",
+            "failed_at": Utc::now(),
+        })
+        .to_string();
+        let id = dead_letters
+            .record("fechatter.notify.dlq", record.as_bytes())
+            .await?;
+        let admin = i64::from(users[0].id);
+        let filter = ReplayFilter {
+            ids: Some(vec![id]),
+            ..ReplayFilter::default()
+        };
+
+        let outcome = dead_letters.replay(&filter, admin).await?;
+        assert_eq!(outcome.replayed, vec![id]);
+        let replayed = events.expect_published::<Value>(&subject, 1).await;
+        assert_eq!(replayed[0], event);
+        assert_eq!(dead_letters.get(id).await?.replay_count, 1);
+
+        // Failing again updates the same dead letter, keeping its replay count
+        let again = dead_letters
+            .record("fechatter.notify.dlq", record.as_bytes())
+            .await?;
+        assert_eq!(again, id);
+        let stored = dead_letters.get(id).await?;
+        assert_eq!(stored.failure_count, 2);
+        assert_eq!(stored.replay_count, 1);
+
+        dead_letters.replay(&filter, admin).await?;
+        let outcome = dead_letters.replay(&filter, admin).await?;
+        assert_eq!(outcome.exhausted, vec![id]);
+        events.expect_published::<Value>(&subject, 2).await;
+        assert_eq!(dead_letters.get(id).await?.replay_count, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replay_without_a_filter_should_be_rejected() -> anyhow::Result<()> {
+        let (state, _users, events) = crate::setup_test_users_with_events!(1).await;
+        let dead_letters = DeadLetterService::new(
+            state.pool(),
+            Some(events.publisher()),
+            &DeadLetterConfig::default(),
+        );
+
+        let result = dead_letters.replay(&ReplayFilter::default(), 1).await;
+
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        Ok(())
+    }
+}
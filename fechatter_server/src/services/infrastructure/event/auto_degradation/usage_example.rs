@@ -44,6 +44,7 @@ mod examples {
             files: None,
             created_at: Utc::now(),
             idempotency_key: None,
+            client_message_id: None,
         };
 
         let chat_members = vec![UserId(789), UserId(101112)];
@@ -100,6 +101,7 @@ mod examples {
             files: None,
             created_at: Utc::now(),
             idempotency_key: None,
+            client_message_id: None,
         };
 
         publish_message_created(&message, &[UserId(789)]).await?;
@@ -141,6 +143,7 @@ mod examples {
                 files: None,
                 created_at: Utc::now(),
                 idempotency_key: None,
+                client_message_id: None,
             };
 
             publish_message_created(&message, &[UserId(789)]).await?;
@@ -243,6 +246,7 @@ mod examples {
                     files: None,
                     created_at: Utc::now(),
                     idempotency_key: None,
+                    client_message_id: None,
                 };
                 (MessageLifecycle::Created, msg, vec![UserId(789)])
             })
@@ -399,6 +403,7 @@ pub mod integration_patterns {
                 files: None,
                 created_at: Utc::now(),
                 idempotency_key: None,
+                client_message_id: None,
             };
             publish_message_created(&message, &[UserId(789)]).await?;
         }
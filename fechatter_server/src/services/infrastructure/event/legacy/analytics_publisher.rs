@@ -15,6 +15,7 @@ use crate::error::AppError;
 use crate::services::infrastructure::event::EventTransport;
 use crate::services::infrastructure::event::NatsTransport;
 use analytics_server::pb::*;
+use fechatter_core::middlewares::RequestTrace;
 
 /// Analytics publisher configuration
 #[derive(Debug, Clone)]
@@ -205,13 +206,18 @@ impl<T: EventTransport + 'static> AnalyticsEventPublisher<T> {
         format!("{}.{}", config.subject_prefix, event_type)
     }
 
-    /// Publish analytics event (non-blocking)
-    pub fn publish(&self, event: AnalyticsEvent) -> Result<(), AppError> {
+    /// Publish analytics event (non-blocking); published while handling a request, it carries
+    /// that request's id and trace context unless it already has its own
+    pub fn publish(&self, mut event: AnalyticsEvent) -> Result<(), AppError> {
         if !self.config.enabled {
             debug!("Analytics disabled, skipping event");
             return Ok(());
         }
 
+        if let (Some(context), Some(trace)) = (event.context.as_mut(), RequestTrace::current()) {
+            Self::stamp_request_trace(context, trace);
+        }
+
         // Log the event type for debugging
         let event_type_name = match &event.event_type {
             Some(analytics_event::EventType::UserLogin(_)) => "user.login",
@@ -244,6 +250,15 @@ impl<T: EventTransport + 'static> AnalyticsEventPublisher<T> {
         Ok(())
     }
 
+    fn stamp_request_trace(context: &mut EventContext, trace: RequestTrace) {
+        if context.request_id.is_empty() {
+            context.request_id = trace.request_id.unwrap_or_default();
+        }
+        if context.traceparent.is_empty() {
+            context.traceparent = trace.traceparent.unwrap_or_default();
+        }
+    }
+
     /// Check if analytics is enabled
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
@@ -315,6 +330,8 @@ impl<T: EventTransport + 'static> AnalyticsTracking for AnalyticsEventPublisher<
                     browser_version: "1.0".to_string(),
                 }),
                 geo: None,
+                request_id: String::new(),
+                traceparent: String::new(),
             }),
             event_type: Some(analytics_event::EventType::UserLogin(UserLoginEvent {
                 email,
@@ -351,6 +368,8 @@ impl<T: EventTransport + 'static> AnalyticsTracking for AnalyticsEventPublisher<
                     browser_version: "1.0".to_string(),
                 }),
                 geo: None,
+                request_id: String::new(),
+                traceparent: String::new(),
             }),
             event_type: Some(analytics_event::EventType::MessageSent(MessageSentEvent {
                 chat_id,
@@ -391,6 +410,8 @@ impl<T: EventTransport + 'static> AnalyticsTracking for AnalyticsEventPublisher<
                     browser_version: "1.0".to_string(),
                 }),
                 geo: None,
+                request_id: String::new(),
+                traceparent: String::new(),
             }),
             event_type: Some(analytics_event::EventType::ChatCreated(ChatCreatedEvent {
                 workspace_id: "1".to_string(), // Default workspace
@@ -427,6 +448,8 @@ impl<T: EventTransport + 'static> AnalyticsTracking for AnalyticsEventPublisher<
                     browser_version: "1.0".to_string(),
                 }),
                 geo: None,
+                request_id: String::new(),
+                traceparent: String::new(),
             }),
             event_type: Some(analytics_event::EventType::ErrorOccurred(
                 ErrorOccurredEvent {
@@ -468,6 +491,8 @@ impl<T: EventTransport + 'static> AnalyticsTracking for AnalyticsEventPublisher<
                     browser_version: "1.0".to_string(),
                 }),
                 geo: None,
+                request_id: String::new(),
+                traceparent: String::new(),
             }),
             event_type: Some(analytics_event::EventType::BotResponse(BotResponseEvent {
                 bot_id,
@@ -498,3 +523,55 @@ impl NatsAnalyticsPublisher {
         Self::new(transport, config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::infrastructure::event::InMemoryTransport;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[tokio::test]
+    async fn event_published_in_a_request_should_carry_its_request_id() {
+        let transport = Arc::new(InMemoryTransport::new());
+        let publisher = AnalyticsEventPublisher::new(
+            transport.clone(),
+            AnalyticsConfig {
+                batch_size: 1,
+                ..AnalyticsConfig::default()
+            },
+        );
+
+        let trace = RequestTrace {
+            request_id: Some("req-42".to_string()),
+            traceparent: Some(TRACEPARENT.to_string()),
+        };
+        trace
+            .scope(publisher.track_chat_created(
+                "7".to_string(),
+                "1".to_string(),
+                "group".to_string(),
+                3,
+            ))
+            .await
+            .unwrap();
+
+        let mut messages = Vec::new();
+        for _ in 0..100 {
+            messages = transport.get_messages().await;
+            if !messages.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let (subject, payload, _) = messages.first().expect("event was not published");
+        assert_eq!(subject, "fechatter.analytics.chat.created");
+
+        let context = AnalyticsEvent::decode(payload.clone())
+            .unwrap()
+            .context
+            .unwrap();
+        assert_eq!(context.request_id, "req-42");
+        assert_eq!(context.traceparent, TRACEPARENT);
+    }
+}
@@ -12,6 +12,10 @@
 use crate::error::AppError;
 use async_nats::Client as NatsClient;
 use chrono::{DateTime, Utc};
+use fechatter_core::contracts::events::{
+    subjects, AiStreamChunkEvent, EphemeralMessageEvent, MessagePreviewsEvent,
+    SystemAnnouncementEvent,
+};
 use fechatter_core::{ChatId, MessageId, UserId};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
@@ -227,6 +231,43 @@ impl EnhancedEventPublisher {
             .await
     }
 
+    /// Publish an admin announcement; notify_server fans it out to connected clients,
+    /// limited to `announcement.workspace_id` when set
+    pub async fn publish_system_announcement(
+        &self,
+        announcement: &SystemAnnouncementEvent,
+    ) -> Result<(), AppError> {
+        self.publish_to_notify_server(subjects::SYSTEM_ANNOUNCEMENT, announcement)
+            .await
+    }
+
+    /// Publish a slash command reply that notify_server delivers to its sender only
+    pub async fn publish_ephemeral_message(
+        &self,
+        event: &EphemeralMessageEvent,
+    ) -> Result<(), AppError> {
+        self.publish_to_notify_server(subjects::EPHEMERAL_MESSAGE, event)
+            .await
+    }
+
+    /// Publish a piece of a streamed AI response; notify_server delivers it to the requester only
+    pub async fn publish_ai_stream_chunk(
+        &self,
+        event: &AiStreamChunkEvent,
+    ) -> Result<(), AppError> {
+        self.publish_to_notify_server(subjects::AI_STREAM_CHUNK, event)
+            .await
+    }
+
+    /// Publish link previews generated for a sent message; notify_server pushes them to the chat
+    pub async fn publish_link_previews(
+        &self,
+        event: &MessagePreviewsEvent,
+    ) -> Result<(), AppError> {
+        self.publish_to_notify_server(subjects::MESSAGE_PREVIEWS, event)
+            .await
+    }
+
     // =============================================================================
     // INTERNAL NATS PUBLISHING
     // =============================================================================
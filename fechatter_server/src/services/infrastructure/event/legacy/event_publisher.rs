@@ -9,10 +9,11 @@ use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use fechatter_core::{
     contracts::events::{
-        subjects, ChatMemberJoinedEvent, ChatMemberLeftEvent, DuplicateMessageEvent, EventVersion,
-        HmacSha256Verifier, MessageEvent, MessageLifecycle, SignatureVerifier,
+        subjects, ChatEvent, ChatLifecycle, ChatMemberJoinedEvent, ChatMemberLeftEvent,
+        DuplicateMessageEvent, EventVersion, FileScannedEvent, HmacSha256Verifier, MessageEvent,
+        MessageFullyReadEvent, MessageLifecycle, SignatureVerifier, UserErasedEvent,
     },
-    ChatId, Message, MessageId, UserId,
+    Chat, ChatId, Message, MessageId, UserId, WorkspaceId,
 };
 use hmac::{Hmac, Mac};
 use serde::Serialize;
@@ -57,6 +58,16 @@ impl Signable for MessageEvent {
     }
 }
 
+impl Signable for ChatEvent {
+    fn set_signature(&mut self, sig: Option<String>) {
+        self.sig = sig;
+    }
+
+    fn get_signature(&self) -> &Option<String> {
+        &self.sig
+    }
+}
+
 impl Signable for ChatMemberJoinedEvent {
     fn set_signature(&mut self, sig: Option<String>) {
         self.sig = sig;
@@ -77,6 +88,36 @@ impl Signable for ChatMemberLeftEvent {
     }
 }
 
+impl Signable for MessageFullyReadEvent {
+    fn set_signature(&mut self, sig: Option<String>) {
+        self.sig = sig;
+    }
+
+    fn get_signature(&self) -> &Option<String> {
+        &self.sig
+    }
+}
+
+impl Signable for UserErasedEvent {
+    fn set_signature(&mut self, sig: Option<String>) {
+        self.sig = sig;
+    }
+
+    fn get_signature(&self) -> &Option<String> {
+        &self.sig
+    }
+}
+
+impl Signable for FileScannedEvent {
+    fn set_signature(&mut self, sig: Option<String>) {
+        self.sig = sig;
+    }
+
+    fn get_signature(&self) -> &Option<String> {
+        &self.sig
+    }
+}
+
 impl Signable for DuplicateMessageEvent {
     fn set_signature(&mut self, sig: Option<String>) {
         self.sig = sig;
@@ -435,6 +476,34 @@ impl<T: EventTransport + ?Sized> EventPublisher<T> {
         self.publish_event(subject, event, "message_event").await
     }
 
+    #[instrument(skip(self, chat, members))]
+    pub async fn publish_chat_event(
+        &self,
+        kind: ChatLifecycle,
+        chat: &Chat,
+        actor_id: UserId,
+        members: &[UserId],
+    ) -> Result<(), AppError> {
+        let subject = match kind {
+            ChatLifecycle::Created => subjects::CHAT_CREATED,
+            ChatLifecycle::Updated => subjects::CHAT_UPDATED,
+            ChatLifecycle::Deleted => subjects::CHAT_DELETED,
+            ChatLifecycle::OwnershipTransferred => subjects::CHAT_OWNERSHIP_TRANSFERRED,
+        };
+
+        let event = ChatEvent {
+            version: EventVersion::default(),
+            kind,
+            chat: chat.clone(),
+            actor_id,
+            members: members.to_vec(),
+            occurred_at: Utc::now(),
+            sig: None,
+        };
+
+        self.publish_event(subject, event, "chat_event").await
+    }
+
     #[instrument(skip(self, chat_id, user_id))]
     pub async fn publish_chat_member_joined(
         &self,
@@ -471,6 +540,52 @@ impl<T: EventTransport + ?Sized> EventPublisher<T> {
             .await
     }
 
+    #[instrument(skip(self, readers))]
+    pub async fn publish_message_fully_read(
+        &self,
+        message_id: MessageId,
+        chat_id: ChatId,
+        sender_id: UserId,
+        readers: &[UserId],
+    ) -> Result<(), AppError> {
+        let event = MessageFullyReadEvent {
+            version: EventVersion::default(),
+            message_id,
+            chat_id,
+            sender_id,
+            readers: readers.to_vec(),
+            occurred_at: Utc::now(),
+            sig: None,
+        };
+
+        self.publish_event(subjects::MESSAGE_FULLY_READ, event, "message_fully_read")
+            .await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn publish_user_erased(
+        &self,
+        user_id: UserId,
+        workspace_id: WorkspaceId,
+    ) -> Result<(), AppError> {
+        let event = UserErasedEvent {
+            version: EventVersion::default(),
+            user_id,
+            workspace_id,
+            occurred_at: Utc::now(),
+            sig: None,
+        };
+
+        self.publish_event(subjects::USER_ERASED, event, "user_erased")
+            .await
+    }
+
+    #[instrument(skip(self, event), fields(file_id = %event.file_id))]
+    pub async fn publish_file_scanned(&self, event: FileScannedEvent) -> Result<(), AppError> {
+        self.publish_event(subjects::FILE_SCANNED, event, "file_scanned")
+            .await
+    }
+
     #[instrument(skip(self, idempotency_key, chat_id, sender_id))]
     pub async fn publish_duplicate_message_attempted(
         &self,
@@ -763,6 +878,7 @@ impl<T: EventTransport + ?Sized> EventPublisher<T> {
             files: None,
             created_at: Utc::now(),
             idempotency_key: None,
+            client_message_id: None,
         };
 
         let event = EnhancedMessageEvent {
@@ -874,6 +990,7 @@ mod tests {
             files: None,
             created_at: Utc::now(),
             idempotency_key: Some(Uuid::new_v4()),
+            client_message_id: None,
         }
     }
 
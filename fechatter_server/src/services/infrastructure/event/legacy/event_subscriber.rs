@@ -113,9 +113,14 @@ pub struct CacheEventSubscriber {
 }
 
 impl CacheEventSubscriber {
-    /// Create a new cache event subscriber
-    pub fn new(cache_service: Arc<UnifiedCacheService>, config: CacheInvalidationConfig) -> Self {
-        let invalidator = Arc::new(DistributedLockCacheInvalidator::new(cache_service));
+    /// Create a new cache event subscriber; chat members for message fan-out are read from `pool`
+    pub fn new(
+        cache_service: Arc<UnifiedCacheService>,
+        config: CacheInvalidationConfig,
+        pool: Arc<sqlx::PgPool>,
+    ) -> Self {
+        let invalidator =
+            Arc::new(DistributedLockCacheInvalidator::new(cache_service).with_pool(pool));
 
         let subscriber = Self {
             invalidator,
@@ -293,6 +298,7 @@ impl CacheEventSubscriber {
                     self.invalidator
                         .invalidate_chat_related_with_lock(0, &affected_users) // Use dummy chat_id
                         .await
+                        .map(|_| ())
                 }
                 InvalidationType::MessageSent { chat_id, sender_id } => {
                     self.invalidator
@@ -304,11 +310,11 @@ impl CacheEventSubscriber {
                         .handle_member_joined_with_lock(*chat_id, *user_id)
                         .await
                 }
-                InvalidationType::UserUpdated { user_id } => {
-                    self.invalidator
-                        .handle_user_updated_with_lock(*user_id)
-                        .await
-                }
+                InvalidationType::UserUpdated { user_id } => self
+                    .invalidator
+                    .handle_user_updated_with_lock(*user_id)
+                    .await
+                    .map(|_| ()),
             };
 
             match result {
@@ -489,7 +495,11 @@ pub fn register_cache_event_subscriber(
   };
 
   // Create and register subscriber
-  let subscriber = Arc::new(CacheEventSubscriber::new(unified_cache, config));
+  let subscriber = Arc::new(CacheEventSubscriber::new(
+    unified_cache,
+    config,
+    app_state.pool(),
+  ));
   event_publisher.register_handler(subscriber);
 
   info!("Cache event subscriber registered successfully");
@@ -114,13 +114,18 @@ pub use shared::{
     TransportFactory,
 };
 
+// Test double recording what gets published
+#[cfg(any(test, feature = "test-util"))]
+pub use shared::RecordingEventPublisher;
+
 // Re-export error types
 pub use crate::error::EventTransportError;
 
 // Re-export core event types from fechatter_core
 pub use fechatter_core::contracts::events::{
-    subjects, ChatMemberJoinedEvent, ChatMemberLeftEvent, EventVersion, HmacSha256Verifier,
-    MessageLifecycle, SignatureVerifier,
+    subjects, ChatEvent, ChatLifecycle, ChatMemberJoinedEvent, ChatMemberLeftEvent, EventVersion,
+    FileScanStatus, FileScannedEvent, HmacSha256Verifier, MessageFullyReadEvent, MessageLifecycle,
+    SignatureVerifier, UserErasedEvent,
 };
 
 // Deprecated unified publisher (use auto_degradation instead)
@@ -0,0 +1,436 @@
+//! Buffered transport wrapper
+//!
+//! Holds events in a bounded in-process queue while the underlying transport is
+//! unreachable, and flushes them in order once it reports healthy again (or the
+//! NATS client signals a reconnect). An event leaves the queue only once it has
+//! been sent, so new events keep queueing behind one that is being flushed. When
+//! the queue is full the oldest event is dropped and counted in
+//! `fechatter_event_buffer_dropped_total`.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::Duration,
+};
+use tokio::sync::Notify;
+use tracing::{debug, info, warn};
+
+use super::transport::EventTransport;
+use crate::error::EventTransportError;
+
+/// Buffer sizing and flush backoff
+#[derive(Debug, Clone)]
+pub struct BufferConfig {
+    /// Maximum number of events held during an outage
+    pub capacity: usize,
+    /// First flush retry delay after a failed flush
+    pub initial_backoff: Duration,
+    /// Upper bound for the flush retry delay
+    pub max_backoff: Duration,
+}
+
+impl Default for BufferConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct PendingEvent {
+    /// Position in the buffer, assigned when queued
+    seq: u64,
+    subject: String,
+    headers: Option<HashMap<String, String>>,
+    payload: Bytes,
+}
+
+struct BufferState {
+    queue: Mutex<VecDeque<PendingEvent>>,
+    config: BufferConfig,
+    wake: Arc<Notify>,
+    dropped: AtomicU64,
+    next_seq: AtomicU64,
+}
+
+impl BufferState {
+    fn len(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+
+    /// Append an event, evicting the oldest one when full
+    fn push(&self, mut event: PendingEvent) {
+        event.seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let mut queue = self.queue.lock().unwrap();
+        if queue.len() >= self.config.capacity {
+            if let Some(evicted) = queue.pop_front() {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                metrics::counter!("fechatter_event_buffer_dropped_total").increment(1);
+                warn!(
+                    subject = %evicted.subject,
+                    capacity = self.config.capacity,
+                    "Event buffer full, dropped oldest event"
+                );
+            }
+        }
+        queue.push_back(event);
+        metrics::gauge!("fechatter_event_buffer_depth").set(queue.len() as f64);
+        drop(queue);
+
+        self.wake.notify_one();
+    }
+
+    /// Oldest event, left in the queue until it has been sent
+    fn front(&self) -> Option<PendingEvent> {
+        self.queue.lock().unwrap().front().cloned()
+    }
+
+    /// Remove a sent event, unless a full buffer already evicted it while it was in flight
+    fn remove_sent(&self, seq: u64) {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.front().is_some_and(|event| event.seq == seq) {
+            queue.pop_front();
+        }
+        metrics::gauge!("fechatter_event_buffer_depth").set(queue.len() as f64);
+    }
+}
+
+/// Transport wrapper that buffers events through short outages of `inner`
+pub struct BufferedTransport {
+    inner: Arc<dyn EventTransport>,
+    state: Arc<BufferState>,
+}
+
+impl BufferedTransport {
+    pub fn new(inner: Arc<dyn EventTransport>, config: BufferConfig) -> Self {
+        Self::with_wake_signal(inner, config, Arc::new(Notify::new()))
+    }
+
+    /// Use an externally owned signal, e.g. one notified by the NATS reconnect callback
+    pub fn with_wake_signal(
+        inner: Arc<dyn EventTransport>,
+        config: BufferConfig,
+        wake: Arc<Notify>,
+    ) -> Self {
+        let state = Arc::new(BufferState {
+            queue: Mutex::new(VecDeque::new()),
+            config,
+            wake,
+            dropped: AtomicU64::new(0),
+            next_seq: AtomicU64::new(0),
+        });
+
+        tokio::spawn(flush_loop(inner.clone(), Arc::downgrade(&state)));
+
+        Self { inner, state }
+    }
+
+    /// Events waiting for the transport to recover
+    pub fn buffered_len(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Events evicted because the buffer was full
+    pub fn dropped_count(&self) -> u64 {
+        self.state.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Trigger an immediate flush attempt (call on reconnect)
+    pub fn notify_reconnected(&self) {
+        self.state.wake.notify_one();
+    }
+
+    async fn publish_or_buffer(
+        &self,
+        subject: &str,
+        headers: Option<HashMap<String, String>>,
+        payload: Bytes,
+    ) -> Result<(), EventTransportError> {
+        let event = PendingEvent {
+            seq: 0,
+            subject: subject.to_string(),
+            headers,
+            payload,
+        };
+
+        // Keep ordering: while anything is queued or being flushed, new events queue behind it
+        if self.state.len() > 0 || !self.inner.is_healthy().await {
+            debug!(subject = %subject, "Transport unavailable, buffering event");
+            self.state.push(event);
+            return Ok(());
+        }
+
+        match send(self.inner.as_ref(), &event).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.is_retryable() || !self.inner.is_healthy().await => {
+                warn!(subject = %subject, error = %e, "Publish failed during outage, buffering event");
+                self.state.push(event);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+async fn send(
+    transport: &dyn EventTransport,
+    event: &PendingEvent,
+) -> Result<(), EventTransportError> {
+    match &event.headers {
+        Some(headers) => {
+            transport
+                .publish_with_headers(&event.subject, headers.clone(), event.payload.clone())
+                .await
+        }
+        None => {
+            transport
+                .publish(&event.subject, event.payload.clone())
+                .await
+        }
+    }
+}
+
+/// Drain the buffer whenever woken, backing off while the transport stays down
+async fn flush_loop(inner: Arc<dyn EventTransport>, state: Weak<BufferState>) {
+    let mut backoff: Option<Duration> = None;
+
+    loop {
+        let Some(current) = state.upgrade() else {
+            return;
+        };
+        let wake = current.wake.clone();
+        let config = current.config.clone();
+        drop(current);
+
+        match backoff {
+            Some(delay) => {
+                tokio::select! {
+                    _ = wake.notified() => {}
+                    _ = tokio::time::sleep(delay) => {}
+                }
+            }
+            None => wake.notified().await,
+        }
+
+        let Some(current) = state.upgrade() else {
+            return;
+        };
+
+        let mut flushed = 0usize;
+        let mut failed = false;
+        while current.len() > 0 {
+            if !inner.is_healthy().await {
+                failed = true;
+                break;
+            }
+            let Some(event) = current.front() else {
+                break;
+            };
+            if let Err(e) = send(inner.as_ref(), &event).await {
+                warn!(subject = %event.subject, error = %e, "Buffered event flush failed");
+                failed = true;
+                break;
+            }
+            current.remove_sent(event.seq);
+            flushed += 1;
+        }
+
+        if flushed > 0 {
+            info!(
+                flushed,
+                remaining = current.len(),
+                "Flushed buffered events"
+            );
+        }
+
+        backoff = if failed {
+            Some(match backoff {
+                Some(delay) => (delay * 2).min(config.max_backoff),
+                None => config.initial_backoff,
+            })
+        } else {
+            None
+        };
+    }
+}
+
+#[async_trait]
+impl EventTransport for BufferedTransport {
+    async fn publish(&self, subject: &str, payload: Bytes) -> Result<(), EventTransportError> {
+        self.publish_or_buffer(subject, None, payload).await
+    }
+
+    async fn publish_with_headers(
+        &self,
+        subject: &str,
+        headers: HashMap<String, String>,
+        payload: Bytes,
+    ) -> Result<(), EventTransportError> {
+        self.publish_or_buffer(subject, Some(headers), payload)
+            .await
+    }
+
+    fn transport_type(&self) -> &'static str {
+        self.inner.transport_type()
+    }
+
+    async fn is_healthy(&self) -> bool {
+        self.inner.is_healthy().await
+    }
+
+    /// Delegates to the wrapped transport so `NatsTransport` downcasts keep working
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::infrastructure::event::shared::InMemoryTransport;
+
+    fn buffered(inner: &InMemoryTransport, capacity: usize) -> BufferedTransport {
+        BufferedTransport::new(
+            Arc::new(inner.clone()),
+            BufferConfig {
+                capacity,
+                initial_backoff: Duration::from_millis(10),
+                max_backoff: Duration::from_millis(50),
+            },
+        )
+    }
+
+    async fn wait_for_delivery(inner: &InMemoryTransport, expected: usize) -> Vec<String> {
+        for _ in 0..100 {
+            let messages = inner.get_messages().await;
+            if messages.len() >= expected {
+                return messages
+                    .into_iter()
+                    .map(|(subject, _, _)| subject)
+                    .collect();
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("buffered events were not delivered");
+    }
+
+    #[tokio::test]
+    async fn events_during_outage_should_be_delivered_after_reconnect() {
+        let inner = InMemoryTransport::new();
+        let transport = buffered(&inner, 100);
+
+        transport.publish("before", Bytes::new()).await.unwrap();
+
+        inner.set_connected(false);
+        transport.publish("outage.1", Bytes::new()).await.unwrap();
+        transport
+            .publish_with_headers("outage.2", HashMap::new(), Bytes::new())
+            .await
+            .unwrap();
+        assert_eq!(transport.buffered_len(), 2);
+
+        inner.set_connected(true);
+        transport.notify_reconnected();
+
+        let subjects = wait_for_delivery(&inner, 3).await;
+        assert_eq!(subjects, vec!["before", "outage.1", "outage.2"]);
+        assert_eq!(transport.buffered_len(), 0);
+        assert_eq!(transport.dropped_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn full_buffer_should_drop_oldest() {
+        let inner = InMemoryTransport::new();
+        let transport = buffered(&inner, 2);
+
+        inner.set_connected(false);
+        for subject in ["e1", "e2", "e3"] {
+            transport.publish(subject, Bytes::new()).await.unwrap();
+        }
+        assert_eq!(transport.buffered_len(), 2);
+        assert_eq!(transport.dropped_count(), 1);
+
+        // Let a flush attempt fail so recovery is driven by the backoff timer alone
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        inner.set_connected(true);
+
+        let subjects = wait_for_delivery(&inner, 2).await;
+        assert_eq!(subjects, vec!["e2", "e3"]);
+    }
+
+    /// Transport whose publishes of `held` wait until the gate is opened
+    struct GatedTransport {
+        inner: InMemoryTransport,
+        held: &'static str,
+        gate: Arc<tokio::sync::Semaphore>,
+    }
+
+    #[async_trait]
+    impl EventTransport for GatedTransport {
+        async fn publish(&self, subject: &str, payload: Bytes) -> Result<(), EventTransportError> {
+            if subject == self.held {
+                let _permit = self.gate.acquire().await;
+            }
+            self.inner.publish(subject, payload).await
+        }
+
+        async fn publish_with_headers(
+            &self,
+            subject: &str,
+            headers: HashMap<String, String>,
+            payload: Bytes,
+        ) -> Result<(), EventTransportError> {
+            self.inner
+                .publish_with_headers(subject, headers, payload)
+                .await
+        }
+
+        fn transport_type(&self) -> &'static str {
+            "gated"
+        }
+
+        async fn is_healthy(&self) -> bool {
+            self.inner.is_healthy().await
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn new_event_should_not_overtake_one_being_flushed() {
+        let inner = InMemoryTransport::new();
+        let gate = Arc::new(tokio::sync::Semaphore::new(0));
+        let transport = BufferedTransport::new(
+            Arc::new(GatedTransport {
+                inner: inner.clone(),
+                held: "outage",
+                gate: gate.clone(),
+            }),
+            BufferConfig::default(),
+        );
+
+        inner.set_connected(false);
+        transport.publish("outage", Bytes::new()).await.unwrap();
+        inner.set_connected(true);
+        transport.notify_reconnected();
+
+        // The flush is now stuck sending "outage"
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        transport.publish("live", Bytes::new()).await.unwrap();
+        assert_eq!(transport.buffered_len(), 2);
+
+        gate.add_permits(1);
+        let subjects = wait_for_delivery(&inner, 2).await;
+        assert_eq!(subjects, vec!["outage", "live"]);
+    }
+}
@@ -3,9 +3,15 @@
 // This module contains components shared between legacy and high-performance
 // event publishing systems, including transport abstractions and utilities.
 
+pub mod buffered;
+#[cfg(any(test, feature = "test-util"))]
+pub mod recording;
 pub mod transport;
 pub mod unified_publisher;
 
+pub use buffered::{BufferConfig, BufferedTransport};
+#[cfg(any(test, feature = "test-util"))]
+pub use recording::RecordingEventPublisher;
 // Re-export transport components
 pub use transport::{
     EventTransport, InMemoryTransport, KafkaConfig, KafkaSecurityConfig, KafkaTransport,
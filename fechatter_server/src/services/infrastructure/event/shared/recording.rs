@@ -0,0 +1,176 @@
+//! Recording event publisher for tests
+//!
+//! A `DynEventPublisher` over an `InMemoryTransport`, plus typed accessors for what was
+//! published. Services publish from spawned tasks, so assertions wait briefly for events
+//! to arrive before counting them.
+
+use serde::de::DeserializeOwned;
+use std::{sync::Arc, time::Duration};
+
+use super::transport::{EventTransport, InMemoryTransport};
+use crate::services::infrastructure::event::DynEventPublisher;
+use fechatter_core::contracts::events::{
+    subjects, ChatEvent, ChatLifecycle, MessageEvent, MessageLifecycle,
+};
+
+/// How long `expect_published` waits for the expected events
+const PUBLISH_WAIT: Duration = Duration::from_secs(2);
+/// Extra time allowed for unexpected surplus events before counting
+const SETTLE: Duration = Duration::from_millis(50);
+
+#[derive(Clone)]
+pub struct RecordingEventPublisher {
+    transport: InMemoryTransport,
+    publisher: Arc<DynEventPublisher>,
+}
+
+impl RecordingEventPublisher {
+    pub fn new() -> Self {
+        let transport = InMemoryTransport::new();
+        let shared: Arc<dyn EventTransport> = Arc::new(transport.clone());
+        Self {
+            transport,
+            publisher: Arc::new(DynEventPublisher::with_dyn_transport(shared)),
+        }
+    }
+
+    /// The publisher to hand to the code under test
+    pub fn publisher(&self) -> Arc<DynEventPublisher> {
+        self.publisher.clone()
+    }
+
+    /// Events published to `subject` so far, oldest first
+    pub async fn published<E: DeserializeOwned>(&self, subject: &str) -> Vec<E> {
+        self.transport
+            .get_messages()
+            .await
+            .into_iter()
+            .filter(|(published_subject, _, _)| published_subject == subject)
+            .map(|(_, payload, _)| {
+                serde_json::from_slice(&payload).unwrap_or_else(|e| {
+                    panic!("event published to {} did not decode: {}", subject, e)
+                })
+            })
+            .collect()
+    }
+
+    /// Waits for `count` events on `subject` and panics unless exactly that many arrive
+    pub async fn expect_published<E: DeserializeOwned>(
+        &self,
+        subject: &str,
+        count: usize,
+    ) -> Vec<E> {
+        let deadline = tokio::time::Instant::now() + PUBLISH_WAIT;
+        while self.count(subject).await < count && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(SETTLE).await;
+
+        let events = self.published(subject).await;
+        assert_eq!(
+            events.len(),
+            count,
+            "expected {} event(s) on {}, got {}",
+            count,
+            subject,
+            events.len()
+        );
+        events
+    }
+
+    /// Waits for the single message lifecycle event of `kind`
+    pub async fn expect_message_event(&self, kind: MessageLifecycle) -> MessageEvent {
+        let subject = match kind {
+            MessageLifecycle::Created => subjects::MESSAGE_CREATED,
+            MessageLifecycle::Updated => subjects::MESSAGE_UPDATED,
+            MessageLifecycle::Deleted => subjects::MESSAGE_DELETED,
+        };
+        self.expect_published::<MessageEvent>(subject, 1)
+            .await
+            .remove(0)
+    }
+
+    /// Waits for the single chat lifecycle event of `kind`
+    pub async fn expect_chat_event(&self, kind: ChatLifecycle) -> ChatEvent {
+        let subject = match kind {
+            ChatLifecycle::Created => subjects::CHAT_CREATED,
+            ChatLifecycle::Updated => subjects::CHAT_UPDATED,
+            ChatLifecycle::Deleted => subjects::CHAT_DELETED,
+            ChatLifecycle::OwnershipTransferred => subjects::CHAT_OWNERSHIP_TRANSFERRED,
+        };
+        self.expect_published::<ChatEvent>(subject, 1)
+            .await
+            .remove(0)
+    }
+
+    /// Forget everything recorded so far
+    pub async fn clear(&self) {
+        self.transport.clear().await;
+    }
+
+    async fn count(&self, subject: &str) -> usize {
+        self.transport
+            .get_messages()
+            .await
+            .iter()
+            .filter(|(published_subject, _, _)| published_subject == subject)
+            .count()
+    }
+}
+
+impl Default for RecordingEventPublisher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use fechatter_core::{ChatId, Message, MessageId, UserId};
+
+    fn message() -> Message {
+        Message {
+            id: MessageId(7),
+            chat_id: ChatId(3),
+            sender_id: UserId(1),
+            content: "hello".to_string(),
+            files: None,
+            created_at: Utc::now(),
+            idempotency_key: None,
+            client_message_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn published_events_should_decode_by_subject() {
+        let recorder = RecordingEventPublisher::new();
+        let members = [UserId(1), UserId(2)];
+        recorder
+            .publisher()
+            .publish_message_event(MessageLifecycle::Created, &message(), &members)
+            .await
+            .unwrap();
+        recorder
+            .publisher()
+            .publish_message_event(MessageLifecycle::Deleted, &message(), &members)
+            .await
+            .unwrap();
+
+        let created = recorder
+            .expect_message_event(MessageLifecycle::Created)
+            .await;
+        assert_eq!(created.msg.id, MessageId(7));
+        assert_eq!(created.members, members.to_vec());
+        assert!(recorder
+            .published::<MessageEvent>(subjects::MESSAGE_UPDATED)
+            .await
+            .is_empty());
+
+        recorder.clear().await;
+        recorder
+            .expect_published::<MessageEvent>(subjects::MESSAGE_DELETED, 0)
+            .await;
+    }
+}
@@ -10,6 +10,7 @@ use tokio::sync::RwLock;
 use tracing::{instrument, warn};
 
 // Import centralized error types from error module
+use super::buffered::{BufferConfig, BufferedTransport};
 use crate::error::EventTransportError;
 
 /// Production-ready trait for event transport implementations
@@ -487,14 +488,33 @@ impl TransportFactory {
     }
 
     /// Create NATS transport with connection URL
+    ///
+    /// Publishes are buffered through short outages and flushed when the client reconnects.
     pub async fn create_nats_transport(
         url: &str,
     ) -> Result<Arc<dyn EventTransport>, EventTransportError> {
-        let client = async_nats::connect(url).await.map_err(|e| {
-            EventTransportError::Connection(format!("Failed to connect to NATS: {}", e))
-        })?;
-
-        Ok(Arc::new(NatsTransport::new(client)))
+        let reconnected = Arc::new(tokio::sync::Notify::new());
+        let signal = reconnected.clone();
+        let client = async_nats::ConnectOptions::new()
+            .event_callback(move |event| {
+                let signal = signal.clone();
+                async move {
+                    if matches!(event, async_nats::Event::Connected) {
+                        signal.notify_one();
+                    }
+                }
+            })
+            .connect(url)
+            .await
+            .map_err(|e| {
+                EventTransportError::Connection(format!("Failed to connect to NATS: {}", e))
+            })?;
+
+        Ok(Arc::new(BufferedTransport::with_wake_signal(
+            Arc::new(NatsTransport::new(client)),
+            BufferConfig::default(),
+            reconnected,
+        )))
     }
 
     /// Create NATS transport with existing client
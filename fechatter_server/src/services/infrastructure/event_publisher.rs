@@ -443,6 +443,7 @@ mod tests {
             files: Some(vec!["file1.txt".to_string(), "file2.jpg".to_string()]),
             created_at: Utc::now(),
             idempotency_key: Some(Uuid::new_v4()),
+            client_message_id: None,
         }
     }
 
@@ -0,0 +1,518 @@
+//! # Attachment Scanning
+//!
+//! **Responsibility**: Run uploaded files through a pluggable `FileScanner` and keep them
+//! quarantined, i.e. not downloadable, until their scan is clean
+//! **Flow**: An upload records a `pending` scan and starts it in the background; the upload
+//! response waits at most `response_timeout_ms` for the verdict. Every verdict is published on
+//! `fechatter.file.scanned`; infected files and failed scans stay quarantined
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::{info, warn};
+
+use crate::config::{FileScanConfig, FileScannerKind};
+use crate::services::infrastructure::event::DynEventPublisher;
+use crate::AppError;
+use fechatter_core::contracts::events::{EventVersion, FileScanStatus, FileScannedEvent};
+use fechatter_core::{UserId, WorkspaceId};
+
+/// Bytes per clamd INSTREAM chunk
+const CLAMAV_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Result of scanning one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    /// Name of the signature or rule that matched
+    Infected(String),
+}
+
+/// Attachment scanning backend
+#[async_trait]
+pub trait FileScanner: Send + Sync {
+    /// Name recorded with each verdict
+    fn name(&self) -> &'static str;
+
+    async fn scan(&self, file_id: &str, data: &[u8]) -> Result<ScanVerdict, AppError>;
+}
+
+/// Passes every file
+pub struct NoopScanner;
+
+#[async_trait]
+impl FileScanner for NoopScanner {
+    fn name(&self) -> &'static str {
+        "noop"
+    }
+
+    async fn scan(&self, _file_id: &str, _data: &[u8]) -> Result<ScanVerdict, AppError> {
+        Ok(ScanVerdict::Clean)
+    }
+}
+
+/// ClamAV daemon over TCP, streaming the file with `INSTREAM`
+pub struct ClamAvScanner {
+    address: String,
+}
+
+impl ClamAvScanner {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+        }
+    }
+
+    /// Verdict of a clamd reply such as `stream: OK` or `stream: Eicar-Signature FOUND`
+    fn parse_reply(reply: &str) -> Result<ScanVerdict, AppError> {
+        let reply = reply.trim_end_matches('\0').trim();
+        let result = reply.strip_prefix("stream:").unwrap_or(reply).trim();
+        if result == "OK" {
+            return Ok(ScanVerdict::Clean);
+        }
+        if let Some(signature) = result.strip_suffix("FOUND") {
+            return Ok(ScanVerdict::Infected(signature.trim().to_string()));
+        }
+        Err(AppError::ExternalServiceError(format!("clamd: {}", reply)))
+    }
+}
+
+#[async_trait]
+impl FileScanner for ClamAvScanner {
+    fn name(&self) -> &'static str {
+        "clamav"
+    }
+
+    async fn scan(&self, _file_id: &str, data: &[u8]) -> Result<ScanVerdict, AppError> {
+        let clamd_error = |e: std::io::Error| {
+            AppError::ExternalServiceError(format!("clamd {}: {}", self.address, e))
+        };
+
+        let mut stream = TcpStream::connect(&self.address)
+            .await
+            .map_err(clamd_error)?;
+        stream
+            .write_all(b"zINSTREAM\0")
+            .await
+            .map_err(clamd_error)?;
+        for chunk in data.chunks(CLAMAV_CHUNK_BYTES) {
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await
+                .map_err(clamd_error)?;
+            stream.write_all(chunk).await.map_err(clamd_error)?;
+        }
+        // A zero-length chunk ends the stream
+        stream
+            .write_all(&0u32.to_be_bytes())
+            .await
+            .map_err(clamd_error)?;
+
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).await.map_err(clamd_error)?;
+        Self::parse_reply(&String::from_utf8_lossy(&reply))
+    }
+}
+
+/// Scanning service over HTTP: the file is POSTed as the request body and the service answers
+/// `{"clean": bool, "detail": "..."}`
+pub struct HttpScanner {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpScanReply {
+    clean: bool,
+    #[serde(default)]
+    detail: Option<String>,
+}
+
+impl HttpScanner {
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Result<Self, AppError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| AppError::Configuration(format!("file scanner client: {}", e)))?;
+        Ok(Self {
+            client,
+            url: url.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl FileScanner for HttpScanner {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn scan(&self, file_id: &str, data: &[u8]) -> Result<ScanVerdict, AppError> {
+        let scanner_error =
+            |e: reqwest::Error| AppError::ExternalServiceError(format!("file scanner: {}", e));
+
+        let reply: HttpScanReply = self
+            .client
+            .post(&self.url)
+            .header("X-File-Id", file_id)
+            .body(data.to_vec())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(scanner_error)?
+            .json()
+            .await
+            .map_err(scanner_error)?;
+
+        Ok(if reply.clean {
+            ScanVerdict::Clean
+        } else {
+            ScanVerdict::Infected(reply.detail.unwrap_or_else(|| "flagged".to_string()))
+        })
+    }
+}
+
+/// Scans uploads and answers whether a stored file may be downloaded
+pub struct FileScanService {
+    scanner: Arc<dyn FileScanner>,
+    pool: Arc<PgPool>,
+    event_publisher: Option<Arc<DynEventPublisher>>,
+    scan_timeout: Duration,
+    response_timeout: Duration,
+}
+
+impl FileScanService {
+    pub fn new(
+        scanner: Arc<dyn FileScanner>,
+        pool: Arc<PgPool>,
+        event_publisher: Option<Arc<DynEventPublisher>>,
+        config: &FileScanConfig,
+    ) -> Self {
+        Self {
+            scanner,
+            pool,
+            event_publisher,
+            scan_timeout: Duration::from_millis(config.scan_timeout_ms),
+            response_timeout: Duration::from_millis(config.response_timeout_ms),
+        }
+    }
+
+    /// Service with the configured scanner
+    pub fn from_config(
+        config: &FileScanConfig,
+        pool: Arc<PgPool>,
+        event_publisher: Option<Arc<DynEventPublisher>>,
+    ) -> Result<Self, AppError> {
+        let scanner: Arc<dyn FileScanner> = match config.scanner {
+            FileScannerKind::Noop => Arc::new(NoopScanner),
+            FileScannerKind::Clamav => Arc::new(ClamAvScanner::new(config.clamav_address.clone())),
+            FileScannerKind::Http => {
+                let url = config.http_url.clone().ok_or_else(|| {
+                    AppError::Configuration(
+                        "features.file_scanning.http_url is required by the http scanner"
+                            .to_string(),
+                    )
+                })?;
+                Arc::new(HttpScanner::new(
+                    url,
+                    Duration::from_millis(config.scan_timeout_ms),
+                )?)
+            }
+        };
+        Ok(Self::new(scanner, pool, event_publisher, config))
+    }
+
+    /// Quarantine a freshly stored file and scan it in the background, waiting at most
+    /// `response_timeout` for the verdict; returns the file's status at that point
+    pub async fn submit(
+        self: &Arc<Self>,
+        file_id: &str,
+        data: Vec<u8>,
+        uploaded_by: UserId,
+        workspace_id: WorkspaceId,
+    ) -> Result<FileScanStatus, AppError> {
+        // The id is the content hash, so a decided verdict holds for re-uploads
+        if let Some(status @ (FileScanStatus::Clean | FileScanStatus::Infected)) =
+            self.status(file_id).await?
+        {
+            return Ok(status);
+        }
+
+        sqlx::query(
+            r#"INSERT INTO file_scans (file_id, status, scanner, uploaded_by, workspace_id)
+         VALUES ($1, 'pending', $2, $3, $4)
+         ON CONFLICT (file_id) DO UPDATE
+         SET status = 'pending', detail = NULL, scanner = EXCLUDED.scanner, scanned_at = NULL"#,
+        )
+        .bind(file_id)
+        .bind(self.scanner.name())
+        .bind(i64::from(uploaded_by))
+        .bind(i64::from(workspace_id))
+        .execute(&*self.pool)
+        .await?;
+
+        let service = Arc::clone(self);
+        let scanned_id = file_id.to_string();
+        let scan = tokio::spawn(async move {
+            service
+                .scan(&scanned_id, &data, uploaded_by, workspace_id)
+                .await
+        });
+        match tokio::time::timeout(self.response_timeout, scan).await {
+            Ok(Ok(status)) => Ok(status),
+            Ok(Err(e)) => {
+                warn!("Scan task of {} failed: {}", file_id, e);
+                Ok(FileScanStatus::Pending)
+            }
+            // Still scanning; the verdict is recorded and published when it lands
+            Err(_) => Ok(FileScanStatus::Pending),
+        }
+    }
+
+    /// Run the scanner, then record and publish its verdict
+    async fn scan(
+        &self,
+        file_id: &str,
+        data: &[u8],
+        uploaded_by: UserId,
+        workspace_id: WorkspaceId,
+    ) -> FileScanStatus {
+        let (status, detail) =
+            match tokio::time::timeout(self.scan_timeout, self.scanner.scan(file_id, data)).await {
+                Ok(Ok(ScanVerdict::Clean)) => (FileScanStatus::Clean, None),
+                Ok(Ok(ScanVerdict::Infected(signature))) => {
+                    (FileScanStatus::Infected, Some(signature))
+                }
+                Ok(Err(e)) => (FileScanStatus::Failed, Some(e.to_string())),
+                Err(_) => (
+                    FileScanStatus::Failed,
+                    Some(format!("scan timed out after {:?}", self.scan_timeout)),
+                ),
+            };
+        if status == FileScanStatus::Clean {
+            info!("File {} scanned clean by {}", file_id, self.scanner.name());
+        } else {
+            warn!(
+                "File {} quarantined: scan {} ({})",
+                file_id,
+                status.as_str(),
+                detail.as_deref().unwrap_or("")
+            );
+        }
+
+        if let Err(e) = sqlx::query(
+            "UPDATE file_scans SET status = $2, detail = $3, scanned_at = NOW() WHERE file_id = $1",
+        )
+        .bind(file_id)
+        .bind(status.as_str())
+        .bind(&detail)
+        .execute(&*self.pool)
+        .await
+        {
+            warn!("Failed to record scan verdict of {}: {}", file_id, e);
+        }
+
+        if let Some(publisher) = &self.event_publisher {
+            let event = FileScannedEvent {
+                version: EventVersion::default(),
+                file_id: file_id.to_string(),
+                status,
+                detail,
+                scanner: self.scanner.name().to_string(),
+                uploaded_by,
+                workspace_id,
+                occurred_at: chrono::Utc::now(),
+                sig: None,
+            };
+            if let Err(e) = publisher.publish_file_scanned(event).await {
+                warn!("Failed to publish scan verdict of {}: {}", file_id, e);
+            }
+        }
+
+        status
+    }
+
+    /// Scan status of a stored file; `None` when it was uploaded without scanning
+    pub async fn status(&self, file_id: &str) -> Result<Option<FileScanStatus>, AppError> {
+        let status: Option<String> =
+            sqlx::query_scalar("SELECT status FROM file_scans WHERE file_id = $1")
+                .bind(file_id)
+                .fetch_optional(&*self.pool)
+                .await?;
+        Ok(status.as_deref().and_then(FileScanStatus::parse))
+    }
+
+    /// Refuse files whose scan is pending, infected or failed
+    pub async fn ensure_downloadable(&self, file_id: &str) -> Result<(), AppError> {
+        match self.status(file_id).await? {
+            None | Some(FileScanStatus::Clean) => Ok(()),
+            Some(status) => Err(AppError::Forbidden(format!(
+                "File {} is quarantined (scan {})",
+                file_id,
+                status.as_str()
+            ))),
+        }
+    }
+}
+
+/// Keep quarantined files off the static `/files` service
+pub async fn quarantine_guard(
+    State(scans): State<Arc<FileScanService>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(file_id) = file_id_from_static_path(request.uri().path()) {
+        if let Err(e) = scans.ensure_downloadable(&file_id).await {
+            return e.into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Storage id of a path under the storage root: both the `hash.ext` links and the nested
+/// `abc/def/rest.ext` files map back to `hash.ext`
+fn file_id_from_static_path(path: &str) -> Option<String> {
+    let file_id: String = path.split('/').collect();
+    (!file_id.is_empty()).then_some(file_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::infrastructure::event::subjects;
+    use axum::{body::Body, http::StatusCode, Router};
+    use tower::ServiceExt;
+
+    /// Flags every file with a fixed signature
+    struct FlaggingScanner;
+
+    #[async_trait]
+    impl FileScanner for FlaggingScanner {
+        fn name(&self) -> &'static str {
+            "stub"
+        }
+
+        async fn scan(&self, _file_id: &str, _data: &[u8]) -> Result<ScanVerdict, AppError> {
+            Ok(ScanVerdict::Infected("Eicar-Test-Signature".to_string()))
+        }
+    }
+
+    fn random_file_id() -> String {
+        format!("{}.txt", uuid::Uuid::new_v4().simple())
+    }
+
+    /// Status of a GET for `path` through the guard, in front of a stand-in file service
+    async fn static_status(scans: Arc<FileScanService>, path: &str) -> StatusCode {
+        let files = Router::new().fallback(|| async { "file contents" }).layer(
+            axum::middleware::from_fn_with_state(scans, quarantine_guard),
+        );
+        files
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[test]
+    fn clamd_replies_should_map_to_verdicts() {
+        assert_eq!(
+            ClamAvScanner::parse_reply("stream: OK\0").unwrap(),
+            ScanVerdict::Clean
+        );
+        assert_eq!(
+            ClamAvScanner::parse_reply("stream: Eicar-Test-Signature FOUND\0").unwrap(),
+            ScanVerdict::Infected("Eicar-Test-Signature".to_string())
+        );
+        assert!(ClamAvScanner::parse_reply("INSTREAM size limit exceeded. ERROR\0").is_err());
+    }
+
+    #[tokio::test]
+    async fn noop_scanner_should_pass_files() -> anyhow::Result<()> {
+        let (state, users, events) = crate::setup_test_users_with_events!(1).await;
+        let scans = Arc::new(FileScanService::new(
+            Arc::new(NoopScanner),
+            state.pool(),
+            Some(events.publisher()),
+            &FileScanConfig::default(),
+        ));
+        let file_id = random_file_id();
+
+        let status = scans
+            .submit(
+                &file_id,
+                b"hello".to_vec(),
+                users[0].id,
+                users[0].workspace_id,
+            )
+            .await?;
+        assert_eq!(status, FileScanStatus::Clean);
+        scans.ensure_downloadable(&file_id).await?;
+        assert_eq!(
+            static_status(scans.clone(), &format!("/{}", file_id)).await,
+            StatusCode::OK
+        );
+
+        let verdict = events
+            .expect_published::<FileScannedEvent>(subjects::FILE_SCANNED, 1)
+            .await
+            .remove(0);
+        assert_eq!(verdict.file_id, file_id);
+        assert_eq!(verdict.status, FileScanStatus::Clean);
+        assert_eq!(verdict.uploaded_by, users[0].id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn flagged_file_should_be_quarantined() -> anyhow::Result<()> {
+        let (state, users, events) = crate::setup_test_users_with_events!(1).await;
+        let scans = Arc::new(FileScanService::new(
+            Arc::new(FlaggingScanner),
+            state.pool(),
+            Some(events.publisher()),
+            &FileScanConfig::default(),
+        ));
+        let file_id = random_file_id();
+
+        let status = scans
+            .submit(
+                &file_id,
+                b"X5O!P%@AP".to_vec(),
+                users[0].id,
+                users[0].workspace_id,
+            )
+            .await?;
+        assert_eq!(status, FileScanStatus::Infected);
+        assert!(matches!(
+            scans.ensure_downloadable(&file_id).await,
+            Err(AppError::Forbidden(_))
+        ));
+        // Neither the link nor the nested file is served
+        let nested = format!("/{}/{}/{}", &file_id[..3], &file_id[3..6], &file_id[6..]);
+        for path in [format!("/{}", file_id), nested] {
+            assert_eq!(
+                static_status(scans.clone(), &path).await,
+                StatusCode::FORBIDDEN,
+                "{}",
+                path
+            );
+        }
+
+        let verdict = events
+            .expect_published::<FileScannedEvent>(subjects::FILE_SCANNED, 1)
+            .await
+            .remove(0);
+        assert_eq!(verdict.status, FileScanStatus::Infected);
+        assert_eq!(verdict.detail.as_deref(), Some("Eicar-Test-Signature"));
+        assert_eq!(verdict.scanner, "stub");
+        Ok(())
+    }
+}
@@ -14,9 +14,11 @@
 
 use crate::error::AppError;
 use crate::services::infrastructure::event::{
-    EventTransport, LegacyEventPublisher as EventPublisher, Signable,
+    ChatLifecycle, DynEventPublisher, EventTransport, LegacyEventPublisher as EventPublisher,
+    Signable,
 };
 use fechatter_core::models::message::MessageCreatedEvent;
+use fechatter_core::{Chat, UserId};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::info;
@@ -654,11 +656,44 @@ pub enum CacheInvalidationEvent {
 /// Simplified event publisher (backward compatible)
 pub struct SimplifiedEventPublisher {
     cache_service: Arc<crate::services::application::CacheStrategyService>,
+    event_publisher: Option<Arc<DynEventPublisher>>,
 }
 
 impl SimplifiedEventPublisher {
     pub fn new(cache_service: Arc<crate::services::application::CacheStrategyService>) -> Self {
-        Self { cache_service }
+        Self {
+            cache_service,
+            event_publisher: None,
+        }
+    }
+
+    /// Publish chat events through `event_publisher`; without one they only invalidate caches
+    pub fn with_event_publisher(mut self, event_publisher: Arc<DynEventPublisher>) -> Self {
+        self.event_publisher = Some(event_publisher);
+        self
+    }
+
+    /// Invalidate the cached chat and the members' chat lists, then publish the chat event
+    pub async fn publish_chat_event(
+        &self,
+        kind: ChatLifecycle,
+        chat: &Chat,
+        actor_id: i64,
+        members: &[i64],
+    ) -> Result<(), AppError> {
+        self.publish_cache_invalidation(CacheInvalidationEvent::ChatUpdated {
+            chat_id: i64::from(chat.id),
+            user_ids: members.to_vec(),
+        })
+        .await?;
+
+        let Some(event_publisher) = &self.event_publisher else {
+            return Ok(());
+        };
+        let members: Vec<UserId> = members.iter().map(|&id| UserId(id)).collect();
+        event_publisher
+            .publish_chat_event(kind, chat, UserId(actor_id), &members)
+            .await
     }
 
     /// Publish cache invalidation event
@@ -30,9 +30,6 @@ pub mod notifications;
 /// Events module - Legacy compatibility
 pub mod events;
 
-/// Typing indicator module - Real-time typing status
-pub mod typing_indicator;
-
 // ── Unified Exports (Based on EventTransport Abstraction) ────────────────────────────────────
 
 // Real-time stream exports
@@ -86,9 +83,6 @@ pub use notifications::{
 // Re-export notification types directly from domain (public access)
 pub use crate::domains::notification::{NotificationPriority, NotificationType};
 
-// Typing indicator exports
-pub use typing_indicator::{create_typing_indicator_service, TypingIndicatorService, TypingUser};
-
 // Events module exports (legacy compatibility)
 pub use events::{
     create_domain_event_service as events_create_domain_event_service,
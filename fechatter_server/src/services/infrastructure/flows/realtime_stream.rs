@@ -260,14 +260,7 @@ impl<T: EventTransport + 'static> RealtimeStreamService<T> {
 
         // 4. Non-blocking realtime push
         tokio::spawn(async move {
-            let stream_message = StreamMessage {
-                id: message_for_push.id.to_string(),
-                chat_id: i64::from(message_for_push.chat_id),
-                sender_id: i64::from(message_for_push.sender_id),
-                content: message_for_push.content.clone(),
-                files: message_for_push.files.clone().unwrap_or_default(),
-                timestamp: message_for_push.created_at.timestamp(),
-            };
+            let stream_message = StreamMessage::from(&message_for_push);
 
             let realtime_event = RealtimeStreamEvent::MessageReceived {
                 message: stream_message,
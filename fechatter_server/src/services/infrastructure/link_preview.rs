@@ -0,0 +1,365 @@
+//! # Link Previews
+//!
+//! **Responsibility**: Fetch Open Graph / Twitter card metadata for URLs posted in messages
+//! **Safety**: URLs go through the outbound SSRF guard (`super::outbound`), so internal hosts are
+//! refused unless allowlisted, and only the first `max_body_bytes` of a page are read
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::Url;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use super::cache::{CacheKeyBuilder, RedisCacheService};
+use super::outbound::guarded_client_builder;
+use crate::config::LinkPreviewConfig;
+use fechatter_core::contracts::events::LinkPreview;
+use fechatter_core::utils::{check_outbound_url, OutboundAllowlist, OutboundUrlError};
+
+/// Redirects followed per fetch
+const MAX_REDIRECTS: usize = 3;
+/// Characters kept of a title or description
+const MAX_TEXT_LEN: usize = 300;
+
+static URL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"https?://[^\s<>"'`]+"#).expect("valid URL pattern"));
+static META_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<meta\b[^>]*>").expect("valid meta tag pattern"));
+static META_ATTRIBUTE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)([a-z][a-z:_-]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+        .expect("valid attribute pattern")
+});
+static TITLE_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("valid title pattern"));
+
+#[derive(Debug, Error)]
+pub enum LinkPreviewError {
+    #[error(transparent)]
+    Outbound(#[from] OutboundUrlError),
+
+    #[error("request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("page responded with status {0}")]
+    Status(u16),
+}
+
+/// Distinct http(s) URLs in `content`, in order of appearance, at most `max`
+pub fn extract_urls(content: &str, max: usize) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for found in URL_PATTERN.find_iter(content) {
+        if urls.len() >= max {
+            break;
+        }
+        // Sentence punctuation right after a link is not part of it
+        let url = found
+            .as_str()
+            .trim_end_matches(|c| matches!(c, '.' | ',' | ';' | ':' | '!' | '?' | ')' | ']' | '}'));
+        if Url::parse(url).is_ok() && !urls.iter().any(|seen| seen == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn clean_text(text: &str) -> Option<String> {
+    let text = decode_entities(text.trim());
+    if text.is_empty() {
+        return None;
+    }
+    Some(text.chars().take(MAX_TEXT_LEN).collect())
+}
+
+/// Preview from a page's `<meta>` tags, falling back to `<title>`; `None` without metadata
+fn parse_preview(url: &str, base: &Url, html: &str) -> Option<LinkPreview> {
+    let mut meta: HashMap<String, String> = HashMap::new();
+    for tag in META_TAG.find_iter(html) {
+        let mut key = None;
+        let mut content = None;
+        for attribute in META_ATTRIBUTE.captures_iter(tag.as_str()) {
+            let value = attribute
+                .get(2)
+                .or_else(|| attribute.get(3))
+                .map_or("", |value| value.as_str());
+            match attribute[1].to_ascii_lowercase().as_str() {
+                "property" | "name" => key = Some(value.to_ascii_lowercase()),
+                "content" => content = clean_text(value),
+                _ => {}
+            }
+        }
+        if let (Some(key), Some(content)) = (key, content) {
+            meta.entry(key).or_insert(content);
+        }
+    }
+
+    let first = |keys: &[&str]| keys.iter().find_map(|key| meta.get(*key).cloned());
+    let title = first(&["og:title", "twitter:title"]).or_else(|| {
+        TITLE_TAG
+            .captures(html)
+            .and_then(|title| clean_text(&title[1]))
+    });
+    let description = first(&["og:description", "twitter:description", "description"]);
+    let image = first(&["og:image", "og:image:url", "twitter:image"])
+        .and_then(|image| base.join(&image).ok())
+        .filter(|image| matches!(image.scheme(), "http" | "https"))
+        .map(|image| image.to_string());
+    let site_name = first(&["og:site_name"]);
+
+    if title.is_none() && description.is_none() && image.is_none() {
+        return None;
+    }
+    Some(LinkPreview {
+        url: url.to_string(),
+        title,
+        description,
+        image,
+        site_name,
+    })
+}
+
+/// Fetches and caches link previews
+#[derive(Clone)]
+pub struct LinkPreviewService {
+    client: reqwest::Client,
+    cache: Option<Arc<RedisCacheService>>,
+    max_urls: usize,
+    max_body_bytes: usize,
+    cache_ttl_secs: u64,
+    allowlist: OutboundAllowlist,
+}
+
+impl LinkPreviewService {
+    pub fn new(
+        config: &LinkPreviewConfig,
+        allowlist: OutboundAllowlist,
+        cache: Option<Arc<RedisCacheService>>,
+    ) -> Result<Self, LinkPreviewError> {
+        let client = guarded_client_builder(allowlist.clone(), MAX_REDIRECTS)
+            .timeout(Duration::from_millis(config.timeout_ms.max(1)))
+            .user_agent("FechatterBot/1.0 (link preview)")
+            .build()?;
+
+        Ok(Self {
+            client,
+            cache,
+            max_urls: config.max_urls_per_message,
+            max_body_bytes: config.max_body_bytes,
+            cache_ttl_secs: config.cache_ttl_secs,
+            allowlist,
+        })
+    }
+
+    /// Previews of the URLs in `content`; URLs that fail or have no metadata are skipped
+    pub async fn unfurl(&self, content: &str) -> Vec<LinkPreview> {
+        let urls = extract_urls(content, self.max_urls);
+        let results = futures::future::join_all(urls.iter().map(|url| self.preview(url))).await;
+
+        urls.iter()
+            .zip(results)
+            .filter_map(|(url, result)| match result {
+                Ok(preview) => preview,
+                Err(e) => {
+                    debug!("No link preview for {}: {}", url, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Preview of one URL. Both previews and their absence are cached per URL; failed
+    /// fetches are not, so they are retried the next time the URL is posted
+    pub async fn preview(&self, url: &str) -> Result<Option<LinkPreview>, LinkPreviewError> {
+        let parsed = check_outbound_url(url, &self.allowlist).await?;
+
+        let key = CacheKeyBuilder::link_preview(url);
+        if let Some(cache) = &self.cache {
+            match cache.get::<Option<LinkPreview>>(&key).await {
+                Ok(Some(cached)) => return Ok(cached),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to read cached link preview for {}: {}", url, e),
+            }
+        }
+
+        let preview = self.fetch(url, parsed).await?;
+
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache.set(&key, &preview, self.cache_ttl_secs).await {
+                warn!("Failed to cache link preview for {}: {}", url, e);
+            }
+        }
+
+        Ok(preview)
+    }
+
+    async fn fetch(&self, url: &str, parsed: Url) -> Result<Option<LinkPreview>, LinkPreviewError> {
+        let mut response = self
+            .client
+            .get(parsed)
+            .header(ACCEPT, "text/html,application/xhtml+xml")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(LinkPreviewError::Status(response.status().as_u16()));
+        }
+
+        let is_html = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map_or(true, |value| value.contains("html"));
+        if !is_html {
+            return Ok(None);
+        }
+
+        // Relative image URLs resolve against the page, after redirects
+        let base = response.url().clone();
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            let room = self.max_body_bytes - body.len();
+            body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+            if body.len() >= self.max_body_bytes {
+                break;
+            }
+        }
+
+        Ok(parse_preview(url, &base, &String::from_utf8_lossy(&body)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, response::Html, routing::get, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::net::TcpListener;
+
+    const OG_PAGE: &str = r#"<html><head>
+        <title>Ignored when og:title is set</title>
+        <meta property="og:title" content="Fechatter &amp; friends">
+        <meta property="og:description" content='Chat for teams'>
+        <meta property="og:image" content="/cover.png">
+        <meta property="og:site_name" content="Fechatter">
+        </head><body>Hello</body></html>"#;
+
+    /// Page server counting every request it receives
+    async fn spawn_pages() -> (String, Arc<AtomicUsize>) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route(
+                "/og",
+                get(|State(hits): State<Arc<AtomicUsize>>| async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    Html(OG_PAGE)
+                }),
+            )
+            .route(
+                "/plain",
+                get(|State(hits): State<Arc<AtomicUsize>>| async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    Html("<html><body>No metadata here</body></html>")
+                }),
+            )
+            .with_state(hits.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (base, hits)
+    }
+
+    /// The test server listens on loopback, which is refused unless allowlisted
+    fn local_service() -> LinkPreviewService {
+        let allowlist = OutboundAllowlist::new(["127.0.0.1"]);
+        LinkPreviewService::new(&LinkPreviewConfig::default(), allowlist, None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn url_with_open_graph_tags_should_produce_a_preview() {
+        let (base, _) = spawn_pages().await;
+        let url = format!("{}/og", base);
+
+        let preview = local_service().preview(&url).await.unwrap().unwrap();
+        assert_eq!(
+            preview,
+            LinkPreview {
+                url: url.clone(),
+                title: Some("Fechatter & friends".to_string()),
+                description: Some("Chat for teams".to_string()),
+                image: Some(format!("{}/cover.png", base)),
+                site_name: Some("Fechatter".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn url_without_metadata_should_produce_no_preview() {
+        let (base, hits) = spawn_pages().await;
+        let service = local_service();
+
+        let plain = format!("{}/plain", base);
+        assert_eq!(service.preview(&plain).await.unwrap(), None);
+
+        let content = format!("See {}/og, and also ({}).", base, plain);
+        assert_eq!(
+            extract_urls(&content, 3),
+            vec![format!("{}/og", base), plain.clone()]
+        );
+        let previews = service.unfurl(&content).await;
+        assert_eq!(previews.len(), 1);
+        assert_eq!(previews[0].url, format!("{}/og", base));
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn internal_ip_url_should_be_blocked_without_a_request() {
+        let (base, hits) = spawn_pages().await;
+        let service = LinkPreviewService::new(
+            &LinkPreviewConfig::default(),
+            OutboundAllowlist::default(),
+            None,
+        )
+        .unwrap();
+
+        for url in [
+            format!("{}/og", base),
+            "http://169.254.169.254/latest/meta-data/".to_string(),
+            "http://10.0.0.8/".to_string(),
+            "http://[::1]/".to_string(),
+            "http://[::ffff:192.168.1.1]/".to_string(),
+            "http://localhost/".to_string(),
+        ] {
+            let result = service.preview(&url).await;
+            assert!(
+                matches!(
+                    result,
+                    Err(LinkPreviewError::Outbound(
+                        OutboundUrlError::BlockedAddress { .. }
+                    ))
+                ),
+                "{} should be blocked, got {:?}",
+                url,
+                result
+            );
+        }
+        assert!(matches!(
+            service.preview("file:///etc/passwd").await,
+            Err(LinkPreviewError::Outbound(
+                OutboundUrlError::UnsupportedScheme(_)
+            ))
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+    }
+}
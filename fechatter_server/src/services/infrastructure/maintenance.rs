@@ -0,0 +1,142 @@
+//! # Maintenance Mode
+//!
+//! **Responsibility**: Runtime read-only switch that rejects writes while reads keep working
+//! **Toggle**: `server.maintenance.read_only` at startup, `PUT /api/admin/maintenance` at runtime
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::AppError;
+
+/// Write paths that stay open in read-only mode: session handling and the toggle itself
+const WRITABLE_PATHS: &[&str] = &[
+    "/signin",
+    "/refresh",
+    "/logout",
+    "/logout-all",
+    "/logout-others",
+    "/admin/maintenance",
+    "/admin/config/reload",
+];
+
+/// Shared read-only flag
+#[derive(Debug, Default)]
+pub struct MaintenanceMode {
+    read_only: AtomicBool,
+}
+
+impl MaintenanceMode {
+    pub fn new(read_only: bool) -> Self {
+        Self {
+            read_only: AtomicBool::new(read_only),
+        }
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    /// Set the flag and return the previous value
+    pub fn set_read_only(&self, read_only: bool) -> bool {
+        self.read_only.swap(read_only, Ordering::Relaxed)
+    }
+
+    /// Fail with a 503 `maintenance` error while read-only
+    pub fn ensure_writable(&self) -> Result<(), AppError> {
+        if self.is_read_only() {
+            return Err(AppError::Maintenance(
+                "writes are temporarily disabled, please retry later".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Reject non-safe requests while read-only; GET/HEAD/OPTIONS always pass
+pub async fn read_only_guard(
+    State(mode): State<Arc<MaintenanceMode>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !req.method().is_safe() && !WRITABLE_PATHS.contains(&req.uri().path()) {
+        if let Err(e) = mode.ensure_writable() {
+            return e.into_response();
+        }
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Method, StatusCode},
+        middleware::from_fn_with_state,
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    fn router(mode: Arc<MaintenanceMode>) -> Router {
+        Router::new()
+            .route(
+                "/chat/{id}/messages",
+                get(|| async { "list" }).post(|| async { "sent" }),
+            )
+            .route("/signin", post(|| async { "signed in" }))
+            .route("/logout-others", post(|| async { "signed out elsewhere" }))
+            .layer(from_fn_with_state(mode, read_only_guard))
+    }
+
+    async fn call(router: &Router, method: Method, uri: &str) -> Response {
+        router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn read_only_should_block_send_but_allow_list() {
+        let mode = Arc::new(MaintenanceMode::default());
+        let router = router(mode.clone());
+
+        let response = call(&router, Method::POST, "/chat/1/messages").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert!(!mode.set_read_only(true));
+
+        let response = call(&router, Method::POST, "/chat/1/messages").await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error_code"], "maintenance");
+
+        let response = call(&router, Method::GET, "/chat/1/messages").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = call(&router, Method::POST, "/signin").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = call(&router, Method::POST, "/logout-others").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert!(mode.set_read_only(false));
+
+        let response = call(&router, Method::POST, "/chat/1/messages").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -16,16 +16,24 @@
 
 // Infrastructure services
 pub mod cache;
+pub mod dead_letters;
 pub mod event;
 pub mod event_publisher;
 pub mod events;
+pub mod file_scan;
 pub mod flows;
+pub mod link_preview;
+pub mod maintenance;
 pub mod notification;
 pub mod observability;
+pub mod outbound;
+pub mod rate_limit;
+pub mod runtime_config;
 pub mod search;
 pub mod storage;
 pub mod third_party_manager;
 pub mod vector_db;
+pub mod webhooks;
 
 // Re-exports - 按职责导出核心基础设施服务
 pub use event::LegacyEventPublisher as EventPublisher;
@@ -0,0 +1,224 @@
+//! # Admin Dashboard Snapshot
+//!
+//! **Responsibility**: One view of system health for `GET /api/admin/dashboard`
+//! **Sources**: Row counts from the database, Redis keyspace hit rate, notify_server connection
+//! counts and JetStream consumer backlog. Sources that are not configured or not reachable are
+//! reported as `null` rather than failing the snapshot; the whole snapshot is reused for
+//! `features.admin_dashboard.cache_ttl_seconds`
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::config::DashboardQueueConfig;
+use crate::{AppError, AppState};
+
+/// How long notify_server gets to answer before connections are left out
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// System health at `generated_at`
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardSnapshot {
+    pub generated_at: DateTime<Utc>,
+    pub counts: EntityCounts,
+    /// Live SSE connections, if notify_server is configured and answered
+    pub connections: Option<ConnectionStats>,
+    /// Redis keyspace hits since the server started, if the cache is enabled
+    pub cache: Option<CacheHitStats>,
+    /// One entry per configured consumer
+    pub queues: Vec<QueueDepth>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EntityCounts {
+    pub users: i64,
+    pub workspaces: i64,
+    pub chats: i64,
+    /// Live messages; tombstoned ones are not counted
+    pub messages: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionStats {
+    pub connected_users: u64,
+    /// Chats with at least one member connected
+    pub active_chats: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheHitStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// `hits / (hits + misses)`, 0 before the first lookup
+    pub hit_rate: f64,
+}
+
+/// Backlog of a JetStream consumer; counts are `null` when it could not be read
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueDepth {
+    pub stream: String,
+    pub consumer: String,
+    /// Messages not yet delivered to the consumer
+    pub pending: Option<u64>,
+    /// Messages delivered but not yet acknowledged
+    pub in_flight: Option<u64>,
+}
+
+/// Latest snapshot, collected again once it is older than the TTL
+pub struct DashboardCache {
+    ttl: Duration,
+    latest: Mutex<Option<(Instant, DashboardSnapshot)>>,
+}
+
+impl DashboardCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            latest: Mutex::new(None),
+        }
+    }
+
+    /// The cached snapshot, or a fresh one if it has expired. Concurrent callers wait for a
+    /// single collection instead of each running the counts
+    pub async fn snapshot(
+        &self,
+        state: &AppState,
+        authorization: Option<&str>,
+    ) -> Result<DashboardSnapshot, AppError> {
+        let mut latest = self.latest.lock().await;
+        if let Some((collected_at, snapshot)) = latest.as_ref() {
+            if collected_at.elapsed() < self.ttl {
+                return Ok(snapshot.clone());
+            }
+        }
+
+        let snapshot = collect(state, authorization).await?;
+        *latest = Some((Instant::now(), snapshot.clone()));
+        Ok(snapshot)
+    }
+}
+
+/// Collect a snapshot from every source; `authorization` is forwarded to notify_server
+pub async fn collect(
+    state: &AppState,
+    authorization: Option<&str>,
+) -> Result<DashboardSnapshot, AppError> {
+    let config = &state.config.features.admin_dashboard;
+    let (counts, connections, cache, queues) = tokio::join!(
+        entity_counts(state),
+        connection_stats(config.notify_url.as_deref(), authorization),
+        cache_hit_stats(state),
+        queue_depths(state, &config.queues),
+    );
+
+    Ok(DashboardSnapshot {
+        generated_at: Utc::now(),
+        counts: counts?,
+        connections,
+        cache,
+        queues,
+    })
+}
+
+async fn entity_counts(state: &AppState) -> Result<EntityCounts, AppError> {
+    let (users, workspaces, chats, messages) =
+        sqlx::query_as::<_, (i64, i64, i64, i64)>(
+            r#"SELECT
+                 (SELECT COUNT(*) FROM users),
+                 (SELECT COUNT(*) FROM workspaces),
+                 (SELECT COUNT(*) FROM chats),
+                 (SELECT COUNT(*) FROM messages WHERE deleted_at IS NULL)"#,
+        )
+        .fetch_one(&*state.pool())
+        .await?;
+
+    Ok(EntityCounts {
+        users,
+        workspaces,
+        chats,
+        messages,
+    })
+}
+
+async fn connection_stats(
+    notify_url: Option<&str>,
+    authorization: Option<&str>,
+) -> Option<ConnectionStats> {
+    let (notify_url, authorization) = (notify_url?, authorization?);
+    let url = format!("{}/sse/health", notify_url.trim_end_matches('/'));
+
+    let response = reqwest::Client::new()
+        .get(&url)
+        .header(reqwest::header::AUTHORIZATION, authorization)
+        .timeout(NOTIFY_TIMEOUT)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    match response {
+        Ok(response) => match response.json::<ConnectionStats>().await {
+            Ok(stats) => Some(stats),
+            Err(e) => {
+                warn!("Dashboard: unexpected notify_server health response: {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            warn!("Dashboard: notify_server unreachable at {}: {}", url, e);
+            None
+        }
+    }
+}
+
+async fn cache_hit_stats(state: &AppState) -> Option<CacheHitStats> {
+    let cache = state.cache_service()?;
+    match cache.get_cache_stats().await {
+        Ok(stats) => Some(CacheHitStats {
+            hits: stats.total_hits,
+            misses: stats.total_misses,
+            hit_rate: stats.hit_rate,
+        }),
+        Err(e) => {
+            warn!("Dashboard: cache stats unavailable: {}", e);
+            None
+        }
+    }
+}
+
+async fn queue_depths(state: &AppState, queues: &[DashboardQueueConfig]) -> Vec<QueueDepth> {
+    let jetstream = state.nats_client().map(async_nats::jetstream::new);
+
+    let mut depths = Vec::with_capacity(queues.len());
+    for queue in queues {
+        let mut depth = QueueDepth {
+            stream: queue.stream.clone(),
+            consumer: queue.consumer.clone(),
+            pending: None,
+            in_flight: None,
+        };
+        if let Some(jetstream) = &jetstream {
+            match consumer_backlog(jetstream, queue).await {
+                Ok((pending, in_flight)) => {
+                    depth.pending = Some(pending);
+                    depth.in_flight = Some(in_flight);
+                }
+                Err(e) => warn!(
+                    "Dashboard: consumer {}/{} unavailable: {}",
+                    queue.stream, queue.consumer, e
+                ),
+            }
+        }
+        depths.push(depth);
+    }
+    depths
+}
+
+async fn consumer_backlog(
+    jetstream: &async_nats::jetstream::Context,
+    queue: &DashboardQueueConfig,
+) -> Result<(u64, u64), async_nats::Error> {
+    let stream = jetstream.get_stream(&queue.stream).await?;
+    let info = stream.consumer_info(&queue.consumer).await?;
+    Ok((info.num_pending, info.num_ack_pending as u64))
+}
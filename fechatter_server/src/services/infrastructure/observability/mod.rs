@@ -1,4 +1,6 @@
+pub mod dashboard;
 pub mod metrics;
+pub mod slow_query;
 pub mod tracing;
 
 use crate::error::AppError;
@@ -0,0 +1,145 @@
+//! # Slow Query Log
+//!
+//! **Responsibility**: Time labeled repository queries, record their duration per label and log
+//! the ones slower than `features.observability.slow_query_threshold_ms`
+//! **Usage**: `sqlx::query(..).fetch_all(&*pool).timed("message.list_messages").await`
+
+use metrics::{counter, histogram};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Threshold until the configured one is applied at startup
+const DEFAULT_THRESHOLD_MS: u64 = 200;
+
+static THRESHOLD_MS: AtomicU64 = AtomicU64::new(DEFAULT_THRESHOLD_MS);
+
+/// Set the duration above which queries are logged; zero stops the logging
+pub fn set_slow_query_threshold(threshold: Duration) {
+    THRESHOLD_MS.store(threshold.as_millis() as u64, Ordering::Relaxed);
+}
+
+pub fn slow_query_threshold() -> Duration {
+    Duration::from_millis(THRESHOLD_MS.load(Ordering::Relaxed))
+}
+
+/// Record a finished query, returning whether it was logged as slow
+pub fn record_query(label: &'static str, elapsed: Duration) -> bool {
+    histogram!("fechatter_db_labeled_query_duration_seconds", "query" => label)
+        .record(elapsed.as_secs_f64());
+
+    let threshold = slow_query_threshold();
+    if threshold.is_zero() || elapsed < threshold {
+        return false;
+    }
+
+    counter!("fechatter_db_slow_queries_total", "query" => label).increment(1);
+    warn!(
+      target: "slow_query",
+      query = label,
+      duration_ms = elapsed.as_millis() as u64,
+      threshold_ms = threshold.as_millis() as u64,
+      "Slow query"
+    );
+    true
+}
+
+/// A query future timed from its first poll to completion
+pub struct Timed<F> {
+    inner: F,
+    label: &'static str,
+    started: Option<Instant>,
+}
+
+impl<F> Future for Timed<F>
+where
+    F: Future + Unpin,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        let output = ready!(Pin::new(&mut self.inner).poll(cx));
+        record_query(self.label, started.elapsed());
+        Poll::Ready(output)
+    }
+}
+
+/// `.timed(label)` for sqlx's boxed query futures
+pub trait TimedQuery: Future + Unpin + Sized {
+    fn timed(self, label: &'static str) -> Timed<Self> {
+        Timed {
+            inner: self,
+            label,
+            started: None,
+        }
+    }
+}
+
+impl<F> TimedQuery for F where F: Future + Unpin {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    /// Log output captured for the duration of a test
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl CapturedLogs {
+        fn contents(&self) -> String {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+        }
+    }
+
+    impl io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn query_slower_than_threshold_should_be_logged() -> anyhow::Result<()> {
+        let (state, _users) = crate::setup_test_users!(1).await;
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+        set_slow_query_threshold(Duration::from_millis(50));
+
+        sqlx::query("SELECT 1")
+            .execute(&*state.pool())
+            .timed("test.fast")
+            .await?;
+        sqlx::query("SELECT pg_sleep(0.3)")
+            .execute(&*state.pool())
+            .timed("test.pg_sleep")
+            .await?;
+
+        let output = logs.contents();
+        assert!(output.contains("Slow query"), "{}", output);
+        assert!(output.contains("test.pg_sleep"), "{}", output);
+        assert!(!output.contains("test.fast"), "{}", output);
+        Ok(())
+    }
+}
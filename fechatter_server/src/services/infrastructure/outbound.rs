@@ -0,0 +1,75 @@
+//! # Outbound HTTP
+//!
+//! **Responsibility**: HTTP clients for user-supplied URLs that cannot reach internal services
+//! **Scope**: Callers check a URL with `fechatter_core::utils::check_outbound_url` before sending;
+//! these clients repeat the check when connecting and on every redirect, so a DNS answer that
+//! changes in between doesn't get past it
+
+use fechatter_core::utils::{is_public_ip, resolve_public, OutboundAllowlist};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+/// Resolver that refuses hosts with internal addresses unless they are allowlisted
+struct GuardedResolver {
+    allowlist: Arc<OutboundAllowlist>,
+}
+
+impl Resolve for GuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allowlist = self.allowlist.clone();
+        Box::pin(async move {
+            // The port is filled in by the client from the URL
+            let addrs = resolve_public(name.as_str(), 0, &allowlist).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Redirect targets get the same treatment as the original URL. IP literals never reach the
+/// resolver, so they are checked here
+fn is_allowed_redirect(url: &Url, allowlist: &OutboundAllowlist) -> bool {
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    if allowlist.allows(host) {
+        return true;
+    }
+    match host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        Ok(ip) => is_public_ip(ip),
+        Err(_) => true,
+    }
+}
+
+/// Client builder that only connects to public addresses or allowlisted hosts and follows at
+/// most `max_redirects` redirects
+pub fn guarded_client_builder(
+    allowlist: OutboundAllowlist,
+    max_redirects: usize,
+) -> reqwest::ClientBuilder {
+    let allowlist = Arc::new(allowlist);
+    let redirect_allowlist = allowlist.clone();
+    let redirects = Policy::custom(move |attempt| {
+        // The first entry is the original URL, not a redirect
+        if attempt.previous().len() > max_redirects {
+            attempt.error("too many redirects")
+        } else if !is_allowed_redirect(attempt.url(), &redirect_allowlist) {
+            attempt.error("redirect to a blocked address")
+        } else {
+            attempt.follow()
+        }
+    });
+
+    reqwest::Client::builder()
+        .dns_resolver(Arc::new(GuardedResolver { allowlist }))
+        .redirect(redirects)
+}
@@ -0,0 +1,544 @@
+//! # Rate Limiting
+//!
+//! **Responsibility**: Fixed-window request throttling shared by every limited endpoint
+//! **Backends**: Redis (distributed) with the core in-memory store as fallback when the cache is
+//! disabled; the limiter itself lives in `fechatter_core` so notify_server can share it
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::config::RateLimitConfig;
+use crate::domains::workspace::limits::WorkspaceLimits;
+use crate::error::AppError;
+use crate::services::infrastructure::cache::{CacheKeyBuilder, RedisCacheService};
+use crate::services::infrastructure::runtime_config::{self, RuntimeConfig, SharedRuntimeConfig};
+use fechatter_core::error::CoreError;
+
+pub use fechatter_core::services::rate_limit::{
+    apply_rate_limit_headers, InMemoryRateLimitStore, RateLimitBucket, RateLimitDecision,
+    RateLimitStore, RateLimiter,
+};
+
+impl From<RateLimitDecision> for AppError {
+    fn from(decision: RateLimitDecision) -> Self {
+        AppError::RateLimited(decision)
+    }
+}
+
+/// Redis-backed store, shared across server instances
+pub struct RedisRateLimitStore {
+    cache: Arc<RedisCacheService>,
+}
+
+impl RedisRateLimitStore {
+    pub fn new(cache: Arc<RedisCacheService>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    async fn hit(&self, key: &str, window: Duration) -> Result<(u64, Duration), CoreError> {
+        let (count, ttl) = self
+            .cache
+            .incr_with_expiry(key, window.as_secs().max(1))
+            .await
+            .map_err(store_error)?;
+        let reset_after = if ttl > 0 {
+            Duration::from_secs(ttl as u64)
+        } else {
+            window
+        };
+        Ok((count.max(0) as u64, reset_after))
+    }
+
+    async fn buckets(&self, prefix: &str) -> Result<Vec<RateLimitBucket>, CoreError> {
+        let mut buckets = Vec::new();
+        let keys = self
+            .cache
+            .scan_keys(&format!("{}*", prefix))
+            .await
+            .map_err(store_error)?;
+        for key in keys {
+            // Keys can expire between SCAN and GET
+            let Some(count) = self.cache.get::<i64>(&key).await.map_err(store_error)? else {
+                continue;
+            };
+            let ttl = self.cache.ttl(&key).await.map_err(store_error)?;
+            buckets.push(RateLimitBucket {
+                key,
+                count: count.max(0) as u64,
+                resets_in_seconds: ttl.max(0) as u64,
+            });
+        }
+        Ok(buckets)
+    }
+
+    async fn reset(&self, prefix: &str) -> Result<u64, CoreError> {
+        self.cache
+            .del_pattern(&format!("{}*", prefix))
+            .await
+            .map_err(store_error)
+    }
+}
+
+fn store_error(error: AppError) -> CoreError {
+    CoreError::Internal(format!("Rate limit store failed: {}", error))
+}
+
+/// AI token counters expire a day after the one they count
+const AI_TOKENS_TTL_SECS: u64 = 2 * 24 * 60 * 60;
+
+/// AI tokens spent per workspace and UTC day; in process when the cache is disabled
+enum TokenLedger {
+    Redis(Arc<RedisCacheService>),
+    InMemory(DashMap<String, u64>),
+}
+
+/// Limiters for throttled endpoints; limits are read from the runtime config on every
+/// request, so a reload applies without rebuilding the limiters or losing their counters
+#[derive(Clone)]
+pub struct EndpointRateLimiters {
+    store: Arc<dyn RateLimitStore>,
+    tokens: Arc<TokenLedger>,
+    runtime: SharedRuntimeConfig,
+}
+
+impl EndpointRateLimiters {
+    pub fn new(cache: Option<Arc<RedisCacheService>>, runtime: SharedRuntimeConfig) -> Self {
+        let (store, tokens): (Arc<dyn RateLimitStore>, _) = match cache {
+            Some(cache) => (
+                Arc::new(RedisRateLimitStore::new(cache.clone())),
+                TokenLedger::Redis(cache),
+            ),
+            None => (
+                Arc::new(InMemoryRateLimitStore::new()),
+                TokenLedger::InMemory(DashMap::new()),
+            ),
+        };
+
+        Self {
+            store,
+            tokens: Arc::new(tokens),
+            runtime,
+        }
+    }
+
+    /// Limiters with fixed settings
+    pub fn from_config(config: &RateLimitConfig, cache: Option<Arc<RedisCacheService>>) -> Self {
+        let runtime = RuntimeConfig {
+            rate_limiting: config.clone(),
+            ..RuntimeConfig::default()
+        };
+        Self::new(cache, Arc::new(RwLock::new(runtime)))
+    }
+
+    /// Message send limiter, `None` while rate limiting is disabled
+    pub fn message_send(&self) -> Option<RateLimiter> {
+        self.limiter(|config| config.max_requests)
+    }
+
+    /// Message send limiter under a workspace's overrides
+    pub fn message_send_in(&self, overrides: &WorkspaceLimits) -> Option<RateLimiter> {
+        self.limiter(|config| override_or(overrides.message_send_limit, config.max_requests))
+    }
+
+    /// File upload limiter under a workspace's overrides
+    pub fn file_upload_in(&self, overrides: &WorkspaceLimits) -> Option<RateLimiter> {
+        self.limiter(|config| override_or(overrides.upload_limit, config.upload_max_requests))
+    }
+
+    /// Per-user file download limiter, `None` while rate limiting is disabled
+    pub fn file_download(&self) -> Option<RateLimiter> {
+        self.limiter(|config| config.download_max_requests)
+    }
+
+    /// Bandwidth cap for a single download, `None` when downloads are unthrottled. Like slow
+    /// mode, it applies even while request rate limiting is disabled
+    pub fn download_bytes_per_second(&self) -> Option<u64> {
+        let runtime = runtime_config::read(&self.runtime);
+        Some(runtime.rate_limiting.download_bytes_per_second).filter(|rate| *rate > 0)
+    }
+
+    /// Per-account login limiter, `None` while rate limiting is disabled
+    pub fn login(&self) -> Option<RateLimiter> {
+        self.limiter(|config| config.login_max_requests)
+    }
+
+    /// Per-client signup limiter on the login budget, `None` while rate limiting is disabled
+    pub fn signup(&self) -> Option<RateLimiter> {
+        self.limiter(|config| config.login_max_requests)
+    }
+
+    /// One message per `interval_secs` for a chat member, `None` when slow mode is off. Slow mode
+    /// is a chat setting, so it applies even while rate limiting is disabled
+    pub fn slow_mode(&self, interval_secs: i32) -> Option<RateLimiter> {
+        let interval = u64::try_from(interval_secs).ok().filter(|secs| *secs > 0)?;
+        Some(RateLimiter::new(
+            self.store.clone(),
+            1,
+            Duration::from_secs(interval),
+        ))
+    }
+
+    /// One pushed typing event per interval for a user in a chat, `None` when every start is
+    /// pushed. Like slow mode, it applies even while rate limiting is disabled
+    pub fn typing_push(&self) -> Option<RateLimiter> {
+        let runtime = runtime_config::read(&self.runtime);
+        let interval = Some(runtime.rate_limiting.typing_interval_secs).filter(|secs| *secs > 0)?;
+        Some(RateLimiter::new(
+            self.store.clone(),
+            1,
+            Duration::from_secs(interval),
+        ))
+    }
+
+    /// Bot translations a user may request per day under a workspace's overrides
+    pub fn bot_daily_quota_in(&self, overrides: &WorkspaceLimits) -> u32 {
+        let runtime = runtime_config::read(&self.runtime);
+        override_or(
+            overrides.bot_daily_quota,
+            runtime.rate_limiting.bot_daily_quota,
+        )
+    }
+
+    /// AI provider tokens a workspace may spend per UTC day, `None` when unbudgeted. Like the
+    /// bot quota, it applies even while rate limiting is disabled
+    pub fn ai_daily_token_budget(&self) -> Option<u64> {
+        let runtime = runtime_config::read(&self.runtime);
+        Some(runtime.rate_limiting.ai_daily_token_budget).filter(|budget| *budget > 0)
+    }
+
+    /// Refuse a workspace that has spent its AI token budget for today. Fails open when the
+    /// counter cannot be read, like the request limiters
+    pub async fn ensure_ai_tokens_left(&self, workspace_id: i64) -> Result<(), AppError> {
+        let Some(budget) = self.ai_daily_token_budget() else {
+            return Ok(());
+        };
+        let used = match self.ai_tokens_used(workspace_id).await {
+            Ok(used) => used,
+            Err(e) => {
+                warn!(
+                    "Failed to read AI token spend of workspace {}: {}",
+                    workspace_id, e
+                );
+                return Ok(());
+            }
+        };
+        if used >= budget {
+            return Err(AppError::BadRequest(format!(
+                "Daily AI token budget exceeded. This workspace has used {}/{} tokens today.",
+                used, budget
+            )));
+        }
+        Ok(())
+    }
+
+    /// AI tokens the workspace has spent today
+    pub async fn ai_tokens_used(&self, workspace_id: i64) -> Result<u64, AppError> {
+        let key = CacheKeyBuilder::ai_tokens(workspace_id, &utc_day());
+        match self.tokens.as_ref() {
+            TokenLedger::Redis(cache) => {
+                Ok(cache.get::<i64>(&key).await?.unwrap_or(0).max(0) as u64)
+            }
+            TokenLedger::InMemory(spent) => Ok(spent.get(&key).map_or(0, |used| *used)),
+        }
+    }
+
+    /// Debit tokens the workspace has spent from today's budget, returning its spend so far
+    pub async fn charge_ai_tokens(&self, workspace_id: i64, tokens: u64) -> Result<u64, AppError> {
+        let day = utc_day();
+        let key = CacheKeyBuilder::ai_tokens(workspace_id, &day);
+        match self.tokens.as_ref() {
+            TokenLedger::Redis(cache) => {
+                let by = i64::try_from(tokens).unwrap_or(i64::MAX);
+                let used = cache
+                    .incr_by_with_expiry(&key, by, AI_TOKENS_TTL_SECS)
+                    .await?;
+                Ok(used.max(0) as u64)
+            }
+            TokenLedger::InMemory(spent) => {
+                // Earlier days' counters are done with
+                spent.retain(|key, _| key.ends_with(&day));
+                let mut used = spent.entry(key).or_insert(0);
+                *used = used.saturating_add(tokens);
+                Ok(*used)
+            }
+        }
+    }
+
+    fn limiter(&self, max_requests: impl Fn(&RateLimitConfig) -> u32) -> Option<RateLimiter> {
+        let runtime = runtime_config::read(&self.runtime);
+        let config = &runtime.rate_limiting;
+        config.enabled.then(|| {
+            RateLimiter::new(
+                self.store.clone(),
+                max_requests(config),
+                config.window_duration(),
+            )
+        })
+    }
+
+    /// Current per-user buckets (`rate_limit:{user_id}:*`)
+    pub async fn user_buckets(&self, user_id: i64) -> Result<Vec<RateLimitBucket>, AppError> {
+        Ok(self.store.buckets(&Self::user_prefix(user_id)).await?)
+    }
+
+    /// Clear every per-user bucket, returning how many were removed
+    pub async fn reset_user(&self, user_id: i64) -> Result<u64, AppError> {
+        Ok(self.store.reset(&Self::user_prefix(user_id)).await?)
+    }
+
+    /// Current login bucket of an account (`rate_limit:login:{email}`)
+    pub async fn login_buckets(&self, email: &str) -> Result<Vec<RateLimitBucket>, AppError> {
+        Ok(self
+            .store
+            .buckets(&CacheKeyBuilder::login_rate_limit(email))
+            .await?)
+    }
+
+    /// Clear an account's login bucket, returning how many were removed
+    pub async fn reset_login(&self, email: &str) -> Result<u64, AppError> {
+        Ok(self
+            .store
+            .reset(&CacheKeyBuilder::login_rate_limit(email))
+            .await?)
+    }
+
+    fn user_prefix(user_id: i64) -> String {
+        CacheKeyBuilder::rate_limit(user_id, "")
+    }
+}
+
+/// Current UTC day, naming the AI token counters
+fn utc_day() -> String {
+    chrono::Utc::now().format("%Y%m%d").to_string()
+}
+
+/// A workspace override when set and positive, the global default otherwise
+fn override_or(value: Option<i32>, default: u32) -> u32 {
+    value
+        .and_then(|value| u32::try_from(value).ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    fn limiter(max_requests: u32) -> RateLimiter {
+        RateLimiter::new(
+            Arc::new(InMemoryRateLimitStore::new()),
+            max_requests,
+            Duration::from_secs(60),
+        )
+    }
+
+    #[tokio::test]
+    async fn over_limit_message_send_should_return_429_with_retry_after() {
+        let limiter = limiter(2);
+        let key = "rate_limit:42:message_send";
+
+        assert!(limiter.enforce(key).await.is_ok());
+        assert!(limiter.enforce(key).await.is_ok());
+        let decision = limiter.enforce(key).await.unwrap_err();
+
+        let response = AppError::from(decision).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let header = |name: &str| {
+            response.headers()[name]
+                .to_str()
+                .unwrap()
+                .parse::<u64>()
+                .unwrap()
+        };
+        let retry_after = header("retry-after");
+        assert!((1..=60).contains(&retry_after));
+        assert_eq!(header("x-ratelimit-remaining"), 0);
+        assert_eq!(header("x-ratelimit-reset"), retry_after);
+    }
+
+    #[tokio::test]
+    async fn resetting_user_buckets_should_unblock_requests() {
+        let config = RateLimitConfig::per_user(1, 60);
+        let limiters = EndpointRateLimiters::from_config(&config, None);
+        let limiter = limiters.message_send().unwrap();
+        let key = CacheKeyBuilder::rate_limit(42, "message_send");
+
+        limiter.enforce(&key).await.unwrap();
+        assert!(limiter.enforce(&key).await.is_err());
+
+        let buckets = limiters.user_buckets(42).await.unwrap();
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].count, 2);
+
+        assert_eq!(limiters.reset_user(42).await.unwrap(), 1);
+        assert!(limiter.enforce(&key).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn resetting_user_buckets_should_not_touch_other_users() {
+        let limiters = EndpointRateLimiters::from_config(&RateLimitConfig::per_user(1, 60), None);
+        let limiter = limiters.message_send().unwrap();
+        limiter
+            .check(&CacheKeyBuilder::rate_limit(4, "message_send"))
+            .await
+            .unwrap();
+        limiter
+            .check(&CacheKeyBuilder::rate_limit(42, "message_send"))
+            .await
+            .unwrap();
+
+        limiters.reset_user(4).await.unwrap();
+
+        assert!(limiters.user_buckets(4).await.unwrap().is_empty());
+        assert_eq!(limiters.user_buckets(42).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn resetting_login_bucket_should_unblock_signin() {
+        let config = RateLimitConfig {
+            login_max_requests: 1,
+            ..RateLimitConfig::per_user(100, 60)
+        };
+        let limiters = EndpointRateLimiters::from_config(&config, None);
+        let limiter = limiters.login().unwrap();
+        let key = CacheKeyBuilder::login_rate_limit("Alice@Example.com");
+
+        limiter.enforce(&key).await.unwrap();
+        assert!(limiter.enforce(&key).await.is_err());
+        let buckets = limiters.login_buckets("alice@example.com").await.unwrap();
+        assert_eq!(buckets.len(), 1);
+
+        assert_eq!(limiters.reset_login("alice@example.com").await.unwrap(), 1);
+        assert!(limiter.enforce(&key).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn downloads_past_the_per_user_limit_should_be_rejected() {
+        let config = RateLimitConfig {
+            download_max_requests: 3,
+            ..RateLimitConfig::per_user(100, 60)
+        };
+        let limiters = EndpointRateLimiters::from_config(&config, None);
+        let limiter = limiters.file_download().unwrap();
+        let key = |user_id| CacheKeyBuilder::rate_limit(user_id, "file_download");
+
+        for _ in 0..3 {
+            assert!(limiter.enforce(&key(7)).await.is_ok());
+        }
+        let decision = limiter.enforce(&key(7)).await.unwrap_err();
+        assert_eq!(decision.limit, 3);
+        assert_eq!(
+            AppError::from(decision).into_response().status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+
+        // Other users keep their own budget
+        assert!(limiter.enforce(&key(8)).await.is_ok());
+        assert_eq!(limiters.download_bytes_per_second(), None);
+    }
+
+    #[tokio::test]
+    async fn signups_past_the_limit_should_carry_rate_limit_headers() {
+        let config = RateLimitConfig {
+            login_max_requests: 2,
+            ..RateLimitConfig::per_user(100, 60)
+        };
+        let limiters = EndpointRateLimiters::from_config(&config, None);
+        let limiter = limiters.signup().unwrap();
+        let key = "rate_limit:signup:203.0.113.7";
+
+        limiter.enforce(key).await.unwrap();
+        limiter.enforce(key).await.unwrap();
+        let response = AppError::from(limiter.enforce(key).await.unwrap_err()).into_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key("retry-after"));
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "0");
+        assert!(response.headers().contains_key("x-ratelimit-reset"));
+    }
+
+    #[tokio::test]
+    async fn raised_workspace_limits_should_allow_more_requests_than_defaults() {
+        let config = RateLimitConfig {
+            upload_max_requests: 2,
+            ..RateLimitConfig::per_user(2, 60)
+        };
+        let limiters = EndpointRateLimiters::from_config(&config, None);
+        let default = WorkspaceLimits::default();
+        let premium = WorkspaceLimits {
+            message_send_limit: Some(4),
+            upload_limit: Some(4),
+            bot_daily_quota: Some(50),
+        };
+
+        async fn allowed(limiter: RateLimiter, key: String) -> usize {
+            let mut allowed = 0;
+            for _ in 0..6 {
+                if limiter.check(&key).await.unwrap().allowed {
+                    allowed += 1;
+                }
+            }
+            allowed
+        }
+
+        // Users 1 and 2 sit in a default and a premium workspace respectively
+        let send = |user_id| CacheKeyBuilder::rate_limit(user_id, "message_send");
+        assert_eq!(
+            allowed(limiters.message_send_in(&default).unwrap(), send(1)).await,
+            2
+        );
+        assert_eq!(
+            allowed(limiters.message_send_in(&premium).unwrap(), send(2)).await,
+            4
+        );
+
+        let upload = |user_id| CacheKeyBuilder::rate_limit(user_id, "file_upload");
+        assert_eq!(
+            allowed(limiters.file_upload_in(&default).unwrap(), upload(1)).await,
+            2
+        );
+        assert_eq!(
+            allowed(limiters.file_upload_in(&premium).unwrap(), upload(2)).await,
+            4
+        );
+
+        assert_eq!(limiters.bot_daily_quota_in(&default), 20);
+        assert_eq!(limiters.bot_daily_quota_in(&premium), 50);
+    }
+
+    #[tokio::test]
+    async fn spent_ai_token_budget_should_refuse_only_that_workspace() {
+        let config = RateLimitConfig {
+            ai_daily_token_budget: 100,
+            ..RateLimitConfig::per_user(100, 60)
+        };
+        let limiters = EndpointRateLimiters::from_config(&config, None);
+
+        assert!(limiters.ensure_ai_tokens_left(1).await.is_ok());
+        assert_eq!(limiters.charge_ai_tokens(1, 60).await.unwrap(), 60);
+        assert!(limiters.ensure_ai_tokens_left(1).await.is_ok());
+        assert_eq!(limiters.charge_ai_tokens(1, 45).await.unwrap(), 105);
+        assert!(matches!(
+            limiters.ensure_ai_tokens_left(1).await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        assert_eq!(limiters.ai_tokens_used(2).await.unwrap(), 0);
+        assert!(limiters.ensure_ai_tokens_left(2).await.is_ok());
+
+        // Unbudgeted by default
+        let unbudgeted = EndpointRateLimiters::from_config(&RateLimitConfig::default(), None);
+        assert_eq!(unbudgeted.ai_daily_token_budget(), None);
+        unbudgeted.charge_ai_tokens(1, 1_000_000).await.unwrap();
+        assert!(unbudgeted.ensure_ai_tokens_left(1).await.is_ok());
+    }
+}
@@ -0,0 +1,252 @@
+//! # Runtime Configuration
+//!
+//! **Responsibility**: The hot-reloadable subset of `AppConfig`, read at request time
+//! **Reload**: `POST /api/admin/config/reload` or SIGHUP re-read the config file; a file that
+//! fails to parse or validate is rejected and the current settings stay in place
+//!
+//! Rate limits, cache TTLs and embedding backfill throttling take effect on the next request.
+//! Everything else in `AppConfig` (ports, backends, route groups) still needs a restart.
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::config::{AppConfig, ConfigError, EmbeddingBackfillConfig, RateLimitConfig};
+use crate::error::AppError;
+
+/// Cache TTL used when no config file has been loaded
+const DEFAULT_CACHE_TTL_SECONDS: u64 = 3600;
+
+/// Settings that can change without a restart
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeConfig {
+    pub rate_limiting: RateLimitConfig,
+    /// Default cache entry TTL (seconds)
+    pub cache_default_ttl: u64,
+    pub embedding_backfill: EmbeddingBackfillConfig,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            rate_limiting: RateLimitConfig::default(),
+            cache_default_ttl: DEFAULT_CACHE_TTL_SECONDS,
+            embedding_backfill: EmbeddingBackfillConfig::default(),
+        }
+    }
+}
+
+impl RuntimeConfig {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        Self {
+            rate_limiting: config.features.rate_limiting.clone(),
+            cache_default_ttl: config.features.cache.default_ttl,
+            embedding_backfill: config.features.embedding_backfill.clone(),
+        }
+    }
+
+    /// Reject values that would disable throttling by accident or break the cache
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let positive = |field: &str, value: u64| {
+            if value == 0 {
+                Err(ConfigError::InvalidValue {
+                    field: field.to_string(),
+                    value: value.to_string(),
+                })
+            } else {
+                Ok(())
+            }
+        };
+
+        positive(
+            "features.rate_limiting.window_seconds",
+            self.rate_limiting.window_seconds,
+        )?;
+        positive(
+            "features.rate_limiting.max_requests",
+            u64::from(self.rate_limiting.max_requests),
+        )?;
+        positive(
+            "features.rate_limiting.login_max_requests",
+            u64::from(self.rate_limiting.login_max_requests),
+        )?;
+        positive(
+            "features.rate_limiting.upload_max_requests",
+            u64::from(self.rate_limiting.upload_max_requests),
+        )?;
+        positive(
+            "features.rate_limiting.bot_daily_quota",
+            u64::from(self.rate_limiting.bot_daily_quota),
+        )?;
+        positive(
+            "features.rate_limiting.download_max_requests",
+            u64::from(self.rate_limiting.download_max_requests),
+        )?;
+        positive("features.cache.default_ttl", self.cache_default_ttl)?;
+        positive(
+            "features.embedding_backfill.batch_size",
+            self.embedding_backfill.batch_size.max(0) as u64,
+        )?;
+        positive(
+            "features.embedding_backfill.requests_per_minute",
+            u64::from(self.embedding_backfill.requests_per_minute),
+        )?;
+        Ok(())
+    }
+}
+
+/// Runtime settings shared by everything that reads them per request
+pub type SharedRuntimeConfig = Arc<RwLock<RuntimeConfig>>;
+
+/// Owner of the shared runtime settings and the file they are reloaded from
+#[derive(Clone)]
+pub struct RuntimeConfigHandle {
+    current: SharedRuntimeConfig,
+    source: Option<PathBuf>,
+}
+
+impl RuntimeConfigHandle {
+    pub fn new(config: RuntimeConfig, source: Option<PathBuf>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(config)),
+            source,
+        }
+    }
+
+    /// The shared settings, for components that read them at request time
+    pub fn shared(&self) -> SharedRuntimeConfig {
+        self.current.clone()
+    }
+
+    /// Current settings
+    pub fn current(&self) -> RuntimeConfig {
+        read(&self.current).clone()
+    }
+
+    /// Validate and swap in new settings; invalid settings leave the current ones in place
+    pub fn apply(&self, config: RuntimeConfig) -> Result<RuntimeConfig, AppError> {
+        config
+            .validate()
+            .map_err(|e| AppError::BadRequest(format!("Rejected config reload: {}", e)))?;
+
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        *current = config.clone();
+        Ok(config)
+    }
+
+    /// Re-read the config file and apply its runtime subset
+    pub fn reload(&self) -> Result<RuntimeConfig, AppError> {
+        let path = self
+            .source
+            .as_ref()
+            .ok_or_else(|| AppError::BadRequest("No config file to reload from".to_string()))?;
+        let config = AppConfig::from_file(&path.to_string_lossy()).map_err(|e| {
+            AppError::BadRequest(format!(
+                "Rejected config reload from {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        self.apply(RuntimeConfig::from_app_config(&config))
+    }
+
+    /// Reload on every SIGHUP for the life of the process
+    #[cfg(unix)]
+    pub fn spawn_sighup_reloader(&self) -> std::io::Result<JoinHandle<()>> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangups = signal(SignalKind::hangup())?;
+        let handle = self.clone();
+        Ok(tokio::spawn(async move {
+            while hangups.recv().await.is_some() {
+                match handle.reload() {
+                    Ok(config) => info!(
+                        "Runtime config reloaded on SIGHUP: {:?}",
+                        config.rate_limiting
+                    ),
+                    Err(e) => error!("Runtime config reload on SIGHUP failed: {}", e),
+                }
+            }
+        }))
+    }
+}
+
+/// Read the shared settings, recovering from a poisoned lock
+pub fn read(config: &SharedRuntimeConfig) -> RwLockReadGuard<'_, RuntimeConfig> {
+    config.read().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::infrastructure::cache::CacheKeyBuilder;
+    use crate::services::infrastructure::rate_limit::EndpointRateLimiters;
+    use std::io::Write;
+
+    /// The repo's chat.yml with the message send limit replaced
+    fn config_file_with_max_requests(max_requests: u32) -> tempfile::NamedTempFile {
+        let base = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/chat.yml"))
+            .expect("chat.yml");
+        let mut yaml: serde_yaml::Value = serde_yaml::from_str(&base).unwrap();
+        yaml["features"]["rate_limiting"]["enabled"] = true.into();
+        yaml["features"]["rate_limiting"]["max_requests"] = max_requests.into();
+
+        let mut file = tempfile::Builder::new().suffix(".yml").tempfile().unwrap();
+        file.write_all(serde_yaml::to_string(&yaml).unwrap().as_bytes())
+            .unwrap();
+        file
+    }
+
+    #[tokio::test]
+    async fn reload_should_change_rate_limit_for_live_limiters() {
+        let file = config_file_with_max_requests(5);
+        let handle = RuntimeConfigHandle::new(
+            RuntimeConfig {
+                rate_limiting: RateLimitConfig::per_user(1, 60),
+                ..RuntimeConfig::default()
+            },
+            Some(file.path().to_path_buf()),
+        );
+        // Held across the reload, like the limiters in a running AppState
+        let limiters = EndpointRateLimiters::new(None, handle.shared());
+        let key = CacheKeyBuilder::rate_limit(42, "message_send");
+
+        let limiter = limiters.message_send().unwrap();
+        assert!(limiter.enforce(&key).await.is_ok());
+        assert!(limiter.enforce(&key).await.is_err());
+
+        let reloaded = handle.reload().unwrap();
+        assert_eq!(reloaded.rate_limiting.max_requests, 5);
+
+        // Existing counters carry over; the next request sees the new limit
+        let limiter = limiters.message_send().unwrap();
+        let decision = limiter.check(&key).await.unwrap();
+        assert!(decision.allowed);
+        assert_eq!(decision.limit, 5);
+        assert_eq!(decision.remaining, 2);
+    }
+
+    #[test]
+    fn invalid_reload_should_keep_current_config() {
+        let file = config_file_with_max_requests(0);
+        let handle = RuntimeConfigHandle::new(
+            RuntimeConfig {
+                rate_limiting: RateLimitConfig::per_user(3, 60),
+                ..RuntimeConfig::default()
+            },
+            Some(file.path().to_path_buf()),
+        );
+
+        assert!(matches!(handle.reload(), Err(AppError::BadRequest(_))));
+        assert_eq!(handle.current().rate_limiting.max_requests, 3);
+    }
+
+    #[test]
+    fn reload_without_source_file_should_be_rejected() {
+        let handle = RuntimeConfigHandle::new(RuntimeConfig::default(), None);
+        assert!(matches!(handle.reload(), Err(AppError::BadRequest(_))));
+    }
+}
@@ -1,10 +1,15 @@
-use super::StorageService;
+use super::{content_file_id, parse_file_id, FileStorage};
+use crate::domains::messaging::messaging_domain::AttachmentSizeLookup;
 use crate::AppError;
 use async_trait::async_trait;
-use sha2::{Digest, Sha256};
+use fechatter_core::error::CoreError;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::fs;
+use tracing::warn;
 
+/// Files under a local directory, nested by hash, with a `hash.ext` symlink at the root for
+/// `ServeDir`. Only suits a single instance
 pub struct LocalStorage {
     base_dir: PathBuf,
     url_prefix: String,
@@ -27,38 +32,47 @@ impl LocalStorage {
 
     /// Generate file path based on hash for better distribution
     fn hash_to_path(&self, hash: &str, extension: &str) -> PathBuf {
+        self.base_dir.join(Self::relative_path(hash, extension))
+    }
+
+    fn relative_path(hash: &str, extension: &str) -> PathBuf {
         let (part1, part2) = hash.split_at(3);
         let (part2, part3) = part2.split_at(3);
 
-        self.base_dir
-            .join(part1)
+        Path::new(part1)
             .join(part2)
             .join(format!("{}.{}", part3, extension))
     }
 
-    /// Calculate SHA256 hash of data
-    fn calculate_hash(&self, data: &[u8]) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(data);
-        format!("{:x}", hasher.finalize())
+    /// Path of a stored file, `NotFound` for ids that cannot name one
+    fn file_path(&self, file_id: &str) -> Result<PathBuf, AppError> {
+        let (hash, extension) = parse_file_id(file_id)
+            .ok_or_else(|| AppError::NotFound(vec!["Invalid file identifier".to_string()]))?;
+        Ok(self.hash_to_path(hash, extension))
     }
 
-    /// Extract extension from filename
-    fn extract_extension(&self, filename: &str) -> String {
-        Path::new(filename)
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or("bin")
-            .to_string()
+    /// Link `hash.ext` at the root to the nested file, so `ServeDir` serves it by its id
+    async fn link(&self, file_id: &str, hash: &str, extension: &str) {
+        let link = self.base_dir.join(file_id);
+        let target = Path::new(".").join(Self::relative_path(hash, extension));
+        if fs::symlink_metadata(&link).await.is_ok() {
+            return;
+        }
+        if let Err(e) = fs::symlink(&target, &link).await {
+            // Downloads through the API still work without it
+            warn!("Failed to link stored file {}: {}", file_id, e);
+        }
     }
 }
 
 #[async_trait]
-impl StorageService for LocalStorage {
-    async fn upload(&self, file_name: String, data: Vec<u8>) -> Result<String, AppError> {
-        let hash = self.calculate_hash(&data);
-        let extension = self.extract_extension(&file_name);
-        let file_path = self.hash_to_path(&hash, &extension);
+impl FileStorage for LocalStorage {
+    async fn put(&self, file_name: &str, data: Vec<u8>) -> Result<String, AppError> {
+        let file_id = content_file_id(file_name, &data);
+        let (hash, extension) = file_id
+            .split_once('.')
+            .expect("content file ids have an extension");
+        let file_path = self.hash_to_path(hash, extension);
 
         // Create parent directories
         if let Some(parent) = file_path.parent() {
@@ -67,33 +81,19 @@ impl StorageService for LocalStorage {
             })?;
         }
 
-        // Check if file already exists (deduplication)
-        if file_path.exists() {
-            return Ok(format!("{}/{}.{}", self.url_prefix, hash, extension));
+        // Identical content is already stored (deduplication)
+        if !file_path.exists() {
+            fs::write(&file_path, data)
+                .await
+                .map_err(|e| AppError::ChatFileError(format!("Failed to write file: {}", e)))?;
         }
+        self.link(&file_id, hash, extension).await;
 
-        // Write file
-        fs::write(&file_path, data)
-            .await
-            .map_err(|e| AppError::ChatFileError(format!("Failed to write file: {}", e)))?;
-
-        Ok(format!("{}/{}.{}", self.url_prefix, hash, extension))
+        Ok(file_id)
     }
 
-    async fn download(&self, file_id: &str) -> Result<Vec<u8>, AppError> {
-        // Extract hash and extension from file_id (format: hash.ext)
-        let parts: Vec<&str> = file_id.split('.').collect();
-        if parts.len() != 2 {
-            return Err(AppError::NotFound(vec![
-                "Invalid file identifier".to_string()
-            ]));
-        }
-
-        let hash = parts[0];
-        let extension = parts[1];
-        let file_path = self.hash_to_path(hash, extension);
-
-        fs::read(&file_path).await.map_err(|e| {
+    async fn get(&self, file_id: &str) -> Result<Vec<u8>, AppError> {
+        fs::read(self.file_path(file_id)?).await.map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 AppError::NotFound(vec![format!("File not found: {}", file_id)])
             } else {
@@ -103,18 +103,13 @@ impl StorageService for LocalStorage {
     }
 
     async fn delete(&self, file_id: &str) -> Result<(), AppError> {
-        // Extract hash and extension from file_id
-        let parts: Vec<&str> = file_id.split('.').collect();
-        if parts.len() != 2 {
-            return Err(AppError::NotFound(vec![
-                "Invalid file identifier".to_string()
-            ]));
-        }
-
-        let hash = parts[0];
-        let extension = parts[1];
-        let file_path = self.hash_to_path(hash, extension);
+        let file_path = self.file_path(file_id)?;
 
+        if let Err(e) = fs::remove_file(self.base_dir.join(file_id)).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove link of stored file {}: {}", file_id, e);
+            }
+        }
         fs::remove_file(&file_path).await.map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 AppError::NotFound(vec![format!("File not found: {}", file_id)])
@@ -124,22 +119,103 @@ impl StorageService for LocalStorage {
         })
     }
 
-    async fn get_url(&self, file_id: &str) -> Result<String, AppError> {
-        // For local storage, just return the URL with prefix
+    async fn signed_url(&self, file_id: &str, _expires_in: Duration) -> Result<String, AppError> {
+        // Served by this instance, which checks access itself, so the URL never expires
         Ok(format!("{}/{}", self.url_prefix, file_id))
     }
 
     async fn exists(&self, file_id: &str) -> Result<bool, AppError> {
-        // Extract hash and extension from file_id
-        let parts: Vec<&str> = file_id.split('.').collect();
-        if parts.len() != 2 {
-            return Ok(false);
+        match self.file_path(file_id) {
+            Ok(file_path) => Ok(file_path.exists()),
+            Err(_) => Ok(false),
         }
+    }
+}
 
-        let hash = parts[0];
-        let extension = parts[1];
-        let file_path = self.hash_to_path(hash, extension);
+#[async_trait]
+impl AttachmentSizeLookup for LocalStorage {
+    async fn attachment_size(&self, file: &str) -> Result<Option<u64>, CoreError> {
+        let file_id = file
+            .strip_prefix(&format!("{}/", self.url_prefix))
+            .unwrap_or(file);
+        // Only content hashes name stored files; anything else is not ours
+        let Some((hash, extension)) = parse_file_id(file_id) else {
+            return Ok(None);
+        };
+
+        match fs::metadata(self.hash_to_path(hash, extension)).await {
+            Ok(metadata) => Ok(Some(metadata.len())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CoreError::Internal(format!(
+                "Failed to read attachment {}: {}",
+                file, e
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn stored_files_should_round_trip_and_share_identical_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path(), "/files").unwrap();
+
+        let file_id = storage.put("notes.txt", b"hello".to_vec()).await.unwrap();
+        assert_eq!(file_id, content_file_id("notes.txt", b"hello"));
+        assert_eq!(
+            storage.put("copy.txt", b"hello".to_vec()).await.unwrap(),
+            file_id
+        );
+        assert!(storage.exists(&file_id).await.unwrap());
+        assert_eq!(storage.get(&file_id).await.unwrap(), b"hello");
+        // ServeDir finds it by id through the root link
+        assert_eq!(std::fs::read(dir.path().join(&file_id)).unwrap(), b"hello");
+        assert_eq!(
+            storage
+                .attachment_size(&format!("/files/{}", file_id))
+                .await
+                .unwrap(),
+            Some(5)
+        );
+        assert_eq!(
+            storage
+                .signed_url(&file_id, Duration::from_secs(60))
+                .await
+                .unwrap(),
+            format!("/files/{}", file_id)
+        );
+
+        storage.delete(&file_id).await.unwrap();
+        assert!(!storage.exists(&file_id).await.unwrap());
+        assert!(!dir.path().join(&file_id).exists());
+        assert!(matches!(
+            storage.get(&file_id).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            storage.delete(&file_id).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn ids_outside_the_storage_scheme_should_not_resolve() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path(), "/files").unwrap();
 
-        Ok(file_path.exists())
+        for file_id in ["../secret.txt", "abc.png", "nodot"] {
+            assert!(!storage.exists(file_id).await.unwrap(), "{file_id}");
+            assert!(storage.get(file_id).await.is_err(), "{file_id}");
+        }
+        assert_eq!(
+            storage
+                .attachment_size("https://example.com/a.png")
+                .await
+                .unwrap(),
+            None
+        );
     }
 }
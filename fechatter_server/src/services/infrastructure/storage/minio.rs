@@ -1,6 +1,10 @@
-use super::{StorageService, s3::S3Storage};
+use super::{s3::S3Storage, FileStorage};
+use crate::config::S3StorageConfig;
+use crate::domains::messaging::messaging_domain::AttachmentSizeLookup;
 use crate::error::AppError;
 use async_trait::async_trait;
+use fechatter_core::error::CoreError;
+use std::time::Duration;
 
 /// MinIO storage service
 /// MinIO is S3-compatible, so we just wrap S3Storage with MinIO-specific defaults
@@ -15,22 +19,25 @@ impl MinIOStorage {
     bucket: String,
     access_key_id: String,
     secret_access_key: String,
+    url_prefix: &str,
   ) -> Result<Self, AppError> {
     // MinIO typically uses "us-east-1" as the default region
-    let inner = S3Storage::new_custom(
+    let config = S3StorageConfig {
       bucket,
-      endpoint_url,
-      access_key_id,
-      secret_access_key,
-      "us-east-1".to_string(),
-    )
-    .await?;
+      region: "us-east-1".to_string(),
+      endpoint_url: Some(endpoint_url),
+      access_key_id: Some(access_key_id),
+      secret_access_key: Some(secret_access_key),
+      key_prefix: String::new(),
+      signed_url_ttl_secs: 3600,
+    };
+    let inner = S3Storage::from_config(&config, url_prefix).await?;
 
     Ok(Self { inner })
   }
 
   /// Create MinIO storage from environment variables
-  pub async fn from_env() -> Result<Self, AppError> {
+  pub async fn from_env(url_prefix: &str) -> Result<Self, AppError> {
     let endpoint_url =
       std::env::var("MINIO_ENDPOINT").unwrap_or_else(|_| "http://localhost:9000".to_string());
     let bucket = std::env::var("MINIO_BUCKET")
@@ -40,7 +47,7 @@ impl MinIOStorage {
     let secret_key = std::env::var("MINIO_SECRET_KEY")
       .map_err(|_| AppError::InvalidInput("MINIO_SECRET_KEY not set".to_string()))?;
 
-    Self::new(endpoint_url, bucket, access_key, secret_key).await
+    Self::new(endpoint_url, bucket, access_key, secret_key, url_prefix).await
   }
 
   /// Get the underlying S3Storage for direct access if needed
@@ -50,21 +57,21 @@ impl MinIOStorage {
 }
 
 #[async_trait]
-impl StorageService for MinIOStorage {
-  async fn upload(&self, file_name: String, data: Vec<u8>) -> Result<String, AppError> {
-    self.inner.upload(file_name, data).await
+impl FileStorage for MinIOStorage {
+  async fn put(&self, file_name: &str, data: Vec<u8>) -> Result<String, AppError> {
+    self.inner.put(file_name, data).await
   }
 
-  async fn download(&self, file_id: &str) -> Result<Vec<u8>, AppError> {
-    self.inner.download(file_id).await
+  async fn get(&self, file_id: &str) -> Result<Vec<u8>, AppError> {
+    self.inner.get(file_id).await
   }
 
   async fn delete(&self, file_id: &str) -> Result<(), AppError> {
     self.inner.delete(file_id).await
   }
 
-  async fn get_url(&self, file_id: &str) -> Result<String, AppError> {
-    self.inner.get_url(file_id).await
+  async fn signed_url(&self, file_id: &str, expires_in: Duration) -> Result<String, AppError> {
+    self.inner.signed_url(file_id, expires_in).await
   }
 
   async fn exists(&self, file_id: &str) -> Result<bool, AppError> {
@@ -72,7 +79,14 @@ impl StorageService for MinIOStorage {
   }
 }
 
-// Delegate all StorageService methods to the inner S3Storage
+#[async_trait]
+impl AttachmentSizeLookup for MinIOStorage {
+  async fn attachment_size(&self, file: &str) -> Result<Option<u64>, CoreError> {
+    self.inner.attachment_size(file).await
+  }
+}
+
+// Delegate all FileStorage methods to the inner S3Storage
 impl std::ops::Deref for MinIOStorage {
   type Target = S3Storage;
 
@@ -1,35 +1,128 @@
+//! # File Storage
+//!
+//! **Responsibility**: Keep uploaded files somewhere every server instance can reach them
+//! **Naming**: Files are content addressed. `put` names a file `hash.ext` after the SHA-256 of
+//! its bytes, and that file id is what messages, scans and download URLs refer to, whichever
+//! backend holds the bytes
+//! **Backends**: The local filesystem (one instance, served by `ServeDir`) or, with the `s3`
+//! feature, an S3-compatible bucket shared by every replica; picked by `storage.backend`
+
+use crate::config::{StorageBackend, StorageConfig};
+use crate::domains::messaging::messaging_domain::AttachmentSizeLookup;
 use crate::AppError;
 use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
-// Storage service trait for different backends
+/// Where uploaded files are kept; backends also size attachments for the per-message budget
 #[async_trait]
-pub trait StorageService: Send + Sync {
-    /// Upload a file and return its identifier/URL
-    async fn upload(&self, file_name: String, data: Vec<u8>) -> Result<String, AppError>;
+pub trait FileStorage: AttachmentSizeLookup {
+    /// Store `data` and return its file id; storing the same bytes again keeps one copy
+    async fn put(&self, file_name: &str, data: Vec<u8>) -> Result<String, AppError>;
 
-    /// Download a file by identifier
-    async fn download(&self, file_id: &str) -> Result<Vec<u8>, AppError>;
+    /// Bytes of a stored file, `AppError::NotFound` when there is none
+    async fn get(&self, file_id: &str) -> Result<Vec<u8>, AppError>;
 
-    /// Delete a file by identifier
+    /// Remove a stored file
     async fn delete(&self, file_id: &str) -> Result<(), AppError>;
 
-    /// Get public URL for a file (for downloads)
-    async fn get_url(&self, file_id: &str) -> Result<String, AppError>;
+    /// URL a client can fetch the file from directly for at least `expires_in`
+    async fn signed_url(&self, file_id: &str, expires_in: Duration) -> Result<String, AppError>;
 
     /// Check if a file exists
     async fn exists(&self, file_id: &str) -> Result<bool, AppError>;
 }
 
+/// Open the backend selected by `storage.backend`
+pub async fn open_storage(config: &StorageConfig) -> Result<Arc<dyn FileStorage>, AppError> {
+    match config.backend {
+        StorageBackend::Local => Ok(Arc::new(LocalStorage::new(
+            &config.path,
+            &config.url_prefix,
+        )?)),
+        #[cfg(feature = "s3")]
+        StorageBackend::S3 => {
+            let s3 = config.s3.as_ref().ok_or_else(|| {
+                AppError::Configuration(
+                    "storage.s3 must be set for the s3 storage backend".to_string(),
+                )
+            })?;
+            Ok(Arc::new(
+                S3Storage::from_config(s3, &config.url_prefix).await?,
+            ))
+        }
+        #[cfg(not(feature = "s3"))]
+        StorageBackend::S3 => Err(AppError::Configuration(
+            "The s3 storage backend needs a build with the `s3` feature".to_string(),
+        )),
+    }
+}
+
+/// File id of `data` uploaded as `file_name`: `{sha256}.{extension}`
+pub fn content_file_id(file_name: &str, data: &[u8]) -> String {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .filter(|ext| !ext.is_empty() && ext.bytes().all(|b| b.is_ascii_alphanumeric()))
+        .unwrap_or("bin");
+    format!("{:x}.{}", Sha256::digest(data), extension)
+}
+
+/// Hash and extension of a well-formed file id, `None` for anything that could not have come
+/// from `content_file_id`
+pub fn parse_file_id(file_id: &str) -> Option<(&str, &str)> {
+    let (hash, extension) = file_id.split_once('.')?;
+    let well_formed = hash.len() >= 6
+        && hash.bytes().all(|b| b.is_ascii_hexdigit())
+        && !extension.is_empty()
+        && extension.bytes().all(|b| b.is_ascii_alphanumeric());
+    well_formed.then_some((hash, extension))
+}
+
 // Local storage implementation
 pub mod local;
-// pub mod minio;  // Temporarily disabled - depends on S3Storage
-// pub mod s3;  // Temporarily disabled to reduce build memory usage
+#[cfg(feature = "s3")]
+pub mod minio;
+#[cfg(feature = "s3")]
+pub mod s3;
 
 // Re-export for convenience
 pub use local::LocalStorage;
-// pub use minio::MinIOStorage;  // Temporarily disabled - depends on S3Storage
-// pub use s3::S3Storage;  // Temporarily disabled to reduce build memory usage
+#[cfg(feature = "s3")]
+pub use minio::MinIOStorage;
+#[cfg(feature = "s3")]
+pub use s3::S3Storage;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-// TODO: Future storage implementations
-// pub mod s3;
-// pub mod minio;
+    #[test]
+    fn file_ids_should_name_content_by_hash_and_extension() {
+        let id = content_file_id("Report.PDF", b"hello");
+        assert_eq!(
+            id,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824.PDF"
+        );
+        assert_eq!(content_file_id("other-name.PDF", b"hello"), id);
+        assert!(content_file_id("no_extension", b"hello").ends_with(".bin"));
+
+        assert_eq!(
+            parse_file_id(&id),
+            Some((
+                "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+                "PDF"
+            ))
+        );
+        for bad in [
+            "abc.png",
+            "not-hex-at-all.png",
+            "2cf24dba.",
+            "../../etc/passwd",
+        ] {
+            assert_eq!(parse_file_id(bad), None, "{bad}");
+        }
+    }
+}
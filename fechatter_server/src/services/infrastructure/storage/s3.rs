@@ -1,124 +1,115 @@
 use async_trait::async_trait;
-use aws_config::Region;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_s3::config::{RequestChecksumCalculation, ResponseChecksumValidation};
+use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::primitives::ByteStream;
-use aws_sdk_s3::{Client, Config};
-use std::path::Path;
+use aws_sdk_s3::Client;
+use std::time::Duration;
 
-use super::StorageService;
+use super::{content_file_id, parse_file_id, FileStorage};
+use crate::config::S3StorageConfig;
+use crate::domains::messaging::messaging_domain::AttachmentSizeLookup;
 use crate::error::AppError;
+use fechatter_core::error::CoreError;
 
 /// S3-compatible storage service
-/// Works with AWS S3, Cloudflare R2, and other S3-compatible services
+/// Works with AWS S3, Cloudflare R2, MinIO and other S3-compatible services. Objects are keyed
+/// by file id under `key_prefix`, so every replica resolves the same id to the same object
 pub struct S3Storage {
   client: Client,
   bucket: String,
-  endpoint_url: Option<String>,
-  public_url_base: Option<String>,
+  key_prefix: String,
+  url_prefix: String,
 }
 
 impl S3Storage {
-  /// Create new S3 storage for AWS S3
-  pub async fn new_aws(bucket: String, region: String) -> Result<Self, AppError> {
-    let config = aws_config::from_env()
-      .region(Region::new(region))
-      .load()
-      .await;
-
-    let client = Client::new(&config);
-
-    Ok(Self {
+  pub fn new(client: Client, bucket: String, key_prefix: String, url_prefix: String) -> Self {
+    Self {
       client,
       bucket,
-      endpoint_url: None,
-      public_url_base: None,
-    })
+      key_prefix,
+      url_prefix,
+    }
   }
 
-  /// Create new S3 storage for Cloudflare R2
-  pub async fn new_r2(
-    bucket: String,
-    account_id: String,
-    access_key_id: String,
-    secret_access_key: String,
-  ) -> Result<Self, AppError> {
-    let endpoint_url = format!("https://{}.r2.cloudflarestorage.com", account_id);
-
-    let credentials =
-      aws_credential_types::Credentials::new(access_key_id, secret_access_key, None, None, "r2");
-
-    let config = Config::builder()
-      .endpoint_url(&endpoint_url)
-      .credentials_provider(credentials)
-      .region(Region::new("auto"))
-      .build();
+  /// Client for the configured bucket; static credentials when set, the AWS default chain
+  /// otherwise. `url_prefix` is the public prefix of stored file URLs, as for local storage
+  pub async fn from_config(config: &S3StorageConfig, url_prefix: &str) -> Result<Self, AppError> {
+    let mut loader =
+      aws_config::defaults(BehaviorVersion::latest()).region(Region::new(config.region.clone()));
+    if let (Some(access_key_id), Some(secret_access_key)) =
+      (&config.access_key_id, &config.secret_access_key)
+    {
+      loader = loader.credentials_provider(aws_credential_types::Credentials::new(
+        access_key_id,
+        secret_access_key,
+        None,
+        None,
+        "fechatter",
+      ));
+    }
+    let shared = loader.load().await;
 
-    let client = Client::from_conf(config);
+    let mut s3_config = aws_sdk_s3::config::Builder::from(&shared);
+    if let Some(endpoint_url) = &config.endpoint_url {
+      // Custom endpoints (MinIO) rarely resolve bucket subdomains, and many reject the
+      // default flexible checksums
+      s3_config = s3_config
+        .endpoint_url(endpoint_url)
+        .force_path_style(true)
+        .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+        .response_checksum_validation(ResponseChecksumValidation::WhenRequired);
+    }
 
-    Ok(Self {
-      client,
-      bucket,
-      endpoint_url: Some(endpoint_url.clone()),
-      public_url_base: Some(format!("https://pub-{}.r2.dev", account_id)),
-    })
+    Ok(Self::new(
+      Client::from_conf(s3_config.build()),
+      config.bucket.clone(),
+      config.key_prefix.clone(),
+      url_prefix.to_string(),
+    ))
   }
 
-  /// Create new S3 storage with custom endpoint (for MinIO, etc.)
-  pub async fn new_custom(
-    bucket: String,
-    endpoint_url: String,
-    access_key_id: String,
-    secret_access_key: String,
-    region: String,
-  ) -> Result<Self, AppError> {
-    let credentials = aws_credential_types::Credentials::new(
-      access_key_id,
-      secret_access_key,
-      None,
-      None,
-      "custom",
-    );
-
-    let config = Config::builder()
-            .endpoint_url(&endpoint_url)
-            .credentials_provider(credentials)
-            .region(Region::new(region))
-            .force_path_style(true) // Required for MinIO
-            .build();
-
-    let client = Client::from_conf(config);
-
-    Ok(Self {
-      client,
-      bucket,
-      endpoint_url: Some(endpoint_url.clone()),
-      public_url_base: Some(endpoint_url),
-    })
+  /// Object key of a file id, `NotFound` for ids that cannot name a stored file
+  fn key(&self, file_id: &str) -> Result<String, AppError> {
+    parse_file_id(file_id)
+      .map(|_| format!("{}{}", self.key_prefix, file_id))
+      .ok_or_else(|| AppError::NotFound(vec!["Invalid file identifier".to_string()]))
   }
 
-  fn generate_key(&self, file_name: &str) -> String {
-    let timestamp = chrono::Utc::now().timestamp();
-    let extension = Path::new(file_name)
-      .extension()
-      .and_then(|ext| ext.to_str())
-      .unwrap_or("bin");
-
-    format!(
-      "files/{}/{}.{}",
-      chrono::Utc::now().format("%Y/%m/%d"),
-      timestamp,
-      extension
-    )
+  /// Size of the object at `key`, `None` when there is none
+  async fn head(&self, key: &str) -> Result<Option<u64>, AppError> {
+    match self
+      .client
+      .head_object()
+      .bucket(&self.bucket)
+      .key(key)
+      .send()
+      .await
+    {
+      Ok(head) => Ok(Some(head.content_length().unwrap_or(0).max(0) as u64)),
+      Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found()) => Ok(None),
+      Err(e) => Err(AppError::ExternalServiceError(format!(
+        "S3 head error: {}",
+        e
+      ))),
+    }
   }
 }
 
 #[async_trait]
-impl StorageService for S3Storage {
-  async fn upload(&self, file_name: String, data: Vec<u8>) -> Result<String, AppError> {
-    let key = self.generate_key(&file_name);
-    let content_type = mime_guess::from_path(&file_name)
+impl FileStorage for S3Storage {
+  async fn put(&self, file_name: &str, data: Vec<u8>) -> Result<String, AppError> {
+    let file_id = content_file_id(file_name, &data);
+    let key = self.key(&file_id)?;
+
+    // Identical content is already stored (deduplication)
+    if self.head(&key).await?.is_some() {
+      return Ok(file_id);
+    }
+
+    let content_type = mime_guess::from_path(file_name)
       .first_or_octet_stream()
       .to_string();
-
     self
       .client
       .put_object()
@@ -130,30 +121,23 @@ impl StorageService for S3Storage {
       .await
       .map_err(|e| AppError::ExternalServiceError(format!("S3 upload error: {}", e)))?;
 
-    // Return public URL if available, otherwise return the key
-    if let Some(base) = &self.public_url_base {
-      Ok(format!("{}/{}", base, key))
-    } else {
-      Ok(key)
-    }
+    Ok(file_id)
   }
 
-  async fn download(&self, file_id: &str) -> Result<Vec<u8>, AppError> {
-    // Extract key from URL if needed
-    let key = if file_id.starts_with("http") {
-      file_id.split('/').last().unwrap_or(file_id)
-    } else {
-      file_id
-    };
-
+  async fn get(&self, file_id: &str) -> Result<Vec<u8>, AppError> {
     let response = self
       .client
       .get_object()
       .bucket(&self.bucket)
-      .key(key)
+      .key(self.key(file_id)?)
       .send()
       .await
-      .map_err(|e| AppError::ExternalServiceError(format!("S3 download error: {}", e)))?;
+      .map_err(|e| match e.as_service_error() {
+        Some(e) if e.is_no_such_key() => {
+          AppError::NotFound(vec![format!("File not found: {}", file_id)])
+        }
+        _ => AppError::ExternalServiceError(format!("S3 download error: {}", e)),
+      })?;
 
     let data = response
       .body
@@ -165,18 +149,11 @@ impl StorageService for S3Storage {
   }
 
   async fn delete(&self, file_id: &str) -> Result<(), AppError> {
-    // Extract key from URL if needed
-    let key = if file_id.starts_with("http") {
-      file_id.split('/').last().unwrap_or(file_id)
-    } else {
-      file_id
-    };
-
     self
       .client
       .delete_object()
       .bucket(&self.bucket)
-      .key(key)
+      .key(self.key(file_id)?)
       .send()
       .await
       .map_err(|e| AppError::ExternalServiceError(format!("S3 delete error: {}", e)))?;
@@ -184,50 +161,164 @@ impl StorageService for S3Storage {
     Ok(())
   }
 
-  async fn get_url(&self, file_id: &str) -> Result<String, AppError> {
-    // For public buckets, return the public URL
-    if let Some(base) = &self.public_url_base {
-      let key = if file_id.starts_with("http") {
-        file_id.split('/').last().unwrap_or(file_id)
-      } else {
-        file_id
-      };
-      return Ok(format!("{}/{}", base, key));
-    }
-
-    // For private buckets, generate presigned URL
+  async fn signed_url(&self, file_id: &str, expires_in: Duration) -> Result<String, AppError> {
+    let presigning = PresigningConfig::expires_in(expires_in)
+      .map_err(|e| AppError::ExternalServiceError(format!("Presigning error: {}", e)))?;
     let presigned_request = self
       .client
       .get_object()
       .bucket(&self.bucket)
-      .key(file_id)
-      .presigned(
-        aws_sdk_s3::presigning::PresigningConfig::expires_in(std::time::Duration::from_secs(3600))
-          .map_err(|e| AppError::ExternalServiceError(format!("Presigning error: {}", e)))?,
-      )
+      .key(self.key(file_id)?)
+      .presigned(presigning)
       .await
-      .map_err(|e| AppError::ExternalServiceError(format!("Failed to generate presigned URL: {}", e)))?;
+      .map_err(|e| {
+        AppError::ExternalServiceError(format!("Failed to generate presigned URL: {}", e))
+      })?;
 
     Ok(presigned_request.uri().to_string())
   }
 
   async fn exists(&self, file_id: &str) -> Result<bool, AppError> {
-    let key = if file_id.starts_with("http") {
-      file_id.split('/').last().unwrap_or(file_id)
-    } else {
-      file_id
+    match self.key(file_id) {
+      Ok(key) => Ok(self.head(&key).await?.is_some()),
+      Err(_) => Ok(false),
+    }
+  }
+}
+
+#[async_trait]
+impl AttachmentSizeLookup for S3Storage {
+  async fn attachment_size(&self, file: &str) -> Result<Option<u64>, CoreError> {
+    let file_id = file
+      .strip_prefix(&format!("{}/", self.url_prefix))
+      .unwrap_or(file);
+    let Ok(key) = self.key(file_id) else {
+      return Ok(None);
     };
 
-    match self
-      .client
-      .head_object()
-      .bucket(&self.bucket)
-      .key(key)
-      .send()
+    self
+      .head(&key)
       .await
-    {
-      Ok(_) => Ok(true),
-      Err(_) => Ok(false), // Object doesn't exist
+      .map_err(|e| CoreError::Internal(format!("Failed to read attachment {}: {}", file, e)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{Method, StatusCode},
+    response::{IntoResponse, Response},
+    routing::any,
+    Router,
+  };
+  use std::collections::HashMap;
+  use std::sync::{Arc, Mutex};
+
+  type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+  /// Just enough of the S3 REST API (path-style object PUT, GET, HEAD and DELETE) to stand in
+  /// for a bucket; signatures are not checked
+  async fn mock_object(
+    State(objects): State<Objects>,
+    Path((_bucket, key)): Path<(String, String)>,
+    method: Method,
+    body: Bytes,
+  ) -> Response {
+    let mut objects = objects.lock().unwrap();
+    match method {
+      Method::PUT => {
+        objects.insert(key, body.to_vec());
+        StatusCode::OK.into_response()
+      }
+      Method::DELETE => {
+        objects.remove(&key);
+        StatusCode::NO_CONTENT.into_response()
+      }
+      Method::GET | Method::HEAD => match objects.get(&key) {
+        Some(data) if method == Method::GET => data.clone().into_response(),
+        Some(data) => ([("content-length", data.len().to_string())]).into_response(),
+        None if method == Method::HEAD => StatusCode::NOT_FOUND.into_response(),
+        None => (
+          StatusCode::NOT_FOUND,
+          [("content-type", "application/xml")],
+          "<Error><Code>NoSuchKey</Code><Message>The specified key does not exist.</Message></Error>",
+        )
+          .into_response(),
+      },
+      _ => StatusCode::METHOD_NOT_ALLOWED.into_response(),
     }
   }
+
+  async fn mock_bucket() -> (S3Storage, Objects, String) {
+    let objects = Objects::default();
+    let app = Router::new()
+      .route("/{bucket}/{*key}", any(mock_object))
+      .with_state(objects.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let config = S3StorageConfig {
+      bucket: "uploads".to_string(),
+      region: "us-east-1".to_string(),
+      endpoint_url: Some(endpoint.clone()),
+      access_key_id: Some("test".to_string()),
+      secret_access_key: Some("test".to_string()),
+      key_prefix: "files/".to_string(),
+      signed_url_ttl_secs: 60,
+    };
+    (
+      S3Storage::from_config(&config, "/files").await.unwrap(),
+      objects,
+      endpoint,
+    )
+  }
+
+  #[tokio::test]
+  async fn stored_files_should_round_trip_through_the_bucket() {
+    let (storage, objects, _) = mock_bucket().await;
+
+    let file_id = storage.put("notes.txt", b"hello".to_vec()).await.unwrap();
+    assert_eq!(file_id, content_file_id("notes.txt", b"hello"));
+    assert_eq!(
+      objects.lock().unwrap().get(&format!("files/{}", file_id)),
+      Some(&b"hello".to_vec())
+    );
+    assert!(storage.exists(&file_id).await.unwrap());
+    assert_eq!(storage.get(&file_id).await.unwrap(), b"hello");
+    assert_eq!(
+      storage
+        .attachment_size(&format!("/files/{}", file_id))
+        .await
+        .unwrap(),
+      Some(5)
+    );
+
+    storage.delete(&file_id).await.unwrap();
+    assert!(!storage.exists(&file_id).await.unwrap());
+    assert!(matches!(
+      storage.get(&file_id).await,
+      Err(AppError::NotFound(_))
+    ));
+  }
+
+  #[tokio::test]
+  async fn signed_url_should_fetch_the_object_until_it_expires() {
+    let (storage, _, endpoint) = mock_bucket().await;
+    let file_id = storage.put("photo.png", b"png".to_vec()).await.unwrap();
+
+    let url = storage
+      .signed_url(&file_id, Duration::from_secs(300))
+      .await
+      .unwrap();
+    assert!(url.starts_with(&format!("{}/uploads/files/{}", endpoint, file_id)));
+    assert!(url.contains("X-Amz-Expires=300"));
+    assert!(url.contains("X-Amz-Signature="));
+
+    let fetched = reqwest::get(&url).await.unwrap().bytes().await.unwrap();
+    assert_eq!(&fetched[..], b"png");
+  }
 }
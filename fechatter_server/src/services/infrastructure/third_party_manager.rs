@@ -4,7 +4,7 @@ use crate::{
     error::AppError,
     services::{
         ai::{CohereClient, HuggingFaceClient, OpenAIClient},
-        infrastructure::storage::{/*MinIOStorage, S3Storage,*/ StorageService},
+        infrastructure::storage::{/*MinIOStorage, S3Storage,*/ FileStorage},
     },
 };
 use fechatter_core::AIService;
@@ -192,7 +192,7 @@ impl ThirdPartyManager {
 
     /// Get storage service based on configuration priority
     /* Temporarily disabled - depends on S3Storage and MinIOStorage
-    pub async fn storage(&self) -> Result<Arc<dyn StorageService>, AppError> {
+    pub async fn storage(&self) -> Result<Arc<dyn FileStorage>, AppError> {
       // Priority: MinIO > AWS S3 > Cloudflare R2
       if let Some(minio_config) = &self.config.minio_config {
         let storage = self
@@ -208,7 +208,7 @@ impl ThirdPartyManager {
             Ok::<Arc<MinIOStorage>, AppError>(Arc::new(storage))
           })
           .await?;
-        return Ok(storage.clone() as Arc<dyn StorageService>);
+        return Ok(storage.clone() as Arc<dyn FileStorage>);
       }
 
       if let Some(aws_config) = &self.config.aws_config {
@@ -220,7 +220,7 @@ impl ThirdPartyManager {
             Ok::<Arc<S3Storage>, AppError>(Arc::new(storage))
           })
           .await?;
-        return Ok(storage.clone() as Arc<dyn StorageService>);
+        return Ok(storage.clone() as Arc<dyn FileStorage>);
       }
 
       if let Some(r2_config) = &self.config.cloudflare_r2_config {
@@ -237,7 +237,7 @@ impl ThirdPartyManager {
             Ok::<Arc<S3Storage>, AppError>(Arc::new(storage))
           })
           .await?;
-        return Ok(storage.clone() as Arc<dyn StorageService>);
+        return Ok(storage.clone() as Arc<dyn FileStorage>);
       }
 
       Err(AppError::InvalidInput(
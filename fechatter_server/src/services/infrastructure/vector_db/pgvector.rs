@@ -35,17 +35,6 @@ impl EmbeddingVector {
         Ok(Self(Vector::from(data.to_vec())))
     }
 
-    /// Creates owned embedding vector from Vec
-    fn from_vec(data: Vec<f32>, expected_dim: usize) -> Result<Self, VectorDbError> {
-        if data.len() != expected_dim {
-            return Err(VectorDbError::Validation(format!(
-                "Vector dimension mismatch: expected {expected_dim}, got {}",
-                data.len()
-            )));
-        }
-        Ok(Self(Vector::from(data)))
-    }
-
     /// Access the underlying pgvector::Vector for database operations (zero-copy)
     fn as_pgvector(&self) -> &Vector {
         &self.0
@@ -498,16 +487,37 @@ impl MessageVectorRepository for PgVectorDatabase {
         source_chat_id: ChatId,
         author_user_id: UserId,
         message_content: &str,
-        _provided_timestamp: DateTime<Utc>,
+        provided_timestamp: DateTime<Utc>,
     ) -> Result<(), CoreError> {
-        let indexing_time = TimeManager::now();
-
         // TODO: 生产环境中应该调用embedding服务
         // 目前使用零向量作为占位符
         let placeholder_vector = vec![0.0f32; self.config.dimension];
-        let validated_placeholder =
-            EmbeddingVector::from_vec(placeholder_vector, self.config.dimension)
-                .map_err(CoreError::VectorDbError)?;
+
+        self.store_message_embedding(
+            target_message_id,
+            source_chat_id,
+            author_user_id,
+            message_content,
+            &placeholder_vector,
+            provided_timestamp,
+        )
+        .await
+    }
+
+    /// Stores a precomputed embedding as chunk 0 of a message, replacing any previous one
+    async fn store_message_embedding(
+        &self,
+        target_message_id: MessageId,
+        source_chat_id: ChatId,
+        author_user_id: UserId,
+        message_content: &str,
+        embedding: &[f32],
+        _provided_timestamp: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let indexing_time = TimeManager::now();
+        let validated_embedding = self
+            .validate_vector(embedding)
+            .map_err(CoreError::VectorDbError)?;
 
         let message_metadata = serde_json::json!({
             "message_id": target_message_id.0,
@@ -533,7 +543,7 @@ impl MessageVectorRepository for PgVectorDatabase {
         .bind(source_chat_id.0)
         .bind(0) // Default chunk index
         .bind(message_content)
-        .bind(validated_placeholder.as_pgvector())
+        .bind(validated_embedding.as_pgvector())
         .bind(&message_metadata)
         .bind(indexing_time)
         .execute(&self.pool)
@@ -0,0 +1,360 @@
+//! # Webhook Delivery
+//!
+//! **Responsibility**: POST signed event payloads to subscribed webhook URLs
+//! **Scope**: One event at a time; failed requests are retried with exponential backoff and the
+//! final outcome per subscription is returned to the caller for bookkeeping. URLs that resolve to
+//! internal addresses are never called, see `super::super::outbound`
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::config::WebhookConfig;
+use crate::domains::workspace::webhooks::{WebhookEventType, WebhookSubscription};
+use crate::services::infrastructure::outbound::guarded_client_builder;
+use fechatter_core::utils::{check_outbound_url, OutboundAllowlist, OutboundUrlError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Event name header
+pub const EVENT_HEADER: &str = "X-Fechatter-Event";
+/// Unique id of the event, identical across retries so receivers can deduplicate
+pub const DELIVERY_HEADER: &str = "X-Fechatter-Delivery";
+/// `sha256=<hex HMAC-SHA256 of the body>` keyed with the subscription secret
+pub const SIGNATURE_HEADER: &str = "X-Fechatter-Signature";
+
+/// Body POSTed to webhook URLs
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub id: Uuid,
+    pub event: WebhookEventType,
+    pub workspace_id: i64,
+    pub occurred_at: DateTime<Utc>,
+    pub data: Value,
+}
+
+impl WebhookEvent {
+    pub fn new(
+        event: WebhookEventType,
+        workspace_id: i64,
+        occurred_at: DateTime<Utc>,
+        data: Value,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event,
+            workspace_id,
+            occurred_at,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookDeliveryError {
+    #[error("request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("endpoint responded with status {0}")]
+    Status(u16),
+
+    #[error(transparent)]
+    Blocked(#[from] OutboundUrlError),
+}
+
+/// `X-Fechatter-Signature` value for `body`
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can handle any key size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Result of delivering one event to one subscription, after retries
+#[derive(Debug)]
+pub struct DeliveryOutcome {
+    pub subscription_id: i64,
+    pub attempts: u32,
+    pub result: Result<(), WebhookDeliveryError>,
+}
+
+/// Sends webhook events over HTTP
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    allowlist: OutboundAllowlist,
+    max_attempts: u32,
+    retry_backoff: Duration,
+}
+
+impl WebhookDispatcher {
+    /// Redirects are not followed; a webhook URL must answer itself
+    pub fn new(
+        config: &WebhookConfig,
+        allowlist: OutboundAllowlist,
+    ) -> Result<Self, WebhookDeliveryError> {
+        let client = guarded_client_builder(allowlist.clone(), 0)
+            .timeout(Duration::from_millis(config.timeout_ms.max(1)))
+            .build()?;
+
+        Ok(Self {
+            client,
+            allowlist,
+            max_attempts: config.max_attempts.max(1),
+            retry_backoff: Duration::from_millis(config.retry_backoff_ms),
+        })
+    }
+
+    /// Deliver `event` to every subscription that accepts it
+    pub async fn dispatch(
+        &self,
+        subscriptions: &[WebhookSubscription],
+        event: &WebhookEvent,
+    ) -> Vec<DeliveryOutcome> {
+        let body = match serde_json::to_vec(event) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("Failed to serialize webhook event {}: {}", event.id, e);
+                return Vec::new();
+            }
+        };
+
+        let deliveries = subscriptions
+            .iter()
+            .filter(|subscription| subscription.accepts(event.event))
+            .map(|subscription| self.deliver(subscription, event, &body));
+
+        futures::future::join_all(deliveries).await
+    }
+
+    /// POST `body` to one subscription, retrying failures with exponential backoff
+    async fn deliver(
+        &self,
+        subscription: &WebhookSubscription,
+        event: &WebhookEvent,
+        body: &[u8],
+    ) -> DeliveryOutcome {
+        // Checked on every delivery: the host may resolve elsewhere than at registration
+        if let Err(e) = check_outbound_url(&subscription.url, &self.allowlist).await {
+            warn!(
+                "Refusing webhook delivery {} to subscription {}: {}",
+                event.id, subscription.id, e
+            );
+            return DeliveryOutcome {
+                subscription_id: subscription.id,
+                attempts: 0,
+                result: Err(e.into()),
+            };
+        }
+
+        let signature = sign_payload(&subscription.secret, body);
+        let mut backoff = self.retry_backoff;
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            let result = self
+                .client
+                .post(&subscription.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, event.event.as_str())
+                .header(DELIVERY_HEADER, event.id.to_string())
+                .header(SIGNATURE_HEADER, &signature)
+                .body(body.to_vec())
+                .send()
+                .await
+                .map_err(WebhookDeliveryError::from)
+                .and_then(|response| match response.status() {
+                    status if status.is_success() => Ok(()),
+                    status => Err(WebhookDeliveryError::Status(status.as_u16())),
+                });
+
+            match result {
+                Ok(()) => {
+                    debug!(
+                        "Delivered webhook event {} to subscription {}",
+                        event.id, subscription.id
+                    );
+                    return DeliveryOutcome {
+                        subscription_id: subscription.id,
+                        attempts,
+                        result: Ok(()),
+                    };
+                }
+                Err(e) if attempts < self.max_attempts => {
+                    warn!(
+                        "Webhook delivery {} to subscription {} failed (attempt {}/{}): {}",
+                        event.id, subscription.id, attempts, self.max_attempts, e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => {
+                    return DeliveryOutcome {
+                        subscription_id: subscription.id,
+                        attempts,
+                        result: Err(e),
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, extract::State, http::HeaderMap, routing::post, Router};
+    use fechatter_core::contracts::events::{HmacSha256Verifier, SignatureVerifier};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    type Received = Arc<Mutex<Vec<(String, HeaderMap, Bytes)>>>;
+
+    /// Receiver recording every request per path
+    async fn spawn_receiver() -> (String, Received) {
+        async fn record(
+            State(received): State<Received>,
+            uri: axum::http::Uri,
+            headers: HeaderMap,
+            body: Bytes,
+        ) {
+            received
+                .lock()
+                .unwrap()
+                .push((uri.path().to_string(), headers, body));
+        }
+
+        let received: Received = Arc::default();
+        let app = Router::new()
+            .route("/{hook}", post(record))
+            .with_state(received.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (base, received)
+    }
+
+    /// The test receiver listens on loopback, which is refused unless allowlisted
+    fn local_dispatcher(config: &WebhookConfig) -> WebhookDispatcher {
+        WebhookDispatcher::new(config, OutboundAllowlist::new(["127.0.0.1"])).unwrap()
+    }
+
+    fn subscription(
+        id: i64,
+        url: String,
+        event_types: Vec<WebhookEventType>,
+    ) -> WebhookSubscription {
+        WebhookSubscription {
+            id,
+            workspace_id: 1,
+            url,
+            event_types,
+            secret: format!("secret-{}", id),
+            enabled: true,
+            consecutive_failures: 0,
+            created_by: 1,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribed_webhook_should_receive_signed_payload_for_matching_events_only() {
+        let (base, received) = spawn_receiver().await;
+        let subscriptions = vec![
+            subscription(
+                1,
+                format!("{}/messages", base),
+                vec![WebhookEventType::MessageCreated],
+            ),
+            subscription(
+                2,
+                format!("{}/members", base),
+                vec![WebhookEventType::MemberJoined],
+            ),
+        ];
+        let event = WebhookEvent::new(
+            WebhookEventType::MessageCreated,
+            1,
+            Utc::now(),
+            json!({ "chat_id": 3, "content": "hello" }),
+        );
+
+        let dispatcher = local_dispatcher(&WebhookConfig::default());
+        let outcomes = dispatcher.dispatch(&subscriptions, &event).await;
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].subscription_id, 1);
+        assert!(outcomes[0].result.is_ok());
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1, "non-matching webhook must not be called");
+        let (path, headers, body) = &received[0];
+        assert_eq!(path, "/messages");
+        assert_eq!(headers[EVENT_HEADER], "message.created");
+        assert_eq!(headers[DELIVERY_HEADER], event.id.to_string().as_str());
+
+        let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+        let hex = signature.strip_prefix("sha256=").expect("sha256 signature");
+        assert!(HmacSha256Verifier.verify_signature(body, hex, b"secret-1"));
+        assert!(!HmacSha256Verifier.verify_signature(body, hex, b"secret-2"));
+
+        let payload: Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload["event"], "message.created");
+        assert_eq!(payload["data"]["content"], "hello");
+    }
+
+    #[tokio::test]
+    async fn failing_endpoint_should_be_retried_until_attempts_run_out() {
+        let config = WebhookConfig {
+            max_attempts: 3,
+            retry_backoff_ms: 1,
+            ..WebhookConfig::default()
+        };
+        // The receiver has no route for this path, so every attempt gets a 404
+        let (base, received) = spawn_receiver().await;
+        let mut unreachable = subscription(1, base, vec![WebhookEventType::MemberJoined]);
+        unreachable.url.push_str("/a/b");
+        let event = WebhookEvent::new(WebhookEventType::MemberJoined, 1, Utc::now(), json!({}));
+
+        let outcomes = local_dispatcher(&config)
+            .dispatch(&[unreachable], &event)
+            .await;
+        assert_eq!(outcomes[0].attempts, 3);
+        assert!(matches!(
+            outcomes[0].result,
+            Err(WebhookDeliveryError::Status(404))
+        ));
+        assert!(received.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn internal_webhook_url_should_not_be_called() {
+        let (base, received) = spawn_receiver().await;
+        let internal = subscription(
+            1,
+            format!("{}/messages", base),
+            vec![WebhookEventType::MessageCreated],
+        );
+        let event = WebhookEvent::new(WebhookEventType::MessageCreated, 1, Utc::now(), json!({}));
+
+        let dispatcher =
+            WebhookDispatcher::new(&WebhookConfig::default(), OutboundAllowlist::default())
+                .unwrap();
+        let outcomes = dispatcher.dispatch(&[internal], &event).await;
+        assert_eq!(outcomes[0].attempts, 0);
+        assert!(matches!(
+            outcomes[0].result,
+            Err(WebhookDeliveryError::Blocked(
+                OutboundUrlError::BlockedAddress { .. }
+            ))
+        ));
+        assert!(received.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,11 @@
+//! # Outgoing Webhooks
+//!
+//! **Responsibility**: Deliver workspace events to integrator URLs as signed HTTP POSTs
+//! **Scope**: `delivery` signs and sends with retries; `worker` consumes NATS events and
+//! keeps subscription failure counts
+
+pub mod delivery;
+pub mod worker;
+
+pub use delivery::{sign_payload, WebhookDispatcher, WebhookEvent, SIGNATURE_HEADER};
+pub use worker::WebhookDeliveryWorker;
@@ -0,0 +1,199 @@
+//! # Webhook Delivery Worker
+//!
+//! **Responsibility**: Turn message and membership events from NATS into webhook deliveries
+//! **Scope**: Consumes the same `fechatter.message.created` / `fechatter.chat.joined` events as
+//! notify_server, looks up the chat's workspace subscriptions, delivers, and records outcomes.
+//! Events that exhaust their retries go to the dead letter subject; repeated failures disable
+//! the subscription
+
+use chrono::{DateTime, Utc};
+use fechatter_core::contracts::events::{subjects, ChatMemberJoinedEvent, MessageEvent};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use super::delivery::{DeliveryOutcome, WebhookDispatcher, WebhookEvent};
+use crate::config::WebhookConfig;
+use crate::domains::workspace::webhooks::{WebhookEventType, WebhookRepository};
+use crate::{AppError, AppState};
+
+/// Chat event waiting for its workspace to be resolved
+struct SourceEvent {
+    event: WebhookEventType,
+    chat_id: i64,
+    occurred_at: DateTime<Utc>,
+    data: Value,
+}
+
+/// Parse a NATS event; `None` for subjects webhooks don't cover
+fn source_event(subject: &str, payload: &[u8]) -> Result<Option<SourceEvent>, serde_json::Error> {
+    match subject {
+        subjects::MESSAGE_CREATED => {
+            let event: MessageEvent = serde_json::from_slice(payload)?;
+            Ok(Some(SourceEvent {
+                event: WebhookEventType::MessageCreated,
+                chat_id: i64::from(event.msg.chat_id),
+                occurred_at: event.occurred_at,
+                data: serde_json::to_value(&event.msg)?,
+            }))
+        }
+        subjects::CHAT_MEMBER_JOINED => {
+            let event: ChatMemberJoinedEvent = serde_json::from_slice(payload)?;
+            Ok(Some(SourceEvent {
+                event: WebhookEventType::MemberJoined,
+                chat_id: i64::from(event.chat_id),
+                occurred_at: event.occurred_at,
+                data: json!({ "chat_id": event.chat_id, "user_id": event.user_id }),
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Consumes chat events and delivers them to webhook subscriptions
+#[derive(Clone)]
+pub struct WebhookDeliveryWorker {
+    repository: Arc<WebhookRepository>,
+    dispatcher: WebhookDispatcher,
+    nats: async_nats::Client,
+    dead_letter_subject: String,
+    disable_after_failures: i32,
+}
+
+impl WebhookDeliveryWorker {
+    /// `None` without a NATS connection, as there are no events to consume, or when the HTTP
+    /// client can't be built
+    pub fn from_state(state: &AppState, config: &WebhookConfig) -> Option<Self> {
+        let nats = state.nats_client()?;
+        let dispatcher =
+            WebhookDispatcher::new(config, state.config.features.outbound.allowed_hosts.clone())
+                .map_err(|e| error!("Failed to create webhook HTTP client: {}", e))
+                .ok()?;
+        Some(Self {
+            repository: Arc::new(WebhookRepository::new(state.pool())),
+            dispatcher,
+            nats,
+            dead_letter_subject: config.dead_letter_subject.clone(),
+            disable_after_failures: config.disable_after_failures,
+        })
+    }
+
+    /// Consume events for the life of the process
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.run().await {
+                error!("Webhook delivery worker stopped: {}", e);
+            }
+        })
+    }
+
+    async fn run(self) -> Result<(), AppError> {
+        let subscribe = |subject: &'static str| {
+            let nats = self.nats.clone();
+            async move {
+                nats.subscribe(subject).await.map_err(|e| {
+                    AppError::ServiceUnavailable(format!(
+                        "Failed to subscribe to {}: {}",
+                        subject, e
+                    ))
+                })
+            }
+        };
+        let messages = subscribe(subjects::MESSAGE_CREATED).await?;
+        let members = subscribe(subjects::CHAT_MEMBER_JOINED).await?;
+        let mut events = futures::stream::select(messages, members);
+        info!("Webhook delivery worker started");
+
+        while let Some(message) = events.next().await {
+            // Deliveries retry with backoff, so each event is handled off the receive loop
+            let worker = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = worker.handle(&message.subject, &message.payload).await {
+                    warn!("Failed to deliver webhooks for {}: {}", message.subject, e);
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Deliver one NATS event to the subscriptions of its workspace
+    async fn handle(&self, subject: &str, payload: &[u8]) -> Result<(), AppError> {
+        let source = source_event(subject, payload)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid {} event: {}", subject, e)))?;
+        let Some(source) = source else {
+            return Ok(());
+        };
+
+        let Some(workspace_id) = self.repository.chat_workspace(source.chat_id).await? else {
+            return Ok(());
+        };
+        let subscriptions = self
+            .repository
+            .list_active(workspace_id, source.event)
+            .await?;
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+
+        let event = WebhookEvent::new(source.event, workspace_id, source.occurred_at, source.data);
+        for outcome in self.dispatcher.dispatch(&subscriptions, &event).await {
+            self.record(&event, outcome).await;
+        }
+
+        Ok(())
+    }
+
+    /// Track the delivery result; failures are dead-lettered and may disable the subscription
+    async fn record(&self, event: &WebhookEvent, outcome: DeliveryOutcome) {
+        let subscription_id = outcome.subscription_id;
+        let error = match outcome.result {
+            Ok(()) => {
+                if let Err(e) = self.repository.record_success(subscription_id).await {
+                    warn!(
+                        "Failed to reset webhook {} failure count: {}",
+                        subscription_id, e
+                    );
+                }
+                return;
+            }
+            Err(error) => error,
+        };
+
+        let record = json!({
+            "subscription_id": subscription_id,
+            "attempts": outcome.attempts,
+            "error": error.to_string(),
+            "failed_at": Utc::now(),
+            "event": event,
+        });
+        if let Err(e) = self
+            .nats
+            .publish(self.dead_letter_subject.clone(), record.to_string().into())
+            .await
+        {
+            error!(
+                "Failed to dead-letter webhook event {} for subscription {}: {}",
+                event.id, subscription_id, e
+            );
+        }
+
+        match self
+            .repository
+            .record_failure(subscription_id, self.disable_after_failures)
+            .await
+        {
+            Ok(true) => warn!(
+                "Disabled webhook {} after {} consecutive failed deliveries",
+                subscription_id, self.disable_after_failures
+            ),
+            Ok(false) => {}
+            Err(e) => warn!(
+                "Failed to record webhook {} failure: {}",
+                subscription_id, e
+            ),
+        }
+    }
+}
@@ -109,3 +109,44 @@ pub async fn setup_integration_test_app() -> TestApp {
 
     TestApp { state, app }
 }
+
+/// Stand-in for Redis that answers every command with OK until the task is aborted
+pub async fn fake_redis() -> (String, tokio::task::JoinHandle<()>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("redis://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 1024];
+        loop {
+            let n = socket.read(&mut chunk).await.unwrap_or(0);
+            if n == 0 {
+                return;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            while let Some(len) = command_len(&buf) {
+                buf.drain(..len);
+                socket.write_all(b"+OK\r\n").await.unwrap();
+            }
+        }
+    });
+    (url, server)
+}
+
+/// Length of the first whole command in `buf`, an array of bulk strings
+fn command_len(buf: &[u8]) -> Option<usize> {
+    fn header(buf: &[u8], at: usize) -> Option<(usize, usize)> {
+        let end = at + buf.get(at..)?.windows(2).position(|w| w == b"\r\n")?;
+        let n = std::str::from_utf8(&buf[at + 1..end]).ok()?.parse().ok()?;
+        Some((n, end + 2))
+    }
+
+    let (args, mut at) = header(buf, 0)?;
+    for _ in 0..args {
+        let (len, start) = header(buf, at)?;
+        at = start + len + 2;
+    }
+    (at <= buf.len()).then_some(at)
+}
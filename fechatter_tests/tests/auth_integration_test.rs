@@ -20,7 +20,7 @@ mod auth_integration_tests {
     CreateUser {
       email: format!("test_user_{}@example.com", uuid::Uuid::new_v4()),
       fullname: "Test User".to_string(),
-      password: "password123".to_string(),
+      password: "Password123".to_string(),
       workspace: "TestWorkspace".to_string(),
     }
   }
@@ -0,0 +1,19 @@
+-- Message Moderation Migration
+-- Migration: 0028_message_moderation.sql
+-- Purpose: Record messages flagged by the content moderation gate for review
+
+CREATE TABLE IF NOT EXISTS message_moderation_flags (
+    message_id BIGINT PRIMARY KEY REFERENCES messages(id) ON DELETE CASCADE,
+    chat_id BIGINT NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    sender_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    verdict VARCHAR(20) NOT NULL,
+    reviewed_at TIMESTAMPTZ,
+    reviewed_by BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Pending review queue
+CREATE INDEX IF NOT EXISTS idx_message_moderation_flags_pending
+    ON message_moderation_flags(created_at DESC)
+    WHERE reviewed_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_message_moderation_flags_chat ON message_moderation_flags(chat_id);
@@ -0,0 +1,17 @@
+-- Message Retention Migration
+-- Migration: 0029_message_retention.sql
+-- Purpose: Per-workspace message retention window and message tombstones
+
+-- NULL keeps messages forever (the default)
+ALTER TABLE workspaces
+ADD COLUMN IF NOT EXISTS message_retention_days INTEGER
+    CHECK (message_retention_days IS NULL OR message_retention_days > 0);
+
+-- Set when a message is tombstoned; content and files are cleared at the same time
+ALTER TABLE messages
+ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- Retention sweep scans live messages by age
+CREATE INDEX IF NOT EXISTS idx_messages_retention
+    ON messages(chat_id, created_at)
+    WHERE deleted_at IS NULL;
@@ -0,0 +1,30 @@
+-- Embedding Backfill Migration
+-- Migration: 0030_embedding_backfill.sql
+-- Purpose: Align message_embeddings with the pgvector repository and track resumable backfill jobs
+
+-- Columns written by PgVectorDatabase (one row per message chunk)
+ALTER TABLE message_embeddings
+ADD COLUMN IF NOT EXISTS chat_id BIGINT REFERENCES chats(id) ON DELETE CASCADE,
+ADD COLUMN IF NOT EXISTS chunk_index INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS chunk_content TEXT,
+ADD COLUMN IF NOT EXISTS metadata JSONB;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_message_embeddings_message_chunk
+    ON message_embeddings(message_id, chunk_index);
+
+CREATE INDEX IF NOT EXISTS idx_message_embeddings_chat_id
+    ON message_embeddings(chat_id);
+
+-- Backfill jobs resume after last_message_id
+CREATE TABLE IF NOT EXISTS embedding_backfill_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    chat_id BIGINT REFERENCES chats(id) ON DELETE CASCADE, -- NULL covers every chat
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'running', 'completed', 'failed')),
+    last_message_id BIGINT NOT NULL DEFAULT 0,
+    embedded_count BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+    created_by BIGINT NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,6 @@
+-- Client Message ID Migration
+-- Migration: 0031_client_message_id.sql
+-- Purpose: Store the sender-chosen id of optimistic local messages so it can be echoed back
+
+ALTER TABLE messages
+ADD COLUMN IF NOT EXISTS client_message_id VARCHAR(64);
@@ -0,0 +1,22 @@
+-- Webhook Subscriptions Migration
+-- Migration: 0032_webhook_subscriptions.sql
+-- Purpose: Per-workspace outgoing webhooks that receive signed event payloads
+
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    id BIGSERIAL PRIMARY KEY,
+    workspace_id BIGINT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    event_types TEXT[] NOT NULL,
+    secret TEXT NOT NULL, -- HMAC-SHA256 key for the X-Fechatter-Signature header
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Reset on every successful delivery; the subscription is disabled once it reaches the limit
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    created_by BIGINT NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Delivery looks up the enabled subscriptions of a workspace per event
+CREATE INDEX IF NOT EXISTS idx_webhook_subscriptions_workspace
+    ON webhook_subscriptions(workspace_id)
+    WHERE enabled;
@@ -0,0 +1,12 @@
+-- Delta Sync Migration
+-- Migration: 0033_delta_sync.sql
+-- Purpose: Indexes for GET /api/sync, which reads everything changed since a cursor
+
+-- Message changes (new, edited and tombstoned) are read per chat in (updated_at, id) order
+CREATE INDEX IF NOT EXISTS idx_messages_chat_updated
+    ON messages(chat_id, updated_at, id);
+
+-- Read state changes of a user's memberships
+CREATE INDEX IF NOT EXISTS idx_chat_members_user_last_read
+    ON chat_members(user_id, last_read_at)
+    WHERE last_read_at IS NOT NULL;
@@ -0,0 +1,12 @@
+-- Workspace Limits Migration
+-- Migration: 0034_workspace_limits.sql
+-- Purpose: Per-workspace overrides of the global rate limits and bot quota
+
+-- NULL falls back to features.rate_limiting (the default)
+ALTER TABLE workspaces
+ADD COLUMN IF NOT EXISTS message_send_limit INTEGER
+    CHECK (message_send_limit IS NULL OR message_send_limit > 0),
+ADD COLUMN IF NOT EXISTS upload_limit INTEGER
+    CHECK (upload_limit IS NULL OR upload_limit > 0),
+ADD COLUMN IF NOT EXISTS bot_daily_quota INTEGER
+    CHECK (bot_daily_quota IS NULL OR bot_daily_quota > 0);
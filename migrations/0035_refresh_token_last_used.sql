@@ -0,0 +1,8 @@
+-- Refresh Token Idle Timeout Migration
+-- Migration: 0035_refresh_token_last_used.sql
+-- Purpose: Track when each refresh token was last presented, for the inactivity timeout
+
+-- Rotation issues a new token on every refresh, so a token's last use starts at its issue.
+-- Existing tokens count as used now, giving live sessions a full idle window after deploy
+ALTER TABLE refresh_tokens
+ADD COLUMN IF NOT EXISTS last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,10 @@
+-- User Roles Migration
+-- Migration: 0036_user_roles.sql
+-- Purpose: Workspace-level role of each user, for the admin user management endpoints
+
+-- The workspace owner is always an admin; 'admin' grants the same rights to other members
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS role VARCHAR(16) NOT NULL DEFAULT 'member'
+    CHECK (role IN ('member', 'admin'));
+
+CREATE INDEX IF NOT EXISTS idx_users_workspace_status ON users(workspace_id, status);
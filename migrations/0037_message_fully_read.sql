@@ -0,0 +1,6 @@
+-- Message Fully Read Migration
+-- Migration: 0037_message_fully_read.sql
+-- Purpose: Remember when every member read a message, so message.fully_read is published once
+
+ALTER TABLE messages
+ADD COLUMN IF NOT EXISTS fully_read_at TIMESTAMPTZ;
@@ -0,0 +1,18 @@
+-- File Scans Migration
+-- Migration: 0038_file_scans.sql
+-- Purpose: Scan state of uploaded attachments; files are quarantined until their scan is clean
+
+-- Keyed by storage id (content hash + extension), so re-uploads of the same bytes share a verdict
+CREATE TABLE IF NOT EXISTS file_scans (
+    file_id TEXT PRIMARY KEY,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'clean', 'infected', 'failed')),
+    detail TEXT,
+    scanner VARCHAR(32) NOT NULL,
+    uploaded_by BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    workspace_id BIGINT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    scanned_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_file_scans_quarantined ON file_scans(status) WHERE status != 'clean';
@@ -0,0 +1,22 @@
+-- Event Dead Letters Migration
+-- Migration: 0039_event_dead_letters.sql
+-- Purpose: Persist dead-lettered NATS events so admins can replay them
+
+CREATE TABLE IF NOT EXISTS event_dead_letters (
+    id BIGSERIAL PRIMARY KEY,
+    dead_letter_subject TEXT NOT NULL,
+    source_subject TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    error TEXT NOT NULL,
+    failure_count INTEGER NOT NULL DEFAULT 1,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    replay_count INTEGER NOT NULL DEFAULT 0,
+    last_replayed_at TIMESTAMPTZ,
+    last_replayed_by BIGINT REFERENCES users(id) ON DELETE SET NULL
+);
+
+-- A replayed event that fails again updates its row instead of starting a fresh replay count
+CREATE UNIQUE INDEX IF NOT EXISTS idx_event_dead_letters_event
+    ON event_dead_letters(source_subject, md5(payload));
+
+CREATE INDEX IF NOT EXISTS idx_event_dead_letters_failed_at ON event_dead_letters(failed_at);
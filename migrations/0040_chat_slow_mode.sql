@@ -0,0 +1,7 @@
+-- Chat Slow Mode Migration
+-- Migration: 0040_chat_slow_mode.sql
+-- Purpose: Per-chat minimum interval between a member's messages; 0 turns slow mode off
+
+ALTER TABLE chats
+    ADD COLUMN IF NOT EXISTS slow_mode_secs INTEGER NOT NULL DEFAULT 0
+        CHECK (slow_mode_secs >= 0);
@@ -0,0 +1,20 @@
+-- User Erasures Migration
+-- Migration: 0041_user_erasures.sql
+-- Purpose: Track admin-requested erasures of a user's personal data so an interrupted run resumes
+
+CREATE TABLE IF NOT EXISTS user_erasures (
+    user_id BIGINT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    workspace_id BIGINT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    requested_by BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    -- Next step to run; 'done' once every step has completed
+    step VARCHAR(16) NOT NULL DEFAULT 'tokens'
+        CHECK (step IN ('tokens', 'files', 'messages', 'profile', 'caches', 'analytics', 'done')),
+    files_deleted INTEGER NOT NULL DEFAULT 0,
+    messages_tombstoned INTEGER NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+-- Erased users keep their row but lose their password
+ALTER TABLE users ALTER COLUMN password_hash DROP NOT NULL;
@@ -0,0 +1,28 @@
+-- Chat Read Cursors Migration
+-- Migration: 0042_chat_read_cursors.sql
+-- Purpose: Newest message each user has read per chat, so unread state is one row lookup
+-- instead of a scan of message_receipts. Advanced with every read receipt; never moves back
+
+CREATE TABLE IF NOT EXISTS chat_read_cursors (
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    chat_id BIGINT NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    last_read_message_id BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, chat_id)
+);
+
+-- Start from the reads already recorded, as receipts or as a read-all watermark
+INSERT INTO chat_read_cursors (user_id, chat_id, last_read_message_id, updated_at)
+SELECT user_id, chat_id, MAX(message_id), NOW()
+FROM (
+    SELECT mr.user_id, m.chat_id, mr.message_id
+    FROM message_receipts mr
+    JOIN messages m ON m.id = mr.message_id
+    WHERE mr.status = 'read'
+    UNION ALL
+    SELECT cm.user_id, cm.chat_id, cm.last_read_message_id
+    FROM chat_members cm
+    WHERE cm.last_read_message_id IS NOT NULL
+) reads
+GROUP BY user_id, chat_id
+ON CONFLICT (user_id, chat_id) DO NOTHING;
@@ -0,0 +1,41 @@
+-- Unique Chat Names Migration
+-- Migration: 0043_unique_chat_names.sql
+-- Purpose: Optional per-workspace rule that no two chats share a name (case-insensitive).
+-- Enforced by a unique index on a key that is only set in workspaces that opted in, so
+-- concurrent creates and renames cannot both win; direct chats are exempt
+
+ALTER TABLE workspaces
+    ADD COLUMN IF NOT EXISTS unique_chat_names BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Lowercased name while the workspace requires unique names, NULL otherwise
+ALTER TABLE chats
+    ADD COLUMN IF NOT EXISTS unique_name_key TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS chats_workspace_unique_name_key
+    ON chats (workspace_id, unique_name_key)
+    WHERE unique_name_key IS NOT NULL;
+
+-- The workspace row is read FOR SHARE, so turning the rule on waits for chats being named
+-- and every later one sees it
+CREATE OR REPLACE FUNCTION set_chat_unique_name_key()
+RETURNS TRIGGER AS $$
+DECLARE
+    required BOOLEAN;
+BEGIN
+    SELECT unique_chat_names INTO required
+    FROM workspaces
+    WHERE id = NEW.workspace_id
+    FOR SHARE;
+
+    NEW.unique_name_key := CASE
+        WHEN COALESCE(required, FALSE) AND NEW.type <> 'Single' THEN lower(btrim(NEW.chat_name))
+    END;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS set_chats_unique_name_key ON chats;
+CREATE TRIGGER set_chats_unique_name_key
+    BEFORE INSERT OR UPDATE OF chat_name, type, workspace_id ON chats
+    FOR EACH ROW
+    EXECUTE FUNCTION set_chat_unique_name_key();
@@ -84,6 +84,8 @@ impl NotifyEventHelper {
                 browser_version: env!("CARGO_PKG_VERSION").to_string(),
             }),
             geo: None, // Will be filled by analytics server
+            request_id: String::new(),
+            traceparent: String::new(),
         }
     }
 }
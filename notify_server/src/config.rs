@@ -1,6 +1,11 @@
 use anyhow::{Result, bail};
 use fechatter_core::models::jwt::TokenConfigProvider;
+use fechatter_core::utils::{
+  redact::{RedactedSummary, redact_optional_secret, redact_secret, redact_url},
+  schema::SchemaCheckMode,
+};
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 use std::path::PathBuf;
 
 use crate::analytics::publisher::AnalyticsConfig;
@@ -15,6 +20,8 @@ pub struct AppConfig {
   pub analytics: AnalyticsConfig,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub security: Option<SecurityConfig>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub presence: Option<PresenceConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,6 +37,12 @@ pub struct ServerConfig {
   pub port: u16,
   pub db_url: String,
   pub request_timeout_ms: u64,
+  /// Startup check of the applied migrations (`enforce`, `warn` or `skip`)
+  #[serde(default)]
+  pub schema_check: SchemaCheckMode,
+  /// Peers whose `X-Forwarded-For` / `X-Real-IP` headers are trusted for the client IP
+  #[serde(default = "fechatter_core::utils::default_trusted_proxies")]
+  pub trusted_proxies: Vec<IpAddr>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -44,9 +57,16 @@ pub struct NatsConfig {
   pub url: String,
   pub auth: NatsAuthConfig,
   pub subscription_subjects: Vec<String>,
+  /// Subject that events failing validation are republished to
+  #[serde(default = "default_dead_letter_subject")]
+  pub dead_letter_subject: String,
   pub jetstream: JetStreamConfig,
 }
 
+fn default_dead_letter_subject() -> String {
+  "fechatter.notify.dlq".to_string()
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NatsAuthConfig {
   pub enabled: bool,
@@ -132,6 +152,96 @@ pub struct WebDeliveryConfig {
   pub sse_enabled: bool,
   pub connection_timeout_ms: u64,
   pub heartbeat_interval_ms: u64,
+  /// Events buffered per SSE connection before the slow consumer policy applies
+  #[serde(default = "default_sse_buffer_capacity")]
+  pub buffer_capacity: usize,
+  #[serde(default)]
+  pub slow_consumer_policy: SlowConsumerPolicy,
+  /// How long a user stays online after their last connection closes, to ride out reconnects
+  #[serde(default = "default_presence_grace_period_ms")]
+  pub presence_grace_period_ms: u64,
+  /// Throttles reconnect storms on the SSE endpoint, separately from request rate limits
+  #[serde(default)]
+  pub connect_rate_limit: ConnectRateLimitConfig,
+  /// Largest serialized event pushed to a client; larger NATS events go to the dead letter
+  /// queue and larger outgoing frames are dropped
+  #[serde(default = "default_max_event_bytes")]
+  pub max_event_bytes: usize,
+}
+
+fn default_sse_buffer_capacity() -> usize {
+  256
+}
+
+fn default_max_event_bytes() -> usize {
+  64 * 1024
+}
+
+fn default_presence_grace_period_ms() -> u64 {
+  10_000
+}
+
+/// Connection attempts allowed per fixed window; over either limit the attempt is refused with
+/// a 429 and a `Retry-After` hint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectRateLimitConfig {
+  #[serde(default = "default_connect_rate_limit_enabled")]
+  pub enabled: bool,
+  #[serde(default = "default_connect_window_secs")]
+  pub window_secs: u64,
+  /// Attempts per user across all of their devices
+  #[serde(default = "default_connect_max_per_user")]
+  pub max_per_user: u32,
+  /// Attempts per client IP; higher, since users behind one NAT share it
+  #[serde(default = "default_connect_max_per_ip")]
+  pub max_per_ip: u32,
+}
+
+fn default_connect_rate_limit_enabled() -> bool {
+  true
+}
+
+fn default_connect_window_secs() -> u64 {
+  60
+}
+
+fn default_connect_max_per_user() -> u32 {
+  20
+}
+
+fn default_connect_max_per_ip() -> u32 {
+  60
+}
+
+impl Default for ConnectRateLimitConfig {
+  fn default() -> Self {
+    Self {
+      enabled: default_connect_rate_limit_enabled(),
+      window_secs: default_connect_window_secs(),
+      max_per_user: default_connect_max_per_user(),
+      max_per_ip: default_connect_max_per_ip(),
+    }
+  }
+}
+
+/// What to do when an SSE client falls a full buffer behind
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowConsumerPolicy {
+  /// Skip the overwritten events and keep streaming
+  #[default]
+  DropOldest,
+  /// Close the stream; the client reconnects and resyncs
+  Disconnect,
+}
+
+impl SlowConsumerPolicy {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::DropOldest => "drop_oldest",
+      Self::Disconnect => "disconnect",
+    }
+  }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -140,6 +250,41 @@ pub struct SecurityConfig {
   pub verify_signatures: bool,
 }
 
+/// Shared typing/presence store (same Redis as fechatter_server)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceConfig {
+  pub redis_url: String,
+}
+
+impl RedactedSummary for AppConfig {
+  fn redacted_summary(&self) -> String {
+    let state = |enabled: bool| if enabled { "enabled" } else { "disabled" };
+    let presence = self
+      .presence
+      .as_ref()
+      .map_or_else(|| "disabled".to_string(), |p| redact_url(&p.redis_url));
+    let hmac_secret = self
+      .security
+      .as_ref()
+      .and_then(|security| security.hmac_secret.as_deref());
+
+    format!(
+      "notify_server config: port={}, db={}, jwt pk={} sk={}, messaging={} ({}), search={} ({}, api_key={}), presence={}, hmac_secret={}",
+      self.server.port,
+      redact_url(&self.server.db_url),
+      redact_secret(&self.auth.pk),
+      redact_optional_secret(self.auth.sk.as_deref()),
+      state(self.messaging.enabled),
+      redact_url(&self.messaging.nats.url),
+      state(self.search.enabled),
+      redact_url(&self.search.meilisearch.url),
+      redact_secret(&self.search.meilisearch.api_key),
+      presence,
+      redact_optional_secret(hmac_secret),
+    )
+  }
+}
+
 impl AppConfig {
   /// Production-level config loader
   ///
@@ -359,6 +504,20 @@ impl AppConfig {
       bail!("Request timeout cannot be zero");
     }
 
+    // Validate SSE keepalive: it has to fire before idle connections are cut
+    let web = &config.notification.delivery.web;
+    if web.heartbeat_interval_ms == 0 || web.heartbeat_interval_ms >= web.connection_timeout_ms {
+      bail!(
+        "SSE heartbeat_interval_ms ({}) must be positive and shorter than connection_timeout_ms ({})",
+        web.heartbeat_interval_ms,
+        web.connection_timeout_ms
+      );
+    }
+
+    if web.buffer_capacity == 0 {
+      bail!("SSE buffer_capacity must be positive");
+    }
+
     // Validate NATS config
     if config.messaging.enabled {
       if config.messaging.nats.url.is_empty() {
@@ -408,29 +567,6 @@ impl AppConfig {
     }
   }
 
-  /// Get config summary (for logging, no sensitive info)
-  pub fn get_summary(&self) -> String {
-    format!(
-      "notify_server config: port={}, messaging={}, search={}, db={}",
-      self.server.port,
-      if self.messaging.enabled {
-        "enabled"
-      } else {
-        "disabled"
-      },
-      if self.search.enabled {
-        "enabled"
-      } else {
-        "disabled"
-      },
-      if self.server.db_url.contains("localhost") {
-        "localhost"
-      } else {
-        "remote"
-      }
-    )
-  }
-
   /// Validate if config is production-ready
   pub fn validate_production_readiness(&self) -> Result<()> {
     let mut warnings = Vec::new();
@@ -509,3 +645,25 @@ impl TokenConfigProvider for AuthConfig {
     60
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn redacted_summary_should_never_contain_secrets() {
+    let mut config = AppConfig::load().expect("config");
+    config.auth.sk = Some("jwt-private-s3cret".to_string());
+    config.server.db_url = "postgres://fechatter:db-s3cret@db:5432/fechatter".to_string();
+    config.presence = Some(PresenceConfig {
+      redis_url: "redis://:redis-s3cret@cache:6379/0".to_string(),
+    });
+
+    let summary = config.redacted_summary();
+    assert!(!summary.contains(&config.auth.pk));
+    for secret in ["jwt-private-s3cret", "db-s3cret", "redis-s3cret"] {
+      assert!(!summary.contains(secret), "summary leaks {secret}");
+    }
+    assert!(summary.contains("redis://:***@cache:6379/0"));
+  }
+}
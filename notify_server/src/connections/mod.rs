@@ -1,5 +1,7 @@
 pub mod manager;
+pub mod rate_limit;
 pub mod sse;
 
 pub use manager::ConnectionManager;
+pub use rate_limit::ConnectRateLimiter;
 pub use sse::sse_handler;
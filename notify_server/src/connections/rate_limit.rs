@@ -0,0 +1,184 @@
+//! # Connection Rate Limiting
+//!
+//! **Responsibility**: Refuse reconnect storms on `/events` before any per-connection work
+//! (channel subscription, chat registration, presence, heartbeats) is done
+//! **Keys**: One fixed window per user and one per client IP, kept in process since each
+//! connection lives on a single notify_server instance
+
+use axum::http::HeaderMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::ConnectRateLimitConfig;
+use fechatter_core::services::rate_limit::{
+  InMemoryRateLimitStore, RateLimitDecision, RateLimitStore, RateLimiter,
+};
+use fechatter_core::UserId;
+
+/// Per-user and per-IP connection attempt limits
+pub struct ConnectRateLimiter {
+  per_user: RateLimiter,
+  per_ip: RateLimiter,
+}
+
+impl ConnectRateLimiter {
+  /// `None` while connection rate limiting is disabled
+  pub fn from_config(config: &ConnectRateLimitConfig) -> Option<Self> {
+    if !config.enabled {
+      return None;
+    }
+
+    let store: Arc<dyn RateLimitStore> = Arc::new(InMemoryRateLimitStore::new());
+    let window = Duration::from_secs(config.window_secs.max(1));
+    Some(Self {
+      per_user: RateLimiter::new(store.clone(), config.max_per_user, window),
+      per_ip: RateLimiter::new(store, config.max_per_ip, window),
+    })
+  }
+
+  /// Count a connection attempt, failing with the decision of the first exceeded limit
+  pub async fn enforce(
+    &self,
+    user_id: UserId,
+    client_ip: Option<&str>,
+  ) -> Result<(), RateLimitDecision> {
+    self
+      .per_user
+      .enforce(&format!("sse_connect:user:{}", user_id.0))
+      .await?;
+    if let Some(ip) = client_ip {
+      self
+        .per_ip
+        .enforce(&format!("sse_connect:ip:{}", ip))
+        .await?;
+    }
+    Ok(())
+  }
+}
+
+/// Client IP of a request: the peer address, or behind one of `trusted_proxies` the nearest
+/// `X-Forwarded-For` hop that isn't a trusted proxy, so clients can't pick the IP they're
+/// counted against
+pub fn client_ip(
+  headers: &HeaderMap,
+  peer: Option<SocketAddr>,
+  trusted_proxies: &[IpAddr],
+) -> Option<String> {
+  fechatter_core::utils::client_ip(peer.map(|addr| addr.ip()), headers, trusted_proxies)
+    .map(|ip| ip.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn limiter(window_secs: u64) -> ConnectRateLimiter {
+    ConnectRateLimiter::from_config(&ConnectRateLimitConfig {
+      enabled: true,
+      window_secs,
+      max_per_user: 3,
+      max_per_ip: 5,
+    })
+    .unwrap()
+  }
+
+  #[tokio::test]
+  async fn rapid_reconnects_from_one_client_should_be_throttled() {
+    let limiter = limiter(60);
+    let user = UserId(7);
+
+    for _ in 0..3 {
+      assert!(limiter.enforce(user, Some("203.0.113.9")).await.is_ok());
+    }
+    let decision = limiter
+      .enforce(user, Some("203.0.113.9"))
+      .await
+      .unwrap_err();
+    assert_eq!(decision.limit, 3);
+    assert!((1..=60).contains(&decision.retry_after_secs()));
+
+    // Other users are unaffected until their shared IP runs out; the refused attempt above
+    // was not counted against it
+    for other in [8, 9] {
+      assert!(limiter
+        .enforce(UserId(other), Some("203.0.113.9"))
+        .await
+        .is_ok());
+    }
+    let decision = limiter
+      .enforce(UserId(10), Some("203.0.113.9"))
+      .await
+      .unwrap_err();
+    assert_eq!(decision.limit, 5);
+  }
+
+  #[tokio::test]
+  async fn throttled_attempt_should_get_429_with_a_retry_hint() {
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    let limiter = limiter(60);
+    for _ in 0..3 {
+      limiter.enforce(UserId(7), None).await.unwrap();
+    }
+    let decision = limiter.enforce(UserId(7), None).await.unwrap_err();
+
+    let response = crate::error::NotifyError::RateLimited(decision).into_response();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response.headers()["retry-after"]
+      .to_str()
+      .unwrap()
+      .parse()
+      .unwrap();
+    assert!((1..=60).contains(&retry_after));
+  }
+
+  #[tokio::test]
+  async fn reconnects_at_a_normal_cadence_should_be_allowed() {
+    let limiter = limiter(1);
+    let user = UserId(7);
+
+    // Three attempts per one-second window, for three windows
+    for _ in 0..3 {
+      for _ in 0..3 {
+        assert!(limiter.enforce(user, Some("203.0.113.9")).await.is_ok());
+      }
+      tokio::time::sleep(Duration::from_millis(1100)).await;
+    }
+  }
+
+  #[test]
+  fn disabled_config_should_build_no_limiter() {
+    let config = ConnectRateLimitConfig {
+      enabled: false,
+      ..ConnectRateLimitConfig::default()
+    };
+    assert!(ConnectRateLimiter::from_config(&config).is_none());
+  }
+
+  #[test]
+  fn client_ip_should_only_believe_trusted_proxies() {
+    let peer: SocketAddr = "10.0.0.2:51000".parse().unwrap();
+    let trusted: Vec<IpAddr> = vec!["10.0.0.2".parse().unwrap()];
+    let mut headers = HeaderMap::new();
+    assert_eq!(
+      client_ip(&headers, Some(peer), &trusted).as_deref(),
+      Some("10.0.0.2")
+    );
+
+    // A client-chosen first hop is skipped in favour of the one the gateway appended
+    headers.insert(
+      "x-forwarded-for",
+      "192.0.2.50, 198.51.100.4".parse().unwrap(),
+    );
+    assert_eq!(
+      client_ip(&headers, Some(peer), &trusted).as_deref(),
+      Some("198.51.100.4")
+    );
+    assert_eq!(
+      client_ip(&headers, Some(peer), &[]).as_deref(),
+      Some("10.0.0.2")
+    );
+  }
+}
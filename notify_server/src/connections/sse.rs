@@ -1,28 +1,139 @@
 use axum::{
   Extension,
-  extract::State,
-  response::{Sse, sse::Event},
+  extract::{ConnectInfo, Query, State},
+  http::HeaderMap,
+  response::{
+    Sse,
+    sse::{Event, KeepAlive},
+  },
 };
 
 use axum_extra::{TypedHeader, headers};
 use futures::{Stream, StreamExt};
 use std::{
   convert::Infallible,
+  net::SocketAddr,
   pin::Pin,
   task::{Context, Poll},
   time::{Duration, Instant},
 };
 use tokio::sync::{broadcast, mpsc::Sender};
-use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
 use tracing::{debug, info, warn};
+use serde::Deserialize;
 use serde_json::json;
 use chrono::Utc;
 use std::sync::Arc;
 
-use crate::{events::types::NotifyEvent, state::AppState};
-use fechatter_core::{AuthUser, UserId};
+use crate::{
+  config::SlowConsumerPolicy, connections::rate_limit::client_ip, error::NotifyError,
+  events::types::NotifyEvent, observability::metrics::collectors::SSEMetrics,
+  state::{AppState, PresenceInterest},
+};
+use fechatter_core::{AuthUser, PresenceStatus, UserId};
+
+/// Comment line sent on idle streams so proxies keep the connection open
+const KEEPALIVE_TEXT: &str = "keepalive";
+
+/// Idle-stream keepalive (`: keepalive`), sent whenever `interval` passes without an event
+pub fn keep_alive(interval: Duration) -> KeepAlive {
+  KeepAlive::new().interval(interval).text(KEEPALIVE_TEXT)
+}
+
+/// Events for one connection from its bounded buffer. Once the consumer falls a full buffer
+/// behind, the oldest events are overwritten; `policy` decides whether the stream skips them
+/// or ends so the client reconnects
+pub fn buffered_events(
+  rx: broadcast::Receiver<Arc<NotifyEvent>>,
+  policy: SlowConsumerPolicy,
+) -> impl Stream<Item = Arc<NotifyEvent>> {
+  BroadcastStream::new(rx)
+    .take_while(move |result| {
+      let keep = match result {
+        Ok(_) => true,
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+          SSEMetrics::events_dropped(*skipped, policy.as_str());
+          warn!(
+            "[SSE] Slow consumer missed {} events ({})",
+            skipped,
+            policy.as_str()
+          );
+          policy == SlowConsumerPolicy::DropOldest
+        }
+      };
+      futures::future::ready(keep)
+    })
+    .filter_map(|result| futures::future::ready(result.ok()))
+}
+
+/// Serialized event data for an SSE frame, or `None` when it exceeds `max_bytes` so no
+/// oversized frame is written to the client
+pub fn encode_event(event: &NotifyEvent, max_bytes: usize) -> Option<String> {
+  let data = serde_json::to_string(event).expect("Failed to serialize event");
+  if data.len() > max_bytes {
+    SSEMetrics::oversized_event_dropped();
+    warn!(
+      "[SSE] Dropped {} byte event over the {} byte limit",
+      data.len(),
+      max_bytes
+    );
+    return None;
+  }
+  Some(data)
+}
+
+/// Tears down a user's SSE registration once its stream is dropped, which happens when the
+/// client disconnects or a write to it fails
+struct ConnectionCleanup {
+  state: AppState,
+  user_id: UserId,
+  connection_id: String,
+  connection_start: Instant,
+}
+
+impl ConnectionCleanup {
+  /// Counts the connection as active until the cleanup runs
+  fn new(
+    state: AppState,
+    user_id: UserId,
+    connection_id: String,
+    connection_start: Instant,
+  ) -> Self {
+    SSEMetrics::connection_opened();
+    Self {
+      state,
+      user_id,
+      connection_id,
+      connection_start,
+    }
+  }
+}
+
+impl Drop for ConnectionCleanup {
+  fn drop(&mut self) {
+    SSEMetrics::connection_closed(self.connection_start.elapsed());
+
+    let state = self.state.clone();
+    let user_id = self.user_id;
+    let connection_id = std::mem::take(&mut self.connection_id);
+    let connection_duration = self.connection_start.elapsed().as_millis() as u64;
+
+    tokio::spawn(async move {
+      info!("🔌 [SSE] User {} disconnected after {}ms", user_id.0, connection_duration);
+
+      // Send analytics event for user disconnection
+      state
+        .analytics
+        .user_disconnected(user_id, connection_id, connection_duration);
 
-const CHANNEL_CAPACITY: usize = 256;
+      // Other open connections, or a reconnect within the grace period, keep the user online
+      if state.presence_tracker.disconnect_and_settle(user_id).await {
+        state.unregister_user_from_chats(user_id).await;
+        state.set_presence(user_id, PresenceStatus::Offline).await;
+      }
+    });
+  }
+}
 
 pub struct EventStream {
   _tx: Sender<Result<Event, Infallible>>,
@@ -37,13 +148,40 @@ impl Stream for EventStream {
   }
 }
 
-/// SSE connection handler - handles user's Server-Sent Events connection
+/// Options of an `/events` connection, next to the `access_token`
+#[derive(Debug, Default, Deserialize)]
+pub struct SseConnectQuery {
+  /// Comma-separated chat ids whose members' presence to receive; the workspace when absent
+  pub presence_chats: Option<String>,
+}
+
+/// SSE connection handler - handles user's Server-Sent Events connection.
+/// Reconnect storms are refused with a 429 and `Retry-After` before the connection is set up
 #[allow(dead_code)]
 pub async fn sse_handler(
   State(state): State<AppState>,
   Extension(user): Extension<AuthUser>,
   user_agent: Option<TypedHeader<headers::UserAgent>>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+  connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+  request_headers: HeaderMap,
+  Query(connect_query): Query<SseConnectQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, NotifyError> {
+  let user_id = UserId(user.id.into());
+  if let Some(limiter) = &state.connect_rate_limiter {
+    let peer = connect_info.map(|Extension(ConnectInfo(addr))| addr);
+    let ip = client_ip(&request_headers, peer, &state.config.server.trusted_proxies);
+    if let Err(decision) = limiter.enforce(user_id, ip.as_deref()).await {
+      SSEMetrics::connection_throttled();
+      warn!(
+        "[SSE] Throttled connection attempt by user {} from {:?}, retry in {}s",
+        user_id.0,
+        ip,
+        decision.retry_after_secs()
+      );
+      return Err(NotifyError::RateLimited(decision));
+    }
+  }
+
   let user_agent_str = user_agent
     .map(|TypedHeader(ua)| ua.as_str().to_string())
     .unwrap_or_else(|| "Unknown".to_string());
@@ -53,13 +191,14 @@ pub async fn sse_handler(
     user.id, user_agent_str
   );
 
-  let user_id = UserId(user.id.into());
   let connection_id = uuid::Uuid::new_v4().to_string();
   let connection_start = Instant::now();
 
-  // 1. Create the user's SSE connection
-  let (tx, rx) = broadcast::channel(CHANNEL_CAPACITY);
-  state.user_connections.insert(user_id, tx.clone());
+  // 1. Join the user's event channel; all of a user's connections share one
+  let web_config = &state.config.notification.delivery.web;
+  let slow_consumer_policy = web_config.slow_consumer_policy;
+  let rx = state.subscribe_user(user_id, user.workspace_id, web_config.buffer_capacity);
+  let new_session = state.presence_tracker.connect(user_id);
 
   // 2. Register the user to all their chats (critical fix)
   let chat_count = if let Err(e) = state.register_user_to_chats(user_id).await {
@@ -69,6 +208,12 @@ pub async fn sse_handler(
     // Get the number of chats the user is registered to
     state.get_user_chat_count(user_id).await.unwrap_or(0)
   };
+  let presence_interest = state
+    .set_presence_interest(
+      user_id,
+      PresenceInterest::from_query(connect_query.presence_chats.as_deref()),
+    )
+    .await;
 
   // CRITICAL FIX 1: Send immediate SSE connection confirmation event
   let welcome_notification = json!({
@@ -76,16 +221,18 @@ pub async fn sse_handler(
     "user_id": user_id.0,
     "connection_id": connection_id,
     "connected_chats": chat_count,
+    "presence_interest": presence_interest,
     "timestamp": Utc::now(),
     "server_time": Utc::now().timestamp(),
     "message": "SSE connection established successfully"
   });
 
-  // Send welcome event immediately
-  if let Err(e) = tx.send(Arc::new(NotifyEvent::Generic(welcome_notification))) {
-    warn!("Failed to send welcome notification to user {}: {}", user_id.0, e);
-  } else {
-    info!("[SSE] Sent connection confirmation to user {}", user_id.0);
+  // Sent first on this connection only, not to the user's other open connections
+  let welcome = Arc::new(NotifyEvent::Generic(welcome_notification));
+
+  // Mark the user online in the shared presence store, unless they already are
+  if new_session.is_some() {
+    state.set_presence(user_id, PresenceStatus::Online).await;
   }
 
   // 3. Send analytics event for user connection
@@ -95,55 +242,83 @@ pub async fn sse_handler(
     Some(user_agent_str.clone()),
   );
 
-  // CRITICAL FIX 2: Start heartbeat mechanism for this user
-  let heartbeat_tx = tx.clone();
-  let heartbeat_user_id = user_id;
-  tokio::spawn(async move {
-    let mut interval = tokio::time::interval(Duration::from_secs(30));
-    let mut heartbeat_count = 1;
-    
-    loop {
-      interval.tick().await;
-      
-      let heartbeat_event = json!({
-        "type": "heartbeat",
-        "user_id": heartbeat_user_id.0,
-        "heartbeat_id": heartbeat_count,
-        "timestamp": Utc::now(),
-        "server_time": Utc::now().timestamp()
-      });
-      
-      if heartbeat_tx.send(Arc::new(NotifyEvent::Generic(heartbeat_event))).is_err() {
-        info!("HEARTBEAT: [SSE] Heartbeat stopped for user {} (connection closed)", heartbeat_user_id.0);
-        break;
+  // CRITICAL FIX 2: Start heartbeat mechanism once per online session
+  if let Some(session) = new_session {
+    let heartbeat_user_id = user_id;
+    let heartbeat_state = state.clone();
+    tokio::spawn(async move {
+      let mut interval = tokio::time::interval(Duration::from_secs(30));
+      let mut heartbeat_count = 1;
+
+      loop {
+        interval.tick().await;
+
+        if !heartbeat_state
+          .presence_tracker
+          .is_current_session(heartbeat_user_id, session)
+        {
+          info!("HEARTBEAT: [SSE] Heartbeat stopped for user {} (offline)", heartbeat_user_id.0);
+          break;
+        }
+
+        let heartbeat_event = json!({
+          "type": "heartbeat",
+          "user_id": heartbeat_user_id.0,
+          "heartbeat_id": heartbeat_count,
+          "timestamp": Utc::now(),
+          "server_time": Utc::now().timestamp()
+        });
+
+        // Nothing to reach during the reconnect grace period
+        let heartbeat = Arc::new(NotifyEvent::Generic(heartbeat_event));
+        if !heartbeat_state.send_to_user(heartbeat_user_id, heartbeat) {
+          continue;
+        }
+
+        // Keep the presence record from going stale while connected
+        heartbeat_state.refresh_presence(heartbeat_user_id).await;
+
+        debug!("HEARTBEAT: [SSE] Sent heartbeat #{} to user {}", heartbeat_count, heartbeat_user_id.0);
+        heartbeat_count += 1;
       }
-      
-      debug!("HEARTBEAT: [SSE] Sent heartbeat #{} to user {}", heartbeat_count, heartbeat_user_id.0);
-      heartbeat_count += 1;
-    }
-  });
+    });
+  }
 
   info!(
     "User {} successfully connected to SSE and registered to {} chats",
     user_id.0, chat_count
   );
 
-  // 4. Create the SSE stream, including cleanup logic on disconnect
-  let state_for_cleanup = state.clone();
-  let cleanup_connection_id = connection_id.clone();
-  let stream = BroadcastStream::new(rx)
-    .filter_map(|result| async move { result.ok() })
-    .map(move |v| {
+  // 4. Create the SSE stream; cleanup runs when it is dropped
+  let keepalive_interval =
+    Duration::from_millis(state.config.notification.delivery.web.heartbeat_interval_ms);
+  let max_event_bytes = state.config.notification.delivery.web.max_event_bytes;
+  let cleanup = ConnectionCleanup::new(
+    state.clone(),
+    user_id,
+    connection_id.clone(),
+    connection_start,
+  );
+  let stream = futures::stream::once(futures::future::ready(welcome))
+    .chain(buffered_events(rx, slow_consumer_policy))
+    .filter_map(move |v| {
+      let _connection = &cleanup;
       let event_type = match v.as_ref() {
         NotifyEvent::NewChat(_) => "NewChat",
         NotifyEvent::UserJoinedChat(_) => "UserJoinedChat",
         NotifyEvent::UserLeftChat(_) => "UserLeftChat",
         NotifyEvent::NewMessage(_) => "NewMessage",
         NotifyEvent::DuplicateMessageAttempted(_) => "DuplicateMessageAttempted",
+        NotifyEvent::MessageDelivered(_) => "MessageDelivered",
         NotifyEvent::MessageRead(_) => "MessageRead",
         NotifyEvent::MessageUnread(_) => "MessageUnread",
         NotifyEvent::TypingStatus(_) => "TypingStatus",
         NotifyEvent::UserPresence(_) => "UserPresence",
+        NotifyEvent::SystemAnnouncement(_) => "SystemAnnouncement",
+        NotifyEvent::EphemeralMessage(_) => "EphemeralMessage",
+        NotifyEvent::AiStreamChunk(_) => "AiStreamChunk",
+        NotifyEvent::LinkPreviews(_) => "LinkPreviews",
+        NotifyEvent::ChatUpdated(_) => "ChatUpdated",
         NotifyEvent::Generic(_) => "Generic",
       };
 
@@ -164,45 +339,132 @@ pub async fn sse_handler(
         );
       });
 
-      let v = serde_json::to_string(&v).expect("Failed to serialize event");
+      let Some(v) = encode_event(&v, max_event_bytes) else {
+        return futures::future::ready(None);
+      };
       debug!(
         "📤 [SSE] Sending event {} to user {}: {}",
         event_type, user_id.0, 
         if v.len() > 100 { format!("{}...", &v[..100]) } else { v.clone() }
       );
-      Ok(Event::default().data(v).event(event_type))
-    })
-    .inspect(move |_| {
-      // Cleanup user connection when the stream ends (triggered when user disconnects SSE)
-      let state_cleanup = state_for_cleanup.clone();
-      let cleanup_user_id = user_id;
-      let final_connection_id = cleanup_connection_id.clone();
-      let final_connection_start = connection_start;
-      
-      tokio::spawn(async move {
-        // Calculate connection duration
-        let connection_duration = final_connection_start.elapsed().as_millis() as u64;
-        
-        info!("🔌 [SSE] User {} disconnected after {}ms", cleanup_user_id.0, connection_duration);
-        
-        // Send analytics event for user disconnection
-        state_cleanup.analytics.user_disconnected(
-          cleanup_user_id,
-          final_connection_id,
-          connection_duration,
-        );
-        
-        // Clean up user from chats
-        state_cleanup
-          .unregister_user_from_chats(cleanup_user_id)
-          .await;
-      });
+      futures::future::ready(Some(Ok(Event::default().data(v).event(event_type))))
     });
 
-  // Enhanced keep-alive with more frequent pings
-  Sse::new(stream).keep_alive(
-    axum::response::sse::KeepAlive::new()
-      .interval(Duration::from_secs(25))
-      .text("ping"),
-  )
+  Ok(Sse::new(stream).keep_alive(keep_alive(keepalive_interval)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use axum::response::IntoResponse;
+
+  /// Fill a 4-event buffer with 10 events before the consumer reads anything
+  fn slow_consumer(policy: SlowConsumerPolicy) -> (
+    broadcast::Sender<Arc<NotifyEvent>>,
+    impl Stream<Item = Arc<NotifyEvent>>,
+  ) {
+    let (tx, rx) = broadcast::channel(4);
+    let events = buffered_events(rx, policy);
+    for seq in 0..10 {
+      tx.send(Arc::new(NotifyEvent::Generic(json!({ "seq": seq }))))
+        .unwrap();
+    }
+    (tx, events)
+  }
+
+  fn seq(event: &NotifyEvent) -> i64 {
+    match event {
+      NotifyEvent::Generic(value) => value["seq"].as_i64().unwrap(),
+      other => panic!("unexpected event {:?}", other),
+    }
+  }
+
+  #[tokio::test]
+  async fn slow_consumer_should_lose_oldest_events_under_drop_oldest() {
+    let (tx, events) = slow_consumer(SlowConsumerPolicy::DropOldest);
+    drop(tx);
+
+    let received: Vec<i64> = events.map(|event| seq(&event)).collect().await;
+    assert_eq!(received, vec![6, 7, 8, 9]);
+  }
+
+  #[tokio::test]
+  async fn slow_consumer_should_be_disconnected_under_disconnect() {
+    let (_tx, events) = slow_consumer(SlowConsumerPolicy::Disconnect);
+    futures::pin_mut!(events);
+
+    // The sender is still alive, so the stream ends only because of the policy
+    let next = tokio::time::timeout(Duration::from_secs(1), events.next())
+      .await
+      .expect("stream should end instead of waiting");
+    assert!(next.is_none());
+  }
+
+  #[test]
+  fn oversized_event_should_not_reach_the_client() {
+    let event = |content: String| NotifyEvent::Generic(json!({ "content": content }));
+
+    let small = encode_event(&event("hi".to_string()), 1024).expect("within the limit");
+    let small: serde_json::Value = serde_json::from_str(&small).unwrap();
+    assert_eq!(small["content"], "hi");
+    assert!(encode_event(&event("x".repeat(2048)), 1024).is_none());
+  }
+
+  #[tokio::test]
+  async fn connections_should_be_counted_while_open() {
+    let recorder = metrics_util::debugging::DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    let _guard = metrics::set_default_local_recorder(&recorder);
+    let active = || {
+      snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .find(|(key, _, _, _)| key.key().name() == "notify_sse_connections_active")
+        .map(|(_, _, _, value)| value)
+    };
+    let gauge = |value: f64| Some(metrics_util::debugging::DebugValue::Gauge(value.into()));
+
+    let state = AppState::new(crate::config::AppConfig::load().expect("config")).unwrap();
+    let connect = |user_id: i64| {
+      ConnectionCleanup::new(
+        state.clone(),
+        UserId(user_id),
+        uuid::Uuid::new_v4().to_string(),
+        Instant::now(),
+      )
+    };
+
+    let first = connect(1);
+    let second = connect(2);
+    assert_eq!(active(), gauge(2.0));
+
+    drop(first);
+    assert_eq!(active(), gauge(1.0));
+    drop(second);
+    assert_eq!(active(), gauge(0.0));
+  }
+
+  #[tokio::test]
+  async fn idle_stream_should_emit_keepalive_comments() {
+    // No events ever arrive on this stream
+    let events = futures::stream::pending::<Result<Event, Infallible>>();
+    let mut body = Sse::new(events)
+      .keep_alive(keep_alive(Duration::from_millis(50)))
+      .into_response()
+      .into_body()
+      .into_data_stream();
+
+    for _ in 0..2 {
+      let frame = tokio::time::timeout(Duration::from_secs(1), body.next())
+        .await
+        .expect("keepalive within the interval")
+        .expect("stream still open")
+        .unwrap();
+      // A comment line, which EventSource clients ignore
+      let frame = std::str::from_utf8(&frame).unwrap();
+      assert!(frame.starts_with(':'), "{frame:?}");
+      assert_eq!(frame.trim_start_matches(':').trim(), KEEPALIVE_TEXT);
+    }
+  }
 }
@@ -4,10 +4,13 @@ use axum::{
   http::StatusCode,
   response::{IntoResponse, Response},
 };
+use fechatter_core::services::rate_limit::{RateLimitDecision, apply_rate_limit_headers};
 use fechatter_core::{CoreError, ErrorMapper};
 use serde_json::json;
 use thiserror::Error;
 
+use crate::events::builder::EventBuildError;
+
 #[derive(Error, Debug)]
 pub enum NotifyError {
   #[error("IO error: {0}")]
@@ -66,13 +69,39 @@ pub enum NotifyError {
 
   #[error("NATS error: {0}")]
   Nats(String),
+
+  #[error("Invalid event: {0}")]
+  InvalidEvent(#[from] EventBuildError),
+
+  #[error("Event of {size} bytes exceeds the {limit} byte limit")]
+  EventTooLarge { size: usize, limit: usize },
+
+  #[error("Too many connection attempts, retry in {}s", .0.retry_after_secs())]
+  RateLimited(RateLimitDecision),
+}
+
+impl NotifyError {
+  /// Errors caused by the event itself; retrying cannot help, so they go to the dead letter queue
+  pub fn is_malformed_event(&self) -> bool {
+    matches!(
+      self,
+      NotifyError::InvalidJson(_)
+        | NotifyError::InvalidEvent(_)
+        | NotifyError::EventTooLarge { .. }
+    )
+  }
 }
 
 impl IntoResponse for NotifyError {
   fn into_response(self) -> Response {
+    let rate_limit = match &self {
+      NotifyError::RateLimited(decision) => Some(decision.clone()),
+      _ => None,
+    };
     let (status, error_message) = match self {
       NotifyError::AuthenticationFailed(msg) => (StatusCode::UNAUTHORIZED, msg),
       NotifyError::Unauthorized(msg) => (StatusCode::FORBIDDEN, msg),
+      NotifyError::NotFoundError(msg) => (StatusCode::NOT_FOUND, msg),
       NotifyError::Database(err) => (StatusCode::INTERNAL_SERVER_ERROR, err),
       NotifyError::Serialization(err) => (StatusCode::BAD_REQUEST, err.to_string()),
       NotifyError::JwtError(err) => (StatusCode::UNAUTHORIZED, err.to_string()),
@@ -80,6 +109,8 @@ impl IntoResponse for NotifyError {
       NotifyError::InvalidJson(err) => (StatusCode::BAD_REQUEST, err),
       NotifyError::Config(err) => (StatusCode::INTERNAL_SERVER_ERROR, err),
       NotifyError::Nats(err) => (StatusCode::SERVICE_UNAVAILABLE, err),
+      NotifyError::InvalidEvent(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+      err @ NotifyError::RateLimited(_) => (StatusCode::TOO_MANY_REQUESTS, err.to_string()),
       _ => (
         StatusCode::INTERNAL_SERVER_ERROR,
         "Unhandled error type".to_string(),
@@ -91,7 +122,11 @@ impl IntoResponse for NotifyError {
         "status": status.as_u16()
     }));
 
-    (status, body).into_response()
+    let mut response = (status, body).into_response();
+    if let Some(decision) = rate_limit {
+      apply_rate_limit_headers(response.headers_mut(), &decision);
+    }
+    response
   }
 }
 
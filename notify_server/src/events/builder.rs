@@ -0,0 +1,461 @@
+use chrono::Utc;
+use serde_json::Value;
+use std::fmt;
+use thiserror::Error;
+
+use crate::events::types::{
+  MessageDeliveredEvent, MessageReadEvent, MessageUnreadEvent, NotifyEvent, TypingEvent,
+  UserPresenceEvent,
+};
+
+/// Presence states clients understand
+const PRESENCE_STATUSES: [&str; 3] = ["online", "offline", "away"];
+
+/// The realtime `NotifyEvent` kinds assembled from loose fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+  MessageDelivered,
+  MessageRead,
+  MessageUnread,
+  TypingStatus,
+  UserPresence,
+}
+
+impl EventKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::MessageDelivered => "MessageDelivered",
+      Self::MessageRead => "MessageRead",
+      Self::MessageUnread => "MessageUnread",
+      Self::TypingStatus => "TypingStatus",
+      Self::UserPresence => "UserPresence",
+    }
+  }
+
+  /// Fields the kind accepts; anything else set on the builder is a mistake
+  fn accepts(&self, field: &str) -> bool {
+    let fields: &[&str] = match self {
+      Self::MessageDelivered | Self::MessageRead => &["chat_id", "user_id", "message_id", "at"],
+      Self::MessageUnread => &["chat_id", "user_id", "message_id"],
+      Self::TypingStatus => &["chat_id", "user_id", "user_name", "is_typing"],
+      Self::UserPresence => &["user_id", "status", "at"],
+    };
+    fields.contains(&field)
+  }
+
+  /// Payload key carrying the kind's timestamp
+  fn timestamp_key(&self) -> Option<&'static str> {
+    match self {
+      Self::MessageDelivered => Some("delivered_at"),
+      Self::MessageRead => Some("read_at"),
+      Self::UserPresence => Some("last_seen"),
+      Self::MessageUnread | Self::TypingStatus => None,
+    }
+  }
+}
+
+impl fmt::Display for EventKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/// Why an event could not be built
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum EventBuildError {
+  #[error("{kind} event is missing `{field}`")]
+  MissingField {
+    kind: EventKind,
+    field: &'static str,
+  },
+
+  #[error("{kind} event does not take `{field}`")]
+  UnexpectedField {
+    kind: EventKind,
+    field: &'static str,
+  },
+
+  #[error("{kind} event has invalid `{field}`: {value}")]
+  InvalidValue {
+    kind: EventKind,
+    field: &'static str,
+    value: String,
+  },
+}
+
+/**
+ * NotifyEventBuilder
+ *
+ * Assembles a realtime `NotifyEvent` and checks it before anything reaches an SSE stream:
+ * every field the kind needs must be set, ids must be positive, and fields that belong to
+ * other kinds are rejected instead of silently dropped.
+ *
+ * `user_id` is the acting user: the recipient of a delivery, the reader of a read receipt,
+ * the typer or the user whose presence changed. `at` is the kind's timestamp and defaults
+ * to now for deliveries and reads.
+ */
+#[derive(Debug, Clone)]
+pub struct NotifyEventBuilder {
+  kind: EventKind,
+  chat_id: Option<i64>,
+  user_id: Option<i64>,
+  message_id: Option<i64>,
+  user_name: Option<String>,
+  is_typing: Option<bool>,
+  status: Option<String>,
+  at: Option<String>,
+}
+
+impl NotifyEventBuilder {
+  pub fn new(kind: EventKind) -> Self {
+    Self {
+      kind,
+      chat_id: None,
+      user_id: None,
+      message_id: None,
+      user_name: None,
+      is_typing: None,
+      status: None,
+      at: None,
+    }
+  }
+
+  pub fn typing(is_typing: bool) -> Self {
+    Self::new(EventKind::TypingStatus).is_typing(is_typing)
+  }
+
+  pub fn presence(status: impl Into<String>) -> Self {
+    Self::new(EventKind::UserPresence).status(status)
+  }
+
+  /// Start from a JSON event payload. Every known key present is taken, so a payload mixing
+  /// fields of different kinds fails in `build`
+  pub fn from_payload(kind: EventKind, payload: &Value) -> Result<Self, EventBuildError> {
+    let id = |field: &'static str| -> Result<Option<i64>, EventBuildError> {
+      match payload.get(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+          .as_i64()
+          .map(Some)
+          .ok_or_else(|| invalid(kind, field, value)),
+      }
+    };
+    let text = |field: &'static str| -> Result<Option<String>, EventBuildError> {
+      match payload.get(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(value) => Err(invalid(kind, field, value)),
+      }
+    };
+    let is_typing = match payload.get("is_typing") {
+      None | Some(Value::Null) => None,
+      Some(Value::Bool(value)) => Some(*value),
+      Some(value) => return Err(invalid(kind, "is_typing", value)),
+    };
+
+    Ok(Self {
+      kind,
+      chat_id: id("chat_id")?,
+      user_id: id("user_id")?,
+      message_id: id("message_id")?,
+      user_name: text("user_name")?,
+      is_typing,
+      status: text("status")?,
+      at: match kind.timestamp_key() {
+        Some(key) => text(key)?,
+        None => None,
+      },
+    })
+  }
+
+  pub fn chat_id(mut self, chat_id: i64) -> Self {
+    self.chat_id = Some(chat_id);
+    self
+  }
+
+  pub fn user_id(mut self, user_id: i64) -> Self {
+    self.user_id = Some(user_id);
+    self
+  }
+
+  pub fn message_id(mut self, message_id: i64) -> Self {
+    self.message_id = Some(message_id);
+    self
+  }
+
+  pub fn user_name(mut self, user_name: impl Into<String>) -> Self {
+    self.user_name = Some(user_name.into());
+    self
+  }
+
+  pub fn is_typing(mut self, is_typing: bool) -> Self {
+    self.is_typing = Some(is_typing);
+    self
+  }
+
+  pub fn status(mut self, status: impl Into<String>) -> Self {
+    self.status = Some(status.into());
+    self
+  }
+
+  pub fn at(mut self, at: impl Into<String>) -> Self {
+    self.at = Some(at.into());
+    self
+  }
+
+  pub fn build(self) -> Result<NotifyEvent, EventBuildError> {
+    let kind = self.kind;
+    let set = [
+      ("chat_id", self.chat_id.is_some()),
+      ("user_id", self.user_id.is_some()),
+      ("message_id", self.message_id.is_some()),
+      ("user_name", self.user_name.is_some()),
+      ("is_typing", self.is_typing.is_some()),
+      ("status", self.status.is_some()),
+      ("at", self.at.is_some()),
+    ];
+    if let Some((field, _)) = set
+      .into_iter()
+      .find(|(field, is_set)| *is_set && !kind.accepts(field))
+    {
+      return Err(EventBuildError::UnexpectedField { kind, field });
+    }
+
+    let id = |field: &'static str, value: Option<i64>| match value {
+      None => Err(EventBuildError::MissingField { kind, field }),
+      Some(id) if id <= 0 => Err(EventBuildError::InvalidValue {
+        kind,
+        field,
+        value: id.to_string(),
+      }),
+      Some(id) => Ok(id),
+    };
+    let now = || Utc::now().to_rfc3339();
+
+    let event = match kind {
+      EventKind::MessageDelivered => NotifyEvent::MessageDelivered(MessageDeliveredEvent {
+        message_id: id("message_id", self.message_id)?,
+        chat_id: id("chat_id", self.chat_id)?,
+        recipient_id: id("user_id", self.user_id)?,
+        delivered_at: self.at.unwrap_or_else(now),
+      }),
+      EventKind::MessageRead => NotifyEvent::MessageRead(MessageReadEvent {
+        message_id: id("message_id", self.message_id)?,
+        chat_id: id("chat_id", self.chat_id)?,
+        reader_id: id("user_id", self.user_id)?,
+        read_at: self.at.unwrap_or_else(now),
+      }),
+      EventKind::MessageUnread => NotifyEvent::MessageUnread(MessageUnreadEvent {
+        message_id: id("message_id", self.message_id)?,
+        chat_id: id("chat_id", self.chat_id)?,
+        user_id: id("user_id", self.user_id)?,
+      }),
+      EventKind::TypingStatus => NotifyEvent::TypingStatus(TypingEvent {
+        chat_id: id("chat_id", self.chat_id)?,
+        user_id: id("user_id", self.user_id)?,
+        user_name: self.user_name,
+        is_typing: self.is_typing.ok_or(EventBuildError::MissingField {
+          kind,
+          field: "is_typing",
+        })?,
+      }),
+      EventKind::UserPresence => {
+        let user_id = id("user_id", self.user_id)?;
+        let status = self.status.ok_or(EventBuildError::MissingField {
+          kind,
+          field: "status",
+        })?;
+        if !PRESENCE_STATUSES.contains(&status.as_str()) {
+          return Err(EventBuildError::InvalidValue {
+            kind,
+            field: "status",
+            value: status,
+          });
+        }
+        NotifyEvent::UserPresence(UserPresenceEvent {
+          user_id,
+          status,
+          last_seen: self.at,
+        })
+      }
+    };
+
+    Ok(event)
+  }
+}
+
+fn invalid(kind: EventKind, field: &'static str, value: &Value) -> EventBuildError {
+  EventBuildError::InvalidValue {
+    kind,
+    field,
+    value: value.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn valid_events_should_build() {
+    let typing = NotifyEventBuilder::typing(true)
+      .chat_id(7)
+      .user_id(3)
+      .user_name("Alice")
+      .build()
+      .unwrap();
+    assert!(matches!(
+      typing,
+      NotifyEvent::TypingStatus(TypingEvent {
+        chat_id: 7,
+        user_id: 3,
+        is_typing: true,
+        ..
+      })
+    ));
+
+    let read = NotifyEventBuilder::new(EventKind::MessageRead)
+      .chat_id(7)
+      .user_id(3)
+      .message_id(42)
+      .build()
+      .unwrap();
+    match read {
+      NotifyEvent::MessageRead(read) => {
+        assert_eq!((read.message_id, read.reader_id), (42, 3));
+        assert!(!read.read_at.is_empty());
+      }
+      other => panic!("unexpected event {:?}", other),
+    }
+
+    let presence = NotifyEventBuilder::from_payload(
+      EventKind::UserPresence,
+      &json!({ "user_id": 3, "status": "away", "last_seen": "2024-01-01T00:00:00Z" }),
+    )
+    .unwrap()
+    .build()
+    .unwrap();
+    assert!(matches!(
+      presence,
+      NotifyEvent::UserPresence(UserPresenceEvent {
+        user_id: 3,
+        last_seen: Some(_),
+        ..
+      })
+    ));
+  }
+
+  #[test]
+  fn missing_required_fields_should_be_rejected() {
+    let err = NotifyEventBuilder::typing(true)
+      .user_id(3)
+      .build()
+      .unwrap_err();
+    assert_eq!(
+      err,
+      EventBuildError::MissingField {
+        kind: EventKind::TypingStatus,
+        field: "chat_id",
+      }
+    );
+
+    let err = NotifyEventBuilder::new(EventKind::TypingStatus)
+      .chat_id(7)
+      .user_id(3)
+      .build()
+      .unwrap_err();
+    assert!(matches!(
+      err,
+      EventBuildError::MissingField {
+        field: "is_typing",
+        ..
+      }
+    ));
+
+    let err = NotifyEventBuilder::new(EventKind::MessageDelivered)
+      .chat_id(7)
+      .user_id(3)
+      .build()
+      .unwrap_err();
+    assert!(matches!(
+      err,
+      EventBuildError::MissingField {
+        field: "message_id",
+        ..
+      }
+    ));
+  }
+
+  #[test]
+  fn inconsistent_combinations_should_be_rejected() {
+    let err = NotifyEventBuilder::presence("online")
+      .user_id(3)
+      .chat_id(7)
+      .build()
+      .unwrap_err();
+    assert_eq!(
+      err,
+      EventBuildError::UnexpectedField {
+        kind: EventKind::UserPresence,
+        field: "chat_id",
+      }
+    );
+
+    let err = NotifyEventBuilder::from_payload(
+      EventKind::MessageUnread,
+      &json!({ "chat_id": 7, "user_id": 3, "message_id": 42, "is_typing": true }),
+    )
+    .unwrap()
+    .build()
+    .unwrap_err();
+    assert!(matches!(
+      err,
+      EventBuildError::UnexpectedField {
+        field: "is_typing",
+        ..
+      }
+    ));
+  }
+
+  #[test]
+  fn invalid_values_should_be_rejected() {
+    let err = NotifyEventBuilder::presence("busy")
+      .user_id(3)
+      .build()
+      .unwrap_err();
+    assert!(matches!(
+      err,
+      EventBuildError::InvalidValue {
+        field: "status",
+        ..
+      }
+    ));
+
+    let err = NotifyEventBuilder::typing(false)
+      .chat_id(0)
+      .user_id(3)
+      .build()
+      .unwrap_err();
+    assert!(matches!(
+      err,
+      EventBuildError::InvalidValue {
+        field: "chat_id",
+        ..
+      }
+    ));
+
+    let err = NotifyEventBuilder::from_payload(
+      EventKind::TypingStatus,
+      &json!({ "chat_id": "7", "user_id": 3 }),
+    )
+    .unwrap_err();
+    assert!(matches!(
+      err,
+      EventBuildError::InvalidValue {
+        field: "chat_id",
+        ..
+      }
+    ));
+  }
+}
@@ -1,5 +1,7 @@
+pub mod builder;
 pub mod nats;
 pub mod processor;
 pub mod types;
 
-pub use processor::{EventProcessor, handle_system_event};
+pub use builder::{EventBuildError, EventKind, NotifyEventBuilder};
+pub use processor::{DeadLetterQueue, EventProcessor, handle_system_event};
@@ -3,20 +3,91 @@ use chrono::Utc;
 use futures::StreamExt;
 use serde_json::{json, Value};
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::{debug, error, info, warn};
 
 use crate::{
     analytics::types::NotifyEventHelper,
     error::NotifyError,
+    events::builder::{EventKind, NotifyEventBuilder},
+    events::types::{
+        MessageDeliveredEvent, MessageReadEvent, NotifyEvent, TypingEvent, UserPresenceEvent,
+    },
+    observability::metrics::{
+        collectors::{DeliveryMetrics, NATSMetrics},
+        subject_label,
+    },
     state::app_state::ConnectionUpdate,
     state::AppState,
 };
+use fechatter_core::contracts::events::{
+    subjects, AiStreamChunkEvent, ChatEvent, EphemeralMessageEvent, MessagePreviewsEvent,
+    SystemAnnouncementEvent,
+};
 use fechatter_core::{ChatId, UserId};
 
+/// NATS subject that events failing validation are republished to, with the reason
+#[derive(Clone)]
+pub struct DeadLetterQueue {
+    client: async_nats::Client,
+    subject: String,
+}
+
+impl DeadLetterQueue {
+    pub fn new(client: async_nats::Client, subject: impl Into<String>) -> Self {
+        Self {
+            client,
+            subject: subject.into(),
+        }
+    }
+
+    /// Park a rejected event; failures are logged since the event is already lost to clients
+    pub async fn publish(&self, source_subject: &str, payload: &[u8], error: &NotifyError) {
+        let record = dead_letter_record(source_subject, payload, error);
+        let bytes = match serde_json::to_vec(&record) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                error!("Failed to encode dead letter for {}: {}", source_subject, e);
+                return;
+            }
+        };
+
+        match self.client.publish(self.subject.clone(), bytes.into()).await {
+            Ok(()) => warn!(
+                "[NOTIFY] Rejected event from {} sent to {}: {}",
+                source_subject, self.subject, error
+            ),
+            Err(e) => error!(
+                "Failed to publish dead letter for {} to {}: {}",
+                source_subject, self.subject, e
+            ),
+        }
+    }
+}
+
+/// Dead letter body: the original subject and payload plus why it was rejected
+fn dead_letter_record(source_subject: &str, payload: &[u8], error: &NotifyError) -> Value {
+    json!({
+        "subject": source_subject,
+        "error": error.to_string(),
+        "payload": String::from_utf8_lossy(payload),
+        "failed_at": Utc::now()
+    })
+}
+
+/// Rejects events too large to push to clients, before they are parsed or fanned out
+fn check_event_size(size: usize, limit: usize) -> Result<(), NotifyError> {
+    if size > limit {
+        return Err(NotifyError::EventTooLarge { size, limit });
+    }
+    Ok(())
+}
+
 /// Event processor for handling incoming NATS events
 pub struct EventProcessor {
     nats_subscriber: Subscriber,
     state: Arc<AppState>,
+    dead_letters: DeadLetterQueue,
 }
 
 impl EventProcessor {
@@ -24,10 +95,12 @@ impl EventProcessor {
     pub async fn new(
         nats_subscriber: Subscriber,
         state: Arc<AppState>,
+        dead_letters: DeadLetterQueue,
     ) -> Result<Self, NotifyError> {
         Ok(Self {
             nats_subscriber,
             state,
+            dead_letters,
         })
     }
 
@@ -36,8 +109,15 @@ impl EventProcessor {
         info!("Starting event processor");
 
         while let Some(message) = self.nats_subscriber.next().await {
+            let subject = message.subject.to_string();
+            let payload = message.payload.clone();
+
             if let Err(e) = self.process_message(message).await {
-                error!("Failed to process message: {}", e);
+                if e.is_malformed_event() {
+                    self.dead_letters.publish(&subject, &payload, &e).await;
+                } else {
+                    error!("Failed to process message: {}", e);
+                }
             }
         }
 
@@ -45,13 +125,33 @@ impl EventProcessor {
         Ok(())
     }
 
-    /// Process a single NATS message
+    /// Process a single NATS message, recording per-subject metrics
     async fn process_message(&self, message: Message) -> Result<(), NotifyError> {
+        let subject = subject_label(&message.subject);
+        let start = Instant::now();
+        NATSMetrics::message_received(&subject);
+
+        let result = DeliveryMetrics::track(&subject, self.route_message(message)).await;
+        match &result {
+            Err(NotifyError::EventTooLarge { .. }) => NATSMetrics::oversized(&subject),
+            Err(e) if e.is_malformed_event() => NATSMetrics::parse_error(&subject),
+            _ => NATSMetrics::message_processed(&subject, start.elapsed(), result.is_ok()),
+        }
+
+        result
+    }
+
+    /// Parse a NATS message and hand it to the handler for its subject
+    async fn route_message(&self, message: Message) -> Result<(), NotifyError> {
         let subject = &message.subject;
         let payload_size = message.payload.len();
         
         // Add INFO level logging for event reception
         info!("EVENT: [NOTIFY] Received NATS event from subject: {} (size: {} bytes)", subject, payload_size);
+        check_event_size(
+            payload_size,
+            self.state.config.notification.delivery.web.max_event_bytes,
+        )?;
 
         // Parse message payload
         let payload: Value = match serde_json::from_slice(&message.payload) {
@@ -67,6 +167,29 @@ impl EventProcessor {
 
         // Route based on subject
         match subject.as_str() {
+            subjects::SYSTEM_ANNOUNCEMENT => {
+                info!("[NOTIFY] Processing system announcement from: {}", subject);
+                self.handle_system_announcement(payload).await?;
+            }
+            subjects::EPHEMERAL_MESSAGE => {
+                info!("[NOTIFY] Processing ephemeral message from: {}", subject);
+                self.handle_ephemeral_message(payload).await?;
+            }
+            subjects::AI_STREAM_CHUNK => {
+                debug!("[NOTIFY] Processing AI stream chunk from: {}", subject);
+                self.handle_ai_stream_chunk(payload).await?;
+            }
+            subjects::MESSAGE_PREVIEWS => {
+                info!("[NOTIFY] Processing link previews from: {}", subject);
+                self.handle_link_previews(payload).await?;
+            }
+            subjects::CHAT_CREATED
+            | subjects::CHAT_UPDATED
+            | subjects::CHAT_DELETED
+            | subjects::CHAT_OWNERSHIP_TRANSFERRED => {
+                info!("[NOTIFY] Processing chat lifecycle event from: {}", subject);
+                self.handle_chat_lifecycle(payload).await?;
+            }
             s if s.starts_with("fechatter.chat.") => {
                 info!("🗨️ [NOTIFY] Processing chat event from: {}", s);
                 self.handle_chat_event(payload).await?;
@@ -184,6 +307,87 @@ impl EventProcessor {
         Ok(())
     }
 
+    /// Fan an admin announcement out to connected users, limited to its workspace if set
+    async fn handle_system_announcement(&self, payload: Value) -> Result<(), NotifyError> {
+        let announcement: SystemAnnouncementEvent = serde_json::from_value(payload)
+            .map_err(|e| NotifyError::InvalidJson(format!("Invalid system announcement: {}", e)))?;
+        let announcement_id = announcement.id;
+        let workspace_id = announcement.workspace_id;
+
+        let delivered = self.state.broadcast_announcement(announcement);
+        info!(
+            "📢 [NOTIFY] Announcement {} (workspace {:?}) delivered to {} users",
+            announcement_id, workspace_id, delivered
+        );
+
+        Ok(())
+    }
+
+    /// Deliver a slash command reply to its sender's connections only
+    async fn handle_ephemeral_message(&self, payload: Value) -> Result<(), NotifyError> {
+        let event: EphemeralMessageEvent = serde_json::from_value(payload)
+            .map_err(|e| NotifyError::InvalidJson(format!("Invalid ephemeral message: {}", e)))?;
+        let user_id = event.user_id;
+
+        if !self
+            .state
+            .send_to_user(user_id, Arc::new(NotifyEvent::EphemeralMessage(event)))
+        {
+            debug!("[NOTIFY] User {} is offline, ephemeral reply dropped", user_id.0);
+        }
+
+        Ok(())
+    }
+
+    /// Deliver a streamed AI response piece to the connections of the user who asked for it
+    async fn handle_ai_stream_chunk(&self, payload: Value) -> Result<(), NotifyError> {
+        let event: AiStreamChunkEvent = serde_json::from_value(payload)
+            .map_err(|e| NotifyError::InvalidJson(format!("Invalid AI stream chunk: {}", e)))?;
+        let user_id = event.user_id;
+
+        if !self
+            .state
+            .send_to_user(user_id, Arc::new(NotifyEvent::AiStreamChunk(event)))
+        {
+            debug!("[NOTIFY] User {} is offline, AI stream chunk dropped", user_id.0);
+        }
+
+        Ok(())
+    }
+
+    /// Push link previews of a message to the members of its chat
+    async fn handle_link_previews(&self, payload: Value) -> Result<(), NotifyError> {
+        let event: MessagePreviewsEvent = serde_json::from_value(payload)
+            .map_err(|e| NotifyError::InvalidJson(format!("Invalid link previews: {}", e)))?;
+        let (message_id, chat_id) = (event.message_id, event.chat_id);
+
+        let delivered = self
+            .state
+            .broadcast_to_chat(chat_id, Arc::new(NotifyEvent::LinkPreviews(event)))
+            .await;
+        debug!(
+            "[NOTIFY] Link previews of message {} delivered to {} users",
+            message_id.0, delivered
+        );
+
+        Ok(())
+    }
+
+    /// Push a chat lifecycle event to the chat's members so their sidebars refresh
+    async fn handle_chat_lifecycle(&self, payload: Value) -> Result<(), NotifyError> {
+        let event: ChatEvent = serde_json::from_value(payload)
+            .map_err(|e| NotifyError::InvalidJson(format!("Invalid chat event: {}", e)))?;
+        let (kind, chat_id) = (event.kind, event.chat.id);
+
+        let delivered = self.state.apply_chat_event(event).await;
+        debug!(
+            "[NOTIFY] {:?} of chat {} delivered to {} users",
+            kind, chat_id.0, delivered
+        );
+
+        Ok(())
+    }
+
     /// Handle member added to chat
     async fn handle_member_added(&self, chat_id: ChatId, user_id: UserId) -> Result<(), NotifyError> {
         info!("User {} added to chat {}", user_id.0, chat_id.0);
@@ -355,44 +559,40 @@ impl EventProcessor {
             .and_then(|v| v.as_str())
             .unwrap_or("unknown");
 
-        let chat_id = payload
-            .get("chat_id")
-            .and_then(|v| v.as_i64())
-            .map(ChatId);
-
-        let user_id = payload
-            .get("user_id")
-            .and_then(|v| v.as_i64())
-            .map(UserId);
+        // Validate into a typed event first, so a malformed payload never reaches a client
+        let event = match event_type {
+            "typing_started" | "typing_stopped" => {
+                NotifyEventBuilder::from_payload(EventKind::TypingStatus, &payload)?
+                    .is_typing(event_type == "typing_started")
+                    .build()?
+            }
+            "message_read" => NotifyEventBuilder::from_payload(EventKind::MessageRead, &payload)?.build()?,
+            "user_presence" => NotifyEventBuilder::from_payload(EventKind::UserPresence, &payload)?.build()?,
+            _ => {
+                debug!("Unhandled realtime event type: {}", event_type);
+                return Ok(());
+            }
+        };
 
-        match event_type {
-            "typing_started" => {
-                if let (Some(chat_id), Some(user_id)) = (chat_id, user_id) {
-                    info!("⌨️ [NOTIFY] User {} started typing in chat {}", user_id.0, chat_id.0);
-                    self.handle_typing_started(chat_id, user_id, payload).await?;
-                }
+        match event {
+            NotifyEvent::TypingStatus(typing) if typing.is_typing => {
+                info!("⌨️ [NOTIFY] User {} started typing in chat {}", typing.user_id, typing.chat_id);
+                self.handle_typing_started(&typing).await?;
             }
-            "typing_stopped" => {
-                if let (Some(chat_id), Some(user_id)) = (chat_id, user_id) {
-                    info!("⏹️ [NOTIFY] User {} stopped typing in chat {}", user_id.0, chat_id.0);
-                    self.handle_typing_stopped(chat_id, user_id).await?;
-                }
+            NotifyEvent::TypingStatus(typing) => {
+                info!("⏹️ [NOTIFY] User {} stopped typing in chat {}", typing.user_id, typing.chat_id);
+                self.handle_typing_stopped(&typing).await?;
             }
-            "message_read" => {
-                if let (Some(chat_id), Some(user_id)) = (chat_id, user_id) {
-                    info!("👁️ [NOTIFY] User {} read messages in chat {}", user_id.0, chat_id.0);
-                    self.handle_message_read(chat_id, user_id, payload).await?;
-                }
+            NotifyEvent::MessageRead(read) => {
+                info!("👁️ [NOTIFY] User {} read messages in chat {}", read.reader_id, read.chat_id);
+                self.handle_message_read(&read).await?;
             }
-            "user_presence" => {
-                if let Some(user_id) = user_id {
-                    let status = payload.get("status").and_then(|v| v.as_str()).unwrap_or("unknown");
-                    info!("🟢 [NOTIFY] User {} presence changed to: {}", user_id.0, status);
-                    self.handle_user_presence(user_id, status).await?;
-                }
+            NotifyEvent::UserPresence(presence) => {
+                info!("🟢 [NOTIFY] User {} presence changed to: {}", presence.user_id, presence.status);
+                self.handle_user_presence(&presence).await?;
             }
-            _ => {
-                debug!("Unhandled realtime event type: {}", event_type);
+            other => {
+                debug!("Unhandled realtime event: {:?}", other);
             }
         }
 
@@ -435,6 +635,13 @@ impl EventProcessor {
                         warn!("Failed to send SSE to user {}: {}", user_id.0, e);
                     } else {
                         info!("[REALTIME] Sent SSE notification to user {} for message {}", user_id.0, message_id);
+
+                        // Pushed to a connected recipient: record delivery and tell the sender
+                        if let (Ok(message_id), Some(sender_id)) = (message_id.parse::<i64>(), sender_id) {
+                            if user_id != sender_id {
+                                self.record_delivery(chat_id, message_id, sender_id, user_id).await;
+                            }
+                        }
                     }
                 }
             }
@@ -445,10 +652,32 @@ impl EventProcessor {
         Ok(())
     }
 
+    /// Mark a pushed message delivered and send the delivery receipt to its sender
+    async fn record_delivery(&self, chat_id: ChatId, message_id: i64, sender_id: UserId, recipient_id: UserId) {
+        match self.state.mark_message_delivered(message_id, recipient_id).await {
+            Ok(true) => {
+                let receipt = Arc::new(NotifyEvent::MessageDelivered(MessageDeliveredEvent {
+                    message_id,
+                    chat_id: chat_id.0,
+                    recipient_id: recipient_id.0,
+                    delivered_at: Utc::now().to_rfc3339(),
+                }));
+                self.state.send_to_user(sender_id, receipt);
+            }
+            Ok(false) => {
+                debug!("Message {} already delivered to user {}", message_id, recipient_id.0);
+            }
+            Err(e) => {
+                warn!("Failed to mark message {} delivered to user {}: {}", message_id, recipient_id.0, e);
+            }
+        }
+    }
+
     /// Handle typing started event
-    async fn handle_typing_started(&self, chat_id: ChatId, user_id: UserId, payload: Value) -> Result<(), NotifyError> {
-        let user_name = payload.get("user_name").and_then(|v| v.as_str()).unwrap_or("Unknown User");
-        
+    async fn handle_typing_started(&self, typing: &TypingEvent) -> Result<(), NotifyError> {
+        let (chat_id, user_id) = (ChatId(typing.chat_id), UserId(typing.user_id));
+        let user_name = typing.user_name.as_deref().unwrap_or("Unknown User");
+
         // Get chat members
         let members = self.state.get_chat_members(chat_id).await.unwrap_or_default();
 
@@ -473,7 +702,9 @@ impl EventProcessor {
     }
 
     /// Handle typing stopped event
-    async fn handle_typing_stopped(&self, chat_id: ChatId, user_id: UserId) -> Result<(), NotifyError> {
+    async fn handle_typing_stopped(&self, typing: &TypingEvent) -> Result<(), NotifyError> {
+        let (chat_id, user_id) = (ChatId(typing.chat_id), UserId(typing.user_id));
+
         // Get chat members
         let members = self.state.get_chat_members(chat_id).await.unwrap_or_default();
 
@@ -497,9 +728,9 @@ impl EventProcessor {
     }
 
     /// Handle message read event
-    async fn handle_message_read(&self, chat_id: ChatId, user_id: UserId, payload: Value) -> Result<(), NotifyError> {
-        let message_id = payload.get("message_id").and_then(|v| v.as_i64());
-        
+    async fn handle_message_read(&self, read: &MessageReadEvent) -> Result<(), NotifyError> {
+        let (chat_id, user_id) = (ChatId(read.chat_id), UserId(read.reader_id));
+
         // Get chat members
         let members = self.state.get_chat_members(chat_id).await.unwrap_or_default();
 
@@ -510,8 +741,8 @@ impl EventProcessor {
                     "type": "message_read",
                     "chat_id": chat_id.0,
                     "user_id": user_id.0,
-                    "message_id": message_id,
-                    "read_at": Utc::now()
+                    "message_id": read.message_id,
+                    "read_at": read.read_at
                 });
 
                 if let Err(e) = self.state.send_notification_to_user(member, notification).await {
@@ -524,7 +755,11 @@ impl EventProcessor {
     }
 
     /// Handle user presence event
-    async fn handle_user_presence(&self, user_id: UserId, status: &str) -> Result<(), NotifyError> {
+    async fn handle_user_presence(&self, presence: &UserPresenceEvent) -> Result<(), NotifyError> {
+        let (user_id, status) = (UserId(presence.user_id), presence.status.as_str());
+        // Before going offline clears the user's chats and workspace
+        let subject = self.state.presence_subject(user_id).await;
+
         // Update user status in state
         match status {
             "online" => {
@@ -538,7 +773,6 @@ impl EventProcessor {
             }
         }
 
-        // Broadcast presence update to relevant users (could be optimized)
         let notification = json!({
             "type": "user_presence",
             "user_id": user_id.0,
@@ -546,9 +780,9 @@ impl EventProcessor {
             "timestamp": Utc::now()
         });
 
-        // For now, broadcast to all connected users
-        // TODO: Optimize to only send to users who can see this user
-        self.state.broadcast_to_all_users(notification).await?;
+        // Only to users whose presence interest covers this user
+        let sent_count = self.state.broadcast_presence(&subject, notification);
+        debug!("Presence of user {} sent to {} users", user_id.0, sent_count);
 
         Ok(())
     }
@@ -598,6 +832,8 @@ pub async fn handle_system_event(
 mod tests {
     use super::*;
     use crate::analytics::types::NotifyEventHelper;
+    use fechatter_core::contracts::events::ChatLifecycle;
+    use fechatter_core::WorkspaceId;
 
     #[test]
     fn test_event_creation() {
@@ -642,4 +878,116 @@ mod tests {
         assert!(event.context.is_some());
         assert!(event.event_type.is_some());
     }
+
+    #[test]
+    fn malformed_realtime_event_should_become_a_dead_letter() {
+        let payload = br#"{"event_type":"typing_started","user_id":3}"#;
+        let event: Value = serde_json::from_slice(payload).unwrap();
+        let error: NotifyError = NotifyEventBuilder::from_payload(EventKind::TypingStatus, &event)
+            .unwrap()
+            .is_typing(true)
+            .build()
+            .unwrap_err()
+            .into();
+        assert!(error.is_malformed_event());
+
+        let record = dead_letter_record("fechatter.realtime.typing.7", payload, &error);
+        assert_eq!(record["subject"], "fechatter.realtime.typing.7");
+        assert_eq!(
+            record["error"],
+            "Invalid event: TypingStatus event is missing `chat_id`"
+        );
+        assert_eq!(record["payload"], std::str::from_utf8(payload).unwrap());
+    }
+
+    #[test]
+    fn oversized_event_should_become_a_dead_letter() {
+        let payload = json!({ "event_type": "message_created", "content": "x".repeat(2048) });
+        let payload = serde_json::to_vec(&payload).unwrap();
+        assert!(check_event_size(payload.len(), 4096).is_ok());
+
+        let error = check_event_size(payload.len(), 1024).unwrap_err();
+        assert!(error.is_malformed_event());
+        let record = dead_letter_record("fechatter.message.created", &payload, &error);
+        assert_eq!(
+            record["error"],
+            format!("Event of {} bytes exceeds the 1024 byte limit", payload.len())
+        );
+    }
+
+    #[tokio::test]
+    async fn workspace_announcement_should_only_reach_that_workspace() {
+        let state = AppState::new(crate::config::AppConfig::load().expect("config")).unwrap();
+        let mut in_workspace = state.subscribe_user(UserId(1), WorkspaceId(10), 8);
+        let mut other_workspace = state.subscribe_user(UserId(2), WorkspaceId(20), 8);
+
+        // As published by fechatter_server's admin endpoint
+        let payload = json!({
+            "id": uuid::Uuid::new_v4(),
+            "title": "Scheduled maintenance",
+            "message": "Fechatter will be read-only from 02:00 UTC",
+            "severity": "warning",
+            "workspace_id": 10,
+            "sent_by": 99,
+            "occurred_at": Utc::now()
+        });
+        let announcement: SystemAnnouncementEvent = serde_json::from_value(payload).unwrap();
+        assert_eq!(state.broadcast_announcement(announcement), 1);
+
+        match in_workspace.try_recv().unwrap().as_ref() {
+            NotifyEvent::SystemAnnouncement(announcement) => {
+                assert_eq!(announcement.title, "Scheduled maintenance");
+                assert_eq!(announcement.workspace_id, Some(WorkspaceId(10)));
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert!(other_workspace.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn chat_lifecycle_events_should_reach_members_and_track_membership() {
+        let state = AppState::new(crate::config::AppConfig::load().expect("config")).unwrap();
+        let mut member = state.subscribe_user(UserId(1), WorkspaceId(10), 8);
+        let mut outsider = state.subscribe_user(UserId(3), WorkspaceId(10), 8);
+
+        // As published by fechatter_server's chat domain service
+        let event = |kind: &str| -> ChatEvent {
+            serde_json::from_value(json!({
+                "kind": kind,
+                "chat": {
+                    "id": 42,
+                    "workspace_id": 10,
+                    "name": "Roadmap",
+                    "chat_type": "Group",
+                    "chat_members": [1, 2],
+                    "description": "",
+                    "created_by": 1,
+                    "created_at": Utc::now(),
+                    "updated_at": Utc::now()
+                },
+                "actor_id": 1,
+                "members": [1, 2],
+                "occurred_at": Utc::now()
+            }))
+            .unwrap()
+        };
+
+        assert_eq!(state.apply_chat_event(event("created")).await, 1);
+        match member.try_recv().unwrap().as_ref() {
+            NotifyEvent::ChatUpdated(chat_event) => {
+                assert_eq!(chat_event.kind, ChatLifecycle::Created);
+                assert_eq!(chat_event.chat.name, "Roadmap");
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert!(outsider.try_recv().is_err());
+        assert_eq!(state.get_online_chat_members(ChatId(42)).await, vec![UserId(1)]);
+
+        assert_eq!(state.apply_chat_event(event("deleted")).await, 1);
+        assert!(matches!(
+            member.try_recv().unwrap().as_ref(),
+            NotifyEvent::ChatUpdated(chat_event) if chat_event.kind == ChatLifecycle::Deleted
+        ));
+        assert_eq!(state.active_chat_count(), 0);
+    }
 }
@@ -1,5 +1,11 @@
 use chrono::{DateTime, Utc};
-use fechatter_core::{Chat, Message};
+use fechatter_core::{
+  Chat, Message,
+  contracts::events::{
+    AiStreamChunkEvent, ChatEvent, EphemeralMessageEvent, MessagePreviewsEvent,
+    SystemAnnouncementEvent,
+  },
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -18,6 +24,15 @@ pub struct MessageReadEvent {
   pub read_at: String,
 }
 
+/// A message reached a connected recipient (not necessarily read yet)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MessageDeliveredEvent {
+  pub message_id: i64,
+  pub chat_id: i64,
+  pub recipient_id: i64,
+  pub delivered_at: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MessageUnreadEvent {
   pub message_id: i64,
@@ -59,11 +74,27 @@ pub enum NotifyEvent {
   DuplicateMessageAttempted(DuplicateMessagePayload),
 
   // Realtime stream events (low-latency delivery)
+  MessageDelivered(MessageDeliveredEvent),
   MessageRead(MessageReadEvent),
   MessageUnread(MessageUnreadEvent),
   TypingStatus(TypingEvent),
   UserPresence(UserPresenceEvent),
 
+  // Admin broadcast (maintenance banners and similar)
+  SystemAnnouncement(SystemAnnouncementEvent),
+
+  // Slash command reply for its sender only
+  EphemeralMessage(EphemeralMessageEvent),
+
+  // Streamed AI response piece for the user who asked for it
+  AiStreamChunk(AiStreamChunkEvent),
+
+  // Link previews for an already delivered message
+  LinkPreviews(MessagePreviewsEvent),
+
+  // Chat created, renamed, deleted or handed over; members refresh their sidebar
+  ChatUpdated(ChatEvent),
+
   // Generic event extensibility
   Generic(serde_json::Value),
 }
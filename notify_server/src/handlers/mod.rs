@@ -1,7 +1,9 @@
 pub mod health;
 pub mod online_users;
+pub mod presence_interest;
 
 pub use health::sse_health_check;
 pub use online_users::{OnlineUserResponse, OnlineUsersQuery, get_online_users_handler};
+pub use presence_interest::set_presence_interest_handler;
 
 pub use health::SSEHealthResponse;
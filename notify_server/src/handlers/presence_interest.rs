@@ -0,0 +1,29 @@
+use axum::{Extension, extract::State, response::Json};
+use tracing::info;
+
+use crate::{
+  error::NotifyError,
+  state::{AppState, PresenceInterest},
+};
+use fechatter_core::{AuthUser, UserId};
+
+/// Replace whose presence changes the caller's open event streams receive. Chats the caller
+/// is not a member of are dropped; the interest in effect is returned
+pub async fn set_presence_interest_handler(
+  State(state): State<AppState>,
+  Extension(user): Extension<AuthUser>,
+  Json(interest): Json<PresenceInterest>,
+) -> Result<Json<PresenceInterest>, NotifyError> {
+  let user_id = UserId(user.id.into());
+  // Interests live with the connection, on the instance serving it
+  if !state.is_user_online(user_id) {
+    return Err(NotifyError::NotFoundError(format!(
+      "User {} has no open event stream",
+      user_id.0
+    )));
+  }
+
+  let interest = state.set_presence_interest(user_id, interest).await;
+  info!("User {} presence interest set to {:?}", user_id.0, interest);
+  Ok(Json(interest))
+}
@@ -24,8 +24,8 @@ pub use state::AppState;
 
 // Re-export handlers
 pub use handlers::{
-  get_online_users_handler, sse_health_check, OnlineUserResponse, OnlineUsersQuery,
-  SSEHealthResponse,
+  get_online_users_handler, set_presence_interest_handler, sse_health_check, OnlineUserResponse,
+  OnlineUsersQuery, SSEHealthResponse,
 };
 
 // Re-export connection handler
@@ -35,7 +35,7 @@ use anyhow::Result;
 use axum::{
   middleware::from_fn_with_state,
   response::{Html, IntoResponse},
-  routing::get,
+  routing::{get, put},
   Router,
 };
 use fechatter_core::middlewares::{verify_query_token_middleware, verify_token_middleware};
@@ -61,16 +61,22 @@ pub async fn get_router(config: AppConfig) -> Result<Router> {
       "fechatter.user.>",
       "fechatter.message.>",
       "fechatter.realtime.>",
+      "fechatter.system.>",
       "fechatter.messages.created",
       "fechatter.chats.member.joined",
       "fechatter.chats.member.left",
     ];
 
     let state_arc = Arc::new(state.clone());
+    let dead_letters = events::DeadLetterQueue::new(
+      nats_client.client().clone(),
+      state.config.messaging.nats.dead_letter_subject.clone(),
+    );
     for subject in subjects {
       tracing::info!("SUBSCRIPTION: [NOTIFY] Subscribing to NATS subject: {}", subject);
       let subscriber = nats_client.subscribe(subject).await?;
-      let processor = EventProcessor::new(subscriber, state_arc.clone()).await?;
+      let processor =
+        EventProcessor::new(subscriber, state_arc.clone(), dead_letters.clone()).await?;
 
       // Spawn event processor for this subject
       tokio::spawn(async move {
@@ -104,6 +110,7 @@ pub async fn get_router(config: AppConfig) -> Result<Router> {
   let api_routes = Router::new()
     .route("/online-users", get(get_online_users_handler))
     .route("/sse/health", get(sse_health_check))
+    .route("/presence/interest", put(set_presence_interest_handler))
     .layer(from_fn_with_state(
       state.clone(),
       verify_token_middleware::<AppState>,
@@ -1,6 +1,11 @@
 use anyhow::Result;
 
+use fechatter_core::utils::{
+  RedactedSummary,
+  schema::{MIGRATOR, SchemaCheckMode, verify_schema},
+};
 use notify_server::{AppConfig, get_router};
+use std::net::SocketAddr;
 use tokio::net::TcpListener;
 use tracing::{info, level_filters::LevelFilter};
 use tracing_subscriber::{
@@ -26,7 +31,7 @@ async fn main() -> Result<()> {
   let config = match AppConfig::load() {
     Ok(config) => {
       // Print configuration summary (excluding sensitive information)
-      info!("{}", config.get_summary());
+      info!("{}", config.redacted_summary());
 
       // Validate configuration in production environment
       if std::env::var("ENVIRONMENT").unwrap_or_default() == "production" {
@@ -48,6 +53,17 @@ async fn main() -> Result<()> {
     }
   };
 
+  // Refuse to start against a schema the embedded migrations don't match
+  if config.server.schema_check != SchemaCheckMode::Skip {
+    let pool = sqlx::PgPool::connect(&config.server.db_url).await?;
+    if let Err(e) = verify_schema(&pool, &MIGRATOR, config.server.schema_check).await {
+      eprintln!("ERROR: Database schema check failed: {}", e);
+      eprintln!("   Run the pending migrations or set server.schema_check to warn");
+      std::process::exit(1);
+    }
+    pool.close().await;
+  }
+
   let addr = format!("0.0.0.0:{}", config.server.port);
   let app = get_router(config).await?;
 
@@ -55,7 +71,12 @@ async fn main() -> Result<()> {
 
   info!("notify_server listening on: {}", addr);
 
-  axum::serve(listener, app.into_make_service()).await?;
+  // Peer addresses key the connection rate limit when no X-Forwarded-For is present
+  axum::serve(
+    listener,
+    app.into_make_service_with_connect_info::<SocketAddr>(),
+  )
+  .await?;
 
   Ok(())
 }
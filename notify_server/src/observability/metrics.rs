@@ -4,6 +4,22 @@ use metrics_exporter_prometheus::PrometheusBuilder;
 use std::time::Duration;
 use tracing::info;
 
+/// A NATS subject as a metric label. Numeric tokens such as chat ids become `*`, so
+/// `fechatter.realtime.typing.7` is counted as `fechatter.realtime.typing.*`
+pub fn subject_label(subject: &str) -> String {
+    subject
+        .split('.')
+        .map(|token| {
+            if !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit()) {
+                "*"
+            } else {
+                token
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 /// Initialize Prometheus metrics for notify_server
 pub async fn init_metrics() -> Result<(), NotifyError> {
     let builder = PrometheusBuilder::new();
@@ -30,7 +46,11 @@ fn register_notify_metrics() {
     gauge!("notify_sse_connections_active").set(0.0);
     counter!("notify_sse_connections_total", "status" => "connected").absolute(0);
     counter!("notify_sse_connections_total", "status" => "disconnected").absolute(0);
+    counter!("notify_sse_connections_total", "status" => "throttled").absolute(0);
     histogram!("notify_sse_connection_duration_seconds").record(0.0);
+    counter!("notify_sse_events_dropped_total", "policy" => "drop_oldest").absolute(0);
+    counter!("notify_sse_events_dropped_total", "policy" => "disconnect").absolute(0);
+    counter!("notify_sse_events_dropped_total", "policy" => "oversized").absolute(0);
 
     // NATS metrics
     counter!("notify_nats_messages_received_total", "subject" => "chat.events").absolute(0);
@@ -47,6 +67,12 @@ fn register_notify_metrics() {
     counter!("notify_events_broadcast_failed_total", "event_type" => "message", "error_type" => "connection_lost").absolute(0);
     histogram!("notify_event_broadcast_duration_seconds", "event_type" => "message").record(0.0);
 
+    // Per-subject delivery metrics
+    counter!("notify_events_delivered_total", "subject" => "fechatter.message.*").absolute(0);
+    counter!("notify_events_dropped_total", "subject" => "fechatter.message.*", "reason" => "offline").absolute(0);
+    counter!("notify_events_dropped_total", "subject" => "fechatter.message.*", "reason" => "closed").absolute(0);
+    histogram!("notify_event_fanout_size", "subject" => "fechatter.message.*").record(0.0);
+
     // Health check metrics
     counter!("notify_health_checks_total", "status" => "healthy").absolute(0);
     histogram!("notify_health_check_duration_seconds").record(0.0);
@@ -57,6 +83,8 @@ fn register_notify_metrics() {
 /// Metrics collection utilities for notify_server
 pub mod collectors {
     use super::*;
+    use std::cell::Cell;
+    use std::future::Future;
 
     /// SSE connection metrics collector
     pub struct SSEMetrics;
@@ -76,6 +104,22 @@ pub mod collectors {
         pub fn record_active_connections(count: usize) {
             gauge!("notify_sse_connections_active").set(count as f64);
         }
+
+        /// Events a slow consumer lost because its buffer overflowed
+        pub fn events_dropped(count: u64, policy: &str) {
+            counter!("notify_sse_events_dropped_total", "policy" => policy.to_string())
+                .increment(count);
+        }
+
+        /// Outgoing event larger than `max_event_bytes`, left out of the stream
+        pub fn oversized_event_dropped() {
+            counter!("notify_sse_events_dropped_total", "policy" => "oversized").increment(1);
+        }
+
+        /// Connection attempt refused by the connection rate limit
+        pub fn connection_throttled() {
+            counter!("notify_sse_connections_total", "status" => "throttled").increment(1);
+        }
     }
 
     /// NATS message processing metrics
@@ -107,6 +151,13 @@ pub mod collectors {
                     "subject" => subject.to_string(), 
                     "error_type" => "parse_error").increment(1);
         }
+
+        /// Event over `max_event_bytes`, sent to the dead letter queue
+        pub fn oversized(subject: &str) {
+            counter!("notify_nats_messages_failed_total", 
+                    "subject" => subject.to_string(), 
+                    "error_type" => "oversized").increment(1);
+        }
     }
 
     /// Online users metrics
@@ -147,6 +198,87 @@ pub mod collectors {
         }
     }
 
+    /// Why an event did not reach one of its recipients
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DropReason {
+        /// The user has no open connection
+        Offline,
+        /// The user's channel had no receivers left
+        Closed,
+    }
+
+    impl DropReason {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                Self::Offline => "offline",
+                Self::Closed => "closed",
+            }
+        }
+    }
+
+    /// Deliveries made while handling one event
+    #[derive(Debug, Default)]
+    struct FanOut {
+        delivered: Cell<u64>,
+        offline: Cell<u64>,
+        closed: Cell<u64>,
+    }
+
+    tokio::task_local! {
+        static FAN_OUT: FanOut;
+    }
+
+    /// Per-subject delivery metrics. Deliveries are attributed to the event whose handling
+    /// made them; those made outside `track`, such as heartbeats, are not counted
+    pub struct DeliveryMetrics;
+
+    impl DeliveryMetrics {
+        /// Run the handling of an event from `subject`, then record how many users it reached
+        /// and how many it missed
+        pub async fn track<F: Future>(subject: &str, handling: F) -> F::Output {
+            let (output, fan_out) = FAN_OUT
+                .scope(FanOut::default(), async {
+                    let output = handling.await;
+                    let fan_out = FAN_OUT.with(|fan_out| {
+                        (fan_out.delivered.get(), fan_out.offline.get(), fan_out.closed.get())
+                    });
+                    (output, fan_out)
+                })
+                .await;
+
+            let (delivered, offline, closed) = fan_out;
+            counter!("notify_events_delivered_total",
+                    "subject" => subject.to_string()).increment(delivered);
+            for (reason, count) in [(DropReason::Offline, offline), (DropReason::Closed, closed)] {
+                if count > 0 {
+                    counter!("notify_events_dropped_total",
+                            "subject" => subject.to_string(),
+                            "reason" => reason.as_str()).increment(count);
+                }
+            }
+            histogram!("notify_event_fanout_size",
+                      "subject" => subject.to_string()).record(delivered as f64);
+
+            output
+        }
+
+        /// An event reached one of its recipients
+        pub fn delivered() {
+            let _ = FAN_OUT.try_with(|fan_out| fan_out.delivered.set(fan_out.delivered.get() + 1));
+        }
+
+        /// An event missed one of its recipients
+        pub fn dropped(reason: DropReason) {
+            let _ = FAN_OUT.try_with(|fan_out| {
+                let count = match reason {
+                    DropReason::Offline => &fan_out.offline,
+                    DropReason::Closed => &fan_out.closed,
+                };
+                count.set(count.get() + 1);
+            });
+        }
+    }
+
     /// Health check metrics
     pub struct HealthMetrics;
 
@@ -157,4 +289,71 @@ pub mod collectors {
             histogram!("notify_health_check_duration_seconds").record(duration.as_secs_f64());
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::collectors::{DeliveryMetrics, DropReason};
+    use super::*;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    #[test]
+    fn numeric_subject_tokens_should_be_collapsed() {
+        assert_eq!(
+            subject_label("fechatter.realtime.typing.7"),
+            "fechatter.realtime.typing.*"
+        );
+        assert_eq!(
+            subject_label("fechatter.chat.12.member.34"),
+            "fechatter.chat.*.member.*"
+        );
+        assert_eq!(
+            subject_label("fechatter.message.previews"),
+            "fechatter.message.previews"
+        );
+    }
+
+    #[tokio::test]
+    async fn deliveries_should_be_counted_per_subject() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        DeliveryMetrics::track("fechatter.message.*", async {
+            DeliveryMetrics::delivered();
+            DeliveryMetrics::delivered();
+            DeliveryMetrics::dropped(DropReason::Offline);
+        })
+        .await;
+        // Outside an event, e.g. a heartbeat
+        DeliveryMetrics::delivered();
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let value = |name: &str, reason: Option<&str>| {
+            snapshot
+                .iter()
+                .find(|(key, _, _, _)| {
+                    let key = key.key();
+                    key.name() == name
+                        && key.labels().any(|label| label.value() == "fechatter.message.*")
+                        && reason.map_or(true, |reason| {
+                            key.labels().any(|label| label.value() == reason)
+                        })
+                })
+                .map(|(_, _, _, value)| value.clone())
+        };
+        assert_eq!(
+            value("notify_events_delivered_total", None),
+            Some(DebugValue::Counter(2))
+        );
+        assert_eq!(
+            value("notify_events_dropped_total", Some("offline")),
+            Some(DebugValue::Counter(1))
+        );
+        assert_eq!(value("notify_events_dropped_total", Some("closed")), None);
+        assert!(matches!(
+            value("notify_event_fanout_size", None),
+            Some(DebugValue::Histogram(sizes)) if sizes.len() == 1 && sizes[0].into_inner() == 2.0
+        ));
+    }
+}
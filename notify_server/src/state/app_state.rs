@@ -7,15 +7,33 @@ use tracing::{debug, info, warn};
 use crate::{
   analytics::AnalyticsPublisher,
   config::AppConfig,
-  connections::manager::{ConnectionManager, ConnectionStats},
+  connections::{
+    manager::{ConnectionManager, ConnectionStats},
+    rate_limit::ConnectRateLimiter,
+  },
   error::NotifyError,
   events::types::NotifyEvent,
+  observability::metrics::collectors::{DeliveryMetrics, DropReason},
+  state::presence::PresenceTracker,
+  state::presence_interest::{PresenceInterest, PresenceInterests, PresenceSubject},
 };
-use fechatter_core::{ChatId, ErrorMapper, TokenManager, TokenVerifier, UserClaims, UserId};
+use fechatter_core::{
+  ChatId, ErrorMapper, PresenceStatus, PresenceStore, TokenManager, TokenVerifier, UserClaims,
+  UserId, WorkspaceId,
+};
+use fechatter_core::contracts::events::{ChatEvent, ChatLifecycle, SystemAnnouncementEvent};
+use fechatter_core::contracts::PresenceTtl;
+use fechatter_core::services::presence::RedisPresenceStore;
 
 type UserConnections = Arc<DashMap<UserId, broadcast::Sender<Arc<NotifyEvent>>>>;
 type ChatMembers = Arc<DashMap<ChatId, HashSet<UserId>>>;
 type UserChats = Arc<DashMap<UserId, HashSet<ChatId>>>;
+type UserWorkspaces = Arc<DashMap<UserId, WorkspaceId>>;
+
+fn presence_tracker(config: &AppConfig) -> PresenceTracker {
+  let grace_period = config.notification.delivery.web.presence_grace_period_ms;
+  PresenceTracker::new(std::time::Duration::from_millis(grace_period))
+}
 
 #[derive(Clone)]
 pub struct AppState {
@@ -27,8 +45,18 @@ pub struct AppStateInner {
   pub user_connections: UserConnections,
   pub chat_members: ChatMembers,
   pub user_chats: UserChats,
+  /// Workspace of each connected user, for workspace-targeted broadcasts
+  pub user_workspaces: UserWorkspaces,
   pub connection_manager: ConnectionManager,
   pub analytics: AnalyticsPublisher,
+  pub presence: Option<Arc<dyn PresenceStore>>,
+  /// Connection-level online state, reconciled before anything is written to `presence`
+  pub presence_tracker: PresenceTracker,
+  /// Whose presence changes each connected user receives
+  pub presence_interests: PresenceInterests,
+  /// Connection attempt limits for `/events`, `None` while disabled
+  pub connect_rate_limiter: Option<ConnectRateLimiter>,
+  pub db_pool: sqlx::PgPool,
   token_manager: TokenManager,
 }
 
@@ -74,7 +102,11 @@ impl AppState {
     let chat_members = Arc::new(DashMap::new());
     let user_chats = Arc::new(DashMap::new());
     let connection_manager = ConnectionManager::new();
+    let presence_tracker = presence_tracker(&config);
+    let connect_rate_limiter =
+      ConnectRateLimiter::from_config(&config.notification.delivery.web.connect_rate_limit);
     let token_manager = TokenManager::new(&config.auth)?;
+    let db_pool = sqlx::PgPool::connect_lazy(&config.server.db_url)?;
     
     // Create a disabled analytics publisher initially
     // Will be initialized properly in try_new_async()
@@ -86,8 +118,14 @@ impl AppState {
         user_connections,
         chat_members,
         user_chats,
+        user_workspaces: Arc::new(DashMap::new()),
         connection_manager,
         analytics,
+        presence: None,
+        presence_tracker,
+        presence_interests: PresenceInterests::new(),
+        connect_rate_limiter,
+        db_pool,
         token_manager,
       }),
     })
@@ -99,20 +137,44 @@ impl AppState {
     let chat_members = Arc::new(DashMap::new());
     let user_chats = Arc::new(DashMap::new());
     let connection_manager = ConnectionManager::new();
+    let presence_tracker = presence_tracker(&config);
+    let connect_rate_limiter =
+      ConnectRateLimiter::from_config(&config.notification.delivery.web.connect_rate_limit);
     let token_manager = TokenManager::new(&config.auth)?;
+    let db_pool = sqlx::PgPool::connect_lazy(&config.server.db_url)?;
     
     // Initialize analytics publisher with proper config
     let analytics = AnalyticsPublisher::new(config.analytics.clone()).await?;
     info!("Analytics publisher initialized: enabled={}", analytics.is_enabled());
 
+    // Connect to the presence store shared with fechatter_server
+    let presence: Option<Arc<dyn PresenceStore>> = match &config.presence {
+      Some(presence_config) => {
+        match RedisPresenceStore::new(&presence_config.redis_url, PresenceTtl::default()).await {
+          Ok(store) => Some(Arc::new(store)),
+          Err(e) => {
+            warn!("Failed to connect presence store, presence will not be tracked: {}", e);
+            None
+          }
+        }
+      }
+      None => None,
+    };
+
     Ok(Self {
       inner: Arc::new(AppStateInner {
         config,
         user_connections,
         chat_members,
         user_chats,
+        user_workspaces: Arc::new(DashMap::new()),
         connection_manager,
         analytics,
+        presence,
+        presence_tracker,
+        presence_interests: PresenceInterests::new(),
+        connect_rate_limiter,
+        db_pool,
         token_manager,
       }),
     })
@@ -195,6 +257,39 @@ impl AppState {
     Ok(user_ids)
   }
 
+  /// Record that a message was pushed to a recipient; returns false if it was already delivered
+  pub async fn mark_message_delivered(
+    &self,
+    message_id: i64,
+    user_id: UserId,
+  ) -> Result<bool, anyhow::Error> {
+    let inserted = sqlx::query(
+      r#"INSERT INTO message_receipts (message_id, user_id, status, timestamp)
+         VALUES ($1, $2, 'delivered', NOW())
+         ON CONFLICT (message_id, user_id, status) DO NOTHING"#,
+    )
+    .bind(message_id)
+    .bind(user_id.0)
+    .execute(&self.db_pool)
+    .await?
+    .rows_affected()
+      > 0;
+
+    if inserted {
+      sqlx::query(
+        r#"UPDATE messages
+           SET delivered_at = COALESCE(delivered_at, NOW()),
+               status = CASE WHEN status = 'sent' THEN 'delivered' ELSE status END
+           WHERE id = $1"#,
+      )
+      .bind(message_id)
+      .execute(&self.db_pool)
+      .await?;
+    }
+
+    Ok(inserted)
+  }
+
   /// Get online members of a chat (based on cache and connection state)
   pub async fn get_online_chat_members(&self, chat_id: ChatId) -> Vec<UserId> {
     // First check cache
@@ -245,10 +340,27 @@ impl AppState {
     Ok(())
   }
 
+  /// Receiver for a new connection of `user_id`; all of a user's connections share one channel
+  pub fn subscribe_user(
+    &self,
+    user_id: UserId,
+    workspace_id: WorkspaceId,
+    capacity: usize,
+  ) -> broadcast::Receiver<Arc<NotifyEvent>> {
+    self.user_workspaces.insert(user_id, workspace_id);
+    self
+      .user_connections
+      .entry(user_id)
+      .or_insert_with(|| broadcast::channel(capacity).0)
+      .subscribe()
+  }
+
   /// Clean up mappings when user disconnects
   pub async fn unregister_user_from_chats(&self, user_id: UserId) {
     // Remove from user connections
     self.user_connections.remove(&user_id);
+    self.user_workspaces.remove(&user_id);
+    self.presence_interests.remove(user_id);
 
     // Remove from all chat member maps
     if let Some((_, user_chats)) = self.user_chats.remove(&user_id) {
@@ -266,8 +378,12 @@ impl AppState {
   pub fn send_to_user(&self, user_id: UserId, event: Arc<NotifyEvent>) -> bool {
     if let Some(tx) = self.user_connections.get(&user_id) {
       match tx.send(event) {
-        Ok(_) => true,
+        Ok(_) => {
+          DeliveryMetrics::delivered();
+          true
+        }
         Err(e) => {
+          DeliveryMetrics::dropped(DropReason::Closed);
           warn!("ERROR: Failed to send event to user {}: {}", user_id.0, e);
           // Automatically clean up invalid connection
           self.user_connections.remove(&user_id);
@@ -275,6 +391,7 @@ impl AppState {
         }
       }
     } else {
+      DeliveryMetrics::dropped(DropReason::Offline);
       false
     }
   }
@@ -403,6 +520,157 @@ impl AppState {
     Ok(())
   }
 
+  /// Push an announcement to every connected user, or only those in its workspace.
+  /// Returns how many users it reached
+  pub fn broadcast_announcement(&self, announcement: SystemAnnouncementEvent) -> usize {
+    let recipients: Vec<UserId> = self
+      .user_connections
+      .iter()
+      .map(|entry| *entry.key())
+      .filter(|user_id| match announcement.workspace_id {
+        Some(workspace_id) => self
+          .user_workspaces
+          .get(user_id)
+          .is_some_and(|user_workspace| *user_workspace == workspace_id),
+        None => true,
+      })
+      .collect();
+
+    let event = Arc::new(NotifyEvent::SystemAnnouncement(announcement));
+    self.broadcast_to_users(recipients, event)
+  }
+
+  /// Keep chat membership in step with a chat lifecycle event and push it to the chat's members.
+  /// Returns how many users it reached
+  pub async fn apply_chat_event(&self, event: ChatEvent) -> usize {
+    let chat_id = event.chat.id;
+    let members = event.members.clone();
+
+    match event.kind {
+      ChatLifecycle::Created => {
+        for &user_id in &members {
+          self.add_user_to_chat(user_id, chat_id).await;
+        }
+      }
+      ChatLifecycle::Deleted => {
+        for &user_id in &members {
+          self.remove_user_from_chat(user_id, chat_id).await;
+        }
+        self.chat_members.remove(&chat_id);
+      }
+      ChatLifecycle::Updated | ChatLifecycle::OwnershipTransferred => {}
+    }
+
+    self.broadcast_to_users(members, Arc::new(NotifyEvent::ChatUpdated(event)))
+  }
+
+  /// Replace a user's presence interest, narrowing chats to ones the user belongs to.
+  /// Returns the interest in effect
+  pub async fn set_presence_interest(
+    &self,
+    user_id: UserId,
+    interest: PresenceInterest,
+  ) -> PresenceInterest {
+    let interest = match interest {
+      PresenceInterest::Chats { chat_ids } => {
+        let own_chats = self.user_chat_ids(user_id).await;
+        PresenceInterest::Chats {
+          chat_ids: chat_ids.intersection(&own_chats).copied().collect(),
+        }
+      }
+      PresenceInterest::Workspace => PresenceInterest::Workspace,
+    };
+    self.presence_interests.set(user_id, interest.clone());
+    interest
+  }
+
+  /// What presence interests are matched against for `user_id`. Take it before an offline
+  /// update clears the user's mappings
+  pub async fn presence_subject(&self, user_id: UserId) -> PresenceSubject {
+    let cached_workspace = self
+      .user_workspaces
+      .get(&user_id)
+      .map(|workspace| *workspace);
+    let workspace_id = match cached_workspace {
+      Some(workspace_id) => Some(workspace_id),
+      None => sqlx::query_scalar::<_, Option<i64>>("SELECT workspace_id FROM users WHERE id = $1")
+        .bind(user_id.0)
+        .fetch_optional(&self.db_pool)
+        .await
+        .unwrap_or_else(|e| {
+          warn!("Failed to load workspace of user {}: {}", user_id.0, e);
+          None
+        })
+        .flatten()
+        .map(WorkspaceId),
+    };
+
+    PresenceSubject {
+      user_id,
+      workspace_id,
+      chats: self.user_chat_ids(user_id).await,
+    }
+  }
+
+  /// Push a presence change to the connected users whose interest covers its subject.
+  /// Returns how many users it reached
+  pub fn broadcast_presence(
+    &self,
+    subject: &PresenceSubject,
+    notification: serde_json::Value,
+  ) -> usize {
+    let recipients: Vec<UserId> = self
+      .user_connections
+      .iter()
+      .map(|entry| *entry.key())
+      .filter(|recipient| {
+        let workspace_id = self
+          .user_workspaces
+          .get(recipient)
+          .map(|workspace| *workspace);
+        self
+          .presence_interests
+          .get(*recipient)
+          .matches(workspace_id, subject)
+      })
+      .collect();
+
+    self.broadcast_to_users(recipients, Arc::new(NotifyEvent::Generic(notification)))
+  }
+
+  /// Chats of a user, from the cache while they are connected
+  async fn user_chat_ids(&self, user_id: UserId) -> HashSet<ChatId> {
+    if let Some(chats) = self.user_chats.get(&user_id) {
+      return chats.clone();
+    }
+    self.get_user_chats(user_id).await.unwrap_or_else(|e| {
+      warn!("Failed to load chats of user {}: {}", user_id.0, e);
+      HashSet::new()
+    })
+  }
+
+  /// Record user presence in the shared store (best effort)
+  pub async fn set_presence(&self, user_id: UserId, status: PresenceStatus) {
+    let Some(presence) = &self.presence else {
+      return;
+    };
+    if let Err(e) = presence.set_online(user_id, status).await {
+      warn!("Failed to update presence for user {}: {}", user_id.0, e);
+    }
+  }
+
+  /// Refresh a connected user's presence, keeping an explicit away status
+  pub async fn refresh_presence(&self, user_id: UserId) {
+    let Some(presence) = &self.presence else {
+      return;
+    };
+    let status = match presence.get_status(user_id).await {
+      Ok(PresenceStatus::Away) => PresenceStatus::Away,
+      _ => PresenceStatus::Online,
+    };
+    self.set_presence(user_id, status).await;
+  }
+
   /// Get analytics publisher reference
   pub fn analytics_publisher(&self) -> &AnalyticsPublisher {
     &self.analytics
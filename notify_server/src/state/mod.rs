@@ -1,3 +1,7 @@
 pub mod app_state;
+pub mod presence;
+pub mod presence_interest;
 
 pub use app_state::AppState;
+pub use presence::PresenceTracker;
+pub use presence_interest::{PresenceInterest, PresenceInterests, PresenceSubject};
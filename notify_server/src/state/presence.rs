@@ -0,0 +1,165 @@
+use dashmap::DashMap;
+use std::{
+  sync::atomic::{AtomicU64, Ordering},
+  time::Duration,
+};
+
+use fechatter_core::UserId;
+
+/// One online period of a user, spanning every connection and quick reconnect
+#[derive(Debug, Clone, Copy)]
+struct PresenceSession {
+  id: u64,
+  /// Open SSE connections
+  connections: usize,
+  /// Bumped on every connect, so a grace timer started before a reconnect is ignored
+  generation: u64,
+}
+
+/**
+ * PresenceTracker
+ *
+ * Reconciles a user's SSE connections into a single online state. Several tabs count as one
+ * online user, and closing the last connection only takes the user offline once
+ * `grace_period` passes without a reconnect, so a dropped and re-established stream never
+ * shows up as an offline gap.
+ */
+pub struct PresenceTracker {
+  sessions: DashMap<UserId, PresenceSession>,
+  next_session: AtomicU64,
+  grace_period: Duration,
+}
+
+impl PresenceTracker {
+  pub fn new(grace_period: Duration) -> Self {
+    Self {
+      sessions: DashMap::new(),
+      next_session: AtomicU64::new(1),
+      grace_period,
+    }
+  }
+
+  pub fn grace_period(&self) -> Duration {
+    self.grace_period
+  }
+
+  /// Record a new connection. Returns the session id when the user just came online, or
+  /// `None` when they already were (another open connection, or a reconnect within the grace
+  /// period)
+  pub fn connect(&self, user_id: UserId) -> Option<u64> {
+    let mut came_online = None;
+    let mut session = self.sessions.entry(user_id).or_insert_with(|| {
+      let id = self.next_session.fetch_add(1, Ordering::Relaxed);
+      came_online = Some(id);
+      PresenceSession {
+        id,
+        connections: 0,
+        generation: 0,
+      }
+    });
+    session.connections += 1;
+    session.generation += 1;
+    came_online
+  }
+
+  /// Record a closed connection. Returns the generation to hand to `confirm_offline` once the
+  /// grace period has passed, or `None` while other connections remain open
+  pub fn disconnect(&self, user_id: UserId) -> Option<u64> {
+    let mut session = self.sessions.get_mut(&user_id)?;
+    session.connections = session.connections.saturating_sub(1);
+    (session.connections == 0).then_some(session.generation)
+  }
+
+  /// End the session if nothing reconnected since `disconnect` returned `generation`.
+  /// Returns true when the user is now offline
+  pub fn confirm_offline(&self, user_id: UserId, generation: u64) -> bool {
+    self
+      .sessions
+      .remove_if(&user_id, |_, session| {
+        session.connections == 0 && session.generation == generation
+      })
+      .is_some()
+  }
+
+  /// Close a connection and wait out the grace period; true if the user should go offline
+  pub async fn disconnect_and_settle(&self, user_id: UserId) -> bool {
+    let Some(generation) = self.disconnect(user_id) else {
+      return false;
+    };
+    tokio::time::sleep(self.grace_period).await;
+    self.confirm_offline(user_id, generation)
+  }
+
+  /// Online, including the grace period after the last connection closed
+  pub fn is_online(&self, user_id: UserId) -> bool {
+    self.sessions.contains_key(&user_id)
+  }
+
+  /// Whether `session` is still the user's current online period
+  pub fn is_current_session(&self, user_id: UserId, session: u64) -> bool {
+    self
+      .sessions
+      .get(&user_id)
+      .is_some_and(|current| current.id == session)
+  }
+
+  /// Open connections for the user
+  pub fn connection_count(&self, user_id: UserId) -> usize {
+    self
+      .sessions
+      .get(&user_id)
+      .map_or(0, |session| session.connections)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::Arc;
+
+  const USER: UserId = UserId(7);
+
+  #[tokio::test]
+  async fn quick_reconnect_within_grace_period_should_keep_user_online() {
+    let tracker = Arc::new(PresenceTracker::new(Duration::from_millis(200)));
+    let session = tracker.connect(USER).expect("first connection comes online");
+
+    // The stream drops and the client reconnects before the grace period ends
+    let settle = tokio::spawn({
+      let tracker = tracker.clone();
+      async move { tracker.disconnect_and_settle(USER).await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(tracker.is_online(USER));
+    assert_eq!(tracker.connect(USER), None);
+
+    assert!(!settle.await.unwrap(), "user must not be marked offline");
+    assert!(tracker.is_online(USER));
+    assert!(tracker.is_current_session(USER, session));
+  }
+
+  #[tokio::test]
+  async fn user_should_go_offline_after_grace_period_without_connections() {
+    let tracker = PresenceTracker::new(Duration::from_millis(20));
+    tracker.connect(USER);
+
+    assert!(tracker.disconnect_and_settle(USER).await);
+    assert!(!tracker.is_online(USER));
+
+    // The next connection starts a new online period
+    assert!(tracker.connect(USER).is_some());
+  }
+
+  #[test]
+  fn multiple_connections_should_count_as_one_online_user() {
+    let tracker = PresenceTracker::new(Duration::from_secs(5));
+    assert!(tracker.connect(USER).is_some());
+    assert_eq!(tracker.connect(USER), None);
+    assert_eq!(tracker.connection_count(USER), 2);
+
+    // Closing one tab leaves the user online with no grace timer
+    assert_eq!(tracker.disconnect(USER), None);
+    let generation = tracker.disconnect(USER).expect("last connection closed");
+    assert!(tracker.confirm_offline(USER, generation));
+  }
+}
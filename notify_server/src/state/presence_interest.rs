@@ -0,0 +1,155 @@
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use fechatter_core::{ChatId, UserId, WorkspaceId};
+
+/// Whose presence changes a client receives. Declared on connect and replaced through
+/// `PUT /presence/interest`; it applies to all of a user's connections, which share a channel
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scope", rename_all = "snake_case")]
+pub enum PresenceInterest {
+  /// Members of these chats; narrowed to chats the user belongs to when set
+  Chats { chat_ids: HashSet<ChatId> },
+  /// Everyone in the user's workspace
+  #[default]
+  Workspace,
+}
+
+impl PresenceInterest {
+  /// Interest from the `/events` query, `presence_chats=1,2,3`; the workspace when absent
+  pub fn from_query(presence_chats: Option<&str>) -> Self {
+    match presence_chats {
+      Some(ids) => Self::Chats {
+        chat_ids: ids
+          .split(',')
+          .filter_map(|id| id.trim().parse().ok())
+          .map(ChatId)
+          .collect(),
+      },
+      None => Self::Workspace,
+    }
+  }
+
+  /// Whether a recipient in `workspace_id` with this interest wants `subject`'s presence
+  pub fn matches(&self, workspace_id: Option<WorkspaceId>, subject: &PresenceSubject) -> bool {
+    match self {
+      Self::Chats { chat_ids } => !chat_ids.is_disjoint(&subject.chats),
+      Self::Workspace => workspace_id.is_some() && workspace_id == subject.workspace_id,
+    }
+  }
+}
+
+/// The user whose presence changed, with what interests are matched against
+#[derive(Debug, Clone)]
+pub struct PresenceSubject {
+  pub user_id: UserId,
+  pub workspace_id: Option<WorkspaceId>,
+  pub chats: HashSet<ChatId>,
+}
+
+/// Declared interest of each connected user
+#[derive(Default)]
+pub struct PresenceInterests {
+  interests: DashMap<UserId, PresenceInterest>,
+}
+
+impl PresenceInterests {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn set(&self, user_id: UserId, interest: PresenceInterest) {
+    self.interests.insert(user_id, interest);
+  }
+
+  /// The declared interest, or the workspace default
+  pub fn get(&self, user_id: UserId) -> PresenceInterest {
+    self
+      .interests
+      .get(&user_id)
+      .map(|interest| interest.clone())
+      .unwrap_or_default()
+  }
+
+  pub fn remove(&self, user_id: UserId) {
+    self.interests.remove(&user_id);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{events::types::NotifyEvent, state::AppState};
+  use serde_json::json;
+
+  fn subject(workspace_id: i64, chats: &[i64]) -> PresenceSubject {
+    PresenceSubject {
+      user_id: UserId(9),
+      workspace_id: Some(WorkspaceId(workspace_id)),
+      chats: chats.iter().copied().map(ChatId).collect(),
+    }
+  }
+
+  #[test]
+  fn chat_interest_should_match_members_of_those_chats_only() {
+    let interest = PresenceInterest::from_query(Some("10, 11,bad"));
+    assert_eq!(
+      interest,
+      PresenceInterest::Chats {
+        chat_ids: HashSet::from([ChatId(10), ChatId(11)])
+      }
+    );
+
+    let workspace = Some(WorkspaceId(1));
+    assert!(interest.matches(workspace, &subject(1, &[11, 30])));
+    assert!(!interest.matches(workspace, &subject(1, &[30])));
+  }
+
+  #[test]
+  fn workspace_interest_should_be_the_default() {
+    let interest = PresenceInterest::from_query(None);
+    assert_eq!(interest, PresenceInterest::Workspace);
+
+    assert!(interest.matches(Some(WorkspaceId(1)), &subject(1, &[])));
+    assert!(!interest.matches(Some(WorkspaceId(2)), &subject(1, &[])));
+    assert!(!interest.matches(None, &subject(1, &[])));
+  }
+
+  #[tokio::test]
+  async fn chat_subscriber_should_not_receive_presence_of_unrelated_users() {
+    let state = AppState::new(crate::config::AppConfig::load().expect("config")).unwrap();
+    let workspace = WorkspaceId(1);
+    let (watcher, member, stranger) = (UserId(1), UserId(2), UserId(3));
+    let mut events = state.subscribe_user(watcher, workspace, 16);
+    let _others = [member, stranger].map(|user_id| state.subscribe_user(user_id, workspace, 16));
+    for (user_id, chat_id) in [(watcher, 10), (member, 10), (stranger, 20)] {
+      state.add_user_to_chat(user_id, ChatId(chat_id)).await;
+    }
+
+    let interest = PresenceInterest::Chats {
+      chat_ids: HashSet::from([ChatId(10), ChatId(20)]),
+    };
+    // The watcher is not in chat 20, so its members stay out of reach
+    assert_eq!(
+      state.set_presence_interest(watcher, interest).await,
+      PresenceInterest::Chats {
+        chat_ids: HashSet::from([ChatId(10)])
+      }
+    );
+
+    for user_id in [stranger, member] {
+      let subject = state.presence_subject(user_id).await;
+      state.broadcast_presence(
+        &subject,
+        json!({ "type": "user_presence", "user_id": user_id.0, "status": "online" }),
+      );
+    }
+
+    match events.try_recv().unwrap().as_ref() {
+      NotifyEvent::Generic(presence) => assert_eq!(presence["user_id"], member.0),
+      other => panic!("unexpected event {:?}", other),
+    }
+    assert!(events.try_recv().is_err());
+  }
+}
@@ -21,6 +21,7 @@ async fn test_event_compatibility() {
     files: Some(vec!["test.txt".to_string()]),
     created_at: Utc::now(),
     idempotency_key: Some(Uuid::new_v4()),
+    client_message_id: None,
   };
 
   let event = MessageEvent {
@@ -108,6 +109,7 @@ async fn test_full_nats_message_flow() {
     files: None,
     created_at: Utc::now(),
     idempotency_key: Some(Uuid::new_v4()),
+    client_message_id: None,
   };
 
   let event = MessageEvent {
@@ -195,6 +197,7 @@ fn test_event_versioning() {
       files: None,
       created_at: Utc::now(),
       idempotency_key: None,
+      client_message_id: None,
     },
     members: vec![UserId(1)],
     occurred_at: Utc::now(),